@@ -34,6 +34,10 @@ impl ModelArchetype for ClaudeArchetype {
         "claude-sonnet-4-20250514"
     }
 
+    fn context_window(&self) -> i32 {
+        200_000
+    }
+
     fn enhance_system_prompt(&self, base_prompt: &str, _tools: &[ToolDefinition]) -> String {
         // Don't list tools in the system prompt - they're passed via the API's `tools` parameter
         base_prompt.to_string()
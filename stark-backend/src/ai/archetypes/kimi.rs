@@ -37,6 +37,10 @@ impl ModelArchetype for KimiArchetype {
         "kimi-k2-turbo-preview" // Kimi K2 turbo preview - supports native tool calling per docs
     }
 
+    fn context_window(&self) -> i32 {
+        128_000
+    }
+
     fn enhance_system_prompt(&self, base_prompt: &str, _tools: &[ToolDefinition]) -> String {
         // Don't list tools in the system prompt - they're passed via the API's `tools` parameter.
         // Listing them as text confuses some models into outputting tool calls as formatted text
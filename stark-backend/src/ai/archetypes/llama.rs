@@ -224,6 +224,10 @@ impl ModelArchetype for LlamaArchetype {
         "llama3.3" // Default Llama model
     }
 
+    fn context_window(&self) -> i32 {
+        128_000 // Llama 3.x; smaller self-hosted windows need a per-session override
+    }
+
     fn enhance_system_prompt(&self, base_prompt: &str, tools: &[ToolDefinition]) -> String {
         let mut prompt = base_prompt.to_string();
 
@@ -83,6 +83,10 @@ pub trait ModelArchetype: Send + Sync {
     /// Used when model is not explicitly specified (x402 endpoints use "default")
     fn default_model(&self) -> &'static str;
 
+    /// Context window of the archetype's models, in tokens. Sessions use this
+    /// as their compaction limit unless configured otherwise.
+    fn context_window(&self) -> i32;
+
     /// Enhance system prompt with tool-calling instructions (for text-based archetypes)
     fn enhance_system_prompt(&self, base_prompt: &str, tools: &[ToolDefinition]) -> String;
 
@@ -4,6 +4,7 @@ pub mod llama;
 pub mod multi_agent;
 pub mod openai;
 pub mod streaming;
+pub mod structured_output;
 pub mod types;
 
 pub use claude::ClaudeClient;
@@ -104,6 +105,15 @@ impl AiClient {
         Ok(AiClient::OpenAI(client))
     }
 
+    /// Provider name used in metrics labels
+    pub fn provider_name(&self) -> &'static str {
+        match self {
+            AiClient::Claude(_) => "claude",
+            AiClient::OpenAI(_) => "openai",
+            AiClient::Llama(_) => "llama",
+        }
+    }
+
     /// Get the archetype ID from agent settings
     pub fn infer_archetype(settings: &AgentSettings) -> ArchetypeId {
         ArchetypeId::from_str(&settings.model_archetype).unwrap_or(ArchetypeId::Kimi)
@@ -111,11 +121,27 @@ impl AiClient {
 
     /// Generate text using the configured provider
     pub async fn generate_text(&self, messages: Vec<Message>) -> Result<String, String> {
-        match self {
-            AiClient::Claude(client) => client.generate_text(messages).await,
-            AiClient::OpenAI(client) => client.generate_text(messages).await,
-            AiClient::Llama(client) => client.generate_text(messages).await,
-        }
+        crate::metrics::time_ai_request(self.provider_name(), async {
+            match self {
+                AiClient::Claude(client) => client.generate_text(messages).await,
+                AiClient::OpenAI(client) => client.generate_text(messages).await,
+                AiClient::Llama(client) => client.generate_text(messages).await,
+            }
+        })
+        .await
+    }
+
+    /// Generate a JSON reply. OpenAI-compatible providers use their JSON mode;
+    /// others rely on the prompt asking for JSON, so callers should validate.
+    pub async fn generate_json(&self, messages: Vec<Message>) -> Result<String, String> {
+        crate::metrics::time_ai_request(self.provider_name(), async {
+            match self {
+                AiClient::OpenAI(client) => client.generate_json(messages).await,
+                AiClient::Claude(client) => client.generate_text(messages).await,
+                AiClient::Llama(client) => client.generate_text(messages).await,
+            }
+        })
+        .await
     }
 
     /// Generate text and emit x402 payment event if applicable
@@ -126,26 +152,29 @@ impl AiClient {
         broadcaster: &Arc<EventBroadcaster>,
         channel_id: i64,
     ) -> Result<(String, Option<X402PaymentInfo>), String> {
-        match self {
-            AiClient::OpenAI(client) => {
-                let (content, payment) = client.generate_text_with_payment_info(messages).await?;
-                // Emit x402 payment event if payment was made
-                if let Some(ref payment_info) = payment {
-                    broadcaster.broadcast(GatewayEvent::x402_payment(
-                        channel_id,
-                        &payment_info.amount,
-                        &payment_info.amount_formatted,
-                        &payment_info.asset,
-                        &payment_info.pay_to,
-                        payment_info.resource.as_deref(),
-                    ));
+        crate::metrics::time_ai_request(self.provider_name(), async {
+            match self {
+                AiClient::OpenAI(client) => {
+                    let (content, payment) = client.generate_text_with_payment_info(messages).await?;
+                    // Emit x402 payment event if payment was made
+                    if let Some(ref payment_info) = payment {
+                        broadcaster.broadcast(GatewayEvent::x402_payment(
+                            channel_id,
+                            &payment_info.amount,
+                            &payment_info.amount_formatted,
+                            &payment_info.asset,
+                            &payment_info.pay_to,
+                            payment_info.resource.as_deref(),
+                        ));
+                    }
+                    Ok((content, payment))
                 }
-                Ok((content, payment))
+                // Other providers don't support x402
+                AiClient::Claude(client) => Ok((client.generate_text(messages).await?, None)),
+                AiClient::Llama(client) => Ok((client.generate_text(messages).await?, None)),
             }
-            // Other providers don't support x402
-            AiClient::Claude(client) => Ok((client.generate_text(messages).await?, None)),
-            AiClient::Llama(client) => Ok((client.generate_text(messages).await?, None)),
-        }
+        })
+        .await
     }
 
     /// Generate response with tool support (Claude, OpenAI, and Llama 3.1+)
@@ -155,30 +184,33 @@ impl AiClient {
         tool_history: Vec<ToolHistoryEntry>,
         tools: Vec<ToolDefinition>,
     ) -> Result<AiResponse, AiError> {
-        match self {
-            AiClient::Claude(client) => {
-                // Convert tool history to Claude format
-                let tool_messages = Self::tool_history_to_claude(&tool_history);
-                client
-                    .generate_with_tools(messages, tool_messages, tools)
-                    .await
-            }
-            AiClient::OpenAI(client) => {
-                // Convert tool history to OpenAI format
-                let tool_messages = Self::tool_history_to_openai(&tool_history);
-                client
-                    .generate_with_tools(messages, tool_messages, tools)
-                    .await
-            }
-            AiClient::Llama(client) => {
-                // Convert tool history to Llama/Ollama format
-                let tool_messages = Self::tool_history_to_llama(&tool_history);
-                client
-                    .generate_with_tools(messages, tool_messages, tools)
-                    .await
-                    .map_err(AiError::from)
+        crate::metrics::time_ai_request(self.provider_name(), async {
+            match self {
+                AiClient::Claude(client) => {
+                    // Convert tool history to Claude format
+                    let tool_messages = Self::tool_history_to_claude(&tool_history);
+                    client
+                        .generate_with_tools(messages, tool_messages, tools)
+                        .await
+                }
+                AiClient::OpenAI(client) => {
+                    // Convert tool history to OpenAI format
+                    let tool_messages = Self::tool_history_to_openai(&tool_history);
+                    client
+                        .generate_with_tools(messages, tool_messages, tools)
+                        .await
+                }
+                AiClient::Llama(client) => {
+                    // Convert tool history to Llama/Ollama format
+                    let tool_messages = Self::tool_history_to_llama(&tool_history);
+                    client
+                        .generate_with_tools(messages, tool_messages, tools)
+                        .await
+                        .map_err(AiError::from)
+                }
             }
-        }
+        })
+        .await
     }
 
     /// Check if the current provider supports tools
@@ -0,0 +1,74 @@
+//! Findings carried between runs of a recurring task
+//!
+//! A cron job that opts in (`seed_findings`) tags its runs with a task
+//! signature: the normalized set of significant words in its prompt. Each
+//! run's findings (the summaries of completed tasks) are stored on its
+//! `agent_contexts` row, and the next run with a similar signature starts
+//! with them as notes, so it doesn't redo research that's still valid.
+
+use std::collections::BTreeSet;
+
+/// Prefix for notes seeded from earlier runs. Seeded notes aren't stored as
+/// findings again, so they don't snowball across runs.
+pub const PRIOR_FINDING_PREFIX: &str = "[Previous run] ";
+
+/// Minimum similarity for two signatures to count as the same task
+pub const MIN_SIMILARITY: f64 = 0.6;
+
+/// Maximum findings seeded into a new run
+pub const MAX_SEEDED_FINDINGS: usize = 10;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "into", "is", "it",
+    "its", "me", "my", "of", "on", "or", "our", "please", "that", "the", "their", "then", "this",
+    "to", "us", "was", "we", "with", "you", "your",
+];
+
+/// Signature of a task prompt: its significant words, lowercased, deduplicated
+/// and sorted. Numbers and dates are dropped so "report for week 12" and
+/// "report for week 13" share a signature.
+pub fn task_signature(text: &str) -> String {
+    let words: BTreeSet<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|w| w.to_lowercase())
+        .filter(|w| w.len() > 1)
+        .filter(|w| !w.chars().all(|c| c.is_ascii_digit()))
+        .filter(|w| !STOPWORDS.contains(&w.as_str()))
+        .collect();
+    words.into_iter().collect::<Vec<_>>().join(" ")
+}
+
+/// Jaccard similarity of two signatures' word sets (0.0 - 1.0)
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a: BTreeSet<&str> = a.split_whitespace().collect();
+    let b: BTreeSet<&str> = b.split_whitespace().collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / a.union(&b).count() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_ignores_order_case_and_numbers() {
+        let a = task_signature("Write the weekly ETH price report for week 12");
+        let b = task_signature("write weekly report: ETH price, week 13");
+        assert_eq!(a, "eth price report week weekly write");
+        assert_eq!(a, b);
+        assert_eq!(similarity(&a, &b), 1.0);
+    }
+
+    #[test]
+    fn test_similarity_separates_different_tasks() {
+        let report = task_signature("Write the weekly ETH price report");
+        let similar = task_signature("Write the weekly ETH and BTC price report");
+        let other = task_signature("Tip @jimmy 100 STARKBOT on discord");
+
+        assert!(similarity(&report, &similar) >= MIN_SIMILARITY);
+        assert!(similarity(&report, &other) < MIN_SIMILARITY);
+        assert_eq!(similarity(&report, ""), 0.0);
+    }
+}
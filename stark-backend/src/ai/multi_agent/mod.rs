@@ -15,6 +15,7 @@
 //! The agent executes tools as needed and uses `add_note` to track
 //! important information during multi-step tasks.
 
+pub mod findings;
 pub mod orchestrator;
 pub mod subagent_manager;
 pub mod tools;
@@ -1,16 +1,27 @@
-//! Simplified orchestrator - manages agent context without mode transitions
-
+//! Simplified orchestrator - manages agent context and the planner/assistant modes
+//!
+//! A request starts in TaskPlanner mode, which moves to Assistant once
+//! `define_tasks` is called, or is forced over after
+//! `STARK_PLANNER_MAX_ITERATIONS` loop iterations without a plan. In Assistant
+//! mode the agent can call `replan` to discard the plan and return to the
+//! planner (at most `STARK_MAX_REPLANS` times per request).
+//!
+//! On channels with the `plan_approval` setting, a defined plan is shown to
+//! the user and the orchestrator stays in the planner until they reply
+//! "approve"; a rejection sends it back to planning with their feedback.
+
+use super::findings::PRIOR_FINDING_PREFIX;
 use super::tools;
-use super::types::{AgentContext, AgentMode, TaskQueue};
+use super::types::{AgentContext, AgentMode, ModeTransition, TaskQueue};
 use crate::tools::ToolDefinition;
 use serde_json::Value;
 
-/// Maximum iterations before forcing completion
-const MAX_ITERATIONS: u32 = 100;
-
 /// The orchestrator manages agent context and tool processing
 pub struct Orchestrator {
     context: AgentContext,
+    /// Transition requested by a tool (e.g. `replan`), reported on the next
+    /// `check_forced_transition` so the dispatcher announces it
+    pending_transition: Option<ModeTransition>,
 }
 
 impl Orchestrator {
@@ -23,12 +34,16 @@ impl Orchestrator {
                 planner_completed: false,
                 ..Default::default()
             },
+            pending_transition: None,
         }
     }
 
     /// Create from existing context (for resuming)
     pub fn from_context(context: AgentContext) -> Self {
-        Self { context }
+        Self {
+            context,
+            pending_transition: None,
+        }
     }
 
     /// Get the current mode (always Assistant now)
@@ -95,7 +110,7 @@ impl Orchestrator {
         }
 
         // If no actual tools were called yet, the agent is probably hallucinating
-        if self.context.actual_tool_calls == 0 && self.context.mode_iterations > 0 {
+        if self.context.actual_tool_calls == 0 && self.context.total_iterations > 0 {
             self.context.no_tool_warnings += 1;
             log::warn!(
                 "[ORCHESTRATOR] Agent tried to respond without calling any tools (warning {}/5)",
@@ -140,9 +155,31 @@ impl Orchestrator {
 
     /// Get the system prompt for task planner mode with available skills
     pub fn get_planner_prompt_with_skills(&self, skills_text: &str) -> String {
-        include_str!("prompts/task_planner.md")
+        let mut prompt = include_str!("prompts/task_planner.md")
             .replace("{original_request}", &self.context.original_request)
-            .replace("{available_skills}", skills_text)
+            .replace("{available_skills}", skills_text);
+
+        let prior: Vec<&String> = self
+            .context
+            .exploration_notes
+            .iter()
+            .filter(|n| n.starts_with(PRIOR_FINDING_PREFIX))
+            .collect();
+        if !prior.is_empty() {
+            prompt.push_str("\n\n## Findings From Previous Runs\n\n");
+            prompt.push_str("This task has run before. Reuse these findings where they still apply instead of researching them again:\n");
+            for note in prior {
+                prompt.push_str(&format!("- {}\n", &note[PRIOR_FINDING_PREFIX.len()..]));
+            }
+        }
+
+        if let Some(ref note) = self.context.replan_note {
+            prompt.push_str("\n\n## Previous Plan Abandoned\n\n");
+            prompt.push_str(note);
+            prompt.push_str("\n\nDefine a new task list that accounts for this. Don't repeat completed tasks.\n");
+        }
+
+        prompt
     }
 
     /// Get the system prompt
@@ -240,6 +277,34 @@ impl Orchestrator {
         summary
     }
 
+    /// Tag this run as a recurring task and seed its notes with findings
+    /// from earlier runs of the same task
+    pub fn seed_prior_findings(&mut self, task_signature: String, prior: Vec<String>) {
+        log::info!(
+            "[ORCHESTRATOR] Seeding {} finding(s) from previous runs of '{}'",
+            prior.len(),
+            task_signature
+        );
+        self.context.task_signature = Some(task_signature);
+        for finding in prior {
+            self.context
+                .exploration_notes
+                .push(format!("{}{}", PRIOR_FINDING_PREFIX, finding));
+        }
+    }
+
+    /// Record a finding for later runs of this task. A no-op unless the run
+    /// has a task signature.
+    pub fn record_finding(&mut self, finding: &str) {
+        let finding = finding.trim();
+        if self.context.task_signature.is_none() || finding.is_empty() {
+            return;
+        }
+        if !self.context.findings.iter().any(|f| f == finding) {
+            self.context.findings.push(finding.to_string());
+        }
+    }
+
     /// Clear the waiting_for_user_context after it's been consumed
     pub fn clear_waiting_for_user_context(&mut self) {
         self.context.waiting_for_user_context = None;
@@ -252,33 +317,67 @@ impl Orchestrator {
 
     /// Process a tool call result
     pub fn process_tool_result(&mut self, tool_name: &str, params: &Value) -> ProcessResult {
-        self.context.mode_iterations += 1;
         self.context.total_iterations += 1;
 
         log::debug!(
             "[ORCHESTRATOR] Processing tool '{}' (iteration {})",
-            tool_name, self.context.mode_iterations
+            tool_name, self.context.total_iterations
         );
 
         match tool_name {
             "define_tasks" => self.handle_define_tasks(params),
+            "replan" => self.handle_replan(params),
             _ => ProcessResult::Continue,
         }
     }
 
-    /// Check if we should force completion due to hitting max iterations
-    pub fn check_forced_transition(&mut self) -> Option<super::types::ModeTransition> {
-        if self.context.mode_iterations >= MAX_ITERATIONS {
+    /// Switch modes, resetting the per-mode iteration count
+    fn transition(&mut self, to: AgentMode, reason: String) -> ModeTransition {
+        let transition = ModeTransition {
+            from: self.context.mode,
+            to,
+            reason,
+        };
+        log::info!(
+            "[ORCHESTRATOR] Mode transition: {} → {} ({})",
+            transition.from, transition.to, transition.reason
+        );
+        self.context.mode = to;
+        self.context.mode_iterations = 0;
+        transition
+    }
+
+    /// Called once per loop iteration. Counts the iteration against the
+    /// current mode and returns a transition the dispatcher should announce:
+    /// one requested by a tool, or a forced one when the planner has used up
+    /// its iterations without defining tasks.
+    pub fn check_forced_transition(&mut self) -> Option<ModeTransition> {
+        if let Some(transition) = self.pending_transition.take() {
+            return Some(transition);
+        }
+
+        self.context.mode_iterations += 1;
+
+        let max_planner_iterations = crate::config::planner_max_iterations();
+        if self.context.mode == AgentMode::TaskPlanner
+            && !self.context.planner_completed
+            && self.context.mode_iterations > max_planner_iterations
+        {
             log::warn!(
-                "[ORCHESTRATOR] Forced completion after {} iterations",
-                MAX_ITERATIONS
+                "[ORCHESTRATOR] Planner defined no tasks in {} iterations, forcing assistant mode",
+                max_planner_iterations
             );
-            // Return None - we don't do mode transitions anymore
-            // The dispatcher should handle this case
-            None
-        } else {
-            None
+            self.context.planner_completed = true;
+            return Some(self.transition(
+                AgentMode::Assistant,
+                format!(
+                    "No task list after {} planning iterations - working on the request directly",
+                    max_planner_iterations
+                ),
+            ));
         }
+
+        None
     }
 
     // =========================================================================
@@ -304,10 +403,6 @@ impl Orchestrator {
             // Create the task queue
             self.context.task_queue = TaskQueue::from_descriptions(task_descriptions.clone());
 
-            // Mark planner as completed and switch to assistant mode
-            self.context.planner_completed = true;
-            self.context.mode = AgentMode::Assistant;
-
             // Format the task list for the response
             let task_list = task_descriptions
                 .iter()
@@ -316,6 +411,21 @@ impl Orchestrator {
                 .collect::<Vec<_>>()
                 .join("\n");
 
+            // Channels that gate plans stay in the planner until the user approves
+            if self.context.plan_approval_required {
+                self.context.plan_awaiting_approval = true;
+                return ProcessResult::AwaitingApproval(format!(
+                    "Here's my plan:\n{}\n\nReply **approve** to run it, or **reject** followed by what to change.",
+                    task_list
+                ));
+            }
+
+            // Mark planner as completed and switch to assistant mode
+            self.context.planner_completed = true;
+            self.context.mode = AgentMode::Assistant;
+            self.context.mode_iterations = 0;
+            self.context.replan_note = None;
+
             ProcessResult::ToolResult(format!(
                 "Tasks defined successfully:\n{}\n\nNow executing task 1...",
                 task_list
@@ -325,9 +435,108 @@ impl Orchestrator {
         }
     }
 
+    /// Discard the task list and return to the planner, e.g. when executing
+    /// the plan showed it was built on a wrong assumption
+    fn handle_replan(&mut self, params: &Value) -> ProcessResult {
+        let reason = match params.get("reason").and_then(|v| v.as_str()) {
+            Some(r) if !r.trim().is_empty() => r.trim().to_string(),
+            _ => return ProcessResult::Error("Missing 'reason' parameter".to_string()),
+        };
+
+        if self.context.mode != AgentMode::Assistant || !self.context.planner_completed {
+            return ProcessResult::Error("replan is only available while executing a plan".to_string());
+        }
+
+        let max_replans = crate::config::max_replans();
+        if self.context.replans >= max_replans {
+            return ProcessResult::Error(format!(
+                "Replan limit reached ({}). Continue with the current plan, or explain the problem to the user.",
+                max_replans
+            ));
+        }
+
+        let completed: Vec<String> = self
+            .context
+            .task_queue
+            .tasks
+            .iter()
+            .filter(|t| t.status == super::types::TaskStatus::Completed)
+            .map(|t| format!("- {}", t.description))
+            .collect();
+        let mut note = format!("Reason: {}", reason);
+        if !completed.is_empty() {
+            note.push_str(&format!("\n\nAlready completed:\n{}", completed.join("\n")));
+        }
+
+        self.context.replans += 1;
+        self.context.replan_note = Some(note);
+        self.context.exploration_notes.push(format!("Replanned: {}", reason));
+        self.context.task_queue = TaskQueue::default();
+        self.context.planner_completed = false;
+        let transition = self.transition(AgentMode::TaskPlanner, format!("Replanning: {}", reason));
+        self.pending_transition = Some(transition);
+
+        ProcessResult::ToolResult(format!(
+            "Plan discarded ({} of {} replans used). Returning to planning mode - define a new task list with `define_tasks`.",
+            self.context.replans, max_replans
+        ))
+    }
+
+    /// Handle the user's reply to a plan awaiting approval. "approve" starts
+    /// executing it; anything else rejects it, and the reply (minus a leading
+    /// "reject") goes back to the planner as feedback. Returns whether the
+    /// plan was approved; the mode change is reported by the next
+    /// `check_forced_transition`.
+    pub fn resolve_plan_approval(&mut self, reply: &str) -> bool {
+        self.context.plan_awaiting_approval = false;
+
+        let reply = reply.trim();
+        let lower = reply.to_lowercase();
+        let approved = matches!(
+            lower.trim_end_matches(|c: char| c.is_ascii_punctuation()),
+            "approve" | "approved" | "/approve"
+        );
+
+        let transition = if approved {
+            self.context.planner_completed = true;
+            self.context.replan_note = None;
+            self.transition(AgentMode::Assistant, "Plan approved by the user".to_string())
+        } else {
+            let feedback = ["/reject", "rejected", "reject"]
+                .iter()
+                .find_map(|prefix| lower.strip_prefix(prefix).map(|_| &reply[prefix.len()..]))
+                .unwrap_or(reply)
+                .trim_start_matches(|c: char| c == ':' || c == ',' || c == '-' || c.is_whitespace());
+            let rejected_plan = self
+                .context
+                .task_queue
+                .tasks
+                .iter()
+                .map(|t| format!("{}. {}", t.id, t.description))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let mut note = String::from("The user rejected the previous plan.");
+            if !feedback.is_empty() {
+                note.push_str(&format!("\n\nFeedback: {}", feedback));
+            }
+            if !rejected_plan.is_empty() {
+                note.push_str(&format!("\n\nRejected plan:\n{}", rejected_plan));
+            }
+
+            self.context.replan_note = Some(note);
+            self.context.task_queue = TaskQueue::default();
+            self.context.planner_completed = false;
+            self.transition(AgentMode::TaskPlanner, "Plan rejected by the user".to_string())
+        };
+        self.pending_transition = Some(transition);
+        approved
+    }
+
     /// Transition to assistant mode after planner completes
     pub fn transition_to_assistant(&mut self) {
         self.context.mode = AgentMode::Assistant;
+        self.context.mode_iterations = 0;
         self.context.planner_completed = true;
     }
 
@@ -382,6 +591,138 @@ pub enum ProcessResult {
     ToolResult(String),
     /// Task is complete with summary
     Complete(String),
+    /// A plan was defined and needs the user's approval; contains the plan
+    /// to show them
+    AwaitingApproval(String),
     /// Error occurred
     Error(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn executing_orchestrator() -> Orchestrator {
+        let mut orchestrator = Orchestrator::new("swap 10 USDC for ETH".to_string());
+        orchestrator.process_tool_result(
+            "define_tasks",
+            &json!({"tasks": ["Check USDC balance", "Swap USDC for ETH"]}),
+        );
+        orchestrator.pop_next_task();
+        orchestrator.complete_current_task();
+        orchestrator
+    }
+
+    #[test]
+    fn test_planner_is_forced_to_assistant_after_max_iterations() {
+        let mut orchestrator = Orchestrator::new("hello".to_string());
+        let max = crate::config::planner_max_iterations();
+        for _ in 0..max {
+            assert!(orchestrator.check_forced_transition().is_none());
+        }
+
+        let transition = orchestrator.check_forced_transition().unwrap();
+        assert_eq!(transition.from, AgentMode::TaskPlanner);
+        assert_eq!(transition.to, AgentMode::Assistant);
+        assert!(orchestrator.context().planner_completed);
+        assert_eq!(orchestrator.context().mode_iterations, 0);
+    }
+
+    #[test]
+    fn test_replan_returns_to_planner() {
+        let mut orchestrator = executing_orchestrator();
+
+        let result = orchestrator.process_tool_result("replan", &json!({"reason": "USDC is on Base"}));
+        assert!(matches!(result, ProcessResult::ToolResult(_)));
+        assert_eq!(orchestrator.current_mode(), AgentMode::TaskPlanner);
+        assert!(orchestrator.task_queue_is_empty());
+
+        // The transition is announced on the next check
+        let transition = orchestrator.check_forced_transition().unwrap();
+        assert_eq!(transition.to, AgentMode::TaskPlanner);
+        assert!(transition.reason.contains("USDC is on Base"));
+
+        let prompt = orchestrator.get_planner_prompt();
+        assert!(prompt.contains("Previous Plan Abandoned"));
+        assert!(prompt.contains("- Check USDC balance"));
+    }
+
+    #[test]
+    fn test_prior_findings_seed_planner_and_are_not_recorded_again() {
+        let mut chat = Orchestrator::new("weekly report".to_string());
+        chat.record_finding("ETH closed at $3,100");
+        assert!(chat.context().findings.is_empty());
+
+        let mut orchestrator = Orchestrator::new("weekly report".to_string());
+        orchestrator.seed_prior_findings(
+            "report weekly".to_string(),
+            vec!["ETH closed at $3,100".to_string()],
+        );
+        assert!(orchestrator.get_planner_prompt().contains("- ETH closed at $3,100"));
+        assert!(orchestrator.context().findings.is_empty());
+
+        orchestrator.record_finding("ETH closed at $3,250");
+        orchestrator.record_finding("ETH closed at $3,250");
+        assert_eq!(orchestrator.context().findings, vec!["ETH closed at $3,250"]);
+    }
+
+    fn gated_orchestrator() -> Orchestrator {
+        let mut orchestrator = Orchestrator::new("swap 10 USDC for ETH".to_string());
+        orchestrator.context_mut().plan_approval_required = true;
+        let result = orchestrator.process_tool_result(
+            "define_tasks",
+            &json!({"tasks": ["Check USDC balance", "Swap USDC for ETH"]}),
+        );
+        match result {
+            ProcessResult::AwaitingApproval(plan) => assert!(plan.contains("2. Swap USDC for ETH")),
+            other => panic!("expected approval request, got {:?}", other),
+        }
+        orchestrator
+    }
+
+    #[test]
+    fn test_gated_plan_waits_for_approval() {
+        let mut orchestrator = gated_orchestrator();
+        assert!(orchestrator.context().plan_awaiting_approval);
+        assert!(!orchestrator.context().planner_completed);
+        assert_eq!(orchestrator.current_mode(), AgentMode::TaskPlanner);
+
+        assert!(orchestrator.resolve_plan_approval(" Approve! "));
+        assert!(!orchestrator.context().plan_awaiting_approval);
+        assert!(orchestrator.context().planner_completed);
+        assert_eq!(orchestrator.task_queue().total(), 2);
+
+        let transition = orchestrator.check_forced_transition().unwrap();
+        assert_eq!(transition.to, AgentMode::Assistant);
+    }
+
+    #[test]
+    fn test_rejected_plan_returns_to_planner_with_feedback() {
+        let mut orchestrator = gated_orchestrator();
+
+        assert!(!orchestrator.resolve_plan_approval("Reject: swap on Base, not mainnet"));
+        assert!(!orchestrator.context().planner_completed);
+        assert!(orchestrator.task_queue_is_empty());
+        assert_eq!(orchestrator.check_forced_transition().unwrap().to, AgentMode::TaskPlanner);
+
+        let prompt = orchestrator.get_planner_prompt();
+        assert!(prompt.contains("Feedback: swap on Base, not mainnet"));
+        assert!(prompt.contains("1. Check USDC balance"));
+    }
+
+    #[test]
+    fn test_replan_limit_and_mode_checks() {
+        let mut orchestrator = Orchestrator::new("hello".to_string());
+        let result = orchestrator.process_tool_result("replan", &json!({"reason": "wrong"}));
+        assert!(matches!(result, ProcessResult::Error(_)));
+
+        let mut orchestrator = executing_orchestrator();
+        orchestrator.context_mut().replans = crate::config::max_replans();
+        let result = orchestrator.process_tool_result("replan", &json!({"reason": "wrong"}));
+        match result {
+            ProcessResult::Error(e) => assert!(e.contains("Replan limit reached")),
+            other => panic!("expected error, got {:?}", other),
+        }
+    }
+}
@@ -12,7 +12,7 @@ use crate::db::Database;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::models::{AgentSettings, SessionScope};
-use crate::tools::{ToolContext, ToolDefinition, ToolRegistry};
+use crate::tools::{SecretRedactor, ToolContext, ToolDefinition, ToolRegistry};
 use dashmap::DashMap;
 use serde_json::json;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -329,7 +329,8 @@ impl SubAgentManager {
             .with_channel(context.parent_channel_id, "subagent".to_string())
             .with_session(session.id)
             .with_workspace(workspace_dir)
-            .with_broadcaster(broadcaster.clone());
+            .with_broadcaster(broadcaster.clone())
+            .with_redactor(SecretRedactor::load(&db));
 
         // Get tool configuration
         let tool_config = db
@@ -709,6 +710,76 @@ impl SubAgentManager {
         // handles the actual concurrency limiting.
         self.active_agents.len()
     }
+
+    /// Longest timeout a sub-agent may be given
+    pub fn max_timeout_secs(&self) -> u64 {
+        self.config.max_timeout_secs
+    }
+
+    /// Count pending or running sub-agents spawned by a session
+    pub fn active_count_for_session(&self, parent_session_id: i64) -> Result<usize, String> {
+        let conn = self.db.conn();
+        conn.query_row(
+            "SELECT COUNT(*) FROM sub_agents
+             WHERE parent_session_id = ?1 AND status IN ('pending', 'running')",
+            [parent_session_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count as usize)
+        .map_err(|e| format!("Failed to count sub-agents: {}", e))
+    }
+
+    /// Nesting depth of a session: 0 for a top-level session, 1 for a
+    /// sub-agent's session, 2 for a sub-agent of a sub-agent, and so on.
+    /// Follows the parent chain through `sub_agents`, stopping once it
+    /// passes `limit` so a malformed chain can't loop forever.
+    pub fn session_depth(&self, session_id: i64, limit: usize) -> Result<usize, String> {
+        let conn = self.db.conn();
+        let mut depth = 0;
+        let mut current = session_id;
+
+        while depth <= limit {
+            let parent = conn.query_row(
+                "SELECT parent_session_id FROM sub_agents WHERE session_id = ?1 LIMIT 1",
+                [current],
+                |row| row.get::<_, i64>(0),
+            );
+            match parent {
+                Ok(parent_session_id) => {
+                    depth += 1;
+                    current = parent_session_id;
+                }
+                Err(rusqlite::Error::QueryReturnedNoRows) => break,
+                Err(e) => return Err(format!("Failed to look up parent session: {}", e)),
+            }
+        }
+
+        Ok(depth)
+    }
+
+    /// Check that a session may spawn another sub-agent under the
+    /// configured depth and per-session concurrency limits
+    pub fn check_spawn_limits(&self, parent_session_id: i64) -> Result<(), String> {
+        let depth = self.session_depth(parent_session_id, self.config.max_depth)?;
+        if depth >= self.config.max_depth {
+            return Err(format!(
+                "Sub-agent depth limit reached: this session is at depth {} and the maximum is {}. \
+                 Do the work directly instead of delegating it.",
+                depth, self.config.max_depth
+            ));
+        }
+
+        let active = self.active_count_for_session(parent_session_id)?;
+        if active >= self.config.max_concurrent_per_session {
+            return Err(format!(
+                "Sub-agent concurrency limit reached: this session already has {} sub-agent(s) \
+                 pending or running (maximum {}). Wait for one to finish before spawning another.",
+                active, self.config.max_concurrent_per_session
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 // Add new gateway events for sub-agents
@@ -755,3 +826,83 @@ impl GatewayEvent {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(
+        dir: &tempfile::TempDir,
+        max_concurrent_per_session: usize,
+        max_depth: usize,
+    ) -> SubAgentManager {
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let config = SubAgentConfig {
+            max_concurrent_per_session,
+            max_depth,
+            ..SubAgentConfig::default()
+        };
+        SubAgentManager::new_with_config(
+            db,
+            Arc::new(EventBroadcaster::new()),
+            Arc::new(ToolRegistry::new()),
+            config,
+            None,
+        )
+    }
+
+    fn session(manager: &SubAgentManager, key: &str) -> i64 {
+        manager
+            .db
+            .get_or_create_chat_session("subagent", 1, key, SessionScope::Dm, None)
+            .unwrap()
+            .id
+    }
+
+    fn record(
+        manager: &SubAgentManager,
+        parent_session_id: i64,
+        session_id: Option<i64>,
+        label: &str,
+    ) {
+        let mut context = SubAgentContext::new(
+            SubAgentManager::generate_id(label),
+            parent_session_id,
+            1,
+            label.to_string(),
+            "task".to_string(),
+            300,
+        );
+        if let Some(id) = session_id {
+            context.mark_running(id);
+        }
+        manager.save_subagent(&context).unwrap();
+    }
+
+    #[test]
+    fn test_spawn_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = manager(&dir, 2, 2);
+
+        let root = session(&manager, "root");
+        let child = session(&manager, "child");
+        let grandchild = session(&manager, "grandchild");
+        assert_eq!(manager.session_depth(root, 2).unwrap(), 0);
+        assert!(manager.check_spawn_limits(root).is_ok());
+
+        record(&manager, root, Some(child), "child");
+        record(&manager, child, Some(grandchild), "grandchild");
+        assert_eq!(manager.session_depth(child, 2).unwrap(), 1);
+        assert_eq!(manager.session_depth(grandchild, 2).unwrap(), 2);
+
+        assert!(manager.check_spawn_limits(child).is_ok());
+        let err = manager.check_spawn_limits(grandchild).unwrap_err();
+        assert!(err.contains("depth limit"));
+
+        // One running child so far; a second pending one fills the session's quota
+        record(&manager, root, None, "pending");
+        assert_eq!(manager.active_count_for_session(root).unwrap(), 2);
+        let err = manager.check_spawn_limits(root).unwrap_err();
+        assert!(err.contains("concurrency limit"));
+    }
+}
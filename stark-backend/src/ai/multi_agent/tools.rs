@@ -41,6 +41,36 @@ pub fn define_tasks_tool() -> ToolDefinition {
     }
 }
 
+// =============================================================================
+// ASSISTANT TOOLS
+// =============================================================================
+
+/// Create the `replan` tool for abandoning the current task list
+pub fn replan_tool() -> ToolDefinition {
+    let mut properties = HashMap::new();
+    properties.insert(
+        "reason".to_string(),
+        PropertySchema {
+            schema_type: "string".to_string(),
+            description: "What you discovered that makes the current plan wrong".to_string(),
+            default: None,
+            items: None,
+            enum_values: None,
+        },
+    );
+
+    ToolDefinition {
+        name: "replan".to_string(),
+        description: "Discard the current task list and return to planning mode. Use only when executing the plan revealed it was built on a wrong assumption (e.g. the token is on a different chain, the file doesn't exist). Completed tasks are kept in mind for the new plan.".to_string(),
+        input_schema: ToolInputSchema {
+            schema_type: "object".to_string(),
+            properties,
+            required: vec!["reason".to_string()],
+        },
+        group: ToolGroup::System,
+    }
+}
+
 /// Get tools available in task planner mode
 pub fn get_planner_tools() -> Vec<ToolDefinition> {
     vec![define_tasks_tool()]
@@ -54,11 +84,11 @@ pub fn get_planner_tools() -> Vec<ToolDefinition> {
 pub fn get_tools_for_mode(mode: super::types::AgentMode) -> Vec<ToolDefinition> {
     match mode {
         super::types::AgentMode::TaskPlanner => get_planner_tools(),
-        super::types::AgentMode::Assistant => vec![], // No orchestrator-specific tools in assistant mode
+        super::types::AgentMode::Assistant => vec![replan_tool()],
     }
 }
 
 /// Get all multi-agent tools (for reference)
 pub fn get_all_tools() -> Vec<ToolDefinition> {
-    vec![define_tasks_tool()]
+    vec![define_tasks_tool(), replan_tool()]
 }
@@ -375,10 +375,10 @@ pub struct AgentContext {
     #[serde(default)]
     pub subtype: AgentSubtype,
 
-    /// Number of iterations in current session
+    /// Loop iterations spent in the current mode (reset on every transition)
     pub mode_iterations: u32,
 
-    /// Total iterations
+    /// Orchestrator tool calls processed in this session
     pub total_iterations: u32,
 
     /// Scratchpad for agent notes
@@ -414,6 +414,35 @@ pub struct AgentContext {
     /// Used as default for web3 operations unless user explicitly specifies otherwise
     #[serde(default)]
     pub selected_network: Option<String>,
+
+    /// Number of times the agent has discarded its plan with `replan`
+    #[serde(default)]
+    pub replans: u32,
+
+    /// Why the previous plan was abandoned and what it had completed,
+    /// shown to the planner when it builds the replacement
+    #[serde(default)]
+    pub replan_note: Option<String>,
+
+    /// Signature of a recurring task whose findings carry across runs
+    /// (see `findings::task_signature`). `None` for ordinary chats.
+    #[serde(default)]
+    pub task_signature: Option<String>,
+
+    /// Findings from this run (completed task summaries), stored for later
+    /// runs of the same task. Only recorded when `task_signature` is set.
+    #[serde(default)]
+    pub findings: Vec<String>,
+
+    /// Whether plans must be approved by the user before they're executed
+    /// (the channel's `plan_approval` setting). Set per dispatch, not stored.
+    #[serde(default)]
+    pub plan_approval_required: bool,
+
+    /// A defined plan is waiting for the user to approve or reject it.
+    /// The task queue is stored with the context while this is set.
+    #[serde(default)]
+    pub plan_awaiting_approval: bool,
 }
 
 /// Active skill context that persists across turns
@@ -434,7 +463,7 @@ pub struct ActiveSkill {
     pub requires_tools: Vec<String>,
 }
 
-/// Mode transition (kept for API compatibility)
+/// A mode switch and why it happened, announced via `agent.mode_change`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModeTransition {
     pub from: AgentMode,
@@ -638,6 +667,10 @@ pub struct SubAgentConfig {
     pub default_timeout_secs: u64,
     /// Maximum timeout allowed (cannot exceed this)
     pub max_timeout_secs: u64,
+    /// Maximum pending or running sub-agents spawned by one parent session
+    pub max_concurrent_per_session: usize,
+    /// Maximum nesting depth (1 = sub-agents can't spawn their own sub-agents)
+    pub max_depth: usize,
 }
 
 impl Default for SubAgentConfig {
@@ -646,7 +679,9 @@ impl Default for SubAgentConfig {
             max_concurrent_per_channel: 3,
             max_total_concurrent: 10,
             default_timeout_secs: 300,
-            max_timeout_secs: 3600,
+            max_timeout_secs: crate::config::subagent_max_timeout_secs(),
+            max_concurrent_per_session: crate::config::subagent_max_concurrent(),
+            max_depth: crate::config::subagent_max_depth(),
         }
     }
 }
@@ -36,6 +36,8 @@ struct OpenAICompletionRequest {
     tool_choice: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<Value>,
 }
 
 /// Streaming chunk response from OpenAI API
@@ -263,14 +265,22 @@ impl OpenAIClient {
     }
 
     pub async fn generate_text(&self, messages: Vec<Message>) -> Result<String, String> {
-        let response = self.generate_with_tools_internal(messages, vec![], vec![]).await
+        let response = self.generate_with_tools_internal(messages, vec![], vec![], None).await
+            .map_err(|e| e.to_string())?;
+        Ok(response.content)
+    }
+
+    /// Generate text in the API's JSON mode, so the reply is a JSON object
+    pub async fn generate_json(&self, messages: Vec<Message>) -> Result<String, String> {
+        let response_format = json!({"type": "json_object"});
+        let response = self.generate_with_tools_internal(messages, vec![], vec![], Some(response_format)).await
             .map_err(|e| e.to_string())?;
         Ok(response.content)
     }
 
     /// Generate text and return payment info if x402 payment was made
     pub async fn generate_text_with_payment_info(&self, messages: Vec<Message>) -> Result<(String, Option<X402PaymentInfo>), String> {
-        let response = self.generate_with_tools_internal(messages, vec![], vec![]).await
+        let response = self.generate_with_tools_internal(messages, vec![], vec![], None).await
             .map_err(|e| e.to_string())?;
         Ok((response.content, response.x402_payment))
     }
@@ -281,7 +291,7 @@ impl OpenAIClient {
         tool_history: Vec<OpenAIMessage>,
         tools: Vec<ToolDefinition>,
     ) -> Result<AiResponse, AiError> {
-        self.generate_with_tools_internal(messages, tool_history, tools).await
+        self.generate_with_tools_internal(messages, tool_history, tools, None).await
     }
 
     async fn generate_with_tools_internal(
@@ -289,6 +299,7 @@ impl OpenAIClient {
         messages: Vec<Message>,
         tool_history: Vec<OpenAIMessage>,
         tools: Vec<ToolDefinition>,
+        response_format: Option<Value>,
     ) -> Result<AiResponse, AiError> {
         // Convert messages to OpenAI format
         let mut api_messages: Vec<OpenAIMessage> = messages
@@ -351,6 +362,7 @@ impl OpenAIClient {
             tools: openai_tools.clone(),
             tool_choice: if tools.is_empty() { None } else { Some("required".to_string()) },
             stream: None,
+            response_format,
         };
 
         // Debug: Log full request details
@@ -675,6 +687,7 @@ impl OpenAIClient {
             tools: openai_tools.clone(),
             tool_choice: if tools.is_empty() { None } else { Some("required".to_string()) },
             stream: Some(true),
+            response_format: None,
         };
 
         log::info!(
@@ -0,0 +1,235 @@
+//! Structured (JSON) responses
+//!
+//! A caller can ask for a machine-readable reply by passing a JSON Schema with
+//! the message (`NormalizedMessage::response_schema`). The agent is told to
+//! finish with a single JSON value matching it, and the dispatcher parses the
+//! final reply and validates it here. An invalid reply is re-prompted once,
+//! using the provider's native JSON mode where it has one.
+//!
+//! Validation covers the commonly used subset of JSON Schema: `type` (a name
+//! or a list of names), `properties`, `required`, `items`, `enum` and
+//! `additionalProperties: false`. Other keywords are ignored.
+
+use serde_json::Value;
+
+/// Instructions appended to the conversation when a schema is requested
+pub fn instructions(schema: &Value) -> String {
+    format!(
+        "## Response Format\n\n\
+         This request needs a machine-readable answer. Do the work as usual, then make your final reply \
+         a single JSON value matching this JSON Schema, with no prose or markdown around it:\n\n{}",
+        serde_json::to_string_pretty(schema).unwrap_or_else(|_| schema.to_string())
+    )
+}
+
+/// Message asking the model to correct a reply that failed validation
+pub fn correction_prompt(error: &str) -> String {
+    format!(
+        "Your reply wasn't valid for the requested format: {}. \
+         Reply again with only the JSON value matching the schema, and nothing else.",
+        error
+    )
+}
+
+/// Parse the JSON value out of a reply. Accepts a bare value, a fenced
+/// ```json block, or a value surrounded by other text (e.g. a tool log).
+pub fn extract_json(text: &str) -> Result<Value, String> {
+    let trimmed = text.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Ok(value);
+    }
+
+    // Fenced code block
+    if let Some(start) = trimmed.find("```") {
+        let body = &trimmed[start + 3..];
+        let body = body.strip_prefix("json").unwrap_or(body);
+        if let Some(end) = body.find("```") {
+            if let Ok(value) = serde_json::from_str(body[..end].trim()) {
+                return Ok(value);
+            }
+        }
+    }
+
+    // Last complete object or array in the text, since a final answer comes last
+    let mut last = None;
+    let mut idx = 0;
+    while let Some(offset) = trimmed[idx..].find(|c: char| c == '{' || c == '[') {
+        let start = idx + offset;
+        let mut stream = serde_json::Deserializer::from_str(&trimmed[start..]).into_iter::<Value>();
+        match stream.next() {
+            Some(Ok(value)) => {
+                idx = start + stream.byte_offset();
+                last = Some(value);
+            }
+            _ => idx = start + 1,
+        }
+    }
+
+    last.ok_or_else(|| "no JSON value found in the reply".to_string())
+}
+
+/// Extract the JSON value from a reply and validate it against the schema
+pub fn parse(schema: &Value, text: &str) -> Result<Value, String> {
+    let value = extract_json(text)?;
+    validate(schema, &value)?;
+    Ok(value)
+}
+
+/// Validate a value against a JSON Schema (see the module docs for the
+/// supported keywords). Errors name the offending path, e.g. `$.items[2].price`.
+pub fn validate(schema: &Value, value: &Value) -> Result<(), String> {
+    validate_at(schema, value, "$")
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    match schema.get("type") {
+        Some(Value::String(t)) if !matches_type(t, value) => {
+            return Err(format!("{} must be of type {}, got {}", path, t, type_name(value)));
+        }
+        Some(Value::Array(types)) => {
+            let names: Vec<&str> = types.iter().filter_map(|t| t.as_str()).collect();
+            if !names.iter().any(|t| matches_type(t, value)) {
+                return Err(format!(
+                    "{} must be of type {}, got {}",
+                    path,
+                    names.join(" or "),
+                    type_name(value)
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+            return Err(format!("{} must be one of: {} (got {})", path, allowed.join(", "), value));
+        }
+    }
+
+    if let Value::Object(map) = value {
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+
+        if let Some(Value::Array(required)) = schema.get("required") {
+            let missing: Vec<&str> = required
+                .iter()
+                .filter_map(|r| r.as_str())
+                .filter(|r| !map.contains_key(*r))
+                .collect();
+            if !missing.is_empty() {
+                return Err(format!("{} is missing required field(s): {}", path, missing.join(", ")));
+            }
+        }
+
+        if let Some(properties) = properties {
+            for (name, property) in properties {
+                if let Some(field) = map.get(name) {
+                    validate_at(property, field, &format!("{}.{}", path, name))?;
+                }
+            }
+        }
+
+        if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+            let mut extra: Vec<&str> = map
+                .keys()
+                .filter(|k| properties.map_or(true, |p| !p.contains_key(*k)))
+                .map(String::as_str)
+                .collect();
+            if !extra.is_empty() {
+                extra.sort();
+                return Err(format!("{} has unexpected field(s): {}", path, extra.join(", ")));
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate_at(item_schema, item, &format!("{}[{}]", path, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn matches_type(schema_type: &str, value: &Value) -> bool {
+    match schema_type {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["symbol", "prices"],
+            "additionalProperties": false,
+            "properties": {
+                "symbol": {"type": "string"},
+                "trend": {"enum": ["up", "down", "flat"]},
+                "prices": {"type": "array", "items": {"type": "number"}},
+                "note": {"type": ["string", "null"]}
+            }
+        })
+    }
+
+    #[test]
+    fn test_extract_json_from_reply() {
+        assert_eq!(extract_json(" {\"a\": 1} ").unwrap(), json!({"a": 1}));
+        assert_eq!(
+            extract_json("Here you go:\n```json\n{\"a\": [1, 2]}\n```").unwrap(),
+            json!({"a": [1, 2]})
+        );
+        assert_eq!(
+            extract_json("🔧 web_fetch {\"url\": \"x\"}\n\nResult: {\"a\": 2}").unwrap(),
+            json!({"a": 2})
+        );
+        assert!(extract_json("no json here").is_err());
+    }
+
+    #[test]
+    fn test_validate_against_schema() {
+        let schema = schema();
+        assert!(validate(&schema, &json!({"symbol": "ETH", "prices": [3100, 3150.5], "note": null})).is_ok());
+
+        let err = validate(&schema, &json!({"symbol": "ETH"})).unwrap_err();
+        assert_eq!(err, "$ is missing required field(s): prices");
+
+        let err = validate(&schema, &json!({"symbol": "ETH", "prices": [1, "2"]})).unwrap_err();
+        assert_eq!(err, "$.prices[1] must be of type number, got string");
+
+        let err = validate(&schema, &json!({"symbol": "ETH", "prices": [], "trend": "sideways"})).unwrap_err();
+        assert!(err.starts_with("$.trend must be one of"));
+
+        let err = validate(&schema, &json!({"symbol": "ETH", "prices": [], "extra": 1})).unwrap_err();
+        assert_eq!(err, "$ has unexpected field(s): extra");
+
+        assert!(validate(&schema, &json!(["ETH"])).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Connection status of channel listeners
+//!
+//! Whether a listener is running says little about whether it can talk to
+//! its platform. Listeners report their connection through a
+//! `ConnectionReporter`: the manager marks a channel connecting when it
+//! spawns the listener and disconnected (or errored) when it exits, and the
+//! listener marks it connected once the platform has accepted its token.
+//! The watchdog's health probes feed in too, so a dropped network shows up
+//! as an error. Every change is broadcast as a `channel.connection` event,
+//! and the latest status of each channel is kept for the REST API.
+
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::GatewayEvent;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Where a channel's connection to its platform stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    Disconnected,
+    Error,
+}
+
+impl ConnectionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectionState::Connecting => "connecting",
+            ConnectionState::Connected => "connected",
+            ConnectionState::Disconnected => "disconnected",
+            ConnectionState::Error => "error",
+        }
+    }
+}
+
+/// A channel's latest connection status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChannelConnection {
+    pub state: ConnectionState,
+    /// Why the channel disconnected or failed, as reported by the platform
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// When the channel entered this state
+    pub since: DateTime<Utc>,
+}
+
+/// Latest connection status of every channel, broadcasting changes
+pub struct ConnectionTracker {
+    broadcaster: Arc<EventBroadcaster>,
+    statuses: DashMap<i64, ChannelConnection>,
+}
+
+impl ConnectionTracker {
+    pub fn new(broadcaster: Arc<EventBroadcaster>) -> Self {
+        Self {
+            broadcaster,
+            statuses: DashMap::new(),
+        }
+    }
+
+    /// Latest status of a channel, if it has been started since boot
+    pub fn get(&self, channel_id: i64) -> Option<ChannelConnection> {
+        self.statuses.get(&channel_id).map(|status| status.clone())
+    }
+
+    /// Record a channel's status, broadcasting it if the state or reason changed
+    pub fn set(
+        &self,
+        channel_id: i64,
+        channel_type: &str,
+        name: &str,
+        state: ConnectionState,
+        reason: Option<&str>,
+    ) {
+        let changed = match self.statuses.get(&channel_id) {
+            Some(current) => current.state != state || current.reason.as_deref() != reason,
+            None => true,
+        };
+        if !changed {
+            return;
+        }
+
+        self.statuses.insert(
+            channel_id,
+            ChannelConnection {
+                state,
+                reason: reason.map(str::to_string),
+                since: Utc::now(),
+            },
+        );
+        self.broadcaster.broadcast(GatewayEvent::channel_connection(
+            channel_id,
+            channel_type,
+            name,
+            state.as_str(),
+            reason,
+        ));
+    }
+}
+
+/// One channel's handle on the tracker, given to its listener
+#[derive(Clone)]
+pub struct ConnectionReporter {
+    tracker: Arc<ConnectionTracker>,
+    channel_id: i64,
+    channel_type: String,
+    name: String,
+}
+
+impl ConnectionReporter {
+    pub fn new(tracker: Arc<ConnectionTracker>, channel_id: i64, channel_type: &str, name: &str) -> Self {
+        Self {
+            tracker,
+            channel_id,
+            channel_type: channel_type.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    pub fn connecting(&self) {
+        self.set(ConnectionState::Connecting, None);
+    }
+
+    pub fn connected(&self) {
+        self.set(ConnectionState::Connected, None);
+    }
+
+    pub fn disconnected(&self, reason: &str) {
+        self.set(ConnectionState::Disconnected, Some(reason));
+    }
+
+    pub fn error(&self, reason: &str) {
+        self.set(ConnectionState::Error, Some(reason));
+    }
+
+    fn set(&self, state: ConnectionState, reason: Option<&str>) {
+        self.tracker
+            .set(self.channel_id, &self.channel_type, &self.name, state, reason);
+    }
+}
@@ -1,14 +1,24 @@
 use crate::channels::dispatcher::MessageDispatcher;
-use crate::channels::types::{ChannelType, NormalizedMessage};
+use crate::channels::connection::ConnectionReporter;
+use crate::channels::flood_guard::{FloodDecision, FloodGuard};
+use crate::channels::types::{ChannelType, EditOutcome, NormalizedMessage, ReplyContext};
 use crate::db::Database;
 use crate::discord_hooks;
+use crate::discord_hooks::DiscordHooksConfig;
 use crate::gateway::events::EventBroadcaster;
+use crate::gateway::methods::{handle_tx_queue_confirm, handle_tx_queue_deny, TxQueueParams};
 use crate::gateway::protocol::GatewayEvent;
 use crate::models::{Channel, ChannelSettingKey, ToolOutputVerbosity};
+use crate::tx_queue::TxQueueManager;
+use dashmap::DashMap;
+use serde::Deserialize;
 use serenity::all::{
-    Client, Context, EditMessage, EventHandler, GatewayIntents, Message, MessageId, Ready,
+    ChannelId, Client, ConnectionStage, Context, CreateEmbed, CreateEmbedFooter, CreateMessage,
+    EditMessage, EventHandler, GatewayIntents, GuildId, Http, Message, MessageId,
+    MessageUpdateEvent, Reaction, ReactionType, Ready, ShardStageUpdateEvent, UserId,
 };
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use tokio::sync::oneshot;
 
 /// Discord channel output configuration
@@ -16,6 +26,8 @@ use tokio::sync::oneshot;
 pub struct DiscordOutputConfig {
     pub tool_call_verbosity: ToolOutputVerbosity,
     pub tool_result_verbosity: ToolOutputVerbosity,
+    /// Render ```embed blocks in replies as Discord embeds
+    pub rich_embeds: bool,
 }
 
 impl DiscordOutputConfig {
@@ -35,9 +47,17 @@ impl DiscordOutputConfig {
             .map(|v| ToolOutputVerbosity::from_str_or_default(&v))
             .unwrap_or(ToolOutputVerbosity::Minimal);
 
+        let rich_embeds = db
+            .get_channel_setting(channel_id, ChannelSettingKey::DiscordRichEmbeds.as_ref())
+            .ok()
+            .flatten()
+            .map(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes" | "on"))
+            .unwrap_or(false);
+
         Self {
             tool_call_verbosity,
             tool_result_verbosity,
+            rich_embeds,
         }
     }
 }
@@ -47,6 +67,256 @@ impl Default for DiscordOutputConfig {
         Self {
             tool_call_verbosity: ToolOutputVerbosity::Minimal,
             tool_result_verbosity: ToolOutputVerbosity::Minimal,
+            rich_embeds: false,
+        }
+    }
+}
+
+/// Discord embed limits (characters / counts)
+const EMBED_TITLE_MAX: usize = 256;
+const EMBED_DESCRIPTION_MAX: usize = 4096;
+const EMBED_FIELDS_MAX: usize = 25;
+const EMBED_FIELD_NAME_MAX: usize = 256;
+const EMBED_FIELD_VALUE_MAX: usize = 1024;
+const EMBED_FOOTER_MAX: usize = 2048;
+const EMBED_TOTAL_MAX: usize = 6000;
+/// Embeds allowed in a single Discord message
+const EMBEDS_PER_MESSAGE: usize = 10;
+
+/// Hint added to the agent's input when rich embeds are enabled for the channel
+const RICH_EMBED_HINT: &str = "[DISCORD RICH EMBEDS ENABLED - To send structured output, include a fenced ```embed block containing JSON: \
+{\"title\", \"description\", \"fields\": [{\"name\", \"value\", \"inline\"}], \"color\": \"#5865F2\", \"footer\", \"url\"}. Text outside the block is sent as a normal message.]";
+
+/// A rich embed requested by the agent with a ```embed fenced JSON block
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+struct EmbedSpec {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    /// Integer or hex string ("#5865F2", "0x5865F2")
+    #[serde(default)]
+    color: Option<serde_json::Value>,
+    #[serde(default)]
+    fields: Vec<EmbedFieldSpec>,
+    /// Plain string or {"text": "..."}
+    #[serde(default)]
+    footer: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+struct EmbedFieldSpec {
+    name: String,
+    value: String,
+    #[serde(default)]
+    inline: bool,
+}
+
+/// Embed parts after Discord's limits have been applied
+struct ClampedEmbed {
+    title: Option<String>,
+    description: Option<String>,
+    fields: Vec<(String, String, bool)>,
+    footer: Option<String>,
+}
+
+impl EmbedSpec {
+    fn is_empty(&self) -> bool {
+        self.title.as_deref().unwrap_or("").trim().is_empty()
+            && self.description.as_deref().unwrap_or("").trim().is_empty()
+            && self.fields.is_empty()
+    }
+
+    fn color_value(&self) -> Option<u32> {
+        match self.color.as_ref()? {
+            serde_json::Value::Number(n) => n.as_u64().filter(|v| *v <= 0xFFFFFF).map(|v| v as u32),
+            serde_json::Value::String(s) => {
+                let hex = s.trim().trim_start_matches('#').trim_start_matches("0x");
+                u32::from_str_radix(hex, 16).ok().filter(|v| *v <= 0xFFFFFF)
+            }
+            _ => None,
+        }
+    }
+
+    fn footer_text(&self) -> Option<String> {
+        let text = match self.footer.as_ref()? {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Object(map) => map.get("text")?.as_str()?.to_string(),
+            _ => return None,
+        };
+        Some(text).filter(|t| !t.trim().is_empty())
+    }
+
+    /// Apply Discord's limits: truncate each part and drop trailing fields
+    /// until the embed fits the total character budget
+    fn clamped(&self) -> ClampedEmbed {
+        let title = self.title.as_deref().map(|t| truncate_chars(t, EMBED_TITLE_MAX));
+        let description = self.description.as_deref().map(|d| truncate_chars(d, EMBED_DESCRIPTION_MAX));
+        let footer = self.footer_text().map(|f| truncate_chars(&f, EMBED_FOOTER_MAX));
+        let mut fields: Vec<(String, String, bool)> = self
+            .fields
+            .iter()
+            .filter(|f| !f.name.trim().is_empty() && !f.value.trim().is_empty())
+            .take(EMBED_FIELDS_MAX)
+            .map(|f| {
+                (
+                    truncate_chars(&f.name, EMBED_FIELD_NAME_MAX),
+                    truncate_chars(&f.value, EMBED_FIELD_VALUE_MAX),
+                    f.inline,
+                )
+            })
+            .collect();
+
+        let len = |s: &Option<String>| s.as_deref().map(|v| v.chars().count()).unwrap_or(0);
+        let fixed = len(&title) + len(&description) + len(&footer);
+        while !fields.is_empty()
+            && fixed + fields.iter().map(|(n, v, _)| n.chars().count() + v.chars().count()).sum::<usize>()
+                > EMBED_TOTAL_MAX
+        {
+            fields.pop();
+        }
+        ClampedEmbed { title, description, fields, footer }
+    }
+
+    fn to_create_embed(&self) -> CreateEmbed {
+        let ClampedEmbed { title, description, fields, footer } = self.clamped();
+        let mut embed = CreateEmbed::new();
+        if let Some(title) = title {
+            embed = embed.title(title);
+        }
+        if let Some(description) = description {
+            embed = embed.description(description);
+        }
+        if let Some(url) = self.url.as_deref().filter(|u| u.starts_with("http://") || u.starts_with("https://")) {
+            embed = embed.url(url);
+        }
+        if let Some(color) = self.color_value() {
+            embed = embed.color(color);
+        }
+        if !fields.is_empty() {
+            embed = embed.fields(fields);
+        }
+        if let Some(footer) = footer {
+            embed = embed.footer(CreateEmbedFooter::new(footer));
+        }
+        embed
+    }
+
+    /// Plain-text rendering used when the embed can't be sent
+    fn to_plain_text(&self) -> String {
+        let ClampedEmbed { title, description, fields, footer } = self.clamped();
+        let mut parts = Vec::new();
+        if let Some(title) = title {
+            parts.push(format!("**{}**", title));
+        }
+        if let Some(description) = description {
+            parts.push(description);
+        }
+        for (name, value, _) in fields {
+            parts.push(format!("**{}:** {}", name, value));
+        }
+        if let Some(footer) = footer {
+            parts.push(format!("_{}_", footer));
+        }
+        parts.join("\n")
+    }
+}
+
+fn truncate_chars(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let mut out: String = s.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// Whether a fence's info string marks an embed block (```embed, ```json embed)
+fn is_embed_fence(info: &str) -> bool {
+    let words: Vec<String> = info.split_whitespace().map(|w| w.to_lowercase()).collect();
+    match words.as_slice() {
+        [one] => one == "embed",
+        [a, b] => (a == "json" && b == "embed") || (a == "embed" && b == "json"),
+        _ => false,
+    }
+}
+
+/// Split a reply into its plain text and the embeds requested by ```embed
+/// blocks. Blocks that aren't valid embed JSON stay in the text unchanged.
+fn extract_embeds(text: &str) -> (String, Vec<EmbedSpec>) {
+    let mut kept: Vec<&str> = Vec::new();
+    let mut embeds = Vec::new();
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        let opening = line.trim_start().strip_prefix("```").filter(|info| is_embed_fence(info));
+        if opening.is_none() {
+            kept.push(line);
+            continue;
+        }
+
+        let mut block: Vec<&str> = Vec::new();
+        let mut closed = false;
+        for inner in lines.by_ref() {
+            if inner.trim() == "```" {
+                closed = true;
+                break;
+            }
+            block.push(inner);
+        }
+
+        let parsed = if closed {
+            serde_json::from_str::<EmbedSpec>(&block.join("\n"))
+                .ok()
+                .filter(|spec| !spec.is_empty())
+        } else {
+            None
+        };
+        match parsed {
+            Some(spec) => embeds.push(spec),
+            None => {
+                kept.push(line);
+                kept.extend(block);
+                if closed {
+                    kept.push("```");
+                }
+            }
+        }
+    }
+
+    (kept.join("\n").trim().to_string(), embeds)
+}
+
+/// Send a reply, rendering ```embed blocks as Discord embeds when enabled.
+/// Falls back to plain text if Discord rejects an embed (e.g. missing Embed Links).
+async fn send_reply(http: &Arc<Http>, channel_id: ChannelId, text: &str, rich_embeds: bool) {
+    let (text, embeds) = if rich_embeds {
+        extract_embeds(text)
+    } else {
+        (text.to_string(), Vec::new())
+    };
+
+    if !text.is_empty() {
+        for chunk in split_message(&text, 2000) {
+            if let Err(e) = channel_id.say(http, &chunk).await {
+                log::error!("Failed to send Discord message: {}", e);
+            }
+        }
+    }
+
+    for batch in embeds.chunks(EMBEDS_PER_MESSAGE) {
+        let message = CreateMessage::new().embeds(batch.iter().map(EmbedSpec::to_create_embed).collect());
+        if let Err(e) = channel_id.send_message(http, message).await {
+            log::warn!("Discord: Failed to send embed, falling back to plain text: {}", e);
+            for spec in batch {
+                for chunk in split_message(&spec.to_plain_text(), 2000) {
+                    if let Err(e) = channel_id.say(http, &chunk).await {
+                        log::error!("Failed to send Discord message: {}", e);
+                    }
+                }
+            }
         }
     }
 }
@@ -125,11 +395,228 @@ fn format_mode_change_for_discord(mode: &str, label: &str, reason: Option<&str>)
     }
 }
 
+/// Reaction an admin uses to approve a pending action
+const APPROVE_EMOJI: &str = "✅";
+/// Reaction an admin uses to cancel a pending action
+const CANCEL_EMOJI: &str = "❌";
+/// Seconds to wait for an admin reaction before auto-cancelling
+const APPROVAL_TIMEOUT_SECS: u64 = 300;
+
+/// How a pending action was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApprovalDecision {
+    Approve,
+    Cancel,
+    /// No admin reacted before the timeout
+    Expired,
+}
+
+impl ApprovalDecision {
+    /// Map a reaction to a decision; other emoji are ignored
+    fn from_reaction(emoji: &ReactionType) -> Option<Self> {
+        match emoji {
+            ReactionType::Unicode(s) if s == APPROVE_EMOJI => Some(Self::Approve),
+            ReactionType::Unicode(s) if s == CANCEL_EMOJI => Some(Self::Cancel),
+            _ => None,
+        }
+    }
+}
+
+/// A sensitive action posted to Discord and waiting for an admin reaction
+#[derive(Debug, Clone)]
+struct PendingAction {
+    /// tx_queue UUID of the transaction awaiting approval
+    id: String,
+    /// Summary shown in the approval message
+    summary: String,
+    discord_channel_id: ChannelId,
+    message_id: MessageId,
+}
+
+/// Format the approval request for a `tx_queue.confirmation_required` event
+fn format_approval_request(data: &serde_json::Value) -> Option<(String, String)> {
+    let field = |key: &str| data.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    let uuid = field("uuid");
+    if uuid.is_empty() {
+        return None;
+    }
+    let value = match field("value_formatted") {
+        "" => field("value"),
+        formatted => formatted,
+    };
+    let summary = format!(
+        "🔐 **Transaction approval required**\n\
+         **Network:** {}\n\
+         **To:** `{}`\n\
+         **Value:** {}\n\
+         **ID:** `{}`",
+        field("network"),
+        field("to"),
+        value,
+        uuid
+    );
+    Some((uuid.to_string(), summary))
+}
+
+/// Reaction-based approval for queued transactions (partner mode).
+///
+/// When the agent queues a transaction for confirmation, the bot posts a
+/// summary and reacts with ✅/❌. An admin's reaction broadcasts or cancels
+/// the transaction; reactions from anyone else are ignored. Pending actions
+/// are keyed by the Discord message id and auto-cancel after a timeout.
+#[derive(Clone)]
+struct ApprovalWorkflow {
+    channel_id: i64,
+    tx_queue: Arc<TxQueueManager>,
+    broadcaster: Arc<EventBroadcaster>,
+    pending: Arc<DashMap<MessageId, PendingAction>>,
+}
+
+impl ApprovalWorkflow {
+    fn new(channel_id: i64, tx_queue: Arc<TxQueueManager>, broadcaster: Arc<EventBroadcaster>) -> Self {
+        Self {
+            channel_id,
+            tx_queue,
+            broadcaster,
+            pending: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Whether an approval message is already open for this action id
+    fn is_pending(&self, id: &str) -> bool {
+        self.pending.iter().any(|entry| entry.value().id == id)
+    }
+
+    /// Take the pending action for a message, so it can only be resolved once
+    fn claim(&self, message_id: MessageId) -> Option<PendingAction> {
+        self.pending.remove(&message_id).map(|(_, action)| action)
+    }
+
+    /// Post an approval request for a `tx_queue.confirmation_required` event
+    async fn request_from_event(&self, http: &Arc<Http>, discord_channel_id: ChannelId, data: &serde_json::Value) {
+        if let Some((id, summary)) = format_approval_request(data) {
+            self.request(http, discord_channel_id, id, summary).await;
+        }
+    }
+
+    /// Post an approval request and start its timeout
+    async fn request(&self, http: &Arc<Http>, discord_channel_id: ChannelId, id: String, summary: String) {
+        if self.is_pending(&id) {
+            return;
+        }
+
+        let content = format!(
+            "{}\n\nAn admin must react {} to broadcast or {} to cancel (expires in {} min).",
+            summary,
+            APPROVE_EMOJI,
+            CANCEL_EMOJI,
+            APPROVAL_TIMEOUT_SECS / 60
+        );
+        let message = match discord_channel_id.say(http, &content).await {
+            Ok(m) => m,
+            Err(e) => {
+                log::error!("Discord: Failed to post approval request for {}: {}", id, e);
+                return;
+            }
+        };
+        for emoji in [APPROVE_EMOJI, CANCEL_EMOJI] {
+            if let Err(e) = message.react(http, ReactionType::Unicode(emoji.to_string())).await {
+                log::warn!("Discord: Failed to add {} reaction: {}", emoji, e);
+            }
+        }
+
+        log::info!("Discord: Awaiting admin approval for {} on message {}", id, message.id);
+        self.pending.insert(
+            message.id,
+            PendingAction {
+                id,
+                summary,
+                discord_channel_id,
+                message_id: message.id,
+            },
+        );
+
+        let workflow = self.clone();
+        let http = http.clone();
+        let message_id = message.id;
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(APPROVAL_TIMEOUT_SECS)).await;
+            if let Some(action) = workflow.claim(message_id) {
+                log::info!("Discord: Approval for {} timed out, cancelling", action.id);
+                workflow.resolve(&http, action, ApprovalDecision::Expired, None).await;
+            }
+        });
+    }
+
+    /// Carry out a decision and edit the approval message to show the outcome
+    async fn resolve(&self, http: &Arc<Http>, action: PendingAction, decision: ApprovalDecision, by: Option<UserId>) {
+        let params = TxQueueParams {
+            uuid: action.id.clone(),
+            channel_id: self.channel_id,
+        };
+        let admin = by.map(|u| format!(" by <@{}>", u)).unwrap_or_default();
+
+        let mut explorer_url = None;
+        let outcome = match decision {
+            ApprovalDecision::Approve => {
+                match handle_tx_queue_confirm(params, self.tx_queue.clone(), self.broadcaster.clone()).await {
+                    Ok(result) => {
+                        explorer_url = result.get("explorer_url").and_then(|v| v.as_str()).map(String::from);
+                        format!(
+                            "{} **Approved{}** - broadcast: {}",
+                            APPROVE_EMOJI,
+                            admin,
+                            explorer_url.as_deref().unwrap_or("submitted")
+                        )
+                    }
+                    Err(e) => format!("⚠️ **Approval failed{}:** {}", admin, e.message),
+                }
+            }
+            ApprovalDecision::Cancel | ApprovalDecision::Expired => {
+                let label = if decision == ApprovalDecision::Expired {
+                    format!("⌛ **Cancelled** - no admin response within {} min", APPROVAL_TIMEOUT_SECS / 60)
+                } else {
+                    format!("{} **Cancelled{}**", CANCEL_EMOJI, admin)
+                };
+                match handle_tx_queue_deny(params, self.tx_queue.clone(), self.broadcaster.clone()).await {
+                    Ok(_) => label,
+                    Err(e) => format!("{} ({})", label, e.message),
+                }
+            }
+        };
+
+        let content = format!("{}\n\n{}", action.summary, outcome);
+        if let Err(e) = action
+            .discord_channel_id
+            .edit_message(http, action.message_id, EditMessage::new().content(&content))
+            .await
+        {
+            log::warn!("Discord: Failed to update approval message: {}", e);
+        }
+
+        // Edits don't notify anyone, so reply with the transaction link too
+        if let Some(url) = explorer_url {
+            let reply = format!("{} Transaction `{}` broadcast: {}", APPROVE_EMOJI, action.id, url);
+            if let Err(e) = action.discord_channel_id.say(http, &reply).await {
+                log::warn!("Discord: Failed to send transaction link: {}", e);
+            }
+        }
+    }
+}
+
 struct DiscordHandler {
     channel_id: i64,
     dispatcher: Arc<MessageDispatcher>,
     broadcaster: Arc<EventBroadcaster>,
     db: Arc<Database>,
+    /// Reaction approvals for queued transactions (requires the tx queue)
+    approvals: Option<ApprovalWorkflow>,
+    /// Drops messages from users flooding the bot before they are dispatched
+    flood_guard: FloodGuard,
+    /// The bot's own user ID, known once the gateway is ready
+    bot_user_id: OnceLock<UserId>,
+    /// Reports the gateway connection's state to the dashboard
+    connection: ConnectionReporter,
 }
 
 #[serenity::async_trait]
@@ -159,8 +646,22 @@ impl EventHandler for DiscordHandler {
 
         // ===== Discord Hooks Integration =====
         // Process through discord_hooks module first (config reloaded from DB each time)
-        match discord_hooks::process(&msg, &ctx, &self.db, self.channel_id).await {
+        let tx_queue = self.approvals.as_ref().map(|a| &a.tx_queue);
+        match discord_hooks::process(&msg, &ctx, &self.db, self.channel_id, tx_queue).await {
             Ok(result) => {
+                // A queued transaction (e.g. a tip) waiting for an admin reaction
+                if let Some(approval) = result.approval {
+                    match self.approvals {
+                        Some(ref approvals) => {
+                            approvals.request(&ctx.http, msg.channel_id, approval.id, approval.summary).await;
+                        }
+                        None => {
+                            let _ = msg.channel_id.say(&ctx.http, "Approvals aren't available on this channel.").await;
+                        }
+                    }
+                    return;
+                }
+
                 // If module handled it with a direct response, send it and return
                 if let Some(response) = result.response {
                     let chunks = split_message(&response, 2000);
@@ -205,6 +706,10 @@ impl EventHandler for DiscordHandler {
                         message_id: Some(msg.id.to_string()),
                         session_mode: None,
                         selected_network: None,
+                        allowed_tools: forward.allowed_tools,
+                        task_signature: None,
+                        response_schema: None,
+                        reply_to: self.reply_context(&msg),
                     };
 
                     // Continue to dispatch below with this normalized message
@@ -251,33 +756,155 @@ impl EventHandler for DiscordHandler {
             message_id: Some(msg.id.to_string()),
             session_mode: None,
             selected_network: None,
+            allowed_tools: None,
+            task_signature: None,
+            response_schema: None,
+            reply_to: self.reply_context(&msg),
         };
 
         self.dispatch_and_respond(&ctx, &msg, normalized, &user_name).await;
     }
 
+    async fn message_update(
+        &self,
+        ctx: Context,
+        _old_if_available: Option<Message>,
+        _new: Option<Message>,
+        event: MessageUpdateEvent,
+    ) {
+        // Embed unfurls also arrive as updates, without content
+        let (Some(content), Some(author)) = (event.content.as_deref(), event.author.as_ref()) else {
+            return;
+        };
+        if author.bot || content.is_empty() {
+            return;
+        }
+
+        let outcome = self.dispatcher.apply_message_edit(
+            ChannelType::Discord.as_str(),
+            self.channel_id,
+            &author.id.to_string(),
+            &event.id.to_string(),
+            content,
+        );
+        if outcome != EditOutcome::Redispatch {
+            return;
+        }
+
+        // Run the edited message through the normal path, hooks and all
+        match event.channel_id.message(&ctx.http, event.id).await {
+            Ok(msg) => {
+                log::info!("Discord: Re-dispatching edited message {} from {}", event.id, author.name);
+                self.message(ctx, msg).await;
+            }
+            Err(e) => log::warn!("Discord: Failed to fetch edited message {}: {}", event.id, e),
+        }
+    }
+
+    async fn message_delete(
+        &self,
+        _ctx: Context,
+        _channel_id: ChannelId,
+        deleted_message_id: MessageId,
+        _guild_id: Option<GuildId>,
+    ) {
+        // Discord message IDs are globally unique, so the ID alone finds it
+        self.dispatcher.apply_message_delete(
+            ChannelType::Discord.as_str(),
+            self.channel_id,
+            &deleted_message_id.to_string(),
+        );
+    }
+
+    async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+        let Some(ref approvals) = self.approvals else {
+            return;
+        };
+        if !approvals.pending.contains_key(&reaction.message_id) {
+            return;
+        }
+        let Some(decision) = ApprovalDecision::from_reaction(&reaction.emoji) else {
+            return;
+        };
+        let Some(user_id) = reaction.user_id else {
+            return;
+        };
+
+        // Only admins can approve or cancel (this also skips the bot's own reactions)
+        let config = DiscordHooksConfig::from_channel_settings(&self.db, self.channel_id);
+        if !config.is_admin(&user_id.to_string()) {
+            log::debug!("Discord: Ignoring approval reaction from non-admin {}", user_id);
+            return;
+        }
+
+        if let Some(action) = approvals.claim(reaction.message_id) {
+            log::info!("Discord: Admin {} resolved {} with {:?}", user_id, action.id, decision);
+            approvals.resolve(&ctx.http, action, decision, Some(user_id)).await;
+        }
+    }
+
     async fn ready(&self, _ctx: Context, ready: Ready) {
         log::info!("Discord: Bot connected as {}", ready.user.name);
+        let _ = self.bot_user_id.set(ready.user.id);
+        self.connection.connected();
+    }
+
+    async fn shard_stage_update(&self, _ctx: Context, event: ShardStageUpdateEvent) {
+        log::info!("Discord: Shard {} {:?} -> {:?}", event.shard_id, event.old, event.new);
+        match event.new {
+            ConnectionStage::Connected => self.connection.connected(),
+            ConnectionStage::Disconnected => self.connection.disconnected("Discord gateway connection lost"),
+            _ if event.old == ConnectionStage::Connected => self.connection.connecting(),
+            _ => {}
+        }
     }
 }
 
 impl DiscordHandler {
+    /// What a message replies to, if the user used Discord's reply feature
+    fn reply_context(&self, msg: &Message) -> Option<ReplyContext> {
+        let replied = msg.referenced_message.as_deref()?;
+        if replied.content.is_empty() {
+            return None;
+        }
+        Some(ReplyContext {
+            message_id: Some(replied.id.to_string()),
+            author: replied.author.name.clone(),
+            from_bot: self.bot_user_id.get() == Some(&replied.author.id),
+            text: replied.content.clone(),
+        })
+    }
+
     /// Dispatch a message to the AI and send the response
     async fn dispatch_and_respond(
         &self,
         ctx: &Context,
         msg: &Message,
-        normalized: NormalizedMessage,
+        mut normalized: NormalizedMessage,
         user_name: &str,
     ) {
+        if let FloodDecision::Drop { notice } = self.flood_guard.check(&normalized.user_id) {
+            log::info!("Discord: Dropping message from {} ({}): sending too fast", user_name, normalized.user_id);
+            if let Some(notice) = notice {
+                let _ = msg.channel_id.say(&ctx.http, notice).await;
+            }
+            return;
+        }
+
         // Load output configuration from channel settings
         let output_config = DiscordOutputConfig::from_channel_settings(&self.db, self.channel_id);
         log::info!(
-            "Discord: Output config - tool_call={:?}, tool_result={:?}",
+            "Discord: Output config - tool_call={:?}, tool_result={:?}, rich_embeds={}",
             output_config.tool_call_verbosity,
-            output_config.tool_result_verbosity
+            output_config.tool_result_verbosity,
+            output_config.rich_embeds
         );
 
+        // Tell the agent it can reply with embeds on this channel
+        if output_config.rich_embeds {
+            normalized.text = format!("{}\n\n{}", RICH_EMBED_HINT, normalized.text);
+        }
+
         // Subscribe to events for real-time tool call forwarding
         let (client_id, mut event_rx) = self.broadcaster.subscribe();
         log::info!("Discord: Subscribed to events as client {}", client_id);
@@ -288,6 +915,7 @@ impl DiscordHandler {
         let channel_id_for_events = self.channel_id;
         // Convert Discord channel ID to string for event filtering
         let chat_id_for_events = discord_channel_id.to_string();
+        let approvals = self.approvals.clone();
 
         // Spawn task to forward events to Discord in real-time
         // Uses a single "status message" that gets edited for each update to reduce spam
@@ -345,7 +973,10 @@ impl DiscordHandler {
                             .unwrap_or("");
 
                         // say_to_user messages are sent as permanent new messages, not status updates
-                        if tool_name == "say_to_user" && success && !content.is_empty() {
+                        if tool_name == "say_to_user" && success && !content.is_empty() && output_config.rich_embeds {
+                            send_reply(&http, discord_channel_id, content, true).await;
+                            None // Don't update status message
+                        } else if tool_name == "say_to_user" && success && !content.is_empty() {
                             let display_content = if content.len() > 2000 {
                                 format!("{}...", &content[..1997])
                             } else {
@@ -360,6 +991,13 @@ impl DiscordHandler {
                             format_tool_result_for_discord(tool_name, success, duration_ms, content, output_config.tool_result_verbosity)
                         }
                     }
+                    "tx_queue.confirmation_required" => {
+                        // Post a reaction-approval message in place of the web confirmation modal
+                        if let Some(ref approvals) = approvals {
+                            approvals.request_from_event(&http, discord_channel_id, &event.data).await;
+                        }
+                        None
+                    }
                     "agent.mode_change" => {
                         // Skip mode changes in minimal/none verbosity
                         if matches!(output_config.tool_call_verbosity, ToolOutputVerbosity::Minimal | ToolOutputVerbosity::None) {
@@ -490,14 +1128,7 @@ impl DiscordHandler {
         // Send final response
         if result.error.is_none() && !result.response.is_empty() {
             // Discord has a 2000 character limit per message
-            let response = &result.response;
-            let chunks = split_message(response, 2000);
-
-            for chunk in chunks {
-                if let Err(e) = msg.channel_id.say(&ctx.http, &chunk).await {
-                    log::error!("Failed to send Discord message: {}", e);
-                }
-            }
+            send_reply(&ctx.http, msg.channel_id, &result.response, output_config.rich_embeds).await;
         } else if let Some(error) = result.error {
             let error_msg = format!("Sorry, I encountered an error: {}", error);
             let _ = msg.channel_id.say(&ctx.http, &error_msg).await;
@@ -548,12 +1179,23 @@ fn split_message(text: &str, max_len: usize) -> Vec<String> {
     chunks
 }
 
+/// Check that the bot token still reaches Discord, for the channel watchdog
+pub async fn probe(channel: &Channel) -> Result<(), String> {
+    Http::new(&channel.bot_token)
+        .get_current_user()
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
 /// Start a Discord bot listener
 pub async fn start_discord_listener(
     channel: Channel,
     dispatcher: Arc<MessageDispatcher>,
     broadcaster: Arc<EventBroadcaster>,
     db: Arc<Database>,
+    tx_queue: Option<Arc<TxQueueManager>>,
+    connection: ConnectionReporter,
     mut shutdown_rx: oneshot::Receiver<()>,
 ) -> Result<(), String> {
     let channel_id = channel.id;
@@ -563,16 +1205,22 @@ pub async fn start_discord_listener(
     log::info!("Starting Discord listener for channel: {}", channel_name);
     log::info!("Discord: Token length = {}", bot_token.len());
 
-    // Set up intents - we need message content to read messages
+    // Set up intents - we need message content to read messages, and reactions for approvals
     let intents = GatewayIntents::GUILD_MESSAGES
         | GatewayIntents::DIRECT_MESSAGES
-        | GatewayIntents::MESSAGE_CONTENT;
+        | GatewayIntents::MESSAGE_CONTENT
+        | GatewayIntents::GUILD_MESSAGE_REACTIONS
+        | GatewayIntents::DIRECT_MESSAGE_REACTIONS;
 
     let handler = DiscordHandler {
         channel_id,
         dispatcher,
         broadcaster: broadcaster.clone(),
         db,
+        approvals: tx_queue.map(|q| ApprovalWorkflow::new(channel_id, q, broadcaster.clone())),
+        flood_guard: FloodGuard::from_config(),
+        bot_user_id: OnceLock::new(),
+        connection,
     };
 
     // Create client
@@ -625,3 +1273,111 @@ pub async fn start_discord_listener(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_embeds_parses_block_and_keeps_text() {
+        let reply = "Here is the summary:\n```embed\n{\"title\": \"Portfolio\", \"description\": \"Daily update\", \"color\": \"#5865F2\", \"fields\": [{\"name\": \"ETH\", \"value\": \"1.5\", \"inline\": true}], \"footer\": {\"text\": \"starkbot\"}}\n```\nLet me know if you need more.";
+        let (text, embeds) = extract_embeds(reply);
+
+        assert_eq!(text, "Here is the summary:\nLet me know if you need more.");
+        assert_eq!(embeds.len(), 1);
+        let embed = &embeds[0];
+        assert_eq!(embed.title.as_deref(), Some("Portfolio"));
+        assert_eq!(embed.color_value(), Some(0x5865F2));
+        assert_eq!(embed.footer_text().as_deref(), Some("starkbot"));
+        assert_eq!(embed.fields.len(), 1);
+        assert!(embed.fields[0].inline);
+    }
+
+    #[test]
+    fn test_extract_embeds_falls_back_to_text() {
+        // Invalid JSON, an unterminated block and non-embed fences are left as-is
+        let invalid = "```embed\n{not json}\n```";
+        assert_eq!(extract_embeds(invalid), (invalid.to_string(), vec![]));
+
+        let unterminated = "```embed\n{\"title\": \"x\"}";
+        assert_eq!(extract_embeds(unterminated), (unterminated.to_string(), vec![]));
+
+        let plain_json = "```json\n{\"title\": \"x\"}\n```";
+        assert_eq!(extract_embeds(plain_json), (plain_json.to_string(), vec![]));
+
+        let empty = "```embed\n{\"color\": 1}\n```";
+        assert_eq!(extract_embeds(empty).1.len(), 0);
+    }
+
+    #[test]
+    fn test_approval_decision_from_reaction() {
+        let unicode = |s: &str| ReactionType::Unicode(s.to_string());
+        assert_eq!(ApprovalDecision::from_reaction(&unicode("✅")), Some(ApprovalDecision::Approve));
+        assert_eq!(ApprovalDecision::from_reaction(&unicode("❌")), Some(ApprovalDecision::Cancel));
+        assert_eq!(ApprovalDecision::from_reaction(&unicode("👍")), None);
+    }
+
+    #[test]
+    fn test_format_approval_request() {
+        let data = serde_json::json!({
+            "uuid": "3f1c9a52-6a0b-4c7e-9d7e-1b2c3d4e5f60",
+            "network": "base",
+            "to": "0xabc",
+            "value": "1000000000000000",
+            "value_formatted": "0.001 ETH"
+        });
+        let (id, summary) = format_approval_request(&data).unwrap();
+        assert_eq!(id, "3f1c9a52-6a0b-4c7e-9d7e-1b2c3d4e5f60");
+        assert!(summary.contains("**Network:** base"));
+        assert!(summary.contains("0.001 ETH"));
+
+        assert!(format_approval_request(&serde_json::json!({"network": "base"})).is_none());
+    }
+
+    #[test]
+    fn test_pending_action_is_claimed_once() {
+        let workflow = ApprovalWorkflow::new(
+            1,
+            Arc::new(TxQueueManager::new()),
+            Arc::new(EventBroadcaster::new()),
+        );
+        let message_id = MessageId::new(42);
+        workflow.pending.insert(
+            message_id,
+            PendingAction {
+                id: "uuid-1".to_string(),
+                summary: String::new(),
+                discord_channel_id: ChannelId::new(7),
+                message_id,
+            },
+        );
+
+        assert!(workflow.is_pending("uuid-1"));
+        assert!(workflow.claim(message_id).is_some());
+        // A second reaction (or the timeout) finds nothing left to resolve
+        assert!(workflow.claim(message_id).is_none());
+        assert!(!workflow.is_pending("uuid-1"));
+    }
+
+    #[test]
+    fn test_embed_limits_are_enforced() {
+        let spec = EmbedSpec {
+            title: Some("t".repeat(300)),
+            description: Some("d".repeat(5000)),
+            fields: (0..30)
+                .map(|i| EmbedFieldSpec { name: format!("f{}", i), value: "v".repeat(1100), inline: false })
+                .collect(),
+            ..Default::default()
+        };
+        let ClampedEmbed { title, description, fields, .. } = spec.clamped();
+
+        assert_eq!(title.unwrap().chars().count(), EMBED_TITLE_MAX);
+        assert_eq!(description.as_ref().unwrap().chars().count(), EMBED_DESCRIPTION_MAX);
+        assert!(fields.len() < EMBED_FIELDS_MAX);
+        assert!(fields.iter().all(|(_, v, _)| v.chars().count() == EMBED_FIELD_VALUE_MAX));
+        let total = EMBED_TITLE_MAX
+            + EMBED_DESCRIPTION_MAX
+            + fields.iter().map(|(n, v, _)| n.chars().count() + v.chars().count()).sum::<usize>();
+        assert!(total <= EMBED_TOTAL_MAX);
+    }
+}
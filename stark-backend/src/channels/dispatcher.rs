@@ -1,9 +1,13 @@
 use crate::ai::{
     multi_agent::{types::{AgentSubtype, AgentMode}, Orchestrator, ProcessResult as OrchestratorResult, SubAgentManager},
-    AiClient, ArchetypeId, ArchetypeRegistry, AiResponse, Message, MessageRole, ModelArchetype,
-    ThinkingLevel, ToolHistoryEntry, ToolResponse,
+    structured_output, AiClient, ArchetypeId, ArchetypeRegistry, AiResponse, Message, MessageRole,
+    ModelArchetype, ThinkingLevel, ToolCall, ToolHistoryEntry, ToolResponse,
 };
-use crate::channels::types::{DispatchResult, NormalizedMessage};
+use crate::channels::language;
+use crate::channels::prompt_template::PromptVariables;
+use crate::channels::response_pipeline::{ResponsePipeline, ResponseTransformer, StripMarkers};
+use crate::channels::tool_log::ToolCallLog;
+use crate::channels::types::{DispatchResult, EditOutcome, NormalizedMessage};
 use crate::config::MemoryConfig;
 use crate::context::{self, estimate_tokens, ContextManager};
 use crate::controllers::api_keys::ApiKeyId;
@@ -12,11 +16,14 @@ use crate::db::Database;
 use crate::execution::ExecutionTracker;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
+use crate::hooks::{HookContext, HookEvent, HookResult};
 use crate::models::session_message::MessageRole as DbMessageRole;
-use crate::models::{AgentSettings, CompletionStatus, SessionScope, DEFAULT_MAX_TOOL_ITERATIONS};
+use crate::models::{AgentSettings, CompletionStatus, SessionScope, ToolOutputVerbosity, DEFAULT_MAX_TOOL_ITERATIONS};
 use crate::qmd_memory::MemoryStore;
-use crate::tools::{ToolConfig, ToolContext, ToolDefinition, ToolExecution, ToolRegistry};
+use crate::tools::redaction;
+use crate::tools::{SecretRedactor, ToolConfig, ToolContext, ToolDefinition, ToolExecution, ToolRegistry};
 use chrono::Utc;
+use futures_util::future::{join_all, BoxFuture};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde_json::Value;
@@ -39,6 +46,13 @@ const FALLBACK_MAX_TOOL_ITERATIONS: usize = DEFAULT_MAX_TOOL_ITERATIONS as usize
 /// How often to broadcast "still waiting" events during long AI calls
 const AI_PROGRESS_INTERVAL_SECS: u64 = 30;
 
+/// Channel types that aren't subject to per-identity quotas: the admin's
+/// dashboard and the bot's own scheduled work
+const QUOTA_EXEMPT_CHANNEL_TYPES: &[&str] = &["web", "cron", "heartbeat", "limit_order"];
+
+/// How long after sending a message an edit to it can still re-run it
+const EDIT_REDISPATCH_WINDOW_MINS: i64 = 10;
+
 /// Result of attempting to advance to the next task in the queue
 enum TaskAdvanceResult {
     /// Started working on the next task
@@ -210,13 +224,240 @@ impl MessageDispatcher {
         self.memory_store.clone()
     }
 
+    /// Get the TxQueueManager (if available)
+    pub fn tx_queue(&self) -> Option<Arc<crate::tx_queue::TxQueueManager>> {
+        self.tx_queue.clone()
+    }
+
+    /// Get the ToolRegistry
+    pub fn tool_registry(&self) -> Arc<ToolRegistry> {
+        self.tool_registry.clone()
+    }
+
+    /// The reply to send if the identity has used up one of its daily
+    /// quotas, or `None` if it may continue
+    fn check_identity_quota(&self, identity_id: &str) -> Option<String> {
+        let quota = match self.db.get_effective_identity_quota(identity_id) {
+            Ok(quota) => quota,
+            Err(e) => {
+                log::warn!("[DISPATCH] Failed to load quota for identity {}: {}", identity_id, e);
+                return None;
+            }
+        };
+        let usage = match self.db.get_identity_usage_today(identity_id) {
+            Ok(usage) => usage,
+            Err(e) => {
+                log::warn!("[DISPATCH] Failed to load usage for identity {}: {}", identity_id, e);
+                return None;
+            }
+        };
+        quota.exceeded(&usage)
+    }
+
+    /// The language to reply to this message in, if not English: the message's
+    /// own language when it can be detected (stored on the session), else the
+    /// language the session was already using, else the channel's default
+    fn resolve_reply_language(
+        &self,
+        message: &NormalizedMessage,
+        session_id: i64,
+        clean_text: Option<&str>,
+    ) -> Option<String> {
+        let stored = self.db.get_session_language(session_id).ok().flatten();
+
+        let name = match language::detect(clean_text.unwrap_or(&message.text)) {
+            Some(detected) => {
+                if stored.as_deref() != Some(detected.code) {
+                    log::info!("[DISPATCH] Session {} language: {}", session_id, detected.name);
+                    if let Err(e) = self.db.set_session_language(session_id, detected.code) {
+                        log::warn!("[DISPATCH] Failed to store language for session {}: {}", session_id, e);
+                    }
+                }
+                detected.name.to_string()
+            }
+            None => match stored.as_deref().and_then(language::by_code) {
+                Some(previous) => previous.name.to_string(),
+                None => {
+                    let default = self
+                        .db
+                        .get_channel_setting(
+                            message.channel_id,
+                            crate::models::ChannelSettingKey::DefaultLanguage.as_ref(),
+                        )
+                        .ok()
+                        .flatten()
+                        .filter(|v| !v.trim().is_empty())?;
+                    // Names the detector doesn't know are passed to the model as written
+                    language::resolve(&default)
+                        .map(|l| l.name.to_string())
+                        .unwrap_or_else(|| default.trim().to_string())
+                }
+            },
+        };
+
+        (name != language::ENGLISH.name).then_some(name)
+    }
+
+    /// How much of the tool-call log a channel's replies start with
+    fn tool_log_verbosity(&self, channel_id: i64) -> ToolOutputVerbosity {
+        self.db
+            .get_channel_setting(channel_id, crate::models::ChannelSettingKey::ToolLog.as_ref())
+            .ok()
+            .flatten()
+            .map(|v| ToolOutputVerbosity::from_str_or_default(v.trim()))
+            .unwrap_or_default()
+    }
+
+    /// Most history messages to load for a channel's prompts
+    fn history_message_limit(&self, channel_id: i64) -> i32 {
+        self.db
+            .get_channel_setting(channel_id, crate::models::ChannelSettingKey::HistoryMessages.as_ref())
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<i32>().ok())
+            .filter(|limit| *limit > 0)
+            .unwrap_or(context::DEFAULT_HISTORY_MESSAGES)
+    }
+
+    /// The post-processing pipeline configured for the message's channel
+    fn response_pipeline(&self, message: &NormalizedMessage) -> ResponsePipeline {
+        let setting = |key: crate::models::ChannelSettingKey| {
+            self.db
+                .get_channel_setting(message.channel_id, key.as_ref())
+                .ok()
+                .flatten()
+                .filter(|v| !v.trim().is_empty())
+        };
+
+        match setting(crate::models::ChannelSettingKey::ResponsePipeline) {
+            Some(spec) => ResponsePipeline::from_setting(
+                &spec,
+                &message.channel_type,
+                setting(crate::models::ChannelSettingKey::ResponseDisclaimer).as_deref(),
+            ),
+            None => ResponsePipeline::new(),
+        }
+    }
+
+    /// Count an x402 payment towards the quota of the identity that caused it
+    fn record_x402_usage(&self, tool_context: &ToolContext, payment: &crate::x402::X402PaymentInfo) {
+        let Some(identity_id) = tool_context.quota_identity() else {
+            return;
+        };
+        let Some(amount) = payment.micro_units() else {
+            log::warn!("[DISPATCH] Unparseable x402 amount '{}' not counted towards quota", payment.amount);
+            return;
+        };
+        if let Err(e) = self.db.record_identity_usage(identity_id, 0, 0, amount) {
+            log::warn!("[DISPATCH] Failed to record x402 usage for identity {}: {}", identity_id, e);
+        }
+    }
+
+    /// Apply a user's edit of a platform message to the session history.
+    /// Returns `Redispatch` when the channel re-runs edits, the message is the
+    /// latest on the channel, it was sent recently, and the edit changes what it
+    /// asks; the old message is then hidden, since dispatching the new text
+    /// stores it again.
+    pub fn apply_message_edit(
+        &self,
+        channel_type: &str,
+        channel_id: i64,
+        user_id: &str,
+        platform_message_id: &str,
+        new_text: &str,
+    ) -> EditOutcome {
+        let stored = match self.db.find_message_by_platform_id(channel_type, channel_id, platform_message_id, Some(user_id)) {
+            Ok(Some(stored)) => stored,
+            Ok(None) => return EditOutcome::Ignored,
+            Err(e) => {
+                log::warn!("[DISPATCH] Failed to look up edited {} message {}: {}", channel_type, platform_message_id, e);
+                return EditOutcome::Ignored;
+            }
+        };
+        // Keep the hints the listener prepended when the message was first dispatched
+        let (hints, original) = split_source_hints(&stored.content);
+        if original.trim() == new_text.trim() {
+            return EditOutcome::Ignored;
+        }
+
+        let redispatch = self
+            .db
+            .get_channel_setting(channel_id, crate::models::ChannelSettingKey::RedispatchEdits.as_ref())
+            .ok()
+            .flatten()
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("true"))
+            && Utc::now() - stored.created_at < chrono::Duration::minutes(EDIT_REDISPATCH_WINDOW_MINS)
+            && self
+                .db
+                .get_latest_session_for_channel(channel_type, channel_id)
+                .ok()
+                .flatten()
+                .is_some_and(|s| s.id == stored.session_id)
+            && self.db.get_latest_user_message_id(stored.session_id).ok().flatten() == Some(stored.id)
+            && edit_changes_intent(original, new_text);
+
+        let result = if redispatch {
+            self.db.soft_delete_session_message(stored.id)
+        } else {
+            self.db.update_session_message_content(stored.id, &format!("{}{}", hints, new_text))
+        };
+        if let Err(e) = result {
+            log::error!("[DISPATCH] Failed to apply edit to message {}: {}", stored.id, e);
+            return EditOutcome::Ignored;
+        }
+
+        log::info!(
+            "[DISPATCH] {} message {} edited in session {}{}",
+            channel_type,
+            platform_message_id,
+            stored.session_id,
+            if redispatch { ", re-dispatching" } else { "" }
+        );
+        if redispatch {
+            EditOutcome::Redispatch
+        } else {
+            EditOutcome::Updated
+        }
+    }
+
+    /// Hide a message the user deleted on their platform from session history.
+    /// Returns whether a stored message was found.
+    pub fn apply_message_delete(&self, channel_type: &str, channel_id: i64, platform_message_id: &str) -> bool {
+        let stored = match self.db.find_message_by_platform_id(channel_type, channel_id, platform_message_id, None) {
+            Ok(Some(stored)) => stored,
+            Ok(None) => return false,
+            Err(e) => {
+                log::warn!("[DISPATCH] Failed to look up deleted {} message {}: {}", channel_type, platform_message_id, e);
+                return false;
+            }
+        };
+
+        match self.db.soft_delete_session_message(stored.id) {
+            Ok(deleted) => {
+                log::info!(
+                    "[DISPATCH] {} message {} deleted from session {}",
+                    channel_type,
+                    platform_message_id,
+                    stored.session_id
+                );
+                deleted
+            }
+            Err(e) => {
+                log::error!("[DISPATCH] Failed to delete message {}: {}", stored.id, e);
+                false
+            }
+        }
+    }
+
     /// Get the SubAgentManager (if available)
     pub fn subagent_manager(&self) -> Option<Arc<SubAgentManager>> {
         self.subagent_manager.clone()
     }
 
     /// Dispatch a normalized message to the AI and return the response
-    pub async fn dispatch(&self, message: NormalizedMessage) -> DispatchResult {
+    pub async fn dispatch(&self, mut message: NormalizedMessage) -> DispatchResult {
+        crate::metrics::record_dispatch(&message.channel_type, message.channel_id);
+
         // Emit message received event
         self.broadcaster.broadcast(GatewayEvent::channel_message(
             message.channel_id,
@@ -225,6 +466,11 @@ impl MessageDispatcher {
             &message.text,
         ));
 
+        // Let hooks filter or rewrite the message before anything else sees it
+        if let Some(result) = self.run_message_received_hooks(&mut message).await {
+            return result;
+        }
+
         // Check for reset commands
         let text_lower = message.text.trim().to_lowercase();
         if text_lower == "/new" || text_lower == "/reset" {
@@ -267,6 +513,23 @@ impl MessageDispatcher {
             }
         };
 
+        // Enforce the identity's daily quotas before doing any work
+        if !QUOTA_EXEMPT_CHANNEL_TYPES.contains(&message.channel_type.as_str()) {
+            if let Some(reply) = self.check_identity_quota(&identity.identity_id) {
+                log::info!(
+                    "[DISPATCH] Quota reached for identity {} ({}:{})",
+                    identity.identity_id,
+                    message.channel_type,
+                    message.user_id
+                );
+                self.execution_tracker.complete_execution(message.channel_id);
+                return DispatchResult::success(reply);
+            }
+            if let Err(e) = self.db.record_identity_usage(&identity.identity_id, 1, 0, 0) {
+                log::warn!("[DISPATCH] Failed to record usage for identity {}: {}", identity.identity_id, e);
+            }
+        }
+
         // Determine session scope based on session_mode (for cron) or chat context
         let scope = if let Some(ref mode) = message.session_mode {
             // Cron job with explicit session_mode
@@ -297,6 +560,12 @@ impl MessageDispatcher {
         let channel_type_lower = message.channel_type.to_lowercase();
         let is_gateway_channel = channel_type_lower == "discord" || channel_type_lower == "telegram";
 
+        // A plan awaiting approval in the previous gateway session moves to the
+        // fresh one, so the reply to it can approve or reject it
+        let mut pending_plan_context = None;
+        // The conversation's language also carries over to the fresh session
+        let mut previous_language = None;
+
         // Collect previous session messages for gateway channels (max 10)
         let previous_gateway_messages: Vec<crate::models::SessionMessage> = if is_gateway_channel {
             const MAX_PREVIOUS_MESSAGES: i32 = 10;
@@ -309,6 +578,12 @@ impl MessageDispatcher {
                 let messages = self.db.get_recent_session_messages(prev_session.id, MAX_PREVIOUS_MESSAGES)
                     .unwrap_or_default();
 
+                pending_plan_context = self.db.get_agent_context(prev_session.id)
+                    .ok()
+                    .flatten()
+                    .filter(|context| context.plan_awaiting_approval);
+                previous_language = self.db.get_session_language(prev_session.id).ok().flatten();
+
                 // Deactivate the old session
                 if let Err(e) = self.db.deactivate_session(prev_session.id) {
                     log::warn!("[DISPATCH] Failed to deactivate previous session {}: {}", prev_session.id, e);
@@ -341,6 +616,14 @@ impl MessageDispatcher {
                         "[DISPATCH] Created fresh {} session {} (previous context: {} messages)",
                         message.channel_type, s.id, previous_gateway_messages.len()
                     );
+                    if let Some(ref context) = pending_plan_context {
+                        if let Err(e) = self.db.save_agent_context(s.id, context) {
+                            log::warn!("[DISPATCH] Failed to carry pending plan to session {}: {}", s.id, e);
+                        }
+                    }
+                    if let Some(ref code) = previous_language {
+                        let _ = self.db.set_session_language(s.id, code);
+                    }
                     s
                 }
                 Err(e) => {
@@ -438,16 +721,24 @@ impl MessageDispatcher {
 
         // Infer archetype from settings
         let archetype_id = AiClient::infer_archetype(&settings);
+        let archetype_window = self.archetype_registry.get(archetype_id)
+            .unwrap_or_else(|| self.archetype_registry.default_archetype())
+            .context_window();
+        let max_context_tokens = context::resolve_context_window(
+            session.context_window_override,
+            settings.max_context_tokens,
+            archetype_window,
+        );
         log::info!(
             "Using endpoint {} for message dispatch (archetype={}, max_response={}, max_context={})",
             settings.endpoint,
             archetype_id,
             settings.max_response_tokens,
-            settings.max_context_tokens
+            max_context_tokens
         );
 
-        // Sync session's max_context_tokens with agent settings for dynamic compaction
-        self.context_manager.sync_max_context_tokens(session.id, settings.max_context_tokens);
+        // Sync session's max_context_tokens with the model's window for dynamic compaction
+        self.context_manager.sync_max_context_tokens(session.id, max_context_tokens);
 
         // Create AI client from settings with x402 wallet support
         let client = match AiClient::from_settings_with_wallet(
@@ -471,9 +762,15 @@ impl MessageDispatcher {
         self.execution_tracker.add_thinking(message.channel_id, "Processing request...");
 
         // Get tool configuration for this channel (needed for system prompt)
-        let tool_config = self.db.get_effective_tool_config(Some(message.channel_id))
+        let mut tool_config = self.db.get_effective_tool_config(Some(message.channel_id))
             .unwrap_or_default();
 
+        // Limited users (e.g. role-gated Discord commands) only get the tools they were granted
+        if let Some(ref allowed_tools) = message.allowed_tools {
+            tool_config = tool_config.restricted_to(allowed_tools);
+            log::info!("[DISPATCH] Restricting tools for this message to: {:?}", allowed_tools);
+        }
+
         // Debug: Log tool configuration
         log::info!(
             "[DISPATCH] Tool config - profile: {:?}, allowed_groups: {:?}",
@@ -481,17 +778,28 @@ impl MessageDispatcher {
             tool_config.allowed_groups
         );
 
+        // Reply in the user's language rather than the persona's English
+        let reply_language = self.resolve_reply_language(&message, session.id, clean_text.as_deref());
+
         // Build context from memories, tools, skills, and session history
-        let system_prompt = self.build_system_prompt(&message, &identity.identity_id, &tool_config);
+        let mut system_prompt = self.build_system_prompt(&message, &identity.identity_id, &tool_config);
+        if let Some(ref name) = reply_language {
+            system_prompt.push_str(&language::reply_instruction(name));
+        }
 
         // Debug: Log full system prompt
         log::debug!("[DISPATCH] System prompt:\n{}", system_prompt);
 
-        // Build context with cross-session memory integration
+        // Build context with cross-session memory integration, with history
+        // trimmed to what fits the model's window next to the system prompt
+        let history_budget = self
+            .context_manager
+            .history_budget(max_context_tokens, estimate_tokens(&system_prompt));
         let (history, context_summary) = self.context_manager.build_context_with_memories(
             session.id,
             Some(&identity.identity_id),
-            20,
+            self.history_message_limit(message.channel_id),
+            history_budget,
         );
 
         // Build messages for the AI
@@ -555,6 +863,14 @@ impl MessageDispatcher {
             }
         }
 
+        // Callers that need a machine-readable reply pass a schema for it
+        if let Some(ref schema) = message.response_schema {
+            messages.push(Message {
+                role: MessageRole::System,
+                content: structured_output::instructions(schema),
+            });
+        }
+
         // Add conversation history (skip the last one since it's the current message)
         // Also skip tool calls and results as they're not part of the AI conversation format
         for msg in history.iter().take(history.len().saturating_sub(1)) {
@@ -609,7 +925,16 @@ impl MessageDispatcher {
             .with_workspace(workspace_dir.clone())
             .with_broadcaster(self.broadcaster.clone())
             .with_database(self.db.clone())
-            .with_selected_network(message.selected_network.clone());
+            .with_selected_network(message.selected_network.clone())
+            .with_cancellation_token(self.execution_tracker.get_cancellation_token(message.channel_id));
+
+        // Tool calls count towards the sender's daily quota
+        if !QUOTA_EXEMPT_CHANNEL_TYPES.contains(&message.channel_type.as_str()) {
+            tool_context.extra.insert(
+                "quota_identity_id".to_string(),
+                serde_json::json!(identity.identity_id),
+            );
+        }
 
         // Log selected network if present
         if let Some(ref network) = message.selected_network {
@@ -679,6 +1004,11 @@ impl MessageDispatcher {
             }
         }
 
+        // Scrub the burner wallet key and API keys from tool results and logs
+        let redactor = SecretRedactor::load(&self.db);
+        redaction::install(&redactor);
+        tool_context = tool_context.with_redactor(redactor);
+
         // If GitHub token is loaded, query GitHub API to get authenticated user
         // and set GITHUB_USER env var for use in git/gh commands
         if github_token_loaded {
@@ -713,10 +1043,35 @@ impl MessageDispatcher {
                 "rogue_mode_enabled".to_string(),
                 serde_json::json!(bot_settings.rogue_mode_enabled),
             );
+
+            // Exec tool command policy
+            tool_context.extra.insert(
+                "exec_allowlist".to_string(),
+                serde_json::json!(bot_settings.exec_allowlist),
+            );
+            tool_context.extra.insert(
+                "exec_denylist".to_string(),
+                serde_json::json!(bot_settings.exec_denylist),
+            );
+
+            // Transfer caps for send_eth / web3_function_call / broadcast_web3_tx
+            tool_context.extra.insert(
+                "transfer_limits".to_string(),
+                serde_json::json!(bot_settings.transfer_limits),
+            );
         }
 
+        // Kept for re-prompting a structured reply that doesn't match its schema
+        let structured_messages = message.response_schema.as_ref().map(|_| messages.clone());
+
         // Generate response with optional tool execution loop
-        let final_response = if use_tools {
+        let final_response = if let Some(result) = self.run_before_generation_hooks(
+            message.channel_id,
+            session.id,
+            &mut messages,
+        ).await {
+            result
+        } else if use_tools {
             self.generate_with_tool_loop(
                 &client,
                 messages,
@@ -733,6 +1088,7 @@ impl MessageDispatcher {
                 Ok((content, payment)) => {
                     // Save x402 payment if one was made
                     if let Some(ref payment_info) = payment {
+                        self.record_x402_usage(&tool_context, payment_info);
                         if let Err(e) = self.db.record_x402_payment(
                             Some(message.channel_id),
                             None,
@@ -753,8 +1109,48 @@ impl MessageDispatcher {
             }
         };
 
+        // Structured requests must end with JSON matching their schema
+        let (final_response, structured) = match (final_response, message.response_schema.as_ref()) {
+            (Ok(response), Some(schema)) => {
+                let messages = structured_messages.unwrap_or_default();
+                match self.coerce_structured_response(&client, messages, response, schema).await {
+                    Ok(value) => (Ok(serde_json::to_string_pretty(&value).unwrap_or_default()), Some(value)),
+                    Err(e) => (Err(e), None),
+                }
+            }
+            (result, _) => (result, None),
+        };
+
         match final_response {
             Ok(response) => {
+                let response = match self.run_response_hooks(message.channel_id, session.id, response).await {
+                    Ok(Some(response)) => response,
+                    Ok(None) => {
+                        log::info!("[HOOKS] Response on channel {} withheld by hook", message.channel_id);
+                        self.execution_tracker.complete_execution(message.channel_id);
+                        return DispatchResult::success(String::new());
+                    }
+                    Err(reason) => {
+                        log::warn!("[HOOKS] Response on channel {} cancelled by hook: {}", message.channel_id, reason);
+                        self.broadcaster.broadcast(GatewayEvent::agent_error(
+                            message.channel_id,
+                            &reason,
+                        ));
+                        self.execution_tracker.complete_execution(message.channel_id);
+                        return DispatchResult::error(reason);
+                    }
+                };
+
+                // History keeps the reply without memory markers; the channel's
+                // other stages (formatting, disclaimer) only shape what is sent.
+                // Structured replies are JSON and go out untouched.
+                let delivered = if structured.is_some() {
+                    response.clone()
+                } else {
+                    self.response_pipeline(&message).apply(&response)
+                };
+                let response = StripMarkers.apply(&response);
+
                 // Estimate tokens for the response
                 let response_tokens = estimate_tokens(&response);
 
@@ -811,7 +1207,7 @@ impl MessageDispatcher {
                 self.broadcaster.broadcast(GatewayEvent::agent_response(
                     message.channel_id,
                     &message.user_name,
-                    &response,
+                    &delivered,
                 ));
 
                 log::info!(
@@ -824,7 +1220,10 @@ impl MessageDispatcher {
                 // Complete execution tracking
                 self.execution_tracker.complete_execution(message.channel_id);
 
-                DispatchResult::success(response)
+                match structured {
+                    Some(value) => DispatchResult::success(delivered).with_structured(value),
+                    None => DispatchResult::success(delivered),
+                }
             }
             Err(e) => {
                 let error = format!("AI generation error ({}): {}", archetype_id, e);
@@ -844,6 +1243,34 @@ impl MessageDispatcher {
         }
     }
 
+    /// Parse a reply requested in structured form. A reply that isn't valid
+    /// for the schema gets one re-prompt, in the provider's JSON mode if it has one.
+    async fn coerce_structured_response(
+        &self,
+        client: &AiClient,
+        mut messages: Vec<Message>,
+        response: String,
+        schema: &Value,
+    ) -> Result<Value, String> {
+        let error = match structured_output::parse(schema, &response) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        log::warn!("[STRUCTURED] Reply doesn't match the requested schema ({}), re-prompting once", error);
+
+        messages.push(Message {
+            role: MessageRole::Assistant,
+            content: response,
+        });
+        messages.push(Message {
+            role: MessageRole::User,
+            content: structured_output::correction_prompt(&error),
+        });
+        let retry = client.generate_json(messages).await?;
+        structured_output::parse(schema, &retry)
+            .map_err(|e| format!("Response didn't match the requested schema: {}", e))
+    }
+
     /// Generate a response with tool execution loop (supports both native and text-based tool calling)
     /// Now always runs in multi-agent mode with Explore → Plan → Perform flow
     async fn generate_with_tool_loop(
@@ -894,6 +1321,45 @@ impl MessageDispatcher {
             log::info!("[MULTI_AGENT] Selected network set to: {}", network);
         }
 
+        // Recurring tasks (opted-in cron jobs) start from what earlier runs found
+        if let Some(ref signature) = original_message.task_signature {
+            if orchestrator.context().task_signature.is_none() {
+                let prior = self
+                    .db
+                    .find_prior_findings(
+                        signature,
+                        session_id,
+                        crate::ai::multi_agent::findings::MAX_SEEDED_FINDINGS,
+                    )
+                    .unwrap_or_else(|e| {
+                        log::warn!("[MULTI_AGENT] Failed to load prior findings: {}", e);
+                        Vec::new()
+                    });
+                orchestrator.seed_prior_findings(signature.clone(), prior);
+            }
+        }
+
+        // Channels can require the user to approve a plan before it runs. A plan
+        // left waiting by the previous turn is approved or rejected by this message.
+        orchestrator.context_mut().plan_approval_required = self
+            .db
+            .get_channel_setting(
+                original_message.channel_id,
+                crate::models::ChannelSettingKey::PlanApproval.as_ref(),
+            )
+            .ok()
+            .flatten()
+            .map(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes" | "on"))
+            .unwrap_or(false);
+        if orchestrator.context().plan_awaiting_approval {
+            let approved = orchestrator.resolve_plan_approval(&original_message.text);
+            log::info!(
+                "[MULTI_AGENT] Plan {} for session {}",
+                if approved { "approved" } else { "rejected" },
+                session_id
+            );
+        }
+
         // Keyword-based skill activation: detect "tip" commands and pre-activate discord_tipping skill
         // This helps the AI use the correct skill without needing to search for it
         let message_lower = original_message.text.to_lowercase();
@@ -1002,6 +1468,7 @@ impl MessageDispatcher {
             let (content, payment) = client.generate_text_with_events(messages, &self.broadcaster, original_message.channel_id).await?;
             // Save x402 payment if one was made
             if let Some(ref payment_info) = payment {
+                self.record_x402_usage(tool_context, payment_info);
                 if let Err(e) = self.db.record_x402_payment(
                     Some(original_message.channel_id),
                     None,
@@ -1280,7 +1747,7 @@ impl MessageDispatcher {
 
         let mut tool_history: Vec<ToolHistoryEntry> = Vec::new();
         let mut iterations = 0;
-        let mut tool_call_log: Vec<String> = Vec::new();
+        let mut tool_call_log = ToolCallLog::default();
         let mut orchestrator_complete = false;
         let mut final_summary = String::new();
         let mut waiting_for_user_response = false;
@@ -1300,6 +1767,80 @@ impl MessageDispatcher {
                 orchestrator.current_mode()
             );
 
+            // Check for a mode transition (planner out of iterations, or a `replan`).
+            // Done before picking this iteration's tools so they match the new mode.
+            if let Some(transition) = orchestrator.check_forced_transition() {
+                log::info!(
+                    "[ORCHESTRATOR] Transition: {} → {} ({})",
+                    transition.from, transition.to, transition.reason
+                );
+
+                // Emit a task for the mode transition
+                if let Some(ref exec_id) = self.execution_tracker.get_execution_id(original_message.channel_id) {
+                    let transition_task = self.execution_tracker.start_task(
+                        original_message.channel_id,
+                        exec_id,
+                        Some(exec_id),
+                        crate::models::TaskType::PlanMode,
+                        format!("Switching to {} mode", transition.to.label()),
+                        Some(&format!("Transitioning: {}", transition.reason)),
+                    );
+                    self.execution_tracker.complete_task(&transition_task);
+                }
+
+                self.broadcaster.broadcast(GatewayEvent::agent_mode_change(
+                    original_message.channel_id,
+                    Some(&original_message.chat_id),
+                    &transition.to.to_string(),
+                    transition.to.label(),
+                    Some(&transition.reason),
+                ));
+                self.broadcast_task_queue_update(original_message.channel_id, session_id, orchestrator);
+
+                // Update tools for new mode (using current subtype)
+                let subtype = orchestrator.current_subtype();
+                tools = self
+                    .tool_registry
+                    .get_tool_definitions_for_subtype(tool_config, subtype);
+                if let Some(skill_tool) = self.create_skill_tool_definition_for_subtype(subtype) {
+                    tools.push(skill_tool);
+                }
+                tools.extend(orchestrator.get_mode_tools());
+
+                // Emit task for toolset update
+                if let Some(ref exec_id) = self.execution_tracker.get_execution_id(original_message.channel_id) {
+                    let toolset_task = self.execution_tracker.start_task(
+                        original_message.channel_id,
+                        exec_id,
+                        Some(exec_id),
+                        crate::models::TaskType::Loading,
+                        format!("Loading {} tools for {} mode", tools.len(), subtype.label()),
+                        Some("Configuring available tools..."),
+                    );
+                    self.execution_tracker.complete_task(&toolset_task);
+                }
+
+                // Broadcast toolset update
+                self.broadcast_toolset_update(
+                    original_message.channel_id,
+                    &transition.to.to_string(),
+                    subtype.as_str(),
+                    &tools,
+                );
+
+                // Update system prompt for new mode
+                if let Some(system_msg) = conversation.first_mut() {
+                    if system_msg.role == MessageRole::System {
+                        let orchestrator_prompt = orchestrator.get_system_prompt();
+                        system_msg.content = format!(
+                            "{}\n\n---\n\n{}",
+                            orchestrator_prompt,
+                            archetype.enhance_system_prompt(&messages[0].content, &tools)
+                        );
+                    }
+                }
+            }
+
             // === DETERMINE TOOLS FOR CURRENT MODE ===
             // In TaskPlanner mode (first iteration), use only define_tasks tool
             let current_tools = if orchestrator.current_mode() == AgentMode::TaskPlanner && !orchestrator.context().planner_completed {
@@ -1452,78 +1993,6 @@ impl MessageDispatcher {
                 }
             }
 
-            // Check for forced mode transition
-            if let Some(transition) = orchestrator.check_forced_transition() {
-                log::info!(
-                    "[ORCHESTRATOR] Forced transition: {} → {} ({})",
-                    transition.from, transition.to, transition.reason
-                );
-
-                // Emit a task for the mode transition
-                if let Some(ref exec_id) = self.execution_tracker.get_execution_id(original_message.channel_id) {
-                    let transition_task = self.execution_tracker.start_task(
-                        original_message.channel_id,
-                        exec_id,
-                        Some(exec_id),
-                        crate::models::TaskType::PlanMode,
-                        format!("Switching to {} mode", transition.to.label()),
-                        Some(&format!("Transitioning: {}", transition.reason)),
-                    );
-                    self.execution_tracker.complete_task(&transition_task);
-                }
-
-                self.broadcaster.broadcast(GatewayEvent::agent_mode_change(
-                    original_message.channel_id,
-                    Some(&original_message.chat_id),
-                    &transition.to.to_string(),
-                    transition.to.label(),
-                    Some(&transition.reason),
-                ));
-
-                // Update tools for new mode (using current subtype)
-                let subtype = orchestrator.current_subtype();
-                tools = self
-                    .tool_registry
-                    .get_tool_definitions_for_subtype(tool_config, subtype);
-                if let Some(skill_tool) = self.create_skill_tool_definition_for_subtype(subtype) {
-                    tools.push(skill_tool);
-                }
-                tools.extend(orchestrator.get_mode_tools());
-
-                // Emit task for toolset update
-                if let Some(ref exec_id) = self.execution_tracker.get_execution_id(original_message.channel_id) {
-                    let toolset_task = self.execution_tracker.start_task(
-                        original_message.channel_id,
-                        exec_id,
-                        Some(exec_id),
-                        crate::models::TaskType::Loading,
-                        format!("Loading {} tools for {} mode", tools.len(), subtype.label()),
-                        Some("Configuring available tools..."),
-                    );
-                    self.execution_tracker.complete_task(&toolset_task);
-                }
-
-                // Broadcast toolset update
-                self.broadcast_toolset_update(
-                    original_message.channel_id,
-                    &transition.to.to_string(),
-                    subtype.as_str(),
-                    &tools,
-                );
-
-                // Update system prompt for new mode
-                if let Some(system_msg) = conversation.first_mut() {
-                    if system_msg.role == MessageRole::System {
-                        let orchestrator_prompt = orchestrator.get_system_prompt();
-                        system_msg.content = format!(
-                            "{}\n\n---\n\n{}",
-                            orchestrator_prompt,
-                            archetype.enhance_system_prompt(&messages[0].content, &tools)
-                        );
-                    }
-                }
-            }
-
             // Generate with native tool support and progress notifications
             let ai_response = match self.generate_with_progress(
                 &client,
@@ -1566,7 +2035,7 @@ impl MessageDispatcher {
                     if !tool_call_log.is_empty() {
                         let summary = format!(
                             "[Session interrupted by error. Work completed before failure:]\n{}\n\nError: {}",
-                            tool_call_log.join("\n"),
+                            tool_call_log.full(),
                             error_str
                         );
                         log::info!("[ORCHESTRATED_LOOP] Saving error summary with {} tool calls", tool_call_log.len());
@@ -1602,6 +2071,7 @@ impl MessageDispatcher {
                     &payment_info.pay_to,
                     payment_info.resource.as_deref(),
                 ));
+                self.record_x402_usage(tool_context, payment_info);
                 let _ = self.db.record_x402_payment(
                     Some(original_message.channel_id),
                     None,
@@ -1652,22 +2122,13 @@ impl MessageDispatcher {
                     continue;
                 }
 
+                let tool_log_verbosity = self.tool_log_verbosity(original_message.channel_id);
                 if orchestrator_complete {
-                    let response = if tool_call_log.is_empty() {
-                        format!("{}\n\n{}", final_summary, ai_response.content)
-                    } else {
-                        let tool_log_text = tool_call_log.join("\n");
-                        format!("{}\n\n{}\n\n{}", tool_log_text, final_summary, ai_response.content)
-                    };
-                    return Ok(response);
+                    let response = format!("{}\n\n{}", final_summary, ai_response.content);
+                    return Ok(tool_call_log.prepend_to(tool_log_verbosity, &response));
                 } else {
                     // No tool calls but not complete - return content as-is
-                    if tool_call_log.is_empty() {
-                        return Ok(ai_response.content);
-                    } else {
-                        let tool_log_text = tool_call_log.join("\n");
-                        return Ok(format!("{}\n\n{}", tool_log_text, ai_response.content));
-                    }
+                    return Ok(tool_call_log.prepend_to(tool_log_verbosity, &ai_response.content));
                 }
             }
 
@@ -1733,7 +2194,26 @@ impl MessageDispatcher {
                 recent_call_signatures.drain(0..recent_call_signatures.len() - SIGNATURE_HISTORY_SIZE);
             }
 
-            for call in &ai_response.tool_calls {
+            // Results of read-only calls run ahead concurrently, by call index
+            let mut prefetched: std::collections::HashMap<usize, crate::tools::ToolResult> =
+                std::collections::HashMap::new();
+
+            for (index, call) in ai_response.tool_calls.iter().enumerate() {
+                // Run this call and the read-only calls right after it concurrently.
+                // Mutating calls stay sequential, so a read never moves past a write.
+                let subtype_selected = orchestrator.current_subtype().is_selected();
+                if !prefetched.contains_key(&index) && self.can_run_concurrently(call, subtype_selected) {
+                    let run: Vec<&ToolCall> = ai_response.tool_calls[index..]
+                        .iter()
+                        .take_while(|c| self.can_run_concurrently(c, subtype_selected))
+                        .collect();
+                    if run.len() > 1 {
+                        log::info!("[TOOL_CALL] Running {} read-only tool calls concurrently", run.len());
+                        let results = self.execute_concurrently(&run, tool_context, tool_config).await;
+                        prefetched.extend(results.into_iter().enumerate().map(|(i, r)| (index + i, r)));
+                    }
+                }
+
                 let args_pretty = serde_json::to_string_pretty(&call.arguments)
                     .unwrap_or_else(|_| call.arguments.to_string());
 
@@ -1743,11 +2223,7 @@ impl MessageDispatcher {
                     args_pretty
                 );
 
-                tool_call_log.push(format!(
-                    "🔧 **Tool Call:** `{}`\n```json\n{}\n```",
-                    call.name,
-                    args_pretty
-                ));
+                tool_call_log.push(&call.name, &args_pretty);
 
                 self.broadcaster.broadcast(GatewayEvent::agent_tool_call(
                     original_message.channel_id,
@@ -1787,6 +2263,13 @@ impl MessageDispatcher {
                             format!("Execution complete: {}", summary),
                         ));
                     }
+                    OrchestratorResult::AwaitingApproval(plan) => {
+                        log::info!("[ORCHESTRATED_LOOP] Plan defined, waiting for user approval");
+                        self.broadcast_task_queue_update(original_message.channel_id, session_id, orchestrator);
+                        waiting_for_user_response = true;
+                        user_question_content = plan.clone();
+                        tool_responses.push(ToolResponse::success(call.id.clone(), plan));
+                    }
                     OrchestratorResult::ToolResult(result) => {
                         tool_responses.push(ToolResponse::success(call.id.clone(), result));
                     }
@@ -1868,6 +2351,12 @@ impl MessageDispatcher {
                                     • set_agent_subtype(subtype=\"secretary\") - for social/messaging",
                                     call.name
                                 ))
+                            } else if let Some(tool_result) = prefetched.remove(&index) {
+                                // Already validated and executed with its concurrent batch
+                                if tool_result.success {
+                                    orchestrator.record_tool_call(&call.name);
+                                }
+                                tool_result
                             } else {
                                 // Run tool validators before execution
                                 if let Some(ref validator_registry) = self.validator_registry {
@@ -1877,8 +2366,8 @@ impl MessageDispatcher {
                                         Arc::new(tool_context.clone()),
                                     );
                                     let validation_result = validator_registry.validate(&validation_ctx).await;
-                                    if let Some(error_msg) = validation_result.to_error_message() {
-                                        crate::tools::ToolResult::error(error_msg)
+                                    if let Some(blocked) = validation_result.to_tool_result() {
+                                        blocked
                                     } else {
                                         // Execute regular tool and record the call for skill tracking
                                         let tool_result = self.tool_registry
@@ -1941,7 +2430,7 @@ impl MessageDispatcher {
                         }
 
                         // Handle retry backoff
-                        let result = if let Some(retry_secs) = result.retry_after_secs {
+                        let mut result = if let Some(retry_secs) = result.retry_after_secs {
                             self.broadcaster.broadcast(GatewayEvent::tool_waiting(
                                 original_message.channel_id,
                                 &call.name,
@@ -1973,6 +2462,7 @@ impl MessageDispatcher {
                                     .to_string();
 
                                 log::info!("[ORCHESTRATED_LOOP] task_fully_completed called");
+                                orchestrator.record_finding(&summary);
 
                                 // Mark current task as completed and broadcast (if task queue exists)
                                 if let Some(completed_task_id) = orchestrator.complete_current_task() {
@@ -1998,6 +2488,16 @@ impl MessageDispatcher {
                             }
                         }
 
+                        // Execute AfterToolCall hooks (metrics, annotations, etc.) before the
+                        // result is broadcast, so clients see the content the model sees
+                        self.run_after_tool_hooks(
+                            original_message.channel_id,
+                            session_id,
+                            &call.name,
+                            &call.arguments,
+                            &mut result,
+                        ).await;
+
                         // Extract duration_ms from metadata if available
                         let duration_ms = result.metadata.as_ref()
                             .and_then(|m| m.get("duration_ms"))
@@ -2013,22 +2513,6 @@ impl MessageDispatcher {
                             &result.content,
                         ));
 
-                        // Execute AfterToolCall hooks (for auto-memory, etc.)
-                        if let Some(hook_manager) = &self.hook_manager {
-                            use crate::hooks::{HookContext, HookEvent, HookResult};
-                            let mut hook_context = HookContext::new(HookEvent::AfterToolCall)
-                                .with_channel(original_message.channel_id, Some(session_id))
-                                .with_tool(call.name.clone(), call.arguments.clone())
-                                .with_tool_result(serde_json::json!({
-                                    "success": result.success,
-                                    "content": result.content,
-                                }));
-                            let hook_result = hook_manager.execute(HookEvent::AfterToolCall, &mut hook_context).await;
-                            if let HookResult::Error(e) = hook_result {
-                                log::warn!("Hook execution failed for tool '{}': {}", call.name, e);
-                            }
-                        }
-
                         // Save tool result to session
                         let tool_result_content = format!(
                             "**{}:** {}\n{}",
@@ -2093,7 +2577,7 @@ impl MessageDispatcher {
         if was_cancelled && !tool_call_log.is_empty() {
             let summary = format!(
                 "[Session stopped by user. Work completed before stop:]\n{}",
-                tool_call_log.join("\n")
+                tool_call_log.full()
             );
             log::info!("[ORCHESTRATED_LOOP] Saving cancellation summary with {} tool calls", tool_call_log.len());
             if let Err(e) = self.db.add_session_message(
@@ -2116,7 +2600,7 @@ impl MessageDispatcher {
             if !tool_call_log.is_empty() {
                 let context_summary = format!(
                     "Before asking the user, I already completed these actions:\n{}",
-                    tool_call_log.join("\n")
+                    tool_call_log.full()
                 );
                 orchestrator.context_mut().waiting_for_user_context = Some(context_summary);
                 // Re-save context with the waiting_for_user_context
@@ -2137,7 +2621,7 @@ impl MessageDispatcher {
             // Max iterations with work done - save summary so context is preserved
             let summary = format!(
                 "[Session hit max iterations. Work completed before limit:]\n{}",
-                tool_call_log.join("\n")
+                tool_call_log.full()
             );
             log::info!("[ORCHESTRATED_LOOP] Saving max-iterations summary with {} tool calls", tool_call_log.len());
             let _ = self.db.add_session_message(
@@ -2192,7 +2676,7 @@ impl MessageDispatcher {
 
         let mut final_response = String::new();
         let mut iterations = 0;
-        let mut tool_call_log: Vec<String> = Vec::new();
+        let mut tool_call_log = ToolCallLog::default();
         let mut orchestrator_complete = false;
         let mut waiting_for_user_response = false;
         let mut user_question_content = String::new();
@@ -2244,6 +2728,7 @@ impl MessageDispatcher {
                     transition.to.label(),
                     Some(&transition.reason),
                 ));
+                self.broadcast_task_queue_update(original_message.channel_id, session_id, orchestrator);
 
                 // Update tools (using current subtype)
                 let subtype = orchestrator.current_subtype();
@@ -2287,7 +2772,7 @@ impl MessageDispatcher {
                     if !tool_call_log.is_empty() {
                         let summary = format!(
                             "[Session interrupted by error. Work completed before failure:]\n{}\n\nError: {}",
-                            tool_call_log.join("\n"),
+                            tool_call_log.full(),
                             e
                         );
                         log::info!("[TEXT_ORCHESTRATED] Saving error summary with {} tool calls", tool_call_log.len());
@@ -2308,6 +2793,7 @@ impl MessageDispatcher {
             };
 
             if let Some(ref payment_info) = payment {
+                self.record_x402_usage(tool_context, payment_info);
                 let _ = self.db.record_x402_payment(
                     Some(original_message.channel_id),
                     None,
@@ -2370,11 +2856,7 @@ impl MessageDispatcher {
                         let args_pretty = serde_json::to_string_pretty(&tool_call.tool_params)
                             .unwrap_or_else(|_| tool_call.tool_params.to_string());
 
-                        tool_call_log.push(format!(
-                            "🔧 **Tool Call:** `{}`\n```json\n{}\n```",
-                            tool_call.tool_name,
-                            args_pretty
-                        ));
+                        tool_call_log.push(&tool_call.tool_name, &args_pretty);
 
                         self.broadcaster.broadcast(GatewayEvent::agent_tool_call(
                             original_message.channel_id,
@@ -2413,11 +2895,18 @@ impl MessageDispatcher {
                                 final_response = summary.clone();
                                 format!("Execution complete: {}", summary)
                             }
+                            OrchestratorResult::AwaitingApproval(plan) => {
+                                log::info!("[TEXT_ORCHESTRATED] Plan defined, waiting for user approval");
+                                self.broadcast_task_queue_update(original_message.channel_id, session_id, orchestrator);
+                                waiting_for_user_response = true;
+                                user_question_content = plan.clone();
+                                plan
+                            }
                             OrchestratorResult::ToolResult(result) => result,
                             OrchestratorResult::Error(err) => format!("Error: {}", err),
                             OrchestratorResult::Continue => {
                                 // Execute regular tool
-                                let result = if tool_call.tool_name == "use_skill" {
+                                let mut result = if tool_call.tool_name == "use_skill" {
                                     // Execute skill and set active skill on orchestrator
                                     let skill_result = self.execute_skill_tool(&tool_call.tool_params, Some(session_id)).await;
 
@@ -2492,8 +2981,8 @@ impl MessageDispatcher {
                                                 Arc::new(tool_context.clone()),
                                             );
                                             let validation_result = validator_registry.validate(&validation_ctx).await;
-                                            if let Some(error_msg) = validation_result.to_error_message() {
-                                                crate::tools::ToolResult::error(error_msg)
+                                            if let Some(blocked) = validation_result.to_tool_result() {
+                                                blocked
                                             } else {
                                                 // Execute regular tool and record the call for skill tracking
                                                 let tool_result = self.tool_registry.execute(
@@ -2576,10 +3065,21 @@ impl MessageDispatcher {
                                         } else {
                                             final_response = result.content.clone();
                                         }
+                                        orchestrator.record_finding(&final_response);
                                         log::info!("[TEXT_ORCHESTRATED] Task fully completed signal received");
                                     }
                                 }
 
+                                // Execute AfterToolCall hooks (metrics, annotations, etc.) before the
+                                // result is broadcast, so clients see the content the model sees
+                                self.run_after_tool_hooks(
+                                    original_message.channel_id,
+                                    session_id,
+                                    &tool_call.tool_name,
+                                    &tool_call.tool_params,
+                                    &mut result,
+                                ).await;
+
                                 // Extract duration_ms from metadata if available
                                 let duration_ms = result.metadata.as_ref()
                                     .and_then(|m| m.get("duration_ms"))
@@ -2595,22 +3095,6 @@ impl MessageDispatcher {
                                     &result.content,
                                 ));
 
-                                // Execute AfterToolCall hooks (for auto-memory, etc.)
-                                if let Some(hook_manager) = &self.hook_manager {
-                                    use crate::hooks::{HookContext, HookEvent, HookResult};
-                                    let mut hook_context = HookContext::new(HookEvent::AfterToolCall)
-                                        .with_channel(original_message.channel_id, Some(session_id))
-                                        .with_tool(tool_call.tool_name.clone(), tool_call.tool_params.clone())
-                                        .with_tool_result(serde_json::json!({
-                                            "success": result.success,
-                                            "content": result.content,
-                                        }));
-                                    let hook_result = hook_manager.execute(HookEvent::AfterToolCall, &mut hook_context).await;
-                                    if let HookResult::Error(e) = hook_result {
-                                        log::warn!("Hook execution failed for tool '{}': {}", tool_call.tool_name, e);
-                                    }
-                                }
-
                                 // Save tool result to session
                                 let tool_result_msg = format!(
                                     "**{}:** {}\n{}",
@@ -2707,12 +3191,10 @@ impl MessageDispatcher {
                             continue;
                         }
 
-                        if tool_call_log.is_empty() {
-                            final_response = agent_response.body;
-                        } else {
-                            let tool_log_text = tool_call_log.join("\n");
-                            final_response = format!("{}\n\n{}", tool_log_text, agent_response.body);
-                        }
+                        final_response = tool_call_log.prepend_to(
+                            self.tool_log_verbosity(original_message.channel_id),
+                            &agent_response.body,
+                        );
                         break;
                     }
                 }
@@ -2725,12 +3207,10 @@ impl MessageDispatcher {
                         &format!("Parse failed, raw AI response:\n{}", &ai_content[..ai_content.len().min(500)]),
                     ));
 
-                    if tool_call_log.is_empty() {
-                        final_response = ai_content;
-                    } else {
-                        let tool_log_text = tool_call_log.join("\n");
-                        final_response = format!("{}\n\n{}", tool_log_text, ai_content);
-                    }
+                    final_response = tool_call_log.prepend_to(
+                        self.tool_log_verbosity(original_message.channel_id),
+                        &ai_content,
+                    );
                     break;
                 }
             }
@@ -2745,7 +3225,7 @@ impl MessageDispatcher {
         if was_cancelled && !tool_call_log.is_empty() {
             let summary = format!(
                 "[Session stopped by user. Work completed before stop:]\n{}",
-                tool_call_log.join("\n")
+                tool_call_log.full()
             );
             log::info!("[TEXT_ORCHESTRATED] Saving cancellation summary with {} tool calls", tool_call_log.len());
             if let Err(e) = self.db.add_session_message(
@@ -2767,7 +3247,7 @@ impl MessageDispatcher {
             if !tool_call_log.is_empty() {
                 let context_summary = format!(
                     "Before asking the user, I already completed these actions:\n{}",
-                    tool_call_log.join("\n")
+                    tool_call_log.full()
                 );
                 orchestrator.context_mut().waiting_for_user_context = Some(context_summary);
                 // Re-save context with the waiting_for_user_context
@@ -2783,7 +3263,7 @@ impl MessageDispatcher {
             if !tool_call_log.is_empty() {
                 let summary = format!(
                     "[Session ended with empty response. Work completed:]\n{}",
-                    tool_call_log.join("\n")
+                    tool_call_log.full()
                 );
                 log::info!("[TEXT_ORCHESTRATED] Saving empty-response summary with {} tool calls", tool_call_log.len());
                 let _ = self.db.add_session_message(
@@ -2802,6 +3282,54 @@ impl MessageDispatcher {
         Ok(final_response)
     }
 
+    /// Whether a tool call can run concurrently with its neighbours: a
+    /// read-only registry tool, with a toolbox already selected
+    fn can_run_concurrently(&self, call: &ToolCall, subtype_selected: bool) -> bool {
+        subtype_selected
+            && call.name != "use_skill"
+            && call.name != "set_agent_subtype"
+            && self.tool_registry.get(&call.name).is_some_and(|tool| !tool.is_mutating())
+    }
+
+    /// Validate and execute read-only tool calls concurrently, at most
+    /// `tool_concurrency()` at a time so paid and rate-limited APIs aren't
+    /// flooded. Results are returned in the calls' order.
+    async fn execute_concurrently(
+        &self,
+        calls: &[&ToolCall],
+        tool_context: &ToolContext,
+        tool_config: &ToolConfig,
+    ) -> Vec<crate::tools::ToolResult> {
+        // Futures are built eagerly and boxed as Send: a lazily mapped stream
+        // over borrowed calls makes dispatch()'s future non-Send
+        let limit = Arc::new(tokio::sync::Semaphore::new(crate::config::tool_concurrency().max(1)));
+        let futures: Vec<BoxFuture<'_, crate::tools::ToolResult>> = calls
+            .iter()
+            .map(|&call| {
+                let limit = Arc::clone(&limit);
+                let future: BoxFuture<'_, crate::tools::ToolResult> = Box::pin(async move {
+                    let _permit = limit.acquire().await;
+                    if let Some(ref validator_registry) = self.validator_registry {
+                        let validation_ctx = crate::tool_validators::ValidationContext::new(
+                            call.name.clone(),
+                            call.arguments.clone(),
+                            Arc::new(tool_context.clone()),
+                        );
+                        if let Some(blocked) = validator_registry.validate(&validation_ctx).await.to_tool_result() {
+                            return blocked;
+                        }
+                    }
+                    self.tool_registry
+                        .execute(&call.name, call.arguments.clone(), tool_context, Some(tool_config))
+                        .await
+                });
+                future
+            })
+            .collect();
+
+        join_all(futures).await
+    }
+
     /// Execute the special "use_skill" tool
     /// If session_id is provided, saves the active skill to the agent context for persistence
     async fn execute_skill_tool(&self, params: &Value, session_id: Option<i64>) -> crate::tools::ToolResult {
@@ -2914,6 +3442,50 @@ impl MessageDispatcher {
         None
     }
 
+    /// Load the persona assigned to a channel, if any
+    fn load_persona(&self, channel_id: i64) -> Option<String> {
+        match self.db.get_channel_persona(channel_id) {
+            Ok(Some(persona)) => {
+                log::debug!("[SOUL] Using persona '{}' for channel {}", persona.name, channel_id);
+                Some(persona.content)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                log::warn!("[SOUL] Failed to load persona for channel {}: {}", channel_id, e);
+                None
+            }
+        }
+    }
+
+    /// Variables available to persona / SOUL.md templates
+    fn prompt_variables(&self, message: &NormalizedMessage, tool_config: &ToolConfig) -> PromptVariables {
+        // The channel's configured wallet, else the primary one
+        let wallet_name = self
+            .db
+            .get_channel_setting(message.channel_id, crate::models::ChannelSettingKey::Wallet.as_ref())
+            .ok()
+            .flatten()
+            .filter(|name| !name.trim().is_empty());
+        let wallet_address = crate::wallet::cached_address(wallet_name.as_deref().map(str::trim))
+            .map(|address| format!("{:?}", address))
+            .unwrap_or_default();
+
+        let mut enabled_tools: Vec<String> = self
+            .tool_registry
+            .get_allowed_tools(tool_config)
+            .iter()
+            .map(|tool| tool.name())
+            .collect();
+        enabled_tools.sort();
+
+        PromptVariables::new()
+            .set("user_name", message.user_name.clone())
+            .set("channel_type", message.channel_type.clone())
+            .set("current_date", Utc::now().format("%Y-%m-%d").to_string())
+            .set("wallet_address", wallet_address)
+            .set("enabled_tools", enabled_tools.join(", "))
+    }
+
     /// Load GUIDELINES.md content if it exists
     fn load_guidelines() -> Option<String> {
         // Primary location: soul directory from config
@@ -2948,13 +3520,16 @@ impl MessageDispatcher {
         &self,
         message: &NormalizedMessage,
         identity_id: &str,
-        _tool_config: &ToolConfig,
+        tool_config: &ToolConfig,
     ) -> String {
         let mut prompt = String::new();
 
-        // Load SOUL.md if available, otherwise use default intro
-        if let Some(soul) = Self::load_soul() {
-            prompt.push_str(&soul);
+        // Use the channel's persona, then SOUL.md, then the default intro
+        if let Some(persona) = self.load_persona(message.channel_id) {
+            prompt.push_str(&self.prompt_variables(message, tool_config).render(&persona));
+            prompt.push_str("\n\n");
+        } else if let Some(soul) = Self::load_soul() {
+            prompt.push_str(&self.prompt_variables(message, tool_config).render(&soul));
             prompt.push_str("\n\n");
         } else {
             prompt.push_str("You are StarkBot, an AI agent who can respond to users and operate tools.\n\n");
@@ -3033,10 +3608,140 @@ impl MessageDispatcher {
             "## Current Request\nUser: {} | Channel: {}\n",
             message.user_name, message.channel_type
         ));
+        if let Some(ref reply_to) = message.reply_to {
+            prompt.push_str(&reply_to.prompt_line());
+        }
 
         prompt
     }
 
+    /// Run `on_message_received` hooks. Hooks may rewrite the message text,
+    /// answer it directly, or drop it before a session is touched.
+    async fn run_message_received_hooks(&self, message: &mut NormalizedMessage) -> Option<DispatchResult> {
+        let hook_manager = self.hook_manager.as_ref()?;
+        let mut hook_context = HookContext::new(HookEvent::OnMessageReceived)
+            .with_channel(message.channel_id, None)
+            .with_message(message.text.clone())
+            .with_extra(serde_json::json!({
+                "channel_type": message.channel_type,
+                "user_id": message.user_id,
+                "user_name": message.user_name,
+            }));
+
+        match hook_manager.execute(HookEvent::OnMessageReceived, &mut hook_context).await {
+            HookResult::Skip => {
+                log::info!("[HOOKS] Message on channel {} dropped by hook", message.channel_id);
+                return Some(DispatchResult::success(String::new()));
+            }
+            HookResult::Cancel(reason) => return Some(DispatchResult::error(reason)),
+            HookResult::Replace(Value::String(reply)) => return Some(DispatchResult::success(reply)),
+            HookResult::Error(e) => log::warn!("[HOOKS] on_message_received hook failed: {}", e),
+            _ => {}
+        }
+
+        if let Some(text) = hook_context.message {
+            message.text = text;
+        }
+        None
+    }
+
+    /// Run `before_ai_generation` hooks against the outgoing prompt. Hooks may
+    /// rewrite the current user message or answer in place of the model.
+    async fn run_before_generation_hooks(
+        &self,
+        channel_id: i64,
+        session_id: i64,
+        messages: &mut [Message],
+    ) -> Option<Result<String, String>> {
+        let hook_manager = self.hook_manager.as_ref()?;
+        let user_message = messages.iter_mut().rev().find(|m| m.role == MessageRole::User)?;
+        let mut hook_context = HookContext::new(HookEvent::BeforeAiGeneration)
+            .with_channel(channel_id, Some(session_id))
+            .with_message(user_message.content.clone());
+
+        match hook_manager.execute(HookEvent::BeforeAiGeneration, &mut hook_context).await {
+            HookResult::Skip => return Some(Err("Generation skipped by hook".to_string())),
+            HookResult::Cancel(reason) => return Some(Err(reason)),
+            HookResult::Replace(Value::String(reply)) => return Some(Ok(reply)),
+            HookResult::Error(e) => log::warn!("[HOOKS] before_ai_generation hook failed: {}", e),
+            _ => {}
+        }
+
+        if let Some(text) = hook_context.message {
+            user_message.content = text;
+        }
+        None
+    }
+
+    /// Run `after_tool_execution` hooks. Hooks may annotate or rewrite the
+    /// tool output before it is saved and handed back to the model.
+    async fn run_after_tool_hooks(
+        &self,
+        channel_id: i64,
+        session_id: i64,
+        tool_name: &str,
+        tool_args: &Value,
+        result: &mut crate::tools::ToolResult,
+    ) {
+        let Some(hook_manager) = &self.hook_manager else {
+            return;
+        };
+        let mut hook_context = HookContext::new(HookEvent::AfterToolCall)
+            .with_channel(channel_id, Some(session_id))
+            .with_tool(tool_name.to_string(), tool_args.clone())
+            .with_tool_result(serde_json::json!({
+                "success": result.success,
+                "content": result.content,
+            }));
+
+        match hook_manager.execute(HookEvent::AfterToolCall, &mut hook_context).await {
+            HookResult::Replace(Value::String(content)) => {
+                result.content = content;
+                return;
+            }
+            HookResult::Error(e) => {
+                log::warn!("Hook execution failed for tool '{}': {}", tool_name, e);
+            }
+            _ => {}
+        }
+
+        if let Some(content) = hook_context.tool_result
+            .as_ref()
+            .and_then(|r| r.get("content"))
+            .and_then(|c| c.as_str())
+        {
+            if content != result.content {
+                result.content = content.to_string();
+            }
+        }
+    }
+
+    /// Run `on_response` hooks on the final response. Returns `Ok(None)` when
+    /// a hook withholds the response and `Err` when one cancels it.
+    async fn run_response_hooks(
+        &self,
+        channel_id: i64,
+        session_id: i64,
+        response: String,
+    ) -> Result<Option<String>, String> {
+        let Some(hook_manager) = &self.hook_manager else {
+            return Ok(Some(response));
+        };
+        let mut hook_context = HookContext::new(HookEvent::BeforeResponse)
+            .with_channel(channel_id, Some(session_id))
+            .with_response(response);
+
+        match hook_manager.execute(HookEvent::BeforeResponse, &mut hook_context).await {
+            HookResult::Skip => return Ok(None),
+            HookResult::Cancel(reason) => return Err(reason),
+            HookResult::Replace(Value::String(reply)) => return Ok(Some(reply)),
+            HookResult::Error(e) => log::warn!("[HOOKS] on_response hook failed: {}", e),
+            _ => {}
+        }
+
+        Ok(hook_context.response)
+    }
+
     /// Handle thinking directive messages (e.g., "/think:medium" sets session default)
     async fn handle_thinking_directive(&self, message: &NormalizedMessage) -> Option<DispatchResult> {
         let text = message.text.trim();
@@ -3305,29 +4010,7 @@ impl MessageDispatcher {
                 ).ok().map(|id| id.identity_id);
 
                 // Save session memory before reset (session memory hook)
-                let message_count = self.db.count_session_messages(session.id).unwrap_or(0);
-                if message_count >= 2 {
-                    // Only save if there are meaningful messages
-                    if let Ok(Some(settings)) = self.db.get_active_agent_settings() {
-                        if let Ok(client) = AiClient::from_settings(&settings) {
-                            match context::save_session_memory(
-                                &self.db,
-                                &client,
-                                session.id,
-                                identity_id.as_deref(),
-                                15, // Save last 15 messages
-                                self.memory_store.as_ref(),
-                            ).await {
-                                Ok(()) => {
-                                    log::info!("[SESSION_MEMORY] Saved session memory before reset");
-                                }
-                                Err(e) => {
-                                    log::warn!("[SESSION_MEMORY] Failed to save session memory: {}", e);
-                                }
-                            }
-                        }
-                    }
-                }
+                self.save_memory_before_reset(session.id, identity_id.as_deref()).await;
 
                 // Reset the session
                 match self.db.reset_chat_session(session.id) {
@@ -3355,6 +4038,100 @@ impl MessageDispatcher {
 
     /// Query GitHub API to get the authenticated user's login name
     /// Uses `gh api user` command which respects the GH_TOKEN env var
+    /// Save a session's recent messages to memory ahead of a reset.
+    /// Sessions with fewer than two messages have nothing worth keeping.
+    async fn save_memory_before_reset(&self, session_id: i64, identity_id: Option<&str>) {
+        let message_count = self.db.count_session_messages(session_id).unwrap_or(0);
+        if message_count < 2 {
+            return;
+        }
+        if let Ok(Some(settings)) = self.db.get_active_agent_settings() {
+            if let Ok(client) = AiClient::from_settings(&settings) {
+                match context::save_session_memory(
+                    &self.db,
+                    &client,
+                    session_id,
+                    identity_id,
+                    15, // Save last 15 messages
+                    self.memory_store.as_ref(),
+                ).await {
+                    Ok(()) => {
+                        log::info!("[SESSION_MEMORY] Saved session memory before reset");
+                    }
+                    Err(e) => {
+                        log::warn!("[SESSION_MEMORY] Failed to save session memory: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reset every active session whose daily or idle reset policy is due,
+    /// saving session memory first like `/reset` does. Sessions on a channel
+    /// with a run in flight are left for the next pass. Returns how many
+    /// sessions were reset.
+    pub async fn apply_session_reset_policies(&self) -> usize {
+        let sessions = match self.db.list_sessions_with_reset_policy() {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                log::error!("[SESSION_RESET] Failed to list sessions: {}", e);
+                return 0;
+            }
+        };
+
+        let now = Utc::now();
+        let tz = crate::config::session_reset_timezone();
+        let mut reset = 0;
+
+        for session in sessions.into_iter().filter(|s| s.reset_due(now, &tz)) {
+            if self.execution_tracker.get_execution_id(session.channel_id).is_some() {
+                continue;
+            }
+            // Nothing to reset: the next message simply continues the session
+            if self.db.count_session_messages(session.id).unwrap_or(0) == 0 {
+                continue;
+            }
+
+            // DM sessions belong to one user; group memories stay unattributed
+            let identity_id = if session.scope == SessionScope::Dm {
+                self.db
+                    .get_identity_by_platform(&session.channel_type, &session.platform_chat_id)
+                    .ok()
+                    .flatten()
+                    .map(|identity| identity.identity_id)
+            } else {
+                None
+            };
+
+            self.save_memory_before_reset(session.id, identity_id.as_deref()).await;
+
+            match self.db.reset_chat_session(session.id) {
+                Ok(new_session) => {
+                    let reason = session.reset_policy.as_str();
+                    log::info!(
+                        "[SESSION_RESET] Session {} ({}) reset by {} policy, new session {}",
+                        session.id,
+                        session.session_key,
+                        reason,
+                        new_session.id
+                    );
+                    self.broadcaster.broadcast(GatewayEvent::session_auto_reset(
+                        session.channel_id,
+                        session.id,
+                        new_session.id,
+                        reason,
+                    ));
+                    reset += 1;
+                }
+                Err(e) => {
+                    log::error!("[SESSION_RESET] Failed to reset session {}: {}", session.id, e);
+                }
+            }
+        }
+
+        reset
+    }
+
     async fn get_github_authenticated_user(&self) -> Result<String, String> {
         use tokio::process::Command;
 
@@ -3385,10 +4162,81 @@ impl MessageDispatcher {
     }
 }
 
+/// Split the bracketed hint paragraphs a listener prepends to a message (e.g.
+/// the Discord skill hint) from the text the user wrote
+fn split_source_hints(content: &str) -> (&str, &str) {
+    let mut body_start = 0;
+    while let Some(end) = content[body_start..].find("\n\n") {
+        let paragraph = &content[body_start..body_start + end];
+        if !(paragraph.starts_with('[') && paragraph.ends_with(']')) {
+            break;
+        }
+        body_start += end + 2;
+    }
+    content.split_at(body_start)
+}
+
+/// Whether an edit changes what a message asks rather than fixing its wording:
+/// any changed number or address counts, otherwise most of the words must differ.
+/// Mentions are ignored, since listeners don't always keep them.
+fn edit_changes_intent(before: &str, after: &str) -> bool {
+    fn words(text: &str) -> std::collections::HashSet<String> {
+        text.split_whitespace()
+            .filter(|w| !w.starts_with("<@"))
+            .flat_map(|w| w.split(|c: char| !(c.is_alphanumeric() || c == '.')))
+            .map(|w| w.trim_matches('.').to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect()
+    }
+
+    let (before, after) = (words(before), words(after));
+    let numbers = |set: &std::collections::HashSet<String>| {
+        set.iter()
+            .filter(|w| w.chars().any(|c| c.is_ascii_digit()))
+            .cloned()
+            .collect::<std::collections::HashSet<_>>()
+    };
+    if numbers(&before) != numbers(&after) {
+        return true;
+    }
+
+    let union = before.union(&after).count();
+    let shared = before.intersection(&after).count();
+    union > 0 && (shared as f64) < 0.5 * union as f64
+}
+
+
+/// Compile-time check that dispatch() can be spawned onto the runtime
+#[allow(dead_code)]
+fn assert_dispatch_is_send(dispatcher: &MessageDispatcher, message: NormalizedMessage) {
+    fn assert_send<T: Send>(_: T) {}
+    assert_send(dispatcher.dispatch(message));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_edit_changes_intent() {
+        // Typo fixes keep the intent
+        assert!(!edit_changes_intent("send 5 USDC to alcie", "send 5 USDC to alice"));
+        assert!(!edit_changes_intent("whats the price of eth", "what's the price of eth?"));
+        // Amounts, addresses and new requests change it
+        assert!(edit_changes_intent("send 5 USDC to alice", "send 50 USDC to alice"));
+        assert!(edit_changes_intent("swap 0.1 ETH to USDC", "swap 0.2 ETH to USDC"));
+        assert!(edit_changes_intent("what's the price of eth", "show my wallet balance"));
+        // Mentions don't count
+        assert!(!edit_changes_intent("<@123> price of eth?", "price of eth"));
+    }
+
+    #[test]
+    fn test_split_source_hints() {
+        let stored = "[DISCORD MESSAGE - use the discord skill]\n\ntip @bob 5 USDC";
+        assert_eq!(split_source_hints(stored), ("[DISCORD MESSAGE - use the discord skill]\n\n", "tip @bob 5 USDC"));
+        assert_eq!(split_source_hints("[not a hint] tip\n\nmore"), ("", "[not a hint] tip\n\nmore"));
+    }
+
     #[test]
     fn test_thinking_directive_pattern() {
         // Test the thinking directive pattern
@@ -0,0 +1,145 @@
+//! Inbound flood protection for channel listeners
+//!
+//! Every message a gateway listener forwards costs a dispatch (and an AI
+//! call), and a burst from one user also thrashes the session lock. Each
+//! listener keeps a `FloodGuard` and checks it before `dispatcher.dispatch`:
+//! a user may send `STARK_FLOOD_BURST` messages per `STARK_FLOOD_WINDOW_SECS`
+//! sliding window, and messages beyond that are dropped. The first dropped
+//! message gets a cooldown notice; the rest are dropped silently.
+
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Tracked users before stale entries are pruned
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// What a listener should do with an inbound message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FloodDecision {
+    /// Dispatch the message
+    Allow,
+    /// Drop the message, replying with the notice if there is one
+    Drop { notice: Option<String> },
+}
+
+#[derive(Debug, Default)]
+struct FloodState {
+    /// Arrival times of the messages let through in the current window
+    recent: VecDeque<Instant>,
+    /// Whether this user has been told to slow down since they were last allowed
+    notified: bool,
+}
+
+/// Per-user sliding-window message limit for one channel
+#[derive(Debug)]
+pub struct FloodGuard {
+    window: Duration,
+    burst: usize,
+    users: DashMap<String, FloodState>,
+}
+
+impl FloodGuard {
+    /// Guard using `STARK_FLOOD_WINDOW_SECS` and `STARK_FLOOD_BURST`
+    pub fn from_config() -> Self {
+        Self::new(
+            Duration::from_secs(crate::config::flood_window_secs()),
+            crate::config::flood_burst(),
+        )
+    }
+
+    /// Guard allowing `burst` messages per user per `window`. A burst of 0
+    /// disables the guard.
+    pub fn new(window: Duration, burst: usize) -> Self {
+        Self {
+            window,
+            burst,
+            users: DashMap::new(),
+        }
+    }
+
+    /// Record an inbound message from `user_id` and decide whether to dispatch it
+    pub fn check(&self, user_id: &str) -> FloodDecision {
+        self.check_at(user_id, Instant::now())
+    }
+
+    fn check_at(&self, user_id: &str, now: Instant) -> FloodDecision {
+        if self.burst == 0 || self.window.is_zero() {
+            return FloodDecision::Allow;
+        }
+
+        if self.users.len() > PRUNE_THRESHOLD {
+            self.users.retain(|_, state| {
+                state.recent.back().is_some_and(|t| now.duration_since(*t) < self.window)
+            });
+        }
+
+        let mut state = self.users.entry(user_id.to_string()).or_default();
+        while state.recent.front().is_some_and(|t| now.duration_since(*t) >= self.window) {
+            state.recent.pop_front();
+        }
+
+        if state.recent.len() < self.burst {
+            state.recent.push_back(now);
+            state.notified = false;
+            return FloodDecision::Allow;
+        }
+
+        if state.notified {
+            return FloodDecision::Drop { notice: None };
+        }
+        state.notified = true;
+
+        // The oldest message in the window is the next to expire
+        let wait = state
+            .recent
+            .front()
+            .map(|t| self.window.saturating_sub(now.duration_since(*t)))
+            .unwrap_or(self.window);
+        FloodDecision::Drop {
+            notice: Some(format!(
+                "You're sending messages too quickly. Please wait {}s before sending another.",
+                wait.as_secs().max(1)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_cooldown_then_recovery() {
+        let guard = FloodGuard::new(Duration::from_secs(10), 2);
+        let start = Instant::now();
+
+        assert_eq!(guard.check_at("alice", start), FloodDecision::Allow);
+        assert_eq!(guard.check_at("alice", start + Duration::from_secs(1)), FloodDecision::Allow);
+
+        // Third message in the window: dropped with a notice, then silently
+        match guard.check_at("alice", start + Duration::from_secs(2)) {
+            FloodDecision::Drop { notice: Some(notice) } => assert!(notice.contains("wait 8s")),
+            other => panic!("expected a cooldown notice, got {:?}", other),
+        }
+        assert_eq!(
+            guard.check_at("alice", start + Duration::from_secs(3)),
+            FloodDecision::Drop { notice: None }
+        );
+
+        // Other users are unaffected
+        assert_eq!(guard.check_at("bob", start + Duration::from_secs(3)), FloodDecision::Allow);
+
+        // Once the first message leaves the window, one more is allowed
+        assert_eq!(guard.check_at("alice", start + Duration::from_secs(10)), FloodDecision::Allow);
+    }
+
+    #[test]
+    fn test_zero_burst_disables_guard() {
+        let guard = FloodGuard::new(Duration::from_secs(10), 0);
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(guard.check_at("alice", now), FloodDecision::Allow);
+        }
+    }
+}
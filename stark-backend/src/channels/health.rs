@@ -0,0 +1,205 @@
+//! Health tracking for channel listeners
+//!
+//! A listener can die or go deaf without anyone noticing: a revoked token
+//! ends it with an error, and a dropped connection can leave it running but
+//! unable to reach the platform. The scheduler's channel watchdog probes each
+//! supervised channel's platform API every poll and records the last success
+//! here. A channel whose listener has exited, or whose probes have failed for
+//! `STARK_CHANNEL_STALL_SECS`, is restarted with exponential backoff. After
+//! `STARK_CHANNEL_MAX_RESTARTS` failed restarts in a row it is disabled.
+//!
+//! Only channels started through `ChannelManager` are supervised, and an
+//! explicit stop forgets the channel, so the watchdog never revives a channel
+//! a user turned off.
+
+use dashmap::DashMap;
+use std::time::{Duration, Instant};
+
+/// Delay before the first restart of a failed channel
+const RESTART_BACKOFF_BASE: Duration = Duration::from_secs(30);
+
+/// Longest delay between restarts
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30 * 60);
+
+/// Delay before restart attempt `failures` (1-based), doubling from
+/// `RESTART_BACKOFF_BASE` up to `RESTART_BACKOFF_MAX`
+pub fn restart_backoff(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(16);
+    RESTART_BACKOFF_BASE
+        .saturating_mul(1 << doublings)
+        .min(RESTART_BACKOFF_MAX)
+}
+
+#[derive(Debug, Clone)]
+struct ChannelHealth {
+    /// Last successful probe (or the start of the current run)
+    last_ok: Instant,
+    /// Times the channel has died or stalled since it was last healthy
+    failures: u32,
+    /// Earliest time the next restart may be attempted
+    retry_at: Option<Instant>,
+}
+
+/// What the watchdog should do with a supervised channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthAction {
+    /// Healthy, or still waiting out a backoff
+    None,
+    /// Restart the listener
+    Restart,
+}
+
+/// Health of every supervised channel
+#[derive(Debug, Default)]
+pub struct ChannelHealthTracker {
+    channels: DashMap<i64, ChannelHealth>,
+}
+
+impl ChannelHealthTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Channel IDs currently supervised by the watchdog
+    pub fn supervised(&self) -> Vec<i64> {
+        self.channels.iter().map(|e| *e.key()).collect()
+    }
+
+    /// A listener was (re)started. Its stall timer starts now, but failures
+    /// and any pending backoff carry over until a probe succeeds, so a
+    /// channel that keeps dying still backs off and hits the restart limit.
+    pub fn started(&self, channel_id: i64) {
+        self.started_at(channel_id, Instant::now());
+    }
+
+    fn started_at(&self, channel_id: i64, now: Instant) {
+        self.channels
+            .entry(channel_id)
+            .and_modify(|health| health.last_ok = now)
+            .or_insert(ChannelHealth {
+                last_ok: now,
+                failures: 0,
+                retry_at: None,
+            });
+    }
+
+    /// Stop supervising a channel, dropping its failure count
+    pub fn forget(&self, channel_id: i64) {
+        self.channels.remove(&channel_id);
+    }
+
+    /// A probe of the channel's platform succeeded
+    pub fn record_ok(&self, channel_id: i64) {
+        self.record_ok_at(channel_id, Instant::now());
+    }
+
+    fn record_ok_at(&self, channel_id: i64, now: Instant) {
+        if let Some(mut health) = self.channels.get_mut(&channel_id) {
+            health.last_ok = now;
+            health.failures = 0;
+            health.retry_at = None;
+        }
+    }
+
+    /// The channel died or stalled and is about to be restarted. Returns the
+    /// number of failures since it was last healthy, and holds off the next
+    /// restart for the backoff.
+    pub fn record_failure(&self, channel_id: i64) -> u32 {
+        self.record_failure_at(channel_id, Instant::now())
+    }
+
+    fn record_failure_at(&self, channel_id: i64, now: Instant) -> u32 {
+        let mut health = self.channels.entry(channel_id).or_insert(ChannelHealth {
+            last_ok: now,
+            failures: 0,
+            retry_at: None,
+        });
+        health.failures += 1;
+        health.retry_at = Some(now + restart_backoff(health.failures));
+        health.failures
+    }
+
+    /// Decide what to do with a supervised channel, given whether its
+    /// listener is still running
+    pub fn check(&self, channel_id: i64, running: bool, stall_after: Duration) -> HealthAction {
+        self.check_at(channel_id, running, stall_after, Instant::now())
+    }
+
+    fn check_at(&self, channel_id: i64, running: bool, stall_after: Duration, now: Instant) -> HealthAction {
+        let Some(health) = self.channels.get(&channel_id) else {
+            return HealthAction::None;
+        };
+
+        let stalled = now.duration_since(health.last_ok) >= stall_after;
+        if running && !stalled {
+            return HealthAction::None;
+        }
+        match health.retry_at {
+            Some(retry_at) if now < retry_at => HealthAction::None,
+            _ => HealthAction::Restart,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_backoff_doubles_and_caps() {
+        assert_eq!(restart_backoff(1), Duration::from_secs(30));
+        assert_eq!(restart_backoff(2), Duration::from_secs(60));
+        assert_eq!(restart_backoff(3), Duration::from_secs(120));
+        assert_eq!(restart_backoff(10), RESTART_BACKOFF_MAX);
+        assert_eq!(restart_backoff(u32::MAX), RESTART_BACKOFF_MAX);
+    }
+
+    #[test]
+    fn test_stall_detection_and_backoff() {
+        let tracker = ChannelHealthTracker::new();
+        let stall = Duration::from_secs(300);
+        let start = Instant::now();
+
+        // Unsupervised channels are left alone
+        assert_eq!(tracker.check_at(1, false, stall, start), HealthAction::None);
+
+        tracker.started_at(1, start);
+        assert_eq!(tracker.check_at(1, true, stall, start), HealthAction::None);
+
+        // A listener that exits is restarted straight away
+        assert_eq!(tracker.check_at(1, false, stall, start), HealthAction::Restart);
+
+        // One that runs but can't reach the platform only once it has stalled
+        tracker.record_ok_at(1, start + Duration::from_secs(60));
+        assert_eq!(
+            tracker.check_at(1, true, stall, start + Duration::from_secs(200)),
+            HealthAction::None
+        );
+        assert_eq!(
+            tracker.check_at(1, true, stall, start + Duration::from_secs(360)),
+            HealthAction::Restart
+        );
+
+        // The next failure after a restart waits out the backoff
+        let restarted_at = start + Duration::from_secs(360);
+        assert_eq!(tracker.record_failure_at(1, restarted_at), 1);
+        tracker.started_at(1, restarted_at);
+        assert_eq!(
+            tracker.check_at(1, false, stall, restarted_at + Duration::from_secs(10)),
+            HealthAction::None
+        );
+        assert_eq!(
+            tracker.check_at(1, false, stall, restarted_at + Duration::from_secs(30)),
+            HealthAction::Restart
+        );
+        assert_eq!(tracker.record_failure_at(1, restarted_at), 2);
+
+        // A successful probe clears the failures and the backoff
+        tracker.record_ok_at(1, restarted_at);
+        assert_eq!(tracker.check_at(1, true, stall, restarted_at), HealthAction::None);
+        assert_eq!(tracker.record_failure_at(1, restarted_at), 1);
+
+        tracker.forget(1);
+        assert!(tracker.supervised().is_empty());
+    }
+}
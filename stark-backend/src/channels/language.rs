@@ -0,0 +1,302 @@
+//! Reply language detection
+//!
+//! The persona and system prompt are written in English, so without help the
+//! model tends to answer in English whatever the user wrote. The dispatcher
+//! detects the language of each inbound message here, stores it on the
+//! session, and tells the model to reply in it.
+//!
+//! Detection is a lightweight heuristic: the writing system decides for
+//! non-Latin scripts, and common function words (plus a few diacritics) decide
+//! between Latin-script languages. Messages too short or mixed to call return
+//! `None`, and the session keeps the language it already had.
+
+use std::collections::HashSet;
+
+/// A language the dispatcher can detect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Language {
+    /// ISO 639-1 code, stored on the session
+    pub code: &'static str,
+    /// English name, used in the prompt
+    pub name: &'static str,
+}
+
+const fn lang(code: &'static str, name: &'static str) -> Language {
+    Language { code, name }
+}
+
+pub const ENGLISH: Language = lang("en", "English");
+
+/// Every language `detect` can return
+pub const LANGUAGES: &[Language] = &[
+    ENGLISH,
+    lang("es", "Spanish"),
+    lang("pt", "Portuguese"),
+    lang("fr", "French"),
+    lang("de", "German"),
+    lang("it", "Italian"),
+    lang("nl", "Dutch"),
+    lang("ru", "Russian"),
+    lang("uk", "Ukrainian"),
+    lang("zh", "Chinese"),
+    lang("ja", "Japanese"),
+    lang("ko", "Korean"),
+    lang("ar", "Arabic"),
+    lang("fa", "Persian"),
+    lang("he", "Hebrew"),
+    lang("el", "Greek"),
+    lang("th", "Thai"),
+    lang("hi", "Hindi"),
+];
+
+/// Common words per Latin-script language. Words shared between languages
+/// count for each of them, so they only tip the balance alongside others.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("en", &[
+        "the", "and", "is", "are", "you", "what", "how", "this", "that", "with", "for", "my", "your",
+        "can", "please", "i", "it", "of", "to", "do", "does", "have", "me", "was", "will", "would",
+        "not", "be", "in", "on", "at", "from", "hello", "hi", "thanks", "show", "send", "check", "want",
+    ]),
+    ("es", &[
+        "el", "la", "los", "las", "de", "que", "y", "es", "en", "un", "una", "por", "para", "con", "mi",
+        "tu", "cómo", "como", "qué", "hola", "gracias", "quiero", "puedes", "está", "estoy", "del",
+        "al", "se", "no", "muy", "cuál", "cuánto", "precio", "saldo", "enviar", "ayuda", "mis",
+    ]),
+    ("pt", &[
+        "o", "os", "as", "de", "que", "e", "é", "em", "um", "uma", "por", "para", "com", "meu", "minha",
+        "você", "voce", "olá", "ola", "obrigado", "obrigada", "quero", "pode", "não", "nao", "está",
+        "do", "da", "dos", "das", "na", "no", "isso", "qual", "quanto", "preço", "enviar", "ajuda",
+    ]),
+    ("fr", &[
+        "le", "la", "les", "de", "des", "du", "et", "est", "un", "une", "pour", "avec", "mon", "ma",
+        "mes", "vous", "tu", "je", "bonjour", "merci", "salut", "pas", "ne", "que", "qui", "quel",
+        "quelle", "combien", "c'est", "suis", "sur", "dans", "au", "aux", "envoyer", "aide", "prix",
+        "solde", "veux", "peux",
+    ]),
+    ("de", &[
+        "der", "die", "das", "und", "ist", "ein", "eine", "nicht", "ich", "du", "sie", "mit", "für",
+        "mein", "meine", "dein", "bitte", "danke", "hallo", "wie", "was", "auf", "zu", "den", "dem",
+        "kann", "können", "wir", "guten", "preis", "senden", "hilfe", "welche", "habe", "hast", "mir",
+    ]),
+    ("it", &[
+        "il", "lo", "la", "gli", "le", "di", "che", "e", "è", "un", "una", "per", "con", "mio", "mia",
+        "tuo", "ciao", "grazie", "voglio", "puoi", "sono", "non", "come", "quanto", "del", "della",
+        "nel", "prezzo", "inviare", "aiuto", "questo", "questa",
+    ]),
+    ("nl", &[
+        "de", "het", "een", "en", "is", "niet", "ik", "je", "jij", "mijn", "jouw", "met", "voor", "van",
+        "dat", "wat", "hoe", "hallo", "dank", "bedankt", "alsjeblieft", "graag", "kan", "kun", "wil",
+        "op", "naar", "prijs", "sturen", "hulp",
+    ]),
+];
+
+/// Characters that (almost) only occur in one Latin-script language
+const DIACRITICS: &[(char, &str)] = &[
+    ('ñ', "es"),
+    ('¿', "es"),
+    ('¡', "es"),
+    ('ã', "pt"),
+    ('õ', "pt"),
+    ('ß', "de"),
+    ('ä', "de"),
+    ('ö', "de"),
+    ('ü', "de"),
+    ('è', "fr"),
+    ('ê', "fr"),
+    ('œ', "fr"),
+    ('ù', "fr"),
+    ('ì', "it"),
+    ('ò', "it"),
+];
+
+/// Look up a language by its code
+pub fn by_code(code: &str) -> Option<Language> {
+    LANGUAGES.iter().copied().find(|l| l.code.eq_ignore_ascii_case(code.trim()))
+}
+
+/// Look up a language by code or English name (as typed into a setting)
+pub fn resolve(value: &str) -> Option<Language> {
+    let value = value.trim();
+    by_code(value).or_else(|| LANGUAGES.iter().copied().find(|l| l.name.eq_ignore_ascii_case(value)))
+}
+
+/// Prompt section telling the model which language to reply in
+pub fn reply_instruction(language_name: &str) -> String {
+    format!(
+        "\n\n## Reply Language\n\n\
+         The user is writing in {name}. Reply in {name}, even though these instructions are in English, \
+         unless the user asks for another language. Keep tool names, code, addresses and token symbols as they are.",
+        name = language_name
+    )
+}
+
+/// Detect the language of a message, or `None` if it's too short or mixed to tell
+pub fn detect(text: &str) -> Option<Language> {
+    let text = strip_noise(text);
+
+    if let Some(language) = detect_script(&text) {
+        return Some(language);
+    }
+
+    detect_latin(&text)
+}
+
+/// Drop what says nothing about the language: URLs, mentions, addresses,
+/// commands and code
+fn strip_noise(text: &str) -> String {
+    text.split("```")
+        .step_by(2)
+        .flat_map(str::split_whitespace)
+        .filter(|w| {
+            !(w.starts_with("http")
+                || w.starts_with('@')
+                || w.starts_with("<@")
+                || w.starts_with('/')
+                || w.starts_with('`')
+                || w.starts_with("0x"))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Decide by writing system when most letters aren't Latin
+fn detect_script(text: &str) -> Option<Language> {
+    let (mut latin, mut other) = (0usize, 0usize);
+    let (mut kana, mut hangul, mut han, mut cyrillic, mut arabic) = (0usize, 0usize, 0usize, 0usize, 0usize);
+    let (mut hebrew, mut greek, mut thai, mut devanagari) = (0usize, 0usize, 0usize, 0usize);
+    let (mut ukrainian, mut persian) = (false, false);
+
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        match c as u32 {
+            0x3040..=0x30FF => kana += 1,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => hangul += 1,
+            0x4E00..=0x9FFF | 0x3400..=0x4DBF => han += 1,
+            0x0400..=0x04FF => {
+                cyrillic += 1;
+                ukrainian |= matches!(c, 'і' | 'ї' | 'є' | 'ґ' | 'І' | 'Ї' | 'Є' | 'Ґ');
+            }
+            0x0600..=0x06FF => {
+                arabic += 1;
+                persian |= matches!(c, 'پ' | 'چ' | 'ژ' | 'گ' | 'ی');
+            }
+            0x0590..=0x05FF => hebrew += 1,
+            0x0370..=0x03FF => greek += 1,
+            0x0E00..=0x0E7F => thai += 1,
+            0x0900..=0x097F => devanagari += 1,
+            _ if c.is_ascii_alphabetic() || (c as u32) < 0x0250 => {
+                latin += 1;
+                continue;
+            }
+            _ => {}
+        }
+        other += 1;
+    }
+
+    // Need a couple of letters, and more of them than Latin ones (which may
+    // just be a token symbol or a name)
+    if other < 2 || other <= latin {
+        return None;
+    }
+
+    let code = if kana > 0 {
+        "ja"
+    } else if hangul > 0 {
+        "ko"
+    } else if han > 0 {
+        "zh"
+    } else if cyrillic > 0 {
+        if ukrainian { "uk" } else { "ru" }
+    } else if arabic > 0 {
+        if persian { "fa" } else { "ar" }
+    } else if hebrew > 0 {
+        "he"
+    } else if greek > 0 {
+        "el"
+    } else if thai > 0 {
+        "th"
+    } else if devanagari > 0 {
+        "hi"
+    } else {
+        return None;
+    };
+
+    by_code(code)
+}
+
+/// Score Latin-script text by common words and diacritics
+fn detect_latin(text: &str) -> Option<Language> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !(c.is_alphabetic() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    // Two-word messages ("ok thanks", "gm ser") don't say enough
+    if words.len() < 3 {
+        return None;
+    }
+
+    let mut scores: Vec<(&str, usize)> = STOPWORDS
+        .iter()
+        .map(|(code, list)| {
+            let list: HashSet<&str> = list.iter().copied().collect();
+            let hits = words.iter().filter(|w| list.contains(*w)).count();
+            let marks = lower
+                .chars()
+                .filter(|c| DIACRITICS.iter().any(|(d, l)| d == c && l == code))
+                .count();
+            (*code, hits + 2 * marks)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+
+    match scores.as_slice() {
+        [(code, best), (_, second), ..] if *best >= 2 && best > second => by_code(code),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(text: &str) -> Option<&'static str> {
+        detect(text).map(|l| l.code)
+    }
+
+    #[test]
+    fn test_detect_by_script() {
+        assert_eq!(code("ETH の価格はいくらですか"), Some("ja"));
+        assert_eq!(code("이더리움 가격 알려줘"), Some("ko"));
+        assert_eq!(code("以太坊现在多少钱"), Some("zh"));
+        assert_eq!(code("Какой у меня баланс ETH?"), Some("ru"));
+        assert_eq!(code("Привіт, який у мене баланс?"), Some("uk"));
+        assert_eq!(code("ما هو سعر الإيثريوم"), Some("ar"));
+    }
+
+    #[test]
+    fn test_detect_latin_languages() {
+        assert_eq!(code("What is the price of ETH on Base?"), Some("en"));
+        assert_eq!(code("¿Cuál es el precio de ETH en Base?"), Some("es"));
+        assert_eq!(code("Qual é o preço do ETH na Base?"), Some("pt"));
+        assert_eq!(code("Quel est le prix de l'ETH sur Base ?"), Some("fr"));
+        assert_eq!(code("Wie hoch ist der Preis von ETH auf Base?"), Some("de"));
+        assert_eq!(code("Qual è il prezzo di ETH su Base?"), Some("it"));
+        assert_eq!(code("Wat is de prijs van ETH op Base?"), Some("nl"));
+    }
+
+    #[test]
+    fn test_short_or_noisy_messages_are_ambiguous() {
+        assert_eq!(code("ok"), None);
+        assert_eq!(code("gm ser"), None);
+        assert_eq!(code("0x742d35Cc6634C0532925a3b844Bc454e4438f44e"), None);
+        assert_eq!(code("https://basescan.org/tx/0xabc ETH"), None);
+    }
+
+    #[test]
+    fn test_resolve_setting_value() {
+        assert_eq!(resolve("es").map(|l| l.name), Some("Spanish"));
+        assert_eq!(resolve(" german ").map(|l| l.code), Some("de"));
+        assert_eq!(resolve("Klingon"), None);
+    }
+}
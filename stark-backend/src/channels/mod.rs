@@ -1,45 +1,79 @@
+pub mod connection;
 pub mod discord;
 pub mod dispatcher;
+pub mod flood_guard;
+pub mod health;
+pub mod language;
+pub mod prompt_template;
+pub mod response_pipeline;
 pub mod slack;
 pub mod telegram;
+pub mod tool_log;
 pub mod types;
 
+pub use connection::{ChannelConnection, ConnectionState};
 pub use dispatcher::MessageDispatcher;
-pub use types::{ChannelHandle, ChannelType, NormalizedMessage};
+pub use types::{ChannelHandle, ChannelStatus, ChannelType, NormalizedMessage};
 
 use crate::db::Database;
 use crate::execution::ExecutionTracker;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
+use crate::hooks::HookManager;
 use crate::models::Channel;
+use crate::tool_validators::ValidatorRegistry;
 use crate::tools::ToolRegistry;
 use crate::tx_queue::TxQueueManager;
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
+use connection::{ConnectionReporter, ConnectionTracker};
+use futures_util::future::BoxFuture;
+use health::{ChannelHealthTracker, HealthAction};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::oneshot;
 
+/// How long a stopped listener gets to shut down before its task is aborted
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long the watchdog waits for a channel's health probe
+const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Source of `ChannelHandle::run_id`s
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(1);
+
 /// Manages all running channel listeners
 pub struct ChannelManager {
     db: Arc<Database>,
     broadcaster: Arc<EventBroadcaster>,
     running_channels: Arc<DashMap<i64, ChannelHandle>>,
+    health: ChannelHealthTracker,
+    connections: Arc<ConnectionTracker>,
     tool_registry: Option<Arc<ToolRegistry>>,
     execution_tracker: Arc<ExecutionTracker>,
     burner_wallet_private_key: Option<String>,
     tx_queue: Option<Arc<TxQueueManager>>,
+    hook_manager: Option<Arc<HookManager>>,
+    validator_registry: Option<Arc<ValidatorRegistry>>,
 }
 
 impl ChannelManager {
     pub fn new(db: Arc<Database>, broadcaster: Arc<EventBroadcaster>) -> Self {
         let execution_tracker = Arc::new(ExecutionTracker::new(broadcaster.clone()));
+        let connections = Arc::new(ConnectionTracker::new(broadcaster.clone()));
         Self {
             db,
             broadcaster,
             running_channels: Arc::new(DashMap::new()),
+            health: ChannelHealthTracker::new(),
+            connections,
             tool_registry: None,
             execution_tracker,
             burner_wallet_private_key: None,
             tx_queue: None,
+            hook_manager: None,
+            validator_registry: None,
         }
     }
 
@@ -58,151 +92,196 @@ impl ChannelManager {
         burner_wallet_private_key: Option<String>,
     ) -> Self {
         let execution_tracker = Arc::new(ExecutionTracker::new(broadcaster.clone()));
+        let connections = Arc::new(ConnectionTracker::new(broadcaster.clone()));
         Self {
             db,
             broadcaster,
             running_channels: Arc::new(DashMap::new()),
+            health: ChannelHealthTracker::new(),
+            connections,
             tool_registry: Some(tool_registry),
             execution_tracker,
             burner_wallet_private_key,
             tx_queue: None,
+            hook_manager: None,
+            validator_registry: None,
         }
     }
 
+    /// Execution tracker shared by every channel dispatcher
+    pub fn execution_tracker(&self) -> Arc<ExecutionTracker> {
+        self.execution_tracker.clone()
+    }
+
     /// Set the transaction queue manager for web3 transactions
     pub fn with_tx_queue(mut self, tx_queue: Arc<TxQueueManager>) -> Self {
         self.tx_queue = Some(tx_queue);
         self
     }
 
+    /// Set the hook manager run by every channel dispatcher
+    pub fn with_hook_manager(mut self, hook_manager: Arc<HookManager>) -> Self {
+        self.hook_manager = Some(hook_manager);
+        self
+    }
+
+    /// Set the tool validators run by every channel dispatcher
+    pub fn with_validator_registry(mut self, validator_registry: Arc<ValidatorRegistry>) -> Self {
+        self.validator_registry = Some(validator_registry);
+        self
+    }
+
+    /// Dispatcher for a channel listener: with tools (and wallet for x402
+    /// payment support) when a tool registry is set, plus the shared hooks,
+    /// validators and tx queue
+    fn build_dispatcher(&self) -> Arc<MessageDispatcher> {
+        let Some(ref tool_registry) = self.tool_registry else {
+            return Arc::new(MessageDispatcher::new_without_tools(
+                self.db.clone(),
+                self.broadcaster.clone(),
+            ));
+        };
+        let mut disp = MessageDispatcher::new_with_wallet(
+            self.db.clone(),
+            self.broadcaster.clone(),
+            tool_registry.clone(),
+            self.execution_tracker.clone(),
+            self.burner_wallet_private_key.clone(),
+        );
+        // Add tx_queue if available (needed for web3 transactions)
+        if let Some(ref tx_queue) = self.tx_queue {
+            disp = disp.with_tx_queue(tx_queue.clone());
+        }
+        if let Some(ref hook_manager) = self.hook_manager {
+            disp = disp.with_hook_manager(hook_manager.clone());
+        }
+        if let Some(ref validator_registry) = self.validator_registry {
+            disp = disp.with_validator_registry(validator_registry.clone());
+        }
+        Arc::new(disp)
+    }
+
     /// Check if a channel is currently running
     pub fn is_running(&self, channel_id: i64) -> bool {
         self.running_channels.contains_key(&channel_id)
     }
 
+    /// Latest connection status of a channel, if it has been started since boot
+    pub fn connection(&self, channel_id: i64) -> Option<ChannelConnection> {
+        self.connections.get(channel_id)
+    }
+
     /// Get list of running channel IDs
     pub fn running_channel_ids(&self) -> Vec<i64> {
         self.running_channels.iter().map(|e| *e.key()).collect()
     }
 
-    /// Start a channel listener
-    pub async fn start_channel(&self, channel: Channel) -> Result<(), String> {
+    /// Start a channel listener and put it under watchdog supervision.
+    /// Starting a channel that is already running is a no-op reported as
+    /// `AlreadyRunning`.
+    pub async fn start_channel(&self, channel: Channel) -> Result<ChannelStatus, String> {
+        let channel_id = channel.id;
+        let status = self.launch(channel).await?;
+        if status == ChannelStatus::Started {
+            // A deliberate start gets a clean slate
+            self.health.forget(channel_id);
+            self.health.started(channel_id);
+        }
+        Ok(status)
+    }
+
+    /// Spawn a channel's listener, unless it is already running
+    async fn launch(&self, channel: Channel) -> Result<ChannelStatus, String> {
         let channel_id = channel.id;
         let channel_type = channel.channel_type.clone();
         let channel_name = channel.name.clone();
 
-        // Check if already running
+        // Fast path; the entry below settles races between concurrent starts
         if self.is_running(channel_id) {
-            return Err(format!("Channel {} is already running", channel_id));
+            return Ok(ChannelStatus::AlreadyRunning);
         }
 
+        // Parse channel type
+        let channel_type_enum = types::ChannelType::from_str(&channel_type)
+            .ok_or_else(|| format!("Unknown channel type: {}", channel_type))?;
+
         // Create shutdown channel
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
 
-        // Create dispatcher with or without tools (and wallet for x402 payment support)
-        let dispatcher = if let Some(ref tool_registry) = self.tool_registry {
-            let mut disp = MessageDispatcher::new_with_wallet(
-                self.db.clone(),
-                self.broadcaster.clone(),
-                tool_registry.clone(),
-                self.execution_tracker.clone(),
-                self.burner_wallet_private_key.clone(),
-            );
-            // Add tx_queue if available (needed for web3 transactions)
-            if let Some(ref tx_queue) = self.tx_queue {
-                disp = disp.with_tx_queue(tx_queue.clone());
-            }
-            Arc::new(disp)
-        } else {
-            Arc::new(MessageDispatcher::new_without_tools(
-                self.db.clone(),
-                self.broadcaster.clone(),
-            ))
-        };
+        let dispatcher = self.build_dispatcher();
 
-        // Store handle
-        let handle = ChannelHandle::new(
+        let connection = ConnectionReporter::new(
+            self.connections.clone(),
             channel_id,
-            channel_type.clone(),
-            channel_name.clone(),
-            shutdown_tx,
+            &channel_type,
+            &channel_name,
         );
-        self.running_channels.insert(channel_id, handle);
 
-        // Start the appropriate listener
+        // Build the appropriate listener (it doesn't run until spawned)
         let broadcaster = self.broadcaster.clone();
-        let running_channels = self.running_channels.clone();
+        let listener: BoxFuture<'static, Result<(), String>> = match channel_type_enum {
+            types::ChannelType::Telegram => Box::pin(telegram::start_telegram_listener(
+                channel,
+                dispatcher,
+                broadcaster.clone(),
+                connection.clone(),
+                shutdown_rx,
+            )),
+            types::ChannelType::Slack => Box::pin(slack::start_slack_listener(
+                channel,
+                dispatcher,
+                broadcaster.clone(),
+                connection.clone(),
+                shutdown_rx,
+            )),
+            types::ChannelType::Discord => Box::pin(discord::start_discord_listener(
+                channel,
+                dispatcher,
+                broadcaster.clone(),
+                self.db.clone(),
+                self.tx_queue.clone(),
+                connection.clone(),
+                shutdown_rx,
+            )),
+        };
 
-        // Parse channel type
-        let channel_type_enum = match types::ChannelType::from_str(&channel_type) {
-            Some(ct) => ct,
-            None => {
-                self.running_channels.remove(&channel_id);
-                return Err(format!("Unknown channel type: {}", channel_type));
-            }
+        let entry = match self.running_channels.entry(channel_id) {
+            Entry::Occupied(_) => return Ok(ChannelStatus::AlreadyRunning),
+            Entry::Vacant(entry) => entry,
         };
 
-        match channel_type_enum {
-            types::ChannelType::Telegram => {
-                tokio::spawn(async move {
-                    let result = telegram::start_telegram_listener(
-                        channel,
-                        dispatcher,
-                        broadcaster.clone(),
-                        shutdown_rx,
-                    )
-                    .await;
-
-                    if let Err(e) = result {
-                        log::error!("Telegram listener error: {}", e);
-                        broadcaster.broadcast(GatewayEvent::channel_error(channel_id, &e));
-                    }
+        let run_id = NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed);
+        let running_channels = self.running_channels.clone();
+        let listener_type = channel_type.clone();
+        connection.connecting();
+        let task = tokio::spawn(async move {
+            let result = listener.await;
 
-                    // Remove from running channels
-                    running_channels.remove(&channel_id);
-                });
-            }
-            types::ChannelType::Slack => {
-                tokio::spawn(async move {
-                    let result = slack::start_slack_listener(
-                        channel,
-                        dispatcher,
-                        broadcaster.clone(),
-                        shutdown_rx,
-                    )
-                    .await;
-
-                    if let Err(e) = result {
-                        log::error!("Slack listener error: {}", e);
-                        broadcaster.broadcast(GatewayEvent::channel_error(channel_id, &e));
-                    }
+            // Remove from running channels, unless this run was already
+            // stopped (and maybe the channel started again since)
+            let exited = running_channels
+                .remove_if(&channel_id, |_, handle| handle.run_id == run_id)
+                .is_some();
 
-                    // Remove from running channels
-                    running_channels.remove(&channel_id);
-                });
+            match result {
+                Err(e) => {
+                    log::error!("{} listener error: {}", listener_type, e);
+                    broadcaster.broadcast(GatewayEvent::channel_error(channel_id, &e));
+                    connection.error(&e);
+                }
+                Ok(()) if exited => connection.disconnected("listener exited"),
+                Ok(()) => connection.disconnected("stopped"),
             }
-            types::ChannelType::Discord => {
-                let db = self.db.clone();
-                tokio::spawn(async move {
-                    let result = discord::start_discord_listener(
-                        channel,
-                        dispatcher,
-                        broadcaster.clone(),
-                        db,
-                        shutdown_rx,
-                    )
-                    .await;
-
-                    if let Err(e) = result {
-                        log::error!("Discord listener error: {}", e);
-                        broadcaster.broadcast(GatewayEvent::channel_error(channel_id, &e));
-                    }
+        });
 
-                    // Remove from running channels
-                    running_channels.remove(&channel_id);
-                });
-            }
-        }
+        entry.insert(ChannelHandle::new(
+            channel_id,
+            channel_type.clone(),
+            channel_name.clone(),
+            run_id,
+            shutdown_tx,
+            task,
+        ));
 
         log::info!(
             "Started {} channel listener: {} (id={})",
@@ -211,26 +290,175 @@ impl ChannelManager {
             channel_id
         );
 
-        Ok(())
+        Ok(ChannelStatus::Started)
     }
 
-    /// Stop a channel listener
-    pub async fn stop_channel(&self, channel_id: i64) -> Result<(), String> {
-        match self.running_channels.remove(&channel_id) {
-            Some((_, handle)) => {
-                log::info!(
-                    "Stopping {} channel: {} (id={})",
-                    handle.channel_type,
-                    handle.name,
-                    channel_id
+    /// Stop a channel listener, ending its watchdog supervision, and wait for
+    /// it to shut down. Stopping a channel that isn't running is a no-op
+    /// reported as `NotRunning`.
+    pub async fn stop_channel(&self, channel_id: i64) -> ChannelStatus {
+        self.health.forget(channel_id);
+        self.halt(channel_id).await
+    }
+
+    /// Signal a channel's listener to shut down and wait for it
+    async fn halt(&self, channel_id: i64) -> ChannelStatus {
+        // A listener that exits on its own removes itself, so it may be gone by now
+        let Some((_, handle)) = self.running_channels.remove(&channel_id) else {
+            return ChannelStatus::NotRunning;
+        };
+
+        log::info!(
+            "Stopping {} channel: {} (id={})",
+            handle.channel_type,
+            handle.name,
+            channel_id
+        );
+
+        // Send shutdown signal (the listener may already be exiting)
+        let _ = handle.shutdown_tx.send(());
+
+        let mut task = handle.task;
+        if tokio::time::timeout(STOP_TIMEOUT, &mut task).await.is_err() {
+            log::warn!(
+                "Channel {} didn't stop within {}s, aborting its listener",
+                channel_id,
+                STOP_TIMEOUT.as_secs()
+            );
+            task.abort();
+            self.connections.set(
+                channel_id,
+                &handle.channel_type,
+                &handle.name,
+                ConnectionState::Disconnected,
+                Some("stopped"),
+            );
+        }
+
+        ChannelStatus::Stopped
+    }
+
+    /// Stop a channel if it is running, then start it with the given config
+    pub async fn restart_channel(&self, channel: Channel) -> Result<ChannelStatus, String> {
+        self.halt(channel.id).await;
+        self.start_channel(channel).await
+    }
+
+    /// Probe every supervised channel and restart those whose listener has
+    /// exited or stalled, backing off between attempts. A channel that still
+    /// fails after `STARK_CHANNEL_MAX_RESTARTS` restarts is disabled, as the
+    /// startup path does. Run by the scheduler every poll.
+    pub async fn supervise_channels(&self) {
+        let stall_after = Duration::from_secs(crate::config::channel_stall_secs());
+        let max_restarts = crate::config::channel_max_restarts();
+
+        for channel_id in self.health.supervised() {
+            // A channel disabled or deleted since it started was turned off
+            // on purpose, so stop watching it
+            let channel = match self.db.get_channel(channel_id) {
+                Ok(Some(channel)) if channel.enabled => channel,
+                Ok(_) => {
+                    self.health.forget(channel_id);
+                    continue;
+                }
+                Err(e) => {
+                    log::error!("Watchdog failed to load channel {}: {}", channel_id, e);
+                    continue;
+                }
+            };
+
+            let running = self.is_running(channel_id);
+            if running {
+                match probe(&channel).await {
+                    Ok(()) => {
+                        self.health.record_ok(channel_id);
+                        // Recovered from a failed poll or probe
+                        if self.connection(channel_id).is_some_and(|c| c.state == ConnectionState::Error) {
+                            self.connections.set(
+                                channel_id,
+                                &channel.channel_type,
+                                &channel.name,
+                                ConnectionState::Connected,
+                                None,
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Health probe failed for {} channel {} (id={}): {}",
+                            channel.channel_type,
+                            channel.name,
+                            channel_id,
+                            e
+                        );
+                        self.connections.set(
+                            channel_id,
+                            &channel.channel_type,
+                            &channel.name,
+                            ConnectionState::Error,
+                            Some(&format!("Health check failed: {}", e)),
+                        );
+                    }
+                }
+            }
+
+            if self.health.check(channel_id, running, stall_after) != HealthAction::Restart {
+                continue;
+            }
+
+            let failures = self.health.record_failure(channel_id);
+            let problem = if running { "stalled" } else { "stopped unexpectedly" };
+
+            if failures > max_restarts {
+                log::error!(
+                    "{} channel {} (id={}) {} after {} restarts, disabling it",
+                    channel.channel_type,
+                    channel.name,
+                    channel_id,
+                    problem,
+                    max_restarts
                 );
+                self.health.forget(channel_id);
+                self.halt(channel_id).await;
+                if let Err(e) = self.db.set_channel_enabled(channel_id, false) {
+                    log::error!("Failed to disable channel {}: {}", channel_id, e);
+                }
+                self.broadcaster.broadcast(GatewayEvent::channel_error(
+                    channel_id,
+                    &format!(
+                        "Channel {} {} after {} restarts and was disabled",
+                        channel.name, problem, max_restarts
+                    ),
+                ));
+                continue;
+            }
 
-                // Send shutdown signal
-                let _ = handle.shutdown_tx.send(());
+            log::warn!(
+                "{} channel {} (id={}) {}, restarting (attempt {}/{})",
+                channel.channel_type,
+                channel.name,
+                channel_id,
+                problem,
+                failures,
+                max_restarts
+            );
+            self.broadcaster.broadcast(GatewayEvent::channel_error(
+                channel_id,
+                &format!(
+                    "Channel {} {}, restarting (attempt {}/{})",
+                    channel.name, problem, failures, max_restarts
+                ),
+            ));
 
-                Ok(())
+            self.halt(channel_id).await;
+            let name = channel.name.clone();
+            match self.launch(channel).await {
+                Ok(_) => self.health.started(channel_id),
+                Err(e) => {
+                    log::error!("Failed to restart channel {} (id={}): {}", name, channel_id, e);
+                    self.broadcaster.broadcast(GatewayEvent::channel_error(channel_id, &e));
+                }
             }
-            None => Err(format!("Channel {} is not running", channel_id)),
         }
     }
 
@@ -238,7 +466,86 @@ impl ChannelManager {
     pub async fn stop_all(&self) {
         let ids: Vec<i64> = self.running_channels.iter().map(|e| *e.key()).collect();
         for id in ids {
-            let _ = self.stop_channel(id).await;
+            self.stop_channel(id).await;
         }
     }
 }
+
+/// Check a channel's credentials against its platform's API
+async fn probe(channel: &Channel) -> Result<(), String> {
+    let check = async {
+        match types::ChannelType::from_str(&channel.channel_type) {
+            Some(types::ChannelType::Telegram) => telegram::probe(channel).await,
+            Some(types::ChannelType::Slack) => slack::probe(channel).await,
+            Some(types::ChannelType::Discord) => discord::probe(channel).await,
+            None => Err(format!("Unknown channel type: {}", channel.channel_type)),
+        }
+    };
+    tokio::time::timeout(PROBE_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| Err(format!("no response within {}s", PROBE_TIMEOUT.as_secs())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::{Hook, HookContext, HookEvent, HookResult};
+    use async_trait::async_trait;
+
+    /// Answers every message itself, so a dispatch that ran it never reaches the model
+    struct AnswerHook;
+
+    #[async_trait]
+    impl Hook for AnswerHook {
+        fn id(&self) -> &str {
+            "answer"
+        }
+
+        fn name(&self) -> &str {
+            "answer"
+        }
+
+        fn events(&self) -> Vec<HookEvent> {
+            vec![HookEvent::OnMessageReceived]
+        }
+
+        async fn on_message_received(&self, _context: &mut HookContext) -> HookResult {
+            HookResult::Replace(serde_json::Value::String("answered by hook".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_channel_dispatcher_runs_hooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let hook_manager = Arc::new(HookManager::new());
+        hook_manager.register(Arc::new(AnswerHook));
+        let manager = ChannelManager::new_with_tools(
+            db,
+            Arc::new(EventBroadcaster::new()),
+            Arc::new(ToolRegistry::new()),
+        )
+        .with_hook_manager(hook_manager);
+
+        let result = manager
+            .build_dispatcher()
+            .dispatch(NormalizedMessage {
+                channel_id: 1,
+                channel_type: ChannelType::Telegram.to_string(),
+                chat_id: "1".to_string(),
+                user_id: "2".to_string(),
+                user_name: "alice".to_string(),
+                text: "hello".to_string(),
+                message_id: None,
+                session_mode: None,
+                selected_network: None,
+                allowed_tools: None,
+                task_signature: None,
+                response_schema: None,
+                reply_to: None,
+            })
+            .await;
+        assert_eq!(result.response, "answered by hook");
+        assert!(result.error.is_none());
+    }
+}
@@ -0,0 +1,75 @@
+//! Variable interpolation for persona / SOUL.md content
+//!
+//! Templates reference variables as `{{name}}` (whitespace inside the braces
+//! is allowed). Unknown variables render as empty text and log a warning, so
+//! a typo in a persona never breaks the system prompt.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+static VARIABLE_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap());
+
+/// Values available to system prompt templates
+#[derive(Debug, Default, Clone)]
+pub struct PromptVariables {
+    values: HashMap<&'static str, String>,
+}
+
+impl PromptVariables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a variable, replacing any previous value
+    pub fn set(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.values.insert(name, value.into());
+        self
+    }
+
+    /// Interpolate variables into `template`
+    pub fn render(&self, template: &str) -> String {
+        VARIABLE_PATTERN
+            .replace_all(template, |caps: &Captures| {
+                let name = &caps[1];
+                match self.values.get(name) {
+                    Some(value) => value.clone(),
+                    None => {
+                        log::warn!("[PROMPT] Unknown template variable '{{{{{}}}}}', rendering as empty", name);
+                        String::new()
+                    }
+                }
+            })
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_known_variables() {
+        let vars = PromptVariables::new()
+            .set("user_name", "alice")
+            .set("channel_type", "discord");
+        assert_eq!(
+            vars.render("Hi {{user_name}} on {{ channel_type }}!"),
+            "Hi alice on discord!"
+        );
+    }
+
+    #[test]
+    fn test_render_unknown_variable_is_empty() {
+        let vars = PromptVariables::new().set("user_name", "alice");
+        assert_eq!(vars.render("[{{nope}}] {{user_name}}"), "[] alice");
+    }
+
+    #[test]
+    fn test_render_leaves_other_braces_alone() {
+        let vars = PromptVariables::new();
+        let text = "json: {\"a\": 1} and {{ not a var }}";
+        assert_eq!(vars.render(text), text);
+    }
+}
@@ -0,0 +1,392 @@
+//! Response post-processing pipeline
+//!
+//! After generation, the agent's reply passes through an ordered list of
+//! transformers before it is sent. Marker stripping always runs first; the
+//! remaining stages are chosen per channel with the `response_pipeline`
+//! setting (a comma-separated list of stage names), so Discord can get its own
+//! formatting while the web and webhook channels receive the text as written.
+//!
+//! Stages only touch prose: fenced code blocks (including Discord ```embed
+//! blocks) pass through unchanged.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Memory markers the guidelines ask the agent to use, e.g. `[REMEMBER: fact]`
+static MEMORY_MARKER_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[(?:REMEMBER_IMPORTANT|REMEMBER|DAILY_LOG|PREFERENCE|FACT|TASK):[^\]]*\]").unwrap()
+});
+static BLANK_LINES_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n[ \t]*\n(?:[ \t]*\n)+").unwrap());
+static MARKDOWN_LINK_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[([^\]\n]+)\]\((https?://[^)\s]+)\)").unwrap()
+});
+static SUPPRESSED_LINK_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"<(https?://[^>\s|]+)>").unwrap());
+static SHORTCODE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r":([a-z0-9_+\-]+):").unwrap());
+static HEADING_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^(#{1,6})[ \t]+(.+?)[ \t]*#*[ \t]*$").unwrap());
+static BOLD_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*\*([^*\n]+)\*\*|__([^_\n]+)__").unwrap());
+static STRIKE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"~~([^~\n]+)~~").unwrap());
+static INLINE_CODE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"`([^`\n]+)`").unwrap());
+
+/// Common emoji shortcodes and their Unicode characters
+const EMOJI_SHORTCODES: &[(&str, &str)] = &[
+    ("smile", "😄"),
+    ("grin", "😁"),
+    ("joy", "😂"),
+    ("wink", "😉"),
+    ("thinking", "🤔"),
+    ("sweat_smile", "😅"),
+    ("sob", "😭"),
+    ("eyes", "👀"),
+    ("wave", "👋"),
+    ("pray", "🙏"),
+    ("clap", "👏"),
+    ("muscle", "💪"),
+    ("+1", "👍"),
+    ("thumbsup", "👍"),
+    ("-1", "👎"),
+    ("thumbsdown", "👎"),
+    ("ok_hand", "👌"),
+    ("point_right", "👉"),
+    ("heart", "❤️"),
+    ("fire", "🔥"),
+    ("rocket", "🚀"),
+    ("tada", "🎉"),
+    ("sparkles", "✨"),
+    ("star", "⭐"),
+    ("zap", "⚡"),
+    ("bulb", "💡"),
+    ("moneybag", "💰"),
+    ("money_with_wings", "💸"),
+    ("gem", "💎"),
+    ("chart_with_upwards_trend", "📈"),
+    ("chart_with_downwards_trend", "📉"),
+    ("bar_chart", "📊"),
+    ("lock", "🔒"),
+    ("key", "🔑"),
+    ("link", "🔗"),
+    ("wrench", "🔧"),
+    ("hourglass", "⌛"),
+    ("warning", "⚠️"),
+    ("x", "❌"),
+    ("white_check_mark", "✅"),
+    ("heavy_check_mark", "✔️"),
+    ("question", "❓"),
+    ("exclamation", "❗"),
+    ("information_source", "ℹ️"),
+];
+
+/// One step of the response pipeline
+pub trait ResponseTransformer: Send + Sync {
+    /// Name used to list the stage in a channel's `response_pipeline` setting
+    fn name(&self) -> &'static str;
+
+    /// Transform the reply
+    fn apply(&self, text: &str) -> String;
+}
+
+/// Apply `f` to the text outside fenced code blocks
+fn map_prose(text: &str, f: impl Fn(&str) -> String) -> String {
+    text.split("```")
+        .enumerate()
+        .map(|(i, part)| if i % 2 == 0 { f(part) } else { part.to_string() })
+        .collect::<Vec<_>>()
+        .join("```")
+}
+
+/// Removes memory markers (`[REMEMBER: ...]`, `[DAILY_LOG: ...]`, ...) from
+/// the reply. Always the first stage.
+pub struct StripMarkers;
+
+impl ResponseTransformer for StripMarkers {
+    fn name(&self) -> &'static str {
+        "strip_markers"
+    }
+
+    fn apply(&self, text: &str) -> String {
+        if !MEMORY_MARKER_PATTERN.is_match(text) {
+            return text.to_string();
+        }
+        let stripped = map_prose(text, |prose| MEMORY_MARKER_PATTERN.replace_all(prose, "").into_owned());
+        BLANK_LINES_PATTERN.replace_all(&stripped, "\n\n").trim().to_string()
+    }
+}
+
+/// Rewrites markdown links as bare URLs (`text (url)`) and unwraps `<url>`,
+/// so the platform shows a link preview for them
+pub struct UnfurlLinks;
+
+impl ResponseTransformer for UnfurlLinks {
+    fn name(&self) -> &'static str {
+        "unfurl_links"
+    }
+
+    fn apply(&self, text: &str) -> String {
+        map_prose(text, |prose| {
+            let prose = MARKDOWN_LINK_PATTERN.replace_all(prose, |caps: &regex::Captures| {
+                if caps[1] == caps[2] {
+                    caps[2].to_string()
+                } else {
+                    format!("{} ({})", &caps[1], &caps[2])
+                }
+            });
+            SUPPRESSED_LINK_PATTERN.replace_all(&prose, "$1").into_owned()
+        })
+    }
+}
+
+/// Replaces `:shortcode:` emoji with their Unicode characters, for platforms
+/// that show shortcodes literally
+pub struct NormalizeEmoji;
+
+impl ResponseTransformer for NormalizeEmoji {
+    fn name(&self) -> &'static str {
+        "emoji"
+    }
+
+    fn apply(&self, text: &str) -> String {
+        map_prose(text, |prose| {
+            SHORTCODE_PATTERN
+                .replace_all(prose, |caps: &regex::Captures| {
+                    EMOJI_SHORTCODES
+                        .iter()
+                        .find(|(code, _)| *code == &caps[1])
+                        .map(|(_, emoji)| emoji.to_string())
+                        .unwrap_or_else(|| caps[0].to_string())
+                })
+                .into_owned()
+        })
+    }
+}
+
+/// Appends a fixed disclaimer (the channel's `response_disclaimer`)
+pub struct AppendDisclaimer {
+    pub disclaimer: String,
+}
+
+impl ResponseTransformer for AppendDisclaimer {
+    fn name(&self) -> &'static str {
+        "disclaimer"
+    }
+
+    fn apply(&self, text: &str) -> String {
+        let disclaimer = self.disclaimer.trim();
+        if disclaimer.is_empty() || text.trim_end().ends_with(disclaimer) {
+            return text.to_string();
+        }
+        format!("{}\n\n{}", text.trim_end(), disclaimer)
+    }
+}
+
+/// Converts markdown to what the channel's platform renders
+pub struct PlatformMarkdown {
+    pub channel_type: String,
+}
+
+impl ResponseTransformer for PlatformMarkdown {
+    fn name(&self) -> &'static str {
+        "markdown"
+    }
+
+    fn apply(&self, text: &str) -> String {
+        match self.channel_type.to_lowercase().as_str() {
+            "discord" => discord_markdown(text),
+            "slack" => map_prose(text, slack_mrkdwn),
+            "telegram" => plain_text(text),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Discord renders most markdown, but not tables or headings below `###`
+fn discord_markdown(text: &str) -> String {
+    map_prose(text, |prose| {
+        let prose = HEADING_PATTERN.replace_all(prose, |caps: &regex::Captures| {
+            if caps[1].len() > 3 {
+                format!("**{}**", &caps[2])
+            } else {
+                caps[0].to_string()
+            }
+        });
+        fence_tables(&prose)
+    })
+}
+
+/// Wrap markdown tables in code blocks so their columns stay aligned
+fn fence_tables(prose: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut table: Vec<&str> = Vec::new();
+
+    let flush = |table: &mut Vec<&str>, out: &mut Vec<String>| {
+        if table.len() >= 2 {
+            out.push(format!("```\n{}\n```", table.join("\n")));
+        } else {
+            out.extend(table.iter().map(|l| l.to_string()));
+        }
+        table.clear();
+    };
+
+    for line in prose.split('\n') {
+        let trimmed = line.trim();
+        if trimmed.len() > 1 && trimmed.starts_with('|') && trimmed.ends_with('|') {
+            table.push(line);
+        } else {
+            flush(&mut table, &mut out);
+            out.push(line.to_string());
+        }
+    }
+    flush(&mut table, &mut out);
+
+    out.join("\n")
+}
+
+/// Slack mrkdwn: single-asterisk bold, single-tilde strike, `<url|text>` links
+fn slack_mrkdwn(prose: &str) -> String {
+    let prose = HEADING_PATTERN.replace_all(prose, "*$2*");
+    let prose = BOLD_PATTERN.replace_all(&prose, |caps: &regex::Captures| {
+        format!("*{}*", caps.get(1).or_else(|| caps.get(2)).map_or("", |m| m.as_str()))
+    });
+    let prose = STRIKE_PATTERN.replace_all(&prose, "~$1~");
+    MARKDOWN_LINK_PATTERN.replace_all(&prose, "<$2|$1>").into_owned()
+}
+
+/// Telegram messages are sent without a parse mode, so markdown would show
+/// literally: drop the syntax and keep the text
+fn plain_text(text: &str) -> String {
+    text.split("```")
+        .enumerate()
+        .map(|(i, part)| {
+            if i % 2 == 1 {
+                // Keep code, minus the fence and its language tag
+                let body = part.split_once('\n').map_or(part, |(tag, rest)| {
+                    if tag.trim().chars().all(|c| c.is_alphanumeric()) { rest } else { part }
+                });
+                body.trim_matches('\n').to_string()
+            } else {
+                let prose = HEADING_PATTERN.replace_all(part, "$2");
+                let prose = BOLD_PATTERN.replace_all(&prose, |caps: &regex::Captures| {
+                    caps.get(1).or_else(|| caps.get(2)).map_or("", |m| m.as_str()).to_string()
+                });
+                let prose = STRIKE_PATTERN.replace_all(&prose, "$1");
+                let prose = INLINE_CODE_PATTERN.replace_all(&prose, "$1");
+                MARKDOWN_LINK_PATTERN.replace_all(&prose, "$1 ($2)").into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// An ordered list of transformers applied to every reply on a channel
+pub struct ResponsePipeline {
+    stages: Vec<Box<dyn ResponseTransformer>>,
+}
+
+impl Default for ResponsePipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResponsePipeline {
+    /// A pipeline with only the built-in marker stripping
+    pub fn new() -> Self {
+        Self {
+            stages: vec![Box::new(StripMarkers)],
+        }
+    }
+
+    /// Add a stage to the end of the pipeline
+    pub fn with_stage(mut self, stage: Box<dyn ResponseTransformer>) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Build a channel's pipeline from its `response_pipeline` setting.
+    /// Unknown stage names are logged and skipped.
+    pub fn from_setting(spec: &str, channel_type: &str, disclaimer: Option<&str>) -> Self {
+        let mut pipeline = Self::new();
+        for name in spec.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+            let stage: Box<dyn ResponseTransformer> = match name.as_str() {
+                // Always runs first
+                "strip_markers" => continue,
+                "unfurl_links" => Box::new(UnfurlLinks),
+                "emoji" => Box::new(NormalizeEmoji),
+                "markdown" => Box::new(PlatformMarkdown {
+                    channel_type: channel_type.to_string(),
+                }),
+                "disclaimer" => Box::new(AppendDisclaimer {
+                    disclaimer: disclaimer.unwrap_or_default().to_string(),
+                }),
+                other => {
+                    log::warn!("[RESPONSE_PIPELINE] Unknown stage '{}' ignored", other);
+                    continue;
+                }
+            };
+            pipeline = pipeline.with_stage(stage);
+        }
+        pipeline
+    }
+
+    /// Names of the stages, in order
+    pub fn stage_names(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|s| s.name()).collect()
+    }
+
+    /// Run the reply through every stage in order
+    pub fn apply(&self, text: &str) -> String {
+        self.stages
+            .iter()
+            .fold(text.to_string(), |text, stage| stage.apply(&text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markers_stripped_first() {
+        let pipeline = ResponsePipeline::new();
+        assert_eq!(pipeline.stage_names(), vec!["strip_markers"]);
+        assert_eq!(
+            pipeline.apply("Done! [REMEMBER: prefers Base]\n\n\n[DAILY_LOG: swapped ETH]\nAnything else?"),
+            "Done! \n\nAnything else?"
+        );
+        let code = "```\n[TASK: literal]\n```";
+        assert_eq!(pipeline.apply(code), code);
+    }
+
+    #[test]
+    fn test_pipeline_from_setting_runs_in_order() {
+        let pipeline = ResponsePipeline::from_setting(
+            "emoji, bogus, unfurl_links, disclaimer",
+            "discord",
+            Some("Not financial advice."),
+        );
+        assert_eq!(pipeline.stage_names(), vec!["strip_markers", "emoji", "unfurl_links", "disclaimer"]);
+        assert_eq!(
+            pipeline.apply(":rocket: See [the chart](https://dexscreener.com/base) :unknown:"),
+            "🚀 See the chart (https://dexscreener.com/base) :unknown:\n\nNot financial advice."
+        );
+    }
+
+    #[test]
+    fn test_platform_markdown() {
+        let slack = PlatformMarkdown { channel_type: "slack".to_string() };
+        assert_eq!(
+            slack.apply("## Prices\n**ETH** is ~~down~~ up, see [chart](https://x.io)"),
+            "*Prices*\n*ETH* is ~down~ up, see <https://x.io|chart>"
+        );
+
+        let telegram = PlatformMarkdown { channel_type: "telegram".to_string() };
+        assert_eq!(
+            telegram.apply("# Prices\n**ETH** `3100`\n```json\n{\"a\": 1}\n```"),
+            "Prices\nETH 3100\n\n{\"a\": 1}\n"
+        );
+
+        let discord = PlatformMarkdown { channel_type: "discord".to_string() };
+        assert_eq!(
+            discord.apply("#### Detail\n| a | b |\n|---|---|\n| 1 | 2 |\ndone"),
+            "**Detail**\n```\n| a | b |\n|---|---|\n| 1 | 2 |\n```\ndone"
+        );
+    }
+}
@@ -1,20 +1,43 @@
+use crate::channels::connection::ConnectionReporter;
 use crate::channels::dispatcher::MessageDispatcher;
-use crate::channels::types::ChannelType;
+use crate::channels::flood_guard::{FloodDecision, FloodGuard};
+use crate::channels::types::{ChannelType, NormalizedMessage};
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::models::Channel;
 use slack_morphism::prelude::*;
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 use tokio::sync::oneshot;
 
+/// Event IDs remembered to drop events Slack delivers twice
+const SEEN_EVENTS_CAPACITY: usize = 256;
+
+/// Check that the bot token still reaches Slack, for the channel watchdog
+pub async fn probe(channel: &Channel) -> Result<(), String> {
+    let client = SlackClient::new(SlackClientHyperConnector::new().map_err(|e| e.to_string())?);
+    let token = SlackApiToken::new(channel.bot_token.clone().into());
+    client
+        .open_session(&token)
+        .auth_test()
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
 /// Start a Slack bot listener using Socket Mode
 ///
-/// Note: Slack Socket Mode requires a complex setup with event subscriptions.
-/// This implementation provides the basic framework for receiving messages.
+/// Socket Mode connects out to Slack over a WebSocket with the channel's app
+/// token, so no public URL is needed. slack-morphism keeps two connections
+/// open, acknowledges each envelope once its callback returns, and reconnects
+/// with backoff when Slack asks for a refresh or a connection drops. Slack
+/// redelivers events that aren't acknowledged within 3 seconds, so callbacks
+/// only queue the message and the dispatch runs in its own task.
 pub async fn start_slack_listener(
     channel: Channel,
-    _dispatcher: Arc<MessageDispatcher>,
+    dispatcher: Arc<MessageDispatcher>,
     broadcaster: Arc<EventBroadcaster>,
+    connection: ConnectionReporter,
     shutdown_rx: oneshot::Receiver<()>,
 ) -> Result<(), String> {
     let channel_id = channel.id;
@@ -33,9 +56,18 @@ pub async fn start_slack_listener(
     );
 
     // Create token values
-    let _token = SlackApiToken::new(bot_token.into());
+    let token = SlackApiToken::new(bot_token.into());
     let socket_token = SlackApiToken::new(app_token.into());
 
+    // Validate the bot token and learn the bot's user ID (to strip mentions)
+    let bot_user_id = client
+        .open_session(&token)
+        .auth_test()
+        .await
+        .map_err(|e| format!("Invalid Slack bot token: {}", e))?
+        .user_id;
+    log::info!("Slack: Bot validated - user id: {}", bot_user_id);
+
     // Emit started event
     broadcaster.broadcast(GatewayEvent::channel_started(
         channel_id,
@@ -43,12 +75,21 @@ pub async fn start_slack_listener(
         &channel_name,
     ));
 
+    let state = Arc::new(SlackListenerState {
+        channel_id,
+        dispatcher,
+        token,
+        bot_user_id,
+        flood_guard: FloodGuard::from_config(),
+        seen_events: Mutex::new(VecDeque::new()),
+    });
+
     // Create listener environment
     let listener_environment = Arc::new(
-        SlackClientEventsListenerEnvironment::new(client.clone()),
+        SlackClientEventsListenerEnvironment::new(client.clone()).with_user_state(state),
     );
 
-    // Create Socket Mode callbacks with a simple handler
+    // Create Socket Mode callbacks
     let socket_mode_callbacks =
         SlackSocketModeListenerCallbacks::new().with_push_events(handle_push_event);
 
@@ -59,22 +100,22 @@ pub async fn start_slack_listener(
         socket_mode_callbacks,
     );
 
-    // Run with shutdown signal
-    let channel_name_for_shutdown = channel_name.clone();
-    tokio::select! {
-        _ = shutdown_rx => {
-            log::info!("Slack listener {} received shutdown signal", channel_name_for_shutdown);
-        }
-        result = socket_mode_listener.listen_for(&socket_token) => {
-            if let Err(e) = result {
-                log::error!("Slack listener error: {}", e);
-                broadcaster.broadcast(GatewayEvent::channel_error(
-                    channel_id,
-                    &format!("Slack error: {}", e),
-                ));
-            }
-        }
+    // listen_for opens the Socket Mode connections and returns; they stay
+    // open until the listener is shut down
+    if let Err(e) = socket_mode_listener.listen_for(&socket_token).await {
+        log::error!("Slack listener error: {}", e);
+        broadcaster.broadcast(GatewayEvent::channel_stopped(
+            channel_id,
+            ChannelType::Slack.as_str(),
+            &channel_name,
+        ));
+        return Err(format!("Slack error: {}", e));
     }
+    connection.connected();
+
+    let _ = shutdown_rx.await;
+    log::info!("Slack listener {} received shutdown signal", channel_name);
+    socket_mode_listener.shutdown().await;
 
     // Emit stopped event
     broadcaster.broadcast(GatewayEvent::channel_stopped(
@@ -86,37 +127,174 @@ pub async fn start_slack_listener(
     Ok(())
 }
 
+/// Shared by the Socket Mode callbacks of one Slack channel
+struct SlackListenerState {
+    channel_id: i64,
+    dispatcher: Arc<MessageDispatcher>,
+    token: SlackApiToken,
+    bot_user_id: SlackUserId,
+    flood_guard: FloodGuard,
+    /// Recently handled event IDs, oldest first
+    seen_events: Mutex<VecDeque<String>>,
+}
+
+impl SlackListenerState {
+    /// Whether an event was already handled; records it if not
+    fn already_seen(&self, event_id: &str) -> bool {
+        let Ok(mut seen) = self.seen_events.lock() else {
+            return false;
+        };
+        if seen.iter().any(|id| id == event_id) {
+            return true;
+        }
+        if seen.len() >= SEEN_EVENTS_CAPACITY {
+            seen.pop_front();
+        }
+        seen.push_back(event_id.to_string());
+        false
+    }
+}
+
+/// A message for the agent: a direct message, or an @mention in a channel
+struct SlackInbound {
+    channel: SlackChannelId,
+    user: SlackUserId,
+    text: String,
+    ts: SlackTs,
+    /// Thread to reply in (None replies at the top level of a DM)
+    reply_thread: Option<SlackTs>,
+}
+
+impl SlackInbound {
+    /// Channel messages arrive twice when they mention the bot, as `message`
+    /// and `app_mention`, so channels are served from mentions and only DMs
+    /// from plain messages.
+    fn from_event(event: SlackEventCallbackBody, bot_user_id: &SlackUserId) -> Option<Self> {
+        match event {
+            SlackEventCallbackBody::Message(msg) => {
+                // Skip bots (including ourselves) and edits, joins and other subtypes
+                if msg.sender.bot_id.is_some() || msg.subtype.is_some() {
+                    return None;
+                }
+                if msg.origin.channel_type.as_ref().map(|t| t.0.as_str()) != Some("im") {
+                    return None;
+                }
+                Some(SlackInbound {
+                    channel: msg.origin.channel?,
+                    user: msg.sender.user?,
+                    text: msg.content?.text?,
+                    ts: msg.origin.ts,
+                    reply_thread: msg.origin.thread_ts,
+                })
+            }
+            SlackEventCallbackBody::AppMention(mention) => {
+                let text = strip_mention(&mention.content.text?, bot_user_id);
+                Some(SlackInbound {
+                    channel: mention.channel,
+                    user: mention.user,
+                    text,
+                    reply_thread: Some(mention.origin.thread_ts.unwrap_or_else(|| mention.origin.ts.clone())),
+                    ts: mention.origin.ts,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Remove the bot's `<@U123>` mention from a message
+fn strip_mention(text: &str, bot_user_id: &SlackUserId) -> String {
+    text.replace(&format!("<@{}>", bot_user_id), "").trim().to_string()
+}
+
 fn handle_push_event(
     event: SlackPushEventCallback,
-    _client: Arc<SlackHyperClient>,
-    _user_state: SlackClientEventsUserState,
+    client: Arc<SlackHyperClient>,
+    user_state: SlackClientEventsUserState,
 ) -> std::pin::Pin<
     Box<dyn std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>> + Send>,
 > {
     Box::pin(async move {
-        // Log the received event for debugging
-        if let SlackEventCallbackBody::Message(msg_event) = event.event {
-            // Skip bot messages
-            if msg_event.sender.bot_id.is_some() {
-                return Ok(());
-            }
-
-            if let Some(text) = msg_event.content.as_ref().and_then(|c| c.text.clone()) {
-                let user = msg_event
-                    .sender
-                    .user
-                    .as_ref()
-                    .map(|u| u.to_string())
-                    .unwrap_or_else(|| "unknown".to_string());
+        let state = {
+            let storage = user_state.read().await;
+            storage.get_user_state::<Arc<SlackListenerState>>().cloned()
+        };
+        let Some(state) = state else {
+            return Ok(());
+        };
 
-                log::info!("Slack message from {}: {}", user, text);
+        if state.already_seen(&event.event_id.to_string()) {
+            log::debug!("Slack: Ignoring redelivered event {}", event.event_id);
+            return Ok(());
+        }
 
-                // TODO: Full integration with dispatcher requires more complex
-                // state management through SlackClientEventsUserStateStorage.
-                // For now, messages are logged but not processed through AI.
-            }
+        if let Some(inbound) = SlackInbound::from_event(event.event, &state.bot_user_id) {
+            // Return straight away so the envelope is acknowledged in time
+            tokio::spawn(handle_message(client, state, inbound));
         }
 
         Ok(())
     })
 }
+
+/// Dispatch a message to the AI and post the reply
+async fn handle_message(client: Arc<SlackHyperClient>, state: Arc<SlackListenerState>, inbound: SlackInbound) {
+    if inbound.text.is_empty() {
+        return;
+    }
+    let user_id = inbound.user.to_string();
+
+    log::info!(
+        "Slack: Message from {} in {}: {}",
+        user_id,
+        inbound.channel,
+        if inbound.text.len() > 50 { &inbound.text[..50] } else { &inbound.text }
+    );
+
+    let session = client.open_session(&state.token);
+
+    if let FloodDecision::Drop { notice } = state.flood_guard.check(&user_id) {
+        log::info!("Slack: Dropping message from {}: sending too fast", user_id);
+        if let Some(notice) = notice {
+            post_message(&session, &inbound, &notice).await;
+        }
+        return;
+    }
+
+    let normalized = NormalizedMessage {
+        channel_id: state.channel_id,
+        channel_type: ChannelType::Slack.to_string(),
+        chat_id: inbound.channel.to_string(),
+        user_id: user_id.clone(),
+        user_name: user_id,
+        text: inbound.text.clone(),
+        message_id: Some(inbound.ts.to_string()),
+        session_mode: None,
+        selected_network: None,
+        allowed_tools: None,
+        task_signature: None,
+        response_schema: None,
+        reply_to: None,
+    };
+
+    let result = state.dispatcher.dispatch(normalized).await;
+
+    if result.error.is_none() && !result.response.is_empty() {
+        post_message(&session, &inbound, &result.response).await;
+    } else if let Some(error) = result.error {
+        post_message(&session, &inbound, &format!("Sorry, I encountered an error: {}", error)).await;
+    }
+}
+
+/// Post a reply next to the message it answers
+async fn post_message(session: &SlackClientSession<'_, SlackClientHyperHttpsConnector>, inbound: &SlackInbound, text: &str) {
+    let request = SlackApiChatPostMessageRequest::new(
+        inbound.channel.clone(),
+        SlackMessageContent::new().with_text(text.to_string()),
+    )
+    .opt_thread_ts(inbound.reply_thread.clone());
+
+    if let Err(e) = session.chat_post_message(&request).await {
+        log::error!("Failed to send Slack message: {}", e);
+    }
+}
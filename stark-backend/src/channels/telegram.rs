@@ -1,11 +1,15 @@
 use crate::channels::dispatcher::MessageDispatcher;
-use crate::channels::types::{ChannelType, NormalizedMessage};
+use crate::channels::connection::ConnectionReporter;
+use crate::channels::flood_guard::{FloodDecision, FloodGuard};
+use crate::channels::types::{ChannelType, EditOutcome, NormalizedMessage, ReplyContext};
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::models::Channel;
 use std::sync::Arc;
 use teloxide::prelude::*;
 use teloxide::requests::Requester;
+use teloxide::types::UserId;
+use teloxide::update_listeners;
 use tokio::sync::oneshot;
 
 /// Format a tool call event for Telegram display (plain text for reliability)
@@ -50,11 +54,21 @@ fn format_mode_change_for_telegram(mode: &str, label: &str, reason: Option<&str>
     }
 }
 
+/// Check that the bot token still reaches Telegram, for the channel watchdog
+pub async fn probe(channel: &Channel) -> Result<(), String> {
+    Bot::new(&channel.bot_token)
+        .get_me()
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
 /// Start a Telegram bot listener
 pub async fn start_telegram_listener(
     channel: Channel,
     dispatcher: Arc<MessageDispatcher>,
     broadcaster: Arc<EventBroadcaster>,
+    connection: ConnectionReporter,
     shutdown_rx: oneshot::Receiver<()>,
 ) -> Result<(), String> {
     let channel_id = channel.id;
@@ -69,20 +83,23 @@ pub async fn start_telegram_listener(
 
     // Validate token by calling getMe
     log::info!("Telegram: Validating bot token...");
-    match bot.get_me().await {
+    let bot_user_id = match bot.get_me().await {
         Ok(me) => {
             log::info!(
                 "Telegram: Bot validated - username: @{}, id: {}",
                 me.username(),
                 me.id
             );
+            me.id
         }
         Err(e) => {
             let error = format!("Invalid Telegram bot token: {}", e);
             log::error!("Telegram: {}", error);
             return Err(error);
         }
-    }
+    };
+
+    connection.connected();
 
     // Emit started event
     broadcaster.broadcast(GatewayEvent::channel_started(
@@ -94,194 +111,40 @@ pub async fn start_telegram_listener(
     // Clone broadcaster for use in handler
     let broadcaster_for_handler = broadcaster.clone();
 
-    // Create message handler
-    let handler = Update::filter_message().endpoint(
-        move |bot: Bot, msg: teloxide::types::Message, dispatcher: Arc<MessageDispatcher>| {
-            let channel_id = channel_id;
-            let broadcaster = broadcaster_for_handler.clone();
-            async move {
-                log::info!("Telegram: Received update from chat {}", msg.chat.id);
-
-                // Only handle text messages
-                if let Some(text) = msg.text() {
-                    let user = msg.from();
-                    let user_id = user.map(|u| u.id.to_string()).unwrap_or_default();
-                    let user_name = user
-                        .map(|u| {
-                            u.username
-                                .clone()
-                                .unwrap_or_else(|| u.first_name.clone())
-                        })
-                        .unwrap_or_else(|| "Unknown".to_string());
-
-                    log::info!(
-                        "Telegram: Message from {} ({}): {}",
-                        user_name,
-                        user_id,
-                        if text.len() > 50 { &text[..50] } else { text }
-                    );
-
-                    let normalized = NormalizedMessage {
-                        channel_id,
-                        channel_type: ChannelType::Telegram.to_string(),
-                        chat_id: msg.chat.id.to_string(),
-                        user_id,
-                        user_name: user_name.clone(),
-                        text: text.to_string(),
-                        message_id: Some(msg.id.to_string()),
-                        session_mode: None,
-                        selected_network: None,
-                    };
-
-                    // Subscribe to events for real-time tool call forwarding
-                    let (client_id, mut event_rx) = broadcaster.subscribe();
-                    log::info!("Telegram: Subscribed to events as client {}", client_id);
-
-                    // Clone bot and chat_id for the event forwarder task
-                    let bot_for_events = bot.clone();
-                    let telegram_chat_id = msg.chat.id;
-                    let channel_id_for_events = channel_id;
-                    // Convert Telegram chat ID to string for event filtering
-                    let chat_id_str_for_events = telegram_chat_id.to_string();
-
-                    // Spawn task to forward events to Telegram in real-time
-                    let event_task = tokio::spawn(async move {
-                        while let Some(event) = event_rx.recv().await {
-                            // Only forward events for this specific channel AND chat session
-                            let event_channel_id = event.data.get("channel_id").and_then(|v| v.as_i64());
-                            let event_chat_id = event.data.get("chat_id").and_then(|v| v.as_str());
-
-                            match (event_channel_id, event_chat_id) {
-                                (Some(ch_id), Some(chat_id)) => {
-                                    // Both IDs present - must match both
-                                    if ch_id != channel_id_for_events || chat_id != chat_id_str_for_events {
-                                        continue;
-                                    }
-                                }
-                                (Some(ch_id), None) => {
-                                    // Only channel_id present (legacy event) - check channel only
-                                    if ch_id != channel_id_for_events {
-                                        continue;
-                                    }
-                                }
-                                _ => {
-                                    // No channel_id - skip this event
-                                    continue;
-                                }
-                            }
-
-                            let message_text = match event.event.as_str() {
-                                "agent.tool_call" => {
-                                    let tool_name = event.data.get("tool_name")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("unknown");
-                                    let params = event.data.get("parameters")
-                                        .cloned()
-                                        .unwrap_or(serde_json::json!({}));
-                                    Some(format_tool_call_for_telegram(tool_name, &params))
-                                }
-                                "tool.result" => {
-                                    let tool_name = event.data.get("tool_name")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("unknown");
-                                    let success = event.data.get("success")
-                                        .and_then(|v| v.as_bool())
-                                        .unwrap_or(false);
-                                    let duration_ms = event.data.get("duration_ms")
-                                        .and_then(|v| v.as_i64())
-                                        .unwrap_or(0);
-                                    let content = event.data.get("content")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("");
-
-                                    // say_to_user messages are displayed directly without tool result formatting
-                                    if tool_name == "say_to_user" && success && !content.is_empty() {
-                                        // Truncate if too long for Telegram (4096 char limit)
-                                        let display_content = if content.len() > 4000 {
-                                            format!("{}...", &content[..4000])
-                                        } else {
-                                            content.to_string()
-                                        };
-                                        Some(display_content)
-                                    } else {
-                                        Some(format_tool_result_for_telegram(tool_name, success, duration_ms, content))
-                                    }
-                                }
-                                "agent.mode_change" => {
-                                    let mode = event.data.get("mode")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("unknown");
-                                    let label = event.data.get("label")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("Unknown");
-                                    let reason = event.data.get("reason")
-                                        .and_then(|v| v.as_str());
-                                    Some(format_mode_change_for_telegram(mode, label, reason))
-                                }
-                                "execution.task_started" => {
-                                    let task_type = event.data.get("type")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("task");
-                                    let name = event.data.get("name")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("Unknown task");
-                                    Some(format!("▶️ *{}:* {}", task_type, name))
-                                }
-                                "execution.task_completed" => {
-                                    let status = event.data.get("status")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("completed");
-                                    let emoji = if status == "completed" { "✅" } else { "❌" };
-                                    Some(format!("{} Task {}", emoji, status))
-                                }
-                                _ => None,
-                            };
+    // Drop messages from users flooding the bot before they reach the dispatcher
+    let flood_guard = Arc::new(FloodGuard::from_config());
 
-                            if let Some(text) = message_text {
-                                // Send as plain text for maximum reliability
-                                if let Err(e) = bot_for_events
-                                    .send_message(telegram_chat_id, &text)
-                                    .await
-                                {
-                                    log::warn!("Telegram: Failed to send event message: {}", e);
-                                }
-                            }
-                        }
-                    });
-
-                    // Dispatch to AI
-                    log::info!("Telegram: Dispatching message to AI for user {}", user_name);
-                    let result = dispatcher.dispatch(normalized).await;
-                    log::info!("Telegram: Dispatch complete, error={:?}", result.error);
-
-                    // Unsubscribe and stop event forwarding
-                    broadcaster.unsubscribe(&client_id);
-                    event_task.abort();
-                    log::info!("Telegram: Unsubscribed from events, client {}", client_id);
-
-                    // Send final response
-                    if result.error.is_none() && !result.response.is_empty() {
-                        if let Err(e) = bot
-                            .send_message(msg.chat.id, &result.response)
-                            .reply_to_message_id(msg.id)
-                            .await
-                        {
-                            log::error!("Failed to send Telegram message: {}", e);
-                        }
-                    } else if let Some(error) = result.error {
-                        // Send error message
-                        let error_msg = format!("Sorry, I encountered an error: {}", error);
-                        let _ = bot
-                            .send_message(msg.chat.id, &error_msg)
-                            .reply_to_message_id(msg.id)
-                            .await;
-                    }
-                }
+    // New messages are dispatched; edits update the stored history (and may
+    // re-dispatch). The Bot API doesn't deliver deletions, so those aren't seen.
+    let flood_guard_for_edits = flood_guard.clone();
+    let broadcaster_for_edits = broadcaster_for_handler.clone();
+    let handler = dptree::entry()
+        .branch(Update::filter_message().endpoint(
+            move |bot: Bot, msg: teloxide::types::Message, dispatcher: Arc<MessageDispatcher>| {
+                let broadcaster = broadcaster_for_handler.clone();
+                let flood_guard = flood_guard.clone();
+                async move { handle_message(bot, msg, dispatcher, channel_id, bot_user_id, broadcaster, flood_guard).await }
+            },
+        ))
+        .branch(Update::filter_edited_message().endpoint(
+            move |bot: Bot, msg: teloxide::types::Message, dispatcher: Arc<MessageDispatcher>| {
+                let broadcaster = broadcaster_for_edits.clone();
+                let flood_guard = flood_guard_for_edits.clone();
+                async move { handle_edited_message(bot, msg, dispatcher, channel_id, bot_user_id, broadcaster, flood_guard).await }
+            },
+        ));
 
-                Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
-            }
-        },
-    );
+    // Long polling; a failed poll (network drop, revoked token) is reported
+    // as a connection error. Polling keeps retrying, and the watchdog marks
+    // the channel connected again once its health probe succeeds.
+    let polling = update_listeners::polling_default(bot.clone()).await;
+    let polling_errors = Arc::new(move |e: teloxide::RequestError| {
+        let connection = connection.clone();
+        async move {
+            log::error!("Telegram: Polling failed: {}", e);
+            connection.error(&e.to_string());
+        }
+    });
 
     // Create dispatcher
     let mut tg_dispatcher = Dispatcher::builder(bot, handler)
@@ -294,7 +157,7 @@ pub async fn start_telegram_listener(
         _ = shutdown_rx => {
             log::info!("Telegram listener {} received shutdown signal", channel_name);
         }
-        _ = tg_dispatcher.dispatch() => {
+        _ = tg_dispatcher.dispatch_with_listener(polling, polling_errors) => {
             log::info!("Telegram listener {} stopped", channel_name);
         }
     }
@@ -308,3 +171,253 @@ pub async fn start_telegram_listener(
 
     Ok(())
 }
+
+/// What a message replies to, if the user used Telegram's reply feature
+fn reply_context(msg: &teloxide::types::Message, bot_user_id: UserId) -> Option<ReplyContext> {
+    let replied = msg.reply_to_message()?;
+    let text = replied.text().or_else(|| replied.caption())?;
+    let author = replied.from();
+    Some(ReplyContext {
+        message_id: Some(replied.id.to_string()),
+        author: author
+            .map(|u| u.username.clone().unwrap_or_else(|| u.first_name.clone()))
+            .unwrap_or_else(|| "Unknown".to_string()),
+        from_bot: author.is_some_and(|u| u.id == bot_user_id),
+        text: text.to_string(),
+    })
+}
+
+type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+/// Dispatch a message to the AI, forwarding tool events and sending the reply
+async fn handle_message(
+    bot: Bot,
+    msg: teloxide::types::Message,
+    dispatcher: Arc<MessageDispatcher>,
+    channel_id: i64,
+    bot_user_id: UserId,
+    broadcaster: Arc<EventBroadcaster>,
+    flood_guard: Arc<FloodGuard>,
+) -> HandlerResult {
+    log::info!("Telegram: Received update from chat {}", msg.chat.id);
+
+    // Only handle text messages
+    if let Some(text) = msg.text() {
+        let user = msg.from();
+        let user_id = user.map(|u| u.id.to_string()).unwrap_or_default();
+        let user_name = user
+            .map(|u| {
+                u.username
+                    .clone()
+                    .unwrap_or_else(|| u.first_name.clone())
+            })
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        log::info!(
+            "Telegram: Message from {} ({}): {}",
+            user_name,
+            user_id,
+            if text.len() > 50 { &text[..50] } else { text }
+        );
+
+        if let FloodDecision::Drop { notice } = flood_guard.check(&user_id) {
+            log::info!("Telegram: Dropping message from {} ({}): sending too fast", user_name, user_id);
+            if let Some(notice) = notice {
+                let _ = bot.send_message(msg.chat.id, notice).reply_to_message_id(msg.id).await;
+            }
+            return Ok(());
+        }
+
+        let normalized = NormalizedMessage {
+            channel_id,
+            channel_type: ChannelType::Telegram.to_string(),
+            chat_id: msg.chat.id.to_string(),
+            user_id,
+            user_name: user_name.clone(),
+            text: text.to_string(),
+            message_id: Some(msg.id.to_string()),
+            session_mode: None,
+            selected_network: None,
+            allowed_tools: None,
+            task_signature: None,
+            response_schema: None,
+            reply_to: reply_context(&msg, bot_user_id),
+        };
+
+        // Subscribe to events for real-time tool call forwarding
+        let (client_id, mut event_rx) = broadcaster.subscribe();
+        log::info!("Telegram: Subscribed to events as client {}", client_id);
+
+        // Clone bot and chat_id for the event forwarder task
+        let bot_for_events = bot.clone();
+        let telegram_chat_id = msg.chat.id;
+        let channel_id_for_events = channel_id;
+        // Convert Telegram chat ID to string for event filtering
+        let chat_id_str_for_events = telegram_chat_id.to_string();
+
+        // Spawn task to forward events to Telegram in real-time
+        let event_task = tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                // Only forward events for this specific channel AND chat session
+                let event_channel_id = event.data.get("channel_id").and_then(|v| v.as_i64());
+                let event_chat_id = event.data.get("chat_id").and_then(|v| v.as_str());
+
+                match (event_channel_id, event_chat_id) {
+                    (Some(ch_id), Some(chat_id)) => {
+                        // Both IDs present - must match both
+                        if ch_id != channel_id_for_events || chat_id != chat_id_str_for_events {
+                            continue;
+                        }
+                    }
+                    (Some(ch_id), None) => {
+                        // Only channel_id present (legacy event) - check channel only
+                        if ch_id != channel_id_for_events {
+                            continue;
+                        }
+                    }
+                    _ => {
+                        // No channel_id - skip this event
+                        continue;
+                    }
+                }
+
+                let message_text = match event.event.as_str() {
+                    "agent.tool_call" => {
+                        let tool_name = event.data.get("tool_name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("unknown");
+                        let params = event.data.get("parameters")
+                            .cloned()
+                            .unwrap_or(serde_json::json!({}));
+                        Some(format_tool_call_for_telegram(tool_name, &params))
+                    }
+                    "tool.result" => {
+                        let tool_name = event.data.get("tool_name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("unknown");
+                        let success = event.data.get("success")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        let duration_ms = event.data.get("duration_ms")
+                            .and_then(|v| v.as_i64())
+                            .unwrap_or(0);
+                        let content = event.data.get("content")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("");
+
+                        // say_to_user messages are displayed directly without tool result formatting
+                        if tool_name == "say_to_user" && success && !content.is_empty() {
+                            // Truncate if too long for Telegram (4096 char limit)
+                            let display_content = if content.len() > 4000 {
+                                format!("{}...", &content[..4000])
+                            } else {
+                                content.to_string()
+                            };
+                            Some(display_content)
+                        } else {
+                            Some(format_tool_result_for_telegram(tool_name, success, duration_ms, content))
+                        }
+                    }
+                    "agent.mode_change" => {
+                        let mode = event.data.get("mode")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("unknown");
+                        let label = event.data.get("label")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("Unknown");
+                        let reason = event.data.get("reason")
+                            .and_then(|v| v.as_str());
+                        Some(format_mode_change_for_telegram(mode, label, reason))
+                    }
+                    "execution.task_started" => {
+                        let task_type = event.data.get("type")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("task");
+                        let name = event.data.get("name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("Unknown task");
+                        Some(format!("▶️ *{}:* {}", task_type, name))
+                    }
+                    "execution.task_completed" => {
+                        let status = event.data.get("status")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("completed");
+                        let emoji = if status == "completed" { "✅" } else { "❌" };
+                        Some(format!("{} Task {}", emoji, status))
+                    }
+                    _ => None,
+                };
+
+                if let Some(text) = message_text {
+                    // Send as plain text for maximum reliability
+                    if let Err(e) = bot_for_events
+                        .send_message(telegram_chat_id, &text)
+                        .await
+                    {
+                        log::warn!("Telegram: Failed to send event message: {}", e);
+                    }
+                }
+            }
+        });
+
+        // Dispatch to AI
+        log::info!("Telegram: Dispatching message to AI for user {}", user_name);
+        let result = dispatcher.dispatch(normalized).await;
+        log::info!("Telegram: Dispatch complete, error={:?}", result.error);
+
+        // Unsubscribe and stop event forwarding
+        broadcaster.unsubscribe(&client_id);
+        event_task.abort();
+        log::info!("Telegram: Unsubscribed from events, client {}", client_id);
+
+        // Send final response
+        if result.error.is_none() && !result.response.is_empty() {
+            if let Err(e) = bot
+                .send_message(msg.chat.id, &result.response)
+                .reply_to_message_id(msg.id)
+                .await
+            {
+                log::error!("Failed to send Telegram message: {}", e);
+            }
+        } else if let Some(error) = result.error {
+            // Send error message
+            let error_msg = format!("Sorry, I encountered an error: {}", error);
+            let _ = bot
+                .send_message(msg.chat.id, &error_msg)
+                .reply_to_message_id(msg.id)
+                .await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply an edited message to the session history, re-dispatching it when the
+/// dispatcher says the edit changed what it asks
+async fn handle_edited_message(
+    bot: Bot,
+    msg: teloxide::types::Message,
+    dispatcher: Arc<MessageDispatcher>,
+    channel_id: i64,
+    bot_user_id: UserId,
+    broadcaster: Arc<EventBroadcaster>,
+    flood_guard: Arc<FloodGuard>,
+) -> HandlerResult {
+    let (Some(text), Some(user)) = (msg.text(), msg.from()) else {
+        return Ok(());
+    };
+
+    let outcome = dispatcher.apply_message_edit(
+        ChannelType::Telegram.as_str(),
+        channel_id,
+        &user.id.to_string(),
+        &msg.id.to_string(),
+        text,
+    );
+    if outcome == EditOutcome::Redispatch {
+        log::info!("Telegram: Re-dispatching edited message {} in chat {}", msg.id, msg.chat.id);
+        return handle_message(bot, msg, dispatcher, channel_id, bot_user_id, broadcaster, flood_guard).await;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Log of the tool calls made while answering a message
+//!
+//! The orchestrated loops prepend this log to the final reply. How much of it
+//! the user sees is the channel's Tool Log setting: every call with its
+//! arguments, a one-line summary, or nothing. The gateway gets every call as
+//! it happens regardless, so the dashboard's debug panel keeps the detail.
+
+use crate::models::ToolOutputVerbosity;
+
+/// Tool calls made during one dispatch, in order
+#[derive(Debug, Default)]
+pub struct ToolCallLog {
+    /// (tool name, pretty-printed arguments)
+    calls: Vec<(String, String)>,
+}
+
+impl ToolCallLog {
+    pub fn push(&mut self, name: &str, args_pretty: &str) {
+        self.calls.push((name.to_string(), args_pretty.to_string()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Every call with its arguments, as stored in session summaries
+    pub fn full(&self) -> String {
+        self.calls
+            .iter()
+            .map(|(name, args)| format!("🔧 **Tool Call:** `{}`\n```json\n{}\n```", name, args))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// One line naming each tool used, with a count for repeats
+    pub fn summary(&self) -> String {
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for (name, _) in &self.calls {
+            match counts.iter_mut().find(|(n, _)| n == name) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((name, 1)),
+            }
+        }
+
+        let tools = counts
+            .iter()
+            .map(|(name, count)| match count {
+                1 => format!("`{}`", name),
+                n => format!("`{}` ×{}", name, n),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let noun = if self.calls.len() == 1 { "call" } else { "calls" };
+        format!("🔧 {} tool {}: {}", self.calls.len(), noun, tools)
+    }
+
+    /// The log as shown to users at the given verbosity, or `None` when
+    /// nothing should be shown
+    pub fn render(&self, verbosity: ToolOutputVerbosity) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        match verbosity {
+            ToolOutputVerbosity::Full => Some(self.full()),
+            ToolOutputVerbosity::Minimal => Some(self.summary()),
+            ToolOutputVerbosity::None => None,
+        }
+    }
+
+    /// Prepend the log, at the given verbosity, to a reply
+    pub fn prepend_to(&self, verbosity: ToolOutputVerbosity, reply: &str) -> String {
+        match self.render(verbosity) {
+            Some(log) => format!("{}\n\n{}", log, reply),
+            None => reply.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log() -> ToolCallLog {
+        let mut log = ToolCallLog::default();
+        log.push("token_lookup", "{\"symbol\": \"ETH\"}");
+        log.push("web_fetch", "{\"url\": \"a\"}");
+        log.push("web_fetch", "{\"url\": \"b\"}");
+        log
+    }
+
+    #[test]
+    fn test_render_by_verbosity() {
+        let log = log();
+
+        let full = log.render(ToolOutputVerbosity::Full).unwrap();
+        assert_eq!(full.matches("🔧 **Tool Call:**").count(), 3);
+        assert!(full.contains("\"url\": \"b\""));
+
+        assert_eq!(
+            log.render(ToolOutputVerbosity::Minimal).as_deref(),
+            Some("🔧 3 tool calls: `token_lookup`, `web_fetch` ×2")
+        );
+
+        assert_eq!(log.render(ToolOutputVerbosity::None), None);
+        assert_eq!(log.prepend_to(ToolOutputVerbosity::None, "Done."), "Done.");
+    }
+
+    #[test]
+    fn test_empty_log_adds_nothing() {
+        let log = ToolCallLog::default();
+        assert_eq!(log.render(ToolOutputVerbosity::Full), None);
+        assert_eq!(log.prepend_to(ToolOutputVerbosity::Minimal, "Done."), "Done.");
+    }
+}
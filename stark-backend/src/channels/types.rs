@@ -74,6 +74,66 @@ pub struct NormalizedMessage {
     /// Used as default for web3 operations unless user explicitly specifies otherwise
     #[serde(default)]
     pub selected_network: Option<String>,
+    /// Restrict the agent to these tools (plus system tools) for this message.
+    /// `None` uses the channel's tool configuration unchanged.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Signature of a recurring task (see `ai::multi_agent::findings`). When
+    /// set, the run is seeded with findings from earlier runs of the task and
+    /// its own findings are stored for the next one.
+    #[serde(default)]
+    pub task_signature: Option<String>,
+    /// JSON Schema the final reply must match (see `ai::structured_output`).
+    /// `None` for ordinary prose replies.
+    #[serde(default)]
+    pub response_schema: Option<serde_json::Value>,
+    /// The earlier message this one replies to, when the user used the
+    /// platform's reply feature
+    #[serde(default)]
+    pub reply_to: Option<ReplyContext>,
+}
+
+/// An earlier message a user replied to, passed to the model so references
+/// like "do that again" resolve to the right message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplyContext {
+    /// Platform-specific ID of the replied-to message
+    pub message_id: Option<String>,
+    /// Display name of its author
+    pub author: String,
+    /// Whether the bot itself wrote it
+    pub from_bot: bool,
+    /// Its text content
+    pub text: String,
+}
+
+impl ReplyContext {
+    /// Longest quote passed to the model; replies usually point at the gist
+    const MAX_QUOTE_CHARS: usize = 500;
+
+    /// Prompt line telling the model what the user is replying to
+    pub fn prompt_line(&self) -> String {
+        let mut quote: String = self.text.chars().take(Self::MAX_QUOTE_CHARS).collect();
+        if quote.len() < self.text.len() {
+            quote.push_str("...");
+        }
+        if self.from_bot {
+            format!("The user is replying to your earlier message: \"{}\"\n", quote)
+        } else {
+            format!("The user is replying to a message from {}: \"{}\"\n", self.author, quote)
+        }
+    }
+}
+
+/// What a listener should do after a user edits a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOutcome {
+    /// The message isn't in any session history (or the text didn't change)
+    Ignored,
+    /// The stored message now has the edited text
+    Updated,
+    /// The edit changed what the latest message asks: dispatch the new text
+    Redispatch,
 }
 
 /// Handle to a running channel listener
@@ -81,7 +141,12 @@ pub struct ChannelHandle {
     pub channel_id: i64,
     pub channel_type: String,
     pub name: String,
+    /// Distinguishes this run from earlier runs of the same channel, so a
+    /// listener that exits late doesn't deregister its replacement
+    pub run_id: u64,
     pub shutdown_tx: tokio::sync::oneshot::Sender<()>,
+    /// The listener task
+    pub task: tokio::task::JoinHandle<()>,
 }
 
 impl ChannelHandle {
@@ -89,22 +154,50 @@ impl ChannelHandle {
         channel_id: i64,
         channel_type: String,
         name: String,
+        run_id: u64,
         shutdown_tx: tokio::sync::oneshot::Sender<()>,
+        task: tokio::task::JoinHandle<()>,
     ) -> Self {
         Self {
             channel_id,
             channel_type,
             name,
+            run_id,
             shutdown_tx,
+            task,
         }
     }
 }
 
+/// Outcome of a request to start or stop a channel. Repeating a request is
+/// not an error: it reports that the channel was already in that state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelStatus {
+    /// The listener was started
+    Started,
+    /// The listener was already running; nothing changed
+    AlreadyRunning,
+    /// The listener was stopped
+    Stopped,
+    /// The listener wasn't running; nothing changed
+    NotRunning,
+}
+
+impl ChannelStatus {
+    /// Whether the channel is running after the request
+    pub fn is_running(&self) -> bool {
+        matches!(self, Self::Started | Self::AlreadyRunning)
+    }
+}
+
 /// Result of dispatching a message to the AI
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DispatchResult {
     pub response: String,
     pub error: Option<String>,
+    /// The parsed reply, when the message asked for a `response_schema`
+    pub structured: Option<serde_json::Value>,
 }
 
 impl DispatchResult {
@@ -112,6 +205,7 @@ impl DispatchResult {
         Self {
             response,
             error: None,
+            structured: None,
         }
     }
 
@@ -119,6 +213,12 @@ impl DispatchResult {
         Self {
             response: String::new(),
             error: Some(error),
+            structured: None,
         }
     }
+
+    pub fn with_structured(mut self, structured: serde_json::Value) -> Self {
+        self.structured = Some(structured);
+        self
+    }
 }
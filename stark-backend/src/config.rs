@@ -7,6 +7,11 @@ use std::path::{Path, PathBuf};
 pub mod env_vars {
     pub const LOGIN_ADMIN_PUBLIC_ADDRESS: &str = "LOGIN_ADMIN_PUBLIC_ADDRESS";
     pub const BURNER_WALLET_PRIVATE_KEY: &str = "BURNER_WALLET_BOT_PRIVATE_KEY";
+    // Transaction signing backend ("local" or "keystore")
+    pub const WALLET_SIGNER: &str = "STARK_WALLET_SIGNER";
+    pub const KEYSTORE_SIGNER_TOKEN: &str = "STARK_KEYSTORE_SIGNER_TOKEN";
+    // Additional named wallets
+    pub const WALLETS: &str = "STARK_WALLETS";
     pub const PORT: &str = "PORT";
     pub const DATABASE_URL: &str = "DATABASE_URL";
     pub const WORKSPACE_DIR: &str = "STARK_WORKSPACE_DIR";
@@ -20,6 +25,57 @@ pub mod env_vars {
     pub const MEMORY_ENABLE_PRE_COMPACTION_FLUSH: &str = "STARK_MEMORY_ENABLE_PRE_COMPACTION_FLUSH";
     pub const MEMORY_ENABLE_CROSS_SESSION: &str = "STARK_MEMORY_ENABLE_CROSS_SESSION";
     pub const MEMORY_CROSS_SESSION_LIMIT: &str = "STARK_MEMORY_CROSS_SESSION_LIMIT";
+    // Exec tool
+    pub const EXEC_MAX_OUTPUT_BYTES: &str = "STARK_EXEC_MAX_OUTPUT_BYTES";
+    // Tool execution
+    pub const TOOL_TIMEOUTS: &str = "STARK_TOOL_TIMEOUTS";
+    // Outbound HTTP tools (web_fetch, webhook)
+    pub const OUTBOUND_URL_ALLOWLIST: &str = "STARK_OUTBOUND_URL_ALLOWLIST";
+    // Gateway WebSocket
+    pub const GATEWAY_REPLAY_BUFFER_SIZE: &str = "STARK_GATEWAY_REPLAY_BUFFER_SIZE";
+    // Logging
+    pub const LOG_FORMAT: &str = "STARK_LOG_FORMAT";
+    // Session reset policies
+    pub const SESSION_RESET_TIMEZONE: &str = "STARK_SESSION_RESET_TIMEZONE";
+    // Portfolio snapshots
+    pub const PORTFOLIO_SNAPSHOT_INTERVAL_MINS: &str = "STARK_PORTFOLIO_SNAPSHOT_INTERVAL_MINS";
+    pub const PORTFOLIO_NETWORKS: &str = "STARK_PORTFOLIO_NETWORKS";
+    // Config hot-reload
+    pub const CONFIG_WATCH_INTERVAL_SECS: &str = "STARK_CONFIG_WATCH_INTERVAL_SECS";
+    // Context bank bounds
+    pub const CONTEXT_BANK_MAX_ITEMS: &str = "STARK_CONTEXT_BANK_MAX_ITEMS";
+    pub const CONTEXT_BANK_MAX_BYTES: &str = "STARK_CONTEXT_BANK_MAX_BYTES";
+    // Tool call deduplication within a dispatch
+    pub const TOOL_CALL_DEDUP: &str = "STARK_TOOL_CALL_DEDUP";
+    // Concurrent read-only tool calls per batch
+    pub const TOOL_CONCURRENCY: &str = "STARK_TOOL_CONCURRENCY";
+    // Sub-agent limits
+    pub const SUBAGENT_MAX_CONCURRENT: &str = "STARK_SUBAGENT_MAX_CONCURRENT";
+    pub const SUBAGENT_MAX_DEPTH: &str = "STARK_SUBAGENT_MAX_DEPTH";
+    pub const SUBAGENT_MAX_TIMEOUT_SECS: &str = "STARK_SUBAGENT_MAX_TIMEOUT_SECS";
+    // Orchestrator mode limits
+    pub const PLANNER_MAX_ITERATIONS: &str = "STARK_PLANNER_MAX_ITERATIONS";
+    pub const MAX_REPLANS: &str = "STARK_MAX_REPLANS";
+    // Per-identity daily usage quotas
+    pub const QUOTA_MESSAGES_PER_DAY: &str = "STARK_QUOTA_MESSAGES_PER_DAY";
+    pub const QUOTA_TOOL_CALLS_PER_DAY: &str = "STARK_QUOTA_TOOL_CALLS_PER_DAY";
+    pub const QUOTA_X402_USDC_PER_DAY: &str = "STARK_QUOTA_X402_USDC_PER_DAY";
+    // Assets and networks x402 payments may use
+    pub const X402_NETWORKS: &str = "STARK_X402_NETWORKS";
+    pub const X402_ASSETS: &str = "STARK_X402_ASSETS";
+    // Inbound flood protection for channel listeners
+    pub const FLOOD_WINDOW_SECS: &str = "STARK_FLOOD_WINDOW_SECS";
+    pub const FLOOD_BURST: &str = "STARK_FLOOD_BURST";
+    // Channel watchdog
+    pub const CHANNEL_STALL_SECS: &str = "STARK_CHANNEL_STALL_SECS";
+    pub const CHANNEL_MAX_RESTARTS: &str = "STARK_CHANNEL_MAX_RESTARTS";
+    // Deploy tool targets
+    pub const DEPLOY_TARGETS: &str = "STARK_DEPLOY_TARGETS";
+    // Webhook dispatch
+    pub const WEBHOOK_API_KEY: &str = "STARK_WEBHOOK_API_KEY";
+    // Gmail OAuth client (for access token refresh)
+    pub const GOOGLE_OAUTH_CLIENT_ID: &str = "GOOGLE_OAUTH_CLIENT_ID";
+    pub const GOOGLE_OAUTH_CLIENT_SECRET: &str = "GOOGLE_OAUTH_CLIENT_SECRET";
 }
 
 /// Default values
@@ -30,6 +86,29 @@ pub mod defaults {
     pub const SKILLS_DIR: &str = "./skills";
     pub const JOURNAL_DIR: &str = "./journal";
     pub const SOUL_DIR: &str = "./soul";
+    pub const EXEC_MAX_OUTPUT_BYTES: usize = 15000;
+    pub const GATEWAY_REPLAY_BUFFER_SIZE: usize = 200;
+    pub const PORTFOLIO_SNAPSHOT_INTERVAL_MINS: u64 = 60;
+    pub const PORTFOLIO_NETWORKS: &str = "base";
+    pub const CONFIG_WATCH_INTERVAL_SECS: u64 = 0;
+    pub const CONTEXT_BANK_MAX_ITEMS: usize = 100;
+    pub const CONTEXT_BANK_MAX_BYTES: usize = 8192;
+    pub const TOOL_CONCURRENCY: usize = 4;
+    pub const SUBAGENT_MAX_CONCURRENT: usize = 3;
+    pub const SUBAGENT_MAX_DEPTH: usize = 2;
+    pub const SUBAGENT_MAX_TIMEOUT_SECS: u64 = 3600;
+    pub const PLANNER_MAX_ITERATIONS: u32 = 3;
+    pub const MAX_REPLANS: u32 = 2;
+    // Quotas are off (0 = unlimited) unless configured
+    pub const QUOTA_MESSAGES_PER_DAY: u64 = 0;
+    pub const QUOTA_TOOL_CALLS_PER_DAY: u64 = 0;
+    pub const QUOTA_X402_USDC_PER_DAY: f64 = 0.0;
+    pub const X402_NETWORKS: &str = "base";
+    pub const X402_ASSETS: &str = "USDC";
+    pub const FLOOD_WINDOW_SECS: u64 = 10;
+    pub const FLOOD_BURST: usize = 5;
+    pub const CHANNEL_STALL_SECS: u64 = 300;
+    pub const CHANNEL_MAX_RESTARTS: u32 = 5;
 }
 
 /// Get the workspace directory from environment or default
@@ -52,11 +131,332 @@ pub fn soul_dir() -> String {
     env::var(env_vars::SOUL_DIR).unwrap_or_else(|_| defaults::SOUL_DIR.to_string())
 }
 
+/// Get the max captured output size for the exec tool from environment or default
+pub fn exec_max_output_bytes() -> usize {
+    env::var(env_vars::EXEC_MAX_OUTPUT_BYTES)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(defaults::EXEC_MAX_OUTPUT_BYTES)
+}
+
+/// Get the number of events kept per channel for replay to reconnecting clients (0 disables replay)
+pub fn gateway_replay_buffer_size() -> usize {
+    env::var(env_vars::GATEWAY_REPLAY_BUFFER_SIZE)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::GATEWAY_REPLAY_BUFFER_SIZE)
+}
+
+/// Whether logs are written as one JSON object per line (`STARK_LOG_FORMAT=json`)
+/// instead of the default human-readable text
+pub fn log_format_json() -> bool {
+    env::var(env_vars::LOG_FORMAT)
+        .map(|v| v.trim().eq_ignore_ascii_case("json"))
+        .unwrap_or(false)
+}
+
+/// Timezone that `daily_reset_hour` on chat sessions is interpreted in
+/// (IANA name, e.g. "America/New_York"). Defaults to UTC.
+pub fn session_reset_timezone() -> chrono_tz::Tz {
+    match env::var(env_vars::SESSION_RESET_TIMEZONE) {
+        Ok(name) => name.trim().parse().unwrap_or_else(|_| {
+            log::warn!("Invalid {} '{}', using UTC", env_vars::SESSION_RESET_TIMEZONE, name);
+            chrono_tz::UTC
+        }),
+        Err(_) => chrono_tz::UTC,
+    }
+}
+
+/// Per-tool execution timeouts in seconds, from a comma-separated list of
+/// `tool_name=secs` entries. Malformed entries are ignored.
+pub fn tool_timeout_overrides() -> std::collections::HashMap<String, u64> {
+    env::var(env_vars::TOOL_TIMEOUTS)
+        .map(|v| {
+            v.split(',')
+                .filter_map(|entry| {
+                    let (name, secs) = entry.split_once('=')?;
+                    let secs = secs.trim().parse().ok().filter(|s| *s > 0)?;
+                    Some((name.trim().to_string(), secs))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Deployment targets the deploy tool may use, from a comma-separated list
+/// of `target=workflow` entries. Malformed entries are ignored.
+pub fn deploy_targets() -> std::collections::HashMap<String, String> {
+    env::var(env_vars::DEPLOY_TARGETS)
+        .map(|v| {
+            v.split(',')
+                .filter_map(|entry| {
+                    let (target, workflow) = entry.split_once('=')?;
+                    let (target, workflow) = (target.trim(), workflow.trim());
+                    if target.is_empty() || workflow.is_empty() {
+                        return None;
+                    }
+                    Some((target.to_lowercase(), workflow.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Hosts that outbound HTTP tools may contact, from a comma-separated env var.
+/// An entry also allows its subdomains. Empty means any public host.
+pub fn outbound_url_allowlist() -> Vec<String> {
+    env::var(env_vars::OUTBOUND_URL_ALLOWLIST)
+        .map(|v| {
+            v.split(',')
+                .map(|h| h.trim().trim_start_matches("*.").to_lowercase())
+                .filter(|h| !h.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Minutes between portfolio snapshots (0 disables them)
+pub fn portfolio_snapshot_interval_mins() -> u64 {
+    env::var(env_vars::PORTFOLIO_SNAPSHOT_INTERVAL_MINS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::PORTFOLIO_SNAPSHOT_INTERVAL_MINS)
+}
+
+/// Networks that portfolio snapshots cover, from a comma-separated env var
+pub fn portfolio_networks() -> Vec<String> {
+    env::var(env_vars::PORTFOLIO_NETWORKS)
+        .unwrap_or_else(|_| defaults::PORTFOLIO_NETWORKS.to_string())
+        .split(',')
+        .map(|n| n.trim().to_lowercase())
+        .filter(|n| !n.is_empty())
+        .collect()
+}
+
+/// Networks x402 payments may settle on, most preferred first, from a
+/// comma-separated env var
+pub fn x402_networks() -> Vec<String> {
+    env::var(env_vars::X402_NETWORKS)
+        .unwrap_or_else(|_| defaults::X402_NETWORKS.to_string())
+        .split(',')
+        .map(|n| n.trim().to_lowercase())
+        .filter(|n| !n.is_empty())
+        .collect()
+}
+
+/// Assets x402 payments may be made in, as symbols or token addresses, from a
+/// comma-separated env var
+pub fn x402_assets() -> Vec<String> {
+    env::var(env_vars::X402_ASSETS)
+        .unwrap_or_else(|_| defaults::X402_ASSETS.to_string())
+        .split(',')
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+/// Seconds between checks of config/*.ron for changes (0 disables watching)
+pub fn config_watch_interval_secs() -> u64 {
+    env::var(env_vars::CONFIG_WATCH_INTERVAL_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::CONFIG_WATCH_INTERVAL_SECS)
+}
+
+/// Whether identical read-only tool calls within one dispatch return the
+/// cached result (default on; set to "false" or "0" to disable)
+pub fn tool_call_dedup_enabled() -> bool {
+    env::var(env_vars::TOOL_CALL_DEDUP)
+        .map(|v| !matches!(v.trim().to_lowercase().as_str(), "false" | "0" | "no" | "off"))
+        .unwrap_or(true)
+}
+
+/// Maximum read-only tool calls from one model response run at once
+/// (1 runs them sequentially)
+pub fn tool_concurrency() -> usize {
+    env::var(env_vars::TOOL_CONCURRENCY)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::TOOL_CONCURRENCY)
+}
+
+/// Maximum pending or running sub-agents per parent session
+pub fn subagent_max_concurrent() -> usize {
+    env::var(env_vars::SUBAGENT_MAX_CONCURRENT)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::SUBAGENT_MAX_CONCURRENT)
+}
+
+/// Maximum sub-agent nesting depth
+pub fn subagent_max_depth() -> usize {
+    env::var(env_vars::SUBAGENT_MAX_DEPTH)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::SUBAGENT_MAX_DEPTH)
+}
+
+/// Longest timeout a sub-agent may be given, in seconds
+pub fn subagent_max_timeout_secs() -> u64 {
+    env::var(env_vars::SUBAGENT_MAX_TIMEOUT_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::SUBAGENT_MAX_TIMEOUT_SECS)
+}
+
+/// Loop iterations the task planner gets to define tasks before the
+/// orchestrator moves on to assistant mode without a task list
+pub fn planner_max_iterations() -> u32 {
+    env::var(env_vars::PLANNER_MAX_ITERATIONS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::PLANNER_MAX_ITERATIONS)
+}
+
+/// Times per request the agent may discard its plan and replan
+pub fn max_replans() -> u32 {
+    env::var(env_vars::MAX_REPLANS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::MAX_REPLANS)
+}
+
+/// Default messages per identity per day (0 = unlimited)
+pub fn quota_messages_per_day() -> u64 {
+    env::var(env_vars::QUOTA_MESSAGES_PER_DAY)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::QUOTA_MESSAGES_PER_DAY)
+}
+
+/// Default tool calls per identity per day (0 = unlimited)
+pub fn quota_tool_calls_per_day() -> u64 {
+    env::var(env_vars::QUOTA_TOOL_CALLS_PER_DAY)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::QUOTA_TOOL_CALLS_PER_DAY)
+}
+
+/// Default x402 spend per identity per day, in USDC (0 = unlimited)
+pub fn quota_x402_usdc_per_day() -> f64 {
+    env::var(env_vars::QUOTA_X402_USDC_PER_DAY)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &f64| v.is_finite() && *v >= 0.0)
+        .unwrap_or(defaults::QUOTA_X402_USDC_PER_DAY)
+}
+
+/// Maximum number of items kept in a context bank
+pub fn context_bank_max_items() -> usize {
+    env::var(env_vars::CONTEXT_BANK_MAX_ITEMS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::CONTEXT_BANK_MAX_ITEMS)
+}
+
+/// Maximum total size in bytes of the items kept in a context bank
+pub fn context_bank_max_bytes() -> usize {
+    env::var(env_vars::CONTEXT_BANK_MAX_BYTES)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::CONTEXT_BANK_MAX_BYTES)
+}
+
 /// Get the burner wallet private key from environment (for tools)
 pub fn burner_wallet_private_key() -> Option<String> {
     env::var(env_vars::BURNER_WALLET_PRIVATE_KEY).ok()
 }
 
+/// Where transactions and orders are signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletSignerMode {
+    /// Sign in-process with BURNER_WALLET_BOT_PRIVATE_KEY (development)
+    Local,
+    /// Request signatures from the keystore; the key never enters this process
+    Keystore,
+}
+
+/// Get the wallet signer mode from environment (defaults to local)
+pub fn wallet_signer_mode() -> WalletSignerMode {
+    match env::var(env_vars::WALLET_SIGNER) {
+        Ok(mode) if mode.trim().eq_ignore_ascii_case("keystore") => WalletSignerMode::Keystore,
+        Ok(mode) if !mode.trim().is_empty() && !mode.trim().eq_ignore_ascii_case("local") => {
+            log::warn!("Invalid {} '{}', using local signer", env_vars::WALLET_SIGNER, mode);
+            WalletSignerMode::Local
+        }
+        _ => WalletSignerMode::Local,
+    }
+}
+
+/// Sliding window, in seconds, for the per-user inbound message limit
+pub fn flood_window_secs() -> u64 {
+    env::var(env_vars::FLOOD_WINDOW_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::FLOOD_WINDOW_SECS)
+}
+
+/// Messages a user may send per flood window before the rest are dropped (0 = no limit)
+pub fn flood_burst() -> usize {
+    env::var(env_vars::FLOOD_BURST)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::FLOOD_BURST)
+}
+
+/// Seconds a running channel may fail its health probes before the watchdog restarts it
+pub fn channel_stall_secs() -> u64 {
+    env::var(env_vars::CHANNEL_STALL_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::CHANNEL_STALL_SECS)
+}
+
+/// Restarts in a row the watchdog attempts before disabling a failing channel
+pub fn channel_max_restarts() -> u32 {
+    env::var(env_vars::CHANNEL_MAX_RESTARTS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::CHANNEL_MAX_RESTARTS)
+}
+
+/// Get the API key that authenticates `POST /api/webhook`. The endpoint is
+/// disabled when this isn't set.
+pub fn webhook_api_key() -> Option<String> {
+    env::var(env_vars::WEBHOOK_API_KEY).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Get the bearer token used to request signatures from the keystore
+pub fn keystore_signer_token() -> Option<String> {
+    env::var(env_vars::KEYSTORE_SIGNER_TOKEN).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Additional named wallets from a comma-separated list of `name=source`
+/// entries, where source is a private key or `keystore`. Names are lowercased;
+/// malformed entries are ignored.
+pub fn named_wallets() -> Vec<(String, String)> {
+    env::var(env_vars::WALLETS)
+        .map(|v| {
+            v.split(',')
+                .filter_map(|entry| {
+                    let (name, source) = entry.split_once('=')?;
+                    let name = name.trim().to_lowercase();
+                    let source = source.trim();
+                    (!name.is_empty() && !source.is_empty()).then(|| (name, source.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Get the Google OAuth client ID and secret used to refresh Gmail access tokens
+pub fn google_oauth_client() -> Option<(String, String)> {
+    let client_id = env::var(env_vars::GOOGLE_OAUTH_CLIENT_ID).ok().filter(|v| !v.is_empty())?;
+    let client_secret = env::var(env_vars::GOOGLE_OAUTH_CLIENT_SECRET).ok().filter(|v| !v.is_empty())?;
+    Some((client_id, client_secret))
+}
+
 /// Derive the public address from a private key
 fn derive_address_from_private_key(private_key: &str) -> Result<String, String> {
     let key_hex = private_key.strip_prefix("0x").unwrap_or(private_key);
@@ -7,6 +7,7 @@
 //! - Summary chaining (preserve context across compactions)
 //! - Pre-compaction memory flush (AI extracts memories before summarization)
 //! - Cross-session memory integration
+//! - History windows sized to the model's token budget
 //! - Session memory hooks (saving session summaries on reset)
 
 pub mod tokenizer;
@@ -33,6 +34,23 @@ pub const MIN_KEEP_RECENT_MESSAGES: i32 = 5;
 /// Default number of messages to keep after compaction
 pub const DEFAULT_KEEP_RECENT_MESSAGES: i32 = 10;
 
+/// Most history messages loaded into a prompt, unless the channel sets its own
+/// limit. The token budget usually trims the window further.
+pub const DEFAULT_HISTORY_MESSAGES: i32 = 50;
+
+/// Pick the context window a session compacts against: the session's own
+/// override, else the agent settings value if it was changed from the default,
+/// else the archetype's real window.
+pub fn resolve_context_window(session_override: Option<i32>, settings_max: i32, archetype_window: i32) -> i32 {
+    if let Some(window) = session_override {
+        return window;
+    }
+    if settings_max != crate::models::agent_settings::DEFAULT_CONTEXT_TOKENS {
+        return settings_max;
+    }
+    archetype_window
+}
+
 /// Configuration for sliding window (incremental) compaction
 #[derive(Debug, Clone)]
 pub struct SlidingWindowConfig {
@@ -73,6 +91,28 @@ pub fn estimate_messages_tokens(messages: &[SessionMessage]) -> i32 {
         .sum()
 }
 
+/// Trim history oldest-first until its estimated size fits `budget` tokens.
+/// The newest message (the current request) is always kept, even alone over budget.
+pub fn fit_history_to_budget(mut messages: Vec<SessionMessage>, budget: i32) -> Vec<SessionMessage> {
+    let estimator = TokenEstimator::ContentAware;
+    let mut used = 0;
+    let mut keep = 0;
+    for message in messages.iter().rev() {
+        let tokens = estimator.estimate_message(&message.content, &message.role);
+        if keep > 0 && used + tokens > budget {
+            break;
+        }
+        used += tokens;
+        keep += 1;
+    }
+
+    let dropped = messages.len() - keep;
+    if dropped > 0 {
+        log::info!("[CONTEXT] Trimmed {} oldest history messages to fit {} tokens", dropped, budget);
+    }
+    messages.split_off(dropped)
+}
+
 /// Context manager for handling session context and compaction
 pub struct ContextManager {
     db: Arc<Database>,
@@ -169,6 +209,12 @@ impl ContextManager {
         self.max_context_tokens - self.reserve_tokens
     }
 
+    /// Tokens left for history in a context window once the reserve and the
+    /// system prompt are accounted for
+    pub fn history_budget(&self, context_window: i32, system_prompt_tokens: i32) -> i32 {
+        (context_window - self.reserve_tokens - system_prompt_tokens).max(0)
+    }
+
     /// Build conversation context for AI, including compaction summary if present
     pub fn build_context(&self, session_id: i64, limit: i32) -> Vec<SessionMessage> {
         // Get recent messages
@@ -683,12 +729,14 @@ impl ContextManager {
 
     /// Build context with optional memory retrieval
     /// Returns (messages, combined_context_summary)
-    /// The combined_context includes both compaction summary and cross-session memories
+    /// The combined_context includes both compaction summary and cross-session memories.
+    /// History is trimmed oldest-first so it fits `token_budget` alongside them.
     pub fn build_context_with_memories(
         &self,
         session_id: i64,
         identity_id: Option<&str>,
         limit: i32,
+        token_budget: i32,
     ) -> (Vec<SessionMessage>, Option<String>) {
         let messages = self.build_context(session_id, limit);
         let compaction_summary = self.get_compaction_summary(session_id);
@@ -707,6 +755,9 @@ impl ContextManager {
             (None, None) => None,
         };
 
+        let summary_tokens = combined.as_deref().map(estimate_tokens).unwrap_or(0);
+        let messages = fit_history_to_budget(messages, token_budget - summary_tokens);
+
         (messages, combined)
     }
 }
@@ -837,6 +888,16 @@ fn parse_title_summary(response: &str) -> (String, String) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_resolve_context_window() {
+        // Default settings: the archetype's window wins
+        assert_eq!(resolve_context_window(None, DEFAULT_MAX_CONTEXT_TOKENS, 200_000), 200_000);
+        // An explicit agent setting beats the archetype
+        assert_eq!(resolve_context_window(None, 150_000, 200_000), 150_000);
+        // A session override beats both
+        assert_eq!(resolve_context_window(Some(32_000), 150_000, 200_000), 32_000);
+    }
+
     #[test]
     fn test_estimate_tokens() {
         // Roughly 4 chars per token
@@ -849,6 +910,46 @@ mod tests {
         assert!(tokens >= 10 && tokens <= 50);
     }
 
+    fn message(id: i64, role: DbMessageRole, content: &str) -> SessionMessage {
+        SessionMessage {
+            id,
+            session_id: 1,
+            role,
+            content: content.to_string(),
+            user_id: None,
+            user_name: None,
+            platform_message_id: None,
+            tokens_used: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_fit_history_to_budget() {
+        let history: Vec<SessionMessage> = (0..40)
+            .map(|i| {
+                let role = if i % 2 == 0 { DbMessageRole::User } else { DbMessageRole::Assistant };
+                message(i, role, &format!("Message {} about swapping some tokens on Base. ", i).repeat(10))
+            })
+            .collect();
+        let total = estimate_messages_tokens(&history);
+
+        // Everything fits in a large budget
+        assert_eq!(fit_history_to_budget(history.clone(), total).len(), 40);
+
+        // A small budget keeps the newest messages, within budget
+        let budget = total / 4;
+        let fitted = fit_history_to_budget(history.clone(), budget);
+        assert!(!fitted.is_empty() && fitted.len() < 40);
+        assert!(estimate_messages_tokens(&fitted) <= budget);
+        assert_eq!(fitted.last().map(|m| m.id), Some(39));
+        assert!(fitted.windows(2).all(|w| w[0].id + 1 == w[1].id));
+
+        // The current request survives even when nothing fits
+        let fitted = fit_history_to_budget(history, 0);
+        assert_eq!(fitted.iter().map(|m| m.id).collect::<Vec<_>>(), vec![39]);
+    }
+
     #[test]
     fn test_parse_title_summary() {
         let response = "TITLE: Discussion about Rust programming\nSUMMARY: User asked about ownership and borrowing in Rust.";
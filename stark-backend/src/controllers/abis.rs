@@ -0,0 +1,176 @@
+//! ABI registry API - list, view and upload ABIs for `web3_function_call`
+//!
+//! Uploaded ABIs are stored in the database and override the files in
+//! abis/ with the same name.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+
+use crate::tools::abis::{self, AbiFile};
+use crate::tools::presets;
+use crate::AppState;
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+/// Listing entry: everything but the ABI itself, plus its function names
+fn abi_summary(name: &str, source: &str, abi: &AbiFile) -> serde_json::Value {
+    serde_json::json!({
+        "name": name,
+        "source": source,
+        "display_name": abi.name,
+        "description": abi.description,
+        "functions": abi.function_names(),
+        "address": abi.address,
+    })
+}
+
+/// List all ABIs: uploaded ones first, then files they don't override
+async fn list_abis(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let stored = match data.db.list_abis() {
+        Ok(s) => s,
+        Err(e) => {
+            log::error!("Failed to list ABIs: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    };
+
+    let mut list: Vec<serde_json::Value> = stored
+        .iter()
+        .map(|a| abi_summary(&a.name, "database", &a.abi))
+        .collect();
+
+    let abis_dir = presets::abis_dir();
+    for name in abis::list_file_abis(&abis_dir) {
+        if stored.iter().any(|a| a.name == name) {
+            continue;
+        }
+        match abis::load_file_abi(&abis_dir, &name) {
+            Ok(abi) => list.push(abi_summary(&name, "file", &abi)),
+            Err(e) => log::warn!("Skipping ABI file '{}': {}", name, e),
+        }
+    }
+
+    HttpResponse::Ok().json(serde_json::json!({ "abis": list }))
+}
+
+/// Get one ABI as `web3_function_call` resolves it
+async fn get_abi(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let name = path.into_inner();
+    if let Err(e) = abis::validate_abi_name(&name) {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+
+    match data.db.get_abi(&name) {
+        Ok(Some(stored)) => HttpResponse::Ok().json(serde_json::json!({
+            "name": stored.name,
+            "source": "database",
+            "abi": stored.abi,
+            "updated_at": stored.updated_at,
+        })),
+        Ok(None) => match abis::load_file_abi(&presets::abis_dir(), &name) {
+            Ok(abi) => HttpResponse::Ok().json(serde_json::json!({
+                "name": name,
+                "source": "file",
+                "abi": abi,
+            })),
+            Err(_) => HttpResponse::NotFound().json(serde_json::json!({
+                "error": "ABI not found"
+            })),
+        },
+        Err(e) => {
+            log::error!("Failed to get ABI: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+/// Upload (create or replace) an ABI
+async fn upload_abi(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<AbiFile>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let name = path.into_inner();
+    let abi = body.into_inner();
+    if let Err(e) = abis::validate_abi_name(&name).and_then(|_| abis::validate_abi(&abi)) {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+
+    match data.db.upsert_abi(&name, &abi) {
+        Ok(stored) => {
+            abis::cache_db_abi(&name, Some(stored.abi.clone()));
+            log::info!("Uploaded ABI '{}' ({} functions)", name, stored.abi.function_names().len());
+            HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "abi": abi_summary(&stored.name, "database", &stored.abi),
+            }))
+        }
+        Err(e) => {
+            log::error!("Failed to save ABI: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/abis")
+            .route("", web::get().to(list_abis))
+            .route("/{name}", web::get().to(get_abi))
+            .route("/{name}", web::put().to(upload_abi)),
+    );
+}
@@ -1,8 +1,14 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use super::config_audit::audit_changes;
 use crate::ai::ArchetypeId;
 use crate::models::{AgentSettings, AgentSettingsResponse, UpdateAgentSettingsRequest, UpdateBotSettingsRequest};
 use crate::tools::rpc_config;
 use crate::AppState;
+use serde::Deserialize;
+
+/// Default and maximum page sizes for the rogue mode audit trail
+const DEFAULT_ROGUE_AUDIT_LIMIT: usize = 100;
+const MAX_ROGUE_AUDIT_LIMIT: usize = 500;
 
 /// Validate session token from request
 fn validate_session_from_request(
@@ -153,6 +159,8 @@ pub async fn update_agent_settings(
         }));
     }
 
+    let previous = state.db.get_active_agent_settings().ok().flatten();
+
     // Save settings
     log::info!(
         "Saving agent settings: endpoint={}, archetype={}, max_response_tokens={}, max_context_tokens={}, has_secret_key={}",
@@ -166,6 +174,7 @@ pub async fn update_agent_settings(
     match state.db.save_agent_settings(&request.endpoint, &request.model_archetype, request.max_response_tokens, request.max_context_tokens, request.secret_key.as_deref()) {
         Ok(settings) => {
             log::info!("Updated agent settings to use {} endpoint with {} archetype", request.endpoint, request.model_archetype);
+            audit_changes(&state, &req, "agent_settings", previous.as_ref(), Some(&settings));
             let response: AgentSettingsResponse = settings.into();
             HttpResponse::Ok().json(response)
         }
@@ -186,9 +195,14 @@ pub async fn disable_agent(
     if let Err(resp) = validate_session_from_request(&state, &req) {
         return resp;
     }
+    let previous = state.db.get_active_agent_settings().ok().flatten();
     match state.db.disable_agent_settings() {
         Ok(_) => {
             log::info!("Disabled AI agent");
+            if let Some(previous) = previous {
+                let disabled = AgentSettings { enabled: false, ..previous.clone() };
+                audit_changes(&state, &req, "agent_settings", Some(&previous), Some(&disabled));
+            }
             HttpResponse::Ok().json(serde_json::json!({
                 "success": true,
                 "message": "AI agent disabled"
@@ -233,6 +247,26 @@ pub async fn update_bot_settings(
     }
     let request = body.into_inner();
 
+    let previous = match state.db.get_bot_settings() {
+        Ok(settings) => settings,
+        Err(e) => {
+            log::error!("Failed to get bot settings: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    };
+
+    // Turning rogue mode on needs an explicit second confirmation
+    if request.rogue_mode_enabled == Some(true)
+        && !previous.rogue_mode_enabled
+        && request.confirm_rogue_mode != Some(true)
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Enabling rogue mode requires \"confirm_rogue_mode\": true"
+        }));
+    }
+
     // Validate rpc_provider if provided
     if let Some(ref provider) = request.rpc_provider {
         if provider != "custom" && rpc_config::get_rpc_provider(provider).is_none() {
@@ -242,6 +276,34 @@ pub async fn update_bot_settings(
         }
     }
 
+    // Normalize exec allowlist/denylist entries
+    let clean_list = |list: &Option<Vec<String>>| -> Option<Vec<String>> {
+        list.as_ref().map(|l| {
+            l.iter()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+    };
+    let exec_allowlist = clean_list(&request.exec_allowlist);
+    let exec_denylist = clean_list(&request.exec_denylist);
+
+    // Validate moderation endpoint if moderation is being configured
+    if let Some(ref moderation) = request.moderation {
+        let endpoint = moderation.endpoint.trim();
+        if moderation.enabled && !(endpoint.starts_with("https://") || endpoint.starts_with("http://")) {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Moderation endpoint must be an http(s) URL"
+            }));
+        }
+    }
+
+    if let Some(ref limits) = request.transfer_limits {
+        if let Err(e) = limits.validate() {
+            return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+        }
+    }
+
     match state.db.update_bot_settings_full(
         request.bot_name.as_deref(),
         request.bot_email.as_deref(),
@@ -250,6 +312,10 @@ pub async fn update_bot_settings(
         request.custom_rpc_endpoints.as_ref(),
         request.max_tool_iterations,
         request.rogue_mode_enabled,
+        exec_allowlist.as_deref(),
+        exec_denylist.as_deref(),
+        request.moderation.as_ref(),
+        request.transfer_limits.as_ref(),
     ) {
         Ok(settings) => {
             log::info!(
@@ -258,6 +324,18 @@ pub async fn update_bot_settings(
                 settings.bot_email,
                 settings.rpc_provider
             );
+            audit_changes(&state, &req, "bot_settings", Some(&previous), Some(&settings));
+            if settings.rogue_mode_enabled != previous.rogue_mode_enabled {
+                let action = if settings.rogue_mode_enabled {
+                    "rogue_mode_enabled"
+                } else {
+                    "rogue_mode_disabled"
+                };
+                log::warn!("[ROGUE] {}", action);
+                if let Err(e) = state.db.record_rogue_audit(action, None, None, None, None) {
+                    log::error!("Failed to record rogue mode audit: {}", e);
+                }
+            }
             HttpResponse::Ok().json(settings)
         }
         Err(e) => {
@@ -269,6 +347,39 @@ pub async fn update_bot_settings(
     }
 }
 
+#[derive(Deserialize)]
+pub struct RogueAuditQuery {
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+/// Get the rogue mode audit trail, newest first
+pub async fn get_rogue_audit(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<RogueAuditQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_ROGUE_AUDIT_LIMIT).min(MAX_ROGUE_AUDIT_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+
+    match state.db.list_rogue_audit(limit, offset) {
+        Ok(entries) => HttpResponse::Ok().json(serde_json::json!({
+            "entries": entries,
+            "limit": limit,
+            "offset": offset,
+        })),
+        Err(e) => {
+            log::error!("Failed to list rogue mode audit: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
 /// Get available RPC providers
 pub async fn get_rpc_providers(
     state: web::Data<AppState>,
@@ -317,6 +428,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         web::scope("/api/bot-settings")
             .route("", web::get().to(get_bot_settings))
             .route("", web::put().to(update_bot_settings))
+            .route("/rogue-audit", web::get().to(get_rogue_audit))
     );
     cfg.service(
         web::resource("/api/rpc-providers")
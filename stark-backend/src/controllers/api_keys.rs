@@ -1,9 +1,12 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use ethers::signers::{LocalWallet, Signer};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use strum::{AsRefStr, EnumIter, EnumString, IntoEnumIterator};
 
+use super::config_audit::request_actor;
 use crate::keystore_client::KEYSTORE_CLIENT;
+use crate::tools::builtin::social_media::oauth_header;
 use crate::models::ApiKeyResponse;
 use crate::AppState;
 
@@ -42,6 +45,8 @@ pub enum ApiKeyId {
     TwitterAccessToken,
     #[strum(serialize = "TWITTER_ACCESS_TOKEN_SECRET")]
     TwitterAccessTokenSecret,
+    #[strum(serialize = "MODERATION_API_KEY")]
+    ModerationApiKey,
 }
 
 impl ApiKeyId {
@@ -62,6 +67,7 @@ impl ApiKeyId {
             Self::TwitterConsumerSecret => "TWITTER_CONSUMER_SECRET",
             Self::TwitterAccessToken => "TWITTER_ACCESS_TOKEN",
             Self::TwitterAccessTokenSecret => "TWITTER_ACCESS_TOKEN_SECRET",
+            Self::ModerationApiKey => "MODERATION_API_KEY",
         }
     }
 
@@ -81,6 +87,7 @@ impl ApiKeyId {
             Self::TwitterConsumerSecret => Some(&["TWITTER_CONSUMER_SECRET", "TWITTER_API_SECRET"]),
             Self::TwitterAccessToken => Some(&["TWITTER_ACCESS_TOKEN"]),
             Self::TwitterAccessTokenSecret => Some(&["TWITTER_ACCESS_TOKEN_SECRET"]),
+            Self::ModerationApiKey => None,
         }
     }
 
@@ -253,6 +260,17 @@ pub fn get_service_configs() -> Vec<ServiceConfig> {
                 },
             ],
         },
+        ServiceConfig {
+            group: "moderation",
+            label: "Content Moderation",
+            description: "Bearer key for the moderation endpoint configured in Agent Settings (e.g. an OpenAI API key)",
+            url: "https://platform.openai.com/api-keys",
+            keys: vec![KeyConfig {
+                name: "MODERATION_API_KEY",
+                label: "API Key",
+                secret: true,
+            }],
+        },
     ]
 }
 
@@ -281,6 +299,13 @@ pub fn get_key_config(key_name: &str) -> Option<(&'static str, KeyConfig)> {
 pub struct UpsertApiKeyRequest {
     pub key_name: String,
     pub api_key: String,
+    /// Check the key against its service before storing it (known services only)
+    #[serde(default = "default_validate")]
+    pub validate: bool,
+}
+
+fn default_validate() -> bool {
+    true
 }
 
 #[derive(Debug, Deserialize)]
@@ -302,10 +327,46 @@ pub struct ApiKeyOperationResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub key: Option<ApiKeyResponse>,
+    /// Result of checking the key against its service, when it was checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation: Option<KeyValidation>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
 
+/// Whether a service accepted a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyValidationStatus {
+    Valid,
+    /// The service rejected the key
+    Invalid,
+    /// The service couldn't be reached or gave an unexpected answer
+    Unchecked,
+}
+
+/// Outcome of checking a key when it is saved. The key is stored either
+/// way; anything but `Valid` is a warning for the user.
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyValidation {
+    pub status: KeyValidationStatus,
+    pub message: String,
+}
+
+impl KeyValidation {
+    fn valid(message: impl Into<String>) -> Self {
+        Self { status: KeyValidationStatus::Valid, message: message.into() }
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self { status: KeyValidationStatus::Invalid, message: message.into() }
+    }
+
+    fn unchecked(message: impl Into<String>) -> Self {
+        Self { status: KeyValidationStatus::Unchecked, message: message.into() }
+    }
+}
+
 /// Response for service configs endpoint
 #[derive(Serialize)]
 pub struct ServiceConfigsResponse {
@@ -490,6 +551,7 @@ async fn upsert_api_key(
         return HttpResponse::BadRequest().json(ApiKeyOperationResponse {
             success: false,
             key: None,
+            validation: None,
             error: Some(format!(
                 "Invalid key name. Valid options: {}",
                 valid_keys.join(", ")
@@ -502,22 +564,45 @@ async fn upsert_api_key(
         return HttpResponse::BadRequest().json(ApiKeyOperationResponse {
             success: false,
             key: None,
+            validation: None,
             error: Some("API key cannot be empty".to_string()),
         });
     }
 
+    let previous = state.db.get_api_key(&body.key_name).ok().flatten();
+
+    // Check the key first, but store it whatever the result: the service may
+    // not be live yet
+    let validation = match ApiKeyId::from_str(&body.key_name) {
+        Ok(key_id) if body.validate => validate_api_key(&state, key_id, body.api_key.trim()).await,
+        _ => None,
+    };
+    if let Some(ref v) = validation {
+        if v.status != KeyValidationStatus::Valid {
+            log::warn!("[API_KEYS] {} saved with a warning: {}", body.key_name, v.message);
+        }
+    }
+
     // Store the key (key_name is the service_name in the database)
     match state.db.upsert_api_key(&body.key_name, &body.api_key) {
-        Ok(key) => HttpResponse::Ok().json(ApiKeyOperationResponse {
-            success: true,
-            key: Some(key.to_response()),
-            error: None,
-        }),
+        Ok(key) => {
+            let old_value = previous.as_ref().map(|k| k.api_key.as_str());
+            if old_value != Some(body.api_key.as_str()) {
+                audit_api_key(&state, &req, &body.key_name, old_value, Some(&body.api_key));
+            }
+            HttpResponse::Ok().json(ApiKeyOperationResponse {
+                success: true,
+                key: Some(key.to_response()),
+                validation,
+                error: None,
+            })
+        }
         Err(e) => {
             log::error!("Failed to save API key: {}", e);
             HttpResponse::InternalServerError().json(ApiKeyOperationResponse {
                 success: false,
                 key: None,
+                validation: None,
                 error: Some("Failed to save API key".to_string()),
             })
         }
@@ -533,18 +618,24 @@ async fn delete_api_key(
         return resp;
     }
 
+    let previous = state.db.get_api_key(&body.key_name).ok().flatten();
+
     match state.db.delete_api_key(&body.key_name) {
         Ok(deleted) => {
             if deleted {
+                let old_value = previous.as_ref().map(|k| k.api_key.as_str());
+                audit_api_key(&state, &req, &body.key_name, old_value, None);
                 HttpResponse::Ok().json(ApiKeyOperationResponse {
                     success: true,
                     key: None,
+                    validation: None,
                     error: None,
                 })
             } else {
                 HttpResponse::NotFound().json(ApiKeyOperationResponse {
                     success: false,
                     key: None,
+                    validation: None,
                     error: Some("API key not found".to_string()),
                 })
             }
@@ -554,6 +645,7 @@ async fn delete_api_key(
             HttpResponse::InternalServerError().json(ApiKeyOperationResponse {
                 success: false,
                 key: None,
+                validation: None,
                 error: Some("Failed to delete API key".to_string()),
             })
         }
@@ -764,10 +856,15 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
     for key in &restored_keys {
         // Only restore valid key names
         if get_valid_key_names().contains(&key.key_name.as_str()) {
+            let previous = state.db.get_api_key(&key.key_name).ok().flatten();
             if let Err(e) = state.db.upsert_api_key(&key.key_name, &key.key_value) {
                 log::error!("Failed to restore key {}: {}", key.key_name, e);
             } else {
                 restored_count += 1;
+                let old_value = previous.as_ref().map(|k| k.api_key.as_str());
+                if old_value != Some(key.key_value.as_str()) {
+                    audit_api_key(&state, &req, &key.key_name, old_value, Some(&key.key_value));
+                }
             }
         }
     }
@@ -780,6 +877,147 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
     })
 }
 
+/// How long to wait for a service when checking a key
+const KEY_VALIDATION_TIMEOUT_SECS: u64 = 10;
+
+/// Check a key with a cheap authenticated call to its service. `None` for
+/// services without a check, and for Twitter keys until all four are set.
+async fn validate_api_key(
+    state: &web::Data<AppState>,
+    key_id: ApiKeyId,
+    value: &str,
+) -> Option<KeyValidation> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(KEY_VALIDATION_TIMEOUT_SECS))
+        .user_agent("StarkBot/1.0")
+        .build()
+        .ok()?;
+
+    let (service, request) = match key_id {
+        ApiKeyId::GithubToken => (
+            "GitHub",
+            client.get("https://api.github.com/user").bearer_auth(value),
+        ),
+        ApiKeyId::DiscordBotToken => (
+            "Discord",
+            client
+                .get("https://discord.com/api/v10/users/@me")
+                .header("Authorization", format!("Bot {}", value)),
+        ),
+        ApiKeyId::TelegramBotToken => (
+            "Telegram",
+            client.get(format!("https://api.telegram.org/bot{}/getMe", value)),
+        ),
+        ApiKeyId::SlackBotToken => (
+            "Slack",
+            client.post("https://slack.com/api/auth.test").bearer_auth(value),
+        ),
+        ApiKeyId::TwitterConsumerKey
+        | ApiKeyId::TwitterConsumerSecret
+        | ApiKeyId::TwitterAccessToken
+        | ApiKeyId::TwitterAccessTokenSecret => {
+            // OAuth 1.0a needs all four; the others come from the stored keys
+            let credential = |id: ApiKeyId| {
+                if id == key_id {
+                    return Some(value.to_string());
+                }
+                state.db.get_api_key(id.as_str()).ok().flatten().map(|k| k.api_key)
+            };
+            let url = "https://api.twitter.com/2/users/me";
+            let header = oauth_header(
+                "GET",
+                url,
+                &credential(ApiKeyId::TwitterConsumerKey)?,
+                &credential(ApiKeyId::TwitterConsumerSecret)?,
+                &credential(ApiKeyId::TwitterAccessToken)?,
+                &credential(ApiKeyId::TwitterAccessTokenSecret)?,
+            );
+            ("Twitter", client.get(url).header("Authorization", header))
+        }
+        ApiKeyId::ModerationApiKey => {
+            let endpoint = state.db.get_bot_settings().ok()?.moderation.endpoint;
+            (
+                "The moderation endpoint",
+                client
+                    .post(endpoint.trim())
+                    .bearer_auth(value)
+                    .json(&serde_json::json!({ "input": "ping" })),
+            )
+        }
+        _ => return None,
+    };
+
+    let response = match request.send().await {
+        Ok(r) => r,
+        Err(e) => {
+            // The Telegram URL contains the token
+            return Some(KeyValidation::unchecked(format!(
+                "Could not reach {} to check the key: {}",
+                service,
+                e.without_url()
+            )));
+        }
+    };
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED
+        || status == reqwest::StatusCode::FORBIDDEN
+        // Telegram answers 404 for a malformed bot token
+        || (key_id == ApiKeyId::TelegramBotToken && status == reqwest::StatusCode::NOT_FOUND)
+    {
+        return Some(KeyValidation::invalid(format!("{} rejected the key ({})", service, status)));
+    }
+    if !status.is_success() {
+        return Some(KeyValidation::unchecked(format!(
+            "{} returned {}; the key could not be checked",
+            service, status
+        )));
+    }
+
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let account = match key_id {
+        ApiKeyId::GithubToken => body["login"].as_str(),
+        ApiKeyId::DiscordBotToken => body["username"].as_str(),
+        ApiKeyId::TelegramBotToken => body["result"]["username"].as_str(),
+        ApiKeyId::SlackBotToken => {
+            // Slack reports auth failures in the body with a 200
+            if body["ok"].as_bool() != Some(true) {
+                let error = body["error"].as_str().unwrap_or("unknown error");
+                return Some(KeyValidation::invalid(format!("Slack rejected the key ({})", error)));
+            }
+            body["user"].as_str()
+        }
+        ApiKeyId::TwitterConsumerKey
+        | ApiKeyId::TwitterConsumerSecret
+        | ApiKeyId::TwitterAccessToken
+        | ApiKeyId::TwitterAccessTokenSecret => body["data"]["username"].as_str(),
+        _ => None,
+    };
+
+    Some(KeyValidation::valid(match account {
+        Some(account) => format!("{} accepted the key (authenticated as {})", service, account),
+        None => format!("{} accepted the key", service),
+    }))
+}
+
+/// Record an API key change in the config audit. Values are redacted; only
+/// whether the key was set, replaced or removed is kept.
+fn audit_api_key(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+    key_name: &str,
+    old_value: Option<&str>,
+    new_value: Option<&str>,
+) {
+    let actor = request_actor(state, req);
+    if let Err(e) = state
+        .db
+        .record_config_change(actor.as_deref(), "api_keys", key_name, old_value, new_value, true)
+    {
+        log::error!("Failed to record config audit for API key {}: {}", key_name, e);
+    }
+}
+
 /// Encrypt data using ECIES with the public key derived from private key
 fn encrypt_with_private_key(private_key: &str, data: &str) -> Result<String, String> {
     use ecies::{encrypt, PublicKey, SecretKey};
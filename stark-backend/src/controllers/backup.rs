@@ -0,0 +1,257 @@
+//! Database backup and restore
+//!
+//! Backups are consistent snapshots taken with SQLite's online backup API and
+//! are safe to take while the bot is serving traffic. The full variant wraps
+//! the snapshot in a ZIP with SOUL.md and the skills directory.
+
+use actix_files::NamedFile;
+use actix_multipart::Multipart;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{mime, web, HttpRequest, HttpResponse, Responder};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::{config, AppState};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/api/backup/download").route(web::get().to(download_backup)))
+        .service(web::resource("/api/backup/restore").route(web::post().to(restore_backup)));
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadQuery {
+    /// Include SOUL.md and the skills directory in a ZIP archive
+    #[serde(default)]
+    full: bool,
+}
+
+#[derive(Serialize)]
+struct BackupResponse {
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// Where the database was saved before it was replaced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pre_restore_backup: Option<String>,
+}
+
+impl BackupResponse {
+    fn error(error: impl Into<String>) -> Self {
+        Self { success: false, message: None, error: Some(error.into()), pre_restore_backup: None }
+    }
+}
+
+fn validate_session_from_request(state: &web::Data<AppState>, req: &HttpRequest) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => return Err(HttpResponse::Unauthorized().json(BackupResponse::error("No authorization token provided"))),
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(BackupResponse::error("Invalid or expired session"))),
+        Err(e) => {
+            log::error!("Failed to validate session: {}", e);
+            Err(HttpResponse::InternalServerError().json(BackupResponse::error("Internal server error")))
+        }
+    }
+}
+
+/// Unique scratch path in the system temp directory
+fn temp_path(extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("starkbot-backup-{}.{}", uuid::Uuid::new_v4(), extension))
+}
+
+/// Download a database snapshot, or with `?full=true` a ZIP that also holds
+/// SOUL.md and the skills directory
+async fn download_backup(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<DownloadQuery>,
+) -> HttpResponse {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let db = state.db.clone();
+    let full = query.full;
+    let built = web::block(move || -> Result<PathBuf, String> {
+        let snapshot = temp_path("db");
+        db.backup_to(&snapshot)?;
+        if !full {
+            return Ok(snapshot);
+        }
+        let archive = temp_path("zip");
+        let result = build_archive(&snapshot, &archive);
+        let _ = std::fs::remove_file(&snapshot);
+        result.map(|_| archive)
+    })
+    .await;
+
+    let path = match built {
+        Ok(Ok(path)) => path,
+        Ok(Err(e)) => {
+            log::error!("[BACKUP] Failed to create backup: {}", e);
+            return HttpResponse::InternalServerError().json(BackupResponse::error(e));
+        }
+        Err(e) => return HttpResponse::InternalServerError().json(BackupResponse::error(e.to_string())),
+    };
+
+    let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+    let filename = if full {
+        format!("starkbot-backup-{}.zip", stamp)
+    } else {
+        format!("starkbot-{}.db", stamp)
+    };
+    log::info!("[BACKUP] Serving {}", filename);
+
+    // Stream from an open handle; unlinking now leaves nothing behind once sent
+    let file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(e) => return HttpResponse::InternalServerError().json(BackupResponse::error(e.to_string())),
+    };
+    let _ = std::fs::remove_file(&path);
+
+    match NamedFile::from_file(file, &filename) {
+        Ok(named) => named
+            .set_content_type(if full { mime_zip() } else { mime::APPLICATION_OCTET_STREAM })
+            .set_content_disposition(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(filename)],
+            })
+            .into_response(&req),
+        Err(e) => HttpResponse::InternalServerError().json(BackupResponse::error(e.to_string())),
+    }
+}
+
+fn mime_zip() -> mime::Mime {
+    "application/zip".parse().unwrap_or(mime::APPLICATION_OCTET_STREAM)
+}
+
+/// Zip the database snapshot with SOUL.md and the skills directory
+fn build_archive(snapshot: &Path, archive: &Path) -> Result<(), String> {
+    let file = std::fs::File::create(archive).map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut add_file = |name: &str, path: &Path| -> Result<(), String> {
+        let mut src = std::fs::File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        std::io::copy(&mut src, &mut zip).map_err(|e| e.to_string())?;
+        Ok(())
+    };
+
+    add_file("stark.db", snapshot)?;
+
+    let soul = config::soul_document_path();
+    if soul.is_file() {
+        add_file("soul/SOUL.md", &soul)?;
+    }
+
+    let skills_dir = PathBuf::from(config::skills_dir());
+    for entry in walkdir::WalkDir::new(&skills_dir).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if let Ok(relative) = entry.path().strip_prefix(&skills_dir) {
+            let name = format!("skills/{}", relative.to_string_lossy().replace('\\', "/"));
+            add_file(&name, entry.path())?;
+        }
+    }
+
+    zip.finish().map_err(|e| e.to_string())?.flush().map_err(|e| e.to_string())
+}
+
+/// Replace the database with an uploaded backup. The current database is
+/// saved next to it first, and the upload is validated before anything changes.
+async fn restore_backup(state: web::Data<AppState>, req: HttpRequest, mut payload: Multipart) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    // Spool the upload to disk; databases can be large
+    let upload = temp_path("db");
+    let mut written = 0usize;
+    {
+        let mut file = match std::fs::File::create(&upload) {
+            Ok(file) => file,
+            Err(e) => return HttpResponse::InternalServerError().json(BackupResponse::error(e.to_string())),
+        };
+        while let Some(item) = payload.next().await {
+            let mut field = match item {
+                Ok(field) => field,
+                Err(e) => {
+                    let _ = std::fs::remove_file(&upload);
+                    return HttpResponse::BadRequest().json(BackupResponse::error(format!("Failed to process upload: {}", e)));
+                }
+            };
+            while let Some(chunk) = field.next().await {
+                let written_ok = chunk
+                    .map_err(|e| e.to_string())
+                    .and_then(|data| file.write_all(&data).map(|_| data.len()).map_err(|e| e.to_string()));
+                match written_ok {
+                    Ok(n) => written += n,
+                    Err(e) => {
+                        let _ = std::fs::remove_file(&upload);
+                        return HttpResponse::BadRequest().json(BackupResponse::error(format!("Failed to read upload data: {}", e)));
+                    }
+                }
+            }
+        }
+    }
+
+    if written == 0 {
+        let _ = std::fs::remove_file(&upload);
+        return HttpResponse::BadRequest().json(BackupResponse::error("No file uploaded"));
+    }
+
+    let db = state.db.clone();
+    let pre_restore = PathBuf::from(format!(
+        "{}.pre-restore-{}",
+        state.config.database_url,
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    let pre_restore_path = pre_restore.clone();
+    let upload_path = upload.clone();
+    let result = web::block(move || -> Result<i32, String> {
+        crate::db::backup::validate_backup(&upload_path)?;
+        db.backup_to(&pre_restore_path)?;
+        db.restore_from(&upload_path)
+    })
+    .await;
+    let _ = std::fs::remove_file(&upload);
+
+    match result {
+        Ok(Ok(version)) => {
+            log::info!(
+                "[BACKUP] Restored database (schema version {}), previous copy at {}",
+                version,
+                pre_restore.display()
+            );
+            HttpResponse::Ok().json(BackupResponse {
+                success: true,
+                message: Some(
+                    "Database restored. Restart StarkBot so running channels and caches pick up the restored data."
+                        .to_string(),
+                ),
+                error: None,
+                pre_restore_backup: Some(pre_restore.display().to_string()),
+            })
+        }
+        Ok(Err(e)) => {
+            log::warn!("[BACKUP] Restore rejected: {}", e);
+            HttpResponse::BadRequest().json(BackupResponse::error(e))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(BackupResponse::error(e.to_string())),
+    }
+}
@@ -1,6 +1,7 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::Serialize;
 
+use crate::channels::{ChannelConnection, ChannelStatus};
 use crate::models::{
     get_settings_for_channel_type, ChannelResponse, ChannelSettingsResponse,
     ChannelSettingsSchemaResponse, ChannelType, CreateChannelRequest, UpdateChannelRequest,
@@ -24,6 +25,24 @@ pub struct ChannelOperationResponse {
     pub channel: Option<ChannelResponse>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// What a start, stop or restart did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ChannelStatus>,
+}
+
+/// Connection status of one channel
+#[derive(Serialize)]
+pub struct ChannelConnectionResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub running: Option<bool>,
+    /// Latest connection state; absent if the channel hasn't run since boot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<ChannelConnection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -37,6 +56,8 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/{id}", web::delete().to(delete_channel))
             .route("/{id}/start", web::post().to(start_channel))
             .route("/{id}/stop", web::post().to(stop_channel))
+            .route("/{id}/restart", web::post().to(restart_channel))
+            .route("/{id}/status", web::get().to(get_channel_status))
             .route("/{id}/settings", web::get().to(get_channel_settings))
             .route("/{id}/settings", web::put().to(update_channel_settings)),
     );
@@ -93,7 +114,10 @@ async fn list_channels(state: web::Data<AppState>, req: HttpRequest) -> impl Res
                 .into_iter()
                 .map(|ch| {
                     let running = channel_manager.is_running(ch.id);
-                    ChannelResponse::from(ch).with_running(running)
+                    let connection = channel_manager.connection(ch.id);
+                    ChannelResponse::from(ch)
+                        .with_running(running)
+                        .with_connection(connection)
                 })
                 .collect();
 
@@ -129,18 +153,23 @@ async fn get_channel(
         Ok(Some(channel)) => {
             let channel_manager = state.gateway.channel_manager();
             let running = channel_manager.is_running(channel.id);
-            let response = ChannelResponse::from(channel).with_running(running);
+            let connection = channel_manager.connection(channel.id);
+            let response = ChannelResponse::from(channel)
+                .with_running(running)
+                .with_connection(connection);
 
             HttpResponse::Ok().json(ChannelOperationResponse {
                 success: true,
                 channel: Some(response),
                 error: None,
+                status: None,
             })
         }
         Ok(None) => HttpResponse::NotFound().json(ChannelOperationResponse {
             success: false,
             channel: None,
             error: Some("Channel not found".to_string()),
+            status: None,
         }),
         Err(e) => {
             log::error!("Failed to get channel: {}", e);
@@ -148,6 +177,7 @@ async fn get_channel(
                 success: false,
                 channel: None,
                 error: Some("Failed to retrieve channel".to_string()),
+                status: None,
             })
         }
     }
@@ -168,6 +198,7 @@ async fn create_channel(
             success: false,
             channel: None,
             error: Some("Invalid channel type. Valid options: telegram, slack, discord".to_string()),
+            status: None,
         });
     }
 
@@ -177,6 +208,7 @@ async fn create_channel(
             success: false,
             channel: None,
             error: Some("Bot token cannot be empty".to_string()),
+            status: None,
         });
     }
 
@@ -186,6 +218,7 @@ async fn create_channel(
             success: false,
             channel: None,
             error: Some("Channel name cannot be empty".to_string()),
+            status: None,
         });
     }
 
@@ -196,6 +229,7 @@ async fn create_channel(
             success: false,
             channel: None,
             error: Some("Slack channels require an app_token for Socket Mode".to_string()),
+            status: None,
         });
     }
 
@@ -209,6 +243,7 @@ async fn create_channel(
             success: true,
             channel: Some(channel.into()),
             error: None,
+            status: None,
         }),
         Err(e) => {
             log::error!("Failed to create channel: {}", e);
@@ -224,6 +259,7 @@ async fn create_channel(
                 success: false,
                 channel: None,
                 error: Some(error_msg),
+                status: None,
             })
         }
     }
@@ -248,6 +284,7 @@ async fn update_channel(
                 success: false,
                 channel: None,
                 error: Some("Channel name cannot be empty".to_string()),
+                status: None,
             });
         }
     }
@@ -259,6 +296,7 @@ async fn update_channel(
                 success: false,
                 channel: None,
                 error: Some("Bot token cannot be empty".to_string()),
+                status: None,
             });
         }
     }
@@ -282,12 +320,14 @@ async fn update_channel(
                 success: true,
                 channel: Some(response),
                 error: None,
+                status: None,
             })
         }
         Ok(None) => HttpResponse::NotFound().json(ChannelOperationResponse {
             success: false,
             channel: None,
             error: Some("Channel not found".to_string()),
+            status: None,
         }),
         Err(e) => {
             log::error!("Failed to update channel: {}", e);
@@ -295,6 +335,7 @@ async fn update_channel(
                 success: false,
                 channel: None,
                 error: Some("Failed to update channel".to_string()),
+                status: None,
             })
         }
     }
@@ -312,10 +353,7 @@ async fn delete_channel(
     let id = path.into_inner();
 
     // Stop the channel if it's running
-    let channel_manager = state.gateway.channel_manager();
-    if channel_manager.is_running(id) {
-        let _ = channel_manager.stop_channel(id).await;
-    }
+    state.gateway.channel_manager().stop_channel(id).await;
 
     match state.db.delete_channel(id) {
         Ok(deleted) => {
@@ -324,12 +362,14 @@ async fn delete_channel(
                     success: true,
                     channel: None,
                     error: None,
+                    status: None,
                 })
             } else {
                 HttpResponse::NotFound().json(ChannelOperationResponse {
                     success: false,
                     channel: None,
                     error: Some("Channel not found".to_string()),
+                    status: None,
                 })
             }
         }
@@ -339,6 +379,7 @@ async fn delete_channel(
                 success: false,
                 channel: None,
                 error: Some("Failed to delete channel".to_string()),
+                status: None,
             })
         }
     }
@@ -363,6 +404,7 @@ async fn start_channel(
                 success: false,
                 channel: None,
                 error: Some("Channel not found".to_string()),
+                status: None,
             });
         }
         Err(e) => {
@@ -371,6 +413,7 @@ async fn start_channel(
                 success: false,
                 channel: None,
                 error: Some("Failed to retrieve channel".to_string()),
+                status: None,
             });
         }
     };
@@ -378,7 +421,7 @@ async fn start_channel(
     // Start the channel
     let channel_manager = state.gateway.channel_manager();
     match channel_manager.start_channel(channel.clone()).await {
-        Ok(()) => {
+        Ok(status) => {
             // Update enabled status in database
             let _ = state.db.set_channel_enabled(id, true);
 
@@ -387,6 +430,7 @@ async fn start_channel(
                 success: true,
                 channel: Some(response),
                 error: None,
+                status: Some(status),
             })
         }
         Err(e) => {
@@ -395,6 +439,7 @@ async fn start_channel(
                 success: false,
                 channel: None,
                 error: Some(e),
+                status: None,
             })
         }
     }
@@ -419,6 +464,7 @@ async fn stop_channel(
                 success: false,
                 channel: None,
                 error: Some("Channel not found".to_string()),
+                status: None,
             });
         }
         Err(e) => {
@@ -427,36 +473,128 @@ async fn stop_channel(
                 success: false,
                 channel: None,
                 error: Some("Failed to retrieve channel".to_string()),
+                status: None,
             });
         }
     };
 
     // Stop the channel
     let channel_manager = state.gateway.channel_manager();
-    match channel_manager.stop_channel(id).await {
-        Ok(()) => {
-            // Update enabled status in database
-            let _ = state.db.set_channel_enabled(id, false);
+    let status = channel_manager.stop_channel(id).await;
+
+    // Update enabled status in database
+    let _ = state.db.set_channel_enabled(id, false);
+
+    let response = ChannelResponse::from(channel).with_running(false);
+    HttpResponse::Ok().json(ChannelOperationResponse {
+        success: true,
+        channel: Some(response),
+        error: None,
+        status: Some(status),
+    })
+}
+
+async fn restart_channel(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let id = path.into_inner();
+
+    // Get channel from database (so the restart picks up config changes)
+    let channel = match state.db.get_channel(id) {
+        Ok(Some(ch)) => ch,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(ChannelOperationResponse {
+                success: false,
+                channel: None,
+                error: Some("Channel not found".to_string()),
+                status: None,
+            });
+        }
+        Err(e) => {
+            log::error!("Failed to get channel: {}", e);
+            return HttpResponse::InternalServerError().json(ChannelOperationResponse {
+                success: false,
+                channel: None,
+                error: Some("Failed to retrieve channel".to_string()),
+                status: None,
+            });
+        }
+    };
 
-            let response = ChannelResponse::from(channel).with_running(false);
+    let channel_manager = state.gateway.channel_manager();
+    match channel_manager.restart_channel(channel.clone()).await {
+        Ok(status) => {
+            let _ = state.db.set_channel_enabled(id, true);
+
+            let response = ChannelResponse::from(channel).with_running(true);
             HttpResponse::Ok().json(ChannelOperationResponse {
                 success: true,
                 channel: Some(response),
                 error: None,
+                status: Some(status),
             })
         }
         Err(e) => {
-            log::error!("Failed to stop channel: {}", e);
+            log::error!("Failed to restart channel: {}", e);
             HttpResponse::BadRequest().json(ChannelOperationResponse {
                 success: false,
                 channel: None,
                 error: Some(e),
+                status: None,
             })
         }
     }
 }
 
 /// Get available settings schema for a channel type
+async fn get_channel_status(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let id = path.into_inner();
+
+    match state.db.get_channel(id) {
+        Ok(Some(_)) => {
+            let channel_manager = state.gateway.channel_manager();
+            HttpResponse::Ok().json(ChannelConnectionResponse {
+                success: true,
+                channel_id: Some(id),
+                running: Some(channel_manager.is_running(id)),
+                connection: channel_manager.connection(id),
+                error: None,
+            })
+        }
+        Ok(None) => HttpResponse::NotFound().json(ChannelConnectionResponse {
+            success: false,
+            channel_id: None,
+            running: None,
+            connection: None,
+            error: Some("Channel not found".to_string()),
+        }),
+        Err(e) => {
+            log::error!("Failed to get channel: {}", e);
+            HttpResponse::InternalServerError().json(ChannelConnectionResponse {
+                success: false,
+                channel_id: None,
+                running: None,
+                connection: None,
+                error: Some("Failed to retrieve channel".to_string()),
+            })
+        }
+    }
+}
+
 async fn get_settings_schema(
     state: web::Data<AppState>,
     req: HttpRequest,
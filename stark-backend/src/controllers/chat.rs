@@ -19,6 +19,10 @@ pub struct ChatRequest {
     /// Currently selected network from the UI (e.g., "base", "polygon", "mainnet")
     #[serde(default)]
     pub network: Option<String>,
+    /// JSON Schema for a machine-readable reply; the parsed reply is returned
+    /// in `structured`
+    #[serde(default)]
+    pub response_schema: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -37,6 +41,9 @@ pub struct ChatResponse {
     /// Session ID for persistent conversations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<i64>,
+    /// Parsed reply, when the request set `response_schema`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structured: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -150,6 +157,7 @@ async fn chat(
                 message: None,
                 error: Some("No authorization token provided".to_string()),
                 session_id: None,
+                structured: None,
             });
         }
     };
@@ -163,6 +171,7 @@ async fn chat(
                 message: None,
                 error: Some("Invalid or expired session".to_string()),
                 session_id: None,
+                structured: None,
             });
         }
         Err(e) => {
@@ -172,6 +181,7 @@ async fn chat(
                 message: None,
                 error: Some("Internal server error".to_string()),
                 session_id: None,
+                structured: None,
             });
         }
     };
@@ -185,6 +195,7 @@ async fn chat(
                 message: None,
                 error: Some("No user message provided".to_string()),
                 session_id: None,
+                structured: None,
             });
         }
     };
@@ -206,6 +217,10 @@ async fn chat(
         message_id: None,
         session_mode: None,
         selected_network: body.network.clone(),
+        allowed_tools: None,
+        task_signature: None,
+        response_schema: body.response_schema.clone(),
+        reply_to: None,
     };
 
     // Dispatch through the unified pipeline
@@ -219,6 +234,7 @@ async fn chat(
             message: None,
             error: Some(error),
             session_id: None,
+            structured: None,
         });
     }
 
@@ -230,6 +246,7 @@ async fn chat(
         }),
         error: None,
         session_id: None, // Could return session ID if needed
+        structured: result.structured,
     })
 }
 
@@ -0,0 +1,118 @@
+//! Config audit API - who changed which settings, and when
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+
+/// Default and maximum page sizes for the config audit trail
+const DEFAULT_AUDIT_LIMIT: usize = 100;
+const MAX_AUDIT_LIMIT: usize = 500;
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+/// Wallet address of the session making a request, recorded as the actor of
+/// the settings changes it makes
+pub fn request_actor(state: &web::Data<AppState>, req: &HttpRequest) -> Option<String> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer "))?;
+
+    state.db.session_public_address(token).ok().flatten()
+}
+
+/// Record the fields that differ between two versions of a settings object
+/// (`None` for settings that didn't exist) as changes made by the request's
+/// session. Failures are logged; they never fail the settings change.
+pub fn audit_changes<T: Serialize>(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+    section: &str,
+    before: Option<&T>,
+    after: Option<&T>,
+) {
+    let to_value = |settings: Option<&T>| {
+        settings
+            .and_then(|s| serde_json::to_value(s).ok())
+            .unwrap_or(serde_json::Value::Null)
+    };
+    let actor = request_actor(state, req);
+    if let Err(e) = state
+        .db
+        .record_config_diff(actor.as_deref(), section, &to_value(before), &to_value(after))
+    {
+        log::error!("Failed to record config audit for {}: {}", section, e);
+    }
+}
+
+#[derive(Deserialize)]
+struct ConfigAuditQuery {
+    section: Option<String>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+/// Get the config audit trail, newest first
+async fn get_config_audit(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ConfigAuditQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_AUDIT_LIMIT).min(MAX_AUDIT_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+
+    match state.db.list_config_audit(query.section.as_deref(), limit, offset) {
+        Ok(entries) => HttpResponse::Ok().json(serde_json::json!({
+            "entries": entries,
+            "limit": limit,
+            "offset": offset,
+        })),
+        Err(e) => {
+            log::error!("Failed to list config audit: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/api/config-audit").route("", web::get().to(get_config_audit)));
+}
@@ -0,0 +1,69 @@
+//! Config reload API - reload token, network and RPC provider configs
+//! from the config directory without restarting the server
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+
+use crate::tools::config_reload;
+use crate::AppState;
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+/// Reload tokens.ron, networks.ron and rpc_providers.ron. A file that fails
+/// validation keeps its previous config and is reported as failed.
+async fn reload_config(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let config_dir = match config_reload::config_dir() {
+        Some(dir) => dir,
+        None => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Config directory not set"
+            }));
+        }
+    };
+
+    let results = config_reload::reload_all(config_dir);
+    let success = results.iter().all(|r| r.success);
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": success,
+        "results": results,
+    }))
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/api/config").route("/reload", web::post().to(reload_config)));
+}
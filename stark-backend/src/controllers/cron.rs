@@ -160,6 +160,7 @@ async fn create_job(
         body.thinking_level.as_deref(),
         body.timeout_seconds,
         body.delete_after_run,
+        body.seed_findings,
     ) {
         Ok(job) => HttpResponse::Created().json(CronJobResponse {
             success: true,
@@ -255,6 +256,7 @@ async fn update_job(
         body.thinking_level.as_deref(),
         body.timeout_seconds,
         body.delete_after_run,
+        body.seed_findings,
         body.status.as_deref(),
     ) {
         Ok(job) => HttpResponse::Ok().json(CronJobResponse {
@@ -393,7 +395,7 @@ async fn pause_job(state: web::Data<AppState>, req: HttpRequest, path: web::Path
 
     match state.db.update_cron_job(
         id,
-        None, None, None, None, None, None, None, None, None, None, None, None, None, None, None,
+        None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None,
         Some("paused"),
     ) {
         Ok(job) => HttpResponse::Ok().json(CronJobResponse {
@@ -421,7 +423,7 @@ async fn resume_job(state: web::Data<AppState>, req: HttpRequest, path: web::Pat
 
     match state.db.update_cron_job(
         id,
-        None, None, None, None, None, None, None, None, None, None, None, None, None, None, None,
+        None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None,
         Some("active"),
     ) {
         Ok(job) => HttpResponse::Ok().json(CronJobResponse {
@@ -6,6 +6,7 @@ use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 
 use crate::eip8004::{
+    agent_card::{self, AgentCardInputs},
     config::Eip8004Config,
     discovery::{AgentDiscovery, SearchCriteria},
     identity::{IdentityRegistry, RegistrationBuilder},
@@ -58,6 +59,18 @@ pub struct DiscoverQuery {
     min_reputation: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SyncAgentsRequest {
+    offset: Option<u64>,
+    limit: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KnownAgentsQuery {
+    x402_only: Option<bool>,
+    limit: Option<usize>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateRegistrationRequest {
     name: String,
@@ -85,6 +98,9 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             // Identity
             .route("/identity", web::get().to(get_our_identity))
             .route("/identity/registration", web::post().to(create_registration_json))
+            .route("/identity/card", web::get().to(get_agent_card_with_hash))
+            // Public agent card (registration URI target) - no auth, other agents fetch this
+            .route("/card.json", web::get().to(get_agent_card))
             .route("/identity/{agent_id}", web::get().to(get_agent_identity))
             // Reputation
             .route("/reputation/{agent_id}", web::get().to(get_agent_reputation))
@@ -92,6 +108,8 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             // Discovery
             .route("/agents", web::get().to(discover_agents))
             .route("/agents/search", web::get().to(search_agents))
+            .route("/agents/known", web::get().to(list_known_agents))
+            .route("/agents/sync", web::post().to(sync_agents))
             .route("/agents/{agent_id}", web::get().to(get_agent_details))
     );
 }
@@ -238,6 +256,118 @@ async fn create_registration_json(
     }
 }
 
+/// Build our agent card from agent_identity, enabled tools/skills and the burner wallet
+fn build_our_agent_card(state: &web::Data<AppState>, config: &Eip8004Config) -> crate::eip8004::types::RegistrationFile {
+    let identity = state.db.conn().query_row(
+        "SELECT agent_id, agent_registry, name, description FROM agent_identity ORDER BY id DESC LIMIT 1",
+        [],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)? as u64,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        },
+    ).ok();
+
+    let bot_name = state
+        .db
+        .get_bot_settings()
+        .map(|s| s.bot_name)
+        .unwrap_or_else(|_| "StarkBot".to_string());
+
+    let (registration, name, description) = match identity {
+        Some((agent_id, agent_registry, name, description)) => (
+            Some((agent_id, agent_registry)),
+            name.unwrap_or(bot_name),
+            description,
+        ),
+        None => (None, bot_name, None),
+    };
+
+    let wallet_address = state
+        .config
+        .burner_wallet_private_key
+        .as_ref()
+        .and_then(|pk| crate::x402::X402Signer::new(pk).ok())
+        .map(|signer| signer.address());
+
+    let tool_config = state.db.get_effective_tool_config(None).unwrap_or_default();
+    let tools = state
+        .tool_registry
+        .get_allowed_tools(&tool_config)
+        .iter()
+        .map(|t| t.name())
+        .collect();
+
+    let skills = state
+        .skill_registry
+        .list_enabled()
+        .into_iter()
+        .map(|s| s.metadata.name)
+        .collect();
+
+    let inputs = AgentCardInputs {
+        name,
+        description: description.unwrap_or_else(|| "Autonomous agent with x402 payments on Base".to_string()),
+        image: None,
+        registration,
+        wallet_address,
+        skills,
+        tools,
+    };
+
+    agent_card::build_agent_card(&inputs, config)
+}
+
+/// Serve our public agent card (registration JSON)
+async fn get_agent_card(state: web::Data<AppState>) -> impl Responder {
+    let config = Eip8004Config::from_env();
+    let card = build_our_agent_card(&state, &config);
+
+    match agent_card::card_hash(&card) {
+        Ok(hash) => HttpResponse::Ok()
+            .insert_header(("ETag", format!("\"{}\"", hash)))
+            .json(card),
+        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
+    }
+}
+
+/// Get our agent card together with its hash (for registration)
+async fn get_agent_card_with_hash(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let config = Eip8004Config::from_env();
+    let card = build_our_agent_card(&state, &config);
+
+    let hash = match agent_card::card_hash(&card) {
+        Ok(h) => h,
+        Err(e) => return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
+    };
+
+    let stored_hash: Option<String> = state
+        .db
+        .conn()
+        .query_row(
+            "SELECT registration_hash FROM agent_identity ORDER BY id DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .ok()
+        .flatten();
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "card": card,
+        "registration_hash": hash,
+        "matches_registered_hash": stored_hash.as_deref().map(|h| h.eq_ignore_ascii_case(&hash)),
+        "card_url": config.public_url.as_ref().map(|u| format!("{}/api/eip8004/card.json", u))
+    }))
+}
+
 // =====================================================
 // Reputation Endpoints
 // =====================================================
@@ -393,6 +523,57 @@ async fn search_agents(
     }
 }
 
+/// Sync agents from the registry into the local known_agents index
+async fn sync_agents(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: Option<web::Json<SyncAgentsRequest>>,
+) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let config = Eip8004Config::from_env();
+
+    if !config.is_identity_deployed() {
+        return HttpResponse::BadRequest().json(ApiResponse::<()>::error("Identity Registry not deployed"));
+    }
+
+    let (offset, limit) = body
+        .map(|b| (b.offset.unwrap_or(0), b.limit.unwrap_or(100).min(500)))
+        .unwrap_or((0, 100));
+
+    let mut discovery = AgentDiscovery::new(config);
+
+    match discovery.sync_known_agents(&state.db, offset, limit).await {
+        Ok(report) => HttpResponse::Ok().json(ApiResponse::success(report)),
+        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
+    }
+}
+
+/// List agents from the local known_agents index
+async fn list_known_agents(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<KnownAgentsQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    match state
+        .db
+        .list_known_agents(query.x402_only.unwrap_or(false), Some(query.limit.unwrap_or(100)))
+    {
+        Ok(agents) => HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "count": agents.len(),
+            "agents": agents
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e.to_string())),
+    }
+}
+
 /// Get full agent details
 async fn get_agent_details(
     state: web::Data<AppState>,
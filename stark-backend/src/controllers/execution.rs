@@ -0,0 +1,60 @@
+//! Execution state snapshots
+//!
+//! Progress is normally streamed as gateway events. This endpoint returns the
+//! tracker's current state for a channel so a client that loads mid-run can
+//! render it, then follow the event stream from the snapshot's `last_event_id`.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+
+use crate::AppState;
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/api/execution/{channel_id}").route(web::get().to(get_execution_state)));
+}
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+/// Current execution state (task tree, active tools, stats) for a channel
+async fn get_execution_state(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    HttpResponse::Ok().json(state.execution_tracker.snapshot(path.into_inner()))
+}
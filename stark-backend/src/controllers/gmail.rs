@@ -12,7 +12,7 @@ use crate::db::Database;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::integrations::gmail::{
-    GmailClient, GmailConfig, GmailConfigResponse, GmailNotificationData,
+    save_attachments, AutoReplyFilter, GmailClient, GmailConfig, GmailConfigResponse, GmailNotificationData,
     ParsedEmail, PubSubPushNotification, SetupGmailRequest, UpdateGmailRequest,
 };
 use crate::AppState;
@@ -204,8 +204,12 @@ async fn dispatch_email(
     email: &ParsedEmail,
     client: &GmailClient,
 ) -> Result<DispatchResult, String> {
+    // Save attachments into the workspace so the file tools can read them
+    let workspace = std::path::PathBuf::from(crate::config::workspace_dir());
+    let attachments = save_attachments(client, email, &workspace).await;
+
     // Build message content
-    let message_content = format!(
+    let mut message_content = format!(
         "New email received:\n\
         From: {}\n\
         Subject: {}\n\
@@ -219,6 +223,10 @@ async fn dispatch_email(
             email.body.clone()
         }
     );
+    if !attachments.is_empty() {
+        message_content.push_str("\n---\n");
+        message_content.push_str(&attachments.to_message());
+    }
 
     // Create normalized message
     let normalized = NormalizedMessage {
@@ -231,6 +239,10 @@ async fn dispatch_email(
         message_id: Some(email.message_id.clone()),
         session_mode: None,
         selected_network: None,
+        allowed_tools: None,
+        task_signature: None,
+        response_schema: None,
+        reply_to: None,
     };
 
     // Broadcast event
@@ -240,14 +252,18 @@ async fn dispatch_email(
             "from": email.from,
             "subject": email.subject,
             "thread_id": email.thread_id,
+            "attachments": attachments.saved.len(),
         }),
     ));
 
+    // Decide up front whether this email may get an auto-reply
+    let reply_allowed = config.auto_reply && auto_reply_allowed(db, config, email);
+
     // Dispatch to agent
     let result = dispatcher.dispatch(normalized).await;
 
-    // If auto-reply is enabled and we got a successful response, send reply
-    if config.auto_reply && result.error.is_none() && !result.response.is_empty() {
+    // If auto-reply is allowed and we got a successful response, send reply
+    if reply_allowed && result.error.is_none() && !result.response.is_empty() {
         let response_text = &result.response;
         log::info!("[GMAIL] Sending auto-reply to {}", email.from);
 
@@ -265,6 +281,9 @@ async fn dispatch_email(
         ).await {
             Ok(_) => {
                 log::info!("[GMAIL] Auto-reply sent successfully");
+                if let Err(e) = db.increment_gmail_thread_reply_count(&email.thread_id) {
+                    log::error!("[GMAIL] Failed to record auto-reply for thread {}: {}", email.thread_id, e);
+                }
                 broadcaster.broadcast(GatewayEvent::custom(
                     "gmail_reply_sent",
                     serde_json::json!({
@@ -282,6 +301,30 @@ async fn dispatch_email(
     Ok(result)
 }
 
+/// Run the auto-reply safety filters, logging why a reply is skipped
+fn auto_reply_allowed(db: &Database, config: &GmailConfig, email: &ParsedEmail) -> bool {
+    let thread_replies = match db.get_gmail_thread_reply_count(&email.thread_id) {
+        Ok(count) => count,
+        Err(e) => {
+            log::error!("[GMAIL] Failed to read reply count for thread {}: {}", email.thread_id, e);
+            0
+        }
+    };
+
+    match AutoReplyFilter::from_config(config).check(email, thread_replies) {
+        Ok(()) => true,
+        Err(reason) => {
+            log::info!(
+                "[GMAIL] Skipping auto-reply to {} (thread {}): {}",
+                email.from,
+                email.thread_id,
+                reason
+            );
+            false
+        }
+    }
+}
+
 /// Extract display name from email address
 fn extract_name_from_email(email: &str) -> String {
     // Format: "Name <email@example.com>" or just "email@example.com"
@@ -403,7 +446,16 @@ async fn setup_gmail(
         body.watch_labels.as_deref().unwrap_or("INBOX"),
         body.response_channel_id,
         body.auto_reply.unwrap_or(false),
-    ) {
+    ).and_then(|config| {
+        if body.reply_allowlist.is_none() && body.reply_denylist.is_none() && body.max_replies_per_thread.is_none() {
+            return Ok(config);
+        }
+        state.db.update_gmail_reply_filters(
+            body.reply_allowlist.as_deref(),
+            body.reply_denylist.as_deref(),
+            body.max_replies_per_thread,
+        )
+    }) {
         Ok(config) => HttpResponse::Created().json(GmailConfigResponse {
             success: true,
             config: Some(config.into()),
@@ -432,7 +484,16 @@ async fn update_config(
         body.response_channel_id,
         body.auto_reply,
         body.enabled,
-    ) {
+    ).and_then(|config| {
+        if body.reply_allowlist.is_none() && body.reply_denylist.is_none() && body.max_replies_per_thread.is_none() {
+            return Ok(config);
+        }
+        state.db.update_gmail_reply_filters(
+            body.reply_allowlist.as_deref(),
+            body.reply_denylist.as_deref(),
+            body.max_replies_per_thread,
+        )
+    }) {
         Ok(config) => HttpResponse::Ok().json(GmailConfigResponse {
             success: true,
             config: Some(config.into()),
@@ -508,9 +569,7 @@ async fn start_watch(state: web::Data<AppState>, req: HttpRequest) -> HttpRespon
     match client.setup_watch("me", &topic_name, &labels).await {
         Ok(watch_response) => {
             // Update config with watch expiration and history ID
-            let expiration = chrono::DateTime::parse_from_rfc3339(&watch_response.expiration)
-                .ok()
-                .map(|dt| dt.with_timezone(&chrono::Utc));
+            let expiration = watch_response.expires_at();
 
             if let Err(e) = state.db.update_gmail_watch(
                 config.id,
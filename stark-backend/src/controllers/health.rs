@@ -1,16 +1,56 @@
 use actix_web::{web, HttpResponse, Responder};
+use serde::Serialize;
+use std::time::{Duration, Instant};
 
 use crate::{config, AppState};
 
 /// Version from Cargo.toml, available at compile time
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// How long the readiness probe waits for the AI endpoint
+const AI_ENDPOINT_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("/api/health").route(web::get().to(health_check)));
+    cfg.service(web::resource("/api/health/live").route(web::get().to(liveness)));
+    cfg.service(web::resource("/api/health/ready").route(web::get().to(readiness)));
     cfg.service(web::resource("/api/version").route(web::get().to(get_version)));
     cfg.service(web::resource("/api/health/config").route(web::get().to(get_config_status)));
 }
 
+/// Result of one readiness check
+#[derive(Debug, Serialize)]
+struct ComponentStatus {
+    /// "ok", "error" or "not_configured"
+    status: &'static str,
+    /// Whether this component keeps the service from being ready
+    ready: bool,
+    latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+impl ComponentStatus {
+    fn ok(started: Instant, detail: Option<String>) -> Self {
+        Self { status: "ok", ready: true, latency_ms: elapsed_ms(started), detail }
+    }
+
+    fn error(started: Instant, detail: String) -> Self {
+        Self { status: "error", ready: false, latency_ms: elapsed_ms(started), detail: Some(detail) }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ReadinessComponents {
+    database: ComponentStatus,
+    ai_endpoint: ComponentStatus,
+    channels: ComponentStatus,
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
+
 async fn health_check() -> impl Responder {
     HttpResponse::Ok().json(serde_json::json!({
         "status": "ok",
@@ -18,6 +58,95 @@ async fn health_check() -> impl Responder {
     }))
 }
 
+/// Liveness: the process is up and serving requests. No dependency checks.
+async fn liveness() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({ "status": "ok" }))
+}
+
+/// Readiness: the database, AI endpoint and enabled channels are usable.
+/// Returns 503 when any of them is not.
+async fn readiness(state: web::Data<AppState>) -> impl Responder {
+    let database = check_database(&state);
+    let ai_endpoint = check_ai_endpoint(&state).await;
+    let channels = check_channels(&state);
+
+    let ready = database.ready && ai_endpoint.ready && channels.ready;
+    let body = serde_json::json!({
+        "ready": ready,
+        "version": VERSION,
+        "components": ReadinessComponents { database, ai_endpoint, channels },
+    });
+
+    if ready {
+        HttpResponse::Ok().json(body)
+    } else {
+        HttpResponse::ServiceUnavailable().json(body)
+    }
+}
+
+fn check_database(state: &AppState) -> ComponentStatus {
+    let started = Instant::now();
+    match state.db.ping() {
+        Ok(()) => ComponentStatus::ok(started, None),
+        Err(e) => ComponentStatus::error(started, e),
+    }
+}
+
+/// Any HTTP response counts as reachable; only connection errors and
+/// timeouts fail. An unconfigured endpoint doesn't block readiness, so a
+/// fresh install can still be set up from the dashboard.
+async fn check_ai_endpoint(state: &AppState) -> ComponentStatus {
+    let started = Instant::now();
+    let settings = match state.db.get_active_agent_settings() {
+        Ok(Some(settings)) => settings,
+        Ok(None) => {
+            return ComponentStatus {
+                status: "not_configured",
+                ready: true,
+                latency_ms: elapsed_ms(started),
+                detail: Some("No AI endpoint is enabled".to_string()),
+            };
+        }
+        Err(e) => return ComponentStatus::error(started, format!("Failed to load agent settings: {}", e)),
+    };
+
+    let client = match reqwest::Client::builder().timeout(AI_ENDPOINT_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => return ComponentStatus::error(started, e.to_string()),
+    };
+    match client.head(&settings.endpoint).send().await {
+        Ok(resp) => ComponentStatus::ok(started, Some(format!("HTTP {}", resp.status().as_u16()))),
+        Err(e) if e.is_timeout() => ComponentStatus::error(
+            started,
+            format!("Timed out after {}s", AI_ENDPOINT_TIMEOUT.as_secs()),
+        ),
+        Err(e) => ComponentStatus::error(started, format!("Unreachable: {}", e)),
+    }
+}
+
+fn check_channels(state: &AppState) -> ComponentStatus {
+    let started = Instant::now();
+    let enabled = match state.db.list_enabled_channels() {
+        Ok(channels) => channels,
+        Err(e) => return ComponentStatus::error(started, format!("Failed to list channels: {}", e)),
+    };
+
+    let stopped: Vec<&str> = enabled
+        .iter()
+        .filter(|c| !state.channel_manager.is_running(c.id))
+        .map(|c| c.name.as_str())
+        .collect();
+
+    if stopped.is_empty() {
+        ComponentStatus::ok(started, Some(format!("{} enabled channel(s) running", enabled.len())))
+    } else {
+        ComponentStatus::error(
+            started,
+            format!("{} of {} enabled channel(s) not running: {}", stopped.len(), enabled.len(), stopped.join(", ")),
+        )
+    }
+}
+
 async fn get_version() -> impl Responder {
     HttpResponse::Ok().json(serde_json::json!({
         "version": VERSION
@@ -2,7 +2,8 @@ use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::Deserialize;
 
 use crate::models::{
-    GetOrCreateIdentityRequest, IdentityResponse, LinkIdentityRequest, LinkedAccountInfo,
+    GetOrCreateIdentityRequest, IdentityQuota, IdentityResponse, LinkIdentityRequest,
+    LinkedAccountInfo,
 };
 use crate::AppState;
 
@@ -344,6 +345,115 @@ async fn get_identity_logs(
     }))
 }
 
+/// Build the quota response: the override, the limits in effect and today's usage
+fn quota_response(data: &web::Data<AppState>, identity_id: &str) -> HttpResponse {
+    let result = data.db.get_identity_quota(identity_id).and_then(|quota_override| {
+        let effective = data.db.get_effective_identity_quota(identity_id)?;
+        let usage = data.db.get_identity_usage_today(identity_id)?;
+        Ok((quota_override, effective, usage))
+    });
+
+    match result {
+        Ok((quota_override, effective, usage)) => HttpResponse::Ok().json(serde_json::json!({
+            "identity_id": identity_id,
+            "override": quota_override,
+            "effective": effective,
+            "usage": usage,
+        })),
+        Err(e) => {
+            log::error!("Failed to get quota for identity: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+/// Check that an identity exists, for the quota endpoints
+fn require_identity(data: &web::Data<AppState>, identity_id: &str) -> Result<(), HttpResponse> {
+    match data.db.get_linked_identities(identity_id) {
+        Ok(links) if !links.is_empty() => Ok(()),
+        Ok(_) => Err(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Identity not found"
+        }))),
+        Err(e) => {
+            log::error!("Failed to get linked identities: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            })))
+        }
+    }
+}
+
+/// Get an identity's daily quota and today's usage
+async fn get_identity_quota(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let identity_id = path.into_inner();
+    if let Err(resp) = require_identity(&data, &identity_id) {
+        return resp;
+    }
+
+    quota_response(&data, &identity_id)
+}
+
+/// Set an identity's quota override (omitted limits use the global defaults)
+async fn set_identity_quota(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<IdentityQuota>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let identity_id = path.into_inner();
+    if let Err(resp) = require_identity(&data, &identity_id) {
+        return resp;
+    }
+
+    if body.x402_usdc_per_day.is_some_and(|v| !v.is_finite() || v < 0.0) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "x402_usdc_per_day must be a non-negative number"
+        }));
+    }
+
+    if let Err(e) = data.db.set_identity_quota(&identity_id, &body) {
+        log::error!("Failed to set quota for identity: {}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        }));
+    }
+
+    quota_response(&data, &identity_id)
+}
+
+/// Remove an identity's quota override
+async fn delete_identity_quota(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let identity_id = path.into_inner();
+
+    if let Err(e) = data.db.delete_identity_quota(&identity_id) {
+        log::error!("Failed to delete quota for identity: {}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        }));
+    }
+
+    quota_response(&data, &identity_id)
+}
+
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/identities")
@@ -352,6 +462,9 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/lookup", web::get().to(get_identity))
             .route("/link", web::post().to(link_identity))
             .route("/{identity_id}", web::get().to(get_linked_identities))
-            .route("/{identity_id}/logs", web::get().to(get_identity_logs)),
+            .route("/{identity_id}/logs", web::get().to(get_identity_logs))
+            .route("/{identity_id}/quota", web::get().to(get_identity_quota))
+            .route("/{identity_id}/quota", web::put().to(set_identity_quota))
+            .route("/{identity_id}/quota", web::delete().to(delete_identity_quota)),
     );
 }
@@ -0,0 +1,117 @@
+//! Limit orders API - list and cancel price-triggered DEX swaps
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+
+use crate::db::tables::limit_orders::LimitOrderStatus;
+use crate::AppState;
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    status: Option<String>,
+}
+
+/// List limit orders, newest first
+async fn list_orders(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ListQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let status = match query.status.as_deref() {
+        Some(s) => match LimitOrderStatus::from_str(s) {
+            Some(status) => Some(status),
+            None => {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": format!("Unknown status '{}'", s)
+                }));
+            }
+        },
+        None => None,
+    };
+
+    match data.db.list_limit_orders(status) {
+        Ok(orders) => HttpResponse::Ok().json(serde_json::json!({ "orders": orders })),
+        Err(e) => {
+            log::error!("Failed to list limit orders: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+/// Cancel a pending limit order
+async fn cancel_order(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let id = path.into_inner();
+    match data.db.cancel_limit_order(id) {
+        Ok(true) => HttpResponse::Ok().json(serde_json::json!({ "success": true })),
+        Ok(false) => match data.db.get_limit_order(id) {
+            Ok(Some(order)) => HttpResponse::Conflict().json(serde_json::json!({
+                "error": format!("Limit order is already {}", order.status.as_str())
+            })),
+            _ => HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Limit order not found"
+            })),
+        },
+        Err(e) => {
+            log::error!("Failed to cancel limit order: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/limit-orders")
+            .route("", web::get().to(list_orders))
+            .route("/{id}/cancel", web::post().to(cancel_order)),
+    );
+}
@@ -0,0 +1,80 @@
+//! Logging API - read and change the log filter without restarting the server
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+
+use crate::logging;
+use crate::AppState;
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SetLevelRequest {
+    /// Level or filter directives in `RUST_LOG` syntax
+    level: String,
+}
+
+/// Current log filter, most verbose enabled level and output format
+async fn get_level(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    HttpResponse::Ok().json(logging::settings())
+}
+
+/// Replace the log filter until the next restart
+async fn set_level(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<SetLevelRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    match logging::set_filter(&body.level) {
+        Ok(settings) => HttpResponse::Ok().json(settings),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e })),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/logging")
+            .route("/level", web::get().to(get_level))
+            .route("/level", web::put().to(set_level)),
+    );
+}
@@ -0,0 +1,21 @@
+use actix_web::{web, HttpResponse, Responder};
+
+use crate::{metrics, AppState};
+
+pub fn config_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/metrics").route(web::get().to(get_metrics)));
+}
+
+/// Prometheus scrape endpoint (text exposition format)
+async fn get_metrics(state: web::Data<AppState>) -> impl Responder {
+    metrics::refresh_gauges(&state.db, &state.tx_queue);
+    match metrics::render() {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4; charset=utf-8")
+            .body(body),
+        Err(e) => {
+            log::error!("[METRICS] Failed to render metrics: {}", e);
+            HttpResponse::InternalServerError().body(e)
+        }
+    }
+}
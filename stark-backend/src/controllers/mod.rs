@@ -1,22 +1,35 @@
+pub mod abis;
 pub mod agent_settings;
 pub mod api_keys;
 pub mod auth;
+pub mod backup;
 pub mod broadcasted_transactions;
 pub mod channels;
 pub mod chat;
+pub mod config_audit;
+pub mod config_reload;
 pub mod cron;
 pub mod dashboard;
 pub mod eip8004;
+pub mod execution;
 pub mod files;
 pub mod gmail;
 pub mod health;
+pub mod metrics;
 pub mod identity;
 pub mod intrinsic;
 pub mod journal;
+pub mod limit_orders;
+pub mod logging;
 pub mod memory;
 pub mod mindmap;
 pub mod payments;
+pub mod personas;
+pub mod portfolio;
+pub mod presets;
 pub mod sessions;
 pub mod skills;
 pub mod tools;
 pub mod tx_queue;
+pub mod wallets;
+pub mod webhook;
@@ -40,6 +40,23 @@ struct PaymentSummary {
     payments_without_feedback: i64,
 }
 
+#[derive(Debug, Serialize)]
+struct SpendTotal {
+    /// Tool name or channel ID, as a string; `null` for AI model calls
+    /// paid over x402 (by tool) or payments outside any channel (by channel)
+    key: Option<String>,
+    asset: String,
+    payments: i64,
+    total_spent: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CostReportQuery {
+    channel_id: Option<i64>,
+    /// Only count payments from the last N days
+    days: Option<i64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PaymentListQuery {
     channel_id: Option<i64>,
@@ -52,6 +69,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
         web::scope("/api/payments")
             .route("", web::get().to(list_payments))
             .route("/summary", web::get().to(get_summary))
+            .route("/costs", web::get().to(get_costs))
             .route("/{id}", web::get().to(get_payment))
     );
 }
@@ -194,6 +212,57 @@ async fn get_summary(
     }))
 }
 
+/// Spend per tool and per channel. Failed payments are left out.
+async fn get_costs(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<CostReportQuery>,
+) -> impl Responder {
+    // Validate auth
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let conn = state.db.conn();
+    let since = query.days.map(|days| format!("-{} days", days.max(0)));
+
+    let totals = |key_column: &str| -> Result<Vec<SpendTotal>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT CAST({key} AS TEXT), asset, COUNT(*), COALESCE(SUM(CAST(amount_formatted AS REAL)), 0)
+             FROM x402_payments
+             WHERE status != 'failed'
+               AND (?1 IS NULL OR channel_id = ?1)
+               AND (?2 IS NULL OR created_at >= datetime('now', ?2))
+             GROUP BY {key}, asset
+             ORDER BY 4 DESC",
+            key = key_column
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params![query.channel_id, since], |row| {
+            Ok(SpendTotal {
+                key: row.get(0)?,
+                asset: row.get(1)?,
+                payments: row.get(2)?,
+                total_spent: format!("{:.6}", row.get::<_, f64>(3)?),
+            })
+        })?;
+        let totals = rows.collect();
+        totals
+    };
+
+    match (totals("tool_name"), totals("channel_id")) {
+        (Ok(by_tool), Ok(by_channel)) => HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "by_tool": by_tool,
+            "by_channel": by_channel
+        })),
+        (Err(e), _) | (_, Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "success": false,
+            "error": format!("Query error: {}", e)
+        })),
+    }
+}
+
 /// Get single payment
 async fn get_payment(
     state: web::Data<AppState>,
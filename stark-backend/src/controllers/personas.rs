@@ -0,0 +1,176 @@
+//! Persona controller - CRUD for named system prompt templates
+//!
+//! Channels pick a persona by name through their `persona` channel setting.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+
+use crate::db::tables::personas::{CreatePersonaRequest, UpdatePersonaRequest};
+use crate::AppState;
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+/// Map a database error, reporting name clashes as a conflict
+fn persona_db_error(action: &str, e: rusqlite::Error) -> HttpResponse {
+    if let rusqlite::Error::SqliteFailure(ref err, _) = e {
+        if err.code == rusqlite::ErrorCode::ConstraintViolation {
+            return HttpResponse::Conflict().json(serde_json::json!({
+                "error": "A persona with that name already exists"
+            }));
+        }
+    }
+    log::error!("Failed to {} persona: {}", action, e);
+    HttpResponse::InternalServerError().json(serde_json::json!({
+        "error": format!("Database error: {}", e)
+    }))
+}
+
+/// List all personas
+async fn list_personas(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    match data.db.list_personas() {
+        Ok(personas) => HttpResponse::Ok().json(personas),
+        Err(e) => persona_db_error("list", e),
+    }
+}
+
+/// Create a persona
+async fn create_persona(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<CreatePersonaRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let request = body.into_inner();
+    if request.name.trim().is_empty() || request.content.trim().is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Persona name and content are required"
+        }));
+    }
+
+    match data.db.create_persona(&request) {
+        Ok(persona) => HttpResponse::Created().json(persona),
+        Err(e) => persona_db_error("create", e),
+    }
+}
+
+/// Get a single persona
+async fn get_persona(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    match data.db.get_persona(path.into_inner()) {
+        Ok(Some(persona)) => HttpResponse::Ok().json(persona),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Persona not found"
+        })),
+        Err(e) => persona_db_error("get", e),
+    }
+}
+
+/// Update a persona
+async fn update_persona(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+    body: web::Json<UpdatePersonaRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let request = body.into_inner();
+    if request.name.as_deref().is_some_and(|n| n.trim().is_empty())
+        || request.content.as_deref().is_some_and(|c| c.trim().is_empty())
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Persona name and content cannot be empty"
+        }));
+    }
+
+    match data.db.update_persona(path.into_inner(), &request) {
+        Ok(Some(persona)) => HttpResponse::Ok().json(persona),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Persona not found"
+        })),
+        Err(e) => persona_db_error("update", e),
+    }
+}
+
+/// Delete a persona. Channels still naming it fall back to SOUL.md.
+async fn delete_persona(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    match data.db.delete_persona(path.into_inner()) {
+        Ok(true) => HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Persona deleted"
+        })),
+        Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Persona not found"
+        })),
+        Err(e) => persona_db_error("delete", e),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/personas")
+            .route("", web::get().to(list_personas))
+            .route("", web::post().to(create_persona))
+            .route("/{id}", web::get().to(get_persona))
+            .route("/{id}", web::put().to(update_persona))
+            .route("/{id}", web::delete().to(delete_persona)),
+    );
+}
@@ -0,0 +1,80 @@
+//! Portfolio API - wallet balance history and P&L from scheduled snapshots
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+
+use crate::tools::builtin::cryptocurrency::network_lookup::resolve_network;
+use crate::tools::builtin::cryptocurrency::portfolio::{portfolio_history, DEFAULT_PORTFOLIO_DAYS};
+use crate::AppState;
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    wallet: Option<String>,
+    network: Option<String>,
+    days: Option<i64>,
+}
+
+/// Snapshot history and P&L for a wallet over the last `days`
+async fn get_history(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<HistoryQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let network = match resolve_network(query.network.as_deref(), None) {
+        Ok(n) => n,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+        }
+    };
+    let days = query.days.unwrap_or(DEFAULT_PORTFOLIO_DAYS);
+
+    match portfolio_history(&data.db, query.wallet.as_deref(), &network, days).await {
+        Ok(history) => HttpResponse::Ok().json(history),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e })),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/portfolio")
+            .route("/history", web::get().to(get_history)),
+    );
+}
@@ -0,0 +1,205 @@
+//! Web3 presets API - manage `web3_function_call` presets at runtime
+//!
+//! Presets saved here are stored in the database and override the file
+//! presets from config/web3_presets.ron with the same name.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+
+use crate::tools::presets::{self, Web3Preset};
+use crate::AppState;
+
+/// Validate session token from request
+fn validate_session_from_request(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "No authorization token provided"
+            })));
+        }
+    };
+
+    match state.db.validate_session(&token) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or expired session"
+        }))),
+        Err(e) => {
+            log::error!("Session validation error: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Internal server error"
+            })))
+        }
+    }
+}
+
+/// List all presets: saved ones first, then file presets they don't override
+async fn list_presets(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let stored = match data.db.list_web3_presets() {
+        Ok(s) => s,
+        Err(e) => {
+            log::error!("Failed to list web3 presets: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    };
+
+    let mut list: Vec<serde_json::Value> = stored
+        .iter()
+        .map(|p| {
+            serde_json::json!({
+                "name": p.name,
+                "source": "database",
+                "preset": p.preset,
+                "updated_at": p.updated_at,
+            })
+        })
+        .collect();
+
+    let mut file_names = presets::list_file_web3_presets();
+    file_names.sort();
+    for name in file_names {
+        if stored.iter().any(|p| p.name == name) {
+            continue;
+        }
+        if let Some(preset) = presets::get_file_web3_preset(&name) {
+            list.push(serde_json::json!({
+                "name": name,
+                "source": "file",
+                "preset": preset,
+            }));
+        }
+    }
+
+    HttpResponse::Ok().json(serde_json::json!({ "presets": list }))
+}
+
+/// Get one preset as `web3_function_call` resolves it
+async fn get_preset(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let name = path.into_inner();
+    match data.db.get_web3_preset(&name) {
+        Ok(Some(stored)) => HttpResponse::Ok().json(serde_json::json!({
+            "name": stored.name,
+            "source": "database",
+            "preset": stored.preset,
+            "updated_at": stored.updated_at,
+        })),
+        Ok(None) => match presets::get_file_web3_preset(&name) {
+            Some(preset) => HttpResponse::Ok().json(serde_json::json!({
+                "name": name,
+                "source": "file",
+                "preset": preset,
+            })),
+            None => HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Preset not found"
+            })),
+        },
+        Err(e) => {
+            log::error!("Failed to get web3 preset: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+/// Create or replace a preset
+async fn save_preset(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<Web3Preset>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let name = path.into_inner();
+    let preset = body.into_inner();
+    if let Err(e) = presets::validate_web3_preset(&name, &preset, &presets::abis_dir()) {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+
+    match data.db.upsert_web3_preset(&name, &preset) {
+        Ok(stored) => {
+            presets::cache_db_web3_preset(&name, Some(stored.preset.clone()));
+            log::info!("Saved web3 preset '{}'", name);
+            HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "preset": stored,
+            }))
+        }
+        Err(e) => {
+            log::error!("Failed to save web3 preset: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+/// Delete a saved preset. File presets can't be deleted here.
+async fn delete_preset(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let name = path.into_inner();
+    match data.db.delete_web3_preset(&name) {
+        Ok(true) => {
+            presets::cache_db_web3_preset(&name, None);
+            log::info!("Deleted web3 preset '{}'", name);
+            HttpResponse::Ok().json(serde_json::json!({ "success": true }))
+        }
+        Ok(false) if presets::get_file_web3_preset(&name).is_some() => {
+            HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Preset is defined in config/web3_presets.ron and can't be deleted through the API"
+            }))
+        }
+        Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Preset not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to delete web3 preset: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/presets/web3")
+            .route("", web::get().to(list_presets))
+            .route("/{name}", web::get().to(get_preset))
+            .route("/{name}", web::put().to(save_preset))
+            .route("/{name}", web::delete().to(delete_preset)),
+    );
+}
@@ -3,7 +3,8 @@ use serde::Deserialize;
 
 use crate::models::{
     ChatSessionResponse, CompletionStatus, GetOrCreateSessionRequest, SessionScope,
-    SessionTranscriptResponse, UpdateResetPolicyRequest,
+    SessionTranscriptResponse, UpdateContextWindowRequest, UpdateResetPolicyRequest,
+    MIN_CONTEXT_WINDOW_OVERRIDE,
 };
 use crate::AppState;
 
@@ -214,6 +215,48 @@ async fn update_reset_policy(
     }
 }
 
+/// Set or clear a session's context window override
+async fn update_context_window(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+    body: web::Json<UpdateContextWindowRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let session_id = path.into_inner();
+
+    if body.context_window_override.is_some_and(|window| window < MIN_CONTEXT_WINDOW_OVERRIDE) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("context_window_override must be at least {}", MIN_CONTEXT_WINDOW_OVERRIDE)
+        }));
+    }
+
+    match data.db.update_session_context_window_override(session_id, body.context_window_override) {
+        Ok(Some(mut session)) => {
+            // Apply right away; clearing takes effect on the next message
+            if let Some(window) = session.context_window_override {
+                match data.db.update_session_max_context_tokens(session_id, window) {
+                    Ok(()) => session.max_context_tokens = window,
+                    Err(e) => log::error!("Failed to update session max_context_tokens: {}", e),
+                }
+            }
+            let response: ChatSessionResponse = session.into();
+            HttpResponse::Ok().json(response)
+        }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Session not found"
+        })),
+        Err(e) => {
+            log::error!("Failed to update session context window: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
 /// Delete all sessions and cancel any running agentic loops
 async fn delete_all_sessions(
     data: web::Data<AppState>,
@@ -517,6 +560,75 @@ async fn get_transcript(
     }
 }
 
+/// Get the orchestrator context for a session (mode, tasks, notes, iteration
+/// counts), for debugging multi-agent runs
+async fn get_agent_context(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let session_id = path.into_inner();
+
+    match data.db.get_agent_context(session_id) {
+        Ok(Some(mut context)) => {
+            // Loading drops the task list unless a plan awaits approval; show it as stored
+            let mut updated_at = None;
+            if let Ok(Some((task_queue, saved_at))) = data.db.get_agent_context_tasks(session_id) {
+                context.task_queue = task_queue;
+                updated_at = Some(saved_at);
+            }
+            HttpResponse::Ok().json(serde_json::json!({
+                "session_id": session_id,
+                "updated_at": updated_at,
+                "context": context,
+            }))
+        }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "No agent context for this session"
+        })),
+        Err(e) => {
+            log::error!("Failed to get agent context: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
+/// Clear a session's orchestrator context so its next message starts the
+/// task over from planning. The message history is kept.
+async fn clear_agent_context(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let session_id = path.into_inner();
+
+    data.execution_tracker.clear_tasks_for_session(session_id);
+
+    match data.db.delete_agent_context(session_id) {
+        Ok(()) => {
+            log::info!("Cleared agent context for session {}", session_id);
+            HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "session_id": session_id
+            }))
+        }
+        Err(e) => {
+            log::error!("Failed to clear agent context: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/sessions")
@@ -529,6 +641,9 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/{id}/stop", web::post().to(stop_session))
             .route("/{id}/resume", web::post().to(resume_session))
             .route("/{id}/policy", web::put().to(update_reset_policy))
-            .route("/{id}/transcript", web::get().to(get_transcript)),
+            .route("/{id}/context_window", web::put().to(update_context_window))
+            .route("/{id}/transcript", web::get().to(get_transcript))
+            .route("/{id}/agent_context", web::get().to(get_agent_context))
+            .route("/{id}/agent_context", web::delete().to(clear_agent_context)),
     );
 }
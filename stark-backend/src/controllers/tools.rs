@@ -1,6 +1,7 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 
+use super::config_audit::audit_changes;
 use crate::tools::{ToolConfig, ToolDefinition, ToolExecution, ToolGroup, ToolProfile};
 use crate::AppState;
 
@@ -326,6 +327,7 @@ async fn update_global_config(
 
     let mut config = state.db.get_effective_tool_config(None).unwrap_or_default();
     config.channel_id = None; // Ensure it's global
+    let previous = config.clone();
 
     // Update fields if provided
     if let Some(ref profile_str) = body.profile {
@@ -357,11 +359,14 @@ async fn update_global_config(
     }
 
     match state.db.save_tool_config(&config) {
-        Ok(_) => HttpResponse::Ok().json(ConfigResponse {
-            success: true,
-            config: Some(config.into()),
-            error: None,
-        }),
+        Ok(_) => {
+            audit_changes(&state, &req, "tool_config", Some(&previous), Some(&config));
+            HttpResponse::Ok().json(ConfigResponse {
+                success: true,
+                config: Some(config.into()),
+                error: None,
+            })
+        }
         Err(e) => {
             log::error!("Failed to save tool config: {}", e);
             HttpResponse::InternalServerError().json(ConfigResponse {
@@ -408,16 +413,12 @@ async fn update_channel_config(
     let channel_id = path.into_inner();
 
     // Start with existing config or default
-    let mut config = state
-        .db
-        .get_channel_tool_config(channel_id)
-        .ok()
-        .flatten()
-        .unwrap_or_else(|| {
-            let mut c = ToolConfig::default();
-            c.channel_id = Some(channel_id);
-            c
-        });
+    let previous = state.db.get_channel_tool_config(channel_id).ok().flatten();
+    let mut config = previous.clone().unwrap_or_else(|| {
+        let mut c = ToolConfig::default();
+        c.channel_id = Some(channel_id);
+        c
+    });
 
     config.channel_id = Some(channel_id);
 
@@ -451,11 +452,15 @@ async fn update_channel_config(
     }
 
     match state.db.save_tool_config(&config) {
-        Ok(_) => HttpResponse::Ok().json(ConfigResponse {
-            success: true,
-            config: Some(config.into()),
-            error: None,
-        }),
+        Ok(_) => {
+            let section = format!("tool_config:channel:{}", channel_id);
+            audit_changes(&state, &req, &section, previous.as_ref(), Some(&config));
+            HttpResponse::Ok().json(ConfigResponse {
+                success: true,
+                config: Some(config.into()),
+                error: None,
+            })
+        }
         Err(e) => {
             log::error!("Failed to save channel tool config: {}", e);
             HttpResponse::InternalServerError().json(ConfigResponse {
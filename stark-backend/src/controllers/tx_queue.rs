@@ -90,6 +90,7 @@ async fn list_transactions(
 
     let tx_queue = &state.tx_queue;
     let limit = query.limit.unwrap_or(50).min(100);
+    tx_queue.expire_stale();
 
     // Parse status filter if provided
     let status_filter: Option<QueuedTxStatus> = query.status.as_ref().and_then(|s| {
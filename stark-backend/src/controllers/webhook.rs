@@ -0,0 +1,120 @@
+//! Webhook dispatch - run a message through the agent over plain HTTP
+//!
+//! For automation that doesn't go through a chat platform: the caller POSTs
+//! a message and gets the agent's reply in the response. Authenticated with
+//! `STARK_WEBHOOK_API_KEY` (as `X-API-Key` or a bearer token); the endpoint
+//! is disabled while that isn't set.
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+
+use crate::channels::NormalizedMessage;
+use crate::AppState;
+
+/// Webhook messages share channel 0 with the web channel, like Gmail. Their
+/// sessions are kept apart by channel type and `session_key`.
+const WEBHOOK_CHANNEL_ID: i64 = 0;
+const WEBHOOK_CHANNEL_TYPE: &str = "webhook";
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookRequest {
+    /// Caller-chosen user identifier (default "webhook")
+    #[serde(default)]
+    pub user_id: Option<String>,
+    pub text: String,
+    /// Messages with the same key continue one session (default: the user ID)
+    #[serde(default)]
+    pub session_key: Option<String>,
+    /// Optional JSON Schema for a structured reply (see `POST /api/chat`)
+    #[serde(default)]
+    pub response_schema: Option<serde_json::Value>,
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/api/webhook").route(web::post().to(dispatch_webhook)));
+}
+
+/// Check the request's API key against the configured one
+fn validate_api_key(req: &HttpRequest) -> Result<(), HttpResponse> {
+    let Some(expected) = crate::config::webhook_api_key() else {
+        return Err(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Webhook dispatch is disabled (STARK_WEBHOOK_API_KEY is not set)"
+        })));
+    };
+
+    let provided = req
+        .headers()
+        .get("X-API-Key")
+        .or_else(|| req.headers().get("Authorization"))
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").trim().to_string());
+
+    match provided {
+        Some(key) if keys_match(&key, &expected) => Ok(()),
+        Some(_) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid API key"
+        }))),
+        None => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "No API key provided"
+        }))),
+    }
+}
+
+/// Compare keys without stopping at the first differing byte
+fn keys_match(provided: &str, expected: &str) -> bool {
+    let (a, b) = (provided.as_bytes(), expected.as_bytes());
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Dispatch a message through the full agent pipeline and return the result
+async fn dispatch_webhook(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<WebhookRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_api_key(&req) {
+        return resp;
+    }
+
+    let body = body.into_inner();
+    if body.text.trim().is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "'text' must not be empty"
+        }));
+    }
+
+    let user_id = body
+        .user_id
+        .filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| "webhook".to_string());
+    let session_key = body
+        .session_key
+        .filter(|k| !k.trim().is_empty())
+        .unwrap_or_else(|| user_id.clone());
+
+    log::info!("[WEBHOOK] Dispatching message from '{}' (session key '{}')", user_id, session_key);
+
+    let normalized = NormalizedMessage {
+        channel_id: WEBHOOK_CHANNEL_ID,
+        channel_type: WEBHOOK_CHANNEL_TYPE.to_string(),
+        chat_id: session_key,
+        user_id: user_id.clone(),
+        user_name: user_id,
+        text: body.text,
+        message_id: None,
+        session_mode: None,
+        selected_network: None,
+        allowed_tools: None,
+        task_signature: None,
+        response_schema: body.response_schema,
+        reply_to: None,
+    };
+
+    let result = state.dispatcher.dispatch(normalized).await;
+
+    if result.error.is_some() {
+        HttpResponse::InternalServerError().json(result)
+    } else {
+        HttpResponse::Ok().json(result)
+    }
+}
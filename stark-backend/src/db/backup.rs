@@ -0,0 +1,136 @@
+//! Online backup and restore
+//!
+//! Backups use SQLite's online backup API, so they are consistent snapshots
+//! even while other pooled connections keep writing.
+
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::{Connection, OpenFlags};
+
+use super::sqlite::{Database, SCHEMA_VERSION};
+
+/// Pause before retrying a backup step that hit a lock
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Tables every restorable database must have
+const REQUIRED_TABLES: &[&str] = &["auth_sessions", "agent_settings", "chat_sessions", "session_messages"];
+
+impl Database {
+    /// Write a consistent snapshot of the live database to `dest`
+    pub fn backup_to(&self, dest: &Path) -> Result<(), String> {
+        let src = self.conn();
+        let mut dst = Connection::open(dest).map_err(|e| format!("Failed to create backup file: {}", e))?;
+        let backup = Backup::new(&src, &mut dst).map_err(|e| format!("Failed to start backup: {}", e))?;
+        copy_all(&backup).map_err(|e| format!("Backup failed: {}", e))
+    }
+
+    /// Replace the live database with the contents of `src`, then re-run
+    /// migrations so older backups gain new columns. Validates `src` first.
+    pub fn restore_from(&self, src: &Path) -> Result<i32, String> {
+        let version = validate_backup(src)?;
+        let src = Connection::open_with_flags(src, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("Failed to open backup: {}", e))?;
+        {
+            let mut dst = self.conn();
+            let backup = Backup::new(&src, &mut dst).map_err(|e| format!("Failed to start restore: {}", e))?;
+            copy_all(&backup).map_err(|e| format!("Restore failed: {}", e))?;
+        }
+        self.init().map_err(|e| format!("Failed to migrate restored database: {}", e))?;
+        Ok(version)
+    }
+}
+
+/// Copy every page in one step, so the snapshot is taken under a single read
+/// lock, retrying briefly while a writer holds the database
+fn copy_all(backup: &Backup) -> rusqlite::Result<()> {
+    loop {
+        match backup.step(-1)? {
+            StepResult::Done => return Ok(()),
+            // More / Busy / Locked
+            _ => std::thread::sleep(BUSY_RETRY_DELAY),
+        }
+    }
+}
+
+/// Check that `path` is an intact StarkBot database no newer than this build.
+/// Returns its schema version.
+pub fn validate_backup(path: &Path) -> Result<i32, String> {
+    // Read-write: quick_check on the FTS5 tables needs it
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        .map_err(|e| format!("Not a SQLite database: {}", e))?;
+
+    let check: String = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| format!("Not a SQLite database: {}", e))?;
+    if check != "ok" {
+        return Err(format!("Database failed integrity check: {}", check));
+    }
+
+    let version: i32 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "Backup schema version {} is newer than this build supports ({})",
+            version, SCHEMA_VERSION
+        ));
+    }
+
+    for table in REQUIRED_TABLES {
+        let exists: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                [table],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if !exists {
+            return Err(format!("Not a StarkBot database: missing table '{}'", table));
+        }
+    }
+
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_backup_and_restore_roundtrip() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("live.db").to_str().unwrap()).unwrap();
+        db.create_session().unwrap();
+
+        let backup_path = dir.path().join("backup.db");
+        db.backup_to(&backup_path).unwrap();
+        assert_eq!(validate_backup(&backup_path), Ok(SCHEMA_VERSION));
+
+        // Changes after the snapshot are rolled back by the restore
+        let later = db.create_session().unwrap();
+        db.restore_from(&backup_path).unwrap();
+        assert!(db.validate_session(&later.token).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_validate_rejects_foreign_and_newer_databases() {
+        let dir = tempdir().unwrap();
+
+        let other = dir.path().join("other.db");
+        Connection::open(&other).unwrap().execute("CREATE TABLE notes (body TEXT)", []).unwrap();
+        assert!(validate_backup(&other).unwrap_err().contains("missing table"));
+
+        let newer = dir.path().join("newer.db");
+        let db = Database::new(newer.to_str().unwrap()).unwrap();
+        db.conn().pragma_update(None, "user_version", SCHEMA_VERSION + 1).unwrap();
+        let err = validate_backup(&newer).unwrap_err();
+        assert!(err.contains("newer"), "{}", err);
+
+        let garbage = dir.path().join("garbage.db");
+        std::fs::write(&garbage, b"definitely not sqlite, just some bytes padded out").unwrap();
+        assert!(validate_backup(&garbage).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod backup;
 pub mod sqlite;
 pub mod tables;
 
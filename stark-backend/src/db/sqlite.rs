@@ -12,9 +12,20 @@ use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::Result as SqliteResult;
 use std::path::Path;
 
+/// Schema version stored in `PRAGMA user_version`. Bump it when a migration
+/// would break older builds reading the database (restores refuse backups
+/// from a newer schema).
+pub const SCHEMA_VERSION: i32 = 1;
+
 /// Pooled connection type alias for convenience
 pub type DbConn = PooledConnection<SqliteConnectionManager>;
 
+/// Pragmas applied to every pooled connection. WAL lets reads proceed while a
+/// write is in flight; busy_timeout makes a contended writer wait instead of
+/// failing with SQLITE_BUSY; synchronous=NORMAL is safe under WAL and
+/// shortens how long each write holds the lock.
+pub const CONNECTION_PRAGMAS: &str = "PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000; PRAGMA synchronous=NORMAL;";
+
 /// Main database wrapper with r2d2 connection pool
 pub struct Database {
     pool: Pool<SqliteConnectionManager>,
@@ -39,9 +50,7 @@ impl Database {
 
         // Create connection manager with SQLite pragmas
         let manager = SqliteConnectionManager::file(database_url)
-            .with_init(|conn| {
-                conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")
-            });
+            .with_init(|conn| conn.execute_batch(CONNECTION_PRAGMAS));
 
         // Build pool with reasonable defaults for SQLite
         // SQLite handles concurrency via WAL, so we don't need many connections
@@ -65,8 +74,15 @@ impl Database {
         self.pool.get().expect("Failed to get database connection from pool")
     }
 
+    /// Check that a pooled connection can be acquired and queried
+    /// (unlike `conn`, reports failure instead of panicking)
+    pub fn ping(&self) -> Result<(), String> {
+        let conn = self.pool.get().map_err(|e| e.to_string())?;
+        conn.query_row("SELECT 1", [], |_| Ok(())).map_err(|e| e.to_string())
+    }
+
     /// Initialize all database tables and run migrations
-    fn init(&self) -> SqliteResult<()> {
+    pub(super) fn init(&self) -> SqliteResult<()> {
         let conn = self.conn();
 
         // Migrate: rename sessions -> auth_sessions if the old table exists
@@ -293,6 +309,49 @@ impl Database {
             conn.execute("ALTER TABLE bot_settings ADD COLUMN rogue_mode_enabled INTEGER NOT NULL DEFAULT 0", [])?;
         }
 
+        // Migration: Add exec allowlist/denylist columns to bot_settings if they don't exist
+        let has_exec_allowlist: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('bot_settings') WHERE name='exec_allowlist'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|c| c > 0)
+            .unwrap_or(false);
+
+        if !has_exec_allowlist {
+            conn.execute("ALTER TABLE bot_settings ADD COLUMN exec_allowlist TEXT", [])?;
+            conn.execute("ALTER TABLE bot_settings ADD COLUMN exec_denylist TEXT", [])?;
+        }
+
+        // Migration: Add moderation settings column to bot_settings if it doesn't exist
+        let has_moderation: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('bot_settings') WHERE name='moderation'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|c| c > 0)
+            .unwrap_or(false);
+
+        if !has_moderation {
+            conn.execute("ALTER TABLE bot_settings ADD COLUMN moderation TEXT", [])?;
+        }
+
+        // Migration: Add transfer limits column to bot_settings if it doesn't exist
+        let has_transfer_limits: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('bot_settings') WHERE name='transfer_limits'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|c| c > 0)
+            .unwrap_or(false);
+
+        if !has_transfer_limits {
+            conn.execute("ALTER TABLE bot_settings ADD COLUMN transfer_limits TEXT", [])?;
+        }
+
         // Initialize bot_settings with defaults if empty
         let bot_settings_count: i64 = conn
             .query_row("SELECT COUNT(*) FROM bot_settings", [], |row| row.get(0))
@@ -344,6 +403,10 @@ impl Database {
         // Sliding window compaction: Add generation counter and timestamp
         let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN compaction_generation INTEGER NOT NULL DEFAULT 0", []);
         let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN last_compaction_at TEXT", []);
+        // Per-session context window override (NULL = use the model's window)
+        let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN context_window_override INTEGER", []);
+        // Language the session's replies are written in (detected from user messages)
+        let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN language TEXT", []);
 
         // Session messages table - conversation transcripts
         conn.execute(
@@ -361,6 +424,8 @@ impl Database {
             )",
             [],
         )?;
+        // Soft-delete marker for messages the user deleted on their platform
+        let _ = conn.execute("ALTER TABLE session_messages ADD COLUMN deleted_at TEXT", []);
 
         // Identity links table - cross-channel user mapping
         conn.execute(
@@ -379,6 +444,31 @@ impl Database {
             [],
         )?;
 
+        // Per-identity quota overrides (NULL limits use the global defaults)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS identity_quotas (
+                identity_id TEXT PRIMARY KEY,
+                messages_per_day INTEGER,
+                tool_calls_per_day INTEGER,
+                x402_usdc_per_day REAL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Per-identity usage counters, one row per UTC day
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS identity_usage (
+                identity_id TEXT NOT NULL,
+                day TEXT NOT NULL,
+                messages INTEGER NOT NULL DEFAULT 0,
+                tool_calls INTEGER NOT NULL DEFAULT 0,
+                x402_micro_usdc INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (identity_id, day)
+            )",
+            [],
+        )?;
+
         // Memories table - daily logs, long-term memories, preferences, facts, entities, tasks
         conn.execute(
             "CREATE TABLE IF NOT EXISTS memories (
@@ -628,6 +718,9 @@ impl Database {
             [],
         )?;
 
+        // Migration: opt-in seeding of each run with findings from earlier runs
+        let _ = conn.execute("ALTER TABLE cron_jobs ADD COLUMN seed_findings INTEGER NOT NULL DEFAULT 0", []);
+
         // Cron job runs history
         conn.execute(
             "CREATE TABLE IF NOT EXISTS cron_job_runs (
@@ -705,6 +798,30 @@ impl Database {
             [],
         )?;
 
+        // Migration: Add auto-reply filter columns to gmail_configs if they don't exist
+        let _ = conn.execute(
+            "ALTER TABLE gmail_configs ADD COLUMN reply_allowlist TEXT NOT NULL DEFAULT ''",
+            [],
+        );
+        let _ = conn.execute(
+            "ALTER TABLE gmail_configs ADD COLUMN reply_denylist TEXT NOT NULL DEFAULT ''",
+            [],
+        );
+        let _ = conn.execute(
+            "ALTER TABLE gmail_configs ADD COLUMN max_replies_per_thread INTEGER NOT NULL DEFAULT 3",
+            [],
+        );
+
+        // Auto-replies sent per Gmail thread (loop prevention)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS gmail_thread_replies (
+                thread_id TEXT PRIMARY KEY,
+                reply_count INTEGER NOT NULL DEFAULT 0,
+                last_reply_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
         // =====================================================
         // EIP-8004 Tables (Trustless Agents)
         // =====================================================
@@ -902,6 +1019,9 @@ impl Database {
             [],
         )?;
 
+        // Migration: task signature for carrying findings across runs of a recurring task
+        let _ = conn.execute("ALTER TABLE agent_contexts ADD COLUMN task_signature TEXT", []);
+
         // Sub-agents table - background agent execution tracking
         conn.execute(
             "CREATE TABLE IF NOT EXISTS sub_agents (
@@ -981,6 +1101,167 @@ impl Database {
             [],
         )?;
 
+        // Queued transactions table - signed transactions awaiting broadcast, so the
+        // transaction queue survives restarts
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS queued_transactions (
+                uuid TEXT PRIMARY KEY,
+                network TEXT NOT NULL,
+                from_address TEXT NOT NULL,
+                to_address TEXT NOT NULL,
+                value TEXT NOT NULL,
+                data TEXT NOT NULL,
+                gas_limit TEXT NOT NULL,
+                max_fee_per_gas TEXT NOT NULL,
+                max_priority_fee_per_gas TEXT NOT NULL,
+                nonce INTEGER NOT NULL,
+                signed_tx_hex TEXT NOT NULL,
+                status TEXT NOT NULL,
+                tx_hash TEXT,
+                error TEXT,
+                explorer_url TEXT,
+                transitions TEXT NOT NULL DEFAULT '[]',
+                channel_id INTEGER,
+                created_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL,
+                broadcast_at TEXT
+            )",
+            [],
+        )?;
+
+        // Personas table - named system prompt templates selectable per channel
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS personas (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                description TEXT,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Address book - named transfer recipients, global ('' identity) or per identity
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS address_book (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL COLLATE NOCASE,
+                address TEXT NOT NULL,
+                identity_id TEXT NOT NULL DEFAULT '',
+                notes TEXT,
+                use_count INTEGER NOT NULL DEFAULT 0,
+                last_used_at TEXT,
+                created_at TEXT NOT NULL,
+                UNIQUE(identity_id, name)
+            )",
+            [],
+        )?;
+
+        // Portfolio snapshots - periodic wallet valuations for balance history and P&L
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS portfolio_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                wallet TEXT NOT NULL,
+                address TEXT NOT NULL,
+                network TEXT NOT NULL,
+                total_usd REAL NOT NULL,
+                holdings TEXT NOT NULL,
+                taken_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_portfolio_snapshots_wallet ON portfolio_snapshots(address, network, taken_at)",
+            [],
+        )?;
+
+        // Limit orders - DEX swaps queued by the scheduler when a price target is crossed
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS limit_orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                network TEXT NOT NULL,
+                wallet TEXT NOT NULL DEFAULT 'primary',
+                sell_token TEXT NOT NULL,
+                buy_token TEXT NOT NULL,
+                sell_amount TEXT NOT NULL,
+                trigger_token TEXT NOT NULL,
+                trigger_address TEXT NOT NULL,
+                trigger_direction TEXT NOT NULL,
+                target_price_usd REAL NOT NULL,
+                auto_broadcast INTEGER NOT NULL DEFAULT 0,
+                status TEXT NOT NULL DEFAULT 'pending',
+                channel_id INTEGER,
+                triggered_price_usd REAL,
+                result TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_limit_orders_status ON limit_orders(status)",
+            [],
+        )?;
+
+        // Polymarket orders tracked for fill notifications
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS polymarket_orders (
+                order_id TEXT PRIMARY KEY,
+                wallet TEXT NOT NULL DEFAULT 'primary',
+                token_id TEXT NOT NULL,
+                side TEXT NOT NULL,
+                price TEXT NOT NULL,
+                original_size TEXT NOT NULL,
+                size_matched TEXT NOT NULL DEFAULT '0',
+                status TEXT NOT NULL,
+                outcome TEXT,
+                is_open INTEGER NOT NULL DEFAULT 1,
+                channel_id INTEGER,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Web3 presets added through the API (override config/web3_presets.ron)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS web3_presets (
+                name TEXT PRIMARY KEY,
+                preset TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // ABIs uploaded through the API (override files in abis/)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS abis (
+                name TEXT PRIMARY KEY,
+                abi TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Rogue mode audit table - every action taken while rogue mode is active
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS rogue_mode_audit (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                action TEXT NOT NULL,
+                details TEXT,
+                channel_id INTEGER,
+                session_id INTEGER,
+                success INTEGER,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
         // Channel settings table - per-channel configuration
         conn.execute(
             "CREATE TABLE IF NOT EXISTS channel_settings (
@@ -1039,9 +1320,54 @@ impl Database {
             [],
         )?;
 
+        // Deployments triggered by the deploy tool
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS deployments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                target TEXT NOT NULL,
+                workflow TEXT NOT NULL,
+                git_ref TEXT NOT NULL,
+                commit_sha TEXT,
+                run_id INTEGER,
+                status TEXT NOT NULL DEFAULT 'pending',
+                rollback_of INTEGER,
+                channel_id INTEGER,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_deployments_target ON deployments(target, id)",
+            [],
+        )?;
+
+        // Config audit table - settings changes made through the API
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS config_audit (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                actor TEXT,
+                section TEXT NOT NULL,
+                field TEXT NOT NULL,
+                old_value TEXT,
+                new_value TEXT,
+                secret INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_config_audit_section ON config_audit(section, id)",
+            [],
+        )?;
+
         // Initialize discord_hooks tables
         crate::discord_hooks::db::init_tables(&conn)?;
 
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
         Ok(())
     }
 
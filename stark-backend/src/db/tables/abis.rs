@@ -0,0 +1,89 @@
+//! Uploaded ABI database operations
+//!
+//! ABIs for `web3_function_call` uploaded through the API. They're cached in
+//! `tools::abis` and take precedence over the files in `abis/`.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::Serialize;
+
+use super::super::Database;
+use crate::tools::abis::AbiFile;
+
+/// An ABI uploaded through the API
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredAbi {
+    pub name: String,
+    pub abi: AbiFile,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Create or replace an uploaded ABI
+    pub fn upsert_abi(&self, name: &str, abi: &AbiFile) -> SqliteResult<StoredAbi> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+        let abi_json = serde_json::to_string(abi).unwrap_or_default();
+
+        conn.execute(
+            "INSERT INTO abis (name, abi, created_at, updated_at) VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT(name) DO UPDATE SET abi = excluded.abi, updated_at = excluded.updated_at",
+            rusqlite::params![name, abi_json, &now],
+        )?;
+
+        drop(conn);
+        self.get_abi(name)
+            .and_then(|a| a.ok_or(rusqlite::Error::QueryReturnedNoRows))
+    }
+
+    /// Get an uploaded ABI by name
+    pub fn get_abi(&self, name: &str) -> SqliteResult<Option<StoredAbi>> {
+        let conn = self.conn();
+        match conn.query_row(
+            "SELECT name, abi, created_at, updated_at FROM abis WHERE name = ?1",
+            [name],
+            Self::row_to_abi,
+        ) {
+            Ok(abi) => Ok(Some(abi)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// List all uploaded ABIs by name
+    pub fn list_abis(&self) -> SqliteResult<Vec<StoredAbi>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT name, abi, created_at, updated_at FROM abis ORDER BY name ASC",
+        )?;
+
+        let abis = stmt
+            .query_map([], Self::row_to_abi)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(abis)
+    }
+
+    fn row_to_abi(row: &rusqlite::Row) -> rusqlite::Result<StoredAbi> {
+        let abi_json: String = row.get(1)?;
+        let created_at_str: String = row.get(2)?;
+        let updated_at_str: String = row.get(3)?;
+
+        let abi = serde_json::from_str(&abi_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(StoredAbi {
+            name: row.get(0)?,
+            abi,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
@@ -0,0 +1,134 @@
+//! Address book database operations
+//!
+//! Named transfer recipients. Entries are global (empty `identity_id` in the
+//! table) or scoped to one identity; an identity's own entry wins over a
+//! global one with the same name.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::Serialize;
+
+use super::super::Database;
+
+/// A named recipient
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressBookEntry {
+    pub id: i64,
+    pub name: String,
+    pub address: String,
+    /// `None` for global entries
+    pub identity_id: Option<String>,
+    pub notes: Option<String>,
+    /// Number of transfers queued to this entry
+    pub use_count: i64,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Add an entry, or update the address/notes of an existing entry with the
+    /// same name and scope. Changing the address resets its use count.
+    pub fn upsert_address_book_entry(
+        &self,
+        name: &str,
+        address: &str,
+        identity_id: Option<&str>,
+        notes: Option<&str>,
+    ) -> SqliteResult<AddressBookEntry> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+        let scope = identity_id.unwrap_or("");
+
+        conn.execute(
+            "INSERT INTO address_book (name, address, identity_id, notes, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(identity_id, name) DO UPDATE SET
+                use_count = CASE WHEN address = excluded.address THEN use_count ELSE 0 END,
+                last_used_at = CASE WHEN address = excluded.address THEN last_used_at ELSE NULL END,
+                address = excluded.address,
+                notes = COALESCE(excluded.notes, notes)",
+            rusqlite::params![name.trim(), address, scope, notes, &now],
+        )?;
+
+        conn.query_row(
+            "SELECT id, name, address, identity_id, notes, use_count, last_used_at, created_at
+             FROM address_book WHERE identity_id = ?1 AND name = ?2",
+            rusqlite::params![scope, name.trim()],
+            Self::row_to_address_book_entry,
+        )
+    }
+
+    /// List global entries plus those scoped to `identity_id`
+    pub fn list_address_book(&self, identity_id: Option<&str>) -> SqliteResult<Vec<AddressBookEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, name, address, identity_id, notes, use_count, last_used_at, created_at
+             FROM address_book WHERE identity_id = '' OR identity_id = ?1
+             ORDER BY name ASC, identity_id DESC",
+        )?;
+
+        let entries = stmt
+            .query_map([identity_id.unwrap_or("")], Self::row_to_address_book_entry)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// Look up a name, preferring the identity's own entry over a global one
+    pub fn resolve_address_book(&self, name: &str, identity_id: Option<&str>) -> SqliteResult<Option<AddressBookEntry>> {
+        let conn = self.conn();
+        match conn.query_row(
+            "SELECT id, name, address, identity_id, notes, use_count, last_used_at, created_at
+             FROM address_book WHERE name = ?1 AND (identity_id = '' OR identity_id = ?2)
+             ORDER BY identity_id = '' ASC LIMIT 1",
+            rusqlite::params![name.trim(), identity_id.unwrap_or("")],
+            Self::row_to_address_book_entry,
+        ) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Delete an entry by name within one scope
+    pub fn remove_address_book_entry(&self, name: &str, identity_id: Option<&str>) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "DELETE FROM address_book WHERE name = ?1 AND identity_id = ?2",
+            rusqlite::params![name.trim(), identity_id.unwrap_or("")],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Record that a transfer was queued to an entry
+    pub fn mark_address_book_used(&self, id: i64) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE address_book SET use_count = use_count + 1, last_used_at = ?1 WHERE id = ?2",
+            rusqlite::params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_address_book_entry(row: &rusqlite::Row) -> rusqlite::Result<AddressBookEntry> {
+        let identity_id: String = row.get(3)?;
+        let last_used_at: Option<String> = row.get(6)?;
+        let created_at_str: String = row.get(7)?;
+
+        Ok(AddressBookEntry {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            address: row.get(2)?,
+            identity_id: (!identity_id.is_empty()).then_some(identity_id),
+            notes: row.get(4)?,
+            use_count: row.get(5)?,
+            last_used_at: last_used_at
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
@@ -3,6 +3,7 @@
 //! Stores AgentContext between messages so the agent can continue
 //! across a multi-turn conversation.
 
+use crate::ai::multi_agent::findings;
 use crate::ai::multi_agent::types::{ActiveSkill, AgentContext, AgentMode, AgentSubtype, TaskQueue};
 use crate::db::Database;
 use chrono::Utc;
@@ -15,7 +16,8 @@ impl Database {
 
         let mut stmt = conn.prepare(
             "SELECT original_request, mode, mode_iterations, total_iterations,
-                    exploration_notes, scratchpad, subtype, active_skill_json
+                    exploration_notes, scratchpad, subtype, active_skill_json,
+                    task_signature, findings, plan_ready, tasks_json
              FROM agent_contexts
              WHERE session_id = ?",
         )?;
@@ -29,6 +31,10 @@ impl Database {
             let scratchpad: String = row.get(5)?;
             let subtype_str: Option<String> = row.get(6).ok();
             let active_skill_json: Option<String> = row.get(7).ok().flatten();
+            let task_signature: Option<String> = row.get(8).ok().flatten();
+            let findings_json: Option<String> = row.get(9).ok().flatten();
+            let plan_awaiting_approval: bool = row.get::<_, i64>(10).map(|v| v != 0).unwrap_or(false);
+            let tasks_json: Option<String> = row.get(11).ok();
 
             // Parse mode (defaults to Assistant)
             let mode = AgentMode::from_str(&mode_str).unwrap_or_default();
@@ -46,6 +52,19 @@ impl Database {
             let active_skill: Option<ActiveSkill> = active_skill_json
                 .and_then(|json| serde_json::from_str(&json).ok());
 
+            let findings: Vec<String> = findings_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+
+            // The task queue only carries over while a plan awaits approval
+            let task_queue: TaskQueue = if plan_awaiting_approval {
+                tasks_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default()
+            } else {
+                TaskQueue::default()
+            };
+
             Ok(AgentContext {
                 original_request,
                 exploration_notes,
@@ -58,9 +77,15 @@ impl Database {
                 actual_tool_calls: 0,      // Reset on load
                 no_tool_warnings: 0,       // Reset on load
                 waiting_for_user_context: None, // Reset on load
-                task_queue: TaskQueue::default(), // Reset on load
+                task_queue,
                 planner_completed: false,  // Reset on load
                 selected_network: None,    // Reset on load
+                replans: 0,                // Reset on load
+                replan_note: None,         // Reset on load
+                task_signature,
+                findings,
+                plan_approval_required: false, // Set per dispatch
+                plan_awaiting_approval,
             })
         });
 
@@ -85,6 +110,10 @@ impl Database {
             .unwrap_or_else(|_| "[]".to_string());
         let active_skill_json: Option<String> = context.active_skill.as_ref()
             .and_then(|s| serde_json::to_string(s).ok());
+        let findings_json = serde_json::to_string(&context.findings)
+            .unwrap_or_else(|_| "[]".to_string());
+        let tasks_json = serde_json::to_string(&context.task_queue)
+            .unwrap_or_else(|_| "{\"tasks\":[]}".to_string());
 
         // Use INSERT OR REPLACE for upsert behavior
         // Note: Using simplified schema - old columns will be NULL/defaults
@@ -93,10 +122,11 @@ impl Database {
                 session_id, original_request, mode, mode_iterations, total_iterations,
                 exploration_notes, scratchpad, subtype, active_skill_json,
                 context_sufficient, plan_ready, findings, plan_summary, tasks_json,
-                created_at, updated_at
+                task_signature, created_at, updated_at
             ) VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9,
-                0, 0, '[]', NULL, '{\"tasks\":[]}',
+                0, ?13, ?11, NULL, ?14,
+                ?12,
                 COALESCE((SELECT created_at FROM agent_contexts WHERE session_id = ?1), ?10),
                 ?10
             )",
@@ -111,12 +141,77 @@ impl Database {
                 context.subtype.as_str(),
                 active_skill_json,
                 now,
+                findings_json,
+                context.task_signature,
+                context.plan_awaiting_approval,
+                tasks_json,
             ],
         )?;
 
         Ok(())
     }
 
+    /// The task list last saved for a session and when the context was last
+    /// saved, for inspection. Unlike `get_agent_context`, which only restores
+    /// the task list while a plan awaits approval, this returns it as stored.
+    pub fn get_agent_context_tasks(&self, session_id: i64) -> SqliteResult<Option<(TaskQueue, String)>> {
+        let conn = self.conn();
+        let result = conn.query_row(
+            "SELECT tasks_json, updated_at FROM agent_contexts WHERE session_id = ?",
+            params![session_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        );
+
+        match result {
+            Ok((tasks_json, updated_at)) => {
+                let task_queue = serde_json::from_str(&tasks_json).unwrap_or_default();
+                Ok(Some((task_queue, updated_at)))
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Findings from earlier runs of a recurring task, most recent first.
+    /// Runs match when their task signatures are at least
+    /// `findings::MIN_SIMILARITY` alike; `exclude_session_id` is the current run.
+    pub fn find_prior_findings(
+        &self,
+        task_signature: &str,
+        exclude_session_id: i64,
+        limit: usize,
+    ) -> SqliteResult<Vec<String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT task_signature, findings FROM agent_contexts
+             WHERE task_signature IS NOT NULL AND session_id != ?1 AND findings != '[]'
+             ORDER BY updated_at DESC
+             LIMIT 50",
+        )?;
+
+        let rows = stmt.query_map(params![exclude_session_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut prior: Vec<String> = Vec::new();
+        for (signature, findings_json) in rows.filter_map(|r| r.ok()) {
+            if findings::similarity(task_signature, &signature) < findings::MIN_SIMILARITY {
+                continue;
+            }
+            let run_findings: Vec<String> = serde_json::from_str(&findings_json).unwrap_or_default();
+            for finding in run_findings {
+                if prior.len() >= limit {
+                    return Ok(prior);
+                }
+                if !prior.contains(&finding) {
+                    prior.push(finding);
+                }
+            }
+        }
+
+        Ok(prior)
+    }
+
     /// Delete agent context for a session (e.g., on session reset)
     pub fn delete_agent_context(&self, session_id: i64) -> SqliteResult<()> {
         let conn = self.conn();
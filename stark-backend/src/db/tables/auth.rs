@@ -88,6 +88,20 @@ impl Database {
         Ok(session)
     }
 
+    /// Wallet address a still-valid session was signed in with
+    pub fn session_public_address(&self, token: &str) -> SqliteResult<Option<String>> {
+        let conn = self.conn();
+        let address = conn
+            .query_row(
+                "SELECT public_address FROM auth_sessions WHERE token = ?1 AND expires_at > ?2",
+                [token, &Utc::now().to_rfc3339()],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .ok()
+            .flatten();
+        Ok(address)
+    }
+
     pub fn delete_session(&self, token: &str) -> SqliteResult<bool> {
         let conn = self.conn();
         let rows_affected = conn.execute("DELETE FROM auth_sessions WHERE token = ?1", [token])?;
@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use rusqlite::Result as SqliteResult;
 use std::collections::HashMap;
 
-use crate::models::{BotSettings, DEFAULT_MAX_TOOL_ITERATIONS};
+use crate::models::{BotSettings, ModerationSettings, TransferLimits, DEFAULT_MAX_TOOL_ITERATIONS};
 use super::super::Database;
 
 impl Database {
@@ -13,7 +13,7 @@ impl Database {
         let conn = self.conn();
 
         let result = conn.query_row(
-            "SELECT id, bot_name, bot_email, web3_tx_requires_confirmation, rpc_provider, custom_rpc_endpoints, max_tool_iterations, rogue_mode_enabled, created_at, updated_at FROM bot_settings LIMIT 1",
+            "SELECT id, bot_name, bot_email, web3_tx_requires_confirmation, rpc_provider, custom_rpc_endpoints, max_tool_iterations, rogue_mode_enabled, created_at, updated_at, exec_allowlist, exec_denylist, moderation, transfer_limits FROM bot_settings LIMIT 1",
             [],
             |row| {
                 let web3_tx_confirmation: i64 = row.get(3)?;
@@ -23,9 +23,25 @@ impl Database {
                 let rogue_mode_enabled: i64 = row.get::<_, Option<i64>>(7)?.unwrap_or(0);
                 let created_at_str: String = row.get(8)?;
                 let updated_at_str: String = row.get(9)?;
+                let exec_allowlist_json: Option<String> = row.get(10)?;
+                let exec_denylist_json: Option<String> = row.get(11)?;
+                let moderation_json: Option<String> = row.get(12)?;
+                let transfer_limits_json: Option<String> = row.get(13)?;
 
                 let custom_rpc_endpoints: Option<HashMap<String, String>> = custom_rpc_endpoints_json
                     .and_then(|json| serde_json::from_str(&json).ok());
+                let exec_allowlist: Vec<String> = exec_allowlist_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+                let exec_denylist: Vec<String> = exec_denylist_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+                let moderation: ModerationSettings = moderation_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+                let transfer_limits: TransferLimits = transfer_limits_json
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
 
                 Ok(BotSettings {
                     id: row.get(0)?,
@@ -36,6 +52,10 @@ impl Database {
                     custom_rpc_endpoints,
                     max_tool_iterations,
                     rogue_mode_enabled: rogue_mode_enabled != 0,
+                    exec_allowlist,
+                    exec_denylist,
+                    moderation,
+                    transfer_limits,
                     created_at: DateTime::parse_from_rfc3339(&created_at_str)
                         .unwrap()
                         .with_timezone(&Utc),
@@ -59,10 +79,11 @@ impl Database {
         bot_email: Option<&str>,
         web3_tx_requires_confirmation: Option<bool>,
     ) -> SqliteResult<BotSettings> {
-        self.update_bot_settings_full(bot_name, bot_email, web3_tx_requires_confirmation, None, None, None, None)
+        self.update_bot_settings_full(bot_name, bot_email, web3_tx_requires_confirmation, None, None, None, None, None, None, None, None)
     }
 
     /// Update bot settings with all fields including RPC config
+    #[allow(clippy::too_many_arguments)]
     pub fn update_bot_settings_full(
         &self,
         bot_name: Option<&str>,
@@ -72,6 +93,10 @@ impl Database {
         custom_rpc_endpoints: Option<&HashMap<String, String>>,
         max_tool_iterations: Option<i32>,
         rogue_mode_enabled: Option<bool>,
+        exec_allowlist: Option<&[String]>,
+        exec_denylist: Option<&[String]>,
+        moderation: Option<&ModerationSettings>,
+        transfer_limits: Option<&TransferLimits>,
     ) -> SqliteResult<BotSettings> {
         let conn = self.conn();
         let now = Utc::now().to_rfc3339();
@@ -129,6 +154,34 @@ impl Database {
                     rusqlite::params![if rogue_mode { 1 } else { 0 }, &now],
                 )?;
             }
+            if let Some(allowlist) = exec_allowlist {
+                let allowlist_json = serde_json::to_string(allowlist).unwrap_or_else(|_| "[]".to_string());
+                conn.execute(
+                    "UPDATE bot_settings SET exec_allowlist = ?1, updated_at = ?2",
+                    [&allowlist_json, &now],
+                )?;
+            }
+            if let Some(denylist) = exec_denylist {
+                let denylist_json = serde_json::to_string(denylist).unwrap_or_else(|_| "[]".to_string());
+                conn.execute(
+                    "UPDATE bot_settings SET exec_denylist = ?1, updated_at = ?2",
+                    [&denylist_json, &now],
+                )?;
+            }
+            if let Some(moderation) = moderation {
+                let moderation_json = serde_json::to_string(moderation).unwrap_or_else(|_| "{}".to_string());
+                conn.execute(
+                    "UPDATE bot_settings SET moderation = ?1, updated_at = ?2",
+                    [&moderation_json, &now],
+                )?;
+            }
+            if let Some(limits) = transfer_limits {
+                let limits_json = serde_json::to_string(limits).unwrap_or_else(|_| "{}".to_string());
+                conn.execute(
+                    "UPDATE bot_settings SET transfer_limits = ?1, updated_at = ?2",
+                    [&limits_json, &now],
+                )?;
+            }
         } else {
             // Insert new
             let name = bot_name.unwrap_or("StarkBot");
@@ -139,9 +192,17 @@ impl Database {
             let rogue_mode = rogue_mode_enabled.unwrap_or(false);
             let endpoints_json = custom_rpc_endpoints
                 .map(|e| serde_json::to_string(e).unwrap_or_else(|_| "{}".to_string()));
+            let allowlist_json = exec_allowlist
+                .map(|l| serde_json::to_string(l).unwrap_or_else(|_| "[]".to_string()));
+            let denylist_json = exec_denylist
+                .map(|l| serde_json::to_string(l).unwrap_or_else(|_| "[]".to_string()));
+            let moderation_json = moderation
+                .map(|m| serde_json::to_string(m).unwrap_or_else(|_| "{}".to_string()));
+            let limits_json = transfer_limits
+                .map(|l| serde_json::to_string(l).unwrap_or_else(|_| "{}".to_string()));
             conn.execute(
-                "INSERT INTO bot_settings (bot_name, bot_email, web3_tx_requires_confirmation, rpc_provider, custom_rpc_endpoints, max_tool_iterations, rogue_mode_enabled, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                rusqlite::params![name, email, if confirmation { 1 } else { 0 }, provider, endpoints_json, max_iterations, if rogue_mode { 1 } else { 0 }, &now, &now],
+                "INSERT INTO bot_settings (bot_name, bot_email, web3_tx_requires_confirmation, rpc_provider, custom_rpc_endpoints, max_tool_iterations, rogue_mode_enabled, exec_allowlist, exec_denylist, moderation, transfer_limits, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                rusqlite::params![name, email, if confirmation { 1 } else { 0 }, provider, endpoints_json, max_iterations, if rogue_mode { 1 } else { 0 }, allowlist_json, denylist_json, moderation_json, limits_json, &now, &now],
             )?;
         }
 
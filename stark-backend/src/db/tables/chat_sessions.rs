@@ -1,6 +1,6 @@
 //! Chat session and session message database operations
 
-use chrono::{DateTime, Timelike, Utc};
+use chrono::{DateTime, Utc};
 use rusqlite::Result as SqliteResult;
 
 use crate::models::{ChatSession, CompletionStatus, MessageRole, ResetPolicy, SessionMessage, SessionScope};
@@ -31,36 +31,11 @@ impl Database {
 
         // Try to get existing active session
         if let Some(mut session) = self.get_chat_session_by_key(&session_key)? {
-            // Check if session needs reset based on policy
-            let should_reset = match session.reset_policy {
-                ResetPolicy::Daily => {
-                    let reset_hour = session.daily_reset_hour.unwrap_or(0);
-                    let last_activity = session.last_activity_at;
-                    let last_day = last_activity.date_naive();
-                    let today = now.date_naive();
-
-                    if today > last_day {
-                        // Check if we've passed the reset hour today
-                        now.hour() >= reset_hour as u32
-                    } else {
-                        false
-                    }
-                }
-                ResetPolicy::Idle => {
-                    if let Some(timeout) = session.idle_timeout_minutes {
-                        let idle_duration = now.signed_duration_since(session.last_activity_at);
-                        idle_duration.num_minutes() > timeout as i64
-                    } else {
-                        false
-                    }
-                }
-                ResetPolicy::Manual | ResetPolicy::Never => false,
-            };
+            // Fallback for sessions the scheduler hasn't reached yet
+            let should_reset = session.reset_due(now, &crate::config::session_reset_timezone());
 
             if should_reset {
-                // Reset the session
-                self.reset_chat_session(session.id)?;
-                session = self.get_chat_session(session.id)?.unwrap();
+                session = self.reset_chat_session(session.id)?;
             } else {
                 // Update last activity
                 let conn = self.conn();
@@ -125,7 +100,7 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, session_key, agent_id, scope, channel_type, channel_id, platform_chat_id,
              is_active, reset_policy, idle_timeout_minutes, daily_reset_hour,
-             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status
+             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status, context_window_override
              FROM chat_sessions WHERE id = ?1",
         )?;
 
@@ -143,7 +118,7 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, session_key, agent_id, scope, channel_type, channel_id, platform_chat_id,
              is_active, reset_policy, idle_timeout_minutes, daily_reset_hour,
-             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status
+             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status, context_window_override
              FROM chat_sessions ORDER BY last_activity_at DESC LIMIT 100",
         )?;
 
@@ -155,6 +130,25 @@ impl Database {
         Ok(sessions)
     }
 
+    /// List active sessions with a time-based reset policy (daily or idle)
+    pub fn list_sessions_with_reset_policy(&self) -> SqliteResult<Vec<ChatSession>> {
+        let conn = self.conn();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, session_key, agent_id, scope, channel_type, channel_id, platform_chat_id,
+             is_active, reset_policy, idle_timeout_minutes, daily_reset_hour,
+             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status, context_window_override
+             FROM chat_sessions WHERE is_active = 1 AND reset_policy IN ('daily', 'idle')",
+        )?;
+
+        let sessions = stmt
+            .query_map([], Self::row_to_chat_session)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(sessions)
+    }
+
     /// Get a chat session by session key
     pub fn get_chat_session_by_key(&self, session_key: &str) -> SqliteResult<Option<ChatSession>> {
         let conn = self.conn();
@@ -162,7 +156,7 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, session_key, agent_id, scope, channel_type, channel_id, platform_chat_id,
              is_active, reset_policy, idle_timeout_minutes, daily_reset_hour,
-             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status
+             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status, context_window_override
              FROM chat_sessions WHERE session_key = ?1 AND is_active = 1",
         )?;
 
@@ -185,7 +179,7 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, session_key, agent_id, scope, channel_type, channel_id, platform_chat_id,
              is_active, reset_policy, idle_timeout_minutes, daily_reset_hour,
-             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status
+             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status, context_window_override
              FROM chat_sessions
              WHERE channel_type = ?1 AND channel_id = ?2 AND is_active = 1
              ORDER BY last_activity_at DESC LIMIT 1",
@@ -259,16 +253,16 @@ impl Database {
         let now_str = now.to_rfc3339();
 
         // Get the old session info
-        let old_session: Option<(String, Option<String>, String, String, i64, String, String, Option<i32>, Option<i32>)> = conn
+        let old_session: Option<(String, Option<String>, String, String, i64, String, String, Option<i32>, Option<i32>, Option<i32>)> = conn
             .query_row(
-                "SELECT session_key, agent_id, scope, channel_type, channel_id, platform_chat_id, reset_policy, idle_timeout_minutes, daily_reset_hour
+                "SELECT session_key, agent_id, scope, channel_type, channel_id, platform_chat_id, reset_policy, idle_timeout_minutes, daily_reset_hour, context_window_override
                  FROM chat_sessions WHERE id = ?1",
                 [id],
-                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?, row.get(8)?)),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?, row.get(8)?, row.get(9)?)),
             )
             .ok();
 
-        let Some((_old_session_key, agent_id, scope, channel_type, channel_id, _platform_chat_id, reset_policy, idle_timeout, daily_hour)) = old_session else {
+        let Some((_old_session_key, agent_id, scope, channel_type, channel_id, _platform_chat_id, reset_policy, idle_timeout, daily_hour, context_window)) = old_session else {
             return Err(rusqlite::Error::QueryReturnedNoRows);
         };
 
@@ -292,8 +286,8 @@ impl Database {
         // Create new session with same settings but new unique key
         conn.execute(
             "INSERT INTO chat_sessions (session_key, agent_id, scope, channel_type, channel_id, platform_chat_id,
-             is_active, reset_policy, idle_timeout_minutes, daily_reset_hour, created_at, updated_at, last_activity_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7, ?8, ?9, ?10, ?10, ?10)",
+             is_active, reset_policy, idle_timeout_minutes, daily_reset_hour, context_window_override, created_at, updated_at, last_activity_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7, ?8, ?9, ?10, ?11, ?11, ?11)",
             rusqlite::params![
                 &new_session_key,
                 agent_id,
@@ -304,6 +298,7 @@ impl Database {
                 &reset_policy,
                 idle_timeout,
                 daily_hour,
+                context_window,
                 &now_str,
             ],
         )?;
@@ -428,6 +423,7 @@ impl Database {
                 let status_str: String = row.get(18).unwrap_or_else(|_| "active".to_string());
                 CompletionStatus::from_str(&status_str).unwrap_or_default()
             },
+            context_window_override: row.get(19).unwrap_or(None),
         })
     }
 
@@ -486,7 +482,7 @@ impl Database {
 
         let mut stmt = conn.prepare(
             "SELECT id, session_id, role, content, user_id, user_name, platform_message_id, tokens_used, created_at
-             FROM session_messages WHERE session_id = ?1 ORDER BY created_at ASC",
+             FROM session_messages WHERE session_id = ?1 AND deleted_at IS NULL ORDER BY created_at ASC",
         )?;
 
         let messages = stmt
@@ -503,7 +499,7 @@ impl Database {
 
         let mut stmt = conn.prepare(
             "SELECT id, session_id, role, content, user_id, user_name, platform_message_id, tokens_used, created_at
-             FROM session_messages WHERE session_id = ?1 ORDER BY created_at DESC LIMIT ?2",
+             FROM session_messages WHERE session_id = ?1 AND deleted_at IS NULL ORDER BY created_at DESC LIMIT ?2",
         )?;
 
         let mut messages: Vec<SessionMessage> = stmt
@@ -516,11 +512,17 @@ impl Database {
         Ok(messages)
     }
 
+    /// Count active chat sessions
+    pub fn count_active_chat_sessions(&self) -> SqliteResult<i64> {
+        let conn = self.conn();
+        conn.query_row("SELECT COUNT(*) FROM chat_sessions WHERE is_active = 1", [], |row| row.get(0))
+    }
+
     /// Count messages in a session
     pub fn count_session_messages(&self, session_id: i64) -> SqliteResult<i64> {
         let conn = self.conn();
         conn.query_row(
-            "SELECT COUNT(*) FROM session_messages WHERE session_id = ?1",
+            "SELECT COUNT(*) FROM session_messages WHERE session_id = ?1 AND deleted_at IS NULL",
             [session_id],
             |row| row.get(0),
         )
@@ -531,7 +533,7 @@ impl Database {
         let conn = self.conn();
         conn.query_row(
             "SELECT content FROM session_messages
-             WHERE session_id = ?1 AND role = 'user'
+             WHERE session_id = ?1 AND role = 'user' AND deleted_at IS NULL
              ORDER BY created_at ASC LIMIT 1",
             [session_id],
             |row| row.get(0),
@@ -544,6 +546,73 @@ impl Database {
         })
     }
 
+    /// Find the most recent live message a platform message ID was stored as on a
+    /// channel. Pass `user_id` where platform message IDs are only unique per chat
+    /// (Telegram), so one user's message isn't matched against another chat's.
+    pub fn find_message_by_platform_id(
+        &self,
+        channel_type: &str,
+        channel_id: i64,
+        platform_message_id: &str,
+        user_id: Option<&str>,
+    ) -> SqliteResult<Option<SessionMessage>> {
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT sm.id, sm.session_id, sm.role, sm.content, sm.user_id, sm.user_name, sm.platform_message_id, sm.tokens_used, sm.created_at
+             FROM session_messages sm
+             INNER JOIN chat_sessions cs ON cs.id = sm.session_id
+             WHERE cs.channel_type = ?1 AND cs.channel_id = ?2 AND sm.platform_message_id = ?3
+               AND (?4 IS NULL OR sm.user_id = ?4) AND sm.deleted_at IS NULL
+             ORDER BY sm.created_at DESC LIMIT 1",
+            rusqlite::params![channel_type, channel_id, platform_message_id, user_id],
+            |row| Self::row_to_session_message(row),
+        ).map(Some).or_else(|e| {
+            if matches!(e, rusqlite::Error::QueryReturnedNoRows) {
+                Ok(None)
+            } else {
+                Err(e)
+            }
+        })
+    }
+
+    /// Get the ID of the latest live user message in a session
+    pub fn get_latest_user_message_id(&self, session_id: i64) -> SqliteResult<Option<i64>> {
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT id FROM session_messages
+             WHERE session_id = ?1 AND role = 'user' AND deleted_at IS NULL
+             ORDER BY created_at DESC, id DESC LIMIT 1",
+            [session_id],
+            |row| row.get(0),
+        ).map(Some).or_else(|e| {
+            if matches!(e, rusqlite::Error::QueryReturnedNoRows) {
+                Ok(None)
+            } else {
+                Err(e)
+            }
+        })
+    }
+
+    /// Replace a message's content (the user edited it on their platform)
+    pub fn update_session_message_content(&self, message_id: i64, content: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "UPDATE session_messages SET content = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            rusqlite::params![content, message_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Hide a message from session history without dropping the row
+    pub fn soft_delete_session_message(&self, message_id: i64) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "UPDATE session_messages SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            rusqlite::params![Utc::now().to_rfc3339(), message_id],
+        )?;
+        Ok(rows > 0)
+    }
+
     /// List heartbeat sessions with their associated mind node IDs
     /// Parses the node ID from the heartbeat message content
     pub fn list_heartbeat_sessions(&self, limit: i32) -> SqliteResult<Vec<(ChatSession, Option<i64>)>> {
@@ -553,7 +622,7 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, session_key, agent_id, scope, channel_type, channel_id, platform_chat_id,
              is_active, reset_policy, idle_timeout_minutes, daily_reset_hour,
-             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status
+             created_at, updated_at, last_activity_at, expires_at, context_tokens, max_context_tokens, compaction_id, completion_status, context_window_override
              FROM chat_sessions
              WHERE channel_type = 'heartbeat'
              ORDER BY created_at DESC
@@ -582,7 +651,7 @@ impl Database {
     fn extract_heartbeat_node_id(&self, conn: &super::super::DbConn, session_id: i64) -> Option<i64> {
         let content: Option<String> = conn.query_row(
             "SELECT content FROM session_messages
-             WHERE session_id = ?1 AND role = 'user'
+             WHERE session_id = ?1 AND role = 'user' AND deleted_at IS NULL
              ORDER BY created_at ASC LIMIT 1",
             [session_id],
             |row| row.get(0),
@@ -647,13 +716,27 @@ impl Database {
         Ok(())
     }
 
+    /// Set or clear a session's context window override
+    pub fn update_session_context_window_override(&self, id: i64, context_window: Option<i32>) -> SqliteResult<Option<ChatSession>> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "UPDATE chat_sessions SET context_window_override = ?1, updated_at = ?2 WHERE id = ?3",
+            rusqlite::params![context_window, &now, id],
+        )?;
+
+        drop(conn);
+        self.get_chat_session(id)
+    }
+
     /// Get oldest messages for compaction (excludes most recent messages)
     pub fn get_messages_for_compaction(&self, session_id: i64, keep_recent: i32) -> SqliteResult<Vec<SessionMessage>> {
         let conn = self.conn();
 
         // Get total count first
         let total: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM session_messages WHERE session_id = ?1",
+            "SELECT COUNT(*) FROM session_messages WHERE session_id = ?1 AND deleted_at IS NULL",
             [session_id],
             |row| row.get(0),
         )?;
@@ -665,7 +748,7 @@ impl Database {
 
         let mut stmt = conn.prepare(
             "SELECT id, session_id, role, content, user_id, user_name, platform_message_id, tokens_used, created_at
-             FROM session_messages WHERE session_id = ?1 ORDER BY created_at ASC LIMIT ?2",
+             FROM session_messages WHERE session_id = ?1 AND deleted_at IS NULL ORDER BY created_at ASC LIMIT ?2",
         )?;
 
         let messages = stmt
@@ -700,7 +783,7 @@ impl Database {
         // Get IDs of messages to delete (all except the most recent)
         let deleted = conn.execute(
             "DELETE FROM session_messages WHERE session_id = ?1 AND id NOT IN (
-                SELECT id FROM session_messages WHERE session_id = ?1 ORDER BY created_at DESC LIMIT ?2
+                SELECT id FROM session_messages WHERE session_id = ?1 AND deleted_at IS NULL ORDER BY created_at DESC LIMIT ?2
             )",
             rusqlite::params![session_id, keep_recent],
         )?;
@@ -768,7 +851,7 @@ impl Database {
 
         let mut stmt = conn.prepare(
             "SELECT id, session_id, role, content, user_id, user_name, platform_message_id, tokens_used, created_at
-             FROM session_messages WHERE session_id = ?1 ORDER BY created_at ASC LIMIT ?2",
+             FROM session_messages WHERE session_id = ?1 AND deleted_at IS NULL ORDER BY created_at ASC LIMIT ?2",
         )?;
 
         let messages = stmt
@@ -786,7 +869,7 @@ impl Database {
         // Delete oldest N messages by ID
         let deleted = conn.execute(
             "DELETE FROM session_messages WHERE id IN (
-                SELECT id FROM session_messages WHERE session_id = ?1 ORDER BY created_at ASC LIMIT ?2
+                SELECT id FROM session_messages WHERE session_id = ?1 AND deleted_at IS NULL ORDER BY created_at ASC LIMIT ?2
             )",
             rusqlite::params![session_id, count],
         )?;
@@ -848,4 +931,30 @@ impl Database {
             }
         })
     }
+
+    /// Get the language code stored for a session's replies, if one has been detected
+    pub fn get_session_language(&self, session_id: i64) -> SqliteResult<Option<String>> {
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT language FROM chat_sessions WHERE id = ?1",
+            [session_id],
+            |row| row.get(0),
+        ).or_else(|e| {
+            if matches!(e, rusqlite::Error::QueryReturnedNoRows) {
+                Ok(None)
+            } else {
+                Err(e)
+            }
+        })
+    }
+
+    /// Store the language code a session's replies should be written in
+    pub fn set_session_language(&self, session_id: i64, language: &str) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE chat_sessions SET language = ?1 WHERE id = ?2",
+            rusqlite::params![language, session_id],
+        )?;
+        Ok(())
+    }
 }
@@ -0,0 +1,160 @@
+//! Config audit database operations
+//!
+//! Every settings change made through the API (agent settings, bot settings,
+//! tool configs, API keys): who made it, which field, and its old and new
+//! values. Secret values are stored as `***REDACTED***`, so the trail shows
+//! that a secret changed without keeping it.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::super::Database;
+use crate::tools::redaction::REDACTED;
+
+/// Fields whose values are never stored. Custom RPC endpoints are included
+/// because provider URLs usually embed an API key.
+const SECRET_FIELDS: &[&str] = &["secret_key", "custom_rpc_endpoints"];
+
+/// Bookkeeping fields left out of diffs
+const IGNORED_FIELDS: &[&str] = &["id", "created_at", "updated_at"];
+
+/// A single settings change
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigAuditEntry {
+    pub id: i64,
+    /// Wallet address of the session that made the change, if it signed in with one
+    pub actor: Option<String>,
+    /// Settings group, e.g. "bot_settings" or "tool_config:channel:3"
+    pub section: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// Whether the values are redacted
+    pub secret: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A settings value as stored in the audit: strings as-is, anything else as JSON
+fn audit_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+impl Database {
+    /// Record a change to one settings field. With `secret`, present values
+    /// are replaced with `***REDACTED***`.
+    pub fn record_config_change(
+        &self,
+        actor: Option<&str>,
+        section: &str,
+        field: &str,
+        old_value: Option<&str>,
+        new_value: Option<&str>,
+        secret: bool,
+    ) -> SqliteResult<i64> {
+        let (old_value, new_value) = if secret {
+            (old_value.map(|_| REDACTED), new_value.map(|_| REDACTED))
+        } else {
+            (old_value, new_value)
+        };
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO config_audit (actor, section, field, old_value, new_value, secret, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                actor,
+                section,
+                field,
+                old_value,
+                new_value,
+                if secret { 1 } else { 0 },
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Record every top-level field that differs between two serialized
+    /// settings objects. `Value::Null` stands for settings that didn't exist.
+    /// Returns how many changes were recorded.
+    pub fn record_config_diff(
+        &self,
+        actor: Option<&str>,
+        section: &str,
+        before: &Value,
+        after: &Value,
+    ) -> SqliteResult<usize> {
+        let empty = serde_json::Map::new();
+        let before = before.as_object().unwrap_or(&empty);
+        let after = after.as_object().unwrap_or(&empty);
+
+        let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+        fields.sort();
+        fields.dedup();
+
+        let mut recorded = 0;
+        for field in fields {
+            if IGNORED_FIELDS.contains(&field.as_str()) {
+                continue;
+            }
+            let old_value = before.get(field).unwrap_or(&Value::Null);
+            let new_value = after.get(field).unwrap_or(&Value::Null);
+            if old_value == new_value {
+                continue;
+            }
+            self.record_config_change(
+                actor,
+                section,
+                field,
+                audit_value(old_value).as_deref(),
+                audit_value(new_value).as_deref(),
+                SECRET_FIELDS.contains(&field.as_str()),
+            )?;
+            recorded += 1;
+        }
+        Ok(recorded)
+    }
+
+    /// List settings changes, newest first. A `section` filter also matches
+    /// its sub-sections ("tool_config" includes "tool_config:channel:3").
+    pub fn list_config_audit(
+        &self,
+        section: Option<&str>,
+        limit: usize,
+        offset: usize,
+    ) -> SqliteResult<Vec<ConfigAuditEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, actor, section, field, old_value, new_value, secret, created_at
+             FROM config_audit
+             WHERE ?1 IS NULL OR section = ?1 OR section LIKE ?1 || ':%'
+             ORDER BY id DESC LIMIT ?2 OFFSET ?3",
+        )?;
+
+        let entries = stmt
+            .query_map(rusqlite::params![section, limit as i64, offset as i64], |row| {
+                let secret: i64 = row.get(6)?;
+                let created_at_str: String = row.get(7)?;
+                Ok(ConfigAuditEntry {
+                    id: row.get(0)?,
+                    actor: row.get(1)?,
+                    section: row.get(2)?,
+                    field: row.get(3)?,
+                    old_value: row.get(4)?,
+                    new_value: row.get(5)?,
+                    secret: secret != 0,
+                    created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+}
@@ -26,6 +26,7 @@ impl Database {
         thinking_level: Option<&str>,
         timeout_seconds: Option<i32>,
         delete_after_run: bool,
+        seed_findings: bool,
     ) -> SqliteResult<CronJob> {
         let conn = self.conn();
         let job_id = Uuid::new_v4().to_string();
@@ -36,13 +37,13 @@ impl Database {
                 job_id, name, description, schedule_type, schedule_value, timezone,
                 session_mode, message, system_event, channel_id, deliver_to, deliver,
                 model_override, thinking_level, timeout_seconds, delete_after_run,
-                status, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, 'active', ?17, ?17)",
+                seed_findings, status, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?18, 'active', ?17, ?17)",
             rusqlite::params![
                 job_id, name, description, schedule_type, schedule_value, timezone,
                 session_mode, message, system_event, channel_id, deliver_to, deliver as i32,
                 model_override, thinking_level, timeout_seconds, delete_after_run as i32,
-                now
+                now, seed_findings as i32
             ],
         )?;
 
@@ -56,7 +57,7 @@ impl Database {
                     session_mode, message, system_event, channel_id, deliver_to, deliver,
                     model_override, thinking_level, timeout_seconds, delete_after_run,
                     status, last_run_at, next_run_at, run_count, error_count, last_error,
-                    created_at, updated_at
+                    created_at, updated_at, seed_findings
              FROM cron_jobs WHERE id = ?1",
             [id],
             |row| self.map_cron_job_row(row),
@@ -90,6 +91,7 @@ impl Database {
             last_error: row.get(22)?,
             created_at: row.get(23)?,
             updated_at: row.get(24)?,
+            seed_findings: row.get::<_, i32>(25)? != 0,
         })
     }
 
@@ -111,7 +113,7 @@ impl Database {
                     session_mode, message, system_event, channel_id, deliver_to, deliver,
                     model_override, thinking_level, timeout_seconds, delete_after_run,
                     status, last_run_at, next_run_at, run_count, error_count, last_error,
-                    created_at, updated_at
+                    created_at, updated_at, seed_findings
              FROM cron_jobs WHERE job_id = ?1",
             [job_id],
             |row| self.map_cron_job_row(row),
@@ -130,7 +132,7 @@ impl Database {
                     session_mode, message, system_event, channel_id, deliver_to, deliver,
                     model_override, thinking_level, timeout_seconds, delete_after_run,
                     status, last_run_at, next_run_at, run_count, error_count, last_error,
-                    created_at, updated_at
+                    created_at, updated_at, seed_findings
              FROM cron_jobs ORDER BY created_at DESC"
         )?;
 
@@ -152,7 +154,7 @@ impl Database {
                     session_mode, message, system_event, channel_id, deliver_to, deliver,
                     model_override, thinking_level, timeout_seconds, delete_after_run,
                     status, last_run_at, next_run_at, run_count, error_count, last_error,
-                    created_at, updated_at
+                    created_at, updated_at, seed_findings
              FROM cron_jobs
              WHERE status = 'active' AND (next_run_at IS NULL OR next_run_at <= ?1)
              ORDER BY next_run_at ASC"
@@ -186,6 +188,7 @@ impl Database {
         thinking_level: Option<&str>,
         timeout_seconds: Option<i32>,
         delete_after_run: Option<bool>,
+        seed_findings: Option<bool>,
         status: Option<&str>,
     ) -> SqliteResult<CronJob> {
         let conn = self.conn();
@@ -210,6 +213,7 @@ impl Database {
         if thinking_level.is_some() { updates.push(format!("thinking_level = ?{}", param_index)); param_index += 1; }
         if timeout_seconds.is_some() { updates.push(format!("timeout_seconds = ?{}", param_index)); param_index += 1; }
         if delete_after_run.is_some() { updates.push(format!("delete_after_run = ?{}", param_index)); param_index += 1; }
+        if seed_findings.is_some() { updates.push(format!("seed_findings = ?{}", param_index)); param_index += 1; }
         if status.is_some() { updates.push(format!("status = ?{}", param_index)); param_index += 1; }
 
         let query = format!(
@@ -235,6 +239,7 @@ impl Database {
         if let Some(v) = thinking_level { params.push(Box::new(v.to_string())); }
         if let Some(v) = timeout_seconds { params.push(Box::new(v)); }
         if let Some(v) = delete_after_run { params.push(Box::new(v as i32)); }
+        if let Some(v) = seed_findings { params.push(Box::new(v as i32)); }
         if let Some(v) = status { params.push(Box::new(v.to_string())); }
         params.push(Box::new(id));
 
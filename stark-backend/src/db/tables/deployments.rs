@@ -0,0 +1,176 @@
+//! Deployment history database operations
+//!
+//! Every deployment the deploy tool triggers or rolls back, with the commit
+//! its workflow run built and how the run ended, so a rollback knows the last
+//! good version of each target.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+
+/// Where a deployment's workflow run stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentStatus {
+    /// Triggered, run not found yet
+    Pending,
+    /// Run queued or in progress
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl DeploymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeploymentStatus::Pending => "pending",
+            DeploymentStatus::Running => "running",
+            DeploymentStatus::Succeeded => "succeeded",
+            DeploymentStatus::Failed => "failed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "pending" => Some(DeploymentStatus::Pending),
+            "running" => Some(DeploymentStatus::Running),
+            "succeeded" => Some(DeploymentStatus::Succeeded),
+            "failed" => Some(DeploymentStatus::Failed),
+            _ => None,
+        }
+    }
+
+    /// Whether the run has finished
+    pub fn is_final(&self) -> bool {
+        matches!(self, DeploymentStatus::Succeeded | DeploymentStatus::Failed)
+    }
+}
+
+/// One deployment of a target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deployment {
+    pub id: i64,
+    pub target: String,
+    pub workflow: String,
+    /// Branch or tag the workflow was dispatched on
+    pub git_ref: String,
+    /// Commit the run built, once the run is known
+    pub commit_sha: Option<String>,
+    /// GitHub Actions run ID
+    pub run_id: Option<i64>,
+    pub status: DeploymentStatus,
+    /// The earlier deployment this one re-ran, for rollbacks
+    pub rollback_of: Option<i64>,
+    pub channel_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Record a deployment as it is triggered
+    pub fn record_deployment(
+        &self,
+        target: &str,
+        workflow: &str,
+        git_ref: &str,
+        rollback_of: Option<i64>,
+        channel_id: Option<i64>,
+    ) -> SqliteResult<i64> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO deployments (target, workflow, git_ref, status, rollback_of, channel_id, created_at, updated_at)
+             VALUES (?1, ?2, ?3, 'pending', ?4, ?5, ?6, ?6)",
+            rusqlite::params![target, workflow, git_ref, rollback_of, channel_id, now],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Record a deployment's workflow run and its latest status. A `None`
+    /// run ID or commit keeps the one already recorded.
+    pub fn update_deployment_run(
+        &self,
+        id: i64,
+        run_id: Option<i64>,
+        commit_sha: Option<&str>,
+        status: DeploymentStatus,
+    ) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE deployments SET
+                run_id = COALESCE(?1, run_id),
+                commit_sha = COALESCE(?2, commit_sha),
+                status = ?3,
+                updated_at = ?4
+             WHERE id = ?5",
+            rusqlite::params![run_id, commit_sha, status.as_str(), Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent deployments of a target, newest first
+    pub fn list_deployments(&self, target: &str, limit: usize) -> SqliteResult<Vec<Deployment>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, target, workflow, git_ref, commit_sha, run_id, status, rollback_of, channel_id, created_at, updated_at
+             FROM deployments WHERE target = ?1
+             ORDER BY id DESC LIMIT ?2",
+        )?;
+
+        let deployments = stmt
+            .query_map(rusqlite::params![target, limit as i64], Self::row_to_deployment)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(deployments)
+    }
+
+    /// The latest successful deployment of a target that shipped a different
+    /// commit from `current_sha`: what a rollback returns to
+    pub fn last_good_deployment(
+        &self,
+        target: &str,
+        current_sha: Option<&str>,
+    ) -> SqliteResult<Option<Deployment>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, target, workflow, git_ref, commit_sha, run_id, status, rollback_of, channel_id, created_at, updated_at
+             FROM deployments
+             WHERE target = ?1 AND status = 'succeeded' AND run_id IS NOT NULL
+               AND commit_sha IS NOT NULL AND (?2 IS NULL OR commit_sha != ?2)
+             ORDER BY id DESC LIMIT 1",
+        )?;
+
+        let deployment = stmt
+            .query_row(rusqlite::params![target, current_sha], Self::row_to_deployment)
+            .ok();
+
+        Ok(deployment)
+    }
+
+    fn row_to_deployment(row: &rusqlite::Row) -> rusqlite::Result<Deployment> {
+        let status: String = row.get(6)?;
+        let created_at: String = row.get(9)?;
+        let updated_at: String = row.get(10)?;
+
+        Ok(Deployment {
+            id: row.get(0)?,
+            target: row.get(1)?,
+            workflow: row.get(2)?,
+            git_ref: row.get(3)?,
+            commit_sha: row.get(4)?,
+            run_id: row.get(5)?,
+            status: DeploymentStatus::from_str(&status).unwrap_or(DeploymentStatus::Pending),
+            rollback_of: row.get(7)?,
+            channel_id: row.get(8)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
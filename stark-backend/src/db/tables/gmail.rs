@@ -13,7 +13,8 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, email, access_token, refresh_token, token_expires_at,
                     watch_labels, project_id, topic_name, watch_expires_at, history_id,
-                    enabled, response_channel_id, auto_reply, created_at, updated_at
+                    enabled, response_channel_id, auto_reply, created_at, updated_at,
+                    reply_allowlist, reply_denylist, max_replies_per_thread
              FROM gmail_configs LIMIT 1"
         )?;
 
@@ -30,7 +31,8 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, email, access_token, refresh_token, token_expires_at,
                     watch_labels, project_id, topic_name, watch_expires_at, history_id,
-                    enabled, response_channel_id, auto_reply, created_at, updated_at
+                    enabled, response_channel_id, auto_reply, created_at, updated_at,
+                    reply_allowlist, reply_denylist, max_replies_per_thread
              FROM gmail_configs WHERE email = ?1"
         )?;
 
@@ -105,6 +107,64 @@ impl Database {
         self.get_gmail_config().map(|opt| opt.unwrap())
     }
 
+    /// Update Gmail auto-reply filters
+    pub fn update_gmail_reply_filters(
+        &self,
+        reply_allowlist: Option<&str>,
+        reply_denylist: Option<&str>,
+        max_replies_per_thread: Option<u32>,
+    ) -> SqliteResult<GmailConfig> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+
+        let sql = format!(
+            "UPDATE gmail_configs SET updated_at = ?1{}{}{}",
+            reply_allowlist.map(|_| ", reply_allowlist = ?2").unwrap_or(""),
+            reply_denylist.map(|_| ", reply_denylist = ?3").unwrap_or(""),
+            max_replies_per_thread.map(|_| ", max_replies_per_thread = ?4").unwrap_or(""),
+        );
+
+        conn.execute(
+            &sql,
+            rusqlite::params![
+                &now,
+                reply_allowlist.unwrap_or(""),
+                reply_denylist.unwrap_or(""),
+                max_replies_per_thread.unwrap_or(0),
+            ],
+        )?;
+
+        drop(conn);
+        self.get_gmail_config().map(|opt| opt.unwrap())
+    }
+
+    /// Number of auto-replies already sent in a thread
+    pub fn get_gmail_thread_reply_count(&self, thread_id: &str) -> SqliteResult<u32> {
+        let conn = self.conn();
+        let count: Option<i64> = conn
+            .query_row(
+                "SELECT reply_count FROM gmail_thread_replies WHERE thread_id = ?1",
+                [thread_id],
+                |row| row.get(0),
+            )
+            .ok();
+        Ok(count.unwrap_or(0).max(0) as u32)
+    }
+
+    /// Record an auto-reply sent in a thread
+    pub fn increment_gmail_thread_reply_count(&self, thread_id: &str) -> SqliteResult<()> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT INTO gmail_thread_replies (thread_id, reply_count, last_reply_at) VALUES (?1, 1, ?2)
+             ON CONFLICT(thread_id) DO UPDATE SET reply_count = reply_count + 1, last_reply_at = ?2",
+            rusqlite::params![thread_id, &now],
+        )?;
+
+        Ok(())
+    }
+
     /// Update Gmail watch info
     pub fn update_gmail_watch(
         &self,
@@ -193,6 +253,9 @@ impl Database {
             updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
                 .unwrap()
                 .with_timezone(&Utc),
+            reply_allowlist: row.get(15)?,
+            reply_denylist: row.get(16)?,
+            max_replies_per_thread: row.get::<_, i64>(17)?.max(0) as u32,
         })
     }
 }
@@ -199,7 +199,8 @@ impl Database {
             "SELECT DISTINCT cs.id, cs.session_key, cs.agent_id, cs.scope, cs.channel_type, cs.channel_id,
                     cs.platform_chat_id, cs.is_active, cs.reset_policy, cs.idle_timeout_minutes,
                     cs.daily_reset_hour, cs.created_at, cs.updated_at, cs.last_activity_at, cs.expires_at,
-                    cs.context_tokens, cs.max_context_tokens, cs.compaction_id, cs.completion_status
+                    cs.context_tokens, cs.max_context_tokens, cs.compaction_id, cs.completion_status,
+                    cs.context_window_override
              FROM chat_sessions cs
              INNER JOIN session_messages sm ON sm.session_id = cs.id
              WHERE sm.user_id IN ({})
@@ -254,6 +255,7 @@ impl Database {
                         let status_str: String = row.get(18).unwrap_or_else(|_| "active".to_string());
                         CompletionStatus::from_str(&status_str).unwrap_or_default()
                     },
+                    context_window_override: row.get(19).unwrap_or(None),
                 })
             })?
             .filter_map(|r| r.ok())
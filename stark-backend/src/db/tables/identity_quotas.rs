@@ -0,0 +1,124 @@
+//! Identity quota and daily usage database operations
+
+use chrono::Utc;
+use rusqlite::Result as SqliteResult;
+
+use crate::models::{IdentityQuota, IdentityUsage};
+use super::super::Database;
+
+/// Today's date in UTC, the key for the current usage window
+fn today() -> String {
+    Utc::now().format("%Y-%m-%d").to_string()
+}
+
+impl Database {
+    /// Get the quota override for an identity, if one is set
+    pub fn get_identity_quota(&self, identity_id: &str) -> SqliteResult<Option<IdentityQuota>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT messages_per_day, tool_calls_per_day, x402_usdc_per_day
+             FROM identity_quotas WHERE identity_id = ?1",
+        )?;
+
+        let quota = stmt
+            .query_row([identity_id], |row| {
+                Ok(IdentityQuota {
+                    messages_per_day: row.get::<_, Option<i64>>(0)?.map(|v| v.max(0) as u64),
+                    tool_calls_per_day: row.get::<_, Option<i64>>(1)?.map(|v| v.max(0) as u64),
+                    x402_usdc_per_day: row.get(2)?,
+                })
+            })
+            .ok();
+
+        Ok(quota)
+    }
+
+    /// Set (or replace) the quota override for an identity
+    pub fn set_identity_quota(&self, identity_id: &str, quota: &IdentityQuota) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO identity_quotas (identity_id, messages_per_day, tool_calls_per_day, x402_usdc_per_day, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(identity_id) DO UPDATE SET
+                messages_per_day = excluded.messages_per_day,
+                tool_calls_per_day = excluded.tool_calls_per_day,
+                x402_usdc_per_day = excluded.x402_usdc_per_day,
+                updated_at = excluded.updated_at",
+            rusqlite::params![
+                identity_id,
+                quota.messages_per_day.map(|v| v as i64),
+                quota.tool_calls_per_day.map(|v| v as i64),
+                quota.x402_usdc_per_day,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Remove an identity's quota override, returning it to the global defaults
+    pub fn delete_identity_quota(&self, identity_id: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute("DELETE FROM identity_quotas WHERE identity_id = ?1", [identity_id])?;
+        Ok(rows > 0)
+    }
+
+    /// The quota that applies to an identity: its override, with unset
+    /// limits taken from the global defaults
+    pub fn get_effective_identity_quota(&self, identity_id: &str) -> SqliteResult<IdentityQuota> {
+        let global = IdentityQuota::global();
+        Ok(self
+            .get_identity_quota(identity_id)?
+            .map(|quota| quota.or(&global))
+            .unwrap_or(global))
+    }
+
+    /// Get an identity's usage for the current UTC day
+    pub fn get_identity_usage_today(&self, identity_id: &str) -> SqliteResult<IdentityUsage> {
+        let day = today();
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT messages, tool_calls, x402_micro_usdc
+             FROM identity_usage WHERE identity_id = ?1 AND day = ?2",
+        )?;
+
+        let usage = stmt
+            .query_row(rusqlite::params![identity_id, &day], |row| {
+                Ok(IdentityUsage {
+                    day: day.clone(),
+                    messages: row.get::<_, i64>(0)?.max(0) as u64,
+                    tool_calls: row.get::<_, i64>(1)?.max(0) as u64,
+                    x402_micro_usdc: row.get::<_, i64>(2)?.max(0) as u64,
+                })
+            })
+            .ok();
+
+        Ok(usage.unwrap_or(IdentityUsage { day, ..Default::default() }))
+    }
+
+    /// Add to an identity's usage for the current UTC day
+    pub fn record_identity_usage(
+        &self,
+        identity_id: &str,
+        messages: u64,
+        tool_calls: u64,
+        x402_micro_usdc: u64,
+    ) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO identity_usage (identity_id, day, messages, tool_calls, x402_micro_usdc)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(identity_id, day) DO UPDATE SET
+                messages = messages + excluded.messages,
+                tool_calls = tool_calls + excluded.tool_calls,
+                x402_micro_usdc = x402_micro_usdc + excluded.x402_micro_usdc",
+            rusqlite::params![
+                identity_id,
+                today(),
+                messages as i64,
+                tool_calls as i64,
+                x402_micro_usdc as i64,
+            ],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+//! Known agents database operations
+//!
+//! Local index of agents discovered from EIP-8004 registries.
+
+use rusqlite::{OptionalExtension, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+
+/// A known agent record (discovered from an EIP-8004 registry)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownAgent {
+    pub id: Option<i64>,
+    pub agent_id: u64,
+    pub agent_registry: String,
+    pub chain_id: u64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub registration_uri: Option<String>,
+    pub owner_address: Option<String>,
+    pub wallet_address: Option<String>,
+    pub x402_support: bool,
+    /// Service entries from the registration card (JSON array)
+    pub services: Vec<serde_json::Value>,
+    pub supported_trust: Vec<String>,
+    pub is_active: bool,
+    pub reputation_score: Option<i64>,
+    pub reputation_count: u64,
+    pub discovered_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl KnownAgent {
+    /// Find the endpoint for a named service (e.g. "x402", "a2a")
+    pub fn service_endpoint(&self, name: &str) -> Option<String> {
+        self.services
+            .iter()
+            .find(|s| s.get("name").and_then(|n| n.as_str()) == Some(name))
+            .and_then(|s| s.get("endpoint").and_then(|e| e.as_str()))
+            .map(|s| s.to_string())
+    }
+}
+
+const KNOWN_AGENT_COLUMNS: &str =
+    "id, agent_id, agent_registry, chain_id, name, description, image_url, registration_uri,
+     owner_address, wallet_address, x402_support, services, supported_trust, is_active,
+     reputation_score, reputation_count, discovered_at, updated_at";
+
+fn row_to_known_agent(row: &rusqlite::Row) -> rusqlite::Result<KnownAgent> {
+    let services_json: Option<String> = row.get(11)?;
+    let trust_json: Option<String> = row.get(12)?;
+
+    Ok(KnownAgent {
+        id: Some(row.get(0)?),
+        agent_id: row.get::<_, i64>(1)? as u64,
+        agent_registry: row.get(2)?,
+        chain_id: row.get::<_, i64>(3)? as u64,
+        name: row.get(4)?,
+        description: row.get(5)?,
+        image_url: row.get(6)?,
+        registration_uri: row.get(7)?,
+        owner_address: row.get(8)?,
+        wallet_address: row.get(9)?,
+        x402_support: row.get::<_, i64>(10)? != 0,
+        services: services_json
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+        supported_trust: trust_json
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+        is_active: row.get::<_, i64>(13)? != 0,
+        reputation_score: row.get(14)?,
+        reputation_count: row.get::<_, i64>(15)? as u64,
+        discovered_at: row.get(16)?,
+        updated_at: row.get(17)?,
+    })
+}
+
+impl Database {
+    /// Insert or update a known agent (keyed by agent_id + agent_registry)
+    pub fn upsert_known_agent(&self, agent: &KnownAgent) -> SqliteResult<()> {
+        let conn = self.conn();
+        let services = serde_json::to_string(&agent.services).unwrap_or_else(|_| "[]".to_string());
+        let supported_trust =
+            serde_json::to_string(&agent.supported_trust).unwrap_or_else(|_| "[]".to_string());
+
+        conn.execute(
+            "INSERT INTO known_agents
+             (agent_id, agent_registry, chain_id, name, description, image_url, registration_uri,
+              owner_address, wallet_address, x402_support, services, supported_trust, is_active,
+              reputation_score, reputation_count, discovered_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, datetime('now'), datetime('now'))
+             ON CONFLICT(agent_id, agent_registry) DO UPDATE SET
+                chain_id = excluded.chain_id,
+                name = excluded.name,
+                description = excluded.description,
+                image_url = excluded.image_url,
+                registration_uri = excluded.registration_uri,
+                owner_address = excluded.owner_address,
+                wallet_address = excluded.wallet_address,
+                x402_support = excluded.x402_support,
+                services = excluded.services,
+                supported_trust = excluded.supported_trust,
+                is_active = excluded.is_active,
+                reputation_score = excluded.reputation_score,
+                reputation_count = excluded.reputation_count,
+                updated_at = datetime('now')",
+            rusqlite::params![
+                agent.agent_id as i64,
+                agent.agent_registry,
+                agent.chain_id as i64,
+                agent.name,
+                agent.description,
+                agent.image_url,
+                agent.registration_uri,
+                agent.owner_address,
+                agent.wallet_address,
+                agent.x402_support as i64,
+                services,
+                supported_trust,
+                agent.is_active as i64,
+                agent.reputation_score,
+                agent.reputation_count as i64,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Mark a known agent inactive, inserting a stub row if we've never seen it.
+    /// Previously discovered metadata (name, services) is left untouched.
+    pub fn mark_known_agent_inactive(
+        &self,
+        agent_id: u64,
+        agent_registry: &str,
+        chain_id: u64,
+        owner_address: Option<&str>,
+        registration_uri: Option<&str>,
+    ) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO known_agents
+             (agent_id, agent_registry, chain_id, owner_address, registration_uri, is_active, discovered_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, 0, datetime('now'), datetime('now'))
+             ON CONFLICT(agent_id, agent_registry) DO UPDATE SET
+                is_active = 0,
+                owner_address = COALESCE(excluded.owner_address, owner_address),
+                registration_uri = COALESCE(excluded.registration_uri, registration_uri),
+                updated_at = datetime('now')",
+            rusqlite::params![
+                agent_id as i64,
+                agent_registry,
+                chain_id as i64,
+                owner_address,
+                registration_uri,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// List known agents, optionally restricted to active x402-capable ones
+    pub fn list_known_agents(&self, x402_only: bool, limit: Option<usize>) -> SqliteResult<Vec<KnownAgent>> {
+        let conn = self.conn();
+        let mut sql = format!("SELECT {} FROM known_agents", KNOWN_AGENT_COLUMNS);
+        if x402_only {
+            sql.push_str(" WHERE x402_support = 1 AND is_active = 1");
+        }
+        sql.push_str(" ORDER BY reputation_score DESC, agent_id ASC");
+        if let Some(l) = limit {
+            sql.push_str(&format!(" LIMIT {}", l));
+        }
+
+        let mut stmt = conn.prepare(&sql)?;
+        let agents = stmt
+            .query_map([], row_to_known_agent)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(agents)
+    }
+
+    /// Find an active known agent by name (case-insensitive)
+    pub fn get_known_agent_by_name(&self, name: &str) -> SqliteResult<Option<KnownAgent>> {
+        let conn = self.conn();
+        let sql = format!(
+            "SELECT {} FROM known_agents
+             WHERE LOWER(name) = LOWER(?1) AND is_active = 1
+             ORDER BY x402_support DESC, reputation_score DESC LIMIT 1",
+            KNOWN_AGENT_COLUMNS
+        );
+        conn.query_row(&sql, [name], row_to_known_agent).optional()
+    }
+}
@@ -0,0 +1,266 @@
+//! Limit order database operations
+//!
+//! Pending DEX swaps that the scheduler executes once a token's USD price
+//! crosses a target. An order leaves `pending` exactly once, so a filled or
+//! failed order never re-triggers.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+
+/// Limit order lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LimitOrderStatus {
+    /// Watching the price
+    Pending,
+    /// Price crossed; the swap is being prepared
+    Triggered,
+    /// The swap transaction was queued
+    Filled,
+    /// Triggered but no swap was queued
+    Failed,
+    Cancelled,
+}
+
+impl LimitOrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LimitOrderStatus::Pending => "pending",
+            LimitOrderStatus::Triggered => "triggered",
+            LimitOrderStatus::Filled => "filled",
+            LimitOrderStatus::Failed => "failed",
+            LimitOrderStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "pending" => Some(LimitOrderStatus::Pending),
+            "triggered" => Some(LimitOrderStatus::Triggered),
+            "filled" => Some(LimitOrderStatus::Filled),
+            "failed" => Some(LimitOrderStatus::Failed),
+            "cancelled" => Some(LimitOrderStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// Which side of the target price triggers the order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerDirection {
+    /// Price at or below the target (buy the dip)
+    Below,
+    /// Price at or above the target (take profit)
+    Above,
+}
+
+impl TriggerDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TriggerDirection::Below => "below",
+            TriggerDirection::Above => "above",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "below" => Some(TriggerDirection::Below),
+            "above" => Some(TriggerDirection::Above),
+            _ => None,
+        }
+    }
+
+    /// Whether `price` has crossed `target`
+    pub fn is_crossed(&self, price: f64, target: f64) -> bool {
+        match self {
+            TriggerDirection::Below => price <= target,
+            TriggerDirection::Above => price >= target,
+        }
+    }
+}
+
+/// A price-triggered swap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LimitOrder {
+    pub id: i64,
+    pub network: String,
+    /// Wallet that signs the swap
+    pub wallet: String,
+    pub sell_token: String,
+    pub buy_token: String,
+    /// Human-readable amount of `sell_token`
+    pub sell_amount: String,
+    /// Token whose USD price is watched
+    pub trigger_token: String,
+    pub trigger_address: String,
+    pub trigger_direction: TriggerDirection,
+    pub target_price_usd: f64,
+    /// Broadcast the swap once queued instead of leaving it for review
+    pub auto_broadcast: bool,
+    pub status: LimitOrderStatus,
+    /// Channel the order was placed from
+    pub channel_id: Option<i64>,
+    pub triggered_price_usd: Option<f64>,
+    /// Queued transaction UUIDs on fill, or why the order failed
+    pub result: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A limit order to create
+#[derive(Debug, Clone)]
+pub struct NewLimitOrder {
+    pub network: String,
+    pub wallet: String,
+    pub sell_token: String,
+    pub buy_token: String,
+    pub sell_amount: String,
+    pub trigger_token: String,
+    pub trigger_address: String,
+    pub trigger_direction: TriggerDirection,
+    pub target_price_usd: f64,
+    pub auto_broadcast: bool,
+    pub channel_id: Option<i64>,
+}
+
+const LIMIT_ORDER_COLUMNS: &str = "id, network, wallet, sell_token, buy_token, sell_amount, trigger_token, \
+     trigger_address, trigger_direction, target_price_usd, auto_broadcast, status, channel_id, \
+     triggered_price_usd, result, created_at, updated_at";
+
+impl Database {
+    /// Create a pending limit order
+    pub fn create_limit_order(&self, order: &NewLimitOrder) -> SqliteResult<LimitOrder> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT INTO limit_orders (network, wallet, sell_token, buy_token, sell_amount, trigger_token,
+                trigger_address, trigger_direction, target_price_usd, auto_broadcast, status, channel_id,
+                created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'pending', ?11, ?12, ?12)",
+            rusqlite::params![
+                order.network,
+                order.wallet,
+                order.sell_token,
+                order.buy_token,
+                order.sell_amount,
+                order.trigger_token,
+                order.trigger_address.to_lowercase(),
+                order.trigger_direction.as_str(),
+                order.target_price_usd,
+                order.auto_broadcast as i32,
+                order.channel_id,
+                &now,
+            ],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        drop(conn);
+        self.get_limit_order(id)
+            .and_then(|o| o.ok_or(rusqlite::Error::QueryReturnedNoRows))
+    }
+
+    /// Get a limit order by ID
+    pub fn get_limit_order(&self, id: i64) -> SqliteResult<Option<LimitOrder>> {
+        let conn = self.conn();
+        match conn.query_row(
+            &format!("SELECT {} FROM limit_orders WHERE id = ?1", LIMIT_ORDER_COLUMNS),
+            [id],
+            Self::row_to_limit_order,
+        ) {
+            Ok(order) => Ok(Some(order)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// List limit orders, newest first, optionally filtered by status
+    pub fn list_limit_orders(&self, status: Option<LimitOrderStatus>) -> SqliteResult<Vec<LimitOrder>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM limit_orders WHERE ?1 IS NULL OR status = ?1 ORDER BY id DESC",
+            LIMIT_ORDER_COLUMNS
+        ))?;
+
+        let orders = stmt
+            .query_map([status.map(|s| s.as_str())], Self::row_to_limit_order)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(orders)
+    }
+
+    /// Cancel a pending order. Returns false if it doesn't exist or has
+    /// already left `pending`.
+    pub fn cancel_limit_order(&self, id: i64) -> SqliteResult<bool> {
+        self.transition_limit_order(id, LimitOrderStatus::Pending, LimitOrderStatus::Cancelled, None, None)
+    }
+
+    /// Claim a pending order for execution at `price`. Returns false if
+    /// another run (or a cancel) got there first, so an order fires once.
+    pub fn mark_limit_order_triggered(&self, id: i64, price: f64) -> SqliteResult<bool> {
+        self.transition_limit_order(id, LimitOrderStatus::Pending, LimitOrderStatus::Triggered, Some(price), None)
+    }
+
+    /// Record the outcome of a triggered order (`Filled` or `Failed`)
+    pub fn complete_limit_order(&self, id: i64, status: LimitOrderStatus, result: &str) -> SqliteResult<bool> {
+        self.transition_limit_order(id, LimitOrderStatus::Triggered, status, None, Some(result))
+    }
+
+    fn transition_limit_order(
+        &self,
+        id: i64,
+        from: LimitOrderStatus,
+        to: LimitOrderStatus,
+        triggered_price: Option<f64>,
+        result: Option<&str>,
+    ) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "UPDATE limit_orders SET
+                status = ?1,
+                triggered_price_usd = COALESCE(?2, triggered_price_usd),
+                result = COALESCE(?3, result),
+                updated_at = ?4
+             WHERE id = ?5 AND status = ?6",
+            rusqlite::params![to.as_str(), triggered_price, result, Utc::now().to_rfc3339(), id, from.as_str()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    fn row_to_limit_order(row: &rusqlite::Row) -> rusqlite::Result<LimitOrder> {
+        let direction_str: String = row.get(8)?;
+        let auto_broadcast: i32 = row.get(10)?;
+        let status_str: String = row.get(11)?;
+        let created_at_str: String = row.get(15)?;
+        let updated_at_str: String = row.get(16)?;
+
+        Ok(LimitOrder {
+            id: row.get(0)?,
+            network: row.get(1)?,
+            wallet: row.get(2)?,
+            sell_token: row.get(3)?,
+            buy_token: row.get(4)?,
+            sell_amount: row.get(5)?,
+            trigger_token: row.get(6)?,
+            trigger_address: row.get(7)?,
+            trigger_direction: TriggerDirection::from_str(&direction_str).unwrap_or(TriggerDirection::Below),
+            target_price_usd: row.get(9)?,
+            auto_broadcast: auto_broadcast != 0,
+            status: LimitOrderStatus::from_str(&status_str).unwrap_or(LimitOrderStatus::Failed),
+            channel_id: row.get(12)?,
+            triggered_price_usd: row.get(13)?,
+            result: row.get(14)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
@@ -10,6 +10,7 @@ mod agent_settings; // agent_settings
 mod bot_settings;   // bot_settings
 mod chat_sessions;  // chat_sessions, session_messages (+ compaction)
 mod identities;     // identity_links
+mod identity_quotas; // identity_quotas, identity_usage (daily quotas)
 mod tool_configs;   // tool_configs, tool_executions
 mod skills;         // skills, skill_scripts
 mod cron_jobs;      // cron_jobs, cron_job_runs
@@ -17,4 +18,17 @@ mod heartbeat;      // heartbeat_configs
 mod gmail;          // gmail_configs
 mod agent_contexts; // agent_contexts (multi-agent orchestrator state)
 pub mod broadcasted_transactions; // broadcasted_transactions (crypto tx history)
+mod queued_transactions; // queued_transactions (tx queue awaiting broadcast)
+pub mod rogue_mode_audit; // rogue_mode_audit (actions taken while rogue mode is active)
+pub mod config_audit; // config_audit (who changed which setting, old and new values)
+pub mod personas; // personas (named system prompt templates)
+pub mod address_book; // address_book (named transfer recipients)
+pub mod portfolio_snapshots; // portfolio_snapshots (wallet valuation history)
+pub mod limit_orders; // limit_orders (price-triggered DEX swaps)
+pub mod deployments; // deployments (deploy tool history for status and rollback)
+pub mod polymarket_orders; // polymarket_orders (tracked for fill notifications)
+pub mod web3_presets; // web3_presets (runtime web3_function_call presets)
+pub mod abis; // abis (uploaded web3_function_call ABIs)
 pub mod mind_nodes;  // mind_nodes, mind_node_connections (mind map feature)
+pub mod known_agents; // known_agents (EIP-8004 discovery index)
+pub mod validations;  // validations (EIP-8004 validation requests/responses)
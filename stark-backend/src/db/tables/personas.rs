@@ -0,0 +1,164 @@
+//! Persona database operations
+//!
+//! Named system prompt templates. A channel selects one via its `persona`
+//! channel setting; channels without one fall back to SOUL.md.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+use crate::models::ChannelSettingKey;
+
+/// A named persona (system prompt template)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Persona {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    /// Markdown used in place of SOUL.md at the top of the system prompt
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to create a persona
+#[derive(Debug, Deserialize)]
+pub struct CreatePersonaRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub content: String,
+}
+
+/// Request to update a persona
+#[derive(Debug, Deserialize)]
+pub struct UpdatePersonaRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub content: Option<String>,
+}
+
+impl Database {
+    /// Create a new persona
+    pub fn create_persona(&self, request: &CreatePersonaRequest) -> SqliteResult<Persona> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT INTO personas (name, description, content, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)",
+            rusqlite::params![request.name.trim(), request.description, request.content, &now],
+        )?;
+
+        let id = conn.last_insert_rowid();
+        drop(conn);
+        self.get_persona(id)
+            .and_then(|p| p.ok_or(rusqlite::Error::QueryReturnedNoRows))
+    }
+
+    /// Get a persona by ID
+    pub fn get_persona(&self, id: i64) -> SqliteResult<Option<Persona>> {
+        let conn = self.conn();
+        match conn.query_row(
+            "SELECT id, name, description, content, created_at, updated_at FROM personas WHERE id = ?1",
+            [id],
+            Self::row_to_persona,
+        ) {
+            Ok(persona) => Ok(Some(persona)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get a persona by name (case-insensitive)
+    pub fn get_persona_by_name(&self, name: &str) -> SqliteResult<Option<Persona>> {
+        let conn = self.conn();
+        match conn.query_row(
+            "SELECT id, name, description, content, created_at, updated_at FROM personas
+             WHERE name = ?1 COLLATE NOCASE",
+            [name.trim()],
+            Self::row_to_persona,
+        ) {
+            Ok(persona) => Ok(Some(persona)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// List all personas
+    pub fn list_personas(&self) -> SqliteResult<Vec<Persona>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, content, created_at, updated_at FROM personas ORDER BY name ASC",
+        )?;
+
+        let personas = stmt
+            .query_map([], Self::row_to_persona)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(personas)
+    }
+
+    /// Update a persona. Returns None if it doesn't exist.
+    pub fn update_persona(&self, id: i64, request: &UpdatePersonaRequest) -> SqliteResult<Option<Persona>> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+
+        let rows = conn.execute(
+            "UPDATE personas SET
+                name = COALESCE(?1, name),
+                description = COALESCE(?2, description),
+                content = COALESCE(?3, content),
+                updated_at = ?4
+             WHERE id = ?5",
+            rusqlite::params![
+                request.name.as_deref().map(str::trim),
+                request.description,
+                request.content,
+                &now,
+                id
+            ],
+        )?;
+
+        drop(conn);
+        if rows == 0 {
+            return Ok(None);
+        }
+        self.get_persona(id)
+    }
+
+    /// Delete a persona
+    pub fn delete_persona(&self, id: i64) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute("DELETE FROM personas WHERE id = ?1", [id])?;
+        Ok(rows > 0)
+    }
+
+    /// The persona assigned to a channel, if it has one and it still exists
+    pub fn get_channel_persona(&self, channel_id: i64) -> SqliteResult<Option<Persona>> {
+        let name = self.get_channel_setting(channel_id, ChannelSettingKey::Persona.as_ref())?;
+        match name.as_deref().map(str::trim) {
+            Some(name) if !name.is_empty() => self.get_persona_by_name(name),
+            _ => Ok(None),
+        }
+    }
+
+    fn row_to_persona(row: &rusqlite::Row) -> rusqlite::Result<Persona> {
+        let created_at_str: String = row.get(4)?;
+        let updated_at_str: String = row.get(5)?;
+
+        Ok(Persona {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            content: row.get(3)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
@@ -0,0 +1,138 @@
+//! Polymarket order tracking database operations
+//!
+//! Orders placed through `polymarket_trade` (and any other open orders found
+//! for the same wallets) are tracked with the last seen matched size, so the
+//! scheduler can notify on each new fill exactly once.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+
+/// A Polymarket CLOB order being watched for fills
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedPolymarketOrder {
+    pub order_id: String,
+    pub wallet: String,
+    pub token_id: String,
+    pub side: String,
+    pub price: String,
+    pub original_size: String,
+    /// Matched size at the last poll
+    pub size_matched: String,
+    /// CLOB order status as last seen (e.g. "Live", "Matched", "Canceled")
+    pub status: String,
+    pub outcome: Option<String>,
+    /// False once the order is no longer on the book
+    pub is_open: bool,
+    /// Channel that placed the order; fill messages go there
+    pub channel_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Start tracking an order. An order that is already tracked is left as is.
+    pub fn track_polymarket_order(&self, order: &TrackedPolymarketOrder) -> SqliteResult<()> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT OR IGNORE INTO polymarket_orders (order_id, wallet, token_id, side, price, original_size,
+                size_matched, status, outcome, is_open, channel_id, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?12)",
+            rusqlite::params![
+                order.order_id,
+                order.wallet,
+                order.token_id,
+                order.side,
+                order.price,
+                order.original_size,
+                order.size_matched,
+                order.status,
+                order.outcome,
+                order.is_open as i32,
+                order.channel_id,
+                &now,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get a tracked order
+    pub fn get_polymarket_order(&self, order_id: &str) -> SqliteResult<Option<TrackedPolymarketOrder>> {
+        let conn = self.conn();
+        match conn.query_row(
+            "SELECT order_id, wallet, token_id, side, price, original_size, size_matched, status, outcome,
+                    is_open, channel_id, created_at, updated_at
+             FROM polymarket_orders WHERE order_id = ?1",
+            [order_id],
+            Self::row_to_polymarket_order,
+        ) {
+            Ok(order) => Ok(Some(order)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Tracked orders still on the book
+    pub fn list_open_polymarket_orders(&self) -> SqliteResult<Vec<TrackedPolymarketOrder>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT order_id, wallet, token_id, side, price, original_size, size_matched, status, outcome,
+                    is_open, channel_id, created_at, updated_at
+             FROM polymarket_orders WHERE is_open = 1 ORDER BY created_at ASC",
+        )?;
+
+        let orders = stmt
+            .query_map([], Self::row_to_polymarket_order)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(orders)
+    }
+
+    /// Record the latest matched size and status of a tracked order
+    pub fn update_polymarket_order(
+        &self,
+        order_id: &str,
+        size_matched: &str,
+        status: &str,
+        is_open: bool,
+    ) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "UPDATE polymarket_orders SET size_matched = ?1, status = ?2, is_open = ?3, updated_at = ?4
+             WHERE order_id = ?5",
+            rusqlite::params![size_matched, status, is_open as i32, Utc::now().to_rfc3339(), order_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    fn row_to_polymarket_order(row: &rusqlite::Row) -> rusqlite::Result<TrackedPolymarketOrder> {
+        let is_open: i32 = row.get(9)?;
+        let created_at_str: String = row.get(11)?;
+        let updated_at_str: String = row.get(12)?;
+
+        Ok(TrackedPolymarketOrder {
+            order_id: row.get(0)?,
+            wallet: row.get(1)?,
+            token_id: row.get(2)?,
+            side: row.get(3)?,
+            price: row.get(4)?,
+            original_size: row.get(5)?,
+            size_matched: row.get(6)?,
+            status: row.get(7)?,
+            outcome: row.get(8)?,
+            is_open: is_open != 0,
+            channel_id: row.get(10)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
@@ -0,0 +1,111 @@
+//! Portfolio snapshot database operations
+//!
+//! Periodic valuations of a wallet's native and token balances, written by
+//! the scheduler and read back for balance history and P&L.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+
+/// One asset held at snapshot time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holding {
+    pub symbol: String,
+    /// Token contract; `None` for the native currency
+    pub address: Option<String>,
+    /// Human-readable amount
+    pub amount: String,
+    /// `None` when no price source had a price (the holding is unvalued)
+    pub price_usd: Option<f64>,
+    pub value_usd: Option<f64>,
+}
+
+/// A wallet's balances and USD value at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub id: i64,
+    /// Wallet name ("primary" or a named wallet)
+    pub wallet: String,
+    pub address: String,
+    pub network: String,
+    /// Sum of the valued holdings
+    pub total_usd: f64,
+    pub holdings: Vec<Holding>,
+    pub taken_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Record a portfolio snapshot
+    pub fn record_portfolio_snapshot(
+        &self,
+        wallet: &str,
+        address: &str,
+        network: &str,
+        total_usd: f64,
+        holdings: &[Holding],
+    ) -> SqliteResult<i64> {
+        let conn = self.conn();
+        let holdings_json = serde_json::to_string(holdings).unwrap_or_else(|_| "[]".to_string());
+
+        conn.execute(
+            "INSERT INTO portfolio_snapshots (wallet, address, network, total_usd, holdings, taken_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![wallet, address.to_lowercase(), network, total_usd, holdings_json, Utc::now().to_rfc3339()],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Snapshots of one wallet on one network since `since`, oldest first
+    pub fn list_portfolio_snapshots(
+        &self,
+        address: &str,
+        network: &str,
+        since: DateTime<Utc>,
+    ) -> SqliteResult<Vec<PortfolioSnapshot>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, wallet, address, network, total_usd, holdings, taken_at FROM portfolio_snapshots
+             WHERE address = ?1 AND network = ?2 AND taken_at >= ?3
+             ORDER BY taken_at ASC",
+        )?;
+
+        let snapshots = stmt
+            .query_map(
+                rusqlite::params![address.to_lowercase(), network, since.to_rfc3339()],
+                Self::row_to_portfolio_snapshot,
+            )?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(snapshots)
+    }
+
+    /// Delete snapshots older than `before`
+    pub fn prune_portfolio_snapshots(&self, before: DateTime<Utc>) -> SqliteResult<usize> {
+        let conn = self.conn();
+        conn.execute(
+            "DELETE FROM portfolio_snapshots WHERE taken_at < ?1",
+            [before.to_rfc3339()],
+        )
+    }
+
+    fn row_to_portfolio_snapshot(row: &rusqlite::Row) -> rusqlite::Result<PortfolioSnapshot> {
+        let holdings_json: String = row.get(5)?;
+        let taken_at_str: String = row.get(6)?;
+
+        Ok(PortfolioSnapshot {
+            id: row.get(0)?,
+            wallet: row.get(1)?,
+            address: row.get(2)?,
+            network: row.get(3)?,
+            total_usd: row.get(4)?,
+            holdings: serde_json::from_str(&holdings_json).unwrap_or_default(),
+            taken_at: DateTime::parse_from_rfc3339(&taken_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
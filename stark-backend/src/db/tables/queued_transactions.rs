@@ -0,0 +1,116 @@
+//! Queued transactions database operations
+//!
+//! Backing store for the in-memory transaction queue, so signed transactions
+//! awaiting broadcast survive restarts.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+
+use super::super::Database;
+use crate::tx_queue::{QueuedTransaction, QueuedTxStatus};
+
+fn parse_time(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+impl Database {
+    /// Insert or update a queued transaction
+    pub fn save_queued_transaction(&self, tx: &QueuedTransaction) -> SqliteResult<()> {
+        let conn = self.conn();
+        let transitions = serde_json::to_string(&tx.transitions).unwrap_or_else(|_| "[]".to_string());
+
+        conn.execute(
+            "INSERT INTO queued_transactions
+             (uuid, network, from_address, to_address, value, data, gas_limit,
+              max_fee_per_gas, max_priority_fee_per_gas, nonce, signed_tx_hex, status,
+              tx_hash, error, explorer_url, transitions, channel_id, created_at, expires_at, broadcast_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)
+             ON CONFLICT(uuid) DO UPDATE SET
+                status = excluded.status,
+                tx_hash = excluded.tx_hash,
+                error = excluded.error,
+                explorer_url = excluded.explorer_url,
+                transitions = excluded.transitions,
+                expires_at = excluded.expires_at,
+                broadcast_at = excluded.broadcast_at",
+            rusqlite::params![
+                tx.uuid,
+                tx.network,
+                tx.from,
+                tx.to,
+                tx.value,
+                tx.data,
+                tx.gas_limit,
+                tx.max_fee_per_gas,
+                tx.max_priority_fee_per_gas,
+                tx.nonce as i64,
+                tx.signed_tx_hex,
+                tx.status.to_string(),
+                tx.tx_hash,
+                tx.error,
+                tx.explorer_url,
+                transitions,
+                tx.channel_id,
+                tx.created_at.to_rfc3339(),
+                tx.expires_at.to_rfc3339(),
+                tx.broadcast_at.map(|t| t.to_rfc3339()),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Delete a queued transaction
+    pub fn delete_queued_transaction(&self, uuid: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute("DELETE FROM queued_transactions WHERE uuid = ?1", [uuid])?;
+        Ok(rows > 0)
+    }
+
+    /// Load every queued transaction, oldest first
+    pub fn list_queued_transactions(&self) -> SqliteResult<Vec<QueuedTransaction>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT uuid, network, from_address, to_address, value, data, gas_limit,
+                    max_fee_per_gas, max_priority_fee_per_gas, nonce, signed_tx_hex, status,
+                    tx_hash, error, explorer_url, transitions, channel_id, created_at, expires_at, broadcast_at
+             FROM queued_transactions ORDER BY created_at ASC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let nonce: i64 = row.get(9)?;
+            let status_str: String = row.get(11)?;
+            let transitions_str: String = row.get(15)?;
+            let created_at_str: String = row.get(17)?;
+            let expires_at_str: String = row.get(18)?;
+            let broadcast_at_str: Option<String> = row.get(19)?;
+
+            Ok(QueuedTransaction {
+                uuid: row.get(0)?,
+                network: row.get(1)?,
+                from: row.get(2)?,
+                to: row.get(3)?,
+                value: row.get(4)?,
+                data: row.get(5)?,
+                gas_limit: row.get(6)?,
+                max_fee_per_gas: row.get(7)?,
+                max_priority_fee_per_gas: row.get(8)?,
+                nonce: nonce as u64,
+                signed_tx_hex: row.get(10)?,
+                status: status_str.parse().unwrap_or(QueuedTxStatus::Failed),
+                tx_hash: row.get(12)?,
+                error: row.get(13)?,
+                explorer_url: row.get(14)?,
+                transitions: serde_json::from_str(&transitions_str).unwrap_or_default(),
+                channel_id: row.get(16)?,
+                created_at: parse_time(&created_at_str),
+                expires_at: parse_time(&expires_at_str),
+                broadcast_at: broadcast_at_str.as_deref().map(parse_time),
+            })
+        })?;
+
+        Ok(rows.filter_map(|r| r.ok()).collect())
+    }
+}
@@ -0,0 +1,82 @@
+//! Rogue mode audit database operations
+//!
+//! Every action taken while rogue mode is active, plus rogue mode being
+//! switched on or off, so its blast radius can be reviewed after the fact.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::Serialize;
+
+use super::super::Database;
+
+/// A single rogue mode audit entry
+#[derive(Debug, Clone, Serialize)]
+pub struct RogueAuditEntry {
+    pub id: i64,
+    /// What happened, e.g. "tool:exec" or "rogue_mode_enabled"
+    pub action: String,
+    /// Action details (redacted tool arguments, etc.)
+    pub details: Option<String>,
+    pub channel_id: Option<i64>,
+    pub session_id: Option<i64>,
+    /// Whether the action succeeded (None for non-tool actions)
+    pub success: Option<bool>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Record an action in the rogue mode audit trail
+    pub fn record_rogue_audit(
+        &self,
+        action: &str,
+        details: Option<&str>,
+        channel_id: Option<i64>,
+        session_id: Option<i64>,
+        success: Option<bool>,
+    ) -> SqliteResult<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO rogue_mode_audit (action, details, channel_id, session_id, success, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                action,
+                details,
+                channel_id,
+                session_id,
+                success.map(|s| if s { 1 } else { 0 }),
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// List rogue mode audit entries, newest first
+    pub fn list_rogue_audit(&self, limit: usize, offset: usize) -> SqliteResult<Vec<RogueAuditEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, action, details, channel_id, session_id, success, created_at
+             FROM rogue_mode_audit ORDER BY id DESC LIMIT ?1 OFFSET ?2",
+        )?;
+
+        let entries = stmt
+            .query_map(rusqlite::params![limit as i64, offset as i64], |row| {
+                let success: Option<i64> = row.get(5)?;
+                let created_at_str: String = row.get(6)?;
+                Ok(RogueAuditEntry {
+                    id: row.get(0)?,
+                    action: row.get(1)?,
+                    details: row.get(2)?,
+                    channel_id: row.get(3)?,
+                    session_id: row.get(4)?,
+                    success: success.map(|s| s != 0),
+                    created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+}
+
@@ -0,0 +1,164 @@
+//! Validation records database operations
+//!
+//! EIP-8004 validation requests we made and responses we gave as a validator.
+
+use rusqlite::Result as SqliteResult;
+use serde::{Deserialize, Serialize};
+
+use super::super::Database;
+
+/// A row in the validations table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationRecord {
+    pub id: i64,
+    /// "requested" (we asked for validation) or "responded" (we validated)
+    pub direction: String,
+    pub request_hash: String,
+    pub agent_id: u64,
+    pub agent_registry: Option<String>,
+    pub validator_address: Option<String>,
+    pub request_uri: Option<String>,
+    pub response: Option<u8>,
+    pub response_uri: Option<String>,
+    pub response_hash: Option<String>,
+    pub tag: Option<String>,
+    pub tx_hash: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Maximum allowed validation score
+pub const MAX_VALIDATION_RESPONSE: u8 = 100;
+
+fn row_to_validation(row: &rusqlite::Row) -> rusqlite::Result<ValidationRecord> {
+    Ok(ValidationRecord {
+        id: row.get(0)?,
+        direction: row.get(1)?,
+        request_hash: row.get(2)?,
+        agent_id: row.get::<_, i64>(3)? as u64,
+        agent_registry: row.get(4)?,
+        validator_address: row.get(5)?,
+        request_uri: row.get(6)?,
+        response: row.get::<_, Option<i64>>(7)?.map(|r| r as u8),
+        response_uri: row.get(8)?,
+        response_hash: row.get(9)?,
+        tag: row.get(10)?,
+        tx_hash: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+    })
+}
+
+const VALIDATION_COLUMNS: &str =
+    "id, direction, request_hash, agent_id, agent_registry, validator_address, request_uri,
+     response, response_uri, response_hash, tag, tx_hash, created_at, updated_at";
+
+impl Database {
+    /// Record a validation request we sent to a validator
+    pub fn insert_validation_request(
+        &self,
+        request_hash: &str,
+        agent_id: u64,
+        agent_registry: Option<&str>,
+        validator_address: &str,
+        request_uri: &str,
+    ) -> SqliteResult<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO validations (direction, request_hash, agent_id, agent_registry, validator_address, request_uri)
+             VALUES ('requested', ?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![request_hash, agent_id as i64, agent_registry, validator_address, request_uri],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Record a validation response we gave as a validator.
+    /// Rejects scores above 100 before touching the database.
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_validation_response(
+        &self,
+        request_hash: &str,
+        agent_id: u64,
+        agent_registry: Option<&str>,
+        response: u8,
+        response_uri: Option<&str>,
+        response_hash: Option<&str>,
+        tag: Option<&str>,
+    ) -> SqliteResult<i64> {
+        if response > MAX_VALIDATION_RESPONSE {
+            return Err(rusqlite::Error::InvalidParameterName(format!(
+                "validation response must be 0-{}, got {}",
+                MAX_VALIDATION_RESPONSE, response
+            )));
+        }
+
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO validations (direction, request_hash, agent_id, agent_registry, response, response_uri, response_hash, tag)
+             VALUES ('responded', ?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                request_hash,
+                agent_id as i64,
+                agent_registry,
+                response as i64,
+                response_uri,
+                response_hash,
+                tag,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Set the on-chain anchoring tx hash for a validation record
+    pub fn set_validation_tx_hash(&self, id: i64, tx_hash: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "UPDATE validations SET tx_hash = ?1, updated_at = datetime('now') WHERE id = ?2",
+            rusqlite::params![tx_hash, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Get all validation records for a request hash
+    pub fn get_validations_by_request_hash(&self, request_hash: &str) -> SqliteResult<Vec<ValidationRecord>> {
+        let conn = self.conn();
+        let sql = format!(
+            "SELECT {} FROM validations WHERE request_hash = ?1 ORDER BY id ASC",
+            VALIDATION_COLUMNS
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let records = stmt
+            .query_map([request_hash], row_to_validation)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(records)
+    }
+
+    /// List recent validation records, optionally filtered by direction
+    pub fn list_validations(&self, direction: Option<&str>, limit: usize) -> SqliteResult<Vec<ValidationRecord>> {
+        let conn = self.conn();
+        let records = match direction {
+            Some(d) => {
+                let sql = format!(
+                    "SELECT {} FROM validations WHERE direction = ?1 ORDER BY id DESC LIMIT ?2",
+                    VALIDATION_COLUMNS
+                );
+                let mut stmt = conn.prepare(&sql)?;
+                stmt.query_map(rusqlite::params![d, limit as i64], row_to_validation)?
+                    .filter_map(|r| r.ok())
+                    .collect()
+            }
+            None => {
+                let sql = format!(
+                    "SELECT {} FROM validations ORDER BY id DESC LIMIT ?1",
+                    VALIDATION_COLUMNS
+                );
+                let mut stmt = conn.prepare(&sql)?;
+                stmt.query_map([limit as i64], row_to_validation)?
+                    .filter_map(|r| r.ok())
+                    .collect()
+            }
+        };
+        Ok(records)
+    }
+}
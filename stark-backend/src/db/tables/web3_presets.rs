@@ -0,0 +1,96 @@
+//! Web3 preset database operations
+//!
+//! Presets for `web3_function_call` saved through the API. They're cached in
+//! `tools::presets` and take precedence over the file presets.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+use serde::Serialize;
+
+use super::super::Database;
+use crate::tools::presets::Web3Preset;
+
+/// A Web3 preset saved through the API
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredWeb3Preset {
+    pub name: String,
+    pub preset: Web3Preset,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Create or replace a preset
+    pub fn upsert_web3_preset(&self, name: &str, preset: &Web3Preset) -> SqliteResult<StoredWeb3Preset> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+        let preset_json = serde_json::to_string(preset).unwrap_or_default();
+
+        conn.execute(
+            "INSERT INTO web3_presets (name, preset, created_at, updated_at) VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT(name) DO UPDATE SET preset = excluded.preset, updated_at = excluded.updated_at",
+            rusqlite::params![name, preset_json, &now],
+        )?;
+
+        drop(conn);
+        self.get_web3_preset(name)
+            .and_then(|p| p.ok_or(rusqlite::Error::QueryReturnedNoRows))
+    }
+
+    /// Get a preset by name
+    pub fn get_web3_preset(&self, name: &str) -> SqliteResult<Option<StoredWeb3Preset>> {
+        let conn = self.conn();
+        match conn.query_row(
+            "SELECT name, preset, created_at, updated_at FROM web3_presets WHERE name = ?1",
+            [name],
+            Self::row_to_web3_preset,
+        ) {
+            Ok(preset) => Ok(Some(preset)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// List all presets by name
+    pub fn list_web3_presets(&self) -> SqliteResult<Vec<StoredWeb3Preset>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT name, preset, created_at, updated_at FROM web3_presets ORDER BY name ASC",
+        )?;
+
+        let presets = stmt
+            .query_map([], Self::row_to_web3_preset)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(presets)
+    }
+
+    /// Delete a preset. Returns false if it didn't exist.
+    pub fn delete_web3_preset(&self, name: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute("DELETE FROM web3_presets WHERE name = ?1", [name])?;
+        Ok(rows > 0)
+    }
+
+    fn row_to_web3_preset(row: &rusqlite::Row) -> rusqlite::Result<StoredWeb3Preset> {
+        let preset_json: String = row.get(1)?;
+        let created_at_str: String = row.get(2)?;
+        let updated_at_str: String = row.get(3)?;
+
+        let preset = serde_json::from_str(&preset_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(StoredWeb3Preset {
+            name: row.get(0)?,
+            preset,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+}
@@ -3,10 +3,16 @@
 mod help;
 mod register;
 mod status;
+pub mod tip;
 mod unregister;
 
 use crate::db::Database;
 
+use super::config::CommandPermissions;
+
+/// Name of the tip command, which is forwarded to the agent rather than run here
+pub const TIP_COMMAND: &str = "tip";
+
 /// Available commands for non-admin users
 #[derive(Debug)]
 pub enum Command {
@@ -20,6 +26,25 @@ pub enum Command {
     Unregister,
 }
 
+impl Command {
+    /// Name used in the role permission table
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Register(_) => "register",
+            Command::Status => "status",
+            Command::Help => "help",
+            Command::Unregister => "unregister",
+        }
+    }
+}
+
+/// Whether the text is a tip request. Requires " tip " as a word to avoid
+/// matching "multiple", "tipper", etc.
+pub fn is_tip_command(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    lower.contains(" tip ") || lower.starts_with("tip ")
+}
+
 /// Parse a command from text
 pub fn parse(text: &str) -> Option<Command> {
     let text = text.trim();
@@ -60,8 +85,23 @@ pub fn parse(text: &str) -> Option<Command> {
     }
 }
 
-/// Execute a command and return the response
-pub async fn execute(cmd: Command, user_id: &str, db: &Database) -> Result<String, String> {
+/// Execute a command and return the response, refusing commands the user's
+/// permissions don't include
+pub async fn execute(
+    cmd: Command,
+    user_id: &str,
+    db: &Database,
+    permissions: &CommandPermissions,
+) -> Result<String, String> {
+    if !permissions.allows_command(cmd.name()) {
+        log::info!(
+            "Discord commands: User {} is not permitted to run '{}'",
+            user_id,
+            cmd.name()
+        );
+        return Ok(permission_denied_message(permissions));
+    }
+
     match cmd {
         Command::Register(addr) => register::execute(user_id, &addr, db).await,
         Command::Status => status::execute(user_id, db).await,
@@ -70,15 +110,34 @@ pub async fn execute(cmd: Command, user_id: &str, db: &Database) -> Result<Strin
     }
 }
 
-/// Message shown when a user tries to run an unauthorized command
-pub fn permission_denied_message() -> String {
-    "You don't have permission to run that command.\n\n\
-    **Available commands:**\n\
-    - `@starkbot register <address>` - Register your public address for tipping\n\
-    - `@starkbot status` - Check your registration status\n\
-    - `@starkbot help` - Show available commands\n\
-    - `@starkbot unregister` - Remove your registered address"
-        .to_string()
+/// Usage line for a command in the permission denied message
+fn usage_line(command: &str) -> Option<&'static str> {
+    match command {
+        "register" => Some("- `@starkbot register <address>` - Register your public address for tipping"),
+        "status" => Some("- `@starkbot status` - Check your registration status"),
+        "help" => Some("- `@starkbot help` - Show available commands"),
+        "unregister" => Some("- `@starkbot unregister` - Remove your registered address"),
+        TIP_COMMAND => Some("- `@starkbot tip @user <amount> <token>` - Tip another user"),
+        _ => None,
+    }
+}
+
+/// Message shown when a user tries to run an unauthorized command,
+/// listing the commands they are allowed to run
+pub fn permission_denied_message(permissions: &CommandPermissions) -> String {
+    let lines: Vec<&str> = ["register", "status", "help", "unregister", TIP_COMMAND]
+        .into_iter()
+        .filter(|cmd| permissions.allows_command(cmd))
+        .filter_map(usage_line)
+        .collect();
+
+    if lines.is_empty() {
+        return "You don't have permission to run that command.".to_string();
+    }
+    format!(
+        "You don't have permission to run that command.\n\n**Available commands:**\n{}",
+        lines.join("\n")
+    )
 }
 
 #[cfg(test)]
@@ -122,6 +181,28 @@ mod tests {
         assert!(parse("tip @someone 100").is_none());
     }
 
+    #[test]
+    fn test_is_tip_command() {
+        assert!(is_tip_command("tip @alice 10 usdc"));
+        assert!(is_tip_command("please TIP @bob 5"));
+        assert!(!is_tip_command("multiple tippers"));
+        assert!(!is_tip_command("status"));
+    }
+
+    #[test]
+    fn test_permission_denied_lists_allowed_commands() {
+        let member = CommandPermissions::parse("status");
+        let message = permission_denied_message(&member);
+        assert!(message.contains("`@starkbot status`"));
+        assert!(!message.contains("register"));
+        assert!(!message.contains("tip"));
+
+        let default_message = permission_denied_message(&CommandPermissions::default_user());
+        assert!(default_message.contains("register <address>"));
+        assert!(default_message.contains("unregister"));
+        assert!(!default_message.contains("tip @user"));
+    }
+
     #[test]
     fn test_case_insensitive() {
         assert!(matches!(parse("REGISTER 0x123"), Some(Command::Register(_))));
@@ -0,0 +1,173 @@
+//! Tip command - send tokens to another user's registered address
+//!
+//! `@starkbot tip @user 5 USDC [on base]` resolves the recipient's registered
+//! address, then signs and queues the transfer from the channel's wallet. The
+//! queued transaction still needs an admin's approval before it's broadcast.
+
+use crate::db::Database;
+use crate::discord_hooks::db;
+use crate::discord_hooks::tools::extract_user_id;
+use crate::models::ChannelSettingKey;
+use crate::tools::builtin::cryptocurrency::transfer::{queue_transfer, TransferToken};
+use crate::tx_queue::TxQueueManager;
+
+/// A parsed `tip` command
+#[derive(Debug, Clone, PartialEq)]
+pub struct TipRequest {
+    /// Discord user ID of the recipient
+    pub recipient_id: String,
+    /// Human-readable amount, e.g. "5" or "0.25"
+    pub amount: String,
+    /// Token symbol, e.g. "USDC"
+    pub token: String,
+    /// Network name; defaults to base
+    pub network: Option<String>,
+}
+
+/// Result of running a tip
+#[derive(Debug, Clone)]
+pub enum TipOutcome {
+    /// The transfer was queued and needs approval
+    Queued {
+        /// tx_queue UUID
+        uuid: String,
+        /// Summary for the approval message
+        summary: String,
+    },
+    /// The recipient has no registered address
+    RecipientNotRegistered(String),
+}
+
+/// Parse `tip <@user> <amount> <token> [on <network>]`. Returns `None` for
+/// anything else so free-form tip requests can fall back to the agent.
+pub fn parse(text: &str) -> Option<TipRequest> {
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    let (mention, amount, token, rest) = match parts.as_slice() {
+        [cmd, mention, amount, token, rest @ ..] if cmd.eq_ignore_ascii_case("tip") => {
+            (*mention, *amount, *token, rest)
+        }
+        _ => return None,
+    };
+
+    // Only accept real mentions, not bare numbers that could be amounts
+    if !mention.starts_with("<@") {
+        return None;
+    }
+    let recipient_id = extract_user_id(mention)?;
+
+    let amount = amount.trim_start_matches('$');
+    let valid_amount = !amount.is_empty()
+        && amount.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && amount.chars().filter(|c| *c == '.').count() <= 1
+        && amount.chars().any(|c| c.is_ascii_digit());
+    if !valid_amount || !token.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let network = match rest {
+        [] => None,
+        [on, network] if on.eq_ignore_ascii_case("on") => Some(network.to_lowercase()),
+        [network] => Some(network.to_lowercase()),
+        _ => return None,
+    };
+
+    Some(TipRequest {
+        recipient_id,
+        amount: amount.to_string(),
+        token: token.to_uppercase(),
+        network,
+    })
+}
+
+/// Message asking an unregistered recipient to register
+fn not_registered_message(recipient_id: &str) -> String {
+    format!(
+        "<@{}> hasn't registered an address yet, so they can't receive tips.\n\n\
+        <@{}>, register with `@starkbot register <your-address>` and the tip can be sent again.",
+        recipient_id, recipient_id
+    )
+}
+
+/// Resolve the recipient and queue the transfer
+pub async fn execute(
+    request: &TipRequest,
+    sender_id: &str,
+    database: &Database,
+    tx_queue: &TxQueueManager,
+    channel_id: i64,
+) -> Result<TipOutcome, String> {
+    let address = db::get_profile(database, &request.recipient_id)?.and_then(|p| p.public_address);
+    let address = match address {
+        Some(a) => a,
+        None => return Ok(TipOutcome::RecipientNotRegistered(not_registered_message(&request.recipient_id))),
+    };
+
+    let token = TransferToken::resolve(&request.token, request.network.as_deref())?;
+    let amount_raw = token.to_raw(&request.amount)?;
+    // Tips come from the channel's wallet, like any other transfer made there
+    let wallet = database
+        .get_channel_setting(channel_id, ChannelSettingKey::Wallet.as_ref())
+        .map_err(|e| format!("Failed to read the channel's wallet: {}", e))?
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    let queued = queue_transfer(tx_queue, &token, &address, &amount_raw, wallet.as_deref(), Some(channel_id)).await?;
+
+    let summary = format!(
+        "💸 **Tip approval required**\n\
+        **From:** <@{}>\n\
+        **To:** <@{}> (`{}`)\n\
+        **Amount:** {} {}\n\
+        **Network:** {}\n\
+        **ID:** `{}`",
+        sender_id, request.recipient_id, address, request.amount, token.symbol, token.network, queued.uuid
+    );
+
+    Ok(TipOutcome::Queued {
+        uuid: queued.uuid,
+        summary,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tip() {
+        assert_eq!(
+            parse("tip <@123456789> 5 usdc"),
+            Some(TipRequest {
+                recipient_id: "123456789".to_string(),
+                amount: "5".to_string(),
+                token: "USDC".to_string(),
+                network: None,
+            })
+        );
+
+        let tip = parse("TIP <@!42> 0.25 eth on Mainnet").unwrap();
+        assert_eq!(tip.recipient_id, "42");
+        assert_eq!(tip.amount, "0.25");
+        assert_eq!(tip.network.as_deref(), Some("mainnet"));
+
+        assert_eq!(parse("tip <@42> $10 USDC base").unwrap().amount, "10");
+    }
+
+    #[test]
+    fn test_parse_tip_rejects_free_form() {
+        // These fall back to the agent
+        assert!(parse("tip <@42> some usdc").is_none());
+        assert!(parse("tip alice 5 usdc").is_none());
+        assert!(parse("tip 42 5 usdc").is_none());
+        assert!(parse("tip <@42> 5").is_none());
+        assert!(parse("tip <@42> 1.2.3 usdc").is_none());
+        assert!(parse("tip <@42> 5 usdc to the moon").is_none());
+        assert!(parse("please tip <@42> 5 usdc").is_none());
+    }
+
+    #[test]
+    fn test_not_registered_message_prompts_registration() {
+        let message = not_registered_message("42");
+        assert!(message.contains("<@42>"));
+        assert!(message.contains("@starkbot register"));
+    }
+}
@@ -1,16 +1,110 @@
 //! Configuration for Discord hooks
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::db::Database;
 use crate::models::ChannelSettingKey;
 
+/// Default seconds an admin's query mode stays active
+pub const DEFAULT_QUERY_TIMEOUT_SECS: u64 = 120;
+
+/// Commands regular users may run when no role permission table is configured
+pub const DEFAULT_USER_COMMANDS: &[&str] = &["register", "status", "help", "unregister"];
+
+/// Commands and agent tools a non-admin Discord user is allowed to use
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandPermissions {
+    /// Admins bypass every check
+    full_access: bool,
+    /// Allowed command names (register, status, help, unregister, tip)
+    commands: HashSet<String>,
+    /// Agent tools available to forwarded commands such as tip
+    tools: HashSet<String>,
+}
+
+impl CommandPermissions {
+    /// Unrestricted permissions (admins)
+    pub fn full() -> Self {
+        Self {
+            full_access: true,
+            ..Default::default()
+        }
+    }
+
+    /// The built-in limited command set for regular users
+    pub fn default_user() -> Self {
+        Self {
+            commands: DEFAULT_USER_COMMANDS.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Parse a comma-separated entry: command names, plus `tool:<name>` for agent tools
+    pub fn parse(entry: &str) -> Self {
+        let mut permissions = Self::default();
+        for item in entry.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+            match item.strip_prefix("tool:") {
+                Some(tool) if !tool.trim().is_empty() => {
+                    permissions.tools.insert(tool.trim().to_string());
+                }
+                Some(_) => {}
+                None => {
+                    permissions.commands.insert(item);
+                }
+            }
+        }
+        permissions
+    }
+
+    /// Whether the user may run the named command
+    pub fn allows_command(&self, command: &str) -> bool {
+        self.full_access || self.commands.contains(&command.to_lowercase())
+    }
+
+    /// Tools a forwarded request may use; `None` means unrestricted
+    pub fn allowed_tools(&self) -> Option<Vec<String>> {
+        if self.full_access {
+            return None;
+        }
+        let mut tools: Vec<String> = self.tools.iter().cloned().collect();
+        tools.sort();
+        Some(tools)
+    }
+
+    fn merge(&mut self, other: &CommandPermissions) {
+        self.full_access |= other.full_access;
+        self.commands.extend(other.commands.iter().cloned());
+        self.tools.extend(other.tools.iter().cloned());
+    }
+}
+
+/// Parse the role permission table: one `ROLE_ID: command, command, tool:name`
+/// entry per line (or `;`-separated). The `default` key applies to users
+/// without any listed role.
+fn parse_role_permissions(raw: &str) -> HashMap<String, CommandPermissions> {
+    raw.split(['\n', ';'])
+        .filter_map(|line| {
+            let (role, entry) = line.split_once(':')?;
+            let role = role.trim();
+            if role.is_empty() {
+                return None;
+            }
+            Some((role.to_lowercase(), CommandPermissions::parse(entry)))
+        })
+        .collect()
+}
+
 /// Configuration for the Discord hooks module
 #[derive(Debug, Clone)]
 pub struct DiscordHooksConfig {
     /// Discord user IDs that have admin access (full agentic commands)
     admin_user_ids: HashSet<String>,
+    /// Discord role ID (or `default`) -> permissions for non-admin users
+    role_permissions: HashMap<String, CommandPermissions>,
+    /// How long query mode stays active; `None` waits indefinitely
+    pub query_timeout: Option<Duration>,
     /// Whether to require @mention in server channels (default: true)
     pub require_mention_in_servers: bool,
     /// Whether to allow DMs without @mention (default: true)
@@ -20,7 +114,8 @@ pub struct DiscordHooksConfig {
 impl DiscordHooksConfig {
     /// Create a new config from channel settings in the database
     ///
-    /// Reads the discord_admin_user_ids setting for the given channel
+    /// Reads the discord_admin_user_ids, discord_role_permissions and
+    /// discord_query_timeout_secs settings for the given channel
     pub fn from_channel_settings(db: &Arc<Database>, channel_id: i64) -> Self {
         let admin_ids: HashSet<String> = db
             .get_channel_setting(channel_id, ChannelSettingKey::DiscordAdminUserIds.as_ref())
@@ -48,8 +143,32 @@ impl DiscordHooksConfig {
             );
         }
 
+        let role_permissions = db
+            .get_channel_setting(channel_id, ChannelSettingKey::DiscordRolePermissions.as_ref())
+            .ok()
+            .flatten()
+            .map(|raw| parse_role_permissions(&raw))
+            .unwrap_or_default();
+
+        if !role_permissions.is_empty() {
+            log::info!(
+                "Discord hooks: Configured permissions for {} role(s) on channel {}",
+                role_permissions.len(),
+                channel_id
+            );
+        }
+
+        let query_timeout_secs = db
+            .get_channel_setting(channel_id, ChannelSettingKey::DiscordQueryTimeoutSecs.as_ref())
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_QUERY_TIMEOUT_SECS);
+
         Self {
             admin_user_ids: admin_ids,
+            role_permissions,
+            query_timeout: query_timeout_from_secs(query_timeout_secs),
             require_mention_in_servers: true,
             allow_dm_without_mention: true,
         }
@@ -82,6 +201,8 @@ impl DiscordHooksConfig {
 
         Self {
             admin_user_ids: admin_ids,
+            role_permissions: HashMap::new(),
+            query_timeout: query_timeout_from_secs(DEFAULT_QUERY_TIMEOUT_SECS),
             require_mention_in_servers: true,
             allow_dm_without_mention: true,
         }
@@ -91,6 +212,8 @@ impl DiscordHooksConfig {
     pub fn empty() -> Self {
         Self {
             admin_user_ids: HashSet::new(),
+            role_permissions: HashMap::new(),
+            query_timeout: query_timeout_from_secs(DEFAULT_QUERY_TIMEOUT_SECS),
             require_mention_in_servers: true,
             allow_dm_without_mention: true,
         }
@@ -100,16 +223,55 @@ impl DiscordHooksConfig {
     pub fn with_admins(admin_ids: Vec<String>) -> Self {
         Self {
             admin_user_ids: admin_ids.into_iter().collect(),
+            role_permissions: HashMap::new(),
+            query_timeout: query_timeout_from_secs(DEFAULT_QUERY_TIMEOUT_SECS),
             require_mention_in_servers: true,
             allow_dm_without_mention: true,
         }
     }
 
+    /// Set the role permission table (same format as the channel setting)
+    #[cfg(test)]
+    pub fn with_role_permissions(mut self, raw: &str) -> Self {
+        self.role_permissions = parse_role_permissions(raw);
+        self
+    }
+
     /// Check if a user ID is an admin
     pub fn is_admin(&self, user_id: &str) -> bool {
         self.admin_user_ids.contains(user_id)
     }
 
+    /// Resolve the permissions for a user from their admin status and Discord roles.
+    ///
+    /// Admins get full access. Otherwise the entries for the user's roles are
+    /// combined; a user with no listed role gets the `default` entry, or the
+    /// built-in command set when the table doesn't define one.
+    pub fn permissions_for(&self, user_id: &str, role_ids: &[String]) -> CommandPermissions {
+        if self.is_admin(user_id) {
+            return CommandPermissions::full();
+        }
+        if self.role_permissions.is_empty() {
+            return CommandPermissions::default_user();
+        }
+
+        let mut permissions = CommandPermissions::default();
+        let mut matched = false;
+        for role_id in role_ids {
+            if let Some(role) = self.role_permissions.get(role_id.as_str()) {
+                permissions.merge(role);
+                matched = true;
+            }
+        }
+        if matched {
+            return permissions;
+        }
+        self.role_permissions
+            .get("default")
+            .cloned()
+            .unwrap_or_else(CommandPermissions::default_user)
+    }
+
     /// Get the number of configured admins
     pub fn admin_count(&self) -> usize {
         self.admin_user_ids.len()
@@ -121,6 +283,11 @@ impl DiscordHooksConfig {
     }
 }
 
+/// 0 disables the timeout
+fn query_timeout_from_secs(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
 impl Default for DiscordHooksConfig {
     fn default() -> Self {
         Self::empty()
@@ -152,4 +319,59 @@ mod tests {
         assert_eq!(config.admin_count(), 2);
         assert!(config.has_admins());
     }
+
+    const TABLE: &str = "111: register, status, help, tip, tool:discord_resolve_user, tool:TOKEN_LOOKUP\n\
+                         222: status\n\
+                         default: help";
+
+    #[test]
+    fn test_role_gated_commands() {
+        let config = DiscordHooksConfig::with_admins(vec!["9".to_string()]).with_role_permissions(TABLE);
+
+        // "trader" role can tip and gets its tools
+        let trader = config.permissions_for("1", &["111".to_string()]);
+        assert!(trader.allows_command("tip"));
+        assert!(trader.allows_command("STATUS"));
+        assert!(!trader.allows_command("unregister"));
+        assert_eq!(
+            trader.allowed_tools(),
+            Some(vec!["discord_resolve_user".to_string(), "token_lookup".to_string()])
+        );
+
+        // "member" role can only check status
+        let member = config.permissions_for("2", &["222".to_string()]);
+        assert!(member.allows_command("status"));
+        assert!(!member.allows_command("tip"));
+        assert!(!member.allows_command("register"));
+        assert_eq!(member.allowed_tools(), Some(vec![]));
+
+        // Roles combine
+        let both = config.permissions_for("3", &["222".to_string(), "111".to_string()]);
+        assert!(both.allows_command("tip"));
+
+        // No listed role falls back to the default entry
+        let other = config.permissions_for("4", &["333".to_string()]);
+        assert_eq!(other, CommandPermissions::parse("help"));
+
+        // Admins remain full-access regardless of roles
+        let admin = config.permissions_for("9", &["222".to_string()]);
+        assert!(admin.allows_command("tip"));
+        assert!(admin.allows_command("anything"));
+        assert_eq!(admin.allowed_tools(), None);
+    }
+
+    #[test]
+    fn test_no_role_table_keeps_default_commands() {
+        let config = DiscordHooksConfig::empty();
+        let user = config.permissions_for("1", &["111".to_string()]);
+        for cmd in DEFAULT_USER_COMMANDS {
+            assert!(user.allows_command(cmd));
+        }
+        assert!(!user.allows_command("tip"));
+
+        // A table without a default entry keeps the built-in set for unlisted users
+        let config = DiscordHooksConfig::empty().with_role_permissions("111: tip; : status; bogus line");
+        assert_eq!(config.permissions_for("1", &[]), CommandPermissions::default_user());
+        assert!(config.permissions_for("1", &["111".to_string()]).allows_command("tip"));
+    }
 }
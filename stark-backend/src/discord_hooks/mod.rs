@@ -2,15 +2,23 @@
 //!
 //! This module provides:
 //! - Admin command detection and forwarding to the agent
-//! - Limited command handling for regular users (register, status, help)
+//! - Limited command handling for regular users (register, status, help),
+//!   gated per Discord role by the `discord_role_permissions` channel setting
 //! - Discord user profile management with public address registration
 //! - Tool for resolving Discord mentions to registered public addresses
+//! - Native `tip @user <amount> <token>` transfers, queued for admin approval
 //!
 //! ## Query Mode for Admins
 //!
 //! By default, admins must first say "@bot query" to activate query mode.
 //! The next @mention from that admin will be treated as an agentic query.
-//! This prevents accidental agent invocations.
+//! This prevents accidental agent invocations. Query mode expires after the
+//! channel's `discord_query_timeout_secs` setting.
+//!
+//! ## Direct Messages
+//!
+//! DMs don't need an @mention. An admin's DM is treated as a query directly,
+//! and regular users can run their commands by DM.
 
 pub mod commands;
 pub mod config;
@@ -21,14 +29,18 @@ use rand::seq::SliceRandom;
 use serenity::all::{Context, Message, UserId};
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::tx_queue::TxQueueManager;
+use commands::tip::TipOutcome;
 
 pub use config::DiscordHooksConfig;
 pub use db::DiscordUserProfile;
 
 // Track which admin users are currently listening for a query
-// Key: discord_user_id, Value: true if waiting for next message to be treated as query
+// Key: discord_user_id, Value: when query mode was activated
 lazy_static::lazy_static! {
-    static ref LISTENING_FOR_QUERY: Mutex<HashMap<String, bool>> = Mutex::new(HashMap::new());
+    static ref LISTENING_FOR_QUERY: Mutex<HashMap<String, Instant>> = Mutex::new(HashMap::new());
 }
 
 /// Result of processing a Discord message
@@ -40,6 +52,8 @@ pub struct ProcessResult {
     pub response: Option<String>,
     /// Request to forward to the agent (if admin command)
     pub forward_to_agent: Option<ForwardRequest>,
+    /// Queued transaction to post for admin approval
+    pub approval: Option<ApprovalRequest>,
 }
 
 impl ProcessResult {
@@ -49,6 +63,7 @@ impl ProcessResult {
             handled: false,
             response: None,
             forward_to_agent: None,
+            approval: None,
         }
     }
 
@@ -58,6 +73,7 @@ impl ProcessResult {
             handled: true,
             response: Some(response),
             forward_to_agent: None,
+            approval: None,
         }
     }
 
@@ -67,10 +83,30 @@ impl ProcessResult {
             handled: true,
             response: None,
             forward_to_agent: Some(request),
+            approval: None,
+        }
+    }
+
+    /// A transaction was queued and should be posted for approval
+    pub fn awaiting_approval(request: ApprovalRequest) -> Self {
+        Self {
+            handled: true,
+            response: None,
+            forward_to_agent: None,
+            approval: Some(request),
         }
     }
 }
 
+/// A queued transaction that needs an admin's approval before broadcast
+#[derive(Debug, Clone)]
+pub struct ApprovalRequest {
+    /// tx_queue UUID
+    pub id: String,
+    /// Summary shown in the approval message
+    pub summary: String,
+}
+
 /// Request to forward a message to the agent dispatcher
 #[derive(Debug, Clone)]
 pub struct ForwardRequest {
@@ -82,23 +118,36 @@ pub struct ForwardRequest {
     pub user_name: String,
     /// Whether the user is an admin
     pub is_admin: bool,
+    /// Tools the agent may use for this request; `None` means unrestricted
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+/// Drop entries whose query mode has expired (`ttl` of `None` never expires)
+fn sweep_expired(map: &mut HashMap<String, Instant>, now: Instant, ttl: Option<Duration>) {
+    let Some(ttl) = ttl else {
+        return;
+    };
+    map.retain(|user_id, started| {
+        let active = now.saturating_duration_since(*started) < ttl;
+        if !active {
+            log::info!("Discord hooks: Admin {} query mode expired", user_id);
+        }
+        active
+    });
 }
 
-/// Check if a user is in "listening for query" mode
-fn is_listening_for_query(user_id: &str) -> bool {
-    LISTENING_FOR_QUERY
-        .lock()
-        .unwrap()
-        .get(user_id)
-        .copied()
-        .unwrap_or(false)
+/// Check if a user is in "listening for query" mode, expiring stale entries
+fn is_listening_for_query(user_id: &str, ttl: Option<Duration>) -> bool {
+    let mut map = LISTENING_FOR_QUERY.lock().unwrap();
+    sweep_expired(&mut map, Instant::now(), ttl);
+    map.contains_key(user_id)
 }
 
 /// Set a user's "listening for query" state
 fn set_listening_for_query(user_id: &str, listening: bool) {
     let mut map = LISTENING_FOR_QUERY.lock().unwrap();
     if listening {
-        map.insert(user_id.to_string(), true);
+        map.insert(user_id.to_string(), Instant::now());
         log::info!("Discord hooks: Admin {} is now listening for query", user_id);
     } else {
         map.remove(user_id);
@@ -129,6 +178,35 @@ pub fn extract_command_text(content: &str, bot_id: UserId) -> String {
         .to_string()
 }
 
+/// Run a tip with the structured `tip @user <amount> <token>` syntax.
+/// Returns `None` when the text doesn't match, so it can go to the agent instead.
+async fn handle_tip(
+    command_text: &str,
+    user_id: &str,
+    db: &crate::db::Database,
+    tx_queue: Option<&std::sync::Arc<TxQueueManager>>,
+    channel_id: i64,
+) -> Option<ProcessResult> {
+    let request = commands::tip::parse(command_text)?;
+    let Some(tx_queue) = tx_queue else {
+        return Some(ProcessResult::handled(
+            "Tipping isn't available right now: the transaction queue isn't configured.".to_string(),
+        ));
+    };
+
+    let result = match commands::tip::execute(&request, user_id, db, tx_queue, channel_id).await {
+        Ok(TipOutcome::Queued { uuid, summary }) => {
+            ProcessResult::awaiting_approval(ApprovalRequest { id: uuid, summary })
+        }
+        Ok(TipOutcome::RecipientNotRegistered(message)) => ProcessResult::handled(message),
+        Err(e) => {
+            log::warn!("Discord hooks: Tip from {} failed: {}", user_id, e);
+            ProcessResult::handled(format!("Couldn't send the tip: {}", e))
+        }
+    };
+    Some(result)
+}
+
 /// Process a Discord message through the hooks system
 ///
 /// Returns a ProcessResult indicating how to handle the message:
@@ -137,12 +215,13 @@ pub fn extract_command_text(content: &str, bot_id: UserId) -> String {
 /// - `handled: true` with `forward_to_agent` - Forward to agent dispatcher
 ///
 /// Note: The config is reloaded from the database on each message to pick up
-/// changes to admin user IDs without requiring a channel restart.
+/// changes to admin user IDs and role permissions without requiring a channel restart.
 pub async fn process(
     msg: &Message,
     ctx: &Context,
     db: &std::sync::Arc<crate::db::Database>,
     channel_id: i64,
+    tx_queue: Option<&std::sync::Arc<TxQueueManager>>,
 ) -> Result<ProcessResult, String> {
     // Reload config from database to pick up any changes
     let config = DiscordHooksConfig::from_channel_settings(db, channel_id);
@@ -161,8 +240,12 @@ pub async fn process(
         return Ok(ProcessResult::not_handled());
     }
 
+    // DMs don't need a mention when the config allows it
+    let is_dm = msg.guild_id.is_none();
+    let dm_without_mention = is_dm && config.allow_dm_without_mention;
+
     // Check if bot is mentioned
-    if !is_bot_mentioned(msg, bot_id) {
+    if !dm_without_mention && !is_bot_mentioned(msg, bot_id) {
         // Check if they mentioned a role the bot has (common mistake)
         if !msg.mention_roles.is_empty() {
             if let Some(guild_id) = msg.guild_id {
@@ -182,8 +265,6 @@ pub async fn process(
                 }
             }
         }
-        // In DMs, we might want to process without mention
-        // For now, require mention in all contexts
         return Ok(ProcessResult::not_handled());
     }
 
@@ -206,14 +287,21 @@ pub async fn process(
         // Don't fail the whole request, just log it
     }
 
-    // Check if user is admin
+    // Check if user is admin, and resolve what their roles allow otherwise
     let is_admin = config.is_admin(&user_id);
+    let role_ids: Vec<String> = msg
+        .member
+        .as_ref()
+        .map(|m| m.roles.iter().map(|r| r.to_string()).collect())
+        .unwrap_or_default();
+    let permissions = config.permissions_for(&user_id, &role_ids);
 
     log::info!(
-        "Discord hooks: Processing message from {} ({}), admin={}, text='{}'",
+        "Discord hooks: Processing message from {} ({}), admin={}, dm={}, text='{}'",
         user_name,
         user_id,
         is_admin,
+        is_dm,
         if command_text.len() > 50 {
             format!("{}...", &command_text[..50])
         } else {
@@ -223,9 +311,21 @@ pub async fn process(
 
     if is_admin {
         // Admin flow: implement query mode state machine
-        let is_listening = is_listening_for_query(&user_id);
+        let is_listening = is_listening_for_query(&user_id, config.query_timeout);
+        let is_register = command_text.to_lowercase().starts_with("register");
 
-        if is_listening {
+        if dm_without_mention && !is_register {
+            // A DM to the bot is already a deliberate request - treat it as the query
+            set_listening_for_query(&user_id, false);
+            log::info!("Discord hooks: Admin {} submitted query by DM", user_name);
+            Ok(ProcessResult::forward_to_agent(ForwardRequest {
+                text: command_text,
+                user_id,
+                user_name,
+                is_admin: true,
+                allowed_tools: None,
+            }))
+        } else if is_listening {
             // Admin was listening for a query - this message IS the query
             // Reset the listening state and forward to agent
             set_listening_for_query(&user_id, false);
@@ -243,6 +343,7 @@ pub async fn process(
                 user_id,
                 user_name,
                 is_admin: true,
+                allowed_tools: None,
             }))
         } else if contains_query_keyword(&command_text) {
             // Admin said "query" - activate listening mode
@@ -252,10 +353,8 @@ pub async fn process(
             ))
         } else {
             // Admin mentioned bot without "query" keyword and wasn't in listening mode
-            let cmd_lower = command_text.to_lowercase();
-
             // Check if this is a "register" command - allow admins to register like regular users
-            if cmd_lower.starts_with("register") {
+            if is_register {
                 log::info!(
                     "Discord hooks: Admin {} using register command as regular user",
                     user_name
@@ -263,7 +362,7 @@ pub async fn process(
                 // Fall through to regular user command handling for registration
                 match commands::parse(&command_text) {
                     Some(cmd) => {
-                        let response = commands::execute(cmd, &user_id, db).await?;
+                        let response = commands::execute(cmd, &user_id, db, &permissions).await?;
                         Ok(ProcessResult::handled(response))
                     }
                     None => {
@@ -274,9 +373,12 @@ pub async fn process(
                         ))
                     }
                 }
-            } else if cmd_lower.contains(" tip ") || cmd_lower.starts_with("tip ") {
-                // Allow admins to tip without query mode - forward directly to agent
-                // Require " tip " as a word to avoid matching "multiple", "tipper", etc.
+            } else if commands::is_tip_command(&command_text) {
+                // Allow admins to tip without query mode - handle structured tips
+                // directly, forward free-form ones to the agent
+                if let Some(result) = handle_tip(&command_text, &user_id, db, tx_queue, channel_id).await {
+                    return Ok(result);
+                }
                 log::info!(
                     "Discord hooks: Admin {} using tip command, forwarding to agent",
                     user_name
@@ -286,6 +388,7 @@ pub async fn process(
                     user_id,
                     user_name,
                     is_admin: true,
+                    allowed_tools: None,
                 }))
             } else {
                 // Explain how to activate query mode
@@ -313,14 +416,35 @@ pub async fn process(
             return Ok(ProcessResult::handled(response.to_string()));
         }
 
+        // Tipping is forwarded to the agent for roles that allow it, limited to the role's tools
+        if commands::is_tip_command(&command_text) {
+            if !permissions.allows_command(commands::TIP_COMMAND) {
+                return Ok(ProcessResult::handled(commands::permission_denied_message(&permissions)));
+            }
+            if let Some(result) = handle_tip(&command_text, &user_id, db, tx_queue, channel_id).await {
+                return Ok(result);
+            }
+            log::info!(
+                "Discord hooks: User {} using tip command via role permissions, forwarding to agent",
+                user_name
+            );
+            return Ok(ProcessResult::forward_to_agent(ForwardRequest {
+                text: command_text,
+                user_id,
+                user_name,
+                is_admin: false,
+                allowed_tools: permissions.allowed_tools(),
+            }));
+        }
+
         match commands::parse(&command_text) {
             Some(cmd) => {
-                let response = commands::execute(cmd, &user_id, db).await?;
+                let response = commands::execute(cmd, &user_id, db, &permissions).await?;
                 Ok(ProcessResult::handled(response))
             }
             None => {
                 // Not a recognized limited command
-                Ok(ProcessResult::handled(commands::permission_denied_message()))
+                Ok(ProcessResult::handled(commands::permission_denied_message(&permissions)))
             }
         }
     }
@@ -381,45 +505,72 @@ mod tests {
     fn test_listening_for_query_state() {
         let user_id = "test_user_123";
 
+        let ttl = Some(Duration::from_secs(120));
+
         // Initially not listening
-        assert!(!is_listening_for_query(user_id));
+        assert!(!is_listening_for_query(user_id, ttl));
 
         // Set to listening
         set_listening_for_query(user_id, true);
-        assert!(is_listening_for_query(user_id));
+        assert!(is_listening_for_query(user_id, ttl));
 
         // Reset
         set_listening_for_query(user_id, false);
-        assert!(!is_listening_for_query(user_id));
+        assert!(!is_listening_for_query(user_id, ttl));
     }
 
     #[test]
     fn test_multiple_users_listening_state() {
         let user1 = "admin_1";
         let user2 = "admin_2";
+        let ttl = Some(Duration::from_secs(120));
 
         // Set user1 to listening
         set_listening_for_query(user1, true);
 
         // user2 should not be listening
-        assert!(is_listening_for_query(user1));
-        assert!(!is_listening_for_query(user2));
+        assert!(is_listening_for_query(user1, ttl));
+        assert!(!is_listening_for_query(user2, ttl));
 
         // Set user2 to listening
         set_listening_for_query(user2, true);
 
         // Both should be listening
-        assert!(is_listening_for_query(user1));
-        assert!(is_listening_for_query(user2));
+        assert!(is_listening_for_query(user1, ttl));
+        assert!(is_listening_for_query(user2, ttl));
 
         // Reset user1
         set_listening_for_query(user1, false);
 
         // Only user2 should be listening
-        assert!(!is_listening_for_query(user1));
-        assert!(is_listening_for_query(user2));
+        assert!(!is_listening_for_query(user1, ttl));
+        assert!(is_listening_for_query(user2, ttl));
 
         // Cleanup
         set_listening_for_query(user2, false);
     }
+
+    #[test]
+    fn test_query_mode_expires() {
+        let ttl = Some(Duration::from_secs(60));
+        let start = Instant::now();
+        let mut map = HashMap::new();
+        map.insert("stale".to_string(), start);
+        map.insert("fresh".to_string(), start + Duration::from_secs(51));
+        let now = start + Duration::from_secs(61);
+
+        // Stale entries are removed so the map doesn't grow unbounded
+        sweep_expired(&mut map, now, ttl);
+        assert!(!map.contains_key("stale"));
+        assert!(map.contains_key("fresh"));
+
+        // The fresh entry expires once the timeout passes
+        sweep_expired(&mut map, now + Duration::from_secs(50), ttl);
+        assert!(map.is_empty());
+
+        // No timeout keeps entries indefinitely
+        map.insert("admin".to_string(), start);
+        sweep_expired(&mut map, start + Duration::from_secs(86_400), None);
+        assert!(map.contains_key("admin"));
+    }
 }
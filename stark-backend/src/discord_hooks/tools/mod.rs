@@ -3,3 +3,4 @@
 mod resolve_user;
 
 pub use resolve_user::DiscordResolveUserTool;
+pub(crate) use resolve_user::extract_user_id;
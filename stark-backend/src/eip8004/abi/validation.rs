@@ -0,0 +1,88 @@
+//! Validation Registry ABI encoding
+
+use super::common::*;
+
+pub const VALIDATION_REQUEST_SIGNATURE: &str = "validationRequest(address,uint256,string,bytes32)";
+pub const VALIDATION_RESPONSE_SIGNATURE: &str = "validationResponse(bytes32,uint8,string,bytes32,string)";
+
+/// Encode validationRequest call
+/// validationRequest(address validatorAddress, uint256 agentId, string requestURI, bytes32 requestHash)
+pub fn encode_validation_request(
+    validator_address: &str,
+    agent_id: u64,
+    request_uri: &str,
+    request_hash: [u8; 32],
+) -> Vec<u8> {
+    let mut calldata = Vec::new();
+
+    calldata.extend_from_slice(&function_selector(VALIDATION_REQUEST_SIGNATURE));
+    calldata.extend(encode_address(validator_address));
+    calldata.extend(encode_uint256(agent_id));
+
+    // Fixed params: validator(32) + agentId(32) + uri_offset(32) + hash(32) = 128
+    calldata.extend(encode_uint256(128));
+    calldata.extend(encode_bytes32(&request_hash));
+
+    calldata.extend(encode_string(request_uri));
+
+    calldata
+}
+
+/// Encode validationResponse call
+/// validationResponse(bytes32 requestHash, uint8 response, string responseURI,
+///                    bytes32 responseHash, string tag)
+pub fn encode_validation_response(
+    request_hash: [u8; 32],
+    response: u8,
+    response_uri: &str,
+    response_hash: [u8; 32],
+    tag: &str,
+) -> Vec<u8> {
+    let mut calldata = Vec::new();
+
+    calldata.extend_from_slice(&function_selector(VALIDATION_RESPONSE_SIGNATURE));
+    calldata.extend(encode_bytes32(&request_hash));
+    calldata.extend(encode_uint256(response as u64));
+
+    // Fixed params: hash(32) + response(32) + uri_offset(32) + hash(32) + tag_offset(32) = 160
+    let base_offset = 160;
+    let uri_encoded = encode_string(response_uri);
+    let tag_encoded = encode_string(tag);
+
+    calldata.extend(encode_uint256(base_offset as u64));
+    calldata.extend(encode_bytes32(&response_hash));
+    calldata.extend(encode_uint256((base_offset + uri_encoded.len()) as u64));
+
+    calldata.extend(uri_encoded);
+    calldata.extend(tag_encoded);
+
+    calldata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_validation_request() {
+        let calldata = encode_validation_request(
+            "0x1234567890abcdef1234567890abcdef12345678",
+            7,
+            "ipfs://QmRequest",
+            [1u8; 32],
+        );
+        assert!(calldata.starts_with(&function_selector(VALIDATION_REQUEST_SIGNATURE)));
+        // selector + 4 head words + string length + 1 padded data word
+        assert_eq!(calldata.len(), 4 + 32 * 4 + 32 + 32);
+        assert_eq!(decode_uint256(&calldata[4 + 64..4 + 96]), 128);
+    }
+
+    #[test]
+    fn test_encode_validation_response() {
+        let calldata = encode_validation_response([2u8; 32], 85, "ipfs://QmResponse", [3u8; 32], "");
+        assert!(calldata.starts_with(&function_selector(VALIDATION_RESPONSE_SIGNATURE)));
+        assert_eq!(decode_uint256(&calldata[4 + 32..4 + 64]), 85);
+        // tag offset points past the 160-byte head and the 64-byte uri
+        assert_eq!(decode_uint256(&calldata[4 + 128..4 + 160]), 224);
+    }
+}
@@ -0,0 +1,164 @@
+//! Agent Card
+//!
+//! Builds our own EIP-8004 registration file ("agent card") from local state.
+//! The output is deterministic so the `registration_hash` recorded at
+//! registration time stays valid for as long as the configuration is unchanged.
+
+use super::config::Eip8004Config;
+use super::types::*;
+
+/// Inputs used to build our agent card
+#[derive(Debug, Clone, Default)]
+pub struct AgentCardInputs {
+    pub name: String,
+    pub description: String,
+    pub image: Option<String>,
+    /// Our on-chain registration, if we have one (agent_id, agent_registry)
+    pub registration: Option<(u64, String)>,
+    /// Burner wallet address that receives x402 payments
+    pub wallet_address: Option<String>,
+    pub skills: Vec<String>,
+    pub tools: Vec<String>,
+}
+
+/// Build the agent card for the given inputs and registry configuration
+pub fn build_agent_card(inputs: &AgentCardInputs, config: &Eip8004Config) -> RegistrationFile {
+    let mut card = RegistrationFile::new(&inputs.name, &inputs.description);
+    card.image = inputs.image.clone();
+
+    if let Some(ref base_url) = config.public_url {
+        card.services.push(ServiceEntry {
+            name: "web".to_string(),
+            endpoint: base_url.clone(),
+            version: "1.0".to_string(),
+        });
+        card.services.push(ServiceEntry {
+            name: "x402".to_string(),
+            endpoint: base_url.clone(),
+            version: "1.0".to_string(),
+        });
+    }
+
+    if let Some(ref wallet) = inputs.wallet_address {
+        // CAIP-10 account identifier
+        card.services.push(ServiceEntry {
+            name: "agentWallet".to_string(),
+            endpoint: format!("eip155:{}:{}", config.chain_id, wallet.to_lowercase()),
+            version: "1.0".to_string(),
+        });
+
+        if let Some(ref base_url) = config.public_url {
+            card.x402_services.push(X402ServiceDescriptor {
+                name: "x402".to_string(),
+                endpoint: base_url.clone(),
+                network: network_name(config.chain_id).to_string(),
+                asset: crate::x402::USDC_ADDRESS.to_string(),
+                pay_to: wallet.to_lowercase(),
+                scheme: "exact".to_string(),
+            });
+        }
+    }
+
+    // Only advertise x402 support when someone could actually pay us
+    card.x402_support = !card.x402_services.is_empty();
+
+    if let Some((agent_id, ref agent_registry)) = inputs.registration {
+        card.registrations = Some(vec![RegistrationEntry {
+            agent_id,
+            agent_registry: agent_registry.clone(),
+        }]);
+    }
+
+    card.supported_trust = vec!["reputation".to_string()];
+    if config.is_validation_deployed() {
+        card.supported_trust.push("validation".to_string());
+    }
+    if card.x402_support {
+        card.supported_trust.push("x402-payments".to_string());
+    }
+
+    card.skills = sorted_unique(&inputs.skills);
+    card.tools = sorted_unique(&inputs.tools);
+
+    card
+}
+
+/// Compute the keccak256 hash of an agent card's canonical JSON (0x-prefixed)
+pub fn card_hash(card: &RegistrationFile) -> Result<String, String> {
+    let bytes = serde_json::to_vec(card).map_err(|e| format!("Failed to serialize card: {}", e))?;
+    Ok(format!("0x{}", hex::encode(ethers::utils::keccak256(bytes))))
+}
+
+fn network_name(chain_id: u64) -> &'static str {
+    match chain_id {
+        84532 => "base-sepolia",
+        _ => "base",
+    }
+}
+
+fn sorted_unique(items: &[String]) -> Vec<String> {
+    let mut out = items.to_vec();
+    out.sort();
+    out.dedup();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> Eip8004Config {
+        let mut config = Eip8004Config::base_mainnet();
+        config.public_url = Some("https://bot.example.com".to_string());
+        config
+    }
+
+    fn test_inputs() -> AgentCardInputs {
+        AgentCardInputs {
+            name: "StarkBot".to_string(),
+            description: "Test agent".to_string(),
+            image: None,
+            registration: Some((42, "eip155:8453:0xabc".to_string())),
+            wallet_address: Some("0xAbC0000000000000000000000000000000000001".to_string()),
+            skills: vec!["swap".to_string(), "bridge".to_string()],
+            tools: vec!["web_fetch".to_string(), "token_lookup".to_string(), "web_fetch".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_card_is_stable() {
+        let config = test_config();
+        let mut shuffled = test_inputs();
+        shuffled.skills.reverse();
+        shuffled.tools.reverse();
+
+        let a = build_agent_card(&test_inputs(), &config);
+        let b = build_agent_card(&shuffled, &config);
+
+        assert_eq!(card_hash(&a).unwrap(), card_hash(&b).unwrap());
+        assert_eq!(a.skills, vec!["bridge", "swap"]);
+        assert_eq!(a.tools, vec!["token_lookup", "web_fetch"]);
+    }
+
+    #[test]
+    fn test_card_x402_descriptors() {
+        let card = build_agent_card(&test_inputs(), &test_config());
+
+        assert!(card.x402_support);
+        assert_eq!(card.x402_services.len(), 1);
+        assert_eq!(card.x402_services[0].pay_to, "0xabc0000000000000000000000000000000000001");
+        assert!(card.services.iter().any(|s| s.name == "agentWallet"
+            && s.endpoint == "eip155:8453:0xabc0000000000000000000000000000000000001"));
+        assert!(card.supported_trust.contains(&"x402-payments".to_string()));
+        assert_eq!(card.registrations.unwrap()[0].agent_id, 42);
+    }
+
+    #[test]
+    fn test_card_without_public_url() {
+        let card = build_agent_card(&test_inputs(), &Eip8004Config::base_mainnet());
+
+        assert!(!card.x402_support);
+        assert!(card.x402_services.is_empty());
+        assert!(!card.supported_trust.contains(&"x402-payments".to_string()));
+    }
+}
@@ -21,6 +21,8 @@ pub struct Eip8004Config {
     pub rpc_endpoint: String,
     /// Block explorer URL
     pub explorer_url: String,
+    /// Public base URL where this agent is reachable (used in the agent card)
+    pub public_url: Option<String>,
 }
 
 impl Eip8004Config {
@@ -37,6 +39,7 @@ impl Eip8004Config {
             chain_name: "Base".to_string(),
             rpc_endpoint: "https://rpc.defirelay.com/rpc/light/base".to_string(),
             explorer_url: "https://basescan.org".to_string(),
+            public_url: None,
         }
     }
 
@@ -51,6 +54,7 @@ impl Eip8004Config {
             chain_name: "Base Sepolia".to_string(),
             rpc_endpoint: "https://sepolia.base.org".to_string(),
             explorer_url: "https://sepolia.basescan.org".to_string(),
+            public_url: None,
         }
     }
 
@@ -61,7 +65,7 @@ impl Eip8004Config {
             .and_then(|s| s.parse().ok())
             .unwrap_or(8453);
 
-        match chain_id {
+        let mut config = match chain_id {
             84532 => Self::base_sepolia(),
             _ => {
                 let mut config = Self::base_mainnet();
@@ -82,7 +86,14 @@ impl Eip8004Config {
 
                 config
             }
-        }
+        };
+
+        config.public_url = std::env::var("EIP8004_PUBLIC_URL")
+            .ok()
+            .map(|url| url.trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty());
+
+        config
     }
 
     /// Check if contracts are deployed (not zero address)
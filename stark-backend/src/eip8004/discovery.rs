@@ -6,6 +6,9 @@ use super::config::Eip8004Config;
 use super::identity::IdentityRegistry;
 use super::reputation::ReputationRegistry;
 use super::types::*;
+use crate::db::tables::known_agents::KnownAgent;
+use crate::db::Database;
+use serde::Serialize;
 use std::collections::HashMap;
 
 /// Agent discovery and indexing
@@ -181,6 +184,160 @@ impl AgentDiscovery {
         self.cache.remove(&cache_key);
         self.discover_agent(agent_id).await
     }
+
+    /// Sync agents from the registry into the `known_agents` table.
+    ///
+    /// Each agent's registration card is fetched fresh. Agents whose card is
+    /// missing, unreachable, or malformed are marked inactive instead of
+    /// aborting the whole sync.
+    pub async fn sync_known_agents(
+        &mut self,
+        db: &Database,
+        offset: u64,
+        limit: u64,
+    ) -> Result<SyncReport, String> {
+        let total = self.total_agents().await?;
+        let mut report = SyncReport {
+            total_registered: total,
+            ..Default::default()
+        };
+
+        if offset >= total {
+            return Ok(report);
+        }
+
+        let end = (offset + limit).min(total);
+        let registry = self.config.agent_registry_string();
+
+        for agent_id in (offset + 1)..=end {
+            report.scanned += 1;
+
+            let owner = match self.identity.get_owner(agent_id).await {
+                Ok(owner) => owner,
+                Err(e) => {
+                    log::warn!("[EIP8004] Sync: failed to read owner of agent {}: {}", agent_id, e);
+                    report.failed += 1;
+                    continue;
+                }
+            };
+
+            let uri = self.identity.get_agent_uri(agent_id).await.ok();
+            let registration = match uri.as_deref() {
+                Some(u) if !u.is_empty() => match self.identity.fetch_registration(u).await {
+                    Ok(reg) => Some(reg),
+                    Err(e) => {
+                        log::warn!("[EIP8004] Sync: agent {} card unusable ({}): {}", agent_id, u, e);
+                        None
+                    }
+                },
+                _ => None,
+            };
+
+            let Some(registration) = registration else {
+                if let Err(e) = db.mark_known_agent_inactive(
+                    agent_id,
+                    &registry,
+                    self.config.chain_id,
+                    Some(&owner),
+                    uri.as_deref(),
+                ) {
+                    log::error!("[EIP8004] Sync: failed to mark agent {} inactive: {}", agent_id, e);
+                    report.failed += 1;
+                } else {
+                    report.inactive += 1;
+                }
+                continue;
+            };
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let mut agent = DiscoveredAgent {
+                identifier: self.identity.create_identifier(agent_id),
+                registration: Some(registration),
+                owner_address: owner,
+                wallet_address: self.identity.get_agent_wallet(agent_id).await.ok(),
+                reputation: None,
+                discovered_at: now.clone(),
+                last_updated: now,
+            };
+
+            if self.config.is_reputation_deployed() {
+                agent.reputation = self.reputation.get_summary(agent_id, &[], "", "").await.ok();
+            }
+
+            let known = to_known_agent(&agent, self.config.chain_id, uri);
+            match db.upsert_known_agent(&known) {
+                Ok(()) => {
+                    if known.x402_support && known.is_active {
+                        report.x402_agents += 1;
+                    }
+                    report.synced += 1;
+                }
+                Err(e) => {
+                    log::error!("[EIP8004] Sync: failed to upsert agent {}: {}", agent_id, e);
+                    report.failed += 1;
+                }
+            }
+
+            self.cache.insert((agent_id, registry.clone()), agent);
+        }
+
+        log::info!(
+            "[EIP8004] Synced {} agents ({} x402, {} inactive, {} failed)",
+            report.synced, report.x402_agents, report.inactive, report.failed
+        );
+
+        Ok(report)
+    }
+}
+
+/// Summary of a `known_agents` sync run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Total agents registered on-chain
+    pub total_registered: u64,
+    /// Agents examined in this run
+    pub scanned: u64,
+    /// Agents upserted with a valid registration card
+    pub synced: u64,
+    /// Synced agents that are active and x402-capable
+    pub x402_agents: u64,
+    /// Agents marked inactive (missing, unreachable, or malformed card)
+    pub inactive: u64,
+    /// Agents that could not be read or written at all
+    pub failed: u64,
+}
+
+/// Convert a discovered agent into a `known_agents` row
+pub fn to_known_agent(agent: &DiscoveredAgent, chain_id: u64, registration_uri: Option<String>) -> KnownAgent {
+    let reg = agent.registration.as_ref();
+
+    KnownAgent {
+        id: None,
+        agent_id: agent.identifier.agent_id,
+        agent_registry: agent.identifier.agent_registry.clone(),
+        chain_id,
+        name: reg.map(|r| r.name.clone()),
+        description: reg.map(|r| r.description.clone()),
+        image_url: reg.and_then(|r| r.image.clone()),
+        registration_uri,
+        owner_address: Some(agent.owner_address.clone()),
+        wallet_address: agent.wallet_address.clone(),
+        x402_support: agent.is_x402_enabled(),
+        services: reg
+            .map(|r| {
+                r.services
+                    .iter()
+                    .filter_map(|s| serde_json::to_value(s).ok())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        supported_trust: reg.map(|r| r.supported_trust.clone()).unwrap_or_default(),
+        is_active: agent.is_active(),
+        reputation_score: agent.reputation.as_ref().map(|r| r.average_score.round() as i64),
+        reputation_count: agent.reputation.as_ref().map(|r| r.count).unwrap_or(0),
+        discovered_at: Some(agent.discovered_at.clone()),
+        updated_at: Some(agent.last_updated.clone()),
+    }
 }
 
 /// Search criteria for agent discovery
@@ -453,4 +610,30 @@ mod tests {
         let by_service = index.by_service("swap");
         assert_eq!(by_service.len(), 1);
     }
+
+    #[test]
+    fn test_to_known_agent() {
+        let mut registration = RegistrationFile::new("JokeBot", "Dad jokes")
+            .with_service("x402", "https://jokes.example.com", "1.0");
+        registration.x402_support = false;
+
+        let agent = DiscoveredAgent {
+            identifier: AgentIdentifier::new(7, 8453, "0x1234"),
+            registration: Some(registration),
+            owner_address: "0x5678".to_string(),
+            wallet_address: Some("0x9abc".to_string()),
+            reputation: None,
+            discovered_at: "2024-01-01".to_string(),
+            last_updated: "2024-01-01".to_string(),
+        };
+
+        let known = to_known_agent(&agent, 8453, Some("ipfs://QmCard".to_string()));
+        assert_eq!(known.agent_id, 7);
+        assert_eq!(known.name.as_deref(), Some("JokeBot"));
+        // An explicit x402 service counts as x402 support even without the flag
+        assert!(known.x402_support);
+        assert!(known.is_active);
+        assert_eq!(known.wallet_address.as_deref(), Some("0x9abc"));
+        assert_eq!(known.service_endpoint("x402").as_deref(), Some("https://jokes.example.com"));
+    }
 }
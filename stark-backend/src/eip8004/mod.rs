@@ -13,6 +13,7 @@ pub mod identity;
 pub mod reputation;
 pub mod discovery;
 pub mod config;
+pub mod agent_card;
 
 pub use types::*;
 pub use config::Eip8004Config;
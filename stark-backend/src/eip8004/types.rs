@@ -73,6 +73,18 @@ pub struct RegistrationFile {
 
     #[serde(rename = "supportedTrust", default)]
     pub supported_trust: Vec<String>,
+
+    /// Paid endpoints with their x402 payment terms
+    #[serde(rename = "x402Services", default, skip_serializing_if = "Vec::is_empty")]
+    pub x402_services: Vec<X402ServiceDescriptor>,
+
+    /// Skills the agent offers (informational)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+
+    /// Tools the agent can use (informational)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
 }
 
 fn default_true() -> bool {
@@ -91,6 +103,9 @@ impl RegistrationFile {
             active: true,
             registrations: None,
             supported_trust: vec!["reputation".to_string(), "x402-payments".to_string()],
+            x402_services: Vec::new(),
+            skills: Vec::new(),
+            tools: Vec::new(),
         }
     }
 
@@ -117,6 +132,21 @@ pub struct ServiceEntry {
     pub version: String,
 }
 
+/// x402 payment terms for a paid service endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct X402ServiceDescriptor {
+    pub name: String,
+    pub endpoint: String,
+    /// Payment network (e.g., "base")
+    pub network: String,
+    /// Payment asset contract address
+    pub asset: String,
+    /// Address that receives payments
+    #[serde(rename = "payTo")]
+    pub pay_to: String,
+    pub scheme: String,
+}
+
 /// Registration entry for cross-chain identity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistrationEntry {
@@ -259,10 +289,12 @@ pub struct DiscoveredAgent {
 }
 
 impl DiscoveredAgent {
+    /// An agent is x402-capable if its card sets `x402Support` or it
+    /// advertises an explicit "x402" service endpoint.
     pub fn is_x402_enabled(&self) -> bool {
         self.registration
             .as_ref()
-            .map(|r| r.x402_support)
+            .map(|r| r.x402_support || r.services.iter().any(|s| s.name == "x402"))
             .unwrap_or(false)
     }
 
@@ -12,7 +12,7 @@ mod pending_confirmation;
 mod process_manager;
 mod session_lanes;
 
-pub use tracker::ExecutionTracker;
+pub use tracker::{ExecutionSnapshot, ExecutionStats, ExecutionTracker};
 pub use pending_confirmation::{PendingConfirmation, PendingConfirmationManager};
 pub use process_manager::{ProcessInfo, ProcessManager, ProcessStatus};
 pub use session_lanes::{SessionLaneGuard, SessionLaneManager, SessionLaneStats};
@@ -9,6 +9,7 @@
 
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
+use crate::tools::redaction;
 use dashmap::DashMap;
 use std::collections::VecDeque;
 use std::path::PathBuf;
@@ -263,6 +264,7 @@ impl ProcessManager {
             if let Some(stdout) = stdout {
                 let mut reader = BufReader::new(stdout).lines();
                 while let Ok(Some(line)) = reader.next_line().await {
+                    let line = redaction::active().redact(&line);
                     // Buffer the line
                     if let Some(mut handle) = stdout_processes.get_mut(&stdout_process_id) {
                         if handle.stdout_buffer.len() >= MAX_OUTPUT_BUFFER {
@@ -288,6 +290,7 @@ impl ProcessManager {
             if let Some(stderr) = stderr {
                 let mut reader = BufReader::new(stderr).lines();
                 while let Ok(Some(line)) = reader.next_line().await {
+                    let line = redaction::active().redact(&line);
                     // Buffer the line
                     if let Some(mut handle) = stderr_processes.get_mut(&stderr_process_id) {
                         if handle.stderr_buffer.len() >= MAX_OUTPUT_BUFFER {
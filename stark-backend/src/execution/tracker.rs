@@ -2,9 +2,43 @@ use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
 use crate::models::{ExecutionTask, TaskMetrics, TaskStatus, TaskType};
 use dashmap::DashMap;
+use serde::Serialize;
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 
+/// Point-in-time view of a channel's execution, for clients that load
+/// mid-run and missed the events so far
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionSnapshot {
+    pub channel_id: i64,
+    /// Gateway event id the snapshot is current as of; reconnecting with it
+    /// as `last_event_id` replays everything that happened since
+    pub last_event_id: u64,
+    pub running: bool,
+    /// Root task of the running execution
+    pub execution: Option<ExecutionTask>,
+    /// Every other task in the execution, oldest first
+    pub tasks: Vec<ExecutionTask>,
+    /// Tool calls still in progress
+    pub active_tools: Vec<ExecutionTask>,
+    pub stats: ExecutionStats,
+    pub planner_tasks: Vec<crate::ai::multi_agent::types::PlannerTask>,
+}
+
+/// Task counts and totals for an execution snapshot
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExecutionStats {
+    pub total_tasks: usize,
+    pub pending: usize,
+    pub in_progress: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub tool_uses: u32,
+    pub tokens_used: u32,
+    /// Time since the execution started
+    pub elapsed_ms: Option<u64>,
+}
+
 /// Tracks execution progress for agent tasks
 ///
 /// This service manages the hierarchical task tree for execution tracking,
@@ -492,6 +526,16 @@ impl ExecutionTracker {
                     .unwrap_or(url);
                 (format!("Fetching {}", host), format!("Fetching {}", host))
             }
+            "webhook" => {
+                let url = args.get("url").and_then(|v| v.as_str()).unwrap_or("");
+                let host = url.split("://")
+                    .nth(1)
+                    .unwrap_or(url)
+                    .split('/')
+                    .next()
+                    .unwrap_or(url);
+                (format!("Sending webhook to {}", host), format!("Sending webhook to {}", host))
+            }
             // Shell/exec operations
             "exec" | "shell" | "bash" => {
                 let cmd = args.get("command")
@@ -792,6 +836,63 @@ impl ExecutionTracker {
             .map(|entry| entry.value().clone())
             .collect()
     }
+
+    /// Snapshot of a channel's current execution: the task tree, tools still
+    /// running and task stats. Empty when nothing is running.
+    pub fn snapshot(&self, channel_id: i64) -> ExecutionSnapshot {
+        // Read the event id first so replaying from it can't miss anything
+        let last_event_id = self.broadcaster.last_event_id();
+        let execution_id = self.get_execution_id(channel_id);
+
+        let mut execution = None;
+        let mut tasks = Vec::new();
+        if let Some(ref execution_id) = execution_id {
+            for task in self.get_channel_tasks(channel_id) {
+                if &task.id == execution_id {
+                    execution = Some(task);
+                } else {
+                    tasks.push(task);
+                }
+            }
+        }
+        tasks.sort_by_key(|t| t.created_at);
+
+        let mut stats = ExecutionStats {
+            total_tasks: tasks.len(),
+            elapsed_ms: execution
+                .as_ref()
+                .and_then(|e| e.started_at)
+                .map(|started| (chrono::Utc::now() - started).num_milliseconds().max(0) as u64),
+            ..Default::default()
+        };
+        for task in &tasks {
+            match task.status {
+                TaskStatus::Pending => stats.pending += 1,
+                TaskStatus::InProgress => stats.in_progress += 1,
+                TaskStatus::Completed => stats.completed += 1,
+                TaskStatus::Error(_) => stats.failed += 1,
+            }
+            stats.tool_uses += task.metrics.tool_uses;
+            stats.tokens_used += task.metrics.tokens_used;
+        }
+
+        let active_tools = tasks
+            .iter()
+            .filter(|t| t.task_type == TaskType::ToolExecution && t.status == TaskStatus::InProgress)
+            .cloned()
+            .collect();
+
+        ExecutionSnapshot {
+            channel_id,
+            last_event_id,
+            running: execution_id.is_some(),
+            execution,
+            tasks,
+            active_tools,
+            stats,
+            planner_tasks: self.get_planner_tasks(channel_id),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -860,6 +961,37 @@ mod tests {
         assert!(task2.description.contains("example.com"));
     }
 
+    #[test]
+    fn test_snapshot() {
+        let tracker = create_test_tracker();
+        assert!(!tracker.snapshot(1).running);
+
+        let execution_id = tracker.start_execution(1, None, "execute", Some("Test execution"));
+        let done = tracker.start_tool(1, &execution_id, "read_file", &serde_json::json!({"path": "a.txt"}));
+        tracker.add_to_task_metrics(&done, 1, 50, 5);
+        tracker.complete_task(&done);
+        let running = tracker.start_tool(1, &execution_id, "exec", &serde_json::json!({"command": "sleep 5"}));
+
+        let snapshot = tracker.snapshot(1);
+        assert!(snapshot.running);
+        assert_eq!(snapshot.execution.as_ref().map(|e| e.id.as_str()), Some(execution_id.as_str()));
+        assert_eq!(snapshot.tasks.len(), 2);
+        assert_eq!(snapshot.active_tools.len(), 1);
+        assert_eq!(snapshot.active_tools[0].id, running);
+        assert_eq!(snapshot.stats.completed, 1);
+        assert_eq!(snapshot.stats.in_progress, 1);
+        assert_eq!(snapshot.stats.tokens_used, 50);
+        assert!(snapshot.last_event_id > 0);
+
+        // Other channels are unaffected
+        assert!(tracker.snapshot(2).tasks.is_empty());
+
+        tracker.complete_execution(1);
+        let snapshot = tracker.snapshot(1);
+        assert!(!snapshot.running);
+        assert!(snapshot.tasks.is_empty());
+    }
+
     #[test]
     fn test_tool_descriptions() {
         // Test that various tools get nice descriptions
@@ -23,17 +23,56 @@ const AUTH_TIMEOUT_SECS: u64 = 30;
 #[derive(Debug, Deserialize)]
 struct AuthParams {
     token: String,
+    /// Last event id seen before a reconnect; newer buffered events are replayed
+    #[serde(default)]
+    last_event_id: Option<u64>,
+}
+
+/// A client that passed authentication
+struct AuthenticatedClient {
+    last_event_id: Option<u64>,
+}
+
+/// Optional query parameters on the `/ws` URL. Passing the session token here
+/// authenticates during the handshake instead of with a first `auth` message.
+#[derive(Debug, Default, Deserialize)]
+pub struct ConnectQuery {
+    token: Option<String>,
+    last_event_id: Option<u64>,
 }
 
 /// WebSocket handler for Actix-Web
 pub async fn ws_handler(
     req: HttpRequest,
     stream: web::Payload,
+    query: web::Query<ConnectQuery>,
     db: web::Data<Arc<Database>>,
     channel_manager: web::Data<Arc<ChannelManager>>,
     broadcaster: web::Data<Arc<EventBroadcaster>>,
     tx_queue: web::Data<Arc<TxQueueManager>>,
 ) -> Result<HttpResponse, actix_web::Error> {
+    // Reject a bad token before upgrading, so the connection is never opened
+    let preauthenticated = match query.token.as_deref() {
+        Some(token) => match db.validate_session(token) {
+            Ok(Some(_)) => Some(AuthenticatedClient {
+                last_event_id: query.last_event_id,
+            }),
+            Ok(None) => {
+                log::warn!("Gateway connection rejected: invalid or expired token");
+                return Ok(HttpResponse::Unauthorized().json(serde_json::json!({
+                    "error": "Invalid or expired token"
+                })));
+            }
+            Err(e) => {
+                log::error!("Database error validating token: {}", e);
+                return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Database error"
+                })));
+            }
+        },
+        None => None,
+    };
+
     let (response, session, msg_stream) = actix_ws::handle(&req, stream)?;
 
     // Spawn the WebSocket handler task
@@ -45,6 +84,7 @@ pub async fn ws_handler(
     actix_web::rt::spawn(handle_ws_connection(
         session,
         msg_stream,
+        preauthenticated,
         db,
         channel_manager,
         broadcaster,
@@ -57,6 +97,7 @@ pub async fn ws_handler(
 async fn handle_ws_connection(
     mut session: actix_ws::Session,
     msg_stream: actix_ws::MessageStream,
+    preauthenticated: Option<AuthenticatedClient>,
     db: Arc<Database>,
     channel_manager: Arc<ChannelManager>,
     broadcaster: Arc<EventBroadcaster>,
@@ -69,15 +110,21 @@ async fn handle_ws_connection(
         .aggregate_continuations()
         .max_continuation_size(64 * 1024);
 
-    // Phase 1: Authentication required before full access
-    let authenticated = match tokio::time::timeout(
-        Duration::from_secs(AUTH_TIMEOUT_SECS),
-        wait_for_auth(&mut session, &mut msg_stream, &db),
-    )
-    .await
-    {
-        Ok(Ok(true)) => true,
-        Ok(Ok(false)) => {
+    // Phase 1: Authentication required before full access (unless the
+    // handshake already carried a valid token)
+    let auth_result = match preauthenticated {
+        Some(client) => Ok(Ok(Some(client))),
+        None => {
+            tokio::time::timeout(
+                Duration::from_secs(AUTH_TIMEOUT_SECS),
+                wait_for_auth(&mut session, &mut msg_stream, &db),
+            )
+            .await
+        }
+    };
+    let client = match auth_result {
+        Ok(Ok(Some(client))) => client,
+        Ok(Ok(None)) => {
             log::warn!("Gateway client failed authentication");
             let _ = session.close(None).await;
             return;
@@ -101,11 +148,6 @@ async fn handle_ws_connection(
         }
     };
 
-    if !authenticated {
-        let _ = session.close(None).await;
-        return;
-    }
-
     log::info!("Gateway client authenticated successfully");
 
     // Phase 2: Full access after authentication
@@ -117,6 +159,22 @@ async fn handle_ws_connection(
         broadcaster.client_count()
     );
 
+    // Events the client missed while disconnected. Subscribing first means none
+    // are lost in between; live duplicates of replayed events are skipped below.
+    let replay = client
+        .last_event_id
+        .map(|id| broadcaster.replay_since(id))
+        .unwrap_or_default();
+    let replayed_up_to = replay.last().and_then(|e| e.event_id).unwrap_or(0);
+    if !replay.is_empty() {
+        log::info!(
+            "Gateway client {} replaying {} missed event(s) since {}",
+            client_id,
+            replay.len(),
+            client.last_event_id.unwrap_or(0)
+        );
+    }
+
     // Create a channel for sending messages to the WebSocket
     let (tx, mut rx) = mpsc::channel::<String>(100);
 
@@ -126,6 +184,13 @@ async fn handle_ws_connection(
 
     // Task to forward messages to WebSocket
     let send_task = tokio::spawn(async move {
+        for json in replay.iter().filter_map(|event| serde_json::to_string(event).ok()) {
+            if send_session.text(json).await.is_err() {
+                log::warn!("[WEBSOCKET] Failed to replay events to client {}", client_id_clone);
+                return;
+            }
+        }
+
         loop {
             tokio::select! {
                 // Forward RPC responses
@@ -137,6 +202,9 @@ async fn handle_ws_connection(
                 }
                 // Forward events
                 Some(event) = event_rx.recv() => {
+                    if event.event_id.is_some_and(|id| id <= replayed_up_to) {
+                        continue;
+                    }
                     let event_name = event.event.clone();
                     if let Ok(json) = serde_json::to_string(&event) {
                         if event_name == "agent.tool_call" || event_name == "tool.result" {
@@ -159,7 +227,7 @@ async fn handle_ws_connection(
         match msg_result {
             Ok(AggregatedMessage::Text(text)) => {
                 log::debug!("[DATAGRAM] <<< FROM AGENT (RPC request):\n{}", text);
-                let response = process_request(&text, &db, &channel_manager, &broadcaster, &tx_queue).await;
+                let response = process_request(&text, &client_id, &db, &channel_manager, &broadcaster, &tx_queue).await;
                 if let Ok(json) = serde_json::to_string(&response) {
                     let _ = tx.send(json).await;
                 }
@@ -192,7 +260,7 @@ async fn wait_for_auth(
     session: &mut actix_ws::Session,
     msg_stream: &mut (impl StreamExt<Item = Result<AggregatedMessage, actix_ws::ProtocolError>> + Unpin),
     db: &Arc<Database>,
-) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<Option<AuthenticatedClient>, Box<dyn std::error::Error + Send + Sync>> {
     while let Some(msg_result) = msg_stream.next().await {
         match msg_result {
             Ok(AggregatedMessage::Text(text)) => {
@@ -241,7 +309,9 @@ async fn wait_for_auth(
                                 if let Ok(json) = serde_json::to_string(&response) {
                                     let _ = session.text(json).await;
                                 }
-                                return Ok(true);
+                                return Ok(Some(AuthenticatedClient {
+                                    last_event_id: params.last_event_id,
+                                }));
                             }
                             Ok(None) => {
                                 let response = RpcResponse::error(
@@ -251,7 +321,7 @@ async fn wait_for_auth(
                                 if let Ok(json) = serde_json::to_string(&response) {
                                     let _ = session.text(json).await;
                                 }
-                                return Ok(false);
+                                return Ok(None);
                             }
                             Err(e) => {
                                 log::error!("Database error validating token: {}", e);
@@ -262,7 +332,7 @@ async fn wait_for_auth(
                                 if let Ok(json) = serde_json::to_string(&response) {
                                     let _ = session.text(json).await;
                                 }
-                                return Ok(false);
+                                return Ok(None);
                             }
                         }
                     }
@@ -291,7 +361,7 @@ async fn wait_for_auth(
                 let _ = session.pong(&data).await;
             }
             Ok(AggregatedMessage::Close(_)) => {
-                return Ok(false);
+                return Ok(None);
             }
             Err(e) => {
                 log::error!("WebSocket error during auth: {:?}", e);
@@ -301,11 +371,12 @@ async fn wait_for_auth(
         }
     }
 
-    Ok(false)
+    Ok(None)
 }
 
 async fn process_request(
     text: &str,
+    client_id: &str,
     db: &Arc<Database>,
     channel_manager: &Arc<ChannelManager>,
     broadcaster: &Arc<EventBroadcaster>,
@@ -320,7 +391,7 @@ async fn process_request(
 
     let id = request.id.clone();
 
-    let result = dispatch_method(&request, db, channel_manager, broadcaster, tx_queue).await;
+    let result = dispatch_method(&request, client_id, db, channel_manager, broadcaster, tx_queue).await;
 
     match result {
         Ok(value) => RpcResponse::success(id, value),
@@ -330,6 +401,7 @@ async fn process_request(
 
 async fn dispatch_method(
     request: &RpcRequest,
+    client_id: &str,
     db: &Arc<Database>,
     channel_manager: &Arc<ChannelManager>,
     broadcaster: &Arc<EventBroadcaster>,
@@ -366,6 +438,117 @@ async fn dispatch_method(
                 .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
             methods::handle_tx_queue_deny(params, tx_queue.clone(), broadcaster.clone()).await
         }
+        "cancel_execution" => {
+            let params: methods::CancelExecutionParams = serde_json::from_value(request.params.clone())
+                .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
+            methods::handle_cancel_execution(params, channel_manager.execution_tracker()).await
+        }
+        "subscribe" => {
+            // No params resets to all channels
+            let params: methods::SubscribeParams = match request.params {
+                serde_json::Value::Null => methods::SubscribeParams::default(),
+                ref params => serde_json::from_value(params.clone())
+                    .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?,
+            };
+            methods::handle_subscribe(params, client_id, broadcaster.clone()).await
+        }
+        "unsubscribe" => {
+            let params: methods::SubscribeParams = serde_json::from_value(request.params.clone())
+                .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
+            methods::handle_unsubscribe(params, client_id, broadcaster.clone()).await
+        }
         _ => Err(RpcError::method_not_found()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test, App};
+
+    fn handshake(uri: &str) -> test::TestRequest {
+        test::TestRequest::get()
+            .uri(uri)
+            .insert_header(("connection", "upgrade"))
+            .insert_header(("upgrade", "websocket"))
+            .insert_header(("sec-websocket-version", "13"))
+            .insert_header(("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ=="))
+    }
+
+    /// A client text frame, masked with a zero key so the payload stays readable
+    fn client_text_frame(text: &str) -> web::Bytes {
+        assert!(text.len() < 126);
+        let mut frame = vec![0x81, 0x80 | text.len() as u8, 0, 0, 0, 0];
+        frame.extend_from_slice(text.as_bytes());
+        web::Bytes::from(frame)
+    }
+
+    #[actix_web::test]
+    async fn test_handshake_token_is_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let broadcaster = Arc::new(EventBroadcaster::with_replay_capacity(0));
+        let channel_manager = Arc::new(ChannelManager::new(db.clone(), broadcaster.clone()));
+        let token = db.create_session().unwrap().token;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(db.clone()))
+                .app_data(web::Data::new(channel_manager))
+                .app_data(web::Data::new(broadcaster))
+                .app_data(web::Data::new(Arc::new(TxQueueManager::new())))
+                .route("/ws", web::get().to(ws_handler)),
+        )
+        .await;
+
+        // An invalid token is rejected before the upgrade
+        let resp = test::call_service(&app, handshake("/ws?token=not-a-session").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        // A valid token upgrades straight away
+        let resp = test::call_service(&app, handshake(&format!("/ws?token={}", token)).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::SWITCHING_PROTOCOLS);
+    }
+
+    #[actix_web::test]
+    async fn test_unauthenticated_connection_is_closed() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let broadcaster = Arc::new(EventBroadcaster::with_replay_capacity(0));
+        let channel_manager = Arc::new(ChannelManager::new(db.clone(), broadcaster.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(db.clone()))
+                .app_data(web::Data::new(channel_manager))
+                .app_data(web::Data::new(broadcaster.clone()))
+                .app_data(web::Data::new(Arc::new(TxQueueManager::new())))
+                .route("/ws", web::get().to(ws_handler)),
+        )
+        .await;
+
+        // Without a token the upgrade succeeds, but a bad `auth` message closes it
+        let auth = r#"{"id":"1","method":"auth","params":{"token":"not-a-session"}}"#;
+        let resp = test::call_service(
+            &app,
+            handshake("/ws").set_payload(client_text_frame(auth)).to_request(),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::SWITCHING_PROTOCOLS);
+
+        let body = test::read_body(resp).await;
+        let text = String::from_utf8_lossy(&body);
+        assert!(text.contains("Invalid or expired token"));
+        // The last frame sent is a close frame
+        let close = body.iter().rposition(|b| *b == 0x88).expect("no close frame");
+        assert!(close > text.find("Invalid or expired token").unwrap());
+        assert_eq!(broadcaster.client_count(), 0);
+
+        // A client that never authenticates is closed too
+        let resp = test::call_service(&app, handshake("/ws").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::SWITCHING_PROTOCOLS);
+        let body = test::read_body(resp).await;
+        assert_eq!(body.first(), Some(&0x88));
+        assert_eq!(broadcaster.client_count(), 0);
+    }
+}
@@ -1,17 +1,110 @@
 use crate::gateway::protocol::GatewayEvent;
 use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
+/// Which channels' events a client receives. Events without a `channel_id`
+/// are global and always delivered.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(tag = "mode", content = "channel_ids", rename_all = "snake_case")]
+pub enum ChannelFilter {
+    /// Every channel (the default)
+    #[default]
+    All,
+    /// Only these channels
+    Only(BTreeSet<i64>),
+    /// Every channel except these
+    Except(BTreeSet<i64>),
+}
+
+impl ChannelFilter {
+    pub fn allows(&self, channel_id: Option<i64>) -> bool {
+        match (self, channel_id) {
+            (_, None) | (ChannelFilter::All, _) => true,
+            (ChannelFilter::Only(ids), Some(id)) => ids.contains(&id),
+            (ChannelFilter::Except(ids), Some(id)) => !ids.contains(&id),
+        }
+    }
+
+    /// Start receiving `channel_ids`. From `All` this narrows to just those
+    /// channels; an empty list resets to `All`.
+    pub fn subscribe(self, channel_ids: &[i64]) -> Self {
+        if channel_ids.is_empty() {
+            return ChannelFilter::All;
+        }
+        match self {
+            ChannelFilter::All => ChannelFilter::Only(channel_ids.iter().copied().collect()),
+            ChannelFilter::Only(mut ids) => {
+                ids.extend(channel_ids);
+                ChannelFilter::Only(ids)
+            }
+            ChannelFilter::Except(mut ids) => {
+                channel_ids.iter().for_each(|id| {
+                    ids.remove(id);
+                });
+                if ids.is_empty() {
+                    ChannelFilter::All
+                } else {
+                    ChannelFilter::Except(ids)
+                }
+            }
+        }
+    }
+
+    /// Stop receiving `channel_ids`
+    pub fn unsubscribe(self, channel_ids: &[i64]) -> Self {
+        match self {
+            ChannelFilter::All if channel_ids.is_empty() => ChannelFilter::All,
+            ChannelFilter::All => ChannelFilter::Except(channel_ids.iter().copied().collect()),
+            ChannelFilter::Only(mut ids) => {
+                channel_ids.iter().for_each(|id| {
+                    ids.remove(id);
+                });
+                ChannelFilter::Only(ids)
+            }
+            ChannelFilter::Except(mut ids) => {
+                ids.extend(channel_ids);
+                ChannelFilter::Except(ids)
+            }
+        }
+    }
+}
+
+/// The channel an event belongs to, from its `data.channel_id`
+fn event_channel_id(event: &GatewayEvent) -> Option<i64> {
+    event.data.get("channel_id").and_then(|v| v.as_i64())
+}
+
 /// Broadcasts events to all connected WebSocket clients
 pub struct EventBroadcaster {
     clients: DashMap<String, mpsc::Sender<GatewayEvent>>,
+    /// Per-client channel filters; clients without an entry get every channel
+    filters: DashMap<String, ChannelFilter>,
+    /// Source of monotonic event ids (the first event gets id 1)
+    next_event_id: AtomicU64,
+    /// Recent events per channel (keyed by `data.channel_id`, `None` for global events)
+    replay: Mutex<HashMap<Option<i64>, VecDeque<GatewayEvent>>>,
+    /// Events kept per channel; 0 disables replay
+    replay_capacity: usize,
 }
 
 impl EventBroadcaster {
     pub fn new() -> Self {
+        Self::with_replay_capacity(crate::config::gateway_replay_buffer_size())
+    }
+
+    /// Create a broadcaster that keeps the last `replay_capacity` events per channel
+    pub fn with_replay_capacity(replay_capacity: usize) -> Self {
         Self {
             clients: DashMap::new(),
+            filters: DashMap::new(),
+            next_event_id: AtomicU64::new(1),
+            replay: Mutex::new(HashMap::new()),
+            replay_capacity,
         }
     }
 
@@ -27,11 +120,16 @@ impl EventBroadcaster {
     /// Unsubscribe a client
     pub fn unsubscribe(&self, client_id: &str) {
         self.clients.remove(client_id);
+        self.filters.remove(client_id);
         log::debug!("Client {} unsubscribed from events", client_id);
     }
 
     /// Broadcast an event to all connected clients
-    pub fn broadcast(&self, event: GatewayEvent) {
+    pub fn broadcast(&self, mut event: GatewayEvent) {
+        event.event_id = Some(self.next_event_id.fetch_add(1, Ordering::Relaxed));
+        self.record(&event);
+        crate::metrics::record_event(&event);
+
         let event_name = event.event.clone();
         let mut failed_clients = Vec::new();
 
@@ -54,10 +152,15 @@ impl EventBroadcaster {
             );
         }
 
+        let channel_id = event_channel_id(&event);
         for entry in self.clients.iter() {
             let client_id = entry.key().clone();
             let sender = entry.value();
 
+            if !self.filters.get(&client_id).is_none_or(|f| f.allows(channel_id)) {
+                continue;
+            }
+
             if sender.try_send(event.clone()).is_err() {
                 // Client channel full or closed
                 failed_clients.push(client_id);
@@ -67,10 +170,56 @@ impl EventBroadcaster {
         // Clean up failed clients
         for client_id in failed_clients {
             self.clients.remove(&client_id);
+            self.filters.remove(&client_id);
             log::debug!("Removed disconnected client {}", client_id);
         }
     }
 
+    /// Keep an event in its channel's replay buffer
+    fn record(&self, event: &GatewayEvent) {
+        if self.replay_capacity == 0 {
+            return;
+        }
+        let channel_id = event_channel_id(event);
+        if let Ok(mut replay) = self.replay.lock() {
+            let buffer = replay.entry(channel_id).or_default();
+            if buffer.len() >= self.replay_capacity {
+                buffer.pop_front();
+            }
+            buffer.push_back(event.clone());
+        }
+    }
+
+    /// Id of the most recently broadcast event (0 before the first)
+    pub fn last_event_id(&self) -> u64 {
+        self.next_event_id.load(Ordering::Relaxed) - 1
+    }
+
+    /// Buffered events with an id greater than `last_event_id`, oldest first
+    pub fn replay_since(&self, last_event_id: u64) -> Vec<GatewayEvent> {
+        let Ok(replay) = self.replay.lock() else {
+            return Vec::new();
+        };
+        let mut events: Vec<GatewayEvent> = replay
+            .values()
+            .flat_map(|buffer| buffer.iter())
+            .filter(|e| e.event_id.is_some_and(|id| id > last_event_id))
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| e.event_id);
+        events
+    }
+
+    /// Update a client's channel filter and return the new one
+    pub fn update_filter(&self, client_id: &str, update: impl FnOnce(ChannelFilter) -> ChannelFilter) -> ChannelFilter {
+        let current = self.filters.remove(client_id).map(|(_, f)| f).unwrap_or_default();
+        let updated = update(current);
+        if updated != ChannelFilter::All {
+            self.filters.insert(client_id.to_string(), updated.clone());
+        }
+        updated
+    }
+
     /// Get the number of connected clients
     pub fn client_count(&self) -> usize {
         self.clients.len()
@@ -82,3 +231,101 @@ impl Default for EventBroadcaster {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn replayed(broadcaster: &EventBroadcaster, since: u64) -> Vec<(u64, String)> {
+        broadcaster
+            .replay_since(since)
+            .into_iter()
+            .map(|e| (e.event_id.unwrap(), e.event))
+            .collect()
+    }
+
+    #[test]
+    fn test_events_get_monotonic_ids() {
+        let broadcaster = EventBroadcaster::with_replay_capacity(10);
+        let (_id, mut rx) = broadcaster.subscribe();
+
+        broadcaster.broadcast(GatewayEvent::custom("a", json!({})));
+        broadcaster.broadcast(GatewayEvent::custom("b", json!({})));
+
+        assert_eq!(rx.try_recv().unwrap().event_id, Some(1));
+        assert_eq!(rx.try_recv().unwrap().event_id, Some(2));
+    }
+
+    #[test]
+    fn test_replay_across_channels_in_order() {
+        let broadcaster = EventBroadcaster::with_replay_capacity(10);
+        broadcaster.broadcast(GatewayEvent::custom("one", json!({"channel_id": 1})));
+        broadcaster.broadcast(GatewayEvent::custom("global", json!({})));
+        broadcaster.broadcast(GatewayEvent::custom("two", json!({"channel_id": 2})));
+        broadcaster.broadcast(GatewayEvent::custom("one-again", json!({"channel_id": 1})));
+
+        assert_eq!(
+            replayed(&broadcaster, 1),
+            vec![
+                (2, "global".to_string()),
+                (3, "two".to_string()),
+                (4, "one-again".to_string())
+            ]
+        );
+        assert!(replayed(&broadcaster, 4).is_empty());
+    }
+
+    #[test]
+    fn test_replay_buffer_is_bounded_per_channel() {
+        let broadcaster = EventBroadcaster::with_replay_capacity(2);
+        for i in 0..5 {
+            broadcaster.broadcast(GatewayEvent::custom(&format!("busy-{}", i), json!({"channel_id": 1})));
+        }
+        broadcaster.broadcast(GatewayEvent::custom("quiet", json!({"channel_id": 2})));
+
+        assert_eq!(
+            replayed(&broadcaster, 0),
+            vec![
+                (4, "busy-3".to_string()),
+                (5, "busy-4".to_string()),
+                (6, "quiet".to_string())
+            ]
+        );
+
+        let disabled = EventBroadcaster::with_replay_capacity(0);
+        disabled.broadcast(GatewayEvent::custom("a", json!({})));
+        assert!(disabled.replay_since(0).is_empty());
+    }
+
+    #[test]
+    fn test_channel_filter_transitions() {
+        let only_one = ChannelFilter::All.subscribe(&[1]);
+        assert!(only_one.allows(Some(1)) && !only_one.allows(Some(2)) && only_one.allows(None));
+
+        let one_and_two = only_one.subscribe(&[2]);
+        assert!(one_and_two.allows(Some(2)));
+        assert_eq!(one_and_two.clone().unsubscribe(&[1, 2]), ChannelFilter::Only(BTreeSet::new()));
+        assert_eq!(one_and_two.subscribe(&[]), ChannelFilter::All);
+
+        let not_three = ChannelFilter::All.unsubscribe(&[3]);
+        assert!(!not_three.allows(Some(3)) && not_three.allows(Some(4)));
+        assert_eq!(not_three.subscribe(&[3]), ChannelFilter::All);
+    }
+
+    #[test]
+    fn test_broadcast_respects_client_filters() {
+        let broadcaster = EventBroadcaster::with_replay_capacity(0);
+        let (focused, mut focused_rx) = broadcaster.subscribe();
+        let (_everything, mut everything_rx) = broadcaster.subscribe();
+
+        broadcaster.update_filter(&focused, |f| f.subscribe(&[1]));
+        broadcaster.broadcast(GatewayEvent::custom("ch1", json!({"channel_id": 1})));
+        broadcaster.broadcast(GatewayEvent::custom("ch2", json!({"channel_id": 2})));
+        broadcaster.broadcast(GatewayEvent::custom("global", json!({})));
+
+        let focused_events: Vec<_> = std::iter::from_fn(|| focused_rx.try_recv().ok()).map(|e| e.event).collect();
+        assert_eq!(focused_events, vec!["ch1", "global"]);
+        assert_eq!(std::iter::from_fn(|| everything_rx.try_recv().ok()).count(), 3);
+    }
+}
@@ -17,7 +17,10 @@ pub async fn handle_channels_status(
         .into_iter()
         .map(|ch| {
             let running = channel_manager.is_running(ch.id);
-            ChannelResponse::from(ch).with_running(running)
+            let connection = channel_manager.connection(ch.id);
+            ChannelResponse::from(ch)
+                .with_running(running)
+                .with_connection(connection)
         })
         .collect();
 
@@ -36,7 +39,7 @@ pub async fn handle_channels_start(
         .ok_or_else(|| RpcError::invalid_params(format!("Channel {} not found", params.id)))?;
 
     // Start the channel
-    channel_manager
+    let status = channel_manager
         .start_channel(channel)
         .await
         .map_err(|e| RpcError::internal_error(e))?;
@@ -47,7 +50,8 @@ pub async fn handle_channels_start(
 
     Ok(serde_json::json!({
         "success": true,
-        "channel_id": params.id
+        "channel_id": params.id,
+        "status": status
     }))
 }
 
@@ -57,10 +61,7 @@ pub async fn handle_channels_stop(
     db: Arc<Database>,
 ) -> Result<Value, RpcError> {
     // Stop the channel
-    channel_manager
-        .stop_channel(params.id)
-        .await
-        .map_err(|e| RpcError::internal_error(e))?;
+    let status = channel_manager.stop_channel(params.id).await;
 
     // Update enabled status in database
     db.set_channel_enabled(params.id, false)
@@ -68,7 +69,8 @@ pub async fn handle_channels_stop(
 
     Ok(serde_json::json!({
         "success": true,
-        "channel_id": params.id
+        "channel_id": params.id,
+        "status": status
     }))
 }
 
@@ -77,23 +79,21 @@ pub async fn handle_channels_restart(
     db: Arc<Database>,
     channel_manager: Arc<ChannelManager>,
 ) -> Result<Value, RpcError> {
-    // Stop if running
-    let _ = channel_manager.stop_channel(params.id).await;
-
     // Get the channel from database
     let channel = db
         .get_channel(params.id)
         .map_err(|e| RpcError::internal_error(format!("Database error: {}", e)))?
         .ok_or_else(|| RpcError::invalid_params(format!("Channel {} not found", params.id)))?;
 
-    // Start the channel
-    channel_manager
-        .start_channel(channel)
+    // Stop if running, then start with the current config
+    let status = channel_manager
+        .restart_channel(channel)
         .await
         .map_err(|e| RpcError::internal_error(e))?;
 
     Ok(serde_json::json!({
         "success": true,
-        "channel_id": params.id
+        "channel_id": params.id,
+        "status": status
     }))
 }
@@ -0,0 +1,35 @@
+use crate::execution::ExecutionTracker;
+use crate::gateway::protocol::RpcError;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct CancelExecutionParams {
+    pub channel_id: i64,
+}
+
+/// Handle cancel_execution RPC method
+/// Cancels the channel's in-flight execution (and any session-based runs on
+/// it). The tool loop stops at its next checkpoint, running `exec` commands
+/// are killed, and `execution.stopped` is broadcast.
+pub async fn handle_cancel_execution(
+    params: CancelExecutionParams,
+    execution_tracker: Arc<ExecutionTracker>,
+) -> Result<Value, RpcError> {
+    let was_running = execution_tracker.get_execution_id(params.channel_id).is_some();
+    log::info!(
+        "[cancel_execution] Cancelling execution for channel {} (running: {})",
+        params.channel_id,
+        was_running
+    );
+
+    execution_tracker.cancel_execution(params.channel_id);
+    execution_tracker.cancel_all_sessions_for_channel(params.channel_id);
+
+    Ok(json!({
+        "success": true,
+        "channel_id": params.channel_id,
+        "was_running": was_running
+    }))
+}
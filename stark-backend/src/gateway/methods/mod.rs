@@ -1,7 +1,11 @@
 pub mod channels;
+pub mod execution;
 pub mod status;
+pub mod subscriptions;
 pub mod tx_queue;
 
 pub use channels::*;
+pub use execution::*;
 pub use status::*;
+pub use subscriptions::*;
 pub use tx_queue::*;
@@ -0,0 +1,36 @@
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::RpcError;
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Params for subscribe/unsubscribe
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SubscribeParams {
+    #[serde(default)]
+    pub channel_ids: Vec<i64>,
+}
+
+/// Receive events for `channel_ids` (narrowing from all channels on the first
+/// call). An empty list resets the client to all channels.
+pub async fn handle_subscribe(
+    params: SubscribeParams,
+    client_id: &str,
+    broadcaster: Arc<EventBroadcaster>,
+) -> Result<Value, RpcError> {
+    let filter = broadcaster.update_filter(client_id, |f| f.subscribe(&params.channel_ids));
+    serde_json::to_value(filter).map_err(|e| RpcError::internal_error(e.to_string()))
+}
+
+/// Stop receiving events for `channel_ids`
+pub async fn handle_unsubscribe(
+    params: SubscribeParams,
+    client_id: &str,
+    broadcaster: Arc<EventBroadcaster>,
+) -> Result<Value, RpcError> {
+    if params.channel_ids.is_empty() {
+        return Err(RpcError::invalid_params("channel_ids is required"));
+    }
+    let filter = broadcaster.update_filter(client_id, |f| f.unsubscribe(&params.channel_ids));
+    serde_json::to_value(filter).map_err(|e| RpcError::internal_error(e.to_string()))
+}
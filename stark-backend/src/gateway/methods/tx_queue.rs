@@ -5,7 +5,7 @@
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::{GatewayEvent, RpcError};
 use crate::tools::rpc_config::resolve_rpc_from_network;
-use crate::tx_queue::{QueuedTxStatus, TxQueueManager};
+use crate::tx_queue::{BroadcastClaim, TxQueueManager};
 use crate::x402::X402EvmRpc;
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -27,21 +27,23 @@ pub async fn handle_tx_queue_confirm(
 ) -> Result<Value, RpcError> {
     log::info!("[tx_queue.confirm] Confirming transaction {}", params.uuid);
 
-    // Get transaction
-    let tx = tx_queue.get(&params.uuid)
-        .ok_or_else(|| RpcError::new(-32000, format!("Transaction {} not found", params.uuid)))?;
-
-    // Validate pending status
-    if tx.status != QueuedTxStatus::Pending {
-        return Err(RpcError::new(-32000, format!("Transaction {} is not pending (status: {:?})", params.uuid, tx.status)));
-    }
-
-    // Mark broadcasting
-    tx_queue.mark_broadcasting(&params.uuid);
+    // Claim the transaction, so a double confirm can't submit it twice
+    let tx = match tx_queue.claim_for_broadcast(&params.uuid) {
+        BroadcastClaim::Claimed(tx) => tx,
+        BroadcastClaim::NotPending(tx) => {
+            return Err(RpcError::new(-32000, format!("Transaction {} is not pending (status: {:?})", params.uuid, tx.status)));
+        }
+        BroadcastClaim::NotFound => {
+            return Err(RpcError::new(-32000, format!("Transaction {} not found", params.uuid)));
+        }
+    };
 
     // Get private key
     let private_key = crate::config::burner_wallet_private_key()
-        .ok_or_else(|| RpcError::new(-32000, "Wallet not configured".to_string()))?;
+        .ok_or_else(|| {
+            tx_queue.mark_failed(&params.uuid, "Wallet not configured");
+            RpcError::new(-32000, "Wallet not configured".to_string())
+        })?;
 
     // Resolve RPC configuration
     let rpc_config = resolve_rpc_from_network(&tx.network);
@@ -50,8 +52,7 @@ pub async fn handle_tx_queue_confirm(
     let rpc = X402EvmRpc::new_with_config(
         &private_key,
         &tx.network,
-        Some(rpc_config.url.clone()),
-        rpc_config.use_x402,
+        &rpc_config,
     ).map_err(|e| {
         tx_queue.mark_failed(&params.uuid, &e);
         RpcError::new(-32000, format!("RPC error: {}", e))
@@ -72,7 +73,7 @@ pub async fn handle_tx_queue_confirm(
         })?;
 
     let tx_hash_str = format!("{:?}", tx_hash);
-    let explorer_url = format!("{}/{}", tx.get_explorer_base_url(), tx_hash_str);
+    let explorer_url = tx.explorer_url_for(&tx_hash_str);
 
     // Mark as broadcast (partner mode - user confirmed)
     tx_queue.mark_broadcast(&params.uuid, &tx_hash_str, &explorer_url, "partner");
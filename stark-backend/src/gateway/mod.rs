@@ -7,6 +7,8 @@ pub use events::EventBroadcaster;
 
 use crate::channels::ChannelManager;
 use crate::db::Database;
+use crate::hooks::HookManager;
+use crate::tool_validators::ValidatorRegistry;
 use crate::tools::ToolRegistry;
 use crate::tx_queue::TxQueueManager;
 use std::sync::Arc;
@@ -41,15 +43,19 @@ impl Gateway {
         tool_registry: Arc<ToolRegistry>,
         burner_wallet_private_key: Option<String>,
     ) -> Self {
-        Self::new_with_tools_wallet_and_tx_queue(db, tool_registry, burner_wallet_private_key, None)
+        Self::new_with_tools_wallet_and_tx_queue(db, tool_registry, burner_wallet_private_key, None, None, None)
     }
 
-    /// Create a new Gateway with tool registry, wallet, and transaction queue support
+    /// Create a new Gateway with tool registry, wallet, and transaction queue
+    /// support. The hook manager and validators, when given, run on every
+    /// channel dispatch just as they do for the web chat.
     pub fn new_with_tools_wallet_and_tx_queue(
         db: Arc<Database>,
         tool_registry: Arc<ToolRegistry>,
         burner_wallet_private_key: Option<String>,
         tx_queue: Option<Arc<TxQueueManager>>,
+        hook_manager: Option<Arc<HookManager>>,
+        validator_registry: Option<Arc<ValidatorRegistry>>,
     ) -> Self {
         let broadcaster = Arc::new(EventBroadcaster::new());
         let mut channel_manager = ChannelManager::new_with_tools_and_wallet(
@@ -62,6 +68,12 @@ impl Gateway {
         if let Some(tq) = tx_queue {
             channel_manager = channel_manager.with_tx_queue(tq);
         }
+        if let Some(hooks) = hook_manager {
+            channel_manager = channel_manager.with_hook_manager(hooks);
+        }
+        if let Some(validators) = validator_registry {
+            channel_manager = channel_manager.with_validator_registry(validators);
+        }
         let channel_manager = Arc::new(channel_manager);
 
         Self {
@@ -81,7 +93,7 @@ impl Gateway {
                     let channel_type = channel.channel_type.clone();
 
                     match self.channel_manager.start_channel(channel).await {
-                        Ok(()) => {
+                        Ok(_) => {
                             log::info!("Started {} channel: {}", channel_type, name);
                         }
                         Err(e) => {
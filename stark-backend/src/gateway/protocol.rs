@@ -9,6 +9,7 @@ pub enum EventType {
     ChannelStarted,
     ChannelStopped,
     ChannelError,
+    ChannelConnection, // Connection state change (connecting/connected/disconnected/error)
     ChannelMessage,
     // Agent events
     AgentResponse,
@@ -68,10 +69,12 @@ pub enum EventType {
     ProcessStarted,    // Background process started
     ProcessOutput,     // Background process output chunk
     ProcessCompleted,  // Background process finished
+    DeployLog,         // Progress line from a deployment workflow run
     // Task planner events
     TaskQueueUpdate,    // Full task queue update (on define_tasks, session load)
     TaskStatusChange,   // Individual task status change
     SessionComplete,    // Session marked complete (all tasks done)
+    SessionAutoReset,   // Session reset by its reset policy (daily/idle)
     // Cron execution events (for web channel)
     CronExecutionStartedOnChannel,  // Cron job started on web channel (main mode)
     CronExecutionStoppedOnChannel,  // Cron job stopped on web channel
@@ -89,6 +92,7 @@ impl EventType {
             Self::ChannelStarted => "channel.started",
             Self::ChannelStopped => "channel.stopped",
             Self::ChannelError => "channel.error",
+            Self::ChannelConnection => "channel.connection",
             Self::ChannelMessage => "channel.message",
             Self::AgentResponse => "agent.response",
             Self::AgentToolCall => "agent.tool_call",
@@ -135,9 +139,11 @@ impl EventType {
             Self::ProcessStarted => "process.started",
             Self::ProcessOutput => "process.output",
             Self::ProcessCompleted => "process.completed",
+            Self::DeployLog => "deploy.log",
             Self::TaskQueueUpdate => "task.queue_update",
             Self::TaskStatusChange => "task.status_change",
             Self::SessionComplete => "session.complete",
+            Self::SessionAutoReset => "session.auto_reset",
             Self::CronExecutionStartedOnChannel => "cron.execution_started_on_channel",
             Self::CronExecutionStoppedOnChannel => "cron.execution_stopped_on_channel",
             Self::AiRetrying => "ai.retrying",
@@ -243,6 +249,9 @@ pub struct GatewayEvent {
     pub type_: String,
     pub event: String,
     pub data: Value,
+    /// Monotonic id assigned by the broadcaster, used to replay missed events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<u64>,
 }
 
 impl GatewayEvent {
@@ -251,6 +260,7 @@ impl GatewayEvent {
             type_: "event".to_string(),
             event: event.into(),
             data,
+            event_id: None,
         }
     }
 
@@ -286,6 +296,25 @@ impl GatewayEvent {
         )
     }
 
+    pub fn channel_connection(
+        channel_id: i64,
+        channel_type: &str,
+        name: &str,
+        state: &str,
+        reason: Option<&str>,
+    ) -> Self {
+        Self::new(
+            EventType::ChannelConnection,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "channel_type": channel_type,
+                "name": name,
+                "state": state,
+                "reason": reason
+            }),
+        )
+    }
+
     pub fn channel_message(
         channel_id: i64,
         channel_type: &str,
@@ -1083,6 +1112,20 @@ impl GatewayEvent {
         )
     }
 
+    /// Progress line from a deployment's workflow run
+    pub fn deploy_log(channel_id: i64, deployment_id: i64, target: &str, line: &str) -> Self {
+        Self::new(
+            EventType::DeployLog,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "deployment_id": deployment_id,
+                "target": target,
+                "line": line,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
     // =====================================================
     // Task Planner Events
     // =====================================================
@@ -1150,6 +1193,20 @@ impl GatewayEvent {
         )
     }
 
+    /// Session reset automatically by its daily or idle reset policy
+    pub fn session_auto_reset(channel_id: i64, session_id: i64, new_session_id: i64, reason: &str) -> Self {
+        Self::new(
+            EventType::SessionAutoReset,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "session_id": session_id,
+                "new_session_id": new_session_id,
+                "reason": reason,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
     // =====================================================
     // Cron Execution Events (for web channel)
     // =====================================================
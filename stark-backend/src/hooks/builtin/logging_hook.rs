@@ -112,8 +112,10 @@ impl Hook for LoggingHook {
 
     fn events(&self) -> Vec<HookEvent> {
         vec![
+            HookEvent::OnMessageReceived,
             HookEvent::BeforeAgentStart,
             HookEvent::AfterAgentEnd,
+            HookEvent::BeforeAiGeneration,
             HookEvent::BeforeToolCall,
             HookEvent::AfterToolCall,
             HookEvent::OnModeTransition,
@@ -3,9 +3,12 @@
 //! This module provides hooks that are commonly needed:
 //! - Logging - Event recording and debugging
 //! - Rate limiting - Request throttling and abuse prevention
+//! - Moderation - Blocks flagged content via a moderation endpoint
 
 mod logging_hook;
+mod moderation_hook;
 mod rate_limit_hook;
 
 pub use logging_hook::{LogLevel, LoggingHook};
+pub use moderation_hook::ModerationHook;
 pub use rate_limit_hook::{RateLimitConfig, RateLimitHook};
@@ -0,0 +1,241 @@
+//! Moderation hook - Screens messages through a content moderation endpoint
+//!
+//! Inbound user text (and optionally outbound responses) is sent to the
+//! endpoint configured in bot settings. Flagged content never reaches the AI or
+//! the user; it is replaced with the configured refusal message. Settings are
+//! read on every call, so toggling moderation takes effect immediately.
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::controllers::api_keys::ApiKeyId;
+use crate::db::Database;
+use crate::hooks::types::{Hook, HookContext, HookEvent, HookPriority, HookResult};
+use crate::models::{ModerationFailurePolicy, ModerationSettings};
+
+/// Request timeout for the moderation endpoint. Kept below the hook timeout
+/// so failures are handled by the failure policy, not the hook manager.
+const MODERATION_REQUEST_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Outcome of a moderation check
+#[derive(Debug, PartialEq)]
+struct Verdict {
+    flagged: bool,
+    categories: Vec<String>,
+}
+
+/// Hook that blocks content flagged by a moderation endpoint
+pub struct ModerationHook {
+    db: Arc<Database>,
+    client: reqwest::Client,
+}
+
+impl ModerationHook {
+    pub fn new(db: Arc<Database>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(MODERATION_REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { db, client }
+    }
+
+    fn settings(&self) -> ModerationSettings {
+        self.db
+            .get_bot_settings()
+            .map(|s| s.moderation)
+            .unwrap_or_default()
+    }
+
+    /// Send text to the moderation endpoint
+    async fn check(&self, settings: &ModerationSettings, text: &str) -> Result<Verdict, String> {
+        let mut request = self
+            .client
+            .post(settings.endpoint.trim())
+            .json(&serde_json::json!({ "input": text }));
+
+        if let Ok(Some(key)) = self.db.get_api_key(ApiKeyId::ModerationApiKey.as_str()) {
+            request = request.bearer_auth(key.api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("request failed: {}", e))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("endpoint returned HTTP {}", status));
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| format!("invalid response: {}", e))?;
+        parse_verdict(&body).ok_or_else(|| "response has no 'flagged' field".to_string())
+    }
+
+    /// Moderate `text`, returning the refusal to use in its place if blocked
+    async fn moderate(&self, settings: &ModerationSettings, direction: &str, text: &str) -> Option<String> {
+        if text.trim().is_empty() {
+            return None;
+        }
+
+        match self.check(settings, text).await {
+            Ok(verdict) if verdict.flagged => {
+                log::warn!(
+                    "[MODERATION] Blocked {} content (categories: {})",
+                    direction,
+                    if verdict.categories.is_empty() {
+                        "unspecified".to_string()
+                    } else {
+                        verdict.categories.join(", ")
+                    }
+                );
+                Some(settings.refusal_message.clone())
+            }
+            Ok(_) => None,
+            Err(e) => match settings.failure_policy {
+                ModerationFailurePolicy::Open => {
+                    log::warn!("[MODERATION] Check failed, allowing {} content: {}", direction, e);
+                    None
+                }
+                ModerationFailurePolicy::Closed => {
+                    log::warn!("[MODERATION] Check failed, blocking {} content: {}", direction, e);
+                    Some(settings.refusal_message.clone())
+                }
+            },
+        }
+    }
+}
+
+/// Extract a verdict from an OpenAI-style (`results[].flagged`) or flat
+/// (`flagged`) moderation response
+fn parse_verdict(body: &Value) -> Option<Verdict> {
+    let results: Vec<&Value> = match body.get("results").and_then(|r| r.as_array()) {
+        Some(results) => results.iter().collect(),
+        None => vec![body],
+    };
+
+    let mut verdict = Verdict {
+        flagged: false,
+        categories: Vec::new(),
+    };
+    let mut found = false;
+    for result in results {
+        let Some(flagged) = result.get("flagged").and_then(|f| f.as_bool()) else {
+            continue;
+        };
+        found = true;
+        verdict.flagged |= flagged;
+        if let Some(categories) = result.get("categories").and_then(|c| c.as_object()) {
+            verdict.categories.extend(
+                categories
+                    .iter()
+                    .filter(|(_, v)| v.as_bool().unwrap_or(false))
+                    .map(|(k, _)| k.clone()),
+            );
+        }
+    }
+
+    found.then_some(verdict)
+}
+
+#[async_trait]
+impl Hook for ModerationHook {
+    fn id(&self) -> &str {
+        "moderation"
+    }
+
+    fn name(&self) -> &str {
+        "Moderation Hook"
+    }
+
+    fn description(&self) -> &str {
+        "Blocks messages and responses flagged by a content moderation endpoint"
+    }
+
+    fn events(&self) -> Vec<HookEvent> {
+        vec![HookEvent::OnMessageReceived, HookEvent::BeforeResponse]
+    }
+
+    fn priority(&self) -> HookPriority {
+        // Screen content before any other hook sees it
+        HookPriority::Critical
+    }
+
+    fn timeout(&self) -> Duration {
+        MODERATION_REQUEST_TIMEOUT + Duration::from_secs(2)
+    }
+
+    async fn on_message_received(&self, context: &mut HookContext) -> HookResult {
+        let settings = self.settings();
+        if !settings.enabled {
+            return HookResult::Continue(None);
+        }
+        let text = context.message.clone().unwrap_or_default();
+        match self.moderate(&settings, "inbound", &text).await {
+            Some(refusal) => HookResult::Replace(Value::String(refusal)),
+            None => HookResult::Continue(None),
+        }
+    }
+
+    async fn on_response(&self, context: &mut HookContext) -> HookResult {
+        let settings = self.settings();
+        if !settings.enabled || !settings.check_responses {
+            return HookResult::Continue(None);
+        }
+        let text = context.response.clone().unwrap_or_default();
+        match self.moderate(&settings, "outbound", &text).await {
+            Some(refusal) => HookResult::Replace(Value::String(refusal)),
+            None => HookResult::Continue(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_openai_verdict() {
+        let body = serde_json::json!({
+            "results": [{
+                "flagged": true,
+                "categories": { "harassment": true, "violence": false }
+            }]
+        });
+        assert_eq!(
+            parse_verdict(&body),
+            Some(Verdict {
+                flagged: true,
+                categories: vec!["harassment".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_flat_verdict() {
+        let body = serde_json::json!({ "flagged": false });
+        assert_eq!(
+            parse_verdict(&body),
+            Some(Verdict {
+                flagged: false,
+                categories: Vec::new(),
+            })
+        );
+        assert_eq!(parse_verdict(&serde_json::json!({ "ok": true })), None);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let hook = ModerationHook::new(db);
+
+        let mut context = HookContext::new(HookEvent::OnMessageReceived)
+            .with_message("hello".to_string());
+        let result = hook.execute(&mut context).await;
+        assert!(matches!(result, HookResult::Continue(None)));
+    }
+}
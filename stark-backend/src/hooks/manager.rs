@@ -337,4 +337,72 @@ mod tests {
 
         assert!(result.should_continue());
     }
+
+    /// Appends its id to the message so tests can observe execution order
+    struct TaggingHook {
+        id: String,
+        priority: HookPriority,
+        result: HookResult,
+    }
+
+    #[async_trait]
+    impl Hook for TaggingHook {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        fn events(&self) -> Vec<HookEvent> {
+            vec![HookEvent::OnMessageReceived]
+        }
+
+        fn priority(&self) -> HookPriority {
+            self.priority
+        }
+
+        async fn on_message_received(&self, context: &mut HookContext) -> HookResult {
+            let message = context.message.get_or_insert_with(String::new);
+            message.push_str(&self.id);
+            self.result.clone()
+        }
+    }
+
+    fn tagging_hook(id: &str, priority: HookPriority, result: HookResult) -> Arc<TaggingHook> {
+        Arc::new(TaggingHook {
+            id: id.to_string(),
+            priority,
+            result,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_hook_ordering() {
+        let manager = HookManager::new();
+        manager.register(tagging_hook("c", HookPriority::Low, HookResult::Continue(None)));
+        manager.register(tagging_hook("a", HookPriority::High, HookResult::Continue(None)));
+        manager.register(tagging_hook("b1", HookPriority::Normal, HookResult::Continue(None)));
+        manager.register(tagging_hook("b2", HookPriority::Normal, HookResult::Continue(None)));
+
+        let mut context = HookContext::new(HookEvent::OnMessageReceived);
+        let result = manager.execute(HookEvent::OnMessageReceived, &mut context).await;
+
+        assert!(result.should_continue());
+        assert_eq!(context.message.as_deref(), Some("ab1b2c"));
+    }
+
+    #[tokio::test]
+    async fn test_hook_short_circuit() {
+        let manager = HookManager::new();
+        manager.register(tagging_hook("a", HookPriority::High, HookResult::Cancel("blocked".to_string())));
+        manager.register(tagging_hook("b", HookPriority::Normal, HookResult::Continue(None)));
+
+        let mut context = HookContext::new(HookEvent::OnMessageReceived);
+        let result = manager.execute(HookEvent::OnMessageReceived, &mut context).await;
+
+        assert!(result.should_cancel());
+        assert_eq!(context.message.as_deref(), Some("a"));
+    }
 }
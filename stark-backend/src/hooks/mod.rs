@@ -9,6 +9,37 @@
 //! - Log and audit (logging hook)
 //! - Enforce limits (rate_limit hook)
 //!
+//! # Dispatcher lifecycle points
+//!
+//! The message dispatcher fires four events per message. Hooks can either
+//! override the matching `Hook` method (the default `execute` routes to it) or
+//! handle the event in `execute` themselves:
+//!
+//! | Event | `Hook` method | Fires | Mutable context |
+//! |-------|---------------|-------|-----------------|
+//! | `OnMessageReceived` | `on_message_received` | Once, before the session is loaded | `message` |
+//! | `BeforeAiGeneration` | `before_ai_generation` | Once, before the first model call | `message` |
+//! | `AfterToolCall` | `after_tool_execution` | After every tool call | `tool_result.content` |
+//! | `BeforeResponse` | `on_response` | Once, before the response is stored and sent | `response` |
+//!
+//! `Replace` with a JSON string substitutes the value directly (a canned reply,
+//! a new tool output, a new response). `Cancel` turns the dispatch into an
+//! error, and `Skip` drops the message or withholds the response silently.
+//!
+//! # Ordering
+//!
+//! When several hooks subscribe to the same event:
+//!
+//! - They run sequentially, lowest `HookPriority` first. A `HookConfig`
+//!   priority override takes precedence over the hook's own priority.
+//! - Hooks with equal priority run in registration order.
+//! - Every hook sees the context as left by the hooks before it, so edits
+//!   accumulate.
+//! - The first `Skip` or `Cancel` stops the chain; later hooks do not run.
+//! - If several hooks return `Replace`, the last one wins.
+//! - `Error` results (including timeouts) are logged and skipped unless the
+//!   manager was built with `HookManager::strict()`.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -12,10 +12,14 @@ use std::time::Duration;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum HookEvent {
+    /// A channel message arrived, before any session or AI work
+    OnMessageReceived,
     /// Before the agent starts processing a message
     BeforeAgentStart,
     /// After the agent finishes processing (success or failure)
     AfterAgentEnd,
+    /// Before the prompt is sent to the AI model
+    BeforeAiGeneration,
     /// Before a tool is called
     BeforeToolCall,
     /// After a tool call completes
@@ -49,8 +53,10 @@ pub enum HookEvent {
 impl HookEvent {
     pub fn as_str(&self) -> &'static str {
         match self {
+            HookEvent::OnMessageReceived => "on_message_received",
             HookEvent::BeforeAgentStart => "before_agent_start",
             HookEvent::AfterAgentEnd => "after_agent_end",
+            HookEvent::BeforeAiGeneration => "before_ai_generation",
             HookEvent::BeforeToolCall => "before_tool_call",
             HookEvent::AfterToolCall => "after_tool_call",
             HookEvent::OnModeTransition => "on_mode_transition",
@@ -325,8 +331,45 @@ pub trait Hook: Send + Sync {
         true
     }
 
-    /// Execute the hook
-    async fn execute(&self, context: &mut HookContext) -> HookResult;
+    /// Called when a channel message arrives. `context.message` holds the
+    /// text and may be rewritten; `Replace` with a string answers the user
+    /// directly, `Skip` drops the message and `Cancel` rejects it.
+    async fn on_message_received(&self, _context: &mut HookContext) -> HookResult {
+        HookResult::Continue(None)
+    }
+
+    /// Called once per dispatch before the first AI call. `context.message`
+    /// holds the user prompt and may be rewritten; `Replace` with a string is
+    /// used as the response without calling the model, `Skip`/`Cancel` abort.
+    async fn before_ai_generation(&self, _context: &mut HookContext) -> HookResult {
+        HookResult::Continue(None)
+    }
+
+    /// Called after every tool call with `context.tool_result` set to
+    /// `{"success", "content"}`. Rewriting `content` (or returning `Replace`
+    /// with a string) changes what the model sees.
+    async fn after_tool_execution(&self, _context: &mut HookContext) -> HookResult {
+        HookResult::Continue(None)
+    }
+
+    /// Called with the final response before it is stored and sent.
+    /// `context.response` may be rewritten; `Replace` with a string swaps it,
+    /// `Skip` withholds it and `Cancel` turns it into an error.
+    async fn on_response(&self, _context: &mut HookContext) -> HookResult {
+        HookResult::Continue(None)
+    }
+
+    /// Execute the hook. The default routes lifecycle events to the methods
+    /// above; hooks on other events override this directly.
+    async fn execute(&self, context: &mut HookContext) -> HookResult {
+        match context.event {
+            HookEvent::OnMessageReceived => self.on_message_received(context).await,
+            HookEvent::BeforeAiGeneration => self.before_ai_generation(context).await,
+            HookEvent::AfterToolCall => self.after_tool_execution(context).await,
+            HookEvent::BeforeResponse => self.on_response(context).await,
+            _ => HookResult::Continue(None),
+        }
+    }
 }
 
 /// Configuration for a hook
@@ -0,0 +1,283 @@
+//! Inbound email attachments
+//!
+//! Attachments are downloaded into the workspace under
+//! `attachments/gmail/<thread_id>/` so the filesystem tools can read them, and
+//! the agent gets a short manifest of what was saved or skipped.
+
+use std::path::Path;
+
+use super::client::GmailClient;
+use super::types::{base64_url_decode_bytes, GmailAttachmentRef, ParsedEmail};
+
+/// Total size cap for one email's attachments (25 MB, Gmail's own limit)
+pub const MAX_TOTAL_ATTACHMENT_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Workspace-relative directory attachments are saved under
+const ATTACHMENTS_DIR: &str = "attachments/gmail";
+
+/// File extensions that are never saved
+const BLOCKED_EXTENSIONS: &[&str] = &[
+    "exe", "dll", "com", "bat", "cmd", "msi", "msp", "scr", "pif", "cpl", "ps1", "psm1",
+    "vbs", "vbe", "js", "jse", "wsf", "wsh", "hta", "jar", "sh", "bash", "app", "dmg",
+    "pkg", "deb", "rpm", "apk", "lnk", "reg",
+];
+
+/// MIME types that are never saved
+const BLOCKED_MIME_TYPES: &[&str] = &[
+    "application/x-msdownload",
+    "application/x-msdos-program",
+    "application/x-executable",
+    "application/x-sh",
+    "application/x-msi",
+    "application/vnd.microsoft.portable-executable",
+    "application/java-archive",
+];
+
+/// An attachment written to the workspace
+#[derive(Debug, Clone)]
+pub struct SavedAttachment {
+    pub filename: String,
+    pub mime_type: String,
+    pub size: u64,
+    /// Path relative to the workspace root
+    pub path: String,
+}
+
+/// An attachment that was not saved, and why
+#[derive(Debug, Clone)]
+pub struct SkippedAttachment {
+    pub filename: String,
+    pub reason: String,
+}
+
+/// Result of saving an email's attachments
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentManifest {
+    pub saved: Vec<SavedAttachment>,
+    pub skipped: Vec<SkippedAttachment>,
+}
+
+impl AttachmentManifest {
+    pub fn is_empty(&self) -> bool {
+        self.saved.is_empty() && self.skipped.is_empty()
+    }
+
+    /// Short listing appended to the message the agent sees
+    pub fn to_message(&self) -> String {
+        let mut lines = vec!["Attachments (saved in the workspace, readable with the file tools):".to_string()];
+        for a in &self.saved {
+            lines.push(format!("- {} ({}, {}) -> {}", a.filename, a.mime_type, format_size(a.size), a.path));
+        }
+        for s in &self.skipped {
+            lines.push(format!("- {} skipped: {}", s.filename, s.reason));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Whether an attachment looks executable by extension or MIME type
+fn is_executable(filename: &str, mime_type: &str) -> bool {
+    let ext = Path::new(filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    BLOCKED_EXTENSIONS.contains(&ext.as_str())
+        || BLOCKED_MIME_TYPES.iter().any(|m| mime_type.eq_ignore_ascii_case(m))
+}
+
+/// Reduce a filename to a safe single path component
+fn sanitize_filename(filename: &str) -> String {
+    let name = filename.rsplit(['/', '\\']).next().unwrap_or("");
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ' ') { c } else { '_' })
+        .collect();
+    let cleaned = cleaned.trim().trim_start_matches('.').to_string();
+    if cleaned.is_empty() {
+        "attachment".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Split attachments into those to download and those skipped up front
+/// (executables, or over the total size cap by their declared size)
+fn plan_attachments(attachments: &[GmailAttachmentRef], cap: u64) -> (Vec<&GmailAttachmentRef>, Vec<SkippedAttachment>) {
+    let mut accepted = Vec::new();
+    let mut skipped = Vec::new();
+    let mut total = 0u64;
+
+    for attachment in attachments {
+        if is_executable(&attachment.filename, &attachment.mime_type) {
+            skipped.push(SkippedAttachment {
+                filename: attachment.filename.clone(),
+                reason: "executable files are not accepted".to_string(),
+            });
+        } else if total + attachment.size > cap {
+            skipped.push(SkippedAttachment {
+                filename: attachment.filename.clone(),
+                reason: format!("exceeds the {} total attachment limit", format_size(cap)),
+            });
+        } else {
+            total += attachment.size;
+            accepted.push(attachment);
+        }
+    }
+
+    (accepted, skipped)
+}
+
+/// Download an email's attachments into `workspace`
+pub async fn save_attachments(client: &GmailClient, email: &ParsedEmail, workspace: &Path) -> AttachmentManifest {
+    let mut manifest = AttachmentManifest::default();
+    if email.attachments.is_empty() {
+        return manifest;
+    }
+
+    let (accepted, skipped) = plan_attachments(&email.attachments, MAX_TOTAL_ATTACHMENT_BYTES);
+    manifest.skipped = skipped;
+
+    let relative_dir = format!("{}/{}", ATTACHMENTS_DIR, sanitize_filename(&email.thread_id));
+    let dir = workspace.join(&relative_dir);
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        log::error!("[GMAIL] Failed to create attachment directory {}: {}", dir.display(), e);
+        manifest.skipped.extend(accepted.iter().map(|a| SkippedAttachment {
+            filename: a.filename.clone(),
+            reason: "could not create the attachment directory".to_string(),
+        }));
+        return manifest;
+    }
+
+    let mut total = 0u64;
+    for attachment in accepted {
+        let content = match (&attachment.inline_data, &attachment.attachment_id) {
+            (Some(data), _) => base64_url_decode_bytes(data),
+            (None, Some(id)) => client.get_attachment("me", &email.message_id, id).await,
+            (None, None) => Err("no content".to_string()),
+        };
+        let bytes = match content {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::warn!("[GMAIL] Failed to download attachment {}: {}", attachment.filename, e);
+                manifest.skipped.push(SkippedAttachment {
+                    filename: attachment.filename.clone(),
+                    reason: "download failed".to_string(),
+                });
+                continue;
+            }
+        };
+
+        // Declared sizes can be off; enforce the cap on the real bytes too
+        let size = bytes.len() as u64;
+        if total + size > MAX_TOTAL_ATTACHMENT_BYTES {
+            manifest.skipped.push(SkippedAttachment {
+                filename: attachment.filename.clone(),
+                reason: format!("exceeds the {} total attachment limit", format_size(MAX_TOTAL_ATTACHMENT_BYTES)),
+            });
+            continue;
+        }
+
+        let filename = sanitize_filename(&attachment.filename);
+        if let Err(e) = std::fs::write(dir.join(&filename), &bytes) {
+            log::error!("[GMAIL] Failed to save attachment {}: {}", filename, e);
+            manifest.skipped.push(SkippedAttachment {
+                filename: attachment.filename.clone(),
+                reason: "could not be saved".to_string(),
+            });
+            continue;
+        }
+
+        total += size;
+        manifest.saved.push(SavedAttachment {
+            filename: attachment.filename.clone(),
+            mime_type: attachment.mime_type.clone(),
+            size,
+            path: format!("{}/{}", relative_dir, filename),
+        });
+    }
+
+    log::info!(
+        "[GMAIL] Saved {} attachment(s), skipped {} for thread {}",
+        manifest.saved.len(),
+        manifest.skipped.len(),
+        email.thread_id
+    );
+    manifest
+}
+
+/// Format file size in human-readable format
+fn format_size(size: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+
+    if size >= MB {
+        format!("{:.1} MB", size as f64 / MB as f64)
+    } else if size >= KB {
+        format!("{:.1} KB", size as f64 / KB as f64)
+    } else {
+        format!("{} B", size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(filename: &str, mime_type: &str, size: u64) -> GmailAttachmentRef {
+        GmailAttachmentRef {
+            attachment_id: Some("a1".to_string()),
+            inline_data: None,
+            filename: filename.to_string(),
+            mime_type: mime_type.to_string(),
+            size,
+        }
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_filename("C:\\Users\\me\\report (final).pdf"), "report _final_.pdf");
+        assert_eq!(sanitize_filename(".hidden"), "hidden");
+        assert_eq!(sanitize_filename(".."), "attachment");
+    }
+
+    #[test]
+    fn test_plan_skips_executables_and_enforces_cap() {
+        let attachments = vec![
+            attachment("report.pdf", "application/pdf", 600),
+            attachment("setup.EXE", "application/octet-stream", 10),
+            attachment("tool.bin", "application/x-msdownload", 10),
+            attachment("photo.jpg", "image/jpeg", 500),
+            attachment("notes.txt", "text/plain", 300),
+        ];
+
+        let (accepted, skipped) = plan_attachments(&attachments, 1000);
+        let names: Vec<_> = accepted.iter().map(|a| a.filename.as_str()).collect();
+        assert_eq!(names, vec!["report.pdf", "notes.txt"]);
+
+        let skipped_names: Vec<_> = skipped.iter().map(|s| s.filename.as_str()).collect();
+        assert_eq!(skipped_names, vec!["setup.EXE", "tool.bin", "photo.jpg"]);
+        assert!(skipped[0].reason.contains("executable"));
+        assert!(skipped[2].reason.contains("limit"));
+    }
+
+    #[test]
+    fn test_manifest_message() {
+        let manifest = AttachmentManifest {
+            saved: vec![SavedAttachment {
+                filename: "report.pdf".to_string(),
+                mime_type: "application/pdf".to_string(),
+                size: 2048,
+                path: "attachments/gmail/t1/report.pdf".to_string(),
+            }],
+            skipped: vec![SkippedAttachment {
+                filename: "setup.exe".to_string(),
+                reason: "executable files are not accepted".to_string(),
+            }],
+        };
+        let message = manifest.to_message();
+        assert!(message.contains("- report.pdf (application/pdf, 2.0 KB) -> attachments/gmail/t1/report.pdf"));
+        assert!(message.contains("- setup.exe skipped: executable files are not accepted"));
+    }
+}
@@ -41,7 +41,7 @@ impl GmailClient {
     }
 
     /// Refresh the access token using the refresh token
-    pub async fn refresh_access_token(&mut self) -> Result<String, String> {
+    pub async fn refresh_access_token(&mut self) -> Result<TokenRefresh, String> {
         let client_id = self.client_id.as_ref()
             .ok_or("Client ID not set for token refresh")?;
         let client_secret = self.client_secret.as_ref()
@@ -64,16 +64,11 @@ impl GmailClient {
             return Err(format!("Token refresh failed: {}", error));
         }
 
-        #[derive(serde::Deserialize)]
-        struct TokenResponse {
-            access_token: String,
-        }
-
-        let token_response: TokenResponse = response.json().await
+        let token_response: TokenRefresh = response.json().await
             .map_err(|e| format!("Failed to parse token response: {}", e))?;
 
         self.access_token = token_response.access_token.clone();
-        Ok(token_response.access_token)
+        Ok(token_response)
     }
 
     /// Get message history since a specific history ID
@@ -141,6 +136,37 @@ impl GmailClient {
             .map_err(|e| format!("Failed to parse message response: {}", e))
     }
 
+    /// Download an attachment's content
+    pub async fn get_attachment(
+        &self,
+        user_id: &str,
+        message_id: &str,
+        attachment_id: &str,
+    ) -> Result<Vec<u8>, String> {
+        let url = format!(
+            "{}/users/{}/messages/{}/attachments/{}",
+            GMAIL_API_BASE, user_id, message_id, attachment_id
+        );
+
+        let response = self.http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.access_token))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to get attachment: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = response.text().await.unwrap_or_default();
+            return Err(format!("Gmail API error ({}): {}", status, error));
+        }
+
+        let body: GmailMessageBody = response.json().await
+            .map_err(|e| format!("Failed to parse attachment response: {}", e))?;
+        let data = body.data.ok_or("Attachment response has no data")?;
+        base64_url_decode_bytes(&data)
+    }
+
     /// Set up a watch on the mailbox
     pub async fn setup_watch(
         &self,
@@ -278,6 +304,18 @@ pub struct WatchResponse {
     pub expiration: String,
 }
 
+impl WatchResponse {
+    /// Watch expiration time. Gmail returns epoch milliseconds as a string.
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self.expiration.parse::<i64>() {
+            Ok(millis) => chrono::DateTime::from_timestamp_millis(millis),
+            Err(_) => chrono::DateTime::parse_from_rfc3339(&self.expiration)
+                .ok()
+                .map(|dt| dt.with_timezone(&chrono::Utc)),
+        }
+    }
+}
+
 /// User profile from Gmail API
 #[derive(Debug, serde::Deserialize)]
 pub struct UserProfile {
@@ -300,4 +338,19 @@ mod tests {
         let client = GmailClient::new("access".to_string(), "refresh".to_string());
         assert_eq!(client.access_token(), "access");
     }
+
+    #[test]
+    fn test_watch_expiration_parsing() {
+        let watch = WatchResponse {
+            history_id: "1".to_string(),
+            expiration: "1700000000000".to_string(),
+        };
+        assert_eq!(watch.expires_at().unwrap().timestamp(), 1_700_000_000);
+
+        let watch = WatchResponse {
+            history_id: "1".to_string(),
+            expiration: "2024-01-01T00:00:00Z".to_string(),
+        };
+        assert_eq!(watch.expires_at().unwrap().timestamp(), 1_704_067_200);
+    }
 }
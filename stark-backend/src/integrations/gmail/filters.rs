@@ -0,0 +1,206 @@
+//! Auto-reply safety filters
+//!
+//! Replying to every inbound email is dangerous: mailing lists, no-reply
+//! senders and other bots will happily reply back. These checks run before an
+//! email is dispatched for an auto-reply and decide whether a reply may be sent.
+
+use super::types::{GmailConfig, ParsedEmail};
+
+/// Why an auto-reply was suppressed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Sender looks like a no-reply address
+    NoReplySender(String),
+    /// Message has a List-Unsubscribe header (mailing list / newsletter)
+    MailingList,
+    /// Message has a Precedence: bulk/list/junk header
+    BulkPrecedence(String),
+    /// Sender matches the denylist
+    Denylisted(String),
+    /// An allowlist is configured and the sender isn't on it
+    NotAllowlisted(String),
+    /// The thread already reached the reply cap
+    ThreadReplyCap { replies: u32, max: u32 },
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::NoReplySender(addr) => write!(f, "no-reply sender {}", addr),
+            SkipReason::MailingList => write!(f, "mailing list (List-Unsubscribe header)"),
+            SkipReason::BulkPrecedence(value) => write!(f, "bulk mail (Precedence: {})", value),
+            SkipReason::Denylisted(addr) => write!(f, "sender {} is denylisted", addr),
+            SkipReason::NotAllowlisted(addr) => write!(f, "sender {} is not on the allowlist", addr),
+            SkipReason::ThreadReplyCap { replies, max } => {
+                write!(f, "thread reply cap reached ({}/{})", replies, max)
+            }
+        }
+    }
+}
+
+/// Auto-reply filter settings from a Gmail config
+#[derive(Debug, Clone, Default)]
+pub struct AutoReplyFilter {
+    allowlist: Vec<String>,
+    denylist: Vec<String>,
+    /// 0 disables the cap
+    max_replies_per_thread: u32,
+}
+
+impl AutoReplyFilter {
+    pub fn from_config(config: &GmailConfig) -> Self {
+        Self {
+            allowlist: parse_address_list(&config.reply_allowlist),
+            denylist: parse_address_list(&config.reply_denylist),
+            max_replies_per_thread: config.max_replies_per_thread,
+        }
+    }
+
+    /// Check whether an auto-reply may be sent to `email`, given how many
+    /// replies were already sent in its thread
+    pub fn check(&self, email: &ParsedEmail, thread_replies: u32) -> Result<(), SkipReason> {
+        let sender = sender_address(&email.from);
+
+        if is_no_reply(&sender) {
+            return Err(SkipReason::NoReplySender(sender));
+        }
+        if email.list_unsubscribe.is_some() {
+            return Err(SkipReason::MailingList);
+        }
+        let bulk = email
+            .precedence
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| ["bulk", "list", "junk"].iter().any(|b| p.eq_ignore_ascii_case(b)));
+        if let Some(precedence) = bulk {
+            return Err(SkipReason::BulkPrecedence(precedence.to_string()));
+        }
+        if self.denylist.iter().any(|entry| address_matches(&sender, entry)) {
+            return Err(SkipReason::Denylisted(sender));
+        }
+        if !self.allowlist.is_empty() && !self.allowlist.iter().any(|entry| address_matches(&sender, entry)) {
+            return Err(SkipReason::NotAllowlisted(sender));
+        }
+        if self.max_replies_per_thread > 0 && thread_replies >= self.max_replies_per_thread {
+            return Err(SkipReason::ThreadReplyCap {
+                replies: thread_replies,
+                max: self.max_replies_per_thread,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Extract the bare, lowercased address from a From header
+/// ("Name <user@example.com>" or "user@example.com")
+fn sender_address(from: &str) -> String {
+    let addr = match (from.rfind('<'), from.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &from[start + 1..end],
+        _ => from,
+    };
+    addr.trim().trim_matches('"').to_lowercase()
+}
+
+/// Split a comma/newline separated list of addresses or domains
+fn parse_address_list(list: &str) -> Vec<String> {
+    list.split([',', '\n', ';'])
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn is_no_reply(address: &str) -> bool {
+    let local = address.split('@').next().unwrap_or("");
+    local.contains("noreply") || local.contains("no-reply") || local.contains("no_reply")
+}
+
+/// An entry matches a full address ("bob@example.com") or a whole domain
+/// ("example.com" or "@example.com", including subdomains)
+fn address_matches(address: &str, entry: &str) -> bool {
+    if entry.contains('@') && !entry.starts_with('@') {
+        return address == entry;
+    }
+    let domain = entry.trim_start_matches('@');
+    match address.rsplit_once('@') {
+        Some((_, addr_domain)) => addr_domain == domain || addr_domain.ends_with(&format!(".{}", domain)),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(from: &str) -> ParsedEmail {
+        ParsedEmail {
+            message_id: "m1".to_string(),
+            thread_id: "t1".to_string(),
+            from: from.to_string(),
+            to: "bot@example.com".to_string(),
+            subject: "Hello".to_string(),
+            snippet: String::new(),
+            body: String::new(),
+            date: None,
+            labels: vec![],
+            list_unsubscribe: None,
+            precedence: None,
+            attachments: vec![],
+        }
+    }
+
+    fn filter(allowlist: &str, denylist: &str, max: u32) -> AutoReplyFilter {
+        AutoReplyFilter {
+            allowlist: parse_address_list(allowlist),
+            denylist: parse_address_list(denylist),
+            max_replies_per_thread: max,
+        }
+    }
+
+    #[test]
+    fn test_sender_address() {
+        assert_eq!(sender_address("\"Alice\" <Alice@Example.com>"), "alice@example.com");
+        assert_eq!(sender_address("bob@example.com"), "bob@example.com");
+    }
+
+    #[test]
+    fn test_skips_automated_mail() {
+        let f = filter("", "", 0);
+        assert!(matches!(
+            f.check(&email("GitHub <noreply@github.com>"), 0),
+            Err(SkipReason::NoReplySender(_))
+        ));
+        assert!(matches!(f.check(&email("no-reply@shop.com"), 0), Err(SkipReason::NoReplySender(_))));
+
+        let mut list = email("news@example.com");
+        list.list_unsubscribe = Some("<mailto:unsub@example.com>".to_string());
+        assert_eq!(f.check(&list, 0), Err(SkipReason::MailingList));
+
+        let mut bulk = email("news@example.com");
+        bulk.precedence = Some("Bulk".to_string());
+        assert!(matches!(f.check(&bulk, 0), Err(SkipReason::BulkPrecedence(_))));
+
+        assert_eq!(f.check(&email("Alice <alice@example.com>"), 0), Ok(()));
+    }
+
+    #[test]
+    fn test_allowlist_and_denylist() {
+        let f = filter("example.com, bob@other.org", "spam@example.com", 0);
+        assert_eq!(f.check(&email("alice@example.com"), 0), Ok(()));
+        assert_eq!(f.check(&email("carol@mail.example.com"), 0), Ok(()));
+        assert_eq!(f.check(&email("bob@other.org"), 0), Ok(()));
+        assert!(matches!(f.check(&email("spam@example.com"), 0), Err(SkipReason::Denylisted(_))));
+        assert!(matches!(f.check(&email("eve@other.org"), 0), Err(SkipReason::NotAllowlisted(_))));
+        assert!(matches!(f.check(&email("x@notexample.com"), 0), Err(SkipReason::NotAllowlisted(_))));
+    }
+
+    #[test]
+    fn test_thread_reply_cap() {
+        let f = filter("", "", 2);
+        assert_eq!(f.check(&email("alice@example.com"), 1), Ok(()));
+        assert_eq!(
+            f.check(&email("alice@example.com"), 2),
+            Err(SkipReason::ThreadReplyCap { replies: 2, max: 2 })
+        );
+        assert_eq!(filter("", "", 0).check(&email("alice@example.com"), 50), Ok(()));
+    }
+}
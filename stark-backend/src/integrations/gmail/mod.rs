@@ -11,8 +11,14 @@
 //! 3. Pub/Sub topic with gmail-api-push@system.gserviceaccount.com as publisher
 //! 4. Watch set up via Gmail API to monitor specific labels
 
+mod attachments;
 mod client;
+mod filters;
+mod renewal;
 mod types;
 
+pub use attachments::save_attachments;
 pub use client::GmailClient;
+pub use filters::AutoReplyFilter;
+pub use renewal::{renew_watches, RENEWAL_CHECK_INTERVAL_SECS};
 pub use types::*;
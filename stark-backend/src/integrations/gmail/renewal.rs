@@ -0,0 +1,189 @@
+//! Gmail watch auto-renewal
+//!
+//! Gmail push watches expire after ~7 days, after which no more Pub/Sub
+//! notifications arrive. The scheduler calls `renew_watches` periodically to
+//! renew the watch before it lapses, refreshing the OAuth access token first
+//! when it is about to expire.
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+
+use super::client::GmailClient;
+use super::types::GmailConfig;
+use crate::db::Database;
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::GatewayEvent;
+
+/// How often the scheduler checks whether a renewal is due (15 minutes)
+pub const RENEWAL_CHECK_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Renew the watch when it expires within this window
+const WATCH_RENEW_BEFORE_HOURS: i64 = 24;
+
+/// Refresh the access token when it expires within this window
+const TOKEN_REFRESH_BEFORE_MINUTES: i64 = 10;
+
+/// Whether an active watch is close enough to expiry to renew
+fn needs_watch_renewal(config: &GmailConfig, now: DateTime<Utc>) -> bool {
+    match config.watch_expires_at {
+        Some(expires_at) => config.enabled && expires_at - now <= Duration::hours(WATCH_RENEW_BEFORE_HOURS),
+        None => false,
+    }
+}
+
+/// Whether the access token should be refreshed before calling the API.
+/// An unknown expiry is treated as expired, since tokens only live an hour.
+fn needs_token_refresh(config: &GmailConfig, now: DateTime<Utc>) -> bool {
+    match config.token_expires_at {
+        Some(expires_at) => expires_at - now <= Duration::minutes(TOKEN_REFRESH_BEFORE_MINUTES),
+        None => true,
+    }
+}
+
+/// Renew the Gmail watch if it is due. Failures are logged and broadcast as a
+/// `gmail_watch_renewal_failed` event; once the watch has actually lapsed its
+/// expiry is cleared so the dashboard shows it as stopped.
+pub async fn renew_watches(db: &Arc<Database>, broadcaster: &Arc<EventBroadcaster>) {
+    let config = match db.get_gmail_config() {
+        Ok(Some(config)) => config,
+        Ok(None) => return,
+        Err(e) => {
+            log::error!("[GMAIL] Failed to load config for watch renewal: {}", e);
+            return;
+        }
+    };
+
+    let now = Utc::now();
+    if !needs_watch_renewal(&config, now) {
+        return;
+    }
+
+    log::info!(
+        "[GMAIL] Renewing watch for {} (expires {:?})",
+        config.email,
+        config.watch_expires_at
+    );
+
+    match renew_watch(db, &config, now).await {
+        Ok(expires_at) => {
+            log::info!("[GMAIL] Watch for {} renewed until {:?}", config.email, expires_at);
+            broadcaster.broadcast(GatewayEvent::custom(
+                "gmail_watch_renewed",
+                serde_json::json!({
+                    "email": config.email,
+                    "watch_expires_at": expires_at.map(|dt| dt.to_rfc3339()),
+                }),
+            ));
+        }
+        Err(e) => {
+            let lapsed = config.watch_expires_at.is_some_and(|expires_at| expires_at <= now);
+            log::error!("[GMAIL] Failed to renew watch for {}: {}", config.email, e);
+
+            if lapsed {
+                // Mark the watch as stopped so it no longer looks active
+                if let Err(e) = db.update_gmail_watch(config.id, None, config.history_id.as_deref()) {
+                    log::error!("[GMAIL] Failed to clear lapsed watch: {}", e);
+                }
+            }
+
+            broadcaster.broadcast(GatewayEvent::custom(
+                "gmail_watch_renewal_failed",
+                serde_json::json!({
+                    "email": config.email,
+                    "error": e,
+                    "watch_expires_at": config.watch_expires_at.map(|dt| dt.to_rfc3339()),
+                    "lapsed": lapsed,
+                }),
+            ));
+        }
+    }
+}
+
+/// Refresh the token if needed, then re-create the watch and store the new
+/// expiry and history ID
+async fn renew_watch(
+    db: &Database,
+    config: &GmailConfig,
+    now: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, String> {
+    let mut client = GmailClient::new(config.access_token.clone(), config.refresh_token.clone());
+
+    if needs_token_refresh(config, now) {
+        match crate::config::google_oauth_client() {
+            Some((client_id, client_secret)) => {
+                client = client.with_client_credentials(client_id, client_secret);
+                let refreshed = client.refresh_access_token().await?;
+                let token_expires_at = refreshed.expires_in.map(|secs| now + Duration::seconds(secs));
+                db.update_gmail_tokens(config.id, &refreshed.access_token, token_expires_at)
+                    .map_err(|e| format!("Failed to store refreshed token: {}", e))?;
+                log::info!("[GMAIL] Refreshed access token for {}", config.email);
+            }
+            None => log::warn!(
+                "[GMAIL] Access token may be expired but GOOGLE_OAUTH_CLIENT_ID/SECRET are not set; renewing with the stored token"
+            ),
+        }
+    }
+
+    let topic_name = format!("projects/{}/topics/{}", config.project_id, config.topic_name);
+    let labels: Vec<&str> = config.watch_labels.split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let watch = client.setup_watch("me", &topic_name, &labels).await?;
+    let expires_at = watch.expires_at();
+    db.update_gmail_watch(config.id, expires_at, Some(&watch.history_id))
+        .map_err(|e| format!("Failed to store watch info: {}", e))?;
+
+    Ok(expires_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(watch_expires_at: Option<DateTime<Utc>>, token_expires_at: Option<DateTime<Utc>>) -> GmailConfig {
+        let now = Utc::now();
+        GmailConfig {
+            id: 1,
+            email: "bot@example.com".to_string(),
+            access_token: "access".to_string(),
+            refresh_token: "refresh".to_string(),
+            token_expires_at,
+            watch_labels: "INBOX".to_string(),
+            project_id: "project".to_string(),
+            topic_name: "topic".to_string(),
+            watch_expires_at,
+            history_id: None,
+            enabled: true,
+            response_channel_id: None,
+            auto_reply: false,
+            reply_allowlist: String::new(),
+            reply_denylist: String::new(),
+            max_replies_per_thread: 3,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_needs_watch_renewal() {
+        let now = Utc::now();
+        assert!(!needs_watch_renewal(&config(None, None), now));
+        assert!(!needs_watch_renewal(&config(Some(now + Duration::days(5)), None), now));
+        assert!(needs_watch_renewal(&config(Some(now + Duration::hours(3)), None), now));
+        assert!(needs_watch_renewal(&config(Some(now - Duration::hours(1)), None), now));
+
+        let mut disabled = config(Some(now + Duration::hours(3)), None);
+        disabled.enabled = false;
+        assert!(!needs_watch_renewal(&disabled, now));
+    }
+
+    #[test]
+    fn test_needs_token_refresh() {
+        let now = Utc::now();
+        assert!(needs_token_refresh(&config(None, None), now));
+        assert!(needs_token_refresh(&config(None, Some(now + Duration::minutes(5))), now));
+        assert!(!needs_token_refresh(&config(None, Some(now + Duration::minutes(45))), now));
+    }
+}
@@ -31,6 +31,12 @@ pub struct GmailConfig {
     pub response_channel_id: Option<i64>,
     /// Whether to auto-reply to emails
     pub auto_reply: bool,
+    /// Senders (addresses or domains, comma-separated) allowed to get auto-replies; empty allows all
+    pub reply_allowlist: String,
+    /// Senders (addresses or domains, comma-separated) never auto-replied to
+    pub reply_denylist: String,
+    /// Maximum auto-replies sent per thread (0 = unlimited)
+    pub max_replies_per_thread: u32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -72,10 +78,13 @@ pub struct GmailNotificationData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GmailMessage {
     pub id: String,
+    #[serde(alias = "threadId")]
     pub thread_id: String,
+    #[serde(alias = "labelIds")]
     pub label_ids: Option<Vec<String>>,
     pub snippet: Option<String>,
     pub payload: Option<GmailMessagePayload>,
+    #[serde(alias = "internalDate")]
     pub internal_date: Option<String>,
 }
 
@@ -103,6 +112,8 @@ pub struct GmailMessagePart {
     pub part_id: Option<String>,
     #[serde(rename = "mimeType")]
     pub mime_type: Option<String>,
+    /// Set for attachments
+    pub filename: Option<String>,
     pub body: Option<GmailMessageBody>,
     pub parts: Option<Vec<GmailMessagePart>>,
 }
@@ -113,6 +124,9 @@ pub struct GmailMessageBody {
     /// Base64url-encoded body data
     pub data: Option<String>,
     pub size: Option<i64>,
+    /// Set when the content must be fetched separately (attachments)
+    #[serde(rename = "attachmentId")]
+    pub attachment_id: Option<String>,
 }
 
 /// History list response from Gmail API
@@ -139,6 +153,14 @@ pub struct GmailMessageAdded {
     pub message: GmailMessageRef,
 }
 
+/// OAuth token refresh response
+#[derive(Debug, Deserialize)]
+pub struct TokenRefresh {
+    pub access_token: String,
+    /// Lifetime of the new access token in seconds
+    pub expires_in: Option<i64>,
+}
+
 /// Reference to a Gmail message
 #[derive(Debug, Deserialize)]
 pub struct GmailMessageRef {
@@ -159,6 +181,26 @@ pub struct ParsedEmail {
     pub body: String,
     pub date: Option<String>,
     pub labels: Vec<String>,
+    /// List-Unsubscribe header (set on mailing lists and newsletters)
+    pub list_unsubscribe: Option<String>,
+    /// Precedence header (e.g. "bulk", "list")
+    pub precedence: Option<String>,
+    /// Attachments listed in the message (content not downloaded)
+    pub attachments: Vec<GmailAttachmentRef>,
+}
+
+/// An attachment on a Gmail message
+#[derive(Debug, Clone, Serialize)]
+pub struct GmailAttachmentRef {
+    /// ID for fetching the content; `None` when the data is inline
+    pub attachment_id: Option<String>,
+    /// Inline base64url data (small attachments)
+    #[serde(skip)]
+    pub inline_data: Option<String>,
+    pub filename: String,
+    pub mime_type: String,
+    /// Size in bytes as reported by Gmail
+    pub size: u64,
 }
 
 impl GmailMessage {
@@ -182,6 +224,12 @@ impl GmailMessage {
         let snippet = self.snippet.clone().unwrap_or_default();
         let body = self.extract_body().unwrap_or_default();
         let labels = self.label_ids.clone().unwrap_or_default();
+        let list_unsubscribe = self.get_header("List-Unsubscribe");
+        let precedence = self.get_header("Precedence");
+        let mut attachments = Vec::new();
+        if let Some(parts) = self.payload.as_ref().and_then(|p| p.parts.as_ref()) {
+            collect_attachments(parts, &mut attachments);
+        }
 
         ParsedEmail {
             message_id: self.id.clone(),
@@ -193,6 +241,9 @@ impl GmailMessage {
             body,
             date,
             labels,
+            list_unsubscribe,
+            precedence,
+            attachments,
         }
     }
 
@@ -220,10 +271,35 @@ impl GmailMessage {
 
 /// Decode base64url-encoded string (Gmail uses URL-safe base64)
 fn base64_url_decode(input: &str) -> Result<String, String> {
+    let bytes = base64_url_decode_bytes(input)?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// Decode base64url-encoded binary data, with or without padding
+pub(super) fn base64_url_decode_bytes(input: &str) -> Result<Vec<u8>, String> {
     use base64::Engine;
     let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
-    let bytes = engine.decode(input).map_err(|e| e.to_string())?;
-    String::from_utf8(bytes).map_err(|e| e.to_string())
+    engine.decode(input.trim_end_matches('=')).map_err(|e| e.to_string())
+}
+
+/// Recursively collect parts that carry a filename (attachments)
+fn collect_attachments(parts: &[GmailMessagePart], out: &mut Vec<GmailAttachmentRef>) {
+    for part in parts {
+        let filename = part.filename.as_deref().unwrap_or("").trim();
+        if !filename.is_empty() {
+            let body = part.body.as_ref();
+            out.push(GmailAttachmentRef {
+                attachment_id: body.and_then(|b| b.attachment_id.clone()),
+                inline_data: body.and_then(|b| b.data.clone()),
+                filename: filename.to_string(),
+                mime_type: part.mime_type.clone().unwrap_or_else(|| "application/octet-stream".to_string()),
+                size: body.and_then(|b| b.size).unwrap_or(0).max(0) as u64,
+            });
+        }
+        if let Some(nested) = &part.parts {
+            collect_attachments(nested, out);
+        }
+    }
 }
 
 /// Recursively extract text/plain content from message parts
@@ -300,6 +376,9 @@ pub struct SetupGmailRequest {
     pub watch_labels: Option<String>,
     pub response_channel_id: Option<i64>,
     pub auto_reply: Option<bool>,
+    pub reply_allowlist: Option<String>,
+    pub reply_denylist: Option<String>,
+    pub max_replies_per_thread: Option<u32>,
 }
 
 /// Request to update Gmail integration
@@ -309,6 +388,9 @@ pub struct UpdateGmailRequest {
     pub response_channel_id: Option<i64>,
     pub auto_reply: Option<bool>,
     pub enabled: Option<bool>,
+    pub reply_allowlist: Option<String>,
+    pub reply_denylist: Option<String>,
+    pub max_replies_per_thread: Option<u32>,
 }
 
 /// Response for Gmail config operations
@@ -330,6 +412,9 @@ pub struct GmailConfigSummary {
     pub watch_expires_at: Option<DateTime<Utc>>,
     pub enabled: bool,
     pub auto_reply: bool,
+    pub reply_allowlist: String,
+    pub reply_denylist: String,
+    pub max_replies_per_thread: u32,
     pub response_channel_id: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -346,6 +431,9 @@ impl From<GmailConfig> for GmailConfigSummary {
             watch_expires_at: config.watch_expires_at,
             enabled: config.enabled,
             auto_reply: config.auto_reply,
+            reply_allowlist: config.reply_allowlist,
+            reply_denylist: config.reply_denylist,
+            max_replies_per_thread: config.max_replies_per_thread,
             response_channel_id: config.response_channel_id,
             created_at: config.created_at,
             updated_at: config.updated_at,
@@ -1,7 +1,8 @@
 //! Keystore API client with SIWE authentication
 //!
 //! Handles authenticated access to the keystore.defirelay.com API for
-//! storing and retrieving encrypted API key backups.
+//! storing and retrieving encrypted API key backups, and for remote signing
+//! with a keystore-held wallet (see `crate::wallet`).
 
 use ethers::signers::{LocalWallet, Signer};
 use serde::{Deserialize, Serialize};
@@ -67,6 +68,27 @@ pub struct StoreKeysResponse {
     pub error: Option<String>,
 }
 
+#[derive(Serialize)]
+struct SignerAddressRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wallet: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct SignHashRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wallet: Option<&'a str>,
+    hash: String,
+}
+
+#[derive(Deserialize)]
+struct SignerResponse {
+    success: bool,
+    address: Option<String>,
+    signature: Option<String>,
+    error: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct GetKeysResponse {
     pub success: bool,
@@ -314,6 +336,64 @@ impl KeystoreClient {
             .map_err(|e| format!("Failed to parse response: {}", e))
     }
 
+    /// POST to a remote signer endpoint authenticated with the signer token
+    async fn signer_request<T: Serialize>(
+        &self,
+        token: &str,
+        path: &str,
+        body: &T,
+    ) -> Result<SignerResponse, String> {
+        let resp = self
+            .http_client
+            .post(format!("{}{}", KEYSTORE_API, path))
+            .header("Authorization", format!("Bearer {}", token))
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to connect to keystore: {}", e))?;
+
+        if !resp.status().is_success() {
+            return Err(format!("Keystore signer request failed with status: {}", resp.status()));
+        }
+
+        let data: SignerResponse = resp
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse signer response: {}", e))?;
+
+        if !data.success {
+            return Err(data.error.unwrap_or_else(|| "Keystore signer request failed".to_string()));
+        }
+        Ok(data)
+    }
+
+    /// Get the address of a keystore-held signing wallet
+    /// (`None` is the account's default wallet)
+    pub async fn signer_address(&self, token: &str, wallet: Option<&str>) -> Result<String, String> {
+        self.signer_request(token, "/api/signer/address", &SignerAddressRequest { wallet })
+            .await?
+            .address
+            .ok_or_else(|| "No address in signer response".to_string())
+    }
+
+    /// Sign a 32-byte digest with a keystore-held wallet.
+    /// Returns the 65-byte `r || s || v` signature as hex.
+    pub async fn sign_hash(
+        &self,
+        token: &str,
+        wallet: Option<&str>,
+        hash: [u8; 32],
+    ) -> Result<String, String> {
+        let request = SignHashRequest {
+            wallet,
+            hash: format!("0x{}", hex::encode(hash)),
+        };
+        self.signer_request(token, "/api/signer/sign_hash", &request)
+            .await?
+            .signature
+            .ok_or_else(|| "No signature in signer response".to_string())
+    }
+
     /// Clear the cached session (for testing or logout)
     pub async fn clear_session(&self) {
         let mut session = self.session.write().await;
@@ -0,0 +1,274 @@
+//! Process-wide logger
+//!
+//! Wraps env_logger so its filter can be replaced at runtime (`PUT
+//! /api/logging/level`) without a restart. Records are written as text by
+//! default, or as one JSON object per line when `STARK_LOG_FORMAT=json`, with
+//! the channel, session and tool a message mentions pulled out into fields
+//! for log aggregators. Both formats redact installed secrets.
+
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use env_logger::fmt::Formatter;
+use log::{LevelFilter, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::tools::redaction;
+
+/// Filter used when `RUST_LOG` is unset, matching env_logger's default
+const DEFAULT_FILTER: &str = "error";
+
+static LOGGER: Lazy<ReloadableLogger> = Lazy::new(|| {
+    let filter = std::env::var("RUST_LOG")
+        .ok()
+        .filter(|f| !f.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_FILTER.to_string());
+    let json = crate::config::log_format_json();
+    ReloadableLogger {
+        json,
+        state: RwLock::new(LoggerState {
+            inner: build_logger(&filter, json),
+            filter,
+        }),
+    }
+});
+
+// "channel 12", "channel_id=12", "channel_id: 12"
+static CHANNEL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bchannel(?:_id)?(?:\s*[=:]\s*|\s+)(\d+)\b").unwrap());
+// "session 7", "session id=7", "session_id=7"
+static SESSION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bsession(?:_id|\s+id)?(?:\s*[=:]\s*|\s+)(\d+)\b").unwrap());
+// "tool_name=exec", "Tool 'exec'", "tool: exec"
+static TOOL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:tool_name\s*[=:]\s*'?|tool\s+'|tool:\s*)([a-z][a-z0-9_]*)").unwrap()
+});
+// Tools tag their messages with their name: "[x402_fetch] ..."
+static TOOL_TAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[([a-z][a-z0-9_]*)\]").unwrap());
+
+/// env_logger behind a lock, so the filter can be swapped while running
+struct ReloadableLogger {
+    json: bool,
+    state: RwLock<LoggerState>,
+}
+
+struct LoggerState {
+    /// The filter directives in `RUST_LOG` syntax
+    filter: String,
+    inner: env_logger::Logger,
+}
+
+impl Log for ReloadableLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.state.read().map(|s| s.inner.enabled(metadata)).unwrap_or(false)
+    }
+
+    fn log(&self, record: &Record) {
+        if let Ok(state) = self.state.read() {
+            state.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(state) = self.state.read() {
+            state.inner.flush();
+        }
+    }
+}
+
+/// The logger's current settings, as reported by the logging API
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LogSettings {
+    /// Filter directives in `RUST_LOG` syntax
+    pub filter: String,
+    /// Most verbose level any directive allows
+    pub max_level: String,
+    /// "text" or "json"
+    pub format: &'static str,
+}
+
+fn build_logger(filter: &str, json: bool) -> env_logger::Logger {
+    let mut builder = env_logger::Builder::new();
+    builder.parse_filters(filter);
+    if json {
+        builder.write_style(env_logger::WriteStyle::Never).format(format_json);
+    } else {
+        if let Ok(style) = std::env::var("RUST_LOG_STYLE") {
+            builder.parse_write_style(&style);
+        }
+        builder.format(format_text);
+    }
+    builder.build()
+}
+
+/// Install the logger, filtered by `RUST_LOG`
+pub fn init() {
+    let max_level = LOGGER.state.read().map(|s| s.inner.filter()).unwrap_or(LevelFilter::Error);
+    if log::set_logger(&*LOGGER).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Replace the filter with `filter` (`RUST_LOG` syntax, e.g.
+/// "info,stark_backend::tools=debug"). Not persisted: a restart goes back to
+/// `RUST_LOG`.
+pub fn set_filter(filter: &str) -> Result<LogSettings, String> {
+    let filter = filter.trim();
+    validate_filter(filter)?;
+
+    let inner = build_logger(filter, LOGGER.json);
+    let max_level = inner.filter();
+    {
+        let mut state = LOGGER.state.write().map_err(|_| "Logger lock poisoned".to_string())?;
+        state.inner = inner;
+        state.filter = filter.to_string();
+    }
+    log::set_max_level(max_level);
+    log::info!("[LOGGING] Log filter set to '{}'", filter);
+    Ok(settings())
+}
+
+/// The logger's current filter and format
+pub fn settings() -> LogSettings {
+    let (filter, max_level) = LOGGER
+        .state
+        .read()
+        .map(|s| (s.filter.clone(), s.inner.filter()))
+        .unwrap_or_else(|_| (String::new(), LevelFilter::Off));
+    LogSettings {
+        filter,
+        max_level: max_level.to_string().to_lowercase(),
+        format: if LOGGER.json { "json" } else { "text" },
+    }
+}
+
+/// Check filter directives before applying them; env_logger would only print
+/// a warning and ignore the invalid ones
+fn validate_filter(filter: &str) -> Result<(), String> {
+    // Anything after '/' is a message pattern, not a directive
+    let directives = filter.split('/').next().unwrap_or_default();
+    if directives.trim().is_empty() {
+        return Err("Log filter must not be empty".to_string());
+    }
+
+    for directive in directives.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+        let level = match directive.split_once('=') {
+            Some((module, level)) => {
+                if module.trim().is_empty() {
+                    return Err(format!("Missing module in directive '{}'", directive));
+                }
+                level
+            }
+            // A bare word is a level, or a module logged at every level
+            None if LevelFilter::from_str(directive).is_ok() => directive,
+            None => continue,
+        };
+        if LevelFilter::from_str(level.trim()).is_err() {
+            return Err(format!(
+                "Invalid level '{}' in directive '{}' (use off, error, warn, info, debug or trace)",
+                level.trim(),
+                directive
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn format_text(buf: &mut Formatter, record: &Record) -> std::io::Result<()> {
+    let message = redaction::active().redact(&record.args().to_string());
+    let level_style = buf.default_level_style(record.level());
+    writeln!(
+        buf,
+        "[{} {level_style}{:<5}{level_style:#} {}] {}",
+        buf.timestamp(),
+        record.level(),
+        record.target(),
+        message
+    )
+}
+
+fn format_json(buf: &mut Formatter, record: &Record) -> std::io::Result<()> {
+    let message = redaction::active().redact(&record.args().to_string());
+    let line = json_record(
+        &chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        record.level().as_str(),
+        record.target(),
+        &message,
+    );
+    writeln!(buf, "{}", line)
+}
+
+/// A log record as a JSON object, with the fields its message mentions
+fn json_record(timestamp: &str, level: &str, target: &str, message: &str) -> Value {
+    let mut line = json!({
+        "timestamp": timestamp,
+        "level": level,
+        "target": target,
+        "message": message,
+    });
+
+    let capture = |re: &Regex| re.captures(message).map(|c| c[1].to_string());
+    if let Some(id) = capture(&CHANNEL_RE).and_then(|id| id.parse::<i64>().ok()) {
+        line["channel_id"] = json!(id);
+    }
+    if let Some(id) = capture(&SESSION_RE).and_then(|id| id.parse::<i64>().ok()) {
+        line["session_id"] = json!(id);
+    }
+    if let Some(tool) = capture(&TOOL_RE).or_else(|| capture(&TOOL_TAG_RE)) {
+        line["tool_name"] = json!(tool);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(message: &str) -> Value {
+        json_record("2026-01-01T00:00:00.000Z", "INFO", "stark_backend", message)
+    }
+
+    #[test]
+    fn test_json_record_extracts_fields() {
+        let line = fields("[SUBAGENT_MANAGER] Cancelling subagent 3 for channel 12");
+        assert_eq!(line["channel_id"], json!(12));
+        assert!(line.get("session_id").is_none());
+
+        let line = fields("[HEARTBEAT-AI] channel_type=telegram, channel_id=5, chat_id=9");
+        assert_eq!(line["channel_id"], json!(5));
+
+        let line = fields("[HEARTBEAT-AI] Found session id=42, updating heartbeat config");
+        assert_eq!(line["session_id"], json!(42));
+
+        let line = fields("[REGISTRY] Tool 'web_fetch' timed out after 30s");
+        assert_eq!(line["tool_name"], json!("web_fetch"));
+
+        let line = fields("[x402_inspect] Preset 'swap_quote' asks for 1.00 USDC");
+        assert_eq!(line["tool_name"], json!("x402_inspect"));
+        assert_eq!(line["message"], json!("[x402_inspect] Preset 'swap_quote' asks for 1.00 USDC"));
+    }
+
+    #[test]
+    fn test_json_record_ignores_lookalikes() {
+        let line = fields("[ORCHESTRATED_LOOP] Saving error summary with 3 tool calls");
+        assert!(line.get("tool_name").is_none());
+
+        let line = fields("Failed to get channel: database is locked");
+        assert!(line.get("channel_id").is_none());
+    }
+
+    #[test]
+    fn test_validate_filter() {
+        assert!(validate_filter("debug").is_ok());
+        assert!(validate_filter("info,stark_backend::tools=trace").is_ok());
+        assert!(validate_filter("stark_backend").is_ok());
+        assert!(validate_filter("warn/timed out").is_ok());
+
+        assert!(validate_filter("").is_err());
+        assert!(validate_filter("stark_backend=loud").is_err());
+        assert!(validate_filter("=debug").is_err());
+    }
+}
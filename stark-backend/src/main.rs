@@ -27,6 +27,9 @@ mod hooks;
 mod tool_validators;
 mod tx_queue;
 mod keystore_client;
+mod logging;
+mod wallet;
+mod metrics;
 
 use channels::{ChannelManager, MessageDispatcher};
 use tx_queue::TxQueueManager;
@@ -67,7 +70,7 @@ async fn spa_fallback() -> actix_web::Result<NamedFile> {
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
-    env_logger::init();
+    logging::init();
 
     // Load presets and tokens from config directory
     // Check ./config first, then ../config (for running from subdirectory)
@@ -87,6 +90,16 @@ async fn main() -> std::io::Result<()> {
     tools::builtin::cryptocurrency::network_lookup::load_networks(config_dir);
     log::info!("Loading RPC provider configs from config directory");
     tools::rpc_config::load_rpc_providers(config_dir);
+    tools::config_reload::set_config_dir(config_dir);
+
+    // Optionally watch token/network/RPC configs for changes
+    let config_watch_interval = config::config_watch_interval_secs();
+    if config_watch_interval > 0 {
+        tools::config_reload::spawn_watcher(
+            config_dir.to_path_buf(),
+            std::time::Duration::from_secs(config_watch_interval),
+        );
+    }
 
     let config = Config::from_env();
     let port = config.port;
@@ -101,6 +114,10 @@ async fn main() -> std::io::Result<()> {
     let db = Database::new(&config.database_url).expect("Failed to initialize database");
     let db = Arc::new(db);
 
+    // Web3 presets saved through the API override the file presets
+    tools::presets::load_db_web3_presets(&db);
+    tools::abis::load_db_abis(&db);
+
     // Initialize Tool Registry with built-in tools
     log::info!("Initializing tool registry");
     let tool_registry = Arc::new(tools::create_default_registry());
@@ -117,33 +134,45 @@ async fn main() -> std::io::Result<()> {
     });
     log::info!("Loaded {} skills from disk, {} total in database", skill_count, skill_registry.len());
 
-    // Initialize Transaction Queue Manager with DB for persistent broadcast history
+    // Initialize Transaction Queue Manager with DB for the persistent queue and broadcast history
     // NOTE: Must be created before Gateway so channels can use it for web3 transactions
     log::info!("Initializing transaction queue manager");
     let tx_queue = Arc::new(TxQueueManager::with_db(db.clone()));
+    tx_queue.start_sweeper();
+    let reconcile_queue = tx_queue.clone();
+    tokio::spawn(async move {
+        let dropped = reconcile_queue.reconcile_nonces().await;
+        if dropped > 0 {
+            log::info!("Dropped {} queued transactions whose nonce was already used", dropped);
+        }
+    });
+
+    // Initialize Hook Manager
+    log::info!("Initializing hook manager");
+    let hook_manager = Arc::new(HookManager::new());
+    hook_manager.register(Arc::new(hooks::builtin::ModerationHook::new(db.clone())));
+    log::info!("Hook manager initialized with {} hooks", hook_manager.hook_count());
+
+    // Initialize Tool Validator Registry
+    log::info!("Initializing tool validator registry");
+    let validator_registry = Arc::new(tool_validators::create_default_registry());
+    log::info!("Registered {} tool validators", validator_registry.len());
 
-    // Initialize Gateway with tool registry, wallet, and tx_queue for channels
+    // Initialize Gateway with tool registry, wallet, tx_queue and hooks for channels
     log::info!("Initializing Gateway");
     let gateway = Arc::new(Gateway::new_with_tools_wallet_and_tx_queue(
         db.clone(),
         tool_registry.clone(),
         config.burner_wallet_private_key.clone(),
         Some(tx_queue.clone()),
+        Some(hook_manager.clone()),
+        Some(validator_registry.clone()),
     ));
 
-    // Initialize Execution Tracker for progress display
+    // Share the channel manager's Execution Tracker for progress display, so a
+    // cancel_execution from the gateway reaches web and external channels alike
     log::info!("Initializing execution tracker");
-    let execution_tracker = Arc::new(ExecutionTracker::new(gateway.broadcaster().clone()));
-
-    // Initialize Hook Manager
-    log::info!("Initializing hook manager");
-    let hook_manager = Arc::new(HookManager::new());
-    log::info!("Hook manager initialized");
-
-    // Initialize Tool Validator Registry
-    log::info!("Initializing tool validator registry");
-    let validator_registry = Arc::new(tool_validators::create_default_registry());
-    log::info!("Registered {} tool validators", validator_registry.len());
+    let execution_tracker = gateway.channel_manager().execution_tracker();
 
     // Create the shared MessageDispatcher for all message processing
     log::info!("Initializing message dispatcher");
@@ -176,7 +205,8 @@ async fn main() -> std::io::Result<()> {
         dispatcher.clone(),
         gateway.broadcaster().clone(),
         scheduler_config,
-    ));
+    )
+    .with_channel_manager(gateway.channel_manager()));
 
     // Start scheduler background task
     let scheduler_handle = Arc::clone(&scheduler);
@@ -248,6 +278,8 @@ async fn main() -> std::io::Result<()> {
             .wrap(Logger::default())
             .wrap(cors)
             .configure(controllers::health::config_routes)
+            .configure(controllers::backup::config_routes)
+            .configure(controllers::metrics::config_routes)
             .configure(controllers::auth::config)
             .configure(controllers::dashboard::config)
             .configure(controllers::chat::config)
@@ -255,6 +287,7 @@ async fn main() -> std::io::Result<()> {
             .configure(controllers::channels::config)
             .configure(controllers::agent_settings::configure)
             .configure(controllers::sessions::config)
+            .configure(controllers::execution::config)
             .configure(controllers::identity::config)
             .configure(controllers::tools::config)
             .configure(controllers::skills::config)
@@ -268,7 +301,17 @@ async fn main() -> std::io::Result<()> {
             .configure(controllers::tx_queue::config)
             .configure(controllers::broadcasted_transactions::config)
             .configure(controllers::mindmap::config)
+            .configure(controllers::personas::config)
+            .configure(controllers::wallets::config)
+            .configure(controllers::portfolio::config)
+            .configure(controllers::limit_orders::config)
+            .configure(controllers::presets::config)
+            .configure(controllers::abis::config)
+            .configure(controllers::config_reload::config)
+            .configure(controllers::config_audit::config)
+            .configure(controllers::logging::config)
             .configure(controllers::memory::config)
+            .configure(controllers::webhook::config)
             // WebSocket Gateway route (same port as HTTP, required for single-port platforms)
             .route("/ws", web::get().to(gateway::actix_ws::ws_handler));
 
@@ -0,0 +1,221 @@
+//! Prometheus metrics
+//!
+//! Counters and histograms are recorded at the existing instrumentation points
+//! (dispatcher, tool registry, AI client, event broadcaster). Gauges for current
+//! state are refreshed when `/metrics` is scraped.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use prometheus::{
+    CounterVec, Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+
+use crate::db::Database;
+use crate::gateway::protocol::GatewayEvent;
+use crate::tx_queue::{QueuedTxStatus, TxQueueManager};
+
+static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Latency buckets (seconds) for AI requests, which routinely take tens of seconds
+const AI_LATENCY_BUCKETS: &[f64] = &[0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0];
+
+const TX_STATUSES: &[QueuedTxStatus] = &[
+    QueuedTxStatus::Pending,
+    QueuedTxStatus::Broadcasting,
+    QueuedTxStatus::Broadcast,
+    QueuedTxStatus::Confirmed,
+    QueuedTxStatus::Failed,
+    QueuedTxStatus::Expired,
+];
+
+fn register<C: prometheus::core::Collector + Clone + 'static>(collector: C) -> C {
+    REGISTRY
+        .register(Box::new(collector.clone()))
+        .expect("metric registered twice");
+    collector
+}
+
+static MESSAGES_DISPATCHED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new("starkbot_messages_dispatched_total", "Messages dispatched to the agent"),
+            &["channel_type", "channel_id"],
+        )
+        .unwrap(),
+    )
+});
+
+static TOOL_EXECUTIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new("starkbot_tool_executions_total", "Tool executions by tool and outcome"),
+            &["tool", "success"],
+        )
+        .unwrap(),
+    )
+});
+
+static TOOL_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register(
+        HistogramVec::new(
+            HistogramOpts::new("starkbot_tool_duration_seconds", "Tool execution time"),
+            &["tool"],
+        )
+        .unwrap(),
+    )
+});
+
+static AI_REQUEST_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register(
+        HistogramVec::new(
+            HistogramOpts::new("starkbot_ai_request_duration_seconds", "AI provider request latency")
+                .buckets(AI_LATENCY_BUCKETS.to_vec()),
+            &["provider", "success"],
+        )
+        .unwrap(),
+    )
+});
+
+static X402_PAYMENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(Opts::new("starkbot_x402_payments_total", "x402 payments made"), &["asset"]).unwrap(),
+    )
+});
+
+static X402_PAYMENT_AMOUNT: Lazy<CounterVec> = Lazy::new(|| {
+    register(
+        CounterVec::new(
+            Opts::new(
+                "starkbot_x402_payment_amount_total",
+                "Total x402 payment amount in the asset's base units",
+            ),
+            &["asset"],
+        )
+        .unwrap(),
+    )
+});
+
+static GATEWAY_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(Opts::new("starkbot_gateway_events_total", "Gateway events broadcast"), &["event"])
+            .unwrap(),
+    )
+});
+
+static ACTIVE_SESSIONS: Lazy<IntGauge> = Lazy::new(|| {
+    register(IntGauge::new("starkbot_active_sessions", "Active chat sessions").unwrap())
+});
+
+static TX_QUEUE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new("starkbot_tx_queue_transactions", "Queued transactions by status"),
+            &["status"],
+        )
+        .unwrap(),
+    )
+});
+
+/// Count a message handed to the dispatcher
+pub fn record_dispatch(channel_type: &str, channel_id: i64) {
+    MESSAGES_DISPATCHED
+        .with_label_values(&[channel_type, &channel_id.to_string()])
+        .inc();
+}
+
+/// Count a tool execution and its duration
+pub fn record_tool_execution(tool: &str, success: bool, duration: Duration) {
+    TOOL_EXECUTIONS
+        .with_label_values(&[tool, if success { "true" } else { "false" }])
+        .inc();
+    TOOL_DURATION.with_label_values(&[tool]).observe(duration.as_secs_f64());
+}
+
+/// Run an AI request, recording its latency and outcome
+pub async fn time_ai_request<T, E, F>(provider: &str, request: F) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let started = Instant::now();
+    let result = request.await;
+    AI_REQUEST_DURATION
+        .with_label_values(&[provider, if result.is_ok() { "true" } else { "false" }])
+        .observe(started.elapsed().as_secs_f64());
+    result
+}
+
+/// Count a broadcast gateway event, and x402 payments it reports
+pub fn record_event(event: &GatewayEvent) {
+    GATEWAY_EVENTS.with_label_values(&[&event.event]).inc();
+
+    if event.event == "x402.payment" {
+        let asset = event.data.get("asset").and_then(|v| v.as_str()).unwrap_or("unknown");
+        X402_PAYMENTS.with_label_values(&[asset]).inc();
+        let amount = event
+            .data
+            .get("amount")
+            .and_then(|v| v.as_str())
+            .and_then(|a| a.parse::<f64>().ok());
+        if let Some(amount) = amount {
+            X402_PAYMENT_AMOUNT.with_label_values(&[asset]).inc_by(amount);
+        }
+    }
+}
+
+/// Refresh the state gauges
+pub fn refresh_gauges(db: &Database, tx_queue: &TxQueueManager) {
+    match db.count_active_chat_sessions() {
+        Ok(count) => ACTIVE_SESSIONS.set(count),
+        Err(e) => log::warn!("[METRICS] Failed to count active sessions: {}", e),
+    }
+    for status in TX_STATUSES {
+        TX_QUEUE
+            .with_label_values(&[&status.to_string()])
+            .set(tx_queue.count_by_status(*status) as i64);
+    }
+}
+
+/// Render every metric in the Prometheus text exposition format
+pub fn render() -> Result<String, String> {
+    // Touch the lazies so metrics without samples still show up
+    Lazy::force(&MESSAGES_DISPATCHED);
+    Lazy::force(&TOOL_EXECUTIONS);
+    Lazy::force(&TOOL_DURATION);
+    Lazy::force(&AI_REQUEST_DURATION);
+    Lazy::force(&X402_PAYMENTS);
+    Lazy::force(&X402_PAYMENT_AMOUNT);
+    Lazy::force(&GATEWAY_EVENTS);
+    Lazy::force(&ACTIVE_SESSIONS);
+    Lazy::force(&TX_QUEUE);
+
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(buffer).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_render_exposition_format() {
+        record_dispatch("discord", 7);
+        record_tool_execution("metrics_test_tool", true, Duration::from_millis(20));
+        let _: Result<(), String> = time_ai_request("claude", async { Ok(()) }).await;
+        record_event(&GatewayEvent::x402_payment(7, "1500", "0.0015", "USDC", "0xabc", None));
+
+        let text = render().unwrap();
+        assert!(text.contains("# TYPE starkbot_messages_dispatched_total counter"));
+        assert!(text.contains("starkbot_messages_dispatched_total{channel_id=\"7\",channel_type=\"discord\"}"));
+        assert!(text.contains("starkbot_tool_executions_total{success=\"true\",tool=\"metrics_test_tool\"} 1"));
+        assert!(text.contains("starkbot_tool_duration_seconds_bucket{tool=\"metrics_test_tool\""));
+        assert!(text.contains("starkbot_ai_request_duration_seconds_count{provider=\"claude\",success=\"true\"}"));
+        assert!(text.contains("starkbot_x402_payments_total{asset=\"USDC\"}"));
+        assert!(text.contains("# TYPE starkbot_active_sessions gauge"));
+    }
+}
@@ -5,6 +5,121 @@ use std::collections::HashMap;
 /// Default max tool iterations
 pub const DEFAULT_MAX_TOOL_ITERATIONS: i32 = 100;
 
+/// Default moderation endpoint (OpenAI-compatible moderation API)
+pub const DEFAULT_MODERATION_ENDPOINT: &str = "https://api.openai.com/v1/moderations";
+
+/// What to do with a message when the moderation endpoint can't be reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationFailurePolicy {
+    /// Let the message through unchecked
+    #[default]
+    Open,
+    /// Refuse the message
+    Closed,
+}
+
+/// Content moderation settings, checked by the built-in ModerationHook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModerationSettings {
+    /// Check inbound user messages before they reach the AI
+    pub enabled: bool,
+    /// Also check the bot's responses before they are sent
+    pub check_responses: bool,
+    /// Moderation endpoint; must accept `{"input": "..."}` and return
+    /// OpenAI-style `results[].flagged` (or a top-level `flagged`)
+    pub endpoint: String,
+    /// Behaviour when the endpoint errors or times out
+    pub failure_policy: ModerationFailurePolicy,
+    /// Reply sent in place of flagged content
+    pub refusal_message: String,
+}
+
+impl Default for ModerationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            check_responses: false,
+            endpoint: DEFAULT_MODERATION_ENDPOINT.to_string(),
+            failure_policy: ModerationFailurePolicy::Open,
+            refusal_message: "Sorry, I can't help with that message.".to_string(),
+        }
+    }
+}
+
+/// Default native balance to keep for gas after a transfer
+pub const DEFAULT_MIN_GAS_RESERVE: &str = "0.001";
+
+/// Transfer caps for one asset. Amounts are human-readable decimal strings
+/// (e.g. "0.5"); `None` means no cap.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransferCap {
+    /// Largest single transfer; exceeding it needs `force: true`
+    pub per_tx: Option<String>,
+    /// Total sent by one wallet over the last 24 hours; exceeding it needs `force: true`
+    pub per_day: Option<String>,
+    /// Transfers above this always wait for user confirmation before
+    /// broadcast, even in rogue mode
+    pub confirm_above: Option<String>,
+}
+
+/// Transfer amount guardrails, enforced by send_eth and web3_function_call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransferLimits {
+    /// Caps on native ETH transfers, on any network whose native currency is ETH
+    pub native: TransferCap,
+    /// Caps on ERC-20 transfers, keyed by token symbol from tokens.ron (e.g.
+    /// "USDC"), and on other native currencies by their networks.ron symbol
+    /// (e.g. "POL")
+    pub tokens: HashMap<String, TransferCap>,
+    /// Warn when a transfer would leave less than this much native currency for gas
+    pub min_gas_reserve: String,
+}
+
+impl Default for TransferLimits {
+    fn default() -> Self {
+        Self {
+            native: TransferCap::default(),
+            tokens: HashMap::new(),
+            min_gas_reserve: DEFAULT_MIN_GAS_RESERVE.to_string(),
+        }
+    }
+}
+
+impl TransferLimits {
+    /// Check every configured amount is a plain decimal number like "0.5"
+    pub fn validate(&self) -> Result<(), String> {
+        fn is_amount(s: &str) -> bool {
+            let s = s.trim();
+            let mut parts = s.splitn(2, '.');
+            let int = parts.next().unwrap_or("");
+            let frac = parts.next().unwrap_or("");
+            !(int.is_empty() && frac.is_empty())
+                && int.chars().all(|c| c.is_ascii_digit())
+                && frac.chars().all(|c| c.is_ascii_digit())
+        }
+
+        let caps = std::iter::once(("native", &self.native))
+            .chain(self.tokens.iter().map(|(symbol, cap)| (symbol.as_str(), cap)));
+        for (asset, cap) in caps {
+            for (field, value) in [("per_tx", &cap.per_tx), ("per_day", &cap.per_day), ("confirm_above", &cap.confirm_above)] {
+                if let Some(value) = value {
+                    if !is_amount(value) {
+                        return Err(format!("Invalid {} {} limit: '{}'", asset, field, value));
+                    }
+                }
+            }
+        }
+        if !is_amount(&self.min_gas_reserve) {
+            return Err(format!("Invalid min_gas_reserve: '{}'", self.min_gas_reserve));
+        }
+        Ok(())
+    }
+}
+
 /// Bot settings stored in database
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotSettings {
@@ -18,8 +133,24 @@ pub struct BotSettings {
     pub custom_rpc_endpoints: Option<HashMap<String, String>>,
     /// Maximum number of tool execution iterations per request
     pub max_tool_iterations: i32,
-    /// Rogue mode: when true, bot operates in "rogue" mode instead of "partner" mode
+    /// Rogue mode: the agent acts without a human in the loop. It skips the exec
+    /// allowlist (the denylist still applies), may send transactions from
+    /// gateway channels, and broadcasts queued transactions without partner
+    /// confirmation. Every tool call made while it is on is written to the
+    /// rogue_mode_audit table.
     pub rogue_mode_enabled: bool,
+    /// Executables the exec tool may run (empty = no allowlist). Bypassed in rogue mode.
+    #[serde(default)]
+    pub exec_allowlist: Vec<String>,
+    /// Command patterns the exec tool always refuses, e.g. "rm -rf" or "curl | sh"
+    #[serde(default)]
+    pub exec_denylist: Vec<String>,
+    /// Content moderation for inbound messages and responses
+    #[serde(default)]
+    pub moderation: ModerationSettings,
+    /// Per-transaction and per-day transfer caps
+    #[serde(default)]
+    pub transfer_limits: TransferLimits,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -35,6 +166,10 @@ impl Default for BotSettings {
             custom_rpc_endpoints: None,
             max_tool_iterations: DEFAULT_MAX_TOOL_ITERATIONS,
             rogue_mode_enabled: false,
+            exec_allowlist: Vec::new(),
+            exec_denylist: Vec::new(),
+            moderation: ModerationSettings::default(),
+            transfer_limits: TransferLimits::default(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -51,4 +186,10 @@ pub struct UpdateBotSettingsRequest {
     pub custom_rpc_endpoints: Option<HashMap<String, String>>,
     pub max_tool_iterations: Option<i32>,
     pub rogue_mode_enabled: Option<bool>,
+    /// Must be true alongside `rogue_mode_enabled: true` to switch rogue mode on
+    pub confirm_rogue_mode: Option<bool>,
+    pub exec_allowlist: Option<Vec<String>>,
+    pub exec_denylist: Option<Vec<String>>,
+    pub moderation: Option<ModerationSettings>,
+    pub transfer_limits: Option<TransferLimits>,
 }
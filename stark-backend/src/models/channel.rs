@@ -1,3 +1,4 @@
+use crate::channels::ChannelConnection;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -68,6 +69,8 @@ pub struct ChannelResponse {
     pub updated_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub running: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<ChannelConnection>,
 }
 
 impl From<Channel> for ChannelResponse {
@@ -82,6 +85,7 @@ impl From<Channel> for ChannelResponse {
             created_at: channel.created_at,
             updated_at: channel.updated_at,
             running: None,
+            connection: None,
         }
     }
 }
@@ -91,6 +95,11 @@ impl ChannelResponse {
         self.running = Some(running);
         self
     }
+
+    pub fn with_connection(mut self, connection: Option<ChannelConnection>) -> Self {
+        self.connection = connection;
+        self
+    }
 }
 
 /// Request type for creating a channel
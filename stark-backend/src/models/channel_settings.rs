@@ -41,6 +41,30 @@ pub enum ChannelSettingKey {
     DiscordToolCallVerbosity,
     /// Discord: How verbose tool result output should be (full, minimal, none)
     DiscordToolResultVerbosity,
+    /// Discord: Render ```embed JSON blocks in replies as rich embeds (true/false)
+    DiscordRichEmbeds,
+    /// Discord: Role ID -> allowed commands/tools for non-admin users
+    DiscordRolePermissions,
+    /// Discord: Seconds an admin's "query" mode stays active before expiring
+    DiscordQueryTimeoutSecs,
+    /// Any channel: Name of the persona used for this channel's system prompt
+    Persona,
+    /// Any channel: Named wallet that wallet tools use by default
+    Wallet,
+    /// Any channel: Hold task plans until a user approves them (true/false)
+    PlanApproval,
+    /// Any channel: Language to reply in when a message's language can't be detected
+    DefaultLanguage,
+    /// Any channel: Ordered, comma-separated post-processing stages for replies
+    ResponsePipeline,
+    /// Any channel: Text appended to replies by the `disclaimer` stage
+    ResponseDisclaimer,
+    /// Any channel: Most session messages loaded into the prompt (trimmed further to fit the model)
+    HistoryMessages,
+    /// Any channel: How much of the tool-call log replies start with (full, minimal, none)
+    ToolLog,
+    /// Discord/Telegram: Re-run a user's latest message when an edit changes what it asks
+    RedispatchEdits,
 }
 
 impl ChannelSettingKey {
@@ -50,6 +74,18 @@ impl ChannelSettingKey {
             Self::DiscordAdminUserIds => "Admin User IDs",
             Self::DiscordToolCallVerbosity => "Tool Call Verbosity",
             Self::DiscordToolResultVerbosity => "Tool Result Verbosity",
+            Self::DiscordRichEmbeds => "Rich Embeds",
+            Self::DiscordRolePermissions => "Role Permissions",
+            Self::DiscordQueryTimeoutSecs => "Query Mode Timeout",
+            Self::Persona => "Persona",
+            Self::Wallet => "Wallet",
+            Self::PlanApproval => "Require Plan Approval",
+            Self::DefaultLanguage => "Default Language",
+            Self::ResponsePipeline => "Response Pipeline",
+            Self::ResponseDisclaimer => "Response Disclaimer",
+            Self::HistoryMessages => "History Messages",
+            Self::ToolLog => "Tool Log in Replies",
+            Self::RedispatchEdits => "Re-run Edited Messages",
         }
     }
 
@@ -68,6 +104,62 @@ impl ChannelSettingKey {
                 "Controls how much detail to show for tool results. \
                  'full' shows tool name and result content, 'minimal' shows only tool name and status, 'none' hides tool results."
             }
+            Self::DiscordRichEmbeds => {
+                "When enabled, fenced ```embed JSON blocks in agent replies (title, description, fields, color, footer) \
+                 are sent as Discord embeds. Leave off for text-only servers; blocks are then sent as plain text."
+            }
+            Self::DiscordRolePermissions => {
+                "Commands non-admin users may run, by Discord role ID. One 'ROLE_ID: command, command' entry per line; \
+                 commands are register, status, help, unregister and tip, and 'tool:NAME' grants an agent tool to forwarded \
+                 commands like tip. A 'default' entry applies to users without a listed role. Leave empty for the built-in \
+                 register/status/help/unregister set. Admins always have full access."
+            }
+            Self::DiscordQueryTimeoutSecs => {
+                "Seconds after an admin says 'query' that the next message is treated as a query. \
+                 Query mode is cancelled once this passes. Set to 0 to wait indefinitely."
+            }
+            Self::Persona => {
+                "Name of the persona (from the Personas page) that sets the bot's personality in this channel. \
+                 Leave empty to use SOUL.md."
+            }
+            Self::Wallet => {
+                "Named wallet (from STARK_WALLETS) that transfers, contract calls, balances and Polymarket use in this \
+                 channel when a tool call doesn't name one. Leave empty for the primary wallet."
+            }
+            Self::PlanApproval => {
+                "When enabled, the agent posts its task plan and waits before executing it. \
+                 Reply 'approve' to run the plan, or 'reject' with what to change to have it replanned. \
+                 Leave off for trusted channels."
+            }
+            Self::DefaultLanguage => {
+                "Language the bot replies in until it can tell which language a user writes in, \
+                 e.g. for short messages like 'ok'. Accepts a name or code (Spanish, es). \
+                 Replies otherwise follow the user's language. Leave empty for English."
+            }
+            Self::ResponsePipeline => {
+                "Comma-separated stages applied to replies, in order: unfurl_links (bare URLs so links get previews), \
+                 emoji (:shortcode: to emoji), markdown (convert formatting for this platform) and disclaimer \
+                 (append the Response Disclaimer). Memory markers are always stripped first. Leave empty to send \
+                 replies as written."
+            }
+            Self::ResponseDisclaimer => {
+                "Text the 'disclaimer' stage appends to every reply, e.g. 'Not financial advice.'"
+            }
+            Self::HistoryMessages => {
+                "Most earlier messages of the conversation to include with each request. \
+                 Older messages are dropped first when they don't fit the model's context window, \
+                 so small models may see fewer. Lower it to keep requests cheap."
+            }
+            Self::ToolLog => {
+                "How replies that used tools start. 'full' lists every tool call with its arguments, \
+                 'minimal' adds one line with the tools used and how often, 'none' sends just the answer. \
+                 The dashboard's debug panel always shows every call."
+            }
+            Self::RedispatchEdits => {
+                "When enabled, editing your latest message within a few minutes of sending it runs it again \
+                 if the edit changes what it asks (a different amount, address or request). \
+                 Edits are always saved to the conversation history; typo fixes never re-run."
+            }
         }
     }
 
@@ -77,6 +169,18 @@ impl ChannelSettingKey {
             Self::DiscordAdminUserIds => SettingInputType::Text,
             Self::DiscordToolCallVerbosity => SettingInputType::Select,
             Self::DiscordToolResultVerbosity => SettingInputType::Select,
+            Self::DiscordRichEmbeds => SettingInputType::Toggle,
+            Self::DiscordRolePermissions => SettingInputType::TextArea,
+            Self::DiscordQueryTimeoutSecs => SettingInputType::Number,
+            Self::Persona => SettingInputType::Text,
+            Self::Wallet => SettingInputType::Text,
+            Self::PlanApproval => SettingInputType::Toggle,
+            Self::DefaultLanguage => SettingInputType::Text,
+            Self::ResponsePipeline => SettingInputType::Text,
+            Self::ResponseDisclaimer => SettingInputType::TextArea,
+            Self::HistoryMessages => SettingInputType::Number,
+            Self::ToolLog => SettingInputType::Select,
+            Self::RedispatchEdits => SettingInputType::Toggle,
         }
     }
 
@@ -86,6 +190,18 @@ impl ChannelSettingKey {
             Self::DiscordAdminUserIds => "123456789012345678, 987654321098765432",
             Self::DiscordToolCallVerbosity => "minimal",
             Self::DiscordToolResultVerbosity => "minimal",
+            Self::DiscordRichEmbeds => "false",
+            Self::DiscordRolePermissions => "123456789012345678: status, help, tip, tool:discord_resolve_user",
+            Self::DiscordQueryTimeoutSecs => "120",
+            Self::Persona => "professional",
+            Self::Wallet => "trading",
+            Self::PlanApproval => "false",
+            Self::DefaultLanguage => "Spanish",
+            Self::ResponsePipeline => "markdown, emoji, disclaimer",
+            Self::ResponseDisclaimer => "Not financial advice.",
+            Self::HistoryMessages => "50",
+            Self::ToolLog => "full",
+            Self::RedispatchEdits => "false",
         }
     }
 
@@ -97,6 +213,11 @@ impl ChannelSettingKey {
                 ("minimal", "Minimal - Tool name only"),
                 ("none", "None - Hide completely"),
             ]),
+            Self::ToolLog => Some(vec![
+                ("full", "Full - Every call with its arguments"),
+                ("minimal", "Minimal - Tool names and counts"),
+                ("none", "None - Answer only"),
+            ]),
             _ => None,
         }
     }
@@ -107,6 +228,18 @@ impl ChannelSettingKey {
             Self::DiscordAdminUserIds => "",
             Self::DiscordToolCallVerbosity => "minimal",
             Self::DiscordToolResultVerbosity => "minimal",
+            Self::DiscordRichEmbeds => "false",
+            Self::DiscordRolePermissions => "",
+            Self::DiscordQueryTimeoutSecs => "120",
+            Self::Persona => "",
+            Self::Wallet => "",
+            Self::PlanApproval => "false",
+            Self::DefaultLanguage => "",
+            Self::ResponsePipeline => "",
+            Self::ResponseDisclaimer => "",
+            Self::HistoryMessages => "50",
+            Self::ToolLog => "full",
+            Self::RedispatchEdits => "false",
         }
     }
 }
@@ -211,12 +344,39 @@ pub fn get_settings_for_channel_type(channel_type: ChannelType) -> Vec<ChannelSe
             ChannelSettingKey::DiscordAdminUserIds.into(),
             ChannelSettingKey::DiscordToolCallVerbosity.into(),
             ChannelSettingKey::DiscordToolResultVerbosity.into(),
+            ChannelSettingKey::DiscordRichEmbeds.into(),
+            ChannelSettingKey::DiscordRolePermissions.into(),
+            ChannelSettingKey::DiscordQueryTimeoutSecs.into(),
+            ChannelSettingKey::Persona.into(),
+            ChannelSettingKey::Wallet.into(),
+            ChannelSettingKey::PlanApproval.into(),
+            ChannelSettingKey::DefaultLanguage.into(),
+            ChannelSettingKey::ResponsePipeline.into(),
+            ChannelSettingKey::ResponseDisclaimer.into(),
+            ChannelSettingKey::HistoryMessages.into(),
+            ChannelSettingKey::ToolLog.into(),
+            ChannelSettingKey::RedispatchEdits.into(),
         ],
         ChannelType::Telegram => vec![
-            // No custom settings yet
+            ChannelSettingKey::Persona.into(),
+            ChannelSettingKey::Wallet.into(),
+            ChannelSettingKey::PlanApproval.into(),
+            ChannelSettingKey::DefaultLanguage.into(),
+            ChannelSettingKey::ResponsePipeline.into(),
+            ChannelSettingKey::ResponseDisclaimer.into(),
+            ChannelSettingKey::HistoryMessages.into(),
+            ChannelSettingKey::ToolLog.into(),
+            ChannelSettingKey::RedispatchEdits.into(),
         ],
         ChannelType::Slack => vec![
-            // No custom settings yet
+            ChannelSettingKey::Persona.into(),
+            ChannelSettingKey::Wallet.into(),
+            ChannelSettingKey::PlanApproval.into(),
+            ChannelSettingKey::DefaultLanguage.into(),
+            ChannelSettingKey::ResponsePipeline.into(),
+            ChannelSettingKey::ResponseDisclaimer.into(),
+            ChannelSettingKey::HistoryMessages.into(),
+            ChannelSettingKey::ToolLog.into(),
         ],
     }
 }
@@ -234,16 +394,37 @@ mod tests {
     #[test]
     fn test_discord_settings() {
         let settings = get_settings_for_channel_type(ChannelType::Discord);
-        assert_eq!(settings.len(), 3);
+        assert_eq!(settings.len(), 15);
         assert_eq!(settings[0].key, "discord_admin_user_ids");
         assert_eq!(settings[1].key, "discord_tool_call_verbosity");
         assert_eq!(settings[2].key, "discord_tool_result_verbosity");
+        assert_eq!(settings[3].key, "discord_rich_embeds");
+        assert_eq!(settings[4].key, "discord_role_permissions");
+        assert_eq!(settings[5].key, "discord_query_timeout_secs");
+        assert_eq!(settings[6].key, "persona");
+        assert_eq!(settings[7].key, "wallet");
+        assert_eq!(settings[8].key, "plan_approval");
+        assert_eq!(settings[9].key, "default_language");
+        assert_eq!(settings[10].key, "response_pipeline");
+        assert_eq!(settings[11].key, "response_disclaimer");
+        assert_eq!(settings[12].key, "history_messages");
+        assert_eq!(settings[13].key, "tool_log");
+        assert_eq!(settings[14].key, "redispatch_edits");
     }
 
     #[test]
     fn test_telegram_settings() {
         let settings = get_settings_for_channel_type(ChannelType::Telegram);
-        assert!(settings.is_empty());
+        assert_eq!(settings.len(), 9);
+        assert_eq!(settings[0].key, "persona");
+        assert_eq!(settings[1].key, "wallet");
+        assert_eq!(settings[2].key, "plan_approval");
+        assert_eq!(settings[3].key, "default_language");
+        assert_eq!(settings[4].key, "response_pipeline");
+        assert_eq!(settings[5].key, "response_disclaimer");
+        assert_eq!(settings[6].key, "history_messages");
+        assert_eq!(settings[7].key, "tool_log");
+        assert_eq!(settings[8].key, "redispatch_edits");
     }
 
     #[test]
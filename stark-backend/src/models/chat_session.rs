@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Session scope determines the context type
@@ -159,6 +159,51 @@ pub struct ChatSession {
     /// Completion status of the session
     #[serde(default)]
     pub completion_status: CompletionStatus,
+    /// Explicit context window for this session, overriding the model's
+    #[serde(default)]
+    pub context_window_override: Option<i32>,
+}
+
+impl ChatSession {
+    /// Whether the session's reset policy says it should be reset at `now`.
+    /// Daily resets happen at `daily_reset_hour` (default 0) in `tz`.
+    pub fn reset_due<Tz: TimeZone>(&self, now: DateTime<Utc>, tz: &Tz) -> bool {
+        match self.reset_policy {
+            ResetPolicy::Daily => {
+                let hour = self.daily_reset_hour.unwrap_or(0).clamp(0, 23) as u32;
+                match last_daily_reset(now, hour, tz) {
+                    Some(boundary) => self.last_activity_at < boundary,
+                    None => false,
+                }
+            }
+            ResetPolicy::Idle => match self.idle_timeout_minutes {
+                Some(timeout) => now.signed_duration_since(self.last_activity_at).num_minutes() > timeout as i64,
+                None => false,
+            },
+            ResetPolicy::Manual | ResetPolicy::Never => false,
+        }
+    }
+}
+
+/// Most recent occurrence of `hour`:00 local time in `tz` at or before `now`
+fn last_daily_reset<Tz: TimeZone>(now: DateTime<Utc>, hour: u32, tz: &Tz) -> Option<DateTime<Utc>> {
+    let local_date = now.with_timezone(tz).date_naive();
+    for days_back in 0..3 {
+        let date = local_date - Duration::days(days_back);
+        let naive = date.and_hms_opt(hour, 0, 0)?;
+        // Skipped by a DST jump: fall back to an hour later
+        let local = tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .or_else(|| tz.from_local_datetime(&(naive + Duration::hours(1))).earliest());
+        if let Some(local) = local {
+            let boundary = local.with_timezone(&Utc);
+            if boundary <= now {
+                return Some(boundary);
+            }
+        }
+    }
+    None
 }
 
 /// Request to get or create a chat session
@@ -180,6 +225,16 @@ pub struct UpdateResetPolicyRequest {
     pub daily_reset_hour: Option<i32>,
 }
 
+/// Smallest allowed per-session context window override
+pub const MIN_CONTEXT_WINDOW_OVERRIDE: i32 = 32_000;
+
+/// Request to set or clear a session's context window override
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateContextWindowRequest {
+    /// `None` goes back to the model's window
+    pub context_window_override: Option<i32>,
+}
+
 /// Chat session response for API
 #[derive(Debug, Clone, Serialize)]
 pub struct ChatSessionResponse {
@@ -202,6 +257,7 @@ pub struct ChatSessionResponse {
     pub context_tokens: i32,
     pub max_context_tokens: i32,
     pub compaction_id: Option<i64>,
+    pub context_window_override: Option<i32>,
     // Completion status
     pub completion_status: CompletionStatus,
     // Initial query (first user message) - for web sessions
@@ -230,8 +286,79 @@ impl From<ChatSession> for ChatSessionResponse {
             context_tokens: session.context_tokens,
             max_context_tokens: session.max_context_tokens,
             compaction_id: session.compaction_id,
+            context_window_override: session.context_window_override,
             completion_status: session.completion_status,
             initial_query: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(policy: ResetPolicy, last_activity_at: DateTime<Utc>) -> ChatSession {
+        ChatSession {
+            id: 1,
+            session_key: "telegram:1:42".to_string(),
+            agent_id: None,
+            scope: SessionScope::Dm,
+            channel_type: "telegram".to_string(),
+            channel_id: 1,
+            platform_chat_id: "42".to_string(),
+            is_active: true,
+            reset_policy: policy,
+            idle_timeout_minutes: None,
+            daily_reset_hour: None,
+            created_at: last_activity_at,
+            updated_at: last_activity_at,
+            last_activity_at,
+            expires_at: None,
+            context_tokens: 0,
+            max_context_tokens: 100000,
+            compaction_id: None,
+            completion_status: CompletionStatus::Active,
+            context_window_override: None,
+        }
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_daily_reset_at_hour() {
+        let mut s = session(ResetPolicy::Daily, utc("2026-03-10T02:00:00Z"));
+        s.daily_reset_hour = Some(4);
+        assert!(!s.reset_due(utc("2026-03-10T03:59:00Z"), &Utc));
+        assert!(s.reset_due(utc("2026-03-10T04:00:00Z"), &Utc));
+        // Active after today's reset hour: not due until tomorrow's
+        s.last_activity_at = utc("2026-03-10T05:00:00Z");
+        assert!(!s.reset_due(utc("2026-03-11T03:00:00Z"), &Utc));
+        assert!(s.reset_due(utc("2026-03-11T04:30:00Z"), &Utc));
+    }
+
+    #[test]
+    fn test_daily_reset_respects_timezone() {
+        let tz = chrono_tz::America::New_York;
+        let mut s = session(ResetPolicy::Daily, utc("2026-01-15T10:00:00Z"));
+        s.daily_reset_hour = Some(9);
+        // 09:00 in New York is 14:00 UTC in January
+        assert!(!s.reset_due(utc("2026-01-15T13:59:00Z"), &tz));
+        assert!(s.reset_due(utc("2026-01-15T14:00:00Z"), &tz));
+    }
+
+    #[test]
+    fn test_idle_and_manual_policies() {
+        let mut s = session(ResetPolicy::Idle, utc("2026-03-10T10:00:00Z"));
+        assert!(!s.reset_due(utc("2026-03-20T10:00:00Z"), &Utc));
+        s.idle_timeout_minutes = Some(30);
+        assert!(!s.reset_due(utc("2026-03-10T10:30:00Z"), &Utc));
+        assert!(s.reset_due(utc("2026-03-10T10:31:00Z"), &Utc));
+
+        let s = session(ResetPolicy::Manual, utc("2020-01-01T00:00:00Z"));
+        assert!(!s.reset_due(utc("2026-03-10T10:00:00Z"), &Utc));
+        let s = session(ResetPolicy::Never, utc("2020-01-01T00:00:00Z"));
+        assert!(!s.reset_due(utc("2026-03-10T10:00:00Z"), &Utc));
+    }
+}
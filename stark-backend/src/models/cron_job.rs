@@ -121,6 +121,8 @@ pub struct CronJob {
     pub timeout_seconds: Option<i32>,
     /// Delete after successful run (for one-shot jobs)
     pub delete_after_run: bool,
+    /// Seed each run with findings from earlier runs of the same task
+    pub seed_findings: bool,
     pub status: String,
     pub last_run_at: Option<String>,
     pub next_run_at: Option<String>,
@@ -161,6 +163,8 @@ pub struct CreateCronJobRequest {
     pub timeout_seconds: Option<i32>,
     #[serde(default)]
     pub delete_after_run: bool,
+    #[serde(default)]
+    pub seed_findings: bool,
 }
 
 fn default_session_mode() -> String {
@@ -201,6 +205,8 @@ pub struct UpdateCronJobRequest {
     #[serde(default)]
     pub delete_after_run: Option<bool>,
     #[serde(default)]
+    pub seed_findings: Option<bool>,
+    #[serde(default)]
     pub status: Option<String>,
 }
 
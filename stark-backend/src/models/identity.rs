@@ -59,3 +59,105 @@ impl From<&IdentityLink> for LinkedAccountInfo {
         }
     }
 }
+
+/// Daily usage limits for an identity. A limit of 0 means unlimited. In a
+/// per-identity override, `None` falls back to the global default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdentityQuota {
+    #[serde(default)]
+    pub messages_per_day: Option<u64>,
+    #[serde(default)]
+    pub tool_calls_per_day: Option<u64>,
+    /// x402 spend in USDC
+    #[serde(default)]
+    pub x402_usdc_per_day: Option<f64>,
+}
+
+impl IdentityQuota {
+    /// Global defaults from the environment
+    pub fn global() -> Self {
+        IdentityQuota {
+            messages_per_day: Some(crate::config::quota_messages_per_day()),
+            tool_calls_per_day: Some(crate::config::quota_tool_calls_per_day()),
+            x402_usdc_per_day: Some(crate::config::quota_x402_usdc_per_day()),
+        }
+    }
+
+    /// Fill the limits this override leaves unset from `defaults`
+    pub fn or(&self, defaults: &IdentityQuota) -> IdentityQuota {
+        IdentityQuota {
+            messages_per_day: self.messages_per_day.or(defaults.messages_per_day),
+            tool_calls_per_day: self.tool_calls_per_day.or(defaults.tool_calls_per_day),
+            x402_usdc_per_day: self.x402_usdc_per_day.or(defaults.x402_usdc_per_day),
+        }
+    }
+
+    /// Check today's usage against the limits, returning a message for the
+    /// user if one has been reached
+    pub fn exceeded(&self, usage: &IdentityUsage) -> Option<String> {
+        let reached = if let Some(limit) = self.messages_per_day.filter(|l| *l > 0 && usage.messages >= *l) {
+            format!("your daily limit of {} messages", limit)
+        } else if let Some(limit) = self.tool_calls_per_day.filter(|l| *l > 0 && usage.tool_calls >= *l) {
+            format!("your daily limit of {} tool calls", limit)
+        } else if let Some(limit) = self
+            .x402_usdc_per_day
+            .filter(|l| *l > 0.0 && usage.x402_micro_usdc as f64 >= l * 1_000_000.0)
+        {
+            format!("your daily spending limit of {:.2} USDC", limit)
+        } else {
+            return None;
+        };
+
+        Some(format!(
+            "You've reached {} for today. Your quota resets at midnight UTC, so please try again then.",
+            reached
+        ))
+    }
+}
+
+/// An identity's usage for one UTC day
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IdentityUsage {
+    /// The day, as YYYY-MM-DD (UTC)
+    pub day: String,
+    pub messages: u64,
+    pub tool_calls: u64,
+    /// x402 spend in micro-USDC
+    pub x402_micro_usdc: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_override_and_limits() {
+        let global = IdentityQuota {
+            messages_per_day: Some(50),
+            tool_calls_per_day: Some(0),
+            x402_usdc_per_day: Some(1.0),
+        };
+        let quota = IdentityQuota {
+            messages_per_day: Some(5),
+            ..Default::default()
+        }
+        .or(&global);
+        assert_eq!(quota.messages_per_day, Some(5));
+        assert_eq!(quota.x402_usdc_per_day, Some(1.0));
+
+        let mut usage = IdentityUsage {
+            messages: 4,
+            tool_calls: 10_000,
+            x402_micro_usdc: 999_999,
+            ..Default::default()
+        };
+        assert_eq!(quota.exceeded(&usage), None);
+
+        usage.messages = 5;
+        assert!(quota.exceeded(&usage).unwrap().contains("5 messages"));
+
+        usage.messages = 0;
+        usage.x402_micro_usdc = 1_000_000;
+        assert!(quota.exceeded(&usage).unwrap().contains("1.00 USDC"));
+    }
+}
@@ -11,7 +11,10 @@ pub mod session;
 pub mod session_message;
 
 pub use agent_settings::{AgentSettings, AgentSettingsResponse, UpdateAgentSettingsRequest, MIN_CONTEXT_TOKENS, DEFAULT_CONTEXT_TOKENS};
-pub use bot_settings::{BotSettings, UpdateBotSettingsRequest, DEFAULT_MAX_TOOL_ITERATIONS};
+pub use bot_settings::{
+    BotSettings, ModerationFailurePolicy, ModerationSettings, TransferCap, TransferLimits, UpdateBotSettingsRequest,
+    DEFAULT_MAX_TOOL_ITERATIONS,
+};
 pub use api_key::{ApiKey, ApiKeyResponse};
 pub use channel::{Channel, ChannelResponse, ChannelType, CreateChannelRequest, UpdateChannelRequest};
 pub use channel_settings::{
@@ -21,11 +24,11 @@ pub use channel_settings::{
 };
 pub use chat_session::{
     ChatSession, ChatSessionResponse, CompletionStatus, GetOrCreateSessionRequest, ResetPolicy,
-    SessionScope, UpdateResetPolicyRequest,
+    SessionScope, UpdateContextWindowRequest, UpdateResetPolicyRequest, MIN_CONTEXT_WINDOW_OVERRIDE,
 };
 pub use identity::{
-    GetOrCreateIdentityRequest, IdentityLink, IdentityResponse, LinkIdentityRequest,
-    LinkedAccountInfo,
+    GetOrCreateIdentityRequest, IdentityLink, IdentityQuota, IdentityResponse, IdentityUsage,
+    LinkIdentityRequest, LinkedAccountInfo,
 };
 pub use session::Session;
 pub use session_message::{AddMessageRequest, MessageRole, SessionMessage, SessionTranscriptResponse};
@@ -7,9 +7,11 @@
 
 use super::file_ops;
 use chrono::{Local, NaiveDate};
-use rusqlite::{params, Connection, Result as SqliteResult};
+use crate::db::sqlite::CONNECTION_PRAGMAS;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{params, Result as SqliteResult};
 use std::path::PathBuf;
-use std::sync::Mutex;
 
 /// Search result from the memory store
 #[derive(Debug, Clone)]
@@ -26,8 +28,8 @@ pub struct SearchResult {
 pub struct MemoryStore {
     /// Path to the memory directory
     memory_dir: PathBuf,
-    /// SQLite connection for FTS5 index
-    conn: Mutex<Connection>,
+    /// SQLite connection pool for the FTS5 index
+    pool: Pool<SqliteConnectionManager>,
 }
 
 impl MemoryStore {
@@ -36,11 +38,16 @@ impl MemoryStore {
         // Ensure memory directory exists
         std::fs::create_dir_all(&memory_dir).ok();
 
-        // Open or create SQLite database
-        let conn = Connection::open(db_path)?;
+        // Pool connections (WAL) so searches aren't blocked behind a reindex
+        let manager = SqliteConnectionManager::file(db_path)
+            .with_init(|conn| conn.execute_batch(CONNECTION_PRAGMAS));
+        let pool = Pool::builder()
+            .max_size(4)
+            .build(manager)
+            .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?;
 
         // Create FTS5 table for indexing
-        conn.execute(
+        Self::get_conn(&pool)?.execute(
             "CREATE VIRTUAL TABLE IF NOT EXISTS qmd_memory_fts USING fts5(
                 file_path,
                 content,
@@ -49,10 +56,7 @@ impl MemoryStore {
             [],
         )?;
 
-        let store = Self {
-            memory_dir,
-            conn: Mutex::new(conn),
-        };
+        let store = Self { memory_dir, pool };
 
         // Initial reindex
         store.reindex()?;
@@ -60,27 +64,8 @@ impl MemoryStore {
         Ok(store)
     }
 
-    /// Create memory store using an existing database connection
-    pub fn with_connection(memory_dir: PathBuf, conn: Connection) -> SqliteResult<Self> {
-        std::fs::create_dir_all(&memory_dir).ok();
-
-        // Create FTS5 table if not exists
-        conn.execute(
-            "CREATE VIRTUAL TABLE IF NOT EXISTS qmd_memory_fts USING fts5(
-                file_path,
-                content,
-                tokenize='porter'
-            )",
-            [],
-        )?;
-
-        let store = Self {
-            memory_dir,
-            conn: Mutex::new(conn),
-        };
-
-        store.reindex()?;
-        Ok(store)
+    fn get_conn(pool: &Pool<SqliteConnectionManager>) -> SqliteResult<PooledConnection<SqliteConnectionManager>> {
+        pool.get().map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))
     }
 
     /// Get the memory directory path
@@ -89,11 +74,15 @@ impl MemoryStore {
     }
 
     /// Reindex all markdown files in the memory directory
+    ///
+    /// Runs in one transaction, so concurrent searches keep seeing the old
+    /// index until the rebuild commits.
     pub fn reindex(&self) -> SqliteResult<usize> {
-        let conn = self.conn.lock().unwrap();
+        let mut conn = Self::get_conn(&self.pool)?;
+        let tx = conn.transaction()?;
 
         // Clear existing index
-        conn.execute("DELETE FROM qmd_memory_fts", [])?;
+        tx.execute("DELETE FROM qmd_memory_fts", [])?;
 
         // List all markdown files
         let files = file_ops::list_memory_files(&self.memory_dir).unwrap_or_default();
@@ -102,7 +91,7 @@ impl MemoryStore {
         for file_path in files {
             if let Ok(content) = file_ops::read_file(&file_path) {
                 if let Some(rel_path) = file_ops::relative_path(&self.memory_dir, &file_path) {
-                    conn.execute(
+                    tx.execute(
                         "INSERT INTO qmd_memory_fts (file_path, content) VALUES (?1, ?2)",
                         params![rel_path, content],
                     )?;
@@ -111,13 +100,15 @@ impl MemoryStore {
             }
         }
 
+        tx.commit()?;
+
         log::info!("[QMD_MEMORY] Indexed {} memory files", count);
         Ok(count)
     }
 
     /// Search memories using BM25 full-text search
     pub fn search(&self, query: &str, limit: i32) -> SqliteResult<Vec<SearchResult>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = Self::get_conn(&self.pool)?;
 
         // Escape and prepare query for FTS5
         let escaped_query = escape_fts5_query(query);
@@ -224,21 +215,21 @@ impl MemoryStore {
 
     /// Index or update a single file in the FTS index
     fn index_file(&self, file_path: &PathBuf) -> SqliteResult<()> {
-        let conn = self.conn.lock().unwrap();
+        let mut conn = Self::get_conn(&self.pool)?;
 
         if let Some(rel_path) = file_ops::relative_path(&self.memory_dir, file_path) {
             if let Ok(content) = file_ops::read_file(file_path) {
-                // Delete existing entry
-                conn.execute(
+                // Replace the entry atomically
+                let tx = conn.transaction()?;
+                tx.execute(
                     "DELETE FROM qmd_memory_fts WHERE file_path = ?1",
                     params![rel_path],
                 )?;
-
-                // Insert updated content
-                conn.execute(
+                tx.execute(
                     "INSERT INTO qmd_memory_fts (file_path, content) VALUES (?1, ?2)",
                     params![rel_path, content],
                 )?;
+                tx.commit()?;
             }
         }
 
@@ -311,6 +302,29 @@ mod tests {
         assert!(results[0].file_path.contains("MEMORY.md"));
     }
 
+    #[test]
+    fn test_search_not_blocked_by_open_write() {
+        let dir = tempdir().unwrap();
+        let mem_dir = dir.path().join("memory");
+        let db_path = dir.path().join("test.db");
+
+        let store =
+            MemoryStore::new(mem_dir.clone(), db_path.to_str().unwrap()).expect("Failed to create store");
+        store
+            .append_long_term("User prefers dark mode", None)
+            .expect("Failed to append");
+
+        // Hold a write transaction open on another connection, like a long reindex
+        let mut writer = MemoryStore::get_conn(&store.pool).unwrap();
+        let tx = writer.transaction().unwrap();
+        tx.execute("DELETE FROM qmd_memory_fts", []).unwrap();
+
+        // Reads still see the committed index
+        let results = store.search("dark mode", 10).expect("Search blocked by writer");
+        assert!(!results.is_empty());
+        tx.rollback().unwrap();
+    }
+
     #[test]
     fn test_daily_log() {
         let dir = tempdir().unwrap();
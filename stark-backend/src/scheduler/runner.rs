@@ -1,13 +1,17 @@
 use crate::channels::dispatcher::MessageDispatcher;
+use crate::channels::ChannelManager;
 use crate::channels::types::NormalizedMessage;
 use crate::db::Database;
-use crate::execution::ExecutionTracker;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
+use crate::db::tables::limit_orders::{LimitOrder, LimitOrderStatus};
 use crate::models::{CronJob, HeartbeatConfig, JobStatus, ScheduleType};
-use crate::tools::ToolRegistry;
+use crate::tools::builtin::cryptocurrency::limit_order;
+use crate::tools::builtin::PolymarketTradeTool;
+use crate::tools::ToolContext;
 use chrono::{DateTime, Duration, Local, NaiveTime, Utc, Weekday, Datelike};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tokio::sync::oneshot;
 use tokio::time::{interval, timeout, Duration as TokioDuration};
 
@@ -18,6 +22,10 @@ pub const HEARTBEAT_USER_NAME: &str = "Heartbeat";
 /// Fixed chat_id ensures we reuse the same session (no timestamp suffix)
 pub const HEARTBEAT_CHAT_ID: &str = "heartbeat:global";
 
+/// Limit orders run on channel `-(offset + order id)`, below the range used
+/// by isolated cron jobs
+const LIMIT_ORDER_CHANNEL_OFFSET: i64 = 1_000_000;
+
 /// Scheduler configuration
 #[derive(Debug, Clone)]
 pub struct SchedulerConfig {
@@ -25,6 +33,18 @@ pub struct SchedulerConfig {
     pub cron_enabled: bool,
     /// Enable heartbeat processing
     pub heartbeat_enabled: bool,
+    /// Enable Gmail watch auto-renewal
+    pub gmail_renewal_enabled: bool,
+    /// Enable daily/idle chat session resets
+    pub session_resets_enabled: bool,
+    /// Enable periodic portfolio snapshots (interval from STARK_PORTFOLIO_SNAPSHOT_INTERVAL_MINS)
+    pub portfolio_snapshots_enabled: bool,
+    /// Enable limit order price checks (every poll)
+    pub limit_orders_enabled: bool,
+    /// Enable Polymarket fill notifications for tracked orders (every poll)
+    pub polymarket_fills_enabled: bool,
+    /// Enable the channel watchdog, which restarts stalled or dead listeners (every poll)
+    pub channel_watchdog_enabled: bool,
     /// Poll interval in seconds for checking due jobs
     pub poll_interval_secs: u64,
     /// Maximum concurrent job executions
@@ -36,6 +56,12 @@ impl Default for SchedulerConfig {
         SchedulerConfig {
             cron_enabled: true,
             heartbeat_enabled: false,  // Disabled - too noisy
+            gmail_renewal_enabled: true,
+            session_resets_enabled: true,
+            portfolio_snapshots_enabled: true,
+            limit_orders_enabled: true,
+            polymarket_fills_enabled: true,
+            channel_watchdog_enabled: true,
             poll_interval_secs: 60,    // Check once per minute instead of 10 seconds
             max_concurrent_jobs: 5,
         }
@@ -51,6 +77,14 @@ pub struct Scheduler {
     dispatcher: Arc<MessageDispatcher>,
     broadcaster: Arc<EventBroadcaster>,
     config: SchedulerConfig,
+    /// Last Gmail watch renewal check (throttled to RENEWAL_CHECK_INTERVAL_SECS)
+    last_gmail_check: Arc<Mutex<Option<Instant>>>,
+    /// Last portfolio snapshot run (throttled to the configured interval)
+    last_portfolio_snapshot: Arc<Mutex<Option<Instant>>>,
+    /// Polymarket client (caches authenticated CLOB clients between polls)
+    polymarket: Arc<PolymarketTradeTool>,
+    /// Channel listeners supervised by the watchdog
+    channel_manager: Option<Arc<ChannelManager>>,
 }
 
 impl Scheduler {
@@ -65,9 +99,19 @@ impl Scheduler {
             dispatcher,
             broadcaster,
             config,
+            last_gmail_check: Arc::new(Mutex::new(None)),
+            last_portfolio_snapshot: Arc::new(Mutex::new(None)),
+            polymarket: Arc::new(PolymarketTradeTool::new()),
+            channel_manager: None,
         }
     }
 
+    /// Set the channel manager whose listeners the watchdog supervises
+    pub fn with_channel_manager(mut self, channel_manager: Arc<ChannelManager>) -> Self {
+        self.channel_manager = Some(channel_manager);
+        self
+    }
+
     /// Compatibility method - db_url is no longer needed with connection pool
     #[deprecated(note = "Use new() instead - db_url is no longer needed with r2d2 connection pool")]
     pub fn new_with_db_url(
@@ -83,9 +127,15 @@ impl Scheduler {
     /// Start the scheduler background task
     pub async fn start(self: Arc<Self>, mut shutdown_rx: oneshot::Receiver<()>) {
         log::info!(
-            "Scheduler started (cron: {}, heartbeat: {}, poll: {}s)",
+            "Scheduler started (cron: {}, heartbeat: {}, gmail renewal: {}, session resets: {}, portfolio snapshots: {}, limit orders: {}, polymarket fills: {}, channel watchdog: {}, poll: {}s)",
             self.config.cron_enabled,
             self.config.heartbeat_enabled,
+            self.config.gmail_renewal_enabled,
+            self.config.session_resets_enabled,
+            self.config.portfolio_snapshots_enabled,
+            self.config.limit_orders_enabled,
+            self.config.polymarket_fills_enabled,
+            self.config.channel_watchdog_enabled,
             self.config.poll_interval_secs
         );
 
@@ -121,6 +171,75 @@ impl Scheduler {
                 log::error!("Error processing heartbeats: {}", e);
             }
         }
+
+        // Renew the Gmail watch before it expires
+        if self.config.gmail_renewal_enabled && self.gmail_check_due() {
+            crate::integrations::gmail::renew_watches(&self.db, &self.broadcaster).await;
+        }
+
+        // Apply session reset policies (daily at the reset hour, idle timeout)
+        if self.config.session_resets_enabled {
+            let reset = self.dispatcher.apply_session_reset_policies().await;
+            if reset > 0 {
+                log::info!("Auto-reset {} chat session(s)", reset);
+            }
+        }
+
+        // Snapshot wallet balances for portfolio history (RPC + price lookups
+        // can be slow, so don't hold up the tick)
+        if self.config.portfolio_snapshots_enabled && self.portfolio_snapshot_due() {
+            let db = Arc::clone(&self.db);
+            tokio::spawn(async move {
+                crate::tools::builtin::cryptocurrency::portfolio::snapshot_all_wallets(&db).await;
+            });
+        }
+
+        // Fire limit orders whose price target has been crossed
+        if self.config.limit_orders_enabled {
+            self.process_limit_orders().await;
+        }
+
+        // Report fills on tracked Polymarket orders
+        if self.config.polymarket_fills_enabled {
+            self.process_polymarket_fills().await;
+        }
+
+        // Restart channel listeners that died or stalled
+        if self.config.channel_watchdog_enabled {
+            if let Some(ref channel_manager) = self.channel_manager {
+                channel_manager.supervise_channels().await;
+            }
+        }
+    }
+
+    /// Whether enough time has passed since the last Gmail renewal check
+    fn gmail_check_due(&self) -> bool {
+        let Ok(mut last) = self.last_gmail_check.lock() else {
+            return false;
+        };
+        let interval = TokioDuration::from_secs(crate::integrations::gmail::RENEWAL_CHECK_INTERVAL_SECS);
+        if last.is_some_and(|at| at.elapsed() < interval) {
+            return false;
+        }
+        *last = Some(Instant::now());
+        true
+    }
+
+    /// Whether a portfolio snapshot is due (never when the interval is 0)
+    fn portfolio_snapshot_due(&self) -> bool {
+        let interval_mins = crate::config::portfolio_snapshot_interval_mins();
+        if interval_mins == 0 {
+            return false;
+        }
+        let Ok(mut last) = self.last_portfolio_snapshot.lock() else {
+            return false;
+        };
+        let interval = TokioDuration::from_secs(interval_mins * 60);
+        if last.is_some_and(|at| at.elapsed() < interval) {
+            return false;
+        }
+        *last = Some(Instant::now());
+        true
     }
 
     /// Process due cron jobs
@@ -142,12 +261,116 @@ impl Scheduler {
         Ok(())
     }
 
+    /// Check pending limit orders against current prices and execute the
+    /// triggered ones
+    async fn process_limit_orders(&self) {
+        let triggered = limit_order::find_triggered_orders(&self.db).await;
+
+        for (order, price) in triggered {
+            // Claim the order before spawning so it can't fire twice
+            match self.db.mark_limit_order_triggered(order.id, price) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    log::error!("Failed to mark limit order #{} triggered: {}", order.id, e);
+                    continue;
+                }
+            }
+
+            let scheduler = Arc::new(self.clone_inner());
+            tokio::spawn(async move {
+                scheduler.execute_limit_order(&order, price).await;
+            });
+        }
+    }
+
+    /// Queue the swap for a triggered limit order by running the swap tools
+    /// directly, and record the queued transaction's UUID
+    async fn execute_limit_order(&self, order: &LimitOrder, price: f64) {
+        log::info!(
+            "Limit order #{} triggered: {} at ${} ({} ${})",
+            order.id,
+            order.trigger_token,
+            price,
+            order.trigger_direction.as_str(),
+            order.target_price_usd
+        );
+        self.broadcaster.broadcast(GatewayEvent::custom(
+            "limit_order_triggered",
+            serde_json::json!({ "id": order.id, "price_usd": price }),
+        ));
+
+        let outcome = match self.limit_order_context(order).await {
+            Ok(context) => limit_order::execute_swap(order, &self.dispatcher.tool_registry(), &context).await,
+            Err(e) => Err(e),
+        };
+        let (status, result) = match outcome {
+            Ok(swap) => (LimitOrderStatus::Filled, swap.summary()),
+            Err(e) => (LimitOrderStatus::Failed, format!("no swap queued: {}", e)),
+        };
+
+        if let Err(e) = self.db.complete_limit_order(order.id, status, &result) {
+            log::error!("Failed to update limit order #{}: {}", order.id, e);
+        }
+        log::info!("Limit order #{} {}: {}", order.id, status.as_str(), result);
+        self.broadcaster.broadcast(GatewayEvent::custom(
+            "limit_order_completed",
+            serde_json::json!({ "id": order.id, "status": status, "result": result }),
+        ));
+    }
+
+    /// Tool context for a limit order's swap: its own channel, the order's
+    /// network and wallet, and the RPC and transfer settings a chat would get
+    async fn limit_order_context(&self, order: &LimitOrder) -> Result<ToolContext, String> {
+        let tx_queue = self.dispatcher.tx_queue().ok_or("Transaction queue not available")?;
+        let signer = crate::wallet::WalletSigner::for_wallet(Some(&order.wallet)).await?;
+
+        let mut context = ToolContext::new()
+            .with_channel(-(LIMIT_ORDER_CHANNEL_OFFSET + order.id), "limit_order".to_string())
+            .with_broadcaster(self.broadcaster.clone())
+            .with_database(self.db.clone())
+            .with_selected_network(Some(order.network.clone()))
+            .with_tx_queue(tx_queue);
+        // The quote is requested for the order's wallet, not the primary one
+        context.set_register("wallet_address", serde_json::json!(format!("{:?}", signer.address())), "limit_order");
+
+        if let Ok(bot_settings) = self.db.get_bot_settings() {
+            context.extra.insert("rpc_provider".to_string(), serde_json::json!(bot_settings.rpc_provider));
+            if let Some(ref endpoints) = bot_settings.custom_rpc_endpoints {
+                context.extra.insert("custom_rpc_endpoints".to_string(), serde_json::json!(endpoints));
+            }
+            context.extra.insert("rogue_mode_enabled".to_string(), serde_json::json!(bot_settings.rogue_mode_enabled));
+            context.extra.insert("transfer_limits".to_string(), serde_json::json!(bot_settings.transfer_limits));
+        }
+        Ok(context)
+    }
+
+    /// Broadcast new Polymarket fills, and post them to the channel that
+    /// placed the order
+    async fn process_polymarket_fills(&self) {
+        for fill in self.polymarket.poll_fills(&self.db).await {
+            let message = fill.message();
+            log::info!("{}", message);
+            self.broadcaster.broadcast(GatewayEvent::custom(
+                if fill.fully_filled { "polymarket_order_filled" } else { "polymarket_order_partially_filled" },
+                serde_json::json!(fill),
+            ));
+            if let Some(channel_id) = fill.channel_id {
+                self.broadcaster.broadcast(GatewayEvent::agent_response(channel_id, "polymarket", &message));
+            }
+        }
+    }
+
     fn clone_inner(&self) -> Scheduler {
         Scheduler {
             db: Arc::clone(&self.db),
             dispatcher: Arc::clone(&self.dispatcher),
             broadcaster: Arc::clone(&self.broadcaster),
             config: self.config.clone(),
+            last_gmail_check: Arc::clone(&self.last_gmail_check),
+            last_portfolio_snapshot: Arc::clone(&self.last_portfolio_snapshot),
+            polymarket: Arc::clone(&self.polymarket),
+            channel_manager: self.channel_manager.clone(),
         }
     }
 
@@ -220,6 +443,11 @@ impl Scheduler {
             ));
         }
 
+        // Jobs that opt in carry findings from one run to the next
+        let task_signature = job
+            .seed_findings
+            .then(|| crate::ai::multi_agent::findings::task_signature(&message_text));
+
         // Create a normalized message for the dispatcher
         let normalized = NormalizedMessage {
             channel_id: cron_channel_id,
@@ -231,6 +459,10 @@ impl Scheduler {
             message_id: Some(format!("cron-run-{}", started_at.timestamp())),
             session_mode: Some(job.session_mode.clone()),
             selected_network: None,
+            allowed_tools: None,
+            task_signature,
+            response_schema: None,
+            reply_to: None,
         };
 
         // Execute the job
@@ -488,6 +720,10 @@ impl Scheduler {
             message_id: Some(format!("heartbeat-{}", now.timestamp())),
             session_mode: Some("isolated".to_string()), // Isolated to prevent state corruption
             selected_network: None,
+            allowed_tools: None,
+            task_signature: None,
+            response_schema: None,
+            reply_to: None,
         };
 
         // Execute the heartbeat
@@ -566,6 +802,9 @@ impl Scheduler {
         // Clone what we need for the background task
         let db = Arc::clone(&self.db);
         let broadcaster = Arc::clone(&self.broadcaster);
+        // The shared dispatcher, so manual pulses get the same tools, hooks
+        // and validators as scheduled ones
+        let dispatcher = Arc::clone(&self.dispatcher);
 
         // Spawn the heartbeat in a background task
         tokio::spawn(async move {
@@ -577,18 +816,6 @@ impl Scheduler {
                 serde_json::json!({ "config_id": config_id }),
             ));
 
-            // Create dispatcher for this task (uses shared db pool)
-            let tracker = Arc::new(ExecutionTracker::new(broadcaster.clone()));
-            let tool_registry = Arc::new(ToolRegistry::new());
-            let burner_wallet = std::env::var("BURNER_WALLET_BOT_PRIVATE_KEY").ok();
-            let dispatcher = Arc::new(MessageDispatcher::new_with_wallet(
-                db.clone(),
-                broadcaster.clone(),
-                tool_registry,
-                tracker,
-                burner_wallet,
-            ));
-
             // Execute with timeout
             let result = timeout(
                 TokioDuration::from_secs(HEARTBEAT_TIMEOUT_SECS),
@@ -731,6 +958,10 @@ async fn execute_heartbeat_isolated(
         message_id: Some(format!("heartbeat-{}", now.timestamp())),
         session_mode: Some("isolated".to_string()),
         selected_network: None,
+        allowed_tools: None,
+        task_signature: None,
+        response_schema: None,
+        reply_to: None,
     };
 
     // === DEFERRED AI CALL (fire and forget) ===
@@ -0,0 +1,169 @@
+//! Schema-driven argument validation
+//!
+//! Checks a tool call's arguments against the tool's declared
+//! `ToolInputSchema` before it runs: required fields must be present, each
+//! declared property must have its declared type, and enum properties must
+//! hold one of their values. Errors name the field and what was expected so
+//! the agent can correct the call.
+//!
+//! Undeclared properties are allowed, and `null` counts as absent. Array
+//! items aren't checked, since several tools take mixed-type arrays (e.g.
+//! `web3_function_call` params). Numeric strings pass as numbers, matching
+//! the tools that parse numbers leniently.
+
+use crate::tools::types::{PropertySchema, ToolInputSchema};
+use serde_json::Value;
+
+/// Validate tool arguments against the tool's input schema
+pub fn validate_input(schema: &ToolInputSchema, args: &Value) -> Result<(), String> {
+    let empty = serde_json::Map::new();
+    let args = match args {
+        Value::Object(map) => map,
+        // Tools without arguments are often called with null
+        Value::Null => &empty,
+        other => return Err(format!("arguments must be an object, got {}", type_name(other))),
+    };
+
+    let mut missing: Vec<&str> = schema
+        .required
+        .iter()
+        .filter(|field| args.get(field.as_str()).map_or(true, Value::is_null))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        missing.sort();
+        return Err(format!("missing required field(s): {}", missing.join(", ")));
+    }
+
+    let mut names: Vec<&String> = schema.properties.keys().collect();
+    names.sort();
+    for name in names {
+        match args.get(name.as_str()) {
+            None | Some(Value::Null) => continue,
+            Some(value) => validate_property(name, &schema.properties[name], value)?,
+        }
+    }
+    Ok(())
+}
+
+fn validate_property(name: &str, property: &PropertySchema, value: &Value) -> Result<(), String> {
+    if !matches_type(&property.schema_type, value) {
+        return Err(format!(
+            "'{}' must be of type {}, got {}",
+            name,
+            property.schema_type,
+            type_name(value)
+        ));
+    }
+
+    if let (Some(allowed), Value::String(s)) = (&property.enum_values, value) {
+        if !allowed.iter().any(|a| a == s) {
+            return Err(format!(
+                "'{}' must be one of: {} (got '{}')",
+                name,
+                allowed.join(", "),
+                s
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn matches_type(schema_type: &str, value: &Value) -> bool {
+    match schema_type {
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "integer" => match value {
+            Value::Number(n) => n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0),
+            Value::String(s) => s.trim().parse::<i64>().is_ok(),
+            _ => false,
+        },
+        "number" => match value {
+            Value::Number(_) => true,
+            Value::String(s) => s.trim().parse::<f64>().is_ok(),
+            _ => false,
+        },
+        // Unknown types are the tool's business
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn property(schema_type: &str, enum_values: Option<Vec<&str>>) -> PropertySchema {
+        PropertySchema {
+            schema_type: schema_type.to_string(),
+            description: String::new(),
+            default: None,
+            items: None,
+            enum_values: enum_values.map(|v| v.into_iter().map(String::from).collect()),
+        }
+    }
+
+    fn schema() -> ToolInputSchema {
+        let mut properties = HashMap::new();
+        properties.insert("action".to_string(), property("string", Some(vec!["buy", "sell"])));
+        properties.insert("size".to_string(), property("number", None));
+        properties.insert("timeout".to_string(), property("integer", None));
+        properties.insert("notify".to_string(), property("boolean", None));
+        properties.insert("files".to_string(), property("array", None));
+        ToolInputSchema {
+            schema_type: "object".to_string(),
+            properties,
+            required: vec!["action".to_string(), "size".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_accepts_valid_arguments() {
+        let schema = schema();
+        assert!(validate_input(&schema, &json!({"action": "buy", "size": 1.5})).is_ok());
+        assert!(validate_input(&schema, &json!({
+            "action": "sell",
+            "size": 2,
+            "timeout": "30",
+            "notify": null,
+            "files": ["a", 1],
+            "extra": "ignored"
+        }))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_arguments() {
+        let schema = schema();
+
+        let err = validate_input(&schema, &json!({"size": null})).unwrap_err();
+        assert_eq!(err, "missing required field(s): action, size");
+
+        let err = validate_input(&schema, &json!({"action": "hold", "size": 1})).unwrap_err();
+        assert_eq!(err, "'action' must be one of: buy, sell (got 'hold')");
+
+        let err = validate_input(&schema, &json!({"action": "buy", "size": 1, "timeout": 1.5})).unwrap_err();
+        assert_eq!(err, "'timeout' must be of type integer, got number");
+
+        let err = validate_input(&schema, &json!({"action": "buy", "size": 1, "notify": "yes"})).unwrap_err();
+        assert_eq!(err, "'notify' must be of type boolean, got string");
+
+        assert!(validate_input(&schema, &json!(["buy"])).is_err());
+        assert!(validate_input(&ToolInputSchema::default(), &Value::Null).is_ok());
+    }
+}
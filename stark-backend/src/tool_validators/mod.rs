@@ -28,10 +28,19 @@
 //! )
 //! ```
 //!
-//! ## 2. Rust Structs (Legacy)
+//! ## 2. Rust Structs
 //!
 //! Implement the `ToolValidator` trait directly for complex validation logic
-//! that can't be expressed in the RON DSL.
+//! that can't be expressed in the RON DSL, e.g. a tool's own invariants
+//! (`PolymarketOrderValidator`). Return `ValidationResult::InvalidParams` for
+//! arguments the agent should correct; the call fails with the
+//! `invalid_params` error code.
+//!
+//! ## Input Schemas
+//!
+//! Independently of the registry, every tool call's arguments are checked
+//! against the tool's declared `ToolInputSchema` (required fields, types,
+//! enum values) before `Tool::execute` runs. See `input_schema`.
 //!
 //! # Condition Types (RON DSL)
 //!
@@ -52,17 +61,24 @@ pub mod types;
 pub mod traits;
 pub mod registry;
 pub mod ron;
+pub mod input_schema;
+pub mod polymarket;
 
 pub use types::*;
 pub use traits::*;
 pub use registry::*;
 
 use std::path::Path;
+use std::sync::Arc;
 
-/// Create the default validator registry, loading RON validators from config/validators/
+/// Create the default validator registry: the built-in validators, plus RON
+/// validators loaded from config/validators/
 pub fn create_default_registry() -> ValidatorRegistry {
     let mut registry = ValidatorRegistry::new();
 
+    // Built-in validators for tool invariants
+    registry.register(Arc::new(polymarket::PolymarketOrderValidator));
+
     // Load RON validators from config directory
     // Check ./config/validators first, then ../config/validators
     let validators_dir = if Path::new("./config/validators").exists() {
@@ -0,0 +1,92 @@
+//! Order invariants for `polymarket_trade` that its input schema can't express
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::traits::ToolValidator;
+use super::types::{ValidationContext, ValidationResult, ValidatorPriority};
+
+/// Rejects `place_order` calls whose price isn't a probability or whose
+/// size isn't positive, before any market lookup or signing happens
+pub struct PolymarketOrderValidator;
+
+impl PolymarketOrderValidator {
+    fn number(args: &Value, key: &str) -> Option<f64> {
+        match args.get(key)? {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolValidator for PolymarketOrderValidator {
+    fn id(&self) -> &str {
+        "polymarket_order"
+    }
+
+    fn name(&self) -> &str {
+        "Polymarket Order Invariants"
+    }
+
+    fn description(&self) -> &str {
+        "place_order price must be between 0.01 and 0.99 and size must be positive"
+    }
+
+    fn applies_to(&self) -> Option<Vec<&str>> {
+        Some(vec!["polymarket_trade"])
+    }
+
+    fn priority(&self) -> ValidatorPriority {
+        ValidatorPriority::High
+    }
+
+    async fn validate(&self, ctx: &ValidationContext) -> ValidationResult {
+        let args = &ctx.tool_args;
+        if args.get("action").and_then(|a| a.as_str()) != Some("place_order") {
+            return ValidationResult::Allow;
+        }
+
+        if let Some(price) = Self::number(args, "price") {
+            if !(0.01..=0.99).contains(&price) {
+                return ValidationResult::InvalidParams(format!(
+                    "'price' must be between 0.01 and 0.99 (got {}). Price is the outcome's probability: 0.65 = 65%.",
+                    price
+                ));
+            }
+        }
+        if let Some(size) = Self::number(args, "size") {
+            if size <= 0.0 {
+                return ValidationResult::InvalidParams(format!("'size' must be positive (got {})", size));
+            }
+        }
+        ValidationResult::Allow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::types::ToolContext;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    async fn validate(args: Value) -> ValidationResult {
+        let ctx = ValidationContext::new("polymarket_trade".into(), args, Arc::new(ToolContext::new()));
+        PolymarketOrderValidator.validate(&ctx).await
+    }
+
+    #[tokio::test]
+    async fn test_price_and_size_invariants() {
+        assert!(validate(json!({"action": "place_order", "price": 0.65, "size": 10})).await.is_allowed());
+        assert!(validate(json!({"action": "get_price", "price": 65})).await.is_allowed());
+
+        let result = validate(json!({"action": "place_order", "price": 65, "size": 10})).await;
+        assert!(matches!(result, ValidationResult::InvalidParams(_)));
+        assert!(result.block_reason().unwrap().contains("between 0.01 and 0.99"));
+
+        let result = validate(json!({"action": "place_order", "price": "0.5", "size": 0})).await;
+        assert!(result.block_reason().unwrap().contains("'size' must be positive"));
+    }
+}
@@ -1,6 +1,6 @@
 //! Types for the tool validator subsystem
 
-use crate::tools::types::ToolContext;
+use crate::tools::types::{ToolContext, ToolErrorCode, ToolResult};
 use serde_json::Value;
 use std::sync::Arc;
 
@@ -79,6 +79,8 @@ pub enum ValidationResult {
         reason: String,
         suggestion: String,
     },
+    /// Block because the arguments break one of the tool's invariants
+    InvalidParams(String),
 }
 
 impl ValidationResult {
@@ -98,6 +100,7 @@ impl ValidationResult {
             ValidationResult::Allow => None,
             ValidationResult::Block(reason) => Some(reason),
             ValidationResult::BlockWithSuggestion { reason, .. } => Some(reason),
+            ValidationResult::InvalidParams(reason) => Some(reason),
         }
     }
 
@@ -117,6 +120,16 @@ impl ValidationResult {
             ValidationResult::BlockWithSuggestion { reason, suggestion } => {
                 Some(format!("Blocked: {} Suggestion: {}", reason, suggestion))
             }
+            ValidationResult::InvalidParams(reason) => Some(format!("Invalid parameters: {}", reason)),
+        }
+    }
+
+    /// The error result to return in place of the tool call, if blocked
+    pub fn to_tool_result(&self) -> Option<ToolResult> {
+        let result = ToolResult::error(self.to_error_message()?);
+        match self {
+            ValidationResult::InvalidParams(_) => Some(result.with_error_code(ToolErrorCode::InvalidParams)),
+            _ => Some(result),
         }
     }
 }
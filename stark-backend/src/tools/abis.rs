@@ -0,0 +1,209 @@
+//! ABI registry for `web3_function_call`
+//!
+//! ABIs come from JSON files in the `abis/` folder and from uploads through
+//! the API. Uploaded ABIs are stored in the database, cached here, and take
+//! precedence over files with the same name.
+
+use crate::db::Database;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// ABIs uploaded through the API (mirrors the `abis` table)
+static DB_ABIS: Lazy<RwLock<HashMap<String, AbiFile>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// ABI file structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbiFile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub abi: Vec<Value>,
+    /// Network -> known contract address
+    #[serde(default)]
+    pub address: HashMap<String, String>,
+}
+
+impl AbiFile {
+    /// Names of the functions the ABI defines
+    pub fn function_names(&self) -> Vec<String> {
+        self.abi
+            .iter()
+            .filter(|entry| entry.get("type").and_then(|t| t.as_str()) == Some("function"))
+            .filter_map(|entry| entry.get("name").and_then(|n| n.as_str()).map(String::from))
+            .collect()
+    }
+}
+
+/// ABI names double as file names, so keep them to a safe character set
+pub fn validate_abi_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        return Err(format!("Invalid ABI name '{}'", name));
+    }
+    Ok(())
+}
+
+/// Check an uploaded ABI: the `abi` array must parse with ethers and
+/// every known address must be a valid contract address.
+pub fn validate_abi(abi_file: &AbiFile) -> Result<(), String> {
+    if abi_file.abi.is_empty() {
+        return Err("abi must not be empty".to_string());
+    }
+    serde_json::from_value::<ethers::abi::Abi>(Value::Array(abi_file.abi.clone()))
+        .map_err(|e| format!("Failed to parse ABI: {}", e))?;
+
+    for (network, address) in &abi_file.address {
+        let valid = address.len() == 42
+            && address.starts_with("0x")
+            && address[2..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(format!("Invalid contract address for '{}': {}", network, address));
+        }
+    }
+    Ok(())
+}
+
+/// Load an ABI by name: uploaded ABIs first, then `<abis_dir>/<name>.json`
+pub fn load_abi(abis_dir: &Path, name: &str) -> Result<AbiFile, String> {
+    validate_abi_name(name)?;
+
+    if let Some(abi_file) = DB_ABIS.read().ok().and_then(|a| a.get(name).cloned()) {
+        return Ok(abi_file);
+    }
+    load_file_abi(abis_dir, name)
+}
+
+/// Load an ABI from `<abis_dir>/<name>.json` only
+pub fn load_file_abi(abis_dir: &Path, name: &str) -> Result<AbiFile, String> {
+    validate_abi_name(name)?;
+
+    let path = abis_dir.join(format!("{}.json", name));
+    let content = std::fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "Failed to load ABI '{}': {}. Available ABIs: {}",
+            name,
+            e,
+            list_abis(abis_dir).join(", ")
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse ABI '{}': {}", name, e))
+}
+
+/// Names of the ABI files in `abis_dir`, sorted
+pub fn list_file_abis(abis_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(abis_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| {
+                    let path = e.path();
+                    if path.extension().and_then(|x| x.to_str()) != Some("json") {
+                        return None;
+                    }
+                    path.file_stem().and_then(|s| s.to_str()).map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Names of all ABIs, uploaded and files, sorted
+pub fn list_abis(abis_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = DB_ABIS
+        .read()
+        .map(|a| a.keys().cloned().collect())
+        .unwrap_or_default();
+    for name in list_file_abis(abis_dir) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names.sort();
+    names
+}
+
+/// Load the ABIs uploaded through the API (call once at startup)
+pub fn load_db_abis(db: &Database) {
+    match db.list_abis() {
+        Ok(stored) => {
+            log::info!("[abis] Loaded {} uploaded ABIs from database", stored.len());
+            if let Ok(mut abis) = DB_ABIS.write() {
+                *abis = stored.into_iter().map(|a| (a.name, a.abi)).collect();
+            }
+        }
+        Err(e) => log::error!("[abis] Failed to load ABIs from database: {}", e),
+    }
+}
+
+/// Update the cached copy of an uploaded ABI after a write
+pub fn cache_db_abi(name: &str, abi_file: Option<AbiFile>) {
+    if let Ok(mut abis) = DB_ABIS.write() {
+        match abi_file {
+            Some(a) => {
+                abis.insert(name.to_string(), a);
+            }
+            None => {
+                abis.remove(name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn abi_file(abi: Value, address: &str) -> AbiFile {
+        serde_json::from_value(json!({
+            "name": "Token",
+            "abi": abi,
+            "address": { "base": address },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_abi() {
+        let approve = json!([{
+            "type": "function",
+            "name": "approve",
+            "inputs": [{"name": "spender", "type": "address"}, {"name": "amount", "type": "uint256"}],
+            "outputs": [{"name": "", "type": "bool"}],
+            "stateMutability": "nonpayable"
+        }]);
+        let address = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+
+        let valid = abi_file(approve.clone(), address);
+        assert!(validate_abi(&valid).is_ok());
+        assert_eq!(valid.function_names(), vec!["approve"]);
+
+        let bad_type = abi_file(json!([{"type": "function", "name": "f", "inputs": [{"name": "x", "type": "notatype"}]}]), address);
+        assert!(validate_abi(&bad_type).unwrap_err().contains("Failed to parse ABI"));
+
+        assert!(validate_abi(&abi_file(json!([]), address)).is_err());
+        assert!(validate_abi(&abi_file(approve, "0x1234")).unwrap_err().contains("Invalid contract address"));
+
+        assert!(validate_abi_name("../erc20").is_err());
+    }
+
+    #[test]
+    fn test_load_and_list_file_abis() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("erc20.json"), r#"{"name": "ERC20", "abi": []}"#).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        assert_eq!(list_file_abis(dir.path()), vec!["erc20"]);
+        assert_eq!(load_abi(dir.path(), "erc20").unwrap().name, "ERC20");
+
+        let err = load_abi(dir.path(), "weth").unwrap_err();
+        assert!(err.contains("Failed to load ABI 'weth'"));
+        assert!(err.contains("erc20"));
+    }
+}
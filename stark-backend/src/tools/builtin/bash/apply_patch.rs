@@ -1,3 +1,5 @@
+use super::diff::{unified_diff, FileDiff};
+use super::path_jail::{resolve_in_root, workspace_root};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -6,7 +8,9 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+
+/// Diff text included in the response; the metadata always carries the full diffs
+const MAX_DIFF_OUTPUT: usize = 20_000;
 
 /// ApplyPatch tool - applies structured patches to files within a sandboxed directory
 ///
@@ -23,6 +27,9 @@ use std::path::{Path, PathBuf};
 /// *** Delete File: path/to/remove.txt
 /// *** End Patch
 /// ```
+///
+/// Hunks must match the file exactly and at a single location; a hunk that
+/// would only apply with whitespace fuzz or at an offset is rejected.
 pub struct ApplyPatchTool {
     definition: ToolDefinition,
 }
@@ -41,10 +48,21 @@ impl ApplyPatchTool {
             },
         );
 
+        properties.insert(
+            "dry_run".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Show the diff of what the patch would change without writing anything (default: false)".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
         ApplyPatchTool {
             definition: ToolDefinition {
                 name: "apply_patch".to_string(),
-                description: "Apply a structured patch to create, modify, or delete files. Use for complex multi-file edits. Patch format uses '*** Begin Patch' / '*** End Patch' markers with '*** Add File:', '*** Update File:', '*** Delete File:', and '*** Move to:' operations. Line changes use '-' for removals, '+' for additions.".to_string(),
+                description: "Apply a structured patch to create, modify, or delete files. Use for complex multi-file edits. Patch format uses '*** Begin Patch' / '*** End Patch' markers with '*** Add File:', '*** Update File:', '*** Delete File:', and '*** Move to:' operations. Line changes use '-' for removals, '+' for additions, ' ' for context. Context and removed lines must match the file exactly. Returns a unified diff with lines added/removed; set dry_run to preview.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -65,6 +83,7 @@ impl Default for ApplyPatchTool {
 #[derive(Debug, Deserialize)]
 struct ApplyPatchParams {
     patch: String,
+    dry_run: Option<bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -244,33 +263,17 @@ fn parse_patch(input: &str) -> Result<Vec<PatchOperation>, PatchParseError> {
 fn apply_hunk(content: &str, hunk: &Hunk) -> Result<String, String> {
     let lines: Vec<&str> = content.lines().collect();
 
-    // Handle end-of-file additions
-    if hunk.is_end_of_file && hunk.removals.is_empty() && hunk.context_before.is_empty() {
-        let mut result = content.to_string();
-        if !result.ends_with('\n') && !result.is_empty() {
-            result.push('\n');
-        }
-        for addition in &hunk.additions {
-            result.push_str(addition);
-            result.push('\n');
-        }
-        return Ok(result.trim_end_matches('\n').to_string());
-    }
-
-    // Find the location to apply the hunk using context
-    let mut match_start = None;
-
-    // Build the pattern we're looking for (context_before + removals)
-    let mut pattern: Vec<&str> = Vec::new();
-    for ctx in &hunk.context_before {
-        pattern.push(ctx.as_str());
-    }
-    for removal in &hunk.removals {
-        pattern.push(removal.as_str());
-    }
+    // Build the pattern we're looking for (context_before + removals + context_after)
+    let pattern: Vec<&str> = hunk
+        .context_before
+        .iter()
+        .chain(&hunk.removals)
+        .chain(&hunk.context_after)
+        .map(|s| s.as_str())
+        .collect();
 
     if pattern.is_empty() {
-        // No pattern to match - if we have only additions and no context, append to end
+        // No pattern to match - only additions, append to end
         if !hunk.additions.is_empty() {
             let mut result = content.to_string();
             if !result.ends_with('\n') && !result.is_empty() {
@@ -285,118 +288,64 @@ fn apply_hunk(content: &str, hunk: &Hunk) -> Result<String, String> {
         return Ok(content.to_string());
     }
 
-    // Search for the pattern in the file
-    'outer: for start_idx in 0..=lines.len().saturating_sub(pattern.len()) {
-        for (j, pattern_line) in pattern.iter().enumerate() {
-            if start_idx + j >= lines.len() {
-                continue 'outer;
-            }
-            if lines[start_idx + j].trim() != pattern_line.trim() {
-                continue 'outer;
-            }
-        }
-        match_start = Some(start_idx);
-        break;
-    }
-
-    let match_start = match match_start {
-        Some(idx) => idx,
-        None => {
-            // Try a more lenient match with just removals
-            if !hunk.removals.is_empty() {
-                'outer2: for start_idx in 0..=lines.len().saturating_sub(hunk.removals.len()) {
-                    for (j, removal) in hunk.removals.iter().enumerate() {
-                        if start_idx + j >= lines.len() {
-                            continue 'outer2;
-                        }
-                        if lines[start_idx + j].trim() != removal.trim() {
-                            continue 'outer2;
-                        }
-                    }
-                    // Found with just removals - adjust start to include context_before
-                    let adjusted = start_idx.saturating_sub(hunk.context_before.len());
-                    return apply_at_position(content, hunk, adjusted);
-                }
-            }
-            return Err(format!(
-                "Could not find matching context in file. Looking for: {:?}",
-                pattern
-            ));
-        }
-    };
-
-    apply_at_position(content, hunk, match_start)
-}
-
-fn apply_at_position(content: &str, hunk: &Hunk, match_start: usize) -> Result<String, String> {
-    let lines: Vec<&str> = content.lines().collect();
-    let mut result = Vec::new();
-
-    // Add lines before the match
-    for i in 0..match_start {
-        result.push(lines[i].to_string());
+    // Only exact matches are applied; an End of File hunk must match at the end
+    let mut matches = find_matches(&lines, &pattern, |a, b| a == b);
+    if hunk.is_end_of_file {
+        matches.retain(|&start| start + pattern.len() == lines.len());
     }
 
-    // Add context before (they should already match)
-    for ctx in &hunk.context_before {
-        result.push(ctx.clone());
+    match matches.as_slice() {
+        [start] => Ok(apply_at_position(&lines, hunk, *start)),
+        [] => Err(explain_mismatch(&lines, hunk, &pattern)),
+        many => Err(format!(
+            "Context matches {} locations (lines {}). Add more context lines so the hunk matches exactly once",
+            many.len(),
+            many.iter().map(|s| (s + 1).to_string()).collect::<Vec<_>>().join(", ")
+        )),
     }
+}
 
-    // Skip the removed lines and add the new lines
-    let skip_count = hunk.context_before.len() + hunk.removals.len();
-
-    // Add the additions
-    for addition in &hunk.additions {
-        result.push(addition.clone());
+/// Start indices where `pattern` matches consecutive `lines` under `eq`
+fn find_matches(lines: &[&str], pattern: &[&str], eq: impl Fn(&str, &str) -> bool) -> Vec<usize> {
+    if pattern.len() > lines.len() {
+        return Vec::new();
     }
+    (0..=lines.len() - pattern.len())
+        .filter(|&start| pattern.iter().enumerate().all(|(j, p)| eq(lines[start + j], p)))
+        .collect()
+}
 
-    // Add context after
-    for ctx in &hunk.context_after {
-        result.push(ctx.clone());
+/// Say why a hunk didn't match exactly, rather than applying it with fuzz or at an offset
+fn explain_mismatch(lines: &[&str], hunk: &Hunk, pattern: &[&str]) -> String {
+    if let Some(start) = find_matches(lines, pattern, |a, b| a.trim() == b.trim()).first() {
+        return format!(
+            "Context only matches at line {} when ignoring whitespace. Refusing to apply with fuzz - copy the lines exactly as they appear in the file",
+            start + 1
+        );
     }
-
-    // Calculate where to continue from in original
-    let continue_from = match_start + skip_count + hunk.context_after.len();
-
-    // Add remaining lines
-    for i in continue_from..lines.len() {
-        result.push(lines[i].to_string());
+    if !hunk.removals.is_empty() {
+        let removals: Vec<&str> = hunk.removals.iter().map(|s| s.as_str()).collect();
+        if let Some(start) = find_matches(lines, &removals, |a, b| a.trim() == b.trim()).first() {
+            return format!(
+                "Removed lines found at line {} but the surrounding context doesn't match. Refusing to apply at an offset - check the context lines",
+                start + 1
+            );
+        }
     }
-
-    Ok(result.join("\n"))
+    format!("Could not find matching context in file. Looking for: {:?}", pattern)
 }
 
-fn resolve_and_validate_path(
-    requested_path: &str,
-    workspace: &Path,
-    canonical_workspace: &Path,
-) -> Result<PathBuf, String> {
-    let path = Path::new(requested_path);
-    let full_path = if path.is_absolute() {
-        path.to_path_buf()
-    } else {
-        workspace.join(path)
-    };
-
-    // For paths that don't exist yet, validate the parent
-    let check_path = if full_path.exists() {
-        full_path.clone()
-    } else {
-        full_path.parent().map(|p| p.to_path_buf()).unwrap_or(full_path.clone())
-    };
-
-    // Try to canonicalize (may fail if path doesn't exist)
-    if check_path.exists() {
-        let canonical = check_path.canonicalize().map_err(|e| e.to_string())?;
-        if !canonical.starts_with(canonical_workspace) {
-            return Err(format!(
-                "Access denied: path '{}' is outside the workspace directory",
-                requested_path
-            ));
-        }
-    }
+fn apply_at_position(lines: &[&str], hunk: &Hunk, match_start: usize) -> String {
+    let matched_len = hunk.context_before.len() + hunk.removals.len() + hunk.context_after.len();
+
+    let mut result: Vec<&str> = Vec::with_capacity(lines.len() + hunk.additions.len());
+    result.extend_from_slice(&lines[..match_start]);
+    result.extend(hunk.context_before.iter().map(|s| s.as_str()));
+    result.extend(hunk.additions.iter().map(|s| s.as_str()));
+    result.extend(hunk.context_after.iter().map(|s| s.as_str()));
+    result.extend_from_slice(&lines[match_start + matched_len..]);
 
-    Ok(full_path)
+    result.join("\n")
 }
 
 #[async_trait]
@@ -410,6 +359,7 @@ impl Tool for ApplyPatchTool {
             Ok(p) => p,
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
+        let dry_run = params.dry_run.unwrap_or(false);
 
         // Parse the patch
         let operations = match parse_patch(&params.patch) {
@@ -421,22 +371,11 @@ impl Tool for ApplyPatchTool {
             return ToolResult::error("No operations found in patch");
         }
 
-        // Get workspace directory from context or use current directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        // Canonicalize workspace for comparison
-        let canonical_workspace = match workspace.canonicalize() {
-            Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve workspace directory: {}", e))
-            }
-        };
+        // Paths are resolved through the workspace jail
+        let workspace = workspace_root(context);
 
         let mut results = Vec::new();
+        let mut diffs: Vec<(FileDiff, Value)> = Vec::new();
         let mut files_added = 0;
         let mut files_updated = 0;
         let mut files_deleted = 0;
@@ -445,7 +384,7 @@ impl Tool for ApplyPatchTool {
         for operation in operations {
             match operation {
                 PatchOperation::AddFile { path, content } => {
-                    let full_path = match resolve_and_validate_path(&path, &workspace, &canonical_workspace) {
+                    let full_path = match resolve_in_root(&workspace, &path) {
                         Ok(p) => p,
                         Err(e) => {
                             results.push(format!("FAILED Add '{}': {}", path, e));
@@ -453,27 +392,37 @@ impl Tool for ApplyPatchTool {
                         }
                     };
 
-                    // Create parent directories if needed
-                    if let Some(parent) = full_path.parent() {
-                        if let Err(e) = tokio::fs::create_dir_all(parent).await {
-                            results.push(format!("FAILED Add '{}': Cannot create directories: {}", path, e));
-                            continue;
-                        }
-                    }
+                    let diff = unified_diff(None, Some(&path), "", &content);
+                    let mut entry = diff.to_json(&path);
+                    entry["operation"] = json!("add");
 
-                    match tokio::fs::write(&full_path, &content).await {
-                        Ok(_) => {
-                            results.push(format!("Added '{}'", path));
-                            files_added += 1;
+                    if !dry_run {
+                        // Create parent directories if needed
+                        if let Some(parent) = full_path.parent() {
+                            if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                                results.push(format!("FAILED Add '{}': Cannot create directories: {}", path, e));
+                                continue;
+                            }
                         }
-                        Err(e) => {
+
+                        if let Err(e) = tokio::fs::write(&full_path, &content).await {
                             results.push(format!("FAILED Add '{}': {}", path, e));
+                            continue;
                         }
                     }
+
+                    results.push(format!(
+                        "{} '{}' (+{})",
+                        if dry_run { "Would add" } else { "Added" },
+                        path,
+                        diff.lines_added
+                    ));
+                    files_added += 1;
+                    diffs.push((diff, entry));
                 }
 
                 PatchOperation::UpdateFile { path, hunks, move_to } => {
-                    let full_path = match resolve_and_validate_path(&path, &workspace, &canonical_workspace) {
+                    let full_path = match resolve_in_root(&workspace, &path) {
                         Ok(p) => p,
                         Err(e) => {
                             results.push(format!("FAILED Update '{}': {}", path, e));
@@ -491,7 +440,7 @@ impl Tool for ApplyPatchTool {
                     };
 
                     // Apply hunks sequentially
-                    let mut current_content = content;
+                    let mut current_content = content.clone();
                     let mut hunk_errors = Vec::new();
 
                     for (idx, hunk) in hunks.iter().enumerate() {
@@ -510,9 +459,14 @@ impl Tool for ApplyPatchTool {
                         continue;
                     }
 
+                    // Hunks are applied line by line; keep the file's trailing newline
+                    if content.ends_with('\n') && !current_content.is_empty() && !current_content.ends_with('\n') {
+                        current_content.push('\n');
+                    }
+
                     // Handle move operation
                     let target_path = if let Some(ref new_path) = move_to {
-                        match resolve_and_validate_path(new_path, &workspace, &canonical_workspace) {
+                        match resolve_in_root(&workspace, new_path) {
                             Ok(p) => p,
                             Err(e) => {
                                 results.push(format!("FAILED Move '{}' to '{}': {}", path, new_path, e));
@@ -522,41 +476,61 @@ impl Tool for ApplyPatchTool {
                     } else {
                         full_path.clone()
                     };
-
-                    // Create parent directories for target if needed
-                    if let Some(parent) = target_path.parent() {
-                        if let Err(e) = tokio::fs::create_dir_all(parent).await {
-                            results.push(format!("FAILED Update '{}': Cannot create directories: {}", path, e));
-                            continue;
-                        }
+                    let moved = target_path != full_path;
+                    let target_name = move_to.as_deref().unwrap_or(&path);
+
+                    let diff = unified_diff(Some(&path), Some(target_name), &content, &current_content);
+                    let mut entry = diff.to_json(&path);
+                    entry["operation"] = json!(if moved { "move" } else { "update" });
+                    if moved {
+                        entry["move_to"] = json!(target_name);
                     }
 
-                    // Write the updated content
-                    match tokio::fs::write(&target_path, &current_content).await {
-                        Ok(_) => {
-                            if move_to.is_some() {
-                                // Delete the original file if we moved
-                                if full_path != target_path {
-                                    let _ = tokio::fs::remove_file(&full_path).await;
-                                    results.push(format!("Updated and moved '{}' to '{}'", path, move_to.as_ref().unwrap()));
-                                    files_moved += 1;
-                                } else {
-                                    results.push(format!("Updated '{}'", path));
-                                    files_updated += 1;
-                                }
-                            } else {
-                                results.push(format!("Updated '{}'", path));
-                                files_updated += 1;
+                    if !dry_run {
+                        // Create parent directories for target if needed
+                        if let Some(parent) = target_path.parent() {
+                            if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                                results.push(format!("FAILED Update '{}': Cannot create directories: {}", path, e));
+                                continue;
                             }
                         }
-                        Err(e) => {
+
+                        // Write the updated content
+                        if let Err(e) = tokio::fs::write(&target_path, &current_content).await {
                             results.push(format!("FAILED Update '{}': Cannot write file: {}", path, e));
+                            continue;
+                        }
+
+                        // Delete the original file if we moved
+                        if moved {
+                            let _ = tokio::fs::remove_file(&full_path).await;
                         }
                     }
+
+                    let counts = format!("(+{} -{})", diff.lines_added, diff.lines_removed);
+                    if moved {
+                        results.push(format!(
+                            "{} '{}' to '{}' {}",
+                            if dry_run { "Would update and move" } else { "Updated and moved" },
+                            path,
+                            target_name,
+                            counts
+                        ));
+                        files_moved += 1;
+                    } else {
+                        results.push(format!(
+                            "{} '{}' {}",
+                            if dry_run { "Would update" } else { "Updated" },
+                            path,
+                            counts
+                        ));
+                        files_updated += 1;
+                    }
+                    diffs.push((diff, entry));
                 }
 
                 PatchOperation::DeleteFile { path } => {
-                    let full_path = match resolve_and_validate_path(&path, &workspace, &canonical_workspace) {
+                    let full_path = match resolve_in_root(&workspace, &path) {
                         Ok(p) => p,
                         Err(e) => {
                             results.push(format!("FAILED Delete '{}': {}", path, e));
@@ -564,30 +538,73 @@ impl Tool for ApplyPatchTool {
                         }
                     };
 
-                    match tokio::fs::remove_file(&full_path).await {
-                        Ok(_) => {
-                            results.push(format!("Deleted '{}'", path));
-                            files_deleted += 1;
-                        }
-                        Err(e) => {
-                            results.push(format!("FAILED Delete '{}': {}", path, e));
-                        }
+                    if !full_path.is_file() {
+                        results.push(format!("FAILED Delete '{}': File not found", path));
+                        continue;
                     }
+
+                    // Non-text files are deleted without a line diff
+                    let old_content = tokio::fs::read_to_string(&full_path).await.unwrap_or_default();
+                    let diff = unified_diff(Some(&path), None, &old_content, "");
+                    let mut entry = diff.to_json(&path);
+                    entry["operation"] = json!("delete");
+
+                    let remove_result = if dry_run {
+                        Ok(())
+                    } else {
+                        tokio::fs::remove_file(&full_path).await
+                    };
+                    if let Err(e) = remove_result {
+                        results.push(format!("FAILED Delete '{}': {}", path, e));
+                        continue;
+                    }
+
+                    results.push(format!(
+                        "{} '{}' (-{})",
+                        if dry_run { "Would delete" } else { "Deleted" },
+                        path,
+                        diff.lines_removed
+                    ));
+                    files_deleted += 1;
+                    diffs.push((diff, entry));
                 }
             }
         }
 
+        let lines_added: usize = diffs.iter().map(|(d, _)| d.lines_added).sum();
+        let lines_removed: usize = diffs.iter().map(|(d, _)| d.lines_removed).sum();
+
         let summary = format!(
-            "Patch applied: {} added, {} updated, {} moved, {} deleted",
-            files_added, files_updated, files_moved, files_deleted
+            "{}: {} added, {} updated, {} moved, {} deleted (+{} -{} lines)",
+            if dry_run { "Dry run, no files written. Patch would apply" } else { "Patch applied" },
+            files_added, files_updated, files_moved, files_deleted, lines_added, lines_removed
         );
 
-        ToolResult::success(format!("{}\n\n{}", summary, results.join("\n")))
+        let mut diff_text: String = diffs.iter().map(|(d, _)| d.unified.as_str()).collect();
+        if diff_text.len() > MAX_DIFF_OUTPUT {
+            let mut cut = MAX_DIFF_OUTPUT;
+            while !diff_text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            diff_text.truncate(cut);
+            diff_text.push_str("\n... (diff truncated, full diff in metadata)\n");
+        }
+
+        let mut message = format!("{}\n\n{}", summary, results.join("\n"));
+        if !diff_text.is_empty() {
+            message.push_str(&format!("\n\n{}", diff_text.trim_end()));
+        }
+
+        ToolResult::success(message)
             .with_metadata(json!({
+                "dry_run": dry_run,
                 "files_added": files_added,
                 "files_updated": files_updated,
                 "files_moved": files_moved,
                 "files_deleted": files_deleted,
+                "lines_added": lines_added,
+                "lines_removed": lines_removed,
+                "diffs": diffs.into_iter().map(|(_, entry)| entry).collect::<Vec<_>>(),
                 "details": results
             }))
     }
@@ -696,6 +713,32 @@ mod tests {
         assert_eq!(result, "line 1\nnew line\nline 3");
     }
 
+    #[test]
+    fn test_apply_hunk_rejects_fuzz_offset_and_ambiguity() {
+        let hunk = |before: &str, removal: &str, after: &str| Hunk {
+            context_before: vec![before.to_string()],
+            removals: vec![removal.to_string()],
+            additions: vec!["new line".to_string()],
+            context_after: vec![after.to_string()],
+            is_end_of_file: false,
+        };
+
+        // Whitespace differences are fuzz
+        let err = apply_hunk("line 1\n    old line\nline 3", &hunk("line 1", "old line", "line 3")).unwrap_err();
+        assert!(err.contains("ignoring whitespace"), "{}", err);
+
+        // Removed lines exist, but not next to the given context
+        let err = apply_hunk("line 1\nother\nold line\nline 3", &hunk("line 1", "old line", "line 3")).unwrap_err();
+        assert!(err.contains("offset"), "{}", err);
+
+        // Trailing context is checked too, not blindly overwritten
+        let err = apply_hunk("line 1\nold line\nline 4", &hunk("line 1", "old line", "line 3")).unwrap_err();
+        assert!(err.contains("offset"), "{}", err);
+
+        let err = apply_hunk("a\nold\nb\na\nold\nb", &hunk("a", "old", "b")).unwrap_err();
+        assert!(err.contains("2 locations"), "{}", err);
+    }
+
     #[tokio::test]
     async fn test_add_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -757,6 +800,51 @@ mod tests {
         assert_eq!(content, "line 1\nnew line\nline 3");
     }
 
+    #[tokio::test]
+    async fn test_update_dry_run_reports_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().to_string_lossy().to_string();
+        let original = "line 1\nold line\nline 3\n";
+        tokio::fs::write(temp_dir.path().join("test.txt"), original).await.unwrap();
+
+        let tool = ApplyPatchTool::new();
+        let context = ToolContext::new().with_workspace(workspace);
+
+        let patch = r#"*** Begin Patch
+*** Update File: test.txt
+@@
+ line 1
+-old line
++new line
++another line
+ line 3
+*** End Patch"#;
+
+        let result = tool
+            .execute(json!({ "patch": patch, "dry_run": true }), &context)
+            .await;
+        assert!(result.success, "Error: {:?}", result.error);
+
+        let metadata = result.metadata.unwrap();
+        assert_eq!(metadata["dry_run"], true);
+        assert_eq!(metadata["lines_added"], 2);
+        assert_eq!(metadata["lines_removed"], 1);
+        assert_eq!(
+            metadata["diffs"][0]["diff"],
+            "--- a/test.txt\n+++ b/test.txt\n@@ -1,3 +1,4 @@\n line 1\n-old line\n+new line\n+another line\n line 3\n"
+        );
+
+        // Nothing written
+        let content = tokio::fs::read_to_string(temp_dir.path().join("test.txt")).await.unwrap();
+        assert_eq!(content, original);
+
+        // Applying for real keeps the trailing newline
+        let result = tool.execute(json!({ "patch": patch }), &context).await;
+        assert!(result.success, "Error: {:?}", result.error);
+        let content = tokio::fs::read_to_string(temp_dir.path().join("test.txt")).await.unwrap();
+        assert_eq!(content, "line 1\nnew line\nanother line\nline 3\n");
+    }
+
     #[tokio::test]
     async fn test_delete_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,3 +1,4 @@
+use super::path_jail::{canonical_workspace_root, resolve_workspace_path};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -6,7 +7,6 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
 
 /// Delete file tool - removes files or directories within a sandboxed directory
 pub struct DeleteFileTool {
@@ -81,42 +81,16 @@ impl Tool for DeleteFileTool {
 
         let recursive = params.recursive.unwrap_or(false);
 
-        // Get workspace directory from context or use current directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        // Resolve the path
-        let requested_path = Path::new(&params.path);
-        let full_path = if requested_path.is_absolute() {
-            requested_path.to_path_buf()
-        } else {
-            workspace.join(requested_path)
-        };
-
-        // Canonicalize paths for comparison
-        let canonical_workspace = match workspace.canonicalize() {
+        // Resolve the path inside the workspace
+        let canonical_workspace = match canonical_workspace_root(context) {
             Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve workspace directory: {}", e))
-            }
+            Err(e) => return ToolResult::error(e),
         };
-
-        let canonical_path = match full_path.canonicalize() {
+        let canonical_path = match resolve_workspace_path(context, &params.path) {
             Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Cannot resolve path: {}", e)),
+            Err(e) => return ToolResult::error(e),
         };
 
-        // Security check: ensure path is within workspace
-        if !canonical_path.starts_with(&canonical_workspace) {
-            return ToolResult::error(format!(
-                "Access denied: path '{}' is outside the workspace directory",
-                params.path
-            ));
-        }
-
         // Don't allow deleting the workspace itself
         if canonical_path == canonical_workspace {
             return ToolResult::error("Cannot delete the workspace root directory");
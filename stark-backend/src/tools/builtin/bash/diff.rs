@@ -0,0 +1,256 @@
+//! Line-based unified diffs for the file editing tools
+//!
+//! Produces `diff -u` style output with 3 lines of context, plus the number
+//! of lines added and removed, so edit results carry a reliable measure of
+//! what changed. The hunks are also kept separately for tools that work on
+//! them one at a time, such as the committer's commit splitting.
+
+use serde_json::{json, Value};
+
+/// Lines of unchanged context around each hunk
+const CONTEXT_LINES: usize = 3;
+
+/// Above this many cells the LCS table is skipped and the changed region is
+/// reported as a straight replacement
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// One hunk of a unified diff
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct DiffHunk {
+    /// The `@@ -a,b +c,d @@` line
+    pub header: String,
+    /// Body lines, each prefixed with ' ', '-' or '+'
+    pub lines: Vec<String>,
+}
+
+impl DiffHunk {
+    /// The hunk's added and removed lines, without their prefix
+    pub fn changed_lines(&self) -> impl Iterator<Item = &str> {
+        self.lines
+            .iter()
+            .filter(|l| l.starts_with('+') || l.starts_with('-'))
+            .map(|l| &l[1..])
+    }
+}
+
+/// A unified diff between two versions of a file
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FileDiff {
+    pub unified: String,
+    pub hunks: Vec<DiffHunk>,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+impl FileDiff {
+    /// Metadata entry for a tool result
+    pub fn to_json(&self, path: &str) -> Value {
+        json!({
+            "path": path,
+            "diff": self.unified,
+            "lines_added": self.lines_added,
+            "lines_removed": self.lines_removed,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tag {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// One line of the edit script, with the old/new line indices before it is applied
+#[derive(Debug, Clone, Copy)]
+struct Edit {
+    tag: Tag,
+    old_idx: usize,
+    new_idx: usize,
+}
+
+/// Diff `old` against `new`. `None` paths are shown as `/dev/null` (file added or deleted).
+pub(crate) fn unified_diff(old_path: Option<&str>, new_path: Option<&str>, old: &str, new: &str) -> FileDiff {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let edits = edit_script(&old_lines, &new_lines);
+
+    let lines_added = edits.iter().filter(|e| e.tag == Tag::Insert).count();
+    let lines_removed = edits.iter().filter(|e| e.tag == Tag::Delete).count();
+    if lines_added == 0 && lines_removed == 0 {
+        return FileDiff::default();
+    }
+
+    let mut unified = format!(
+        "--- {}\n+++ {}\n",
+        old_path.map(|p| format!("a/{}", p)).unwrap_or_else(|| "/dev/null".to_string()),
+        new_path.map(|p| format!("b/{}", p)).unwrap_or_else(|| "/dev/null".to_string()),
+    );
+
+    let mut hunks = Vec::new();
+    for (start, end) in hunk_ranges(&edits) {
+        let hunk = &edits[start..end];
+        let old_len = hunk.iter().filter(|e| e.tag != Tag::Insert).count();
+        let new_len = hunk.iter().filter(|e| e.tag != Tag::Delete).count();
+        let header = format!(
+            "@@ -{} +{} @@",
+            hunk_range(hunk[0].old_idx, old_len),
+            hunk_range(hunk[0].new_idx, new_len)
+        );
+        let lines: Vec<String> = hunk
+            .iter()
+            .map(|edit| match edit.tag {
+                Tag::Equal => format!(" {}", old_lines[edit.old_idx]),
+                Tag::Delete => format!("-{}", old_lines[edit.old_idx]),
+                Tag::Insert => format!("+{}", new_lines[edit.new_idx]),
+            })
+            .collect();
+
+        unified.push_str(&header);
+        unified.push('\n');
+        for line in &lines {
+            unified.push_str(line);
+            unified.push('\n');
+        }
+        hunks.push(DiffHunk { header, lines });
+    }
+
+    FileDiff { unified, hunks, lines_added, lines_removed }
+}
+
+/// `start,len` as printed in a hunk header (empty ranges point at the preceding line)
+fn hunk_range(start: usize, len: usize) -> String {
+    let first = if len == 0 { start } else { start + 1 };
+    format!("{},{}", first, len)
+}
+
+/// Minimal line edit script via LCS, after trimming the common prefix and suffix
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut edits = Vec::with_capacity(old.len().max(new.len()));
+    for i in 0..prefix {
+        edits.push(Edit { tag: Tag::Equal, old_idx: i, new_idx: i });
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n.saturating_mul(m) <= MAX_LCS_CELLS {
+        // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..]
+        let width = m + 1;
+        let mut lcs = vec![0u32; (n + 1) * width];
+        for a in (0..n).rev() {
+            for b in (0..m).rev() {
+                lcs[a * width + b] = if old_mid[a] == new_mid[b] {
+                    lcs[(a + 1) * width + b + 1] + 1
+                } else {
+                    lcs[(a + 1) * width + b].max(lcs[a * width + b + 1])
+                };
+            }
+        }
+        while i < n && j < m {
+            let tag = if old_mid[i] == new_mid[j] {
+                Tag::Equal
+            } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+                Tag::Delete
+            } else {
+                Tag::Insert
+            };
+            edits.push(Edit { tag, old_idx: prefix + i, new_idx: prefix + j });
+            match tag {
+                Tag::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+                Tag::Delete => i += 1,
+                Tag::Insert => j += 1,
+            }
+        }
+    }
+    while i < n {
+        edits.push(Edit { tag: Tag::Delete, old_idx: prefix + i, new_idx: prefix + j });
+        i += 1;
+    }
+    while j < m {
+        edits.push(Edit { tag: Tag::Insert, old_idx: prefix + i, new_idx: prefix + j });
+        j += 1;
+    }
+
+    for k in 0..suffix {
+        edits.push(Edit { tag: Tag::Equal, old_idx: prefix + n + k, new_idx: prefix + m + k });
+    }
+    edits
+}
+
+/// Group changes into `[start, end)` ranges of the edit script, with context,
+/// merging changes separated by no more than twice the context
+fn hunk_ranges(edits: &[Edit]) -> Vec<(usize, usize)> {
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.tag != Tag::Equal)
+        .map(|(k, _)| k)
+        .collect();
+
+    let mut ranges = Vec::new();
+    let mut k = 0;
+    while k < changes.len() {
+        let start = changes[k].saturating_sub(CONTEXT_LINES);
+        let mut last = changes[k];
+        while k + 1 < changes.len() && changes[k + 1] - last <= 2 * CONTEXT_LINES + 1 {
+            k += 1;
+            last = changes[k];
+        }
+        ranges.push((start, (last + CONTEXT_LINES + 1).min(edits.len())));
+        k += 1;
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_single_change() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nb\nc\nd\nE\nf\ng\nh\n";
+        let diff = unified_diff(Some("x.txt"), Some("x.txt"), old, new);
+
+        assert_eq!(diff.lines_added, 1);
+        assert_eq!(diff.lines_removed, 1);
+        assert_eq!(
+            diff.unified,
+            "--- a/x.txt\n+++ b/x.txt\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_added_file_and_separate_hunks() {
+        let diff = unified_diff(None, Some("new.txt"), "", "one\ntwo\n");
+        assert_eq!(diff.unified, "--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+one\n+two\n");
+        assert_eq!((diff.lines_added, diff.lines_removed), (2, 0));
+
+        let old: Vec<String> = (1..=20).map(|i| i.to_string()).collect();
+        let mut new = old.clone();
+        new[1] = "two".to_string();
+        new.remove(17);
+        let diff = unified_diff(Some("n"), Some("n"), &old.join("\n"), &new.join("\n"));
+        assert_eq!(diff.unified.matches("@@ -").count(), 2);
+        assert_eq!(diff.hunks.len(), 2);
+        assert_eq!(diff.hunks[0].changed_lines().collect::<Vec<_>>(), vec!["2", "two"]);
+        assert_eq!((diff.lines_added, diff.lines_removed), (1, 2));
+
+        let diff = unified_diff(Some("n"), Some("n"), "same\n", "same\n");
+        assert!(diff.unified.is_empty() && diff.lines_added == 0 && diff.lines_removed == 0);
+    }
+}
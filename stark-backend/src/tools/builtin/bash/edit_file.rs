@@ -1,3 +1,5 @@
+use super::diff::unified_diff;
+use super::path_jail::resolve_workspace_path;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -6,7 +8,6 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
 
 /// Edit file tool for precise string replacement
 /// Similar to Claude Code's edit tool - requires exact match of old_text
@@ -67,10 +68,21 @@ impl EditFileTool {
             },
         );
 
+        properties.insert(
+            "dry_run".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Show the diff of the edit without writing the file (default: false)".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
         EditFileTool {
             definition: ToolDefinition {
                 name: "edit_file".to_string(),
-                description: "Edit a file by replacing exact text. old_text must match exactly (including whitespace). Returns the edited section with context. For large changes, prefer write_file or apply_patch.".to_string(),
+                description: "Edit a file by replacing exact text. old_text must match exactly (including whitespace). Returns a unified diff and the edited section with context; set dry_run to preview. For large changes, prefer write_file or apply_patch.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -85,29 +97,6 @@ impl EditFileTool {
         }
     }
 
-    /// Generate a simple diff-like preview of the change
-    fn generate_diff(old_text: &str, new_text: &str, context_lines: usize) -> String {
-        let old_lines: Vec<&str> = old_text.lines().collect();
-        let new_lines: Vec<&str> = new_text.lines().collect();
-
-        let mut diff = String::new();
-        diff.push_str("--- before\n");
-        diff.push_str("+++ after\n");
-        diff.push_str("@@\n");
-
-        // Show removed lines
-        for line in &old_lines {
-            diff.push_str(&format!("-{}\n", line));
-        }
-
-        // Show added lines
-        for line in &new_lines {
-            diff.push_str(&format!("+{}\n", line));
-        }
-
-        diff
-    }
-
     /// Show context around the edit location
     fn show_context(content: &str, edit_start: usize, new_text: &str, context_lines: usize) -> String {
         let lines: Vec<&str> = content.lines().collect();
@@ -145,6 +134,7 @@ struct EditFileParams {
     old_text: String,
     new_text: String,
     occurrence: Option<String>,
+    dry_run: Option<bool>,
 }
 
 #[async_trait]
@@ -168,42 +158,12 @@ impl Tool for EditFileTool {
             return ToolResult::error("old_text and new_text are identical - no change needed");
         }
 
-        // Get workspace directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        // Resolve the path
-        let requested_path = Path::new(&params.path);
-        let full_path = if requested_path.is_absolute() {
-            requested_path.to_path_buf()
-        } else {
-            workspace.join(requested_path)
-        };
-
-        // Canonicalize paths for comparison
-        let canonical_workspace = match workspace.canonicalize() {
-            Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve workspace directory: {}", e))
-            }
-        };
-
-        let canonical_path = match full_path.canonicalize() {
+        // Resolve the path inside the workspace
+        let canonical_path = match resolve_workspace_path(context, &params.path) {
             Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Cannot resolve file path: {}", e)),
+            Err(e) => return ToolResult::error(e),
         };
 
-        // Security check: ensure path is within workspace
-        if !canonical_path.starts_with(&canonical_workspace) {
-            return ToolResult::error(format!(
-                "Access denied: path '{}' is outside the workspace directory",
-                params.path
-            ));
-        }
-
         // Check if file exists
         if !canonical_path.exists() {
             return ToolResult::error(format!("File not found: {}", params.path));
@@ -274,24 +234,33 @@ impl Tool for EditFileTool {
             }
         };
 
+        let dry_run = params.dry_run.unwrap_or(false);
+
         // Write the file
-        if let Err(e) = tokio::fs::write(&canonical_path, &new_content).await {
+        let write_result = if dry_run {
+            Ok(())
+        } else {
+            tokio::fs::write(&canonical_path, &new_content).await
+        };
+        if let Err(e) = write_result {
             return ToolResult::error(format!("Failed to write file: {}", e));
         }
 
         // Generate output
-        let diff = Self::generate_diff(&params.old_text, &params.new_text, 3);
+        let diff = unified_diff(Some(&params.path), Some(&params.path), &content, &new_content);
         let context_view = Self::show_context(&new_content, edit_position, &params.new_text, 3);
 
+        let action = if dry_run { "Would replace" } else { "Replaced" };
+        let after = if dry_run { "Context after edit (not written)" } else { "Context after edit" };
         let message = if count > 1 && occurrence != "all" {
             format!(
-                "Replaced {} of {} occurrences ({} mode).\n\n{}\n\nContext after edit:\n{}",
-                replaced_count, count, occurrence, diff, context_view
+                "{} {} of {} occurrences ({} mode), +{} -{} lines.\n\n{}\n{}:\n{}",
+                action, replaced_count, count, occurrence, diff.lines_added, diff.lines_removed, diff.unified, after, context_view
             )
         } else {
             format!(
-                "Replaced {} occurrence(s).\n\n{}\n\nContext after edit:\n{}",
-                replaced_count, diff, context_view
+                "{} {} occurrence(s), +{} -{} lines.\n\n{}\n{}:\n{}",
+                action, replaced_count, diff.lines_added, diff.lines_removed, diff.unified, after, context_view
             )
         };
 
@@ -299,7 +268,11 @@ impl Tool for EditFileTool {
             "path": params.path,
             "occurrences_found": count,
             "occurrences_replaced": replaced_count,
-            "mode": occurrence
+            "mode": occurrence,
+            "dry_run": dry_run,
+            "diff": diff.unified,
+            "lines_added": diff.lines_added,
+            "lines_removed": diff.lines_removed
         }))
     }
 }
@@ -336,6 +309,40 @@ mod tests {
         assert_eq!(content, "Hello Rust");
     }
 
+    #[tokio::test]
+    async fn test_edit_file_dry_run_diff() {
+        let tool = EditFileTool::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let test_file = temp_dir.path().join("test.txt");
+        std::fs::write(&test_file, "one\ntwo\nthree\n").unwrap();
+
+        let context =
+            ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
+
+        let result = tool
+            .execute(
+                json!({
+                    "path": "test.txt",
+                    "old_text": "two\n",
+                    "new_text": "2\n2.5\n",
+                    "dry_run": true
+                }),
+                &context,
+            )
+            .await;
+
+        assert!(result.success);
+        let metadata = result.metadata.unwrap();
+        assert_eq!(metadata["lines_added"], 2);
+        assert_eq!(metadata["lines_removed"], 1);
+        assert_eq!(
+            metadata["diff"],
+            "--- a/test.txt\n+++ b/test.txt\n@@ -1,3 +1,4 @@\n one\n-two\n+2\n+2.5\n three\n"
+        );
+        assert_eq!(std::fs::read_to_string(&test_file).unwrap(), "one\ntwo\nthree\n");
+    }
+
     #[tokio::test]
     async fn test_edit_file_not_found() {
         let tool = EditFileTool::new();
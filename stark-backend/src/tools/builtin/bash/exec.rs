@@ -1,18 +1,31 @@
+use super::exec_policy::ExecPolicy;
+use super::path_jail::{resolve_in_root, workspace_root};
 use crate::controllers::api_keys::ApiKeyId;
+use crate::tools::redaction::SecretRedactor;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::GatewayEvent;
 use async_trait::async_trait;
 use serde::{Deserialize, Deserializer};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
 use tokio::time::timeout;
 
+/// Longest partial line buffered before it is streamed anyway
+const MAX_STREAM_LINE: usize = 4096;
+
+/// How long to wait for output pipes to drain after the process exits or is killed
+const PIPE_DRAIN_GRACE: Duration = Duration::from_secs(2);
+
 /// Deserialize a u64 from either a number or a string
 fn deserialize_u64_lenient<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
 where
@@ -27,6 +40,136 @@ where
     }
 }
 
+/// Bounded output buffer: keeps the first and last halves of the limit and
+/// drops the middle, so huge outputs can't exhaust memory.
+struct OutputCapture {
+    head: Vec<u8>,
+    tail: VecDeque<u8>,
+    limit: usize,
+    total: usize,
+}
+
+impl OutputCapture {
+    fn new(limit: usize) -> Self {
+        OutputCapture {
+            head: Vec::new(),
+            tail: VecDeque::new(),
+            limit,
+            total: 0,
+        }
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        self.total += data.len();
+        let head_limit = self.limit / 2;
+        let tail_limit = self.limit - head_limit;
+
+        let head_take = head_limit.saturating_sub(self.head.len()).min(data.len());
+        self.head.extend_from_slice(&data[..head_take]);
+
+        self.tail.extend(&data[head_take..]);
+        while self.tail.len() > tail_limit {
+            self.tail.pop_front();
+        }
+    }
+
+    fn truncated_bytes(&self) -> usize {
+        self.total - self.head.len() - self.tail.len()
+    }
+
+    fn to_text(&self) -> String {
+        let head = String::from_utf8_lossy(&self.head);
+        let tail_bytes: Vec<u8> = self.tail.iter().copied().collect();
+        let tail = String::from_utf8_lossy(&tail_bytes);
+        match self.truncated_bytes() {
+            0 => format!("{}{}", head, tail),
+            dropped => format!(
+                "{}\n\n[... {} bytes truncated from the middle (max {} bytes captured) ...]\n\n{}",
+                head, dropped, self.limit, tail
+            ),
+        }
+    }
+}
+
+/// Truncate text to `max` bytes by removing the middle, respecting char boundaries
+fn truncate_middle(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut head_end = max / 2;
+    while !text.is_char_boundary(head_end) {
+        head_end -= 1;
+    }
+    let mut tail_start = text.len() - (max - max / 2);
+    while !text.is_char_boundary(tail_start) {
+        tail_start += 1;
+    }
+    format!(
+        "{}\n\n[... {} bytes truncated from the middle ...]\n\n{}",
+        &text[..head_end],
+        tail_start - head_end,
+        &text[tail_start..]
+    )
+}
+
+/// Read a child pipe into a bounded capture, streaming complete lines as exec.output events
+async fn pump_output<R: AsyncRead + Unpin>(
+    mut pipe: R,
+    stream: &'static str,
+    capture: Arc<Mutex<OutputCapture>>,
+    broadcast: Option<(Arc<EventBroadcaster>, i64)>,
+    redactor: SecretRedactor,
+) {
+    let mut buf = [0u8; 8192];
+    let mut pending: Vec<u8> = Vec::new();
+
+    let emit = |line: &[u8]| {
+        if let Some((ref broadcaster, channel_id)) = broadcast {
+            let line = redactor.redact(&String::from_utf8_lossy(line));
+            broadcaster.broadcast(GatewayEvent::exec_output(channel_id, line.trim_end_matches('\r'), stream));
+        }
+    };
+
+    loop {
+        let n = match pipe.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        if let Ok(mut cap) = capture.lock() {
+            cap.push(&buf[..n]);
+        }
+        if broadcast.is_none() {
+            continue;
+        }
+
+        pending.extend_from_slice(&buf[..n]);
+        while let Some(pos) = pending.iter().position(|b| *b == b'\n') {
+            emit(&pending[..pos]);
+            pending.drain(..=pos);
+        }
+        if pending.len() > MAX_STREAM_LINE {
+            emit(&pending);
+            pending.clear();
+        }
+    }
+
+    if !pending.is_empty() {
+        emit(&pending);
+    }
+}
+
+/// Kill the child's whole process group (the shell and everything it spawned)
+fn kill_process_tree(child: &mut tokio::process::Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // The child was spawned as its own process group leader, so pgid == pid
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    let _ = child.start_kill();
+}
+
 /// Command execution tool with configurable security
 pub struct ExecTool {
     definition: ToolDefinition,
@@ -34,6 +177,8 @@ pub struct ExecTool {
     max_timeout: u64,
     /// Security mode: "full" (shell allowed), "restricted" (no shell), "sandbox" (future)
     security_mode: String,
+    /// Maximum bytes of stdout/stderr captured per command (middle truncated beyond this)
+    max_output: usize,
 }
 
 impl ExecTool {
@@ -57,7 +202,7 @@ impl ExecTool {
             "workdir".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Working directory for command execution (defaults to workspace)".to_string(),
+                description: "Working directory for command execution, inside the workspace (defaults to workspace root)".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -68,7 +213,7 @@ impl ExecTool {
             PropertySchema {
                 schema_type: "integer".to_string(),
                 description: format!(
-                    "Timeout in seconds (default: 60, max: {})",
+                    "Timeout in seconds (default: 60, max: {}). On expiry the command and its child processes are killed.",
                     max_timeout
                 ),
                 default: Some(json!(60)),
@@ -110,6 +255,7 @@ impl ExecTool {
             },
             max_timeout,
             security_mode,
+            max_output: crate::config::exec_max_output_bytes(),
         }
     }
 
@@ -259,32 +405,29 @@ impl ExecTool {
         None
     }
 
-    /// Execute a command in background mode using ProcessManager
-    async fn execute_background(&self, params: &ExecParams, context: &ToolContext) -> ToolResult {
-        // Determine working directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        let working_dir = if let Some(ref wd) = params.workdir {
-            let wd_path = PathBuf::from(wd);
-            if wd_path.is_absolute() {
-                wd_path
-            } else {
-                workspace.join(wd_path)
-            }
-        } else {
-            workspace
-        };
+    /// Resolve and create the working directory, confined to the workspace
+    fn resolve_working_dir(params: &ExecParams, context: &ToolContext) -> Result<PathBuf, String> {
+        let workspace = workspace_root(context);
+        if !workspace.exists() {
+            std::fs::create_dir_all(&workspace)
+                .map_err(|e| format!("Cannot create workspace directory: {}", e))?;
+        }
 
-        // Ensure working directory exists
+        let working_dir = resolve_in_root(&workspace, params.workdir.as_deref().unwrap_or("."))?;
         if !working_dir.exists() {
-            if let Err(e) = std::fs::create_dir_all(&working_dir) {
-                return ToolResult::error(format!("Cannot create working directory: {}", e));
-            }
+            std::fs::create_dir_all(&working_dir)
+                .map_err(|e| format!("Cannot create working directory: {}", e))?;
         }
+        Ok(working_dir)
+    }
+
+    /// Execute a command in background mode using ProcessManager
+    async fn execute_background(&self, params: &ExecParams, context: &ToolContext) -> ToolResult {
+        // Determine working directory (must stay inside the workspace)
+        let working_dir = match Self::resolve_working_dir(params, context) {
+            Ok(dir) => dir,
+            Err(e) => return ToolResult::error(e),
+        };
 
         // Get channel ID from context (default to 0 if not set)
         let channel_id = context.channel_id.unwrap_or(0);
@@ -415,6 +558,11 @@ impl Tool for ExecTool {
         self.definition.clone()
     }
 
+    /// Leave room for the command's own timeout to fire and its output to drain
+    fn timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(self.max_timeout + 30))
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: ExecParams = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -426,6 +574,11 @@ impl Tool for ExecTool {
             return ToolResult::error(format!("Command blocked: {}", reason));
         }
 
+        // Enforce the bot_settings allowlist/denylist (rogue mode bypasses the allowlist)
+        if let Err(reason) = ExecPolicy::from_context(context).check(&params.command) {
+            return ToolResult::error(format!("Command blocked: {}", reason));
+        }
+
         let background = params.background.unwrap_or(false);
 
         // Detect server commands and warn if not using background mode
@@ -448,31 +601,12 @@ impl Tool for ExecTool {
 
         let timeout_secs = params.timeout.unwrap_or(60).min(self.max_timeout);
 
-        // Determine working directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        let working_dir = if let Some(ref wd) = params.workdir {
-            let wd_path = PathBuf::from(wd);
-            if wd_path.is_absolute() {
-                wd_path
-            } else {
-                workspace.join(wd_path)
-            }
-        } else {
-            workspace.clone()
+        // Determine working directory (must stay inside the workspace)
+        let working_dir = match Self::resolve_working_dir(&params, context) {
+            Ok(dir) => dir,
+            Err(e) => return ToolResult::error(e),
         };
 
-        // Ensure working directory exists
-        if !working_dir.exists() {
-            if let Err(e) = std::fs::create_dir_all(&working_dir) {
-                return ToolResult::error(format!("Cannot create working directory: {}", e));
-            }
-        }
-
         // Build the command using shell
         let shell = if cfg!(target_os = "windows") {
             "cmd"
@@ -490,8 +624,14 @@ impl Tool for ExecTool {
         cmd.arg(shell_arg)
             .arg(&params.command)
             .current_dir(&working_dir)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        // Run in a new process group so a timeout can kill everything the shell spawned
+        #[cfg(unix)]
+        cmd.process_group(0);
 
         // Set environment variables from context (API keys)
         // Track which keys are available for diagnostic output
@@ -540,24 +680,103 @@ impl Tool for ExecTool {
         log::info!("Executing command: {} (timeout: {}s, workdir: {:?})",
             params.command, timeout_secs, working_dir);
 
-        let output = match timeout(Duration::from_secs(timeout_secs), cmd.output()).await {
-            Ok(Ok(output)) => output,
-            Ok(Err(e)) => return ToolResult::error(format!("Failed to execute command: {}", e)),
-            Err(_) => {
-                return ToolResult::error(format!(
-                    "Command timed out after {} seconds. Consider increasing timeout or running in background.",
-                    timeout_secs
-                ))
+        let mut child = match cmd.spawn() {
+            Ok(c) => c,
+            Err(e) => return ToolResult::error(format!("Failed to execute command: {}", e)),
+        };
+
+        // Stream output to the channel while capturing a bounded copy
+        let broadcast = context.broadcaster.clone().zip(context.channel_id);
+        let stdout_capture = Arc::new(Mutex::new(OutputCapture::new(self.max_output)));
+        let stderr_capture = Arc::new(Mutex::new(OutputCapture::new(self.max_output)));
+        let mut pumps = Vec::new();
+        if let Some(pipe) = child.stdout.take() {
+            pumps.push(tokio::spawn(pump_output(pipe, "stdout", stdout_capture.clone(), broadcast.clone(), context.redactor.clone())));
+        }
+        if let Some(pipe) = child.stderr.take() {
+            pumps.push(tokio::spawn(pump_output(pipe, "stderr", stderr_capture.clone(), broadcast.clone(), context.redactor.clone())));
+        }
+
+        // Wait for exit or the timeout, unless the user stops the execution first
+        let cancel = context.cancellation_token.clone().unwrap_or_default();
+        let waited = tokio::select! {
+            result = timeout(Duration::from_secs(timeout_secs), child.wait()) => Some(result),
+            _ = cancel.cancelled() => None,
+        };
+        let status = match waited {
+            Some(Ok(Ok(status))) => Some(status),
+            Some(Ok(Err(e))) => {
+                kill_process_tree(&mut child);
+                return ToolResult::error(format!("Failed to execute command: {}", e));
+            }
+            Some(Err(_)) => {
+                kill_process_tree(&mut child);
+                let _ = child.wait().await;
+                None
+            }
+            None => {
+                kill_process_tree(&mut child);
+                let _ = child.wait().await;
+                for pump in pumps {
+                    pump.abort();
+                }
+                return ToolResult::error("Command cancelled: execution was stopped by the user").with_metadata(json!({
+                    "command": params.command,
+                    "exit_code": Value::Null,
+                    "cancelled": true,
+                    "duration_ms": start.elapsed().as_millis() as i64,
+                    "working_dir": working_dir.to_string_lossy()
+                }));
             }
         };
+
+        // Let the pipes drain; a detached grandchild holding them open must not hang us
+        for pump in pumps {
+            if timeout(PIPE_DRAIN_GRACE, pump).await.is_err() {
+                log::warn!("[exec] Output pipe still open after process exit, not waiting further");
+            }
+        }
         let duration_ms = start.elapsed().as_millis() as i64;
 
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let exit_code = output.status.code().unwrap_or(-1);
+        let stdout = stdout_capture.lock().map(|c| c.to_text()).unwrap_or_default();
+        let stderr = stderr_capture.lock().map(|c| c.to_text()).unwrap_or_default();
+
+        let status = match status {
+            Some(s) => s,
+            None => {
+                let mut msg = format!(
+                    "Command timed out after {} seconds and was killed (including child processes). \
+                    Consider increasing timeout or running in background.",
+                    timeout_secs
+                );
+                let partial = [stdout.as_str(), stderr.as_str()]
+                    .iter()
+                    .filter(|s| !s.is_empty())
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join("\n--- stderr ---\n");
+                if !partial.is_empty() {
+                    msg.push_str("\n\n--- partial output ---\n");
+                    msg.push_str(&truncate_middle(&partial, self.max_output));
+                }
+                return ToolResult::error(msg).with_metadata(json!({
+                    "command": params.command,
+                    "exit_code": Value::Null,
+                    "timed_out": true,
+                    "duration_ms": duration_ms,
+                    "working_dir": working_dir.to_string_lossy()
+                }));
+            }
+        };
+
+        let exit_code = status.code().unwrap_or(-1);
+        #[cfg(unix)]
+        let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+        #[cfg(not(unix))]
+        let signal: Option<i32> = None;
 
         // Build response
-        let success = output.status.success();
+        let success = status.success();
         let mut result_text = String::new();
 
         if !stdout.is_empty() {
@@ -615,13 +834,15 @@ impl Tool for ExecTool {
         }
 
         // Truncate if too long (keep small to avoid context bloat for smaller models)
-        const MAX_OUTPUT: usize = 15000;
-        if result_text.len() > MAX_OUTPUT {
-            result_text = format!(
-                "{}\n\n[Output truncated at {} characters]",
-                &result_text[..MAX_OUTPUT],
-                MAX_OUTPUT
-            );
+        result_text = truncate_middle(&result_text, self.max_output);
+
+        // Make failure unambiguous even when the command printed something
+        if !success && !result_text.starts_with("Command failed") {
+            let reason = match signal {
+                Some(sig) => format!("Command terminated by signal {}", sig),
+                None => format!("Command failed with exit code: {}", exit_code),
+            };
+            result_text = format!("{}\n\n{}", reason, result_text);
         }
 
         log::info!("Command completed: exit_code={}, duration={}ms, output_len={}",
@@ -636,6 +857,8 @@ impl Tool for ExecTool {
         result.with_metadata(json!({
             "command": params.command,
             "exit_code": exit_code,
+            "signal": signal,
+            "timed_out": false,
             "duration_ms": duration_ms,
             "working_dir": working_dir.to_string_lossy()
         }))
@@ -645,6 +868,7 @@ impl Tool for ExecTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
     #[test]
     fn test_dangerous_command_detection() {
@@ -675,7 +899,8 @@ mod tests {
     #[tokio::test]
     async fn test_exec_simple_command() {
         let tool = ExecTool::new();
-        let context = ToolContext::new();
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
 
         let result = tool
             .execute(
@@ -693,7 +918,8 @@ mod tests {
     #[tokio::test]
     async fn test_exec_with_pipes() {
         let tool = ExecTool::new();
-        let context = ToolContext::new();
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
 
         let result = tool
             .execute(
@@ -707,4 +933,164 @@ mod tests {
         assert!(result.success);
         assert!(result.content.contains("HELLO WORLD"));
     }
+
+    #[tokio::test]
+    async fn test_exec_cancelled_kills_command() {
+        let tool = ExecTool::new();
+        let temp_dir = TempDir::new().unwrap();
+        let token = tokio_util::sync::CancellationToken::new();
+        let context = ToolContext::new()
+            .with_workspace(temp_dir.path().to_string_lossy().to_string())
+            .with_cancellation_token(token.clone());
+
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            token.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let result = tool.execute(json!({ "command": "sleep 30" }), &context).await;
+        canceller.await.unwrap();
+
+        assert!(!result.success);
+        assert!(result.content.contains("cancelled"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_output_capture_truncates_middle() {
+        let mut cap = OutputCapture::new(10);
+        cap.push(b"abc");
+        assert_eq!(cap.to_text(), "abc");
+
+        cap.push(b"defghijklmnopqrstuvwxyz");
+        assert_eq!(cap.truncated_bytes(), 16);
+        let text = cap.to_text();
+        assert!(text.starts_with("abcde"));
+        assert!(text.ends_with("vwxyz"));
+        assert!(text.contains("16 bytes truncated"));
+    }
+
+    #[test]
+    fn test_truncate_middle_char_boundaries() {
+        assert_eq!(truncate_middle("short", 100), "short");
+        let text = "é".repeat(100);
+        let truncated = truncate_middle(&text, 51);
+        assert!(truncated.contains("truncated from the middle"));
+        assert!(truncated.starts_with('é') && truncated.ends_with('é'));
+    }
+
+    #[tokio::test]
+    async fn test_exec_reports_exit_code() {
+        let tool = ExecTool::new();
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
+
+        let result = tool
+            .execute(json!({ "command": "echo oops; exit 3" }), &context)
+            .await;
+
+        assert!(!result.success);
+        assert!(result.content.starts_with("Command failed with exit code: 3"));
+        assert!(result.content.contains("oops"));
+        assert_eq!(result.metadata.unwrap()["exit_code"], json!(3));
+    }
+
+    #[tokio::test]
+    async fn test_exec_output_limit() {
+        let mut tool = ExecTool::new();
+        tool.max_output = 100;
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
+
+        let result = tool
+            .execute(json!({ "command": "seq 1 10000" }), &context)
+            .await;
+
+        assert!(result.success);
+        assert!(result.content.starts_with("1\n2\n"));
+        assert!(result.content.trim_end().ends_with("10000"));
+        assert!(result.content.contains("truncated from the middle"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_exec_timeout_kills_process_group() {
+        let tool = ExecTool::new();
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
+        let start = std::time::Instant::now();
+
+        // The backgrounded sleep keeps stdout open; it must be killed with the shell
+        let result = tool
+            .execute(json!({ "command": "sleep 30 & sleep 30", "timeout": 1 }), &context)
+            .await;
+
+        assert!(!result.success);
+        assert!(result.content.contains("timed out"));
+        assert_eq!(result.metadata.unwrap()["timed_out"], json!(true));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_exec_workdir_confined_to_workspace() {
+        let tool = ExecTool::new();
+        let temp_dir = TempDir::new().unwrap();
+        let context = ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
+
+        for workdir in ["../..", "/etc"] {
+            let result = tool
+                .execute(json!({ "command": "pwd", "workdir": workdir }), &context)
+                .await;
+            assert!(!result.success);
+            assert!(result.error.unwrap().contains("outside the workspace"));
+        }
+
+        let result = tool
+            .execute(json!({ "command": "pwd", "workdir": "sub/dir" }), &context)
+            .await;
+        assert!(result.success);
+        assert!(result.content.trim().ends_with("sub/dir"));
+    }
+
+    #[tokio::test]
+    async fn test_exec_output_redacts_private_key() {
+        let key = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+        let temp_dir = TempDir::new().unwrap();
+        let broadcaster = Arc::new(EventBroadcaster::new());
+        let (_client_id, mut events) = broadcaster.subscribe();
+        let context = ToolContext::new()
+            .with_workspace(temp_dir.path().to_string_lossy().to_string())
+            .with_channel(1, "web".to_string())
+            .with_broadcaster(broadcaster.clone())
+            .with_redactor(SecretRedactor::new([key]));
+
+        let mut registry = crate::tools::ToolRegistry::new();
+        registry.register(Arc::new(ExecTool::new()));
+        let result = registry
+            .execute(
+                "exec",
+                json!({
+                    "command": "echo \"key=$BURNER_WALLET_BOT_PRIVATE_KEY\"",
+                    "env": { "BURNER_WALLET_BOT_PRIVATE_KEY": key }
+                }),
+                &context,
+                None,
+            )
+            .await;
+
+        assert!(result.success);
+        assert!(result.content.contains("key=***REDACTED***"), "{}", result.content);
+        assert!(!result.content.contains(&key[2..]));
+
+        // Streamed lines are redacted too
+        let mut streamed = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if event.event == "exec.output" {
+                streamed.push(event.data.to_string());
+            }
+        }
+        assert!(!streamed.is_empty());
+        assert!(streamed.iter().all(|line| !line.contains(&key[2..])));
+    }
 }
@@ -1,3 +1,4 @@
+use super::path_jail::{resolve_workspace_path, workspace_root};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -24,13 +25,14 @@ impl GitTool {
             "operation".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Git operation: status, diff, log, add, commit, branch, checkout, stash, reset, push, pull, fetch, clone, remote".to_string(),
+                description: "Git operation: status, diff, log, blame, add, commit, branch, checkout, stash, reset, push, pull, fetch, clone, remote".to_string(),
                 default: None,
                 items: None,
                 enum_values: Some(vec![
                     "status".to_string(),
                     "diff".to_string(),
                     "log".to_string(),
+                    "blame".to_string(),
                     "add".to_string(),
                     "commit".to_string(),
                     "branch".to_string(),
@@ -140,6 +142,39 @@ impl GitTool {
             },
         );
 
+        properties.insert(
+            "path".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "File to blame (required for blame), or file/directory to limit log history to".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "start_line".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "For blame: first line to include (default: 1)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "end_line".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "For blame: last line to include (default: end of file)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "staged".to_string(),
             PropertySchema {
@@ -176,7 +211,7 @@ impl GitTool {
         GitTool {
             definition: ToolDefinition {
                 name: "git".to_string(),
-                description: "Execute git operations safely. Supports: status, diff, log, add, commit, branch, checkout, stash, reset, push, pull, fetch, clone, remote. Protected branches (main, master) have safety restrictions - force push is forbidden. For safer commits with secret detection, use the 'committer' tool instead.".to_string(),
+                description: "Execute git operations safely. Supports: status, diff, log, blame, add, commit, branch, checkout, stash, reset, push, pull, fetch, clone, remote. log lists commits (optionally for a path) as hash, date, author and subject; blame shows the commit, date and author that last changed each line of a file. Protected branches (main, master) have safety restrictions - force push is forbidden. For safer commits with secret detection, use the 'committer' tool instead.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -206,7 +241,8 @@ impl GitTool {
         cmd.args(args)
             .current_dir(workspace)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
         // Set git author from context if available
         let bot_name = context.get_bot_name();
@@ -225,6 +261,12 @@ impl GitTool {
         let stderr = String::from_utf8_lossy(&output.stderr);
 
         if !output.status.success() {
+            if stderr.contains("not a git repository") {
+                return Err(format!(
+                    "The workspace ({}) is not a git repository. Clone a repository or run 'git init' first.",
+                    workspace.display()
+                ));
+            }
             return Err(format!(
                 "Git command failed:\n{}{}",
                 stdout,
@@ -254,6 +296,9 @@ struct GitParams {
     branch: Option<String>,
     base_branch: Option<String>,
     count: Option<usize>,
+    path: Option<String>,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
     staged: Option<bool>,
     create: Option<bool>,
     remote: Option<String>,
@@ -262,6 +307,172 @@ struct GitParams {
     set_upstream: Option<bool>,
 }
 
+/// Log format: short hash, date, author, subject, refs (unit-separator delimited)
+const LOG_FORMAT: &str = "--format=%h%x1f%ad%x1f%an%x1f%s%x1f%D";
+
+#[derive(Debug, Clone, PartialEq)]
+struct LogEntry {
+    hash: String,
+    date: String,
+    author: String,
+    subject: String,
+    refs: String,
+}
+
+impl LogEntry {
+    /// `hash<TAB>date<TAB>author<TAB>subject [refs]`
+    fn to_line(&self) -> String {
+        let mut line = format!("{}\t{}\t{}\t{}", self.hash, self.date, self.author, self.subject);
+        if !self.refs.is_empty() {
+            line.push_str(&format!(" ({})", self.refs));
+        }
+        line
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "hash": self.hash,
+            "date": self.date,
+            "author": self.author,
+            "subject": self.subject,
+            "refs": self.refs,
+        })
+    }
+}
+
+fn parse_log(output: &str) -> Vec<LogEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x1f');
+            Some(LogEntry {
+                hash: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+                refs: fields.next().unwrap_or("").to_string(),
+            })
+        })
+        .collect()
+}
+
+/// One line of `git blame --line-porcelain` output
+#[derive(Debug, Clone, PartialEq)]
+struct BlameLine {
+    commit: String,
+    author: String,
+    date: String,
+    summary: String,
+    line_number: usize,
+    text: String,
+}
+
+impl BlameLine {
+    fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(8)]
+    }
+}
+
+fn parse_blame(output: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+
+    for line in output.lines() {
+        if let Some(text) = line.strip_prefix('\t') {
+            // Content line ends each entry
+            if let Some(mut entry) = current.take() {
+                entry.text = text.to_string();
+                lines.push(entry);
+            }
+        } else if let Some(ref mut entry) = current {
+            if let Some(author) = line.strip_prefix("author ") {
+                entry.author = author.to_string();
+            } else if let Some(time) = line.strip_prefix("author-time ") {
+                entry.date = time
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+            } else if let Some(summary) = line.strip_prefix("summary ") {
+                entry.summary = summary.to_string();
+            }
+        } else {
+            // Header: <commit> <original line> <final line> [<group size>]
+            let mut parts = line.split_whitespace();
+            if let (Some(commit), Some(_), Some(final_line)) = (parts.next(), parts.next(), parts.next()) {
+                current = Some(BlameLine {
+                    commit: commit.to_string(),
+                    author: String::new(),
+                    date: String::new(),
+                    summary: String::new(),
+                    line_number: final_line.parse().unwrap_or(0),
+                    text: String::new(),
+                });
+            }
+        }
+    }
+
+    lines
+}
+
+/// `commit date author line| text` per line, then the subject of each commit
+fn format_blame(lines: &[BlameLine]) -> String {
+    let author_width = lines.iter().map(|l| l.author.chars().count()).max().unwrap_or(0);
+    let number_width = lines.iter().map(|l| l.line_number).max().unwrap_or(0).to_string().len();
+
+    let mut out: Vec<String> = lines
+        .iter()
+        .map(|l| {
+            format!(
+                "{} {} {:<aw$} {:>nw$}| {}",
+                l.short_commit(),
+                l.date,
+                l.author,
+                l.line_number,
+                l.text,
+                aw = author_width,
+                nw = number_width
+            )
+        })
+        .collect();
+
+    out.push(String::new());
+    out.push("Commits:".to_string());
+    let mut seen = std::collections::HashSet::new();
+    for l in lines {
+        if seen.insert(l.commit.as_str()) {
+            out.push(format!("{} {} {}: {}", l.short_commit(), l.date, l.author, l.summary));
+        }
+    }
+
+    out.join("\n")
+}
+
+/// Consecutive lines from the same commit, for metadata
+fn blame_ranges(lines: &[BlameLine]) -> Vec<Value> {
+    let mut ranges: Vec<(usize, usize, &BlameLine)> = Vec::new();
+    for l in lines {
+        match ranges.last_mut() {
+            Some((_, end, first)) if first.commit == l.commit && *end + 1 == l.line_number => *end = l.line_number,
+            _ => ranges.push((l.line_number, l.line_number, l)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(start, end, l)| {
+            json!({
+                "commit": l.commit,
+                "author": l.author,
+                "date": l.date,
+                "summary": l.summary,
+                "start_line": start,
+                "end_line": end,
+            })
+        })
+        .collect()
+}
+
 #[async_trait]
 impl Tool for GitTool {
     fn definition(&self) -> ToolDefinition {
@@ -303,11 +514,7 @@ impl Tool for GitTool {
         }
 
         // Get workspace directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        let workspace = workspace_root(context);
 
         match params.operation.as_str() {
             "status" => {
@@ -376,17 +583,96 @@ impl Tool for GitTool {
             }
 
             "log" => {
-                let count = params.count.unwrap_or(10);
-                let count_str = format!("-{}", count);
-                match self
-                    .run_git(
-                        &["log", &count_str, "--oneline", "--decorate"],
-                        &workspace,
-                        context,
-                    )
-                    .await
-                {
-                    Ok(output) => ToolResult::success(output),
+                let count_str = format!("-{}", params.count.unwrap_or(10));
+                let mut args = vec!["log", count_str.as_str(), "--date=short", LOG_FORMAT];
+
+                let path = match params.path.as_deref().map(|p| resolve_workspace_path(context, p)) {
+                    Some(Ok(p)) => Some(p),
+                    Some(Err(e)) => return ToolResult::error(e),
+                    None => None,
+                };
+                let path_str = path.as_ref().map(|p| p.to_string_lossy().to_string());
+                if let Some(ref p) = path_str {
+                    // Follow renames when looking at a single file
+                    if path.as_ref().is_some_and(|p| p.is_file()) {
+                        args.push("--follow");
+                    }
+                    args.push("--");
+                    args.push(p);
+                }
+
+                match self.run_git(&args, &workspace, context).await {
+                    Ok(output) => {
+                        let commits = parse_log(&output);
+                        if commits.is_empty() {
+                            return ToolResult::success("No commits found.");
+                        }
+                        let lines: Vec<String> = commits.iter().map(LogEntry::to_line).collect();
+                        ToolResult::success(lines.join("\n")).with_metadata(json!({
+                            "commits": commits.iter().map(LogEntry::to_json).collect::<Vec<_>>(),
+                        }))
+                    }
+                    Err(e) => ToolResult::error(e),
+                }
+            }
+
+            "blame" => {
+                let requested = match params.path.as_deref().or(params.files.as_ref().and_then(|f| f.first().map(|s| s.as_str()))) {
+                    Some(p) => p.to_string(),
+                    None => return ToolResult::error("'path' is required for blame"),
+                };
+                let path = match resolve_workspace_path(context, &requested) {
+                    Ok(p) => p,
+                    Err(e) => return ToolResult::error(e),
+                };
+                if !path.is_file() {
+                    return ToolResult::error(format!("File not found: {}", requested));
+                }
+
+                let range = match (params.start_line, params.end_line) {
+                    (None, None) => None,
+                    (Some(start), Some(end)) if end < start => {
+                        return ToolResult::error("end_line must not be before start_line");
+                    }
+                    (start, Some(end)) => Some(format!("{},{}", start.unwrap_or(1).max(1), end)),
+                    (Some(start), None) => Some(format!("{},", start.max(1))),
+                };
+
+                let path_str = path.to_string_lossy().to_string();
+                let mut args = vec!["blame", "--line-porcelain"];
+                if let Some(ref r) = range {
+                    args.push("-L");
+                    args.push(r);
+                }
+                args.push("--");
+                args.push(&path_str);
+
+                match self.run_git(&args, &workspace, context).await {
+                    Ok(output) => {
+                        let lines = parse_blame(&output);
+                        if lines.is_empty() {
+                            return ToolResult::success(format!("No lines to blame in {}", requested));
+                        }
+                        let output = format_blame(&lines);
+                        let max_output = 30000;
+                        let content = if output.len() > max_output {
+                            let mut cut = max_output;
+                            while !output.is_char_boundary(cut) {
+                                cut -= 1;
+                            }
+                            format!(
+                                "{}\n\n[Output truncated. {} more characters not shown. Use start_line/end_line to narrow.]",
+                                &output[..cut],
+                                output.len() - cut
+                            )
+                        } else {
+                            output
+                        };
+                        ToolResult::success(content).with_metadata(json!({
+                            "path": requested,
+                            "ranges": blame_ranges(&lines),
+                        }))
+                    }
                     Err(e) => ToolResult::error(e),
                 }
             }
@@ -729,7 +1015,7 @@ impl Tool for GitTool {
             }
 
             _ => ToolResult::error(format!(
-                "Unknown operation: {}. Supported: status, diff, log, add, commit, branch, checkout, stash, reset, push, pull, fetch, clone, remote",
+                "Unknown operation: {}. Supported: status, diff, log, blame, add, commit, branch, checkout, stash, reset, push, pull, fetch, clone, remote",
                 params.operation
             )),
         }
@@ -781,6 +1067,106 @@ mod tests {
         assert!(result.error.unwrap().contains("not allowed"));
     }
 
+    /// Run git in `dir` with a fixed identity
+    async fn git_in(dir: &std::path::Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Alice")
+            .env("GIT_AUTHOR_EMAIL", "alice@example.com")
+            .env("GIT_COMMITTER_NAME", "Alice")
+            .env("GIT_COMMITTER_EMAIL", "alice@example.com")
+            .output()
+            .await
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[tokio::test]
+    async fn test_git_log_and_blame() {
+        let tool = GitTool::new();
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        git_in(root, &["init", "-q"]).await;
+        std::fs::write(root.join("a.txt"), "one\ntwo\n").unwrap();
+        git_in(root, &["add", "a.txt"]).await;
+        git_in(root, &["commit", "-q", "-m", "Add a.txt"]).await;
+        std::fs::write(root.join("a.txt"), "one\nTWO\nthree\n").unwrap();
+        std::fs::write(root.join("b.txt"), "b\n").unwrap();
+        git_in(root, &["add", "a.txt", "b.txt"]).await;
+        git_in(root, &["commit", "-q", "-m", "Update a.txt, add b.txt"]).await;
+
+        let context = ToolContext::new().with_workspace(root.to_string_lossy().to_string());
+
+        let result = tool
+            .execute(json!({ "operation": "log", "path": "a.txt" }), &context)
+            .await;
+        assert!(result.success, "{:?}", result.error);
+        let commits = &result.metadata.unwrap()["commits"];
+        assert_eq!(commits.as_array().unwrap().len(), 2);
+        assert_eq!(commits[0]["subject"], "Update a.txt, add b.txt");
+        assert_eq!(commits[1]["author"], "Alice");
+
+        let result = tool
+            .execute(json!({ "operation": "blame", "path": "a.txt", "start_line": 2 }), &context)
+            .await;
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.content.contains("Alice 2| TWO"), "{}", result.content);
+        let ranges = &result.metadata.unwrap()["ranges"];
+        assert_eq!(ranges.as_array().unwrap().len(), 1);
+        assert_eq!(ranges[0]["start_line"], 2);
+        assert_eq!(ranges[0]["end_line"], 3);
+        assert_eq!(ranges[0]["summary"], "Update a.txt, add b.txt");
+
+        let result = tool
+            .execute(json!({ "operation": "blame", "path": "../outside.txt" }), &context)
+            .await;
+        assert!(result.error.unwrap().contains("outside the workspace"));
+    }
+
+    #[tokio::test]
+    async fn test_git_not_a_repository() {
+        let tool = GitTool::new();
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "one\n").unwrap();
+        let context =
+            ToolContext::new().with_workspace(temp_dir.path().to_string_lossy().to_string());
+
+        for params in [json!({ "operation": "log" }), json!({ "operation": "blame", "path": "a.txt" })] {
+            let result = tool.execute(params, &context).await;
+            assert!(!result.success);
+            assert!(result.error.unwrap().contains("is not a git repository"));
+        }
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let output = "\
+4f1c2a9e0b7d6c5a4f3e2d1c0b9a8f7e6d5c4b3a 1 1 2
+author Alice
+author-mail <alice@example.com>
+author-time 1767225600
+author-tz +0000
+summary Initial commit
+filename a.txt
+\tfn main() {
+4f1c2a9e0b7d6c5a4f3e2d1c0b9a8f7e6d5c4b3a 2 2
+author Alice
+author-time 1767225600
+summary Initial commit
+filename a.txt
+\t}
+";
+        let lines = parse_blame(output);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].short_commit(), "4f1c2a9e");
+        assert_eq!(lines[0].date, "2026-01-01");
+        assert_eq!(lines[1].line_number, 2);
+        assert_eq!(lines[1].text, "}");
+        assert_eq!(blame_ranges(&lines).len(), 1);
+    }
+
     #[test]
     fn test_protected_branch() {
         assert!(GitTool::is_protected_branch("main"));
@@ -1,3 +1,4 @@
+use super::path_jail::{canonical_workspace_root, resolve_workspace_path};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -8,7 +9,7 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::time::SystemTime;
 
 /// Glob tool for file pattern matching
@@ -127,51 +128,26 @@ impl Tool for GlobTool {
         self.definition.clone()
     }
 
+    fn is_mutating(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: GlobParams = match serde_json::from_value(params) {
             Ok(p) => p,
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
 
-        // Get workspace directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        // Resolve base path
-        let base_path = if let Some(ref path) = params.path {
-            let p = Path::new(path);
-            if p.is_absolute() {
-                p.to_path_buf()
-            } else {
-                workspace.join(p)
-            }
-        } else {
-            workspace.clone()
-        };
-
-        // Security check: ensure path is within workspace
-        let canonical_workspace = match workspace.canonicalize() {
+        // Resolve base path inside the workspace
+        let canonical_workspace = match canonical_workspace_root(context) {
             Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve workspace directory: {}", e))
-            }
+            Err(e) => return ToolResult::error(e),
         };
-
-        let canonical_base = match base_path.canonicalize() {
+        let canonical_base = match resolve_workspace_path(context, params.path.as_deref().unwrap_or(".")) {
             Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Cannot resolve base path: {}", e)),
+            Err(e) => return ToolResult::error(e),
         };
 
-        if !canonical_base.starts_with(&canonical_workspace) {
-            return ToolResult::error(format!(
-                "Access denied: path '{}' is outside the workspace directory",
-                params.path.as_deref().unwrap_or(".")
-            ));
-        }
-
         // Build full glob pattern
         let full_pattern = canonical_base.join(&params.pattern);
         let pattern_str = full_pattern.to_string_lossy();
@@ -183,11 +159,10 @@ impl Tool for GlobTool {
         match glob_match(&pattern_str) {
             Ok(paths) => {
                 for entry in paths.filter_map(Result::ok) {
-                    // Security: verify each path is within workspace
-                    if let Ok(canonical) = entry.canonicalize() {
-                        if !canonical.starts_with(&canonical_workspace) {
-                            continue;
-                        }
+                    // Security: verify each path is within workspace (patterns can contain ..)
+                    match entry.canonicalize() {
+                        Ok(canonical) if canonical.starts_with(&canonical_workspace) => {}
+                        _ => continue,
                     }
 
                     // Skip hidden files unless requested
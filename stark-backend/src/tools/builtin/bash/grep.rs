@@ -1,23 +1,114 @@
+use super::path_jail::resolve_workspace_path;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use async_trait::async_trait;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use serde::Deserialize;
 use serde_json::{json, Value};
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
 use std::process::Stdio;
 use tokio::process::Command;
 use walkdir::WalkDir;
 
+/// Default cap on the number of matching lines returned
+const DEFAULT_MAX_MATCHES: usize = 100;
+
+/// Output size cap (keep small to avoid context bloat)
+const MAX_OUTPUT: usize = 12000;
+
 /// Grep tool for content search within files
 /// Uses ripgrep (rg) if available, falls back to Rust-native search
 pub struct GrepTool {
     definition: ToolDefinition,
 }
 
+/// A matching line, or a context line around one
+#[derive(Debug, Clone, PartialEq)]
+struct GrepLine {
+    path: String,
+    line_number: usize,
+    text: String,
+    is_match: bool,
+}
+
+#[derive(Debug, Default)]
+struct SearchResults {
+    lines: Vec<GrepLine>,
+    /// Matching files in search order, with their match counts
+    file_counts: Vec<(String, usize)>,
+    total_matches: usize,
+    /// Stopped at max_matches
+    truncated: bool,
+}
+
+impl SearchResults {
+    /// Record a line, enforcing the global match cap. Context lines are only kept
+    /// while they can still belong to an accepted match.
+    fn push(&mut self, line: GrepLine, max_matches: usize, context: usize) {
+        if line.is_match {
+            if self.total_matches >= max_matches {
+                self.truncated = true;
+                return;
+            }
+            self.total_matches += 1;
+            match self.file_counts.last_mut() {
+                Some((path, count)) if *path == line.path => *count += 1,
+                _ => self.file_counts.push((line.path.clone(), 1)),
+            }
+        } else if self.truncated || self.total_matches >= max_matches {
+            // Only trailing context of the last accepted match
+            let last_match = self.lines.iter().rev().find(|l| l.is_match);
+            match last_match {
+                Some(m) if m.path == line.path && line.line_number <= m.line_number + context => {}
+                _ => return,
+            }
+        }
+        self.lines.push(line);
+    }
+}
+
+/// Search settings shared by both backends
+struct SearchOptions {
+    pattern: String,
+    fixed_strings: bool,
+    case_insensitive: bool,
+    context: usize,
+    max_matches: usize,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl SearchOptions {
+    fn from_params(params: &GrepParams) -> Self {
+        let mut include: Vec<String> = params.glob.iter().cloned().collect();
+        include.extend(params.include.iter().flatten().cloned());
+        SearchOptions {
+            pattern: params.pattern.clone(),
+            fixed_strings: !params.regex.unwrap_or(true),
+            case_insensitive: params.case_insensitive.unwrap_or(false),
+            context: params.context_lines.unwrap_or(0),
+            max_matches: params.max_matches.unwrap_or(DEFAULT_MAX_MATCHES).max(1),
+            include,
+            exclude: params.exclude.clone().unwrap_or_default(),
+        }
+    }
+
+    fn build_regex(&self) -> Result<Regex, String> {
+        let pattern = if self.fixed_strings {
+            regex::escape(&self.pattern)
+        } else {
+            self.pattern.clone()
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(self.case_insensitive)
+            .build()
+            .map_err(|e| format!("Invalid regex pattern: {}", e))
+    }
+}
+
 impl GrepTool {
     pub fn new() -> Self {
         let mut properties = HashMap::new();
@@ -26,13 +117,24 @@ impl GrepTool {
             "pattern".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Regular expression pattern to search for".to_string(),
+                description: "Pattern to search for (a regular expression unless regex is false)".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
             },
         );
 
+        properties.insert(
+            "regex".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Treat pattern as a regular expression (default: true). Set false to match it as a literal string".to_string(),
+                default: Some(json!(true)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "path".to_string(),
             PropertySchema {
@@ -58,6 +160,40 @@ impl GrepTool {
             },
         );
 
+        properties.insert(
+            "include".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "Only search files matching any of these globs (e.g., ['*.rs', 'src/**/*.ts']). Patterns without '/' match the file name".to_string(),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Glob pattern".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "exclude".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "Skip files and directories matching any of these globs (e.g., ['target', '*.min.js'])".to_string(),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Glob pattern".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "output_mode".to_string(),
             PropertySchema {
@@ -74,10 +210,10 @@ impl GrepTool {
         );
 
         properties.insert(
-            "context".to_string(),
+            "context_lines".to_string(),
             PropertySchema {
                 schema_type: "integer".to_string(),
-                description: "Number of context lines before and after each match (default: 0)"
+                description: "Number of context lines before and after each match, like grep -C (default: 0)"
                     .to_string(),
                 default: Some(json!(0)),
                 items: None,
@@ -97,11 +233,11 @@ impl GrepTool {
         );
 
         properties.insert(
-            "max_results".to_string(),
+            "max_matches".to_string(),
             PropertySchema {
                 schema_type: "integer".to_string(),
-                description: "Maximum number of results to return (default: 100)".to_string(),
-                default: Some(json!(100)),
+                description: "Maximum number of matching lines to return across all files (default: 100)".to_string(),
+                default: Some(json!(DEFAULT_MAX_MATCHES)),
                 items: None,
                 enum_values: None,
             },
@@ -110,7 +246,7 @@ impl GrepTool {
         GrepTool {
             definition: ToolDefinition {
                 name: "grep".to_string(),
-                description: "Search for patterns in file contents. Supports regex patterns, file filtering with glob patterns, and various output modes.".to_string(),
+                description: "Search for patterns in file contents. Supports regex or literal patterns, case-insensitive search, context lines, include/exclude glob filters, a match cap, and various output modes. Matches are returned as path:line:text.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -133,51 +269,37 @@ impl GrepTool {
             .unwrap_or(false)
     }
 
-    /// Run search using ripgrep
+    /// Run search using ripgrep's JSON output
     async fn search_with_ripgrep(
         &self,
-        pattern: &str,
         search_path: &Path,
-        params: &GrepParams,
-    ) -> Result<String, String> {
+        options: &SearchOptions,
+    ) -> Result<SearchResults, String> {
         let mut cmd = Command::new("rg");
+        cmd.arg("--json").kill_on_drop(true);
 
-        // Add pattern and path
-        cmd.arg(pattern).arg(search_path);
-
-        // Output mode
-        match params.output_mode.as_deref() {
-            Some("files_with_matches") => {
-                cmd.arg("-l");
-            }
-            Some("count") => {
-                cmd.arg("-c");
-            }
-            _ => {
-                cmd.arg("-n"); // Line numbers
-                if let Some(ctx) = params.context {
-                    if ctx > 0 {
-                        cmd.arg("-C").arg(ctx.to_string());
-                    }
-                }
-            }
+        if options.fixed_strings {
+            cmd.arg("-F");
         }
-
-        // Case insensitivity
-        if params.case_insensitive.unwrap_or(false) {
+        if options.case_insensitive {
             cmd.arg("-i");
         }
-
-        // Glob filter
-        if let Some(ref glob) = params.glob {
+        if options.context > 0 {
+            cmd.arg("-C").arg(options.context.to_string());
+        }
+        for glob in &options.include {
             cmd.arg("-g").arg(glob);
         }
+        for glob in &options.exclude {
+            cmd.arg("-g").arg(format!("!{}", glob));
+        }
 
-        // Max results
-        let max_results = params.max_results.unwrap_or(100);
-        cmd.arg("-m").arg(max_results.to_string());
+        // Per-file cap; the global cap is applied while collecting
+        cmd.arg("-m").arg(options.max_matches.to_string());
+
+        // Pattern and path
+        cmd.arg("-e").arg(&options.pattern).arg(search_path);
 
-        // Run the command
         let output = cmd
             .output()
             .await
@@ -186,55 +308,40 @@ impl GrepTool {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let stderr = String::from_utf8_lossy(&output.stderr);
 
-        if !output.status.success() && stdout.is_empty() {
+        let results = parse_ripgrep_json(&stdout, options);
+
+        // rg returns exit code 1 for "no matches" which is normal
+        if !output.status.success() && output.status.code() != Some(1) && results.lines.is_empty() {
             if stderr.contains("No such file or directory") {
                 return Err("Path not found".to_string());
             }
-            // rg returns exit code 1 for "no matches" which is normal
-            if output.status.code() == Some(1) {
-                return Ok("No matches found.".to_string());
-            }
             return Err(format!("ripgrep error: {}", stderr));
         }
 
-        if stdout.is_empty() {
-            return Ok("No matches found.".to_string());
-        }
-
-        Ok(stdout.to_string())
+        Ok(results)
     }
 
     /// Run search using native Rust implementation (fallback)
     fn search_native(
         &self,
-        pattern: &str,
         search_path: &Path,
-        params: &GrepParams,
-    ) -> Result<String, String> {
-        let regex = if params.case_insensitive.unwrap_or(false) {
-            Regex::new(&format!("(?i){}", pattern))
-        } else {
-            Regex::new(pattern)
-        }
-        .map_err(|e| format!("Invalid regex pattern: {}", e))?;
-
-        let max_results = params.max_results.unwrap_or(100);
-        let context = params.context.unwrap_or(0);
-        let output_mode = params.output_mode.as_deref().unwrap_or("content");
+        options: &SearchOptions,
+    ) -> Result<SearchResults, String> {
+        let regex = options.build_regex()?;
+        let include = compile_globs(&options.include)?;
+        let exclude = compile_globs(&options.exclude)?;
+        let context = options.context;
 
-        let glob_pattern = params.glob.as_ref().map(|g| {
-            glob::Pattern::new(g).unwrap_or_else(|_| glob::Pattern::new("*").unwrap())
-        });
-
-        let mut results = Vec::new();
-        let mut match_count = 0;
+        let mut results = SearchResults::default();
 
-        for entry in WalkDir::new(search_path)
+        let walker = WalkDir::new(search_path)
             .follow_links(false)
+            .sort_by_file_name()
             .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            if match_count >= max_results {
+            .filter_entry(|e| e.depth() == 0 || !glob_matches(&exclude, e.path(), search_path));
+
+        for entry in walker.filter_map(|e| e.ok()) {
+            if results.truncated {
                 break;
             }
 
@@ -243,81 +350,164 @@ impl GrepTool {
                 continue;
             }
 
-            // Check glob pattern
-            if let Some(ref gp) = glob_pattern {
-                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
-                if !gp.matches(&file_name) {
-                    continue;
-                }
+            // An explicitly searched file is always included
+            if entry.depth() > 0 && !include.is_empty() && !glob_matches(&include, path, search_path) {
+                continue;
             }
 
             // Skip binary files
-            if let Ok(content) = std::fs::read_to_string(path) {
-                let lines: Vec<&str> = content.lines().collect();
-                let mut file_matches = Vec::new();
-                let mut file_count = 0;
-
-                for (line_num, line) in lines.iter().enumerate() {
-                    if regex.is_match(line) {
-                        file_count += 1;
-                        match_count += 1;
-
-                        if match_count > max_results {
-                            break;
-                        }
-
-                        match output_mode {
-                            "files_with_matches" => {
-                                // Will handle after loop
-                            }
-                            "count" => {
-                                // Will handle after loop
-                            }
-                            _ => {
-                                // Content mode with context
-                                let start = line_num.saturating_sub(context);
-                                let end = (line_num + context + 1).min(lines.len());
-
-                                for i in start..end {
-                                    let prefix = if i == line_num { ">" } else { " " };
-                                    file_matches.push(format!(
-                                        "{}{}:{}: {}",
-                                        prefix,
-                                        path.display(),
-                                        i + 1,
-                                        lines[i]
-                                    ));
-                                }
-                                if context > 0 && end < lines.len() {
-                                    file_matches.push("--".to_string());
-                                }
-                            }
-                        }
-                    }
-                }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let lines: Vec<&str> = content.lines().collect();
+            let match_lines: Vec<usize> = lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| regex.is_match(line))
+                .map(|(i, _)| i)
+                .collect();
+
+            if match_lines.is_empty() {
+                continue;
+            }
 
-                if file_count > 0 {
-                    match output_mode {
-                        "files_with_matches" => {
-                            results.push(path.display().to_string());
-                        }
-                        "count" => {
-                            results.push(format!("{}:{}", path.display(), file_count));
-                        }
-                        _ => {
-                            results.extend(file_matches);
-                        }
-                    }
-                }
+            // Lines to print: each match plus its surrounding context
+            let mut shown = BTreeSet::new();
+            for &m in &match_lines {
+                shown.extend(m.saturating_sub(context)..(m + context + 1).min(lines.len()));
+            }
+
+            let display_path = path.display().to_string();
+            for i in shown {
+                results.push(
+                    GrepLine {
+                        path: display_path.clone(),
+                        line_number: i + 1,
+                        text: lines[i].to_string(),
+                        is_match: match_lines.binary_search(&i).is_ok(),
+                    },
+                    options.max_matches,
+                    context,
+                );
             }
         }
 
-        if results.is_empty() {
-            return Ok("No matches found.".to_string());
+        Ok(results)
+    }
+}
+
+/// Collect match and context events from `rg --json` output
+fn parse_ripgrep_json(stdout: &str, options: &SearchOptions) -> SearchResults {
+    let mut results = SearchResults::default();
+
+    for line in stdout.lines() {
+        let Ok(event) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let is_match = match event["type"].as_str() {
+            Some("match") => true,
+            Some("context") => false,
+            _ => continue,
+        };
+        let data = &event["data"];
+        // Non-UTF-8 paths and lines are reported as base64 "bytes"; skip them
+        let (Some(path), Some(text), Some(line_number)) = (
+            data["path"]["text"].as_str(),
+            data["lines"]["text"].as_str(),
+            data["line_number"].as_u64(),
+        ) else {
+            continue;
+        };
+
+        results.push(
+            GrepLine {
+                path: path.to_string(),
+                line_number: line_number as usize,
+                text: text.trim_end_matches(['\n', '\r']).to_string(),
+                is_match,
+            },
+            options.max_matches,
+            options.context,
+        );
+    }
+
+    results
+}
+
+/// Expand one level of `{a,b}` alternation, which the glob crate doesn't support
+fn expand_braces(pattern: &str) -> Vec<String> {
+    match (pattern.find('{'), pattern.find('}')) {
+        (Some(open), Some(close)) if open < close => {
+            let (prefix, rest) = pattern.split_at(open);
+            let suffix = &rest[close - open + 1..];
+            rest[1..close - open]
+                .split(',')
+                .flat_map(|alt| expand_braces(&format!("{}{}{}", prefix, alt, suffix)))
+                .collect()
+        }
+        _ => vec![pattern.to_string()],
+    }
+}
+
+fn compile_globs(patterns: &[String]) -> Result<Vec<glob::Pattern>, String> {
+    patterns
+        .iter()
+        .flat_map(|p| expand_braces(p))
+        .map(|p| glob::Pattern::new(&p).map_err(|e| format!("Invalid glob pattern '{}': {}", p, e)))
+        .collect()
+}
+
+/// Patterns containing '/' match the path relative to the search root, others the file name
+fn glob_matches(patterns: &[glob::Pattern], path: &Path, root: &Path) -> bool {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let relative = path.strip_prefix(root).unwrap_or(path).to_string_lossy();
+    patterns.iter().any(|p| {
+        if p.as_str().contains('/') {
+            p.matches(&relative)
+        } else {
+            p.matches(&file_name)
+        }
+    })
+}
+
+/// Render results as grep output: `path:line:text` for matches, `path-line-text`
+/// for context, with `--` between non-adjacent groups
+fn format_results(results: &SearchResults, output_mode: &str, context: usize) -> String {
+    if results.file_counts.is_empty() {
+        return "No matches found.".to_string();
+    }
+
+    let mut out: Vec<String> = match output_mode {
+        "files_with_matches" => results.file_counts.iter().map(|(p, _)| p.clone()).collect(),
+        "count" => results
+            .file_counts
+            .iter()
+            .map(|(p, c)| format!("{}:{}", p, c))
+            .collect(),
+        _ => {
+            let mut out = Vec::new();
+            let mut prev: Option<&GrepLine> = None;
+            for line in &results.lines {
+                let new_group = prev.is_some_and(|p| p.path != line.path || p.line_number + 1 != line.line_number);
+                if context > 0 && new_group {
+                    out.push("--".to_string());
+                }
+                let sep = if line.is_match { ':' } else { '-' };
+                out.push(format!("{}{}{}{}{}", line.path, sep, line.line_number, sep, line.text));
+                prev = Some(line);
+            }
+            out
         }
+    };
 
-        Ok(results.join("\n"))
+    if results.truncated {
+        out.push(format!(
+            "\n[Stopped after {} matches (max_matches). Use a more specific pattern or raise max_matches.]",
+            results.total_matches
+        ));
     }
+
+    out.join("\n")
 }
 
 impl Default for GrepTool {
@@ -329,12 +519,17 @@ impl Default for GrepTool {
 #[derive(Debug, Deserialize)]
 struct GrepParams {
     pattern: String,
+    regex: Option<bool>,
     path: Option<String>,
     glob: Option<String>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
     output_mode: Option<String>,
-    context: Option<usize>,
+    #[serde(alias = "context")]
+    context_lines: Option<usize>,
     case_insensitive: Option<bool>,
-    max_results: Option<usize>,
+    #[serde(alias = "max_results")]
+    max_matches: Option<usize>,
 }
 
 #[async_trait]
@@ -343,75 +538,66 @@ impl Tool for GrepTool {
         self.definition.clone()
     }
 
+    fn is_mutating(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: GrepParams = match serde_json::from_value(params) {
             Ok(p) => p,
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
 
-        // Get workspace directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        // Resolve search path
-        let search_path = if let Some(ref path) = params.path {
-            let p = Path::new(path);
-            if p.is_absolute() {
-                p.to_path_buf()
-            } else {
-                workspace.join(p)
-            }
-        } else {
-            workspace.clone()
-        };
-
-        // Security check: ensure path is within workspace
-        let canonical_workspace = match workspace.canonicalize() {
+        // Resolve search path inside the workspace
+        let canonical_path = match resolve_workspace_path(context, params.path.as_deref().unwrap_or(".")) {
             Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve workspace directory: {}", e))
-            }
+            Err(e) => return ToolResult::error(e),
         };
 
-        let canonical_path = match search_path.canonicalize() {
-            Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Cannot resolve search path: {}", e)),
-        };
-
-        if !canonical_path.starts_with(&canonical_workspace) {
-            return ToolResult::error(format!(
-                "Access denied: path '{}' is outside the workspace directory",
-                params.path.as_deref().unwrap_or(".")
-            ));
-        }
+        let options = SearchOptions::from_params(&params);
 
         // Run search
-        let result = if Self::has_ripgrep().await {
-            self.search_with_ripgrep(&params.pattern, &canonical_path, &params)
-                .await
+        let use_ripgrep = Self::has_ripgrep().await;
+        let result = if use_ripgrep {
+            self.search_with_ripgrep(&canonical_path, &options).await
         } else {
-            self.search_native(&params.pattern, &canonical_path, &params)
+            self.search_native(&canonical_path, &options)
         };
 
-        match result {
-            Ok(output) => {
-                // Truncate if too long (keep small to avoid context bloat)
-                let max_output = 12000;
-                if output.len() > max_output {
-                    let truncated = &output[..max_output];
-                    ToolResult::success(format!(
-                        "{}\n\n[Output truncated. {} more characters not shown. Use more specific patterns.]",
-                        truncated,
-                        output.len() - max_output
-                    ))
-                } else {
-                    ToolResult::success(output)
-                }
+        let results = match result {
+            Ok(r) => r,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let output_mode = params.output_mode.as_deref().unwrap_or("content");
+        let output = format_results(&results, output_mode, options.context);
+
+        let metadata = json!({
+            "backend": if use_ripgrep { "ripgrep" } else { "native" },
+            "total_matches": results.total_matches,
+            "files_matched": results.file_counts.len(),
+            "truncated": results.truncated,
+            "matches": results.lines.iter().filter(|l| l.is_match).map(|l| json!({
+                "path": l.path,
+                "line_number": l.line_number,
+                "line": l.text,
+            })).collect::<Vec<_>>(),
+        });
+
+        // Truncate if too long
+        if output.len() > MAX_OUTPUT {
+            let mut cut = MAX_OUTPUT;
+            while !output.is_char_boundary(cut) {
+                cut -= 1;
             }
-            Err(e) => ToolResult::error(e),
+            ToolResult::success(format!(
+                "{}\n\n[Output truncated. {} more characters not shown. Use more specific patterns.]",
+                &output[..cut],
+                output.len() - cut
+            ))
+            .with_metadata(metadata)
+        } else {
+            ToolResult::success(output).with_metadata(metadata)
         }
     }
 }
@@ -421,6 +607,18 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    fn options(pattern: &str) -> SearchOptions {
+        SearchOptions {
+            pattern: pattern.to_string(),
+            fixed_strings: false,
+            case_insensitive: false,
+            context: 0,
+            max_matches: DEFAULT_MAX_MATCHES,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
     #[tokio::test]
     async fn test_grep_basic() {
         let tool = GrepTool::new();
@@ -437,6 +635,95 @@ mod tests {
 
         assert!(result.success);
         assert!(result.content.contains("main"));
+        let metadata = result.metadata.unwrap();
+        assert_eq!(metadata["total_matches"], 1);
+        assert_eq!(metadata["matches"][0]["line_number"], 1);
+        assert_eq!(metadata["matches"][0]["line"], "fn main() {");
+    }
+
+    #[test]
+    fn test_native_context_literal_and_cap() {
+        let tool = GrepTool::new();
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("a.rs"), "one\nfoo(1)\nthree\nfour\nfive\nsix\nFOO(2)\n").unwrap();
+
+        let mut opts = options("foo(");
+        opts.fixed_strings = true;
+        opts.case_insensitive = true;
+        opts.context = 1;
+        let results = tool.search_native(root, &opts).unwrap();
+        assert_eq!(results.total_matches, 2);
+        let numbers: Vec<usize> = results.lines.iter().map(|l| l.line_number).collect();
+        assert_eq!(numbers, vec![1, 2, 3, 6, 7]);
+
+        let path = root.join("a.rs").display().to_string();
+        let output = format_results(&results, "content", 1);
+        assert_eq!(
+            output,
+            format!("{p}-1-one\n{p}:2:foo(1)\n{p}-3-three\n--\n{p}-6-six\n{p}:7:FOO(2)", p = path)
+        );
+
+        // A literal "foo(" is an invalid regex
+        assert!(tool.search_native(root, &options("foo(")).is_err());
+
+        // The cap is global and keeps trailing context of the last match
+        opts.max_matches = 1;
+        let results = tool.search_native(root, &opts).unwrap();
+        assert!(results.truncated);
+        let numbers: Vec<usize> = results.lines.iter().map(|l| l.line_number).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_native_include_exclude_globs() {
+        let tool = GrepTool::new();
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        for file in ["src/lib.rs", "src/app.ts", "src/view.tsx", "notes.md", "target/out.rs"] {
+            std::fs::write(root.join(file), "needle\n").unwrap();
+        }
+
+        let files = |include: &[&str], exclude: &[&str]| -> Vec<String> {
+            let mut opts = options("needle");
+            opts.include = include.iter().map(|s| s.to_string()).collect();
+            opts.exclude = exclude.iter().map(|s| s.to_string()).collect();
+            let results = tool.search_native(root, &opts).unwrap();
+            results
+                .file_counts
+                .iter()
+                .map(|(p, _)| Path::new(p).strip_prefix(root).unwrap().display().to_string())
+                .collect()
+        };
+
+        assert_eq!(files(&["*.rs"], &["target"]), vec!["src/lib.rs"]);
+        assert_eq!(files(&["*.{ts,tsx}"], &[]), vec!["src/app.ts", "src/view.tsx"]);
+        assert_eq!(files(&["src/*"], &["*.rs"]), vec!["src/app.ts", "src/view.tsx"]);
+        assert_eq!(files(&[], &["src", "target"]), vec!["notes.md"]);
+    }
+
+    #[test]
+    fn test_parse_ripgrep_json() {
+        let stdout = concat!(
+            r#"{"type":"begin","data":{"path":{"text":"src/a.rs"}}}"#, "\n",
+            r#"{"type":"context","data":{"path":{"text":"src/a.rs"},"lines":{"text":"before\n"},"line_number":4}}"#, "\n",
+            r#"{"type":"match","data":{"path":{"text":"src/a.rs"},"lines":{"text":"let x = 1;\n"},"line_number":5,"submatches":[]}}"#, "\n",
+            r#"{"type":"match","data":{"path":{"text":"src/b.rs"},"lines":{"text":"let y = 2;\r\n"},"line_number":9,"submatches":[]}}"#, "\n",
+            r#"{"type":"summary","data":{}}"#, "\n",
+        );
+        let results = parse_ripgrep_json(stdout, &options("let"));
+
+        assert_eq!(results.total_matches, 2);
+        assert_eq!(results.file_counts, vec![("src/a.rs".to_string(), 1), ("src/b.rs".to_string(), 1)]);
+        assert_eq!(results.lines[0], GrepLine {
+            path: "src/a.rs".to_string(),
+            line_number: 4,
+            text: "before".to_string(),
+            is_match: false,
+        });
+        assert_eq!(results.lines[2].text, "let y = 2;");
     }
 
     #[tokio::test]
@@ -1,3 +1,4 @@
+use super::path_jail::{canonical_workspace_root, resolve_workspace_path};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -6,7 +7,7 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 /// Intrinsic files that appear in all workspaces
 const INTRINSIC_FILES: &[(&str, &str)] = &[
@@ -162,6 +163,10 @@ impl Tool for ListFilesTool {
         self.definition.clone()
     }
 
+    fn is_mutating(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: ListFilesParams = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -176,42 +181,16 @@ impl Tool for ListFilesTool {
         let limit = params.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
         let offset = params.offset.unwrap_or(0);
 
-        // Get workspace directory from context or use current directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        // Resolve the path
-        let requested_path = Path::new(&path);
-        let full_path = if requested_path.is_absolute() {
-            requested_path.to_path_buf()
-        } else {
-            workspace.join(requested_path)
-        };
-
-        // Canonicalize paths for comparison
-        let canonical_workspace = match workspace.canonicalize() {
+        // Resolve the path inside the workspace
+        let canonical_workspace = match canonical_workspace_root(context) {
             Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve workspace directory: {}", e))
-            }
+            Err(e) => return ToolResult::error(e),
         };
-
-        let canonical_path = match full_path.canonicalize() {
+        let canonical_path = match resolve_workspace_path(context, &path) {
             Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Cannot resolve path: {}", e)),
+            Err(e) => return ToolResult::error(e),
         };
 
-        // Security check: ensure path is within workspace
-        if !canonical_path.starts_with(&canonical_workspace) {
-            return ToolResult::error(format!(
-                "Access denied: path '{}' is outside the workspace directory",
-                path
-            ));
-        }
-
         // Check if path exists and is a directory
         if !canonical_path.exists() {
             return ToolResult::error(format!("Path not found: {}", path));
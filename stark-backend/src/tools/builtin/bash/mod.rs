@@ -4,12 +4,15 @@
 
 mod apply_patch;
 mod delete_file;
+pub(crate) mod diff;
 mod edit_file;
 mod exec;
+mod exec_policy;
 mod git;
 mod glob;
 mod grep;
 mod list_files;
+pub(crate) mod path_jail;
 mod read_file;
 mod rename_file;
 mod write_file;
@@ -0,0 +1,156 @@
+//! Workspace path jail shared by the filesystem tools
+//!
+//! Paths are resolved against a root directory and canonicalized, so `..`
+//! traversal, absolute paths and symlinks pointing elsewhere are all rejected
+//! if the real location ends up outside the root. Paths that don't exist yet
+//! are checked through their deepest existing ancestor.
+
+use crate::config::{journal_dir, workspace_dir};
+use crate::tools::types::ToolContext;
+use std::path::{Component, Path, PathBuf};
+
+/// Workspace root for a tool call: the context's workspace, else `config::workspace_dir()`
+pub(super) fn workspace_root(context: &ToolContext) -> PathBuf {
+    context
+        .workspace_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(workspace_dir()))
+}
+
+/// Canonical workspace root, for comparing already-canonical paths (e.g. walk results)
+pub(super) fn canonical_workspace_root(context: &ToolContext) -> Result<PathBuf, String> {
+    workspace_root(context)
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve workspace directory: {}", e))
+}
+
+fn outside_error(requested: &str) -> String {
+    format!("Access denied: path '{}' is outside the workspace directory", requested)
+}
+
+/// Resolve `requested` (relative to `root`, or absolute) to a canonical path inside `root`.
+/// The path itself doesn't need to exist.
+pub(super) fn resolve_in_root(root: &Path, requested: &str) -> Result<PathBuf, String> {
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve workspace directory: {}", e))?;
+
+    let requested_path = Path::new(requested);
+    let full_path = if requested_path.is_absolute() {
+        requested_path.to_path_buf()
+    } else {
+        root.join(requested_path)
+    };
+
+    // Find the deepest ancestor that exists (symlink_metadata so dangling links count)
+    let mut existing = full_path.as_path();
+    let mut remainder: Vec<Component> = Vec::new();
+    while existing.symlink_metadata().is_err() {
+        match (existing.parent(), existing.components().next_back()) {
+            (Some(parent), Some(last)) => {
+                remainder.push(last);
+                existing = parent;
+            }
+            _ => return Err(outside_error(requested)),
+        }
+    }
+
+    // Dangling symlinks can't be canonicalized and could point anywhere
+    let mut resolved = existing
+        .canonicalize()
+        .map_err(|_| outside_error(requested))?;
+
+    // The non-existent tail can't traverse symlinks, only `..`
+    for component in remainder.into_iter().rev() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            _ => return Err(outside_error(requested)),
+        }
+    }
+
+    if !resolved.starts_with(&canonical_root) {
+        return Err(outside_error(requested));
+    }
+
+    Ok(resolved)
+}
+
+/// Resolve a path inside the tool context's workspace
+pub(crate) fn resolve_workspace_path(context: &ToolContext, requested: &str) -> Result<PathBuf, String> {
+    resolve_in_root(&workspace_root(context), requested)
+}
+
+/// Resolve a path for tools that also accept `journal/...` paths, which map to the journal directory
+pub(super) fn resolve_workspace_or_journal_path(context: &ToolContext, requested: &str) -> Result<PathBuf, String> {
+    if requested == "journal" || requested.starts_with("journal/") {
+        let journal = PathBuf::from(journal_dir());
+        if !journal.exists() {
+            std::fs::create_dir_all(&journal)
+                .map_err(|e| format!("Cannot create journal directory: {}", e))?;
+        }
+        let relative = requested.strip_prefix("journal/").unwrap_or("");
+        resolve_in_root(&journal, relative)
+    } else {
+        resolve_workspace_path(context, requested)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_relative_paths_inside_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "").unwrap();
+        let canonical_root = root.canonicalize().unwrap();
+
+        assert_eq!(resolve_in_root(root, "src/main.rs").unwrap(), canonical_root.join("src/main.rs"));
+        assert_eq!(resolve_in_root(root, "src/../src/main.rs").unwrap(), canonical_root.join("src/main.rs"));
+        // Not-yet-existing files and directories are fine
+        assert_eq!(resolve_in_root(root, "new/dir/file.txt").unwrap(), canonical_root.join("new/dir/file.txt"));
+        assert_eq!(resolve_in_root(root, ".").unwrap(), canonical_root);
+    }
+
+    #[test]
+    fn test_traversal_and_absolute_paths_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        for path in ["../../etc/passwd", "/etc/passwd", "..", "missing/../../outside.txt"] {
+            let err = resolve_in_root(root, path).unwrap_err();
+            assert!(err.contains("outside the workspace"), "{}: {}", path, err);
+        }
+
+        // Absolute paths that point inside the workspace are allowed
+        let inside = root.join("file.txt");
+        assert!(resolve_in_root(root, inside.to_str().unwrap()).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_escapes_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(outside_dir.path().join("secret.txt"), "secret").unwrap();
+
+        std::os::unix::fs::symlink(outside_dir.path(), root.join("link")).unwrap();
+        std::os::unix::fs::symlink(outside_dir.path().join("nope.txt"), root.join("dangling")).unwrap();
+        std::os::unix::fs::symlink(root.join("inner"), root.join("inner_link")).unwrap();
+        std::fs::create_dir(root.join("inner")).unwrap();
+
+        for path in ["link/secret.txt", "link/new.txt", "link", "dangling"] {
+            let err = resolve_in_root(root, path).unwrap_err();
+            assert!(err.contains("outside the workspace"), "{}: {}", path, err);
+        }
+
+        // Symlinks that stay inside the workspace are fine
+        assert!(resolve_in_root(root, "inner_link/file.txt").is_ok());
+    }
+}
@@ -1,3 +1,4 @@
+use super::path_jail::resolve_workspace_path;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -6,7 +7,6 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
 
 /// Rename/move file tool - renames or moves files within a sandboxed directory
 pub struct RenameFileTool {
@@ -91,51 +91,19 @@ impl Tool for RenameFileTool {
 
         let create_dirs = params.create_dirs.unwrap_or(true);
 
-        // Get workspace directory from context or use current directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        // Resolve source path
-        let source_path = Path::new(&params.source);
-        let full_source = if source_path.is_absolute() {
-            source_path.to_path_buf()
-        } else {
-            workspace.join(source_path)
-        };
-
-        // Resolve destination path
-        let dest_path = Path::new(&params.destination);
-        let full_dest = if dest_path.is_absolute() {
-            dest_path.to_path_buf()
-        } else {
-            workspace.join(dest_path)
-        };
-
-        // Canonicalize workspace for comparison
-        let canonical_workspace = match workspace.canonicalize() {
+        // Resolve both paths inside the workspace
+        let canonical_source = match resolve_workspace_path(context, &params.source) {
             Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve workspace directory: {}", e))
-            }
+            Err(e) => return ToolResult::error(format!("Invalid source: {}", e)),
         };
-
-        // Canonicalize source (must exist)
-        let canonical_source = match full_source.canonicalize() {
+        if !canonical_source.exists() {
+            return ToolResult::error(format!("Source not found: {}", params.source));
+        }
+        let full_dest = match resolve_workspace_path(context, &params.destination) {
             Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Cannot resolve source path: {}", e)),
+            Err(e) => return ToolResult::error(format!("Invalid destination: {}", e)),
         };
 
-        // Security check: ensure source is within workspace
-        if !canonical_source.starts_with(&canonical_workspace) {
-            return ToolResult::error(format!(
-                "Access denied: source '{}' is outside the workspace directory",
-                params.source
-            ));
-        }
-
         // Create parent directories for destination if needed
         if create_dirs {
             if let Some(parent) = full_dest.parent() {
@@ -150,23 +118,6 @@ impl Tool for RenameFileTool {
             }
         }
 
-        // For the destination, we check the parent directory is within workspace
-        // (the destination file itself doesn't exist yet)
-        let dest_parent = full_dest.parent().unwrap_or(&full_dest);
-        let canonical_dest_parent = match dest_parent.canonicalize() {
-            Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve destination path: {}", e))
-            }
-        };
-
-        if !canonical_dest_parent.starts_with(&canonical_workspace) {
-            return ToolResult::error(format!(
-                "Access denied: destination '{}' is outside the workspace directory",
-                params.destination
-            ));
-        }
-
         // Check if destination already exists
         if full_dest.exists() {
             return ToolResult::error(format!(
@@ -1,4 +1,4 @@
-use crate::config::journal_dir;
+use super::path_jail::resolve_workspace_or_journal_path;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -7,7 +7,6 @@ use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
 
 /// Write file tool - writes contents to files within a sandboxed directory
 pub struct WriteFileTool {
@@ -104,96 +103,30 @@ impl Tool for WriteFileTool {
         let append = params.append.unwrap_or(false);
         let create_dirs = params.create_dirs.unwrap_or(true);
 
-        // Get workspace directory from context or use current directory
-        let workspace = context
-            .workspace_dir
-            .as_ref()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
-
-        // Get journal directory
-        let journal = PathBuf::from(journal_dir());
-
-        // Resolve the path - check if it starts with "journal/" to use journal dir
-        let requested_path = Path::new(&params.path);
-        let (full_path, base_dir) = if params.path.starts_with("journal/") || params.path == "journal" {
-            // Strip "journal/" prefix and use journal directory
-            let relative = params.path.strip_prefix("journal/").unwrap_or(&params.path);
-            (journal.join(relative), journal.clone())
-        } else if requested_path.is_absolute() {
-            (requested_path.to_path_buf(), workspace.clone())
-        } else {
-            (workspace.join(requested_path), workspace.clone())
+        // Resolve inside the workspace (journal/ paths map to the journal directory)
+        let final_path = match resolve_workspace_or_journal_path(context, &params.path) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(e),
         };
 
-        // Canonicalize base directory for comparison
-        // For journal, create it if it doesn't exist
-        if params.path.starts_with("journal") && !base_dir.exists() {
-            if let Err(e) = tokio::fs::create_dir_all(&base_dir).await {
-                return ToolResult::error(format!("Cannot create journal directory: {}", e));
-            }
+        if final_path.exists() && !final_path.is_file() {
+            return ToolResult::error(format!("Path exists but is not a file: {}", params.path));
         }
 
-        let canonical_base = match base_dir.canonicalize() {
-            Ok(p) => p,
-            Err(e) => {
-                return ToolResult::error(format!("Cannot resolve base directory: {}", e))
-            }
-        };
-
-        // For new files, we need to check the parent directory
-        let parent = match full_path.parent() {
+        // Create parent directories if needed and allowed (only after the jail check)
+        let parent = match final_path.parent() {
             Some(p) => p.to_path_buf(),
             None => return ToolResult::error("Invalid file path: no parent directory"),
         };
-
-        // Create parent directories if needed and allowed
-        if create_dirs && !parent.exists() {
+        if !parent.exists() {
+            if !create_dirs {
+                return ToolResult::error(format!("Parent directory does not exist: {}", parent.display()));
+            }
             if let Err(e) = tokio::fs::create_dir_all(&parent).await {
                 return ToolResult::error(format!("Failed to create directories: {}", e));
             }
         }
 
-        // Now canonicalize the parent to verify it's within allowed directory
-        let canonical_parent = match parent.canonicalize() {
-            Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Cannot resolve parent directory: {}", e)),
-        };
-
-        // Security check: ensure parent is within allowed directory (workspace or journal)
-        if !canonical_parent.starts_with(&canonical_base) {
-            return ToolResult::error(format!(
-                "Access denied: path '{}' is outside the allowed directory",
-                params.path
-            ));
-        }
-
-        // Construct the final path
-        let file_name = match full_path.file_name() {
-            Some(n) => n,
-            None => return ToolResult::error("Invalid file path: no file name"),
-        };
-        let final_path = canonical_parent.join(file_name);
-
-        // Additional check if file exists
-        if final_path.exists() {
-            let canonical_path = match final_path.canonicalize() {
-                Ok(p) => p,
-                Err(e) => return ToolResult::error(format!("Cannot resolve file path: {}", e)),
-            };
-
-            if !canonical_path.starts_with(&canonical_base) {
-                return ToolResult::error(format!(
-                    "Access denied: path '{}' is outside the allowed directory",
-                    params.path
-                ));
-            }
-
-            if !canonical_path.is_file() {
-                return ToolResult::error(format!("Path exists but is not a file: {}", params.path));
-            }
-        }
-
         // Write the file
         let result = if append {
             use tokio::io::AsyncWriteExt;
@@ -0,0 +1,363 @@
+//! Splitting working tree changes into logical commits
+//!
+//! The committer's `propose_split` diffs every changed file against HEAD and
+//! groups the changes by conventional commit type and scope (the file's
+//! directory). Within a file, hunks that only change whitespace or comments
+//! are split off into `style` and `docs` commits, so formatting noise doesn't
+//! bury the real change. The result is a proposal: the agent shows it for
+//! approval, then commits the groups one at a time with `commit_group`.
+
+use super::quality_rules::is_test_file;
+use crate::tools::builtin::bash::diff::{DiffHunk, FileDiff};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Register the plan is kept in when the caller doesn't name one
+pub(super) const DEFAULT_PLAN_REGISTER: &str = "commit_plan";
+
+/// Files named in a proposed commit message before the rest are counted
+const MAX_NAMED_FILES: usize = 3;
+
+/// Commit types in the order their groups are committed: build changes
+/// first, so later commits build, and formatting last
+const TYPE_ORDER: &[&str] = &["build", "feat", "refactor", "test", "docs", "style", "ci"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum FileStatus {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// A file with uncommitted changes, diffed against HEAD
+#[derive(Debug, Clone)]
+pub(super) struct ChangedFile {
+    pub path: String,
+    pub status: FileStatus,
+    pub diff: FileDiff,
+    /// Whether the file's hunks can be committed separately: a modified text
+    /// file ending in a newline, so a partial patch applies cleanly
+    pub splittable: bool,
+    /// Hash of the working copy when the plan was made ("deleted" if gone)
+    pub fingerprint: String,
+}
+
+/// One file's part in a proposed commit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct PlannedChange {
+    pub path: String,
+    pub fingerprint: String,
+    /// 1-based hunks of the file in this commit; `None` for the whole file
+    pub hunks: Option<Vec<usize>>,
+    /// Patch staging just those hunks, for partial changes
+    pub patch: Option<String>,
+}
+
+/// A proposed commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CommitGroup {
+    pub message: String,
+    pub changes: Vec<PlannedChange>,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    #[serde(default)]
+    pub committed: bool,
+}
+
+impl CommitGroup {
+    pub fn paths(&self) -> Vec<String> {
+        self.changes.iter().map(|c| c.path.clone()).collect()
+    }
+}
+
+/// A proposed split of the working tree, as stored in its register
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct SplitPlan {
+    pub branch: String,
+    pub groups: Vec<CommitGroup>,
+}
+
+impl SplitPlan {
+    /// Human-readable listing of the groups, for approval
+    pub fn describe(&self) -> String {
+        let mut out = format!("Proposed {} commit(s) on branch '{}':\n", self.groups.len(), self.branch);
+        for (i, group) in self.groups.iter().enumerate() {
+            out.push_str(&format!(
+                "\n{}. {} (+{} -{}){}\n",
+                i + 1,
+                group.message,
+                group.lines_added,
+                group.lines_removed,
+                if group.committed { " [committed]" } else { "" }
+            ));
+            for change in &group.changes {
+                match &change.hunks {
+                    Some(hunks) => out.push_str(&format!(
+                        "   - {} (hunks {})\n",
+                        change.path,
+                        hunks.iter().map(|h| h.to_string()).collect::<Vec<_>>().join(", ")
+                    )),
+                    None => out.push_str(&format!("   - {}\n", change.path)),
+                }
+            }
+        }
+        out
+    }
+}
+
+/// What a hunk changes
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HunkKind {
+    Code,
+    /// Only whitespace differs
+    Whitespace,
+    /// Only comment lines (or blank lines) change
+    Comments,
+}
+
+fn classify_hunk(hunk: &DiffHunk) -> HunkKind {
+    let strip = |prefix: char| -> String {
+        hunk.lines
+            .iter()
+            .filter(|l| l.starts_with(prefix))
+            .flat_map(|l| l[1..].chars().filter(|c| !c.is_whitespace()))
+            .collect()
+    };
+    if strip('-') == strip('+') {
+        return HunkKind::Whitespace;
+    }
+
+    let is_comment = |line: &str| {
+        let line = line.trim();
+        line.is_empty()
+            || line == "*"
+            || ["//", "/*", "*/", "* ", "#", "-- ", "<!--"]
+                .iter()
+                .any(|marker| line.starts_with(marker))
+    };
+    // Rust attributes start with '#' but are code
+    if hunk
+        .changed_lines()
+        .all(|l| is_comment(l) && !l.trim_start().starts_with("#["))
+    {
+        return HunkKind::Comments;
+    }
+    HunkKind::Code
+}
+
+/// Conventional commit type for a whole file
+fn file_type(file: &ChangedFile) -> &'static str {
+    let lower = file.path.to_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+
+    if lower.starts_with(".github/") || lower.starts_with(".gitlab") || lower.starts_with(".circleci/") {
+        "ci"
+    } else if matches!(
+        file_name,
+        "cargo.toml" | "cargo.lock" | "package.json" | "package-lock.json" | "yarn.lock"
+            | "pnpm-lock.yaml" | "dockerfile" | "makefile" | "build.rs"
+    ) {
+        "build"
+    } else if lower.ends_with(".md") || lower.ends_with(".txt") || lower.starts_with("docs/") || lower.contains("/docs/") {
+        "docs"
+    } else if is_test_file(&file.path, None) {
+        "test"
+    } else if file.status == FileStatus::Added || file.diff.lines_added > file.diff.lines_removed {
+        "feat"
+    } else {
+        "refactor"
+    }
+}
+
+/// The directory a file is in, as a commit scope
+fn scope(path: &str) -> Option<String> {
+    let dir = path.rsplit_once('/')?.0;
+    let name = dir.rsplit('/').next().unwrap_or(dir);
+    let name = name.trim_start_matches('.');
+    (!name.is_empty()).then(|| name.to_lowercase())
+}
+
+/// Patch applying only `hunks` of a file's diff
+fn partial_patch(path: &str, hunks: &[&DiffHunk]) -> String {
+    let mut patch = format!("--- a/{}\n+++ b/{}\n", path, path);
+    for hunk in hunks {
+        patch.push_str(&hunk.header);
+        patch.push('\n');
+        for line in &hunk.lines {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+    }
+    patch
+}
+
+fn lines_changed(hunks: &[&DiffHunk]) -> (usize, usize) {
+    hunks.iter().flat_map(|h| h.lines.iter()).fold((0, 0), |(added, removed), l| {
+        match l.chars().next() {
+            Some('+') => (added + 1, removed),
+            Some('-') => (added, removed + 1),
+            _ => (added, removed),
+        }
+    })
+}
+
+fn commit_message(commit_type: &str, scope: &Option<String>, changes: &[PlannedChange], all_added: bool) -> String {
+    let mut names: Vec<&str> = changes
+        .iter()
+        .map(|c| c.path.rsplit('/').next().unwrap_or(&c.path))
+        .collect();
+    names.dedup();
+    let mut subject = names.iter().take(MAX_NAMED_FILES).copied().collect::<Vec<_>>().join(", ");
+    if names.len() > MAX_NAMED_FILES {
+        subject.push_str(&format!(" and {} more", names.len() - MAX_NAMED_FILES));
+    }
+
+    let verb = match commit_type {
+        "style" => "format",
+        "docs" if changes.iter().any(|c| c.hunks.is_some()) => "update comments in",
+        _ if all_added => "add",
+        _ => "update",
+    };
+    match scope {
+        Some(scope) => format!("{}({}): {} {}", commit_type, scope, verb, subject),
+        None => format!("{}: {} {}", commit_type, verb, subject),
+    }
+}
+
+/// Group changed files and hunks into proposed commits
+pub(super) fn propose(files: &[ChangedFile]) -> Vec<CommitGroup> {
+    // (order, type, scope) -> changes, lines added and removed, whether every file is new
+    let mut groups: BTreeMap<(usize, &'static str, Option<String>), (Vec<PlannedChange>, usize, usize, bool)> =
+        BTreeMap::new();
+    let mut add = |commit_type: &'static str, file: &ChangedFile, hunks: Option<Vec<usize>>| {
+        let selected: Vec<&DiffHunk> = match &hunks {
+            Some(indices) => indices.iter().map(|i| &file.diff.hunks[i - 1]).collect(),
+            None => file.diff.hunks.iter().collect(),
+        };
+        let (added, removed) = lines_changed(&selected);
+        let order = TYPE_ORDER.iter().position(|t| *t == commit_type).unwrap_or(TYPE_ORDER.len());
+        let entry = groups
+            .entry((order, commit_type, scope(&file.path)))
+            .or_insert_with(|| (Vec::new(), 0, 0, true));
+        entry.0.push(PlannedChange {
+            path: file.path.clone(),
+            fingerprint: file.fingerprint.clone(),
+            patch: hunks.as_ref().map(|_| partial_patch(&file.path, &selected)),
+            hunks,
+        });
+        entry.1 += added;
+        entry.2 += removed;
+        entry.3 &= file.status == FileStatus::Added;
+    };
+
+    for file in files {
+        let commit_type = file_type(file);
+        if !file.splittable || !matches!(commit_type, "feat" | "refactor" | "test") {
+            add(commit_type, file, None);
+            continue;
+        }
+
+        let mut by_kind: Vec<(&'static str, Vec<usize>)> = Vec::new();
+        for (i, hunk) in file.diff.hunks.iter().enumerate() {
+            let kind = match classify_hunk(hunk) {
+                HunkKind::Code => commit_type,
+                HunkKind::Whitespace => "style",
+                HunkKind::Comments => "docs",
+            };
+            match by_kind.iter_mut().find(|(k, _)| *k == kind) {
+                Some((_, hunks)) => hunks.push(i + 1),
+                None => by_kind.push((kind, vec![i + 1])),
+            }
+        }
+
+        if by_kind.len() <= 1 {
+            let kind = by_kind.first().map(|(k, _)| *k).unwrap_or(commit_type);
+            add(kind, file, None);
+        } else {
+            for (kind, hunks) in by_kind {
+                add(kind, file, Some(hunks));
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((_, commit_type, scope), (changes, lines_added, lines_removed, all_added))| CommitGroup {
+            message: commit_message(commit_type, &scope, &changes, all_added),
+            changes,
+            lines_added,
+            lines_removed,
+            committed: false,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::builtin::bash::diff::unified_diff;
+
+    fn changed(path: &str, old: &str, new: &str) -> ChangedFile {
+        let status = match (old.is_empty(), new.is_empty()) {
+            (true, _) => FileStatus::Added,
+            (_, true) => FileStatus::Deleted,
+            _ => FileStatus::Modified,
+        };
+        ChangedFile {
+            path: path.to_string(),
+            status,
+            diff: unified_diff(Some(path), Some(path), old, new),
+            splittable: status == FileStatus::Modified,
+            fingerprint: "x".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_classify_hunks() {
+        let hunk = |lines: &[&str]| DiffHunk {
+            header: "@@ -1,1 +1,1 @@".to_string(),
+            lines: lines.iter().map(|l| l.to_string()).collect(),
+        };
+        assert_eq!(classify_hunk(&hunk(&["-let x=1;", "+let x = 1;"])), HunkKind::Whitespace);
+        assert_eq!(classify_hunk(&hunk(&[" fn a() {}", "-// old", "+// new", "+"])), HunkKind::Comments);
+        assert_eq!(classify_hunk(&hunk(&["+#[derive(Debug)]"])), HunkKind::Code);
+        assert_eq!(classify_hunk(&hunk(&["-let x = 1;", "+let x = 2;"])), HunkKind::Code);
+    }
+
+    #[test]
+    fn test_propose_groups_by_type_and_scope() {
+        let old: String = (1..=30).map(|i| format!("let v{} = {};\n", i, i)).collect();
+        // A real change at the top, and reformatting far below it
+        let new = old
+            .replacen("let v2 = 2;", "let v2 = 20;", 1)
+            .replacen("let v25 = 25;", "let v25   =   25;", 1);
+
+        let files = vec![
+            changed("src/tools/deploy.rs", &old, &new),
+            changed("src/tools/committer.rs", "", "fn main() {}\n"),
+            changed("docs/README.md", "old\n", "new\n"),
+            changed("Cargo.toml", "a = 1\n", "a = 2\n"),
+        ];
+        let groups = propose(&files);
+        let messages: Vec<&str> = groups.iter().map(|g| g.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "build: update Cargo.toml",
+                "feat(tools): add committer.rs",
+                "refactor(tools): update deploy.rs",
+                "docs(docs): update README.md",
+                "style(tools): format deploy.rs",
+            ]
+        );
+
+        // deploy.rs is split across the refactor and style commits
+        let code = &groups[2].changes[0];
+        assert_eq!(code.hunks, Some(vec![1]));
+        assert!(code.patch.as_deref().unwrap().contains("+let v2 = 20;"));
+        assert_eq!((groups[2].lines_added, groups[2].lines_removed), (1, 1));
+        assert_eq!(groups[4].changes[0].hunks, Some(vec![2]));
+        // A new file is committed whole
+        assert_eq!(groups[1].changes[0].hunks, None);
+    }
+}
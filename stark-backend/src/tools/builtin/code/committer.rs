@@ -1,3 +1,5 @@
+use super::commit_split::{self, ChangedFile, FileStatus, SplitPlan, DEFAULT_PLAN_REGISTER};
+use crate::tools::builtin::bash::diff::{unified_diff, FileDiff};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -6,9 +8,11 @@ use async_trait::async_trait;
 use regex::Regex;
 use serde::Deserialize;
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
 /// Scoped Committer Tool - Safe, intelligent git commits
@@ -20,6 +24,8 @@ use tokio::process::Command;
 /// - Adding attribution (Co-Authored-By)
 /// - Validating files exist and are actually modified
 /// - Preventing commits on protected branches
+/// - Proposing a split of the working tree into logical commits, committed
+///   group by group once approved
 pub struct CommitterTool {
     definition: ToolDefinition,
 }
@@ -39,6 +45,8 @@ const SECRET_PATTERNS: &[(&str, &str)] = &[
     (r#"sk-[a-zA-Z0-9]{48}"#, "OpenAI API Key"),
     (r#"sk-ant-[a-zA-Z0-9-]{95}"#, "Anthropic API Key"),
     (r#"xox[baprs]-[a-zA-Z0-9-]{10,}"#, "Slack Token"),
+    (r#"(?i)(private|priv|wallet|signer)[_-]?key.{0,40}?\b(0x)?[0-9a-fA-F]{64}\b"#, "Wallet private key"),
+    (r#"(?i)(mnemonic|seed[_-]?phrase)\s*[:=]\s*['"]?([a-z]+\s+){11,23}[a-z]+"#, "Seed phrase"),
 ];
 
 /// Sensitive file patterns that should never be committed
@@ -78,11 +86,26 @@ impl CommitterTool {
     pub fn new() -> Self {
         let mut properties = HashMap::new();
 
+        properties.insert(
+            "operation".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'commit' (default) commits the given files. 'propose_split' groups all uncommitted changes into logical commits with suggested messages, for approval; nothing is committed. 'commit_group' commits one approved group of that plan.".to_string(),
+                default: Some(json!("commit")),
+                items: None,
+                enum_values: Some(vec![
+                    "commit".to_string(),
+                    "propose_split".to_string(),
+                    "commit_group".to_string(),
+                ]),
+            },
+        );
+
         properties.insert(
             "message".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Commit message. Should follow conventional commits format: type(scope): description. Types: feat, fix, docs, style, refactor, perf, test, chore, ci, build, revert".to_string(),
+                description: "Commit message (required for commit; overrides the proposed message for commit_group). Should follow conventional commits format: type(scope): description. Types: feat, fix, docs, style, refactor, perf, test, chore, ci, build, revert".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -93,7 +116,7 @@ impl CommitterTool {
             "files".to_string(),
             PropertySchema {
                 schema_type: "array".to_string(),
-                description: "Files to stage and commit. Must specify exact file paths - no wildcards or '.' allowed for safety.".to_string(),
+                description: "Files to stage and commit (for commit). Must specify exact file paths - no wildcards or '.' allowed for safety.".to_string(),
                 default: None,
                 items: Some(Box::new(PropertySchema {
                     schema_type: "string".to_string(),
@@ -106,6 +129,28 @@ impl CommitterTool {
             },
         );
 
+        properties.insert(
+            "plan".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register holding the split plan (for propose_split and commit_group)".to_string(),
+                default: Some(json!(DEFAULT_PLAN_REGISTER)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "group".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "Number of the plan group to commit, as listed by propose_split (for commit_group)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "allow_sensitive".to_string(),
             PropertySchema {
@@ -153,11 +198,11 @@ impl CommitterTool {
         CommitterTool {
             definition: ToolDefinition {
                 name: "committer".to_string(),
-                description: "Safe, scoped git commits with secret detection and conventional commit enforcement. Only stages specified files, prevents accidental commits of sensitive data, and adds proper attribution. Preferred over direct git commit for safety.".to_string(),
+                description: "Safe, scoped git commits with secret detection and conventional commit enforcement. Only stages specified files, prevents accidental commits of sensitive data, and adds proper attribution. Can also split unrelated changes into several logical commits: propose_split returns the grouping for approval, then commit_group commits each group. Preferred over direct git commit for safety.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
-                    required: vec!["message".to_string(), "files".to_string()],
+                    required: vec![],
                 },
                 group: ToolGroup::Development,
             },
@@ -173,7 +218,7 @@ impl CommitterTool {
     }
 
     /// Validate conventional commit format
-    fn validate_conventional_commit(message: &str) -> Result<(), String> {
+    pub(super) fn validate_conventional_commit(message: &str) -> Result<(), String> {
         // Pattern: type(scope)?: description
         let pattern = format!(
             r"^({})(\([a-zA-Z0-9_-]+\))?!?:\s+.+",
@@ -197,7 +242,7 @@ impl CommitterTool {
     }
 
     /// Check if a file matches sensitive patterns
-    fn is_sensitive_file(file: &str) -> Option<&'static str> {
+    pub(super) fn is_sensitive_file(file: &str) -> Option<&'static str> {
         let file_lower = file.to_lowercase();
         let file_name = PathBuf::from(file)
             .file_name()
@@ -220,13 +265,16 @@ impl CommitterTool {
 
     /// Scan file content for secrets
     async fn scan_for_secrets(&self, file_path: &PathBuf) -> Vec<(String, usize)> {
-        let mut findings = Vec::new();
-
         // Read file content
-        let content = match tokio::fs::read_to_string(file_path).await {
-            Ok(c) => c,
-            Err(_) => return findings, // Skip binary files or unreadable files
-        };
+        match tokio::fs::read_to_string(file_path).await {
+            Ok(content) => Self::find_secrets(&content),
+            Err(_) => Vec::new(), // Skip binary files or unreadable files
+        }
+    }
+
+    /// Find secret-looking strings, as (secret type, line number)
+    pub(super) fn find_secrets(content: &str) -> Vec<(String, usize)> {
+        let mut findings = Vec::new();
 
         for (pattern_str, name) in SECRET_PATTERNS {
             if let Ok(re) = Regex::new(pattern_str) {
@@ -252,7 +300,8 @@ impl CommitterTool {
         cmd.args(args)
             .current_dir(workspace)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
         // Set git author from context
         let bot_name = context.get_bot_name();
@@ -306,6 +355,323 @@ impl CommitterTool {
             })
             .collect())
     }
+
+    /// Refuse sensitive files and files containing secret-looking strings
+    async fn check_sensitive(&self, files: &[String], workspace: &PathBuf) -> Result<(), String> {
+        let mut sensitive_warnings = Vec::new();
+        let mut secret_warnings = Vec::new();
+
+        for file in files {
+            // Check for sensitive file patterns
+            if let Some(pattern) = Self::is_sensitive_file(file) {
+                sensitive_warnings.push(format!("{} (matches pattern: {})", file, pattern));
+            }
+
+            // Scan for secrets in content
+            for (secret_type, line_num) in self.scan_for_secrets(&workspace.join(file)).await {
+                secret_warnings.push(format!("{}: {} found on line {}", file, secret_type, line_num));
+            }
+        }
+
+        // Block on sensitive files unless explicitly allowed
+        if !sensitive_warnings.is_empty() {
+            return Err(format!(
+                "SECURITY: Cannot commit sensitive files:\n{}\n\nIf you're sure these are safe, set allow_sensitive: true (requires explicit user confirmation).",
+                sensitive_warnings.join("\n")
+            ));
+        }
+
+        // Block on secrets found
+        if !secret_warnings.is_empty() {
+            return Err(format!(
+                "SECURITY: Potential secrets detected:\n{}\n\nReview and remove secrets before committing. If these are false positives, set allow_sensitive: true.",
+                secret_warnings.join("\n")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Commit message with the bot's attribution appended
+    fn attributed_message(message: &str, context: &ToolContext) -> String {
+        format!(
+            "{}\n\nCo-Authored-By: {} <{}>",
+            message,
+            context.get_bot_name(),
+            context.get_bot_email()
+        )
+    }
+
+    /// Identifies a working copy, so a plan isn't committed after the file changed
+    async fn fingerprint(workspace: &PathBuf, path: &str) -> String {
+        match tokio::fs::read(workspace.join(path)).await {
+            Ok(bytes) => hex::encode(Sha256::digest(&bytes)),
+            Err(_) => "deleted".to_string(),
+        }
+    }
+
+    /// Error unless nothing is staged, since a split commits from the index
+    async fn ensure_index_clean(&self, workspace: &PathBuf, context: &ToolContext) -> Result<(), String> {
+        let staged = self.run_git(&["diff", "--cached", "--name-only"], workspace, context).await?;
+        if staged.trim().is_empty() {
+            return Ok(());
+        }
+        Err(format!(
+            "Some changes are already staged:\n{}\nCommit or unstage them (git reset) before splitting.",
+            staged.trim()
+        ))
+    }
+
+    /// Every uncommitted change in the workspace, diffed against HEAD
+    async fn changed_files(&self, workspace: &PathBuf, context: &ToolContext) -> Result<Vec<ChangedFile>, String> {
+        let status = self
+            .run_git(&["status", "--porcelain", "-z", "--untracked-files=all"], workspace, context)
+            .await?;
+
+        let mut files = Vec::new();
+        for entry in status.split('\0').filter(|e| e.len() > 3) {
+            let (code, path) = (entry[..2].trim(), entry[3..].to_string());
+            let status = match code {
+                "??" | "A" => FileStatus::Added,
+                "D" => FileStatus::Deleted,
+                _ => FileStatus::Modified,
+            };
+
+            let old = match status {
+                FileStatus::Added => Some(String::new()),
+                _ => self
+                    .run_git(&["show", &format!("HEAD:{}", path)], workspace, context)
+                    .await
+                    .ok()
+                    .filter(|c| !c.contains('\0') && !c.contains('\u{FFFD}')),
+            };
+            let new = match status {
+                FileStatus::Deleted => Some(String::new()),
+                _ => tokio::fs::read(workspace.join(&path))
+                    .await
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .filter(|c| !c.contains('\0')),
+            };
+
+            // Binary files are committed whole, without a diff
+            let (diff, splittable) = match (&old, &new) {
+                (Some(old), Some(new)) => {
+                    let old_path = (status != FileStatus::Added).then_some(path.as_str());
+                    let new_path = (status != FileStatus::Deleted).then_some(path.as_str());
+                    (
+                        unified_diff(old_path, new_path, old, new),
+                        status == FileStatus::Modified && old.ends_with('\n') && new.ends_with('\n'),
+                    )
+                }
+                _ => (FileDiff::default(), false),
+            };
+
+            files.push(ChangedFile {
+                fingerprint: Self::fingerprint(workspace, &path).await,
+                path,
+                status,
+                diff,
+                splittable,
+            });
+        }
+        Ok(files)
+    }
+
+    /// Stage part of a file's changes with `git apply --cached`
+    async fn apply_to_index(&self, patch: &str, workspace: &PathBuf) -> Result<(), String> {
+        let mut child = Command::new("git")
+            .args(["apply", "--cached", "-"])
+            .current_dir(workspace)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to execute git: {}", e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(patch.as_bytes())
+                .await
+                .map_err(|e| format!("Failed to write patch: {}", e))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| format!("Failed to execute git: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("git apply failed: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(())
+    }
+
+    /// Group the working tree's changes into proposed commits and keep the
+    /// plan in a register. Nothing is staged or committed.
+    async fn propose_split(&self, params: &CommitterParams, workspace: &PathBuf, context: &ToolContext) -> ToolResult {
+        if let Err(e) = self.ensure_index_clean(workspace, context).await {
+            return ToolResult::error(e);
+        }
+        let branch = match self.get_current_branch(workspace, context).await {
+            Ok(b) => b,
+            Err(e) => return ToolResult::error(format!("Failed to get current branch: {}", e)),
+        };
+        let files = match self.changed_files(workspace, context).await {
+            Ok(f) => f,
+            Err(e) => return ToolResult::error(format!("Failed to read changes: {}", e)),
+        };
+        if files.is_empty() {
+            return ToolResult::error("No uncommitted changes to split");
+        }
+
+        let plan = SplitPlan { branch, groups: commit_split::propose(&files) };
+        let register = params.plan.as_deref().unwrap_or(DEFAULT_PLAN_REGISTER);
+        match serde_json::to_value(&plan) {
+            Ok(value) => context.set_register(register, value, "committer"),
+            Err(e) => return ToolResult::error(format!("Failed to store plan: {}", e)),
+        }
+
+        ToolResult::success(format!(
+            "{}\nNothing has been committed. Show this plan to the user for approval, then commit each group in order with operation 'commit_group' (plan: '{}', group: N). A group's message can be replaced with 'message'.",
+            plan.describe(),
+            register
+        ))
+        .with_metadata(json!({
+            "plan": register,
+            "groups": plan.groups.iter().map(|g| json!({
+                "message": g.message,
+                "files": g.paths(),
+                "lines_added": g.lines_added,
+                "lines_removed": g.lines_removed,
+            })).collect::<Vec<_>>(),
+        }))
+    }
+
+    /// Commit one group of a split plan
+    async fn commit_group(&self, params: &CommitterParams, workspace: &PathBuf, context: &ToolContext) -> ToolResult {
+        let register = params.plan.as_deref().unwrap_or(DEFAULT_PLAN_REGISTER);
+        let mut plan: SplitPlan = match context.registers.get(register).map(serde_json::from_value::<SplitPlan>) {
+            Some(Ok(plan)) => plan,
+            Some(Err(_)) => return ToolResult::error(format!("Register '{}' does not hold a commit plan", register)),
+            None => return ToolResult::error(format!("Register '{}' not found. Run propose_split first.", register)),
+        };
+        let index = match params.group {
+            Some(n) if n >= 1 && n <= plan.groups.len() => n - 1,
+            _ => return ToolResult::error(format!("group must be between 1 and {}", plan.groups.len())),
+        };
+        let group = plan.groups[index].clone();
+        if group.committed {
+            return ToolResult::error(format!("Group {} is already committed", index + 1));
+        }
+
+        let message = params.message.clone().unwrap_or_else(|| group.message.clone());
+        if !params.skip_validation.unwrap_or(false) {
+            if let Err(e) = Self::validate_conventional_commit(&message) {
+                return ToolResult::error(e);
+            }
+        }
+
+        let branch = match self.get_current_branch(workspace, context).await {
+            Ok(b) => b,
+            Err(e) => return ToolResult::error(format!("Failed to get current branch: {}", e)),
+        };
+        if branch != plan.branch {
+            return ToolResult::error(format!(
+                "The plan was made on branch '{}' but '{}' is checked out",
+                plan.branch, branch
+            ));
+        }
+        let push = params.push.unwrap_or(false);
+        if Self::is_protected_branch(&branch) && push {
+            return ToolResult::error(format!(
+                "Cannot push directly to protected branch '{}'. Please create a feature branch and use a pull request.",
+                branch
+            ));
+        }
+        if let Err(e) = self.ensure_index_clean(workspace, context).await {
+            return ToolResult::error(e);
+        }
+
+        // The plan's patches only apply to the files as they were
+        for change in &group.changes {
+            if Self::fingerprint(workspace, &change.path).await != change.fingerprint {
+                return ToolResult::error(format!(
+                    "{} changed since the plan was made. Run propose_split again.",
+                    change.path
+                ));
+            }
+        }
+
+        let paths = group.paths();
+        if !params.allow_sensitive.unwrap_or(false) {
+            if let Err(e) = self.check_sensitive(&paths, workspace).await {
+                return ToolResult::error(e);
+            }
+        }
+
+        if params.dry_run.unwrap_or(false) {
+            return ToolResult::success(format!(
+                "DRY RUN - Would commit group {}:\nBranch: {}\nFiles ({}):\n  {}\nMessage: {}\nPush: {}",
+                index + 1,
+                branch,
+                paths.len(),
+                paths.join("\n  "),
+                message,
+                push
+            ));
+        }
+
+        for change in &group.changes {
+            let staged = match &change.patch {
+                Some(patch) => self.apply_to_index(patch, workspace).await,
+                None => self
+                    .run_git(&["add", "-A", "--", &change.path], workspace, context)
+                    .await
+                    .map(|_| ()),
+            };
+            if let Err(e) = staged {
+                let _ = self.run_git(&["reset", "HEAD"], workspace, context).await;
+                return ToolResult::error(format!("Failed to stage {}: {}", change.path, e));
+            }
+        }
+
+        let full_message = Self::attributed_message(&message, context);
+        let output = match self.run_git(&["commit", "-m", &full_message], workspace, context).await {
+            Ok(output) => output,
+            Err(e) => {
+                let _ = self.run_git(&["reset", "HEAD"], workspace, context).await;
+                return ToolResult::error(format!("Commit failed: {}", e));
+            }
+        };
+
+        plan.groups[index].committed = true;
+        if let Ok(value) = serde_json::to_value(&plan) {
+            context.set_register(register, value, "committer");
+        }
+        let remaining = plan.groups.iter().filter(|g| !g.committed).count();
+
+        let mut result = format!(
+            "Committed group {} of {} on branch '{}':\n{}\n\nMessage: {}\n\n{}",
+            index + 1,
+            plan.groups.len(),
+            branch,
+            paths.iter().map(|f| format!("  - {}", f)).collect::<Vec<_>>().join("\n"),
+            message,
+            output
+        );
+        if remaining > 0 {
+            result.push_str(&format!("\n{} group(s) left to commit.", remaining));
+        }
+
+        if push {
+            match self.run_git(&["push", "-u", "origin", &branch], workspace, context).await {
+                Ok(push_output) => result.push_str(&format!("\nPushed to origin/{}:\n{}", branch, push_output)),
+                Err(e) => result.push_str(&format!("\nCommit succeeded but push failed: {}", e)),
+            }
+        }
+
+        ToolResult::success(result)
+    }
 }
 
 impl Default for CommitterTool {
@@ -316,8 +682,11 @@ impl Default for CommitterTool {
 
 #[derive(Debug, Deserialize)]
 struct CommitterParams {
-    message: String,
-    files: Vec<String>,
+    operation: Option<String>,
+    message: Option<String>,
+    files: Option<Vec<String>>,
+    plan: Option<String>,
+    group: Option<usize>,
     allow_sensitive: Option<bool>,
     skip_validation: Option<bool>,
     push: Option<bool>,
@@ -348,13 +717,31 @@ impl Tool for CommitterTool {
             .map(PathBuf::from)
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
+        match params.operation.as_deref().unwrap_or("commit") {
+            "commit" => {}
+            "propose_split" => return self.propose_split(&params, &workspace, context).await,
+            "commit_group" => return self.commit_group(&params, &workspace, context).await,
+            other => {
+                return ToolResult::error(format!(
+                    "Unknown operation: {}. Supported: commit, propose_split, commit_group",
+                    other
+                ))
+            }
+        }
+
+        let message = match &params.message {
+            Some(m) => m.clone(),
+            None => return ToolResult::error("message is required for commit"),
+        };
+        let files = params.files.clone().unwrap_or_default();
+
         // Validate files list isn't empty
-        if params.files.is_empty() {
+        if files.is_empty() {
             return ToolResult::error("No files specified. You must provide specific file paths to commit.");
         }
 
         // Block dangerous patterns
-        let dangerous = params.files.iter().find(|f| {
+        let dangerous = files.iter().find(|f| {
             *f == "." || *f == "-A" || *f == "--all" || f.contains('*') || *f == "-a"
         });
         if let Some(d) = dangerous {
@@ -366,7 +753,7 @@ impl Tool for CommitterTool {
 
         // Validate conventional commit format
         if !skip_validation {
-            if let Err(e) = Self::validate_conventional_commit(&params.message) {
+            if let Err(e) = Self::validate_conventional_commit(&message) {
                 return ToolResult::error(e);
             }
         }
@@ -392,10 +779,8 @@ impl Tool for CommitterTool {
 
         // Validate all specified files exist and are modified
         let mut issues = Vec::new();
-        let mut sensitive_warnings = Vec::new();
-        let mut secret_warnings = Vec::new();
 
-        for file in &params.files {
+        for file in &files {
             let file_path = workspace.join(file);
 
             // Check if file exists
@@ -405,7 +790,7 @@ impl Tool for CommitterTool {
             }
 
             // Check if file is modified (or new)
-            if !modified_files.iter().any(|m| m == file || m.ends_with(file)) {
+            if !modified_files.iter().any(|m| m == file || m.ends_with(file.as_str())) {
                 // Check if it's a new untracked file
                 let status_output = self.run_git(&["status", "--porcelain", file], &workspace, context).await;
                 if let Ok(status) = status_output {
@@ -415,21 +800,6 @@ impl Tool for CommitterTool {
                     }
                 }
             }
-
-            // Check for sensitive file patterns
-            if let Some(pattern) = Self::is_sensitive_file(file) {
-                if !allow_sensitive {
-                    sensitive_warnings.push(format!("{} (matches pattern: {})", file, pattern));
-                }
-            }
-
-            // Scan for secrets in content
-            let secrets = self.scan_for_secrets(&file_path).await;
-            for (secret_type, line_num) in secrets {
-                if !allow_sensitive {
-                    secret_warnings.push(format!("{}: {} found on line {}", file, secret_type, line_num));
-                }
-            }
         }
 
         // Report issues
@@ -440,20 +810,10 @@ impl Tool for CommitterTool {
             ));
         }
 
-        // Block on sensitive files unless explicitly allowed
-        if !sensitive_warnings.is_empty() {
-            return ToolResult::error(format!(
-                "SECURITY: Cannot commit sensitive files:\n{}\n\nIf you're sure these are safe, set allow_sensitive: true (requires explicit user confirmation).",
-                sensitive_warnings.join("\n")
-            ));
-        }
-
-        // Block on secrets found
-        if !secret_warnings.is_empty() {
-            return ToolResult::error(format!(
-                "SECURITY: Potential secrets detected:\n{}\n\nReview and remove secrets before committing. If these are false positives, set allow_sensitive: true.",
-                secret_warnings.join("\n")
-            ));
+        if !allow_sensitive {
+            if let Err(e) = self.check_sensitive(&files, &workspace).await {
+                return ToolResult::error(e);
+            }
         }
 
         // Dry run - just report what would happen
@@ -465,39 +825,33 @@ impl Tool for CommitterTool {
                 Message: {}\n\
                 Push: {}",
                 branch,
-                params.files.len(),
-                params.files.join("\n  "),
-                params.message,
+                files.len(),
+                files.join("\n  "),
+                message,
                 push
             ));
         }
 
         // Stage the files
         let mut stage_args = vec!["add"];
-        for f in &params.files {
+        for f in &files {
             stage_args.push(f.as_str());
         }
         if let Err(e) = self.run_git(&stage_args, &workspace, context).await {
             return ToolResult::error(format!("Failed to stage files: {}", e));
         }
 
-        // Build commit message with attribution
-        let bot_name = context.get_bot_name();
-        let bot_email = context.get_bot_email();
-        let full_message = format!(
-            "{}\n\nCo-Authored-By: {} <{}>",
-            params.message, bot_name, bot_email
-        );
+        let full_message = Self::attributed_message(&message, context);
 
         // Create commit
         match self.run_git(&["commit", "-m", &full_message], &workspace, context).await {
             Ok(output) => {
                 let mut result = format!(
                     "Committed {} file(s) on branch '{}':\n{}\n\nMessage: {}\n\n{}",
-                    params.files.len(),
+                    files.len(),
                     branch,
-                    params.files.iter().map(|f| format!("  - {}", f)).collect::<Vec<_>>().join("\n"),
-                    params.message,
+                    files.iter().map(|f| format!("  - {}", f)).collect::<Vec<_>>().join("\n"),
+                    message,
                     output
                 );
 
@@ -553,6 +907,17 @@ mod tests {
         assert!(CommitterTool::is_sensitive_file("README.md").is_none());
     }
 
+    #[test]
+    fn test_secret_detection() {
+        let key = "ab".repeat(32);
+        let findings = CommitterTool::find_secrets(&format!("let x = 1;\nPRIVATE_KEY=0x{}\n", key));
+        assert_eq!(findings, vec![("Wallet private key".to_string(), 2)]);
+
+        // A bare 32-byte hex value (e.g. a tx hash) isn't a secret on its own
+        assert!(CommitterTool::find_secrets(&format!("let tx_hash = \"0x{}\";", key)).is_empty());
+        assert!(!CommitterTool::find_secrets("MNEMONIC=\"test test test test test test test test test test test junk\"").is_empty());
+    }
+
     #[test]
     fn test_protected_branch() {
         assert!(CommitterTool::is_protected_branch("main"));
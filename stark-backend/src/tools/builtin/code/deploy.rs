@@ -1,24 +1,37 @@
 use crate::controllers::api_keys::ApiKeyId;
+use crate::db::tables::deployments::{Deployment, DeploymentStatus};
+use crate::db::Database;
+use crate::gateway::protocol::GatewayEvent;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 
+/// Longest a `watch` waits for a deployment's workflow run to finish
+const DEPLOY_WATCH_TIMEOUT_SECS: u64 = 1800;
+
+/// Log lines from the end of a watched run kept in the tool result
+const WATCH_RESULT_LINES: usize = 40;
+
 /// Deploy Tool - Git push, PR creation, and CI/CD monitoring
 ///
 /// This tool provides deployment capabilities:
 /// - Push to remote repositories (with safety checks)
 /// - Create pull requests via GitHub CLI
 /// - Monitor CI/CD workflow runs
-/// - Trigger deployments
-/// - Check deployment status
+/// - Trigger deployments to a named target, recording each attempt
+/// - Check a target's deployment status, stream its run logs, and roll it
+///   back to the last deployment that succeeded
 pub struct DeployTool {
     definition: ToolDefinition,
 }
@@ -31,7 +44,7 @@ impl DeployTool {
             "operation".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Deploy operation: push, pull, fetch, create_pr, pr_status, workflow_status, trigger_deploy, merge_pr".to_string(),
+                description: "Deploy operation: push, pull, fetch, create_pr, pr_status, workflow_status, trigger_deploy, status, watch, rollback, merge_pr".to_string(),
                 default: None,
                 items: None,
                 enum_values: Some(vec![
@@ -42,6 +55,9 @@ impl DeployTool {
                     "pr_status".to_string(),
                     "workflow_status".to_string(),
                     "trigger_deploy".to_string(),
+                    "status".to_string(),
+                    "watch".to_string(),
+                    "rollback".to_string(),
                     "merge_pr".to_string(),
                 ]),
             },
@@ -124,6 +140,17 @@ impl DeployTool {
             },
         );
 
+        properties.insert(
+            "target".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Service or environment to deploy (for trigger_deploy, status, watch, rollback). Must be one of the configured deploy targets when any are set.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "set_upstream".to_string(),
             PropertySchema {
@@ -171,7 +198,7 @@ impl DeployTool {
         DeployTool {
             definition: ToolDefinition {
                 name: "deploy".to_string(),
-                description: "Deployment operations: push code, create/manage PRs, monitor CI/CD workflows. Integrates with GitHub CLI for full deployment lifecycle management.".to_string(),
+                description: "Deployment operations: push code, create/manage PRs, monitor CI/CD workflows, and deploy to a target. Deployments are recorded: 'status' shows a target's current and last good deployment, 'watch' streams the run's logs until it finishes, and 'rollback' re-runs the last successful deployment. Integrates with GitHub CLI for full deployment lifecycle management.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -204,7 +231,8 @@ impl DeployTool {
         cmd.args(args)
             .current_dir(workspace)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
         // Prevent git from prompting for credentials interactively
         cmd.env("GIT_TERMINAL_PROMPT", "0");
@@ -259,25 +287,31 @@ impl DeployTool {
         Ok(stdout.to_string())
     }
 
-    /// Run gh CLI command
-    async fn run_gh(
-        &self,
-        args: &[&str],
-        workspace: &PathBuf,
-        context: &ToolContext,
-    ) -> Result<String, String> {
+    /// Build a gh CLI command with piped output and the GitHub token set
+    fn gh_command(args: &[&str], workspace: &PathBuf, context: &ToolContext) -> Command {
         let mut cmd = Command::new("gh");
         cmd.args(args)
             .current_dir(workspace)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
         // Set GitHub token if available
         if let Some(token) = context.get_api_key_by_id(ApiKeyId::GithubToken) {
             cmd.env("GH_TOKEN", token);
         }
 
-        let output = cmd
+        cmd
+    }
+
+    /// Run gh CLI command
+    async fn run_gh(
+        &self,
+        args: &[&str],
+        workspace: &PathBuf,
+        context: &ToolContext,
+    ) -> Result<String, String> {
+        let output = Self::gh_command(args, workspace, context)
             .output()
             .await
             .map_err(|e| format!("Failed to execute gh CLI: {}. Is GitHub CLI installed?", e))?;
@@ -312,6 +346,138 @@ impl DeployTool {
         let output = self.run_git(&["status", "--porcelain"], workspace, context).await?;
         Ok(!output.is_empty())
     }
+
+    /// Find the run a `gh workflow run` dispatched at `dispatched_at`. The
+    /// dispatch doesn't return the run, so poll for the newest
+    /// workflow_dispatch run of the workflow on that ref created since.
+    async fn find_dispatched_run(
+        &self,
+        workflow: &str,
+        git_ref: &str,
+        dispatched_at: DateTime<Utc>,
+        workspace: &PathBuf,
+        context: &ToolContext,
+    ) -> Option<GhRun> {
+        let args = [
+            "run", "list", "--workflow", workflow, "--branch", git_ref, "--event", "workflow_dispatch",
+            "--limit", "1", "--json", "databaseId,headSha,status,conclusion,createdAt",
+        ];
+        // Run timestamps have second precision
+        let since = dispatched_at - chrono::Duration::seconds(5);
+
+        for _ in 0..5 {
+            tokio::time::sleep(Duration::from_secs(3)).await;
+            let Ok(output) = self.run_gh(&args, workspace, context).await else {
+                continue;
+            };
+            let runs: Vec<GhRun> = serde_json::from_str(&output).unwrap_or_default();
+            if let Some(run) = runs.into_iter().find(|r| r.created_at.is_some_and(|t| t >= since)) {
+                return Some(run);
+            }
+        }
+        None
+    }
+
+    /// Bring an unfinished deployment up to date with its workflow run
+    async fn refresh_deployment(
+        &self,
+        deployment: Deployment,
+        db: &Database,
+        workspace: &PathBuf,
+        context: &ToolContext,
+    ) -> Deployment {
+        let Some(run_id) = deployment.run_id else {
+            return deployment;
+        };
+        if deployment.status.is_final() {
+            return deployment;
+        }
+
+        let run_id_str = run_id.to_string();
+        let run: GhRun = match self
+            .run_gh(&["run", "view", &run_id_str, "--json", "status,conclusion,headSha"], workspace, context)
+            .await
+            .map(|output| serde_json::from_str::<GhRun>(&output))
+        {
+            Ok(Ok(run)) => run,
+            Ok(Err(e)) => {
+                log::warn!("[DEPLOY] Unreadable status for run {}: {}", run_id, e);
+                return deployment;
+            }
+            Err(e) => {
+                log::warn!("[DEPLOY] Failed to check run {}: {}", run_id, e);
+                return deployment;
+            }
+        };
+
+        let status = run.deployment_status();
+        // A rollback re-runs an old run, so keep the commit it was recorded with
+        let commit_sha = deployment.commit_sha.clone().or(run.head_sha);
+        if let Err(e) = db.update_deployment_run(deployment.id, None, commit_sha.as_deref(), status) {
+            log::warn!("[DEPLOY] Failed to update deployment {}: {}", deployment.id, e);
+        }
+        Deployment { status, commit_sha, ..deployment }
+    }
+
+    /// Follow a deployment's run with `gh run watch` until it finishes,
+    /// broadcasting each line as a deploy.log event. Returns the last lines.
+    async fn watch_run(
+        &self,
+        deployment: &Deployment,
+        run_id: i64,
+        workspace: &PathBuf,
+        context: &ToolContext,
+    ) -> Result<Vec<String>, String> {
+        let run_id_str = run_id.to_string();
+        let mut cmd = Self::gh_command(
+            &["run", "watch", &run_id_str, "--exit-status", "--interval", "5"],
+            workspace,
+            context,
+        );
+        cmd.stderr(Stdio::null());
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to execute gh CLI: {}. Is GitHub CLI installed?", e))?;
+        let stdout = child.stdout.take().ok_or("Failed to read gh run watch output")?;
+
+        let broadcast = context.broadcaster.clone().zip(context.channel_id);
+        let mut tail: Vec<String> = Vec::new();
+        let mut lines = BufReader::new(stdout).lines();
+
+        let follow = async {
+            while let Ok(Some(line)) = lines.next_line().await {
+                let line = line.trim_end().to_string();
+                // gh redraws the whole summary every interval; skip unchanged lines
+                if line.is_empty() || tail.last() == Some(&line) {
+                    continue;
+                }
+                if let Some((ref broadcaster, channel_id)) = broadcast {
+                    broadcaster.broadcast(GatewayEvent::deploy_log(
+                        channel_id,
+                        deployment.id,
+                        &deployment.target,
+                        &line,
+                    ));
+                }
+                tail.push(line);
+                if tail.len() > WATCH_RESULT_LINES {
+                    tail.remove(0);
+                }
+            }
+            let _ = child.wait().await;
+        };
+
+        if tokio::time::timeout(Duration::from_secs(DEPLOY_WATCH_TIMEOUT_SECS), follow)
+            .await
+            .is_err()
+        {
+            tail.push(format!(
+                "Stopped watching after {} seconds; the run is still going.",
+                DEPLOY_WATCH_TIMEOUT_SECS
+            ));
+        }
+        Ok(tail)
+    }
 }
 
 impl Default for DeployTool {
@@ -330,18 +496,91 @@ struct DeployParams {
     body: Option<String>,
     pr_number: Option<i64>,
     workflow_name: Option<String>,
+    target: Option<String>,
     set_upstream: Option<bool>,
     force: Option<bool>,
     draft: Option<bool>,
     auto_merge: Option<bool>,
 }
 
+/// A workflow run as reported by `gh run list/view --json`
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhRun {
+    database_id: Option<i64>,
+    head_sha: Option<String>,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    conclusion: String,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl GhRun {
+    /// Deployment status for the run's GitHub status and conclusion
+    fn deployment_status(&self) -> DeploymentStatus {
+        match (self.status.as_str(), self.conclusion.as_str()) {
+            ("completed", "success") => DeploymentStatus::Succeeded,
+            ("completed", _) => DeploymentStatus::Failed,
+            _ => DeploymentStatus::Running,
+        }
+    }
+}
+
+/// Resolve the deployment target and the workflow that deploys it.
+///
+/// With targets configured (`STARK_DEPLOY_TARGETS`), `target` is required and
+/// must be one of them; a `workflow_name` that disagrees with its workflow is
+/// rejected. Without them, `workflow_name` is required and names the target
+/// unless `target` is given.
+fn resolve_target(
+    target: Option<&str>,
+    workflow_name: Option<&str>,
+    targets: &HashMap<String, String>,
+) -> Result<(String, String), String> {
+    let target = target.map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty());
+
+    if targets.is_empty() {
+        let workflow = workflow_name
+            .ok_or("workflow_name is required when no deploy targets are configured")?;
+        return Ok((target.unwrap_or_else(|| workflow.to_lowercase()), workflow.to_string()));
+    }
+
+    let mut configured: Vec<&str> = targets.keys().map(String::as_str).collect();
+    configured.sort_unstable();
+
+    let target = target.ok_or_else(|| {
+        format!("target is required. Configured targets: {}", configured.join(", "))
+    })?;
+    let workflow = targets.get(&target).ok_or_else(|| {
+        format!(
+            "Unknown deploy target '{}'. Configured targets: {}",
+            target,
+            configured.join(", ")
+        )
+    })?;
+    if let Some(requested) = workflow_name {
+        if requested != workflow {
+            return Err(format!(
+                "Target '{}' deploys with workflow '{}', not '{}'",
+                target, workflow, requested
+            ));
+        }
+    }
+    Ok((target, workflow.clone()))
+}
+
 #[async_trait]
 impl Tool for DeployTool {
     fn definition(&self) -> ToolDefinition {
         self.definition.clone()
     }
 
+    /// `watch` follows a workflow run for up to DEPLOY_WATCH_TIMEOUT_SECS
+    fn timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(DEPLOY_WATCH_TIMEOUT_SECS + 120))
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: DeployParams = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -521,9 +760,13 @@ impl Tool for DeployTool {
             }
 
             "trigger_deploy" => {
-                let workflow = match &params.workflow_name {
-                    Some(w) => w.clone(),
-                    None => return ToolResult::error("workflow_name is required for trigger_deploy"),
+                let (target, workflow) = match resolve_target(
+                    params.target.as_deref(),
+                    params.workflow_name.as_deref(),
+                    &crate::config::deploy_targets(),
+                ) {
+                    Ok(t) => t,
+                    Err(e) => return ToolResult::error(e),
                 };
 
                 let branch = match &params.branch {
@@ -534,20 +777,192 @@ impl Tool for DeployTool {
                     },
                 };
 
-                match self.run_gh(
+                let deployment_id = context.database.as_ref().and_then(|db| {
+                    db.record_deployment(&target, &workflow, &branch, None, context.channel_id)
+                        .map_err(|e| log::warn!("[DEPLOY] Failed to record deployment of '{}': {}", target, e))
+                        .ok()
+                });
+
+                let dispatched_at = Utc::now();
+                if let Err(e) = self.run_gh(
                     &["workflow", "run", &workflow, "--ref", &branch],
                     &workspace,
                     context
                 ).await {
-                    Ok(output) => {
-                        let result = if output.is_empty() {
-                            format!("Triggered workflow '{}' on branch '{}'", workflow, branch)
+                    if let (Some(db), Some(id)) = (&context.database, deployment_id) {
+                        let _ = db.update_deployment_run(id, None, None, DeploymentStatus::Failed);
+                    }
+                    return ToolResult::error(e);
+                }
+
+                let run = self
+                    .find_dispatched_run(&workflow, &branch, dispatched_at, &workspace, context)
+                    .await;
+                if let (Some(db), Some(id), Some(run)) = (&context.database, deployment_id, &run) {
+                    if let Err(e) = db.update_deployment_run(id, run.database_id, run.head_sha.as_deref(), run.deployment_status()) {
+                        log::warn!("[DEPLOY] Failed to update deployment {}: {}", id, e);
+                    }
+                }
+
+                let mut result = format!(
+                    "Triggered workflow '{}' on branch '{}' to deploy '{}'",
+                    workflow, branch, target
+                );
+                match run.as_ref().and_then(|r| r.database_id) {
+                    Some(run_id) => result.push_str(&format!(
+                        " (run {}). Use operation 'watch' to follow it.",
+                        run_id
+                    )),
+                    None => result.push_str(". The run hasn't started yet; check it with operation 'status'."),
+                }
+                ToolResult::success(result).with_metadata(json!({
+                    "deployment_id": deployment_id,
+                    "target": target,
+                    "workflow": workflow,
+                    "ref": branch,
+                    "run_id": run.as_ref().and_then(|r| r.database_id),
+                }))
+            }
+
+            "status" | "watch" | "rollback" => {
+                let db = match &context.database {
+                    Some(db) => db,
+                    None => return ToolResult::error("Database not available"),
+                };
+                let (target, _) = match resolve_target(
+                    params.target.as_deref(),
+                    params.workflow_name.as_deref(),
+                    &crate::config::deploy_targets(),
+                ) {
+                    Ok(t) => t,
+                    Err(e) => return ToolResult::error(e),
+                };
+
+                let current = match db.list_deployments(&target, 1) {
+                    Ok(mut recent) => recent.pop(),
+                    Err(e) => return ToolResult::error(format!("Failed to load deployments: {}", e)),
+                };
+                let current = match current {
+                    Some(d) => Some(self.refresh_deployment(d, db, &workspace, context).await),
+                    None => None,
+                };
+
+                match params.operation.as_str() {
+                    "status" => {
+                        let last_good = db
+                            .last_good_deployment(&target, None)
+                            .unwrap_or(None);
+                        let recent = db.list_deployments(&target, 5).unwrap_or_default();
+                        let report = json!({
+                            "target": target,
+                            "current": current,
+                            "last_good": last_good,
+                            "recent": recent,
+                        });
+                        ToolResult::success(serde_json::to_string_pretty(&report).unwrap_or_default())
+                            .with_metadata(report)
+                    }
+
+                    "watch" => {
+                        let Some(current) = current else {
+                            return ToolResult::error(format!("No deployments recorded for '{}'", target));
+                        };
+                        let Some(run_id) = current.run_id else {
+                            return ToolResult::error(format!(
+                                "Deployment {} of '{}' has no workflow run yet; check again with operation 'status'",
+                                current.id, target
+                            ));
+                        };
+
+                        let tail = if current.status.is_final() {
+                            Vec::new()
                         } else {
-                            output
+                            match self.watch_run(&current, run_id, &workspace, context).await {
+                                Ok(tail) => tail,
+                                Err(e) => return ToolResult::error(e),
+                            }
                         };
-                        ToolResult::success(result)
+                        let current = self.refresh_deployment(current, db, &workspace, context).await;
+
+                        let mut result = format!(
+                            "Deployment {} of '{}' (run {}): {}",
+                            current.id, target, run_id, current.status.as_str()
+                        );
+                        if !tail.is_empty() {
+                            result.push_str(&format!("\n\n{}", tail.join("\n")));
+                        }
+                        if current.status == DeploymentStatus::Failed {
+                            let run_id_str = run_id.to_string();
+                            if let Ok(log) = self.run_gh(&["run", "view", &run_id_str, "--log-failed"], &workspace, context).await {
+                                let lines: Vec<&str> = log.lines().collect();
+                                let from = lines.len().saturating_sub(WATCH_RESULT_LINES);
+                                result.push_str(&format!("\n\nFailed steps:\n{}", lines[from..].join("\n")));
+                            }
+                            result.push_str("\n\nUse operation 'rollback' to return to the last good deployment.");
+                        }
+                        ToolResult::success(result).with_metadata(json!({
+                            "deployment_id": current.id,
+                            "target": target,
+                            "status": current.status,
+                        }))
+                    }
+
+                    _ => {
+                        if let Some(current) = &current {
+                            if !current.status.is_final() {
+                                return ToolResult::error(format!(
+                                    "Deployment {} of '{}' is still {}. Wait for it to finish (operation 'watch') before rolling back.",
+                                    current.id, target, current.status.as_str()
+                                ));
+                            }
+                        }
+
+                        let current_sha = current.as_ref().and_then(|d| d.commit_sha.as_deref());
+                        let good = match db.last_good_deployment(&target, current_sha) {
+                            Ok(Some(d)) => d,
+                            Ok(None) => return ToolResult::error(format!(
+                                "No earlier successful deployment of '{}' to roll back to",
+                                target
+                            )),
+                            Err(e) => return ToolResult::error(format!("Failed to load deployments: {}", e)),
+                        };
+                        // last_good_deployment only returns deployments with both
+                        let (Some(run_id), Some(sha)) = (good.run_id, good.commit_sha.clone()) else {
+                            return ToolResult::error("Last good deployment has no run to re-run");
+                        };
+
+                        let id = match db.record_deployment(&target, &good.workflow, &good.git_ref, Some(good.id), context.channel_id) {
+                            Ok(id) => id,
+                            Err(e) => return ToolResult::error(format!("Failed to record rollback: {}", e)),
+                        };
+
+                        // Re-running the good run rebuilds its exact commit, which a
+                        // workflow_dispatch on the branch would not
+                        let run_id_str = run_id.to_string();
+                        if let Err(e) = self.run_gh(&["run", "rerun", &run_id_str], &workspace, context).await {
+                            let _ = db.update_deployment_run(id, Some(run_id), Some(&sha), DeploymentStatus::Failed);
+                            return ToolResult::error(e);
+                        }
+                        if let Err(e) = db.update_deployment_run(id, Some(run_id), Some(&sha), DeploymentStatus::Running) {
+                            log::warn!("[DEPLOY] Failed to update deployment {}: {}", id, e);
+                        }
+
+                        log::info!(
+                            "[DEPLOY] Rolling back '{}' to {} (deployment {}, run {})",
+                            target, sha, good.id, run_id
+                        );
+                        ToolResult::success(format!(
+                            "Rolling back '{}' to commit {} by re-running run {} of deployment {}. Use operation 'watch' to follow it.",
+                            target, sha, run_id, good.id
+                        ))
+                        .with_metadata(json!({
+                            "deployment_id": id,
+                            "target": target,
+                            "rollback_of": good.id,
+                            "commit_sha": sha,
+                            "run_id": run_id,
+                        }))
                     }
-                    Err(e) => ToolResult::error(e),
                 }
             }
 
@@ -583,7 +998,7 @@ impl Tool for DeployTool {
             }
 
             _ => ToolResult::error(format!(
-                "Unknown operation: {}. Supported: push, pull, fetch, create_pr, pr_status, workflow_status, trigger_deploy, merge_pr",
+                "Unknown operation: {}. Supported: push, pull, fetch, create_pr, pr_status, workflow_status, trigger_deploy, status, watch, rollback, merge_pr",
                 params.operation
             )),
         }
@@ -601,4 +1016,41 @@ mod tests {
         assert!(DeployTool::is_protected_branch("production"));
         assert!(!DeployTool::is_protected_branch("feature/test"));
     }
+
+    #[test]
+    fn test_resolve_target() {
+        let none = HashMap::new();
+        assert_eq!(
+            resolve_target(None, Some("deploy.yml"), &none),
+            Ok(("deploy.yml".to_string(), "deploy.yml".to_string()))
+        );
+        assert!(resolve_target(Some("prod"), None, &none).is_err());
+
+        let targets = HashMap::from([
+            ("prod".to_string(), "deploy-prod.yml".to_string()),
+            ("staging".to_string(), "deploy-staging.yml".to_string()),
+        ]);
+        assert_eq!(
+            resolve_target(Some(" Prod "), None, &targets),
+            Ok(("prod".to_string(), "deploy-prod.yml".to_string()))
+        );
+        let err = resolve_target(None, Some("deploy-prod.yml"), &targets).unwrap_err();
+        assert!(err.contains("prod, staging"));
+        assert!(resolve_target(Some("qa"), None, &targets).is_err());
+        // A workflow can't be swapped in under a configured target
+        assert!(resolve_target(Some("staging"), Some("deploy-prod.yml"), &targets).is_err());
+    }
+
+    #[test]
+    fn test_run_deployment_status() {
+        let run = |status: &str, conclusion: &str| GhRun {
+            status: status.to_string(),
+            conclusion: conclusion.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(run("queued", "").deployment_status(), DeploymentStatus::Running);
+        assert_eq!(run("in_progress", "").deployment_status(), DeploymentStatus::Running);
+        assert_eq!(run("completed", "success").deployment_status(), DeploymentStatus::Succeeded);
+        assert_eq!(run("completed", "cancelled").deployment_status(), DeploymentStatus::Failed);
+    }
 }
@@ -2,9 +2,11 @@
 //!
 //! Tools for code quality, deployment, and version control operations.
 
+mod commit_split;
 mod committer;
 mod deploy;
 mod pr_quality;
+mod quality_rules;
 
 pub use committer::CommitterTool;
 pub use deploy::DeployTool;
@@ -2,6 +2,7 @@ use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
+use super::quality_rules::{evaluate, ChangeSet, QualityConfig, RuleId, CONFIG_FILE};
 use async_trait::async_trait;
 use regex::Regex;
 use serde::Deserialize;
@@ -20,6 +21,10 @@ use tokio::process::Command;
 /// - Validates PR size (lines changed, files changed)
 /// - Checks for common code smells
 /// - Ensures tests exist for new code (optional)
+/// - Scans for secrets and checks conventional commit messages
+///
+/// `full_check` runs a weighted rule set that can be configured per repository
+/// (see `quality_rules`).
 pub struct PrQualityTool {
     definition: ToolDefinition,
 }
@@ -146,10 +151,21 @@ impl PrQualityTool {
             },
         );
 
+        properties.insert(
+            "config_path".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: format!("Rule config file for full_check, relative to the workspace (default: {} if present)", CONFIG_FILE),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
         PrQualityTool {
             definition: ToolDefinition {
                 name: "pr_quality".to_string(),
-                description: "Pre-PR quality checks: detects debug code, TODO/FIXME comments, validates PR size, and provides diff summary. Run before creating PRs to ensure code quality.".to_string(),
+                description: "Pre-PR quality checks: detects debug code, TODO/FIXME comments, validates PR size, and provides diff summary. full_check scores a configurable rule set (no_secrets, no_debug_prints, max_diff_size, requires_tests, conventional_commits, todo_references) from .pr_quality.json and returns per-rule results. Run before creating PRs to ensure code quality.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -171,6 +187,7 @@ impl PrQualityTool {
             .current_dir(workspace)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .output()
             .await
             .map_err(|e| format!("Failed to execute git: {}", e))?;
@@ -235,7 +252,7 @@ impl PrQualityTool {
     }
 
     /// Scan file for debug code
-    fn scan_for_debug(&self, content: &str, file_ext: &str) -> Vec<(String, usize)> {
+    pub(super) fn scan_for_debug(&self, content: &str, file_ext: &str) -> Vec<(String, usize)> {
         let mut findings = Vec::new();
 
         for (pattern_str, desc, extensions) in DEBUG_PATTERNS {
@@ -263,7 +280,7 @@ impl PrQualityTool {
     }
 
     /// Scan file for TODO/FIXME comments
-    fn scan_for_todos(&self, content: &str) -> Vec<(String, usize)> {
+    pub(super) fn scan_for_todos(&self, content: &str) -> Vec<(String, usize)> {
         let mut findings = Vec::new();
 
         for pattern_str in TODO_PATTERNS {
@@ -290,7 +307,7 @@ impl PrQualityTool {
     }
 
     /// Get file extension
-    fn get_extension(file: &str) -> String {
+    pub(super) fn get_extension(file: &str) -> String {
         PathBuf::from(file)
             .extension()
             .map(|s| s.to_string_lossy().to_lowercase())
@@ -320,6 +337,7 @@ struct PrQualityParams {
     strict: Option<bool>,
     ignore_todos: Option<bool>,
     max_lines: Option<usize>,
+    config_path: Option<String>,
 }
 
 #[async_trait]
@@ -560,95 +578,63 @@ impl Tool for PrQualityTool {
             }
 
             "full_check" | _ => {
-                // Run all checks
-                let mut results = Vec::new();
-                let mut has_errors = false;
-                let mut has_warnings = false;
+                let mut config = match QualityConfig::load(&workspace, params.config_path.as_deref()).await {
+                    Ok(c) => c,
+                    Err(e) => return ToolResult::error(e),
+                };
+                // Explicit parameters win over the config file
+                if let Some(max) = params.max_lines {
+                    config.rule_mut(RuleId::MaxDiffSize).max_lines = max;
+                }
+                if ignore_todos {
+                    config.rule_mut(RuleId::TodoReferences).enabled = false;
+                }
 
-                // 1. Size check
                 let (files_changed, insertions, deletions) = match self.get_diff_stats(&workspace, base_branch).await {
                     Ok(stats) => stats,
                     Err(_) => (files_to_check.len(), 0, 0),
                 };
 
-                let total_lines = insertions + deletions;
-                results.push(format!(
-                    "SIZE: {} files, +{} -{} ({} total lines)",
-                    files_changed, insertions, deletions, total_lines
-                ));
-
-                if files_changed > MAX_FILES_IN_PR || total_lines > max_lines {
-                    results.push(format!(
-                        "  WARNING: PR may be too large (max {} files, {} lines recommended)",
-                        MAX_FILES_IN_PR, max_lines
-                    ));
-                    has_warnings = true;
-                }
+                let commit_messages = self
+                    .run_git(&["log", "--format=%s", &format!("{}..HEAD", base_branch)], &workspace)
+                    .await
+                    .ok()
+                    .map(|out| out.lines().map(|l| l.to_string()).filter(|l| !l.is_empty()).collect::<Vec<_>>())
+                    .filter(|messages| !messages.is_empty());
 
-                // 2. Debug code scan
-                let mut debug_count = 0;
+                let mut files = Vec::new();
                 for file in &files_to_check {
-                    let file_path = workspace.join(file);
-                    if let Ok(content) = tokio::fs::read_to_string(&file_path).await {
-                        let ext = Self::get_extension(file);
-                        let findings = self.scan_for_debug(&content, &ext);
-                        debug_count += findings.len();
-                        for (desc, line) in findings.iter().take(3) {
-                            results.push(format!("  DEBUG: {}:{} - {}", file, line, desc));
-                        }
-                        if findings.len() > 3 {
-                            results.push(format!("  ... and {} more in {}", findings.len() - 3, file));
-                        }
-                    }
-                }
-
-                if debug_count > 0 {
-                    results.insert(results.len() - debug_count.min(4), format!("DEBUG CODE: {} issues found", debug_count));
-                    has_errors = true;
-                } else {
-                    results.push("DEBUG CODE: None found".to_string());
+                    let content = tokio::fs::read_to_string(workspace.join(file)).await.ok();
+                    files.push((file.clone(), content));
                 }
 
-                // 3. TODO scan
-                if !ignore_todos {
-                    let mut todo_count = 0;
-                    for file in &files_to_check {
-                        let file_path = workspace.join(file);
-                        if let Ok(content) = tokio::fs::read_to_string(&file_path).await {
-                            let findings = self.scan_for_todos(&content);
-                            todo_count += findings.len();
-                        }
-                    }
-
-                    if todo_count > 0 {
-                        results.push(format!("TODO/FIXME: {} without issue references", todo_count));
-                        has_warnings = true;
-                    } else {
-                        results.push("TODO/FIXME: All have issue references or none found".to_string());
-                    }
-                }
-
-                // Summary
-                let status = if has_errors {
-                    "FAILED"
-                } else if has_warnings {
-                    "PASSED WITH WARNINGS"
-                } else {
-                    "PASSED"
+                let changes = ChangeSet {
+                    files,
+                    files_changed,
+                    insertions,
+                    deletions,
+                    commit_messages,
                 };
+                let report = evaluate(self, &config, &changes);
 
                 let result_text = format!(
-                    "PR QUALITY CHECK: {}\n\n{}\n\nChecked {} files against {}",
-                    status,
-                    results.join("\n"),
+                    "PR QUALITY CHECK: {} (score {}/100, pass at {})\n\n{}\n\nChecked {} files against {} using {}",
+                    if report.passed { "PASSED" } else { "FAILED" },
+                    report.score,
+                    report.pass_score,
+                    report.render(),
                     files_to_check.len(),
-                    base_branch
+                    base_branch,
+                    config.source.as_deref().unwrap_or("default rules")
                 );
 
-                if has_errors && strict {
-                    ToolResult::error(result_text)
+                let mut metadata = report.to_json();
+                metadata["config"] = json!(config.source);
+
+                if !report.passed && strict {
+                    ToolResult::error(result_text).with_metadata(metadata)
                 } else {
-                    ToolResult::success(result_text)
+                    ToolResult::success(result_text).with_metadata(metadata)
                 }
             }
         }
@@ -0,0 +1,530 @@
+//! Configurable rule set for the PR quality check
+//!
+//! Rules can be tuned per repository with a `.pr_quality.json` file at the
+//! workspace root. Anything not set there keeps its default:
+//!
+//! ```json
+//! {
+//!   "pass_score": 70,
+//!   "rules": {
+//!     "max_diff_size": { "weight": 15, "max_lines": 1000, "max_files": 50 },
+//!     "requires_tests": { "enabled": false },
+//!     "no_secrets": { "weight": 30, "blocking": true }
+//!   }
+//! }
+//! ```
+//!
+//! Each enabled rule contributes its weight to the score when it passes. The
+//! check passes when the score reaches `pass_score` and no blocking rule failed.
+
+use super::committer::CommitterTool;
+use super::pr_quality::PrQualityTool;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Config file looked up at the workspace root
+pub(super) const CONFIG_FILE: &str = ".pr_quality.json";
+
+const DEFAULT_PASS_SCORE: u32 = 70;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum RuleId {
+    NoSecrets,
+    NoDebugPrints,
+    MaxDiffSize,
+    RequiresTests,
+    ConventionalCommits,
+    TodoReferences,
+}
+
+impl RuleId {
+    const ALL: [RuleId; 6] = [
+        RuleId::NoSecrets,
+        RuleId::NoDebugPrints,
+        RuleId::MaxDiffSize,
+        RuleId::RequiresTests,
+        RuleId::ConventionalCommits,
+        RuleId::TodoReferences,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleId::NoSecrets => "no_secrets",
+            RuleId::NoDebugPrints => "no_debug_prints",
+            RuleId::MaxDiffSize => "max_diff_size",
+            RuleId::RequiresTests => "requires_tests",
+            RuleId::ConventionalCommits => "conventional_commits",
+            RuleId::TodoReferences => "todo_references",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.as_str() == s)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Rule {
+    pub id: RuleId,
+    pub enabled: bool,
+    pub weight: u32,
+    /// A failing blocking rule fails the check regardless of score
+    pub blocking: bool,
+    /// Only used by max_diff_size
+    pub max_lines: usize,
+    pub max_files: usize,
+}
+
+impl Rule {
+    fn default_for(id: RuleId) -> Self {
+        let (weight, blocking) = match id {
+            RuleId::NoSecrets => (30, true),
+            RuleId::NoDebugPrints => (20, false),
+            RuleId::MaxDiffSize => (15, false),
+            RuleId::RequiresTests => (15, false),
+            RuleId::ConventionalCommits => (10, false),
+            RuleId::TodoReferences => (10, false),
+        };
+        Rule {
+            id,
+            enabled: true,
+            weight,
+            blocking,
+            max_lines: 1000,
+            max_files: 50,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleOverride {
+    enabled: Option<bool>,
+    weight: Option<u32>,
+    blocking: Option<bool>,
+    max_lines: Option<usize>,
+    max_files: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    pass_score: Option<u32>,
+    #[serde(default)]
+    rules: HashMap<String, RuleOverride>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct QualityConfig {
+    pub pass_score: u32,
+    pub rules: Vec<Rule>,
+    /// Config file the settings came from, if any
+    pub source: Option<String>,
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        QualityConfig {
+            pass_score: DEFAULT_PASS_SCORE,
+            rules: RuleId::ALL.into_iter().map(Rule::default_for).collect(),
+            source: None,
+        }
+    }
+}
+
+impl QualityConfig {
+    /// Apply a JSON config on top of the defaults
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let file: ConfigFile =
+            serde_json::from_str(json).map_err(|e| format!("Invalid PR quality config: {}", e))?;
+
+        let mut config = QualityConfig::default();
+        if let Some(score) = file.pass_score {
+            if score > 100 {
+                return Err("Invalid PR quality config: pass_score must be between 0 and 100".to_string());
+            }
+            config.pass_score = score;
+        }
+
+        for (name, over) in file.rules {
+            let id = RuleId::from_str(&name).ok_or_else(|| {
+                format!(
+                    "Invalid PR quality config: unknown rule '{}' (valid: {})",
+                    name,
+                    RuleId::ALL.map(|r| r.as_str()).join(", ")
+                )
+            })?;
+            let rule = config.rule_mut(id);
+            if let Some(v) = over.enabled {
+                rule.enabled = v;
+            }
+            if let Some(v) = over.weight {
+                rule.weight = v;
+            }
+            if let Some(v) = over.blocking {
+                rule.blocking = v;
+            }
+            if let Some(v) = over.max_lines {
+                rule.max_lines = v;
+            }
+            if let Some(v) = over.max_files {
+                rule.max_files = v;
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Load `config_path` (relative to the workspace), or `.pr_quality.json` if present
+    pub async fn load(workspace: &Path, config_path: Option<&str>) -> Result<Self, String> {
+        let relative = config_path.unwrap_or(CONFIG_FILE);
+        let path = workspace.join(relative);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(content) => {
+                let mut config = Self::parse(&content)?;
+                config.source = Some(relative.to_string());
+                Ok(config)
+            }
+            Err(_) if config_path.is_none() => Ok(Self::default()),
+            Err(e) => Err(format!("Cannot read PR quality config '{}': {}", relative, e)),
+        }
+    }
+
+    pub fn rule_mut(&mut self, id: RuleId) -> &mut Rule {
+        self.rules
+            .iter_mut()
+            .find(|r| r.id == id)
+            .expect("every rule has a config entry")
+    }
+}
+
+/// What the rules are evaluated against
+#[derive(Debug, Default)]
+pub(super) struct ChangeSet {
+    /// Changed files with their current content (None if deleted or binary)
+    pub files: Vec<(String, Option<String>)>,
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Subjects of the commits being checked, if they could be listed
+    pub commit_messages: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct RuleResult {
+    pub rule: Rule,
+    pub passed: bool,
+    pub summary: String,
+    pub findings: Vec<String>,
+}
+
+impl RuleResult {
+    fn to_json(&self) -> Value {
+        json!({
+            "rule": self.rule.id.as_str(),
+            "enabled": self.rule.enabled,
+            "passed": self.passed,
+            "weight": self.rule.weight,
+            "blocking": self.rule.blocking,
+            "summary": self.summary,
+            "findings": self.findings,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct QualityReport {
+    pub results: Vec<RuleResult>,
+    pub score: u32,
+    pub pass_score: u32,
+    pub passed: bool,
+}
+
+impl QualityReport {
+    /// Per-rule listing, failures first, with up to 10 findings each
+    pub fn render(&self) -> String {
+        let mut ordered: Vec<&RuleResult> = self.results.iter().collect();
+        ordered.sort_by_key(|r| (!r.rule.enabled, r.passed));
+
+        let mut out = Vec::new();
+        for result in ordered {
+            if !result.rule.enabled {
+                out.push(format!("[SKIP] {}: disabled", result.rule.id.as_str()));
+                continue;
+            }
+            out.push(format!(
+                "[{}] {} (weight {}{}): {}",
+                if result.passed { "PASS" } else { "FAIL" },
+                result.rule.id.as_str(),
+                result.rule.weight,
+                if result.rule.blocking { ", blocking" } else { "" },
+                result.summary
+            ));
+            for finding in result.findings.iter().take(10) {
+                out.push(format!("  {}", finding));
+            }
+            if result.findings.len() > 10 {
+                out.push(format!("  ... and {} more", result.findings.len() - 10));
+            }
+        }
+        out.join("\n")
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "passed": self.passed,
+            "score": self.score,
+            "pass_score": self.pass_score,
+            "rules": self.results.iter().map(RuleResult::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Whether a changed file counts as a test
+pub(super) fn is_test_file(path: &str, content: Option<&str>) -> bool {
+    let lower = path.to_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+    lower.starts_with("tests/")
+        || lower.contains("/tests/")
+        || lower.contains("__tests__/")
+        || file_name.starts_with("test_")
+        || file_name.contains("_test.")
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+        // Rust keeps unit tests inline
+        || (lower.ends_with(".rs")
+            && content.is_some_and(|c| c.contains("#[test]") || c.contains("#[tokio::test]")))
+}
+
+/// Whether a changed file is source code that should come with tests
+fn is_source_file(path: &str) -> bool {
+    let ext = path.rsplit('.').next().unwrap_or("").to_lowercase();
+    matches!(
+        ext.as_str(),
+        "rs" | "go" | "py" | "js" | "ts" | "jsx" | "tsx" | "java" | "c" | "cpp" | "rb" | "php" | "sol"
+    )
+}
+
+fn check_rule(tool: &PrQualityTool, rule: &Rule, changes: &ChangeSet) -> (bool, String, Vec<String>) {
+    let mut findings = Vec::new();
+    match rule.id {
+        RuleId::NoSecrets => {
+            for (file, content) in &changes.files {
+                if let Some(pattern) = CommitterTool::is_sensitive_file(file) {
+                    findings.push(format!("{} - sensitive file (matches {})", file, pattern));
+                }
+                if let Some(content) = content {
+                    // Never echo the secret itself
+                    for (kind, line) in CommitterTool::find_secrets(content) {
+                        findings.push(format!("{}:{} - {}", file, line, kind));
+                    }
+                }
+            }
+            let summary = if findings.is_empty() {
+                "No secrets found".to_string()
+            } else {
+                format!("{} potential secret(s) - remove them and rotate any real keys", findings.len())
+            };
+            (findings.is_empty(), summary, findings)
+        }
+        RuleId::NoDebugPrints => {
+            for (file, content) in &changes.files {
+                if let Some(content) = content {
+                    let ext = PrQualityTool::get_extension(file);
+                    for (desc, line) in tool.scan_for_debug(content, &ext) {
+                        findings.push(format!("{}:{} - {}", file, line, desc));
+                    }
+                }
+            }
+            let summary = if findings.is_empty() {
+                "No debug code found".to_string()
+            } else {
+                format!("{} debug statement(s)", findings.len())
+            };
+            (findings.is_empty(), summary, findings)
+        }
+        RuleId::MaxDiffSize => {
+            let total = changes.insertions + changes.deletions;
+            if changes.files_changed > rule.max_files {
+                findings.push(format!("{} files changed (max {})", changes.files_changed, rule.max_files));
+            }
+            if total > rule.max_lines {
+                findings.push(format!("{} lines changed (max {})", total, rule.max_lines));
+            }
+            let summary = format!(
+                "{} files, +{} -{} ({} total lines)",
+                changes.files_changed, changes.insertions, changes.deletions, total
+            );
+            (findings.is_empty(), summary, findings)
+        }
+        RuleId::RequiresTests => {
+            let tests: Vec<&str> = changes
+                .files
+                .iter()
+                .filter(|(f, c)| is_test_file(f, c.as_deref()))
+                .map(|(f, _)| f.as_str())
+                .collect();
+            let untested: Vec<&str> = changes
+                .files
+                .iter()
+                .filter(|(f, c)| c.is_some() && is_source_file(f) && !tests.contains(&f.as_str()))
+                .map(|(f, _)| f.as_str())
+                .collect();
+            if untested.is_empty() {
+                (true, "No source changes that need tests".to_string(), findings)
+            } else if !tests.is_empty() {
+                (true, format!("{} test file(s) changed", tests.len()), findings)
+            } else {
+                findings.extend(untested.iter().map(|f| format!("{} - source change without tests", f)));
+                (false, "Source files changed but no tests were added or updated".to_string(), findings)
+            }
+        }
+        RuleId::ConventionalCommits => match &changes.commit_messages {
+            None => (true, "No commits to check".to_string(), findings),
+            Some(messages) => {
+                for message in messages {
+                    if CommitterTool::validate_conventional_commit(message).is_err() {
+                        findings.push(format!("'{}' - expected type(scope): description", message));
+                    }
+                }
+                let summary = if findings.is_empty() {
+                    format!("{} commit(s) follow conventional commits", messages.len())
+                } else {
+                    format!("{} of {} commit(s) don't follow conventional commits", findings.len(), messages.len())
+                };
+                (findings.is_empty(), summary, findings)
+            }
+        },
+        RuleId::TodoReferences => {
+            for (file, content) in &changes.files {
+                if let Some(content) = content {
+                    for (text, line) in tool.scan_for_todos(content) {
+                        findings.push(format!("{}:{} - {}", file, line, text));
+                    }
+                }
+            }
+            let summary = if findings.is_empty() {
+                "All TODO/FIXME comments reference an issue".to_string()
+            } else {
+                format!("{} TODO/FIXME without an issue reference", findings.len())
+            };
+            (findings.is_empty(), summary, findings)
+        }
+    }
+}
+
+/// Run every rule and compute the weighted score
+pub(super) fn evaluate(tool: &PrQualityTool, config: &QualityConfig, changes: &ChangeSet) -> QualityReport {
+    let results: Vec<RuleResult> = config
+        .rules
+        .iter()
+        .map(|rule| {
+            if !rule.enabled {
+                return RuleResult {
+                    rule: rule.clone(),
+                    passed: true,
+                    summary: "disabled".to_string(),
+                    findings: Vec::new(),
+                };
+            }
+            let (passed, summary, findings) = check_rule(tool, rule, changes);
+            RuleResult { rule: rule.clone(), passed, summary, findings }
+        })
+        .collect();
+
+    let enabled = results.iter().filter(|r| r.rule.enabled);
+    let total: u32 = enabled.clone().map(|r| r.rule.weight).sum();
+    let earned: u32 = enabled.clone().filter(|r| r.passed).map(|r| r.rule.weight).sum();
+    let score = if total == 0 {
+        100
+    } else {
+        ((earned as f64 / total as f64) * 100.0).round() as u32
+    };
+    let blocked = results.iter().any(|r| r.rule.enabled && r.rule.blocking && !r.passed);
+
+    QualityReport {
+        passed: !blocked && score >= config.pass_score,
+        score,
+        pass_score: config.pass_score,
+        results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change_set(files: &[(&str, &str)]) -> ChangeSet {
+        ChangeSet {
+            files: files.iter().map(|(f, c)| (f.to_string(), Some(c.to_string()))).collect(),
+            files_changed: files.len(),
+            insertions: 10,
+            deletions: 2,
+            commit_messages: Some(vec!["feat: add thing".to_string()]),
+        }
+    }
+
+    fn result(report: &QualityReport, id: RuleId) -> &RuleResult {
+        report.results.iter().find(|r| r.rule.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_config_overrides_and_validation() {
+        let config = QualityConfig::parse(
+            r#"{ "pass_score": 90, "rules": { "no_secrets": { "weight": 50 }, "requires_tests": { "enabled": false } } }"#,
+        )
+        .unwrap();
+        assert_eq!(config.pass_score, 90);
+        let secrets = config.rules.iter().find(|r| r.id == RuleId::NoSecrets).unwrap();
+        assert_eq!(secrets.weight, 50);
+        // Unset fields keep the rule's own defaults
+        assert!(secrets.blocking);
+        assert!(!config.rules.iter().find(|r| r.id == RuleId::RequiresTests).unwrap().enabled);
+
+        let err = QualityConfig::parse(r#"{ "rules": { "no_typos": {} } }"#).unwrap_err();
+        assert!(err.contains("unknown rule 'no_typos'"), "{}", err);
+        assert!(QualityConfig::parse(r#"{ "rules": { "no_secrets": { "wieght": 1 } } }"#).is_err());
+    }
+
+    #[test]
+    fn test_secrets_block_regardless_of_score() {
+        let tool = PrQualityTool::new();
+        let config = QualityConfig { pass_score: 0, ..Default::default() };
+        let key = "1f".repeat(32);
+        let changes = change_set(&[
+            ("src/wallet.rs", &format!("const PRIVATE_KEY: &str = \"0x{}\";\n#[test]\nfn t() {{}}\n", key)),
+        ]);
+
+        let report = evaluate(&tool, &config, &changes);
+        assert!(!report.passed);
+        let secrets = result(&report, RuleId::NoSecrets);
+        assert!(!secrets.passed);
+        assert_eq!(secrets.findings, vec!["src/wallet.rs:1 - Wallet private key"]);
+        // The key itself is never echoed back
+        assert!(!report.render().contains(&key));
+        assert!(result(&report, RuleId::RequiresTests).passed);
+    }
+
+    #[test]
+    fn test_weighted_score() {
+        let tool = PrQualityTool::new();
+        let config = QualityConfig::parse(r#"{ "rules": { "max_diff_size": { "max_lines": 5 } } }"#).unwrap();
+        let mut changes = change_set(&[("src/lib.rs", "fn f() {}\n")]);
+        changes.commit_messages = Some(vec!["Fixed stuff".to_string()]);
+
+        let report = evaluate(&tool, &config, &changes);
+        // Size (15), tests (15) and commits (10) fail: 60/100
+        assert!(!result(&report, RuleId::MaxDiffSize).passed);
+        assert!(!result(&report, RuleId::RequiresTests).passed);
+        assert!(!result(&report, RuleId::ConventionalCommits).passed);
+        assert_eq!(report.score, 60);
+        assert!(!report.passed);
+
+        let config = QualityConfig::parse(r#"{ "pass_score": 60 }"#).unwrap();
+        let report = evaluate(&tool, &config, &changes);
+        assert_eq!(report.score, 75);
+        assert!(report.passed);
+    }
+}
@@ -19,5 +19,5 @@ pub use manage_skills::ManageSkillsTool;
 pub use modify_soul::ModifySoulTool;
 pub use say_to_user::SayToUserTool;
 pub use set_agent_subtype::SetAgentSubtypeTool;
-pub use subagent::{SubagentStatusTool, SubagentTool};
+pub use subagent::{SubagentCollectTool, SubagentStatusTool, SubagentTool};
 pub use task_complete::TaskFullyCompletedTool;
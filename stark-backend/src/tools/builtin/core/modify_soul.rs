@@ -2,10 +2,14 @@ use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
+use crate::db::tables::personas::{Persona, UpdatePersonaRequest};
+use crate::db::Database;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Tool for the agent to modify its own soul document
 pub struct ModifySoulTool {
@@ -49,7 +53,7 @@ impl ModifySoulTool {
         ModifySoulTool {
             definition: ToolDefinition {
                 name: "modify_soul".to_string(),
-                description: "Modify your soul document (SOUL.md) to update your personality, add new truths, or refine your identity. Use this to evolve your understanding of yourself.".to_string(),
+                description: "Modify your soul document to update your personality, add new truths, or refine your identity. Edits the persona assigned to this channel if there is one, otherwise SOUL.md. Use this to evolve your understanding of yourself.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -74,10 +78,127 @@ struct ModifySoulParams {
     content: Option<String>,
 }
 
-/// Get the path to SOUL.md in the workspace
-/// The agent can only modify the workspace copy, not the original
-fn soul_path() -> std::path::PathBuf {
-    crate::config::soul_document_path()
+/// The soul document being modified: the channel's persona if it has one,
+/// otherwise the workspace SOUL.md (the agent never edits the original)
+enum SoulTarget {
+    Persona { db: Arc<Database>, persona: Persona },
+    File(PathBuf),
+}
+
+impl SoulTarget {
+    fn resolve(context: &ToolContext) -> Self {
+        if let (Some(db), Some(channel_id)) = (&context.database, context.channel_id) {
+            match db.get_channel_persona(channel_id) {
+                Ok(Some(persona)) => {
+                    return SoulTarget::Persona { db: db.clone(), persona };
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to load persona for channel {}: {}", channel_id, e),
+            }
+        }
+        SoulTarget::File(crate::config::soul_document_path())
+    }
+
+    fn label(&self) -> String {
+        match self {
+            SoulTarget::Persona { persona, .. } => format!("persona '{}'", persona.name),
+            SoulTarget::File(_) => "SOUL.md".to_string(),
+        }
+    }
+
+    fn metadata(&self) -> Value {
+        match self {
+            SoulTarget::Persona { persona, .. } => json!({ "persona": persona.name }),
+            SoulTarget::File(path) => json!({ "path": path.display().to_string() }),
+        }
+    }
+
+    async fn read(&self) -> Result<String, String> {
+        match self {
+            SoulTarget::Persona { persona, .. } => Ok(persona.content.clone()),
+            SoulTarget::File(path) => tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| format!("Failed to read SOUL.md: {}", e)),
+        }
+    }
+
+    async fn write(&self, content: &str) -> Result<(), String> {
+        match self {
+            SoulTarget::Persona { db, persona } => {
+                let request = UpdatePersonaRequest {
+                    name: None,
+                    description: None,
+                    content: Some(content.to_string()),
+                };
+                db.update_persona(persona.id, &request)
+                    .map(|_| ())
+                    .map_err(|e| format!("Failed to update persona '{}': {}", persona.name, e))
+            }
+            SoulTarget::File(path) => tokio::fs::write(path, content)
+                .await
+                .map_err(|e| format!("Failed to write SOUL.md: {}", e)),
+        }
+    }
+}
+
+/// Append content at the end with a blank line of separation
+fn append_content(current: &str, content: &str) -> String {
+    if current.ends_with('\n') {
+        format!("{}\n{}", current, content)
+    } else {
+        format!("{}\n\n{}", current, content)
+    }
+}
+
+/// Replace the body of a section, keeping its header. Returns None if the
+/// section isn't found.
+fn replace_section(current: &str, section: &str, content: &str) -> Option<String> {
+    // Normalize section header (support both "Core Truths" and "## Core Truths")
+    let section_header = if section.starts_with('#') {
+        section.to_string()
+    } else {
+        format!("## {}", section)
+    };
+
+    let mut new_lines: Vec<String> = Vec::new();
+    let mut in_target_section = false;
+    let mut found_section = false;
+
+    for line in current.lines() {
+        if line.starts_with("## ") || line.starts_with("# ") {
+            // Any header ends the section being replaced; its new content was
+            // already added
+            in_target_section = false;
+
+            // Check if this is our target section
+            let normalized = if line.starts_with("## ") {
+                line.to_string()
+            } else {
+                format!("## {}", line.trim_start_matches("# "))
+            };
+
+            if normalized.trim() == section_header.trim() ||
+               line.trim() == section_header.trim() ||
+               line.trim().trim_start_matches('#').trim() == section.trim() {
+                found_section = true;
+                in_target_section = true;
+                // Add the section header and new content
+                new_lines.push(line.to_string());
+                new_lines.push(String::new()); // Empty line after header
+                for content_line in content.lines() {
+                    new_lines.push(content_line.to_string());
+                }
+                new_lines.push(String::new()); // Empty line after content
+                continue;
+            }
+        }
+
+        if !in_target_section {
+            new_lines.push(line.to_string());
+        }
+    }
+
+    found_section.then(|| new_lines.join("\n"))
 }
 
 #[async_trait]
@@ -86,23 +207,24 @@ impl Tool for ModifySoulTool {
         self.definition.clone()
     }
 
-    async fn execute(&self, params: Value, _context: &ToolContext) -> ToolResult {
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: ModifySoulParams = match serde_json::from_value(params) {
             Ok(p) => p,
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
 
-        let path = soul_path();
+        let target = SoulTarget::resolve(context);
 
         match params.action.as_str() {
             "read" => {
                 // Read and return current soul content
-                match tokio::fs::read_to_string(&path).await {
-                    Ok(content) => ToolResult::success(content).with_metadata(json!({
-                        "action": "read",
-                        "path": path.display().to_string()
-                    })),
-                    Err(e) => ToolResult::error(format!("Failed to read SOUL.md: {}", e)),
+                match target.read().await {
+                    Ok(content) => {
+                        let mut metadata = target.metadata();
+                        metadata["action"] = json!("read");
+                        ToolResult::success(content).with_metadata(metadata)
+                    }
+                    Err(e) => ToolResult::error(e),
                 }
             }
             "append" => {
@@ -112,27 +234,21 @@ impl Tool for ModifySoulTool {
                     None => return ToolResult::error("Content is required for append action"),
                 };
 
-                let current = match tokio::fs::read_to_string(&path).await {
+                let current = match target.read().await {
                     Ok(c) => c,
-                    Err(e) => return ToolResult::error(format!("Failed to read SOUL.md: {}", e)),
-                };
-
-                // Append with proper spacing
-                let new_content = if current.ends_with('\n') {
-                    format!("{}\n{}", current, content)
-                } else {
-                    format!("{}\n\n{}", current, content)
+                    Err(e) => return ToolResult::error(e),
                 };
 
-                match tokio::fs::write(&path, &new_content).await {
+                match target.write(&append_content(&current, &content)).await {
                     Ok(_) => {
-                        log::info!("Soul document updated (append)");
-                        ToolResult::success("Successfully appended content to soul document").with_metadata(json!({
-                            "action": "append",
-                            "added_length": content.len()
-                        }))
+                        log::info!("Soul document updated (append, {})", target.label());
+                        let mut metadata = target.metadata();
+                        metadata["action"] = json!("append");
+                        metadata["added_length"] = json!(content.len());
+                        ToolResult::success(format!("Successfully appended content to {}", target.label()))
+                            .with_metadata(metadata)
                     }
-                    Err(e) => ToolResult::error(format!("Failed to write SOUL.md: {}", e)),
+                    Err(e) => ToolResult::error(e),
                 }
             }
             "replace_section" => {
@@ -146,78 +262,35 @@ impl Tool for ModifySoulTool {
                     None => return ToolResult::error("Content is required for replace_section action"),
                 };
 
-                let current = match tokio::fs::read_to_string(&path).await {
+                let current = match target.read().await {
                     Ok(c) => c,
-                    Err(e) => return ToolResult::error(format!("Failed to read SOUL.md: {}", e)),
+                    Err(e) => return ToolResult::error(e),
                 };
 
-                // Normalize section header (support both "Core Truths" and "## Core Truths")
-                let section_header = if section.starts_with('#') {
-                    section.clone()
-                } else {
-                    format!("## {}", section)
+                let new_content = match replace_section(&current, &section, &content) {
+                    Some(c) => c,
+                    None => return ToolResult::error(format!(
+                        "Section '{}' not found in {}",
+                        section,
+                        target.label()
+                    )),
                 };
 
-                // Find the section and replace it
-                let lines: Vec<&str> = current.lines().collect();
-                let mut new_lines: Vec<String> = Vec::new();
-                let mut in_target_section = false;
-                let mut found_section = false;
-                let mut section_replaced = false;
-
-                for line in lines {
-                    if line.starts_with("## ") || line.starts_with("# ") {
-                        if in_target_section {
-                            // We were in the target section, now we've hit the next section
-                            // The replacement content was already added
-                            in_target_section = false;
-                        }
-
-                        // Check if this is our target section
-                        let normalized = if line.starts_with("## ") {
-                            line.to_string()
-                        } else {
-                            format!("## {}", line.trim_start_matches("# "))
-                        };
-
-                        if normalized.trim() == section_header.trim() ||
-                           line.trim() == section_header.trim() ||
-                           line.trim().trim_start_matches('#').trim() == section.trim() {
-                            found_section = true;
-                            in_target_section = true;
-                            // Add the section header and new content
-                            new_lines.push(line.to_string());
-                            new_lines.push(String::new()); // Empty line after header
-                            for content_line in content.lines() {
-                                new_lines.push(content_line.to_string());
-                            }
-                            new_lines.push(String::new()); // Empty line after content
-                            section_replaced = true;
-                            continue;
-                        }
-                    }
-
-                    if !in_target_section {
-                        new_lines.push(line.to_string());
-                    }
-                }
-
-                if !found_section {
-                    return ToolResult::error(format!("Section '{}' not found in soul document", section));
-                }
-
-                let new_content = new_lines.join("\n");
-
-                match tokio::fs::write(&path, &new_content).await {
+                match target.write(&new_content).await {
                     Ok(_) => {
-                        log::info!("Soul document updated (replace_section: {})", section);
-                        ToolResult::success(format!("Successfully replaced section '{}' in soul document", section)).with_metadata(json!({
-                            "action": "replace_section",
-                            "section": section,
-                            "replaced": section_replaced
-                        }))
+                        log::info!("Soul document updated (replace_section: {}, {})", section, target.label());
+                        let mut metadata = target.metadata();
+                        metadata["action"] = json!("replace_section");
+                        metadata["section"] = json!(section);
+                        metadata["replaced"] = json!(true);
+                        ToolResult::success(format!(
+                            "Successfully replaced section '{}' in {}",
+                            section,
+                            target.label()
+                        ))
+                        .with_metadata(metadata)
                     }
-                    Err(e) => ToolResult::error(format!("Failed to write SOUL.md: {}", e)),
+                    Err(e) => ToolResult::error(e),
                 }
             }
             _ => ToolResult::error(format!("Unknown action: {}. Use 'read', 'append', or 'replace_section'", params.action)),
@@ -228,8 +301,6 @@ impl Tool for ModifySoulTool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::TempDir;
-    use tokio::fs;
 
     #[tokio::test]
     async fn test_read_soul() {
@@ -238,4 +309,42 @@ mod tests {
         // For now, just verify the tool can be created
         assert_eq!(tool.definition().name, "modify_soul");
     }
+
+    #[test]
+    fn test_replace_section() {
+        let soul = "# Soul\n\n## Core Truths\n\nold truth\n\n## Vibe\n\nchill";
+        let updated = replace_section(soul, "Core Truths", "new truth").unwrap();
+        assert!(updated.contains("new truth"));
+        assert!(!updated.contains("old truth"));
+        assert!(updated.contains("## Vibe\n\nchill"));
+        assert!(replace_section(soul, "Missing", "x").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_edits_channel_persona() {
+        use crate::db::tables::personas::CreatePersonaRequest;
+        use crate::models::ChannelSettingKey;
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let channel = db.create_channel("discord", "test", "token", None).unwrap();
+        db.create_persona(&CreatePersonaRequest {
+            name: "casual".to_string(),
+            description: None,
+            content: "## Vibe\n\nrelaxed".to_string(),
+        })
+        .unwrap();
+        db.set_channel_setting(channel.id, ChannelSettingKey::Persona.as_ref(), "casual").unwrap();
+
+        let context = ToolContext::new()
+            .with_channel(channel.id, "discord".to_string())
+            .with_database(db.clone());
+        let result = ModifySoulTool::new()
+            .execute(json!({ "action": "append", "content": "likes emoji" }), &context)
+            .await;
+        assert!(result.success, "{}", result.content);
+
+        let persona = db.get_persona_by_name("casual").unwrap().unwrap();
+        assert_eq!(persona.content, "## Vibe\n\nrelaxed\n\nlikes emoji");
+    }
 }
@@ -1,8 +1,9 @@
 //! Sub-agent tools for spawning and monitoring background agent instances
 //!
-//! This module provides two tools:
+//! This module provides three tools:
 //! - `subagent`: Spawn a new sub-agent to work on a task
 //! - `subagent_status`: Check the status of sub-agents
+//! - `subagent_collect`: Wait for several sub-agents and gather their results
 
 use crate::ai::multi_agent::{SubAgentContext, SubAgentManager, SubAgentStatus};
 use crate::tools::registry::Tool;
@@ -103,7 +104,7 @@ impl SubagentTool {
             "timeout".to_string(),
             PropertySchema {
                 schema_type: "integer".to_string(),
-                description: "Timeout in seconds for the subagent task (default: 300, max: 3600).".to_string(),
+                description: "Timeout in seconds for the subagent task (default: 300, max: 3600 unless configured otherwise).".to_string(),
                 default: Some(json!(300)),
                 items: None,
                 enum_values: None,
@@ -202,6 +203,11 @@ impl Tool for SubagentTool {
         self.definition.clone()
     }
 
+    /// Waiting on a subagent is bounded by its own timeout
+    fn timeout(&self) -> Option<std::time::Duration> {
+        Some(std::time::Duration::from_secs(crate::config::subagent_max_timeout_secs() + 30))
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: SubagentParams = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -216,7 +222,10 @@ impl Tool for SubagentTool {
             .unwrap_or_else(|| format!("task-{}", counter));
         let subagent_id = SubAgentManager::generate_id(&label);
 
-        let timeout_secs = params.timeout.unwrap_or(300).min(3600);
+        let timeout_secs = params
+            .timeout
+            .unwrap_or(300)
+            .min(crate::config::subagent_max_timeout_secs());
         let wait = params.wait.unwrap_or(false);
 
         log::info!(
@@ -242,6 +251,12 @@ impl Tool for SubagentTool {
                 let session_id = context.session_id.unwrap();
                 let channel_id = context.channel_id.unwrap();
 
+                if let Err(e) = manager.check_spawn_limits(session_id) {
+                    log::warn!("[SUBAGENT] Refusing to spawn '{}': {}", subagent_id, e);
+                    return ToolResult::error(e);
+                }
+                let timeout_secs = timeout_secs.min(manager.max_timeout_secs());
+
                 let subagent_context = SubAgentContext::new(
                     subagent_id.clone(),
                     session_id,
@@ -793,6 +808,216 @@ impl Tool for SubagentStatusTool {
     }
 }
 
+/// Tool for gathering the results of several subagents (fan-out / fan-in)
+pub struct SubagentCollectTool {
+    definition: ToolDefinition,
+}
+
+impl SubagentCollectTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "ids".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "Subagent IDs to collect results from.".to_string(),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Subagent ID".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "label".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Collect every subagent spawned by this session with this label. Used when ids is omitted.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "timeout".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "Seconds to wait for the subagents to finish (default: 300, 0 = don't wait). Subagents still running when it expires are reported as pending.".to_string(),
+                default: Some(json!(300)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        SubagentCollectTool {
+            definition: ToolDefinition {
+                name: "subagent_collect".to_string(),
+                description: "Wait for a set of subagents (by IDs or label) to finish and return all their results together, with each subagent's success or error. Subagents still running at the timeout are returned as pending.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::System,
+            },
+        }
+    }
+
+    /// Current state of the requested subagents
+    fn snapshot(
+        manager: &SubAgentManager,
+        params: &SubagentCollectParams,
+        context: &ToolContext,
+    ) -> Result<Vec<SubAgentContext>, String> {
+        if let Some(ids) = params.ids.as_ref().filter(|ids| !ids.is_empty()) {
+            let mut agents = Vec::with_capacity(ids.len());
+            for id in ids {
+                match manager.get_status(id)? {
+                    Some(agent) => agents.push(agent),
+                    None => return Err(format!("Subagent '{}' not found", id)),
+                }
+            }
+            return Ok(agents);
+        }
+
+        let label = params
+            .label
+            .as_deref()
+            .ok_or_else(|| "Provide either 'ids' or 'label'".to_string())?;
+        let mut agents: Vec<SubAgentContext> = manager
+            .list_by_channel(context.channel_id.unwrap_or(0))?
+            .into_iter()
+            .filter(|agent| agent.label == label)
+            .filter(|agent| context.session_id.map_or(true, |id| agent.parent_session_id == id))
+            .collect();
+        if agents.is_empty() {
+            return Err(format!("No subagents found with label '{}'", label));
+        }
+        // list_by_channel is newest first; report in spawn order
+        agents.reverse();
+        Ok(agents)
+    }
+
+    /// Combined report of the subagents' results
+    fn format_results(agents: &[SubAgentContext]) -> (String, Value) {
+        let completed = agents.iter().filter(|a| a.status == SubAgentStatus::Completed).count();
+        let pending = agents.iter().filter(|a| !a.status.is_terminal()).count();
+        let failed = agents.len() - completed - pending;
+
+        let mut output = format!(
+            "## Subagent Results ({} total: {} completed, {} failed, {} pending)\n",
+            agents.len(),
+            completed,
+            failed,
+            pending
+        );
+        let mut results = Vec::with_capacity(agents.len());
+
+        for agent in agents {
+            output.push_str(&format!("\n### {} ({}) - {}\n", agent.id, agent.label, agent.status));
+            match agent.status {
+                SubAgentStatus::Completed => {
+                    output.push_str(agent.result.as_deref().unwrap_or("Task completed"));
+                    output.push('\n');
+                }
+                status if status.is_terminal() => {
+                    output.push_str(&format!(
+                        "Error: {}\n",
+                        agent.error.as_deref().unwrap_or("No error message")
+                    ));
+                }
+                _ => output.push_str("[PENDING] Still running - no result yet.\n"),
+            }
+
+            results.push(json!({
+                "id": agent.id,
+                "label": agent.label,
+                "status": agent.status.to_string(),
+                "success": agent.status == SubAgentStatus::Completed,
+                "pending": !agent.status.is_terminal(),
+                "result": agent.result,
+                "error": agent.error,
+            }));
+        }
+
+        let metadata = json!({
+            "total": agents.len(),
+            "completed": completed,
+            "failed": failed,
+            "pending": pending,
+            "all_finished": pending == 0,
+            "results": results,
+        });
+        (output, metadata)
+    }
+}
+
+impl Default for SubagentCollectTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SubagentCollectParams {
+    ids: Option<Vec<String>>,
+    label: Option<String>,
+    timeout: Option<u64>,
+}
+
+#[async_trait]
+impl Tool for SubagentCollectTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    /// Waiting is bounded by the requested timeout, itself capped at the subagent maximum
+    fn timeout(&self) -> Option<std::time::Duration> {
+        Some(std::time::Duration::from_secs(crate::config::subagent_max_timeout_secs() + 30))
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: SubagentCollectParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let manager = match &context.subagent_manager {
+            Some(manager) => manager,
+            None => return ToolResult::error("Subagent manager not available in this context"),
+        };
+
+        let timeout_secs = params
+            .timeout
+            .unwrap_or(300)
+            .min(manager.max_timeout_secs());
+        let start = std::time::Instant::now();
+        let timeout_duration = std::time::Duration::from_secs(timeout_secs);
+
+        loop {
+            let agents = match Self::snapshot(manager, &params, context) {
+                Ok(agents) => agents,
+                Err(e) => return ToolResult::error(e),
+            };
+
+            let finished = agents.iter().all(|a| a.status.is_terminal());
+            if finished || start.elapsed() >= timeout_duration {
+                let (output, metadata) = Self::format_results(&agents);
+                return ToolResult::success(output).with_metadata(metadata);
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -817,6 +1042,31 @@ mod tests {
         assert!(def.input_schema.required.is_empty());
     }
 
+    fn agent(id: &str) -> SubAgentContext {
+        SubAgentContext::new(id.into(), 1, 1, "research".into(), "task".into(), 300)
+    }
+
+    #[test]
+    fn test_collect_reports_partial_results() {
+        let mut done = agent("subagent-research-1");
+        done.mark_completed("answer A".into());
+        let mut failed = agent("subagent-research-2");
+        failed.mark_failed("rate limited".into());
+        let mut running = agent("subagent-research-3");
+        running.mark_running(2);
+
+        let (output, metadata) = SubagentCollectTool::format_results(&[done, failed, running]);
+
+        assert!(output.contains("3 total: 1 completed, 1 failed, 1 pending"));
+        assert!(output.contains("answer A"));
+        assert!(output.contains("Error: rate limited"));
+        assert!(output.contains("[PENDING]"));
+        assert_eq!(metadata["all_finished"], false);
+        assert_eq!(metadata["results"][0]["success"], true);
+        assert_eq!(metadata["results"][1]["error"], "rate limited");
+        assert_eq!(metadata["results"][2]["pending"], true);
+    }
+
     #[tokio::test]
     async fn test_spawn_subagent_legacy() {
         let tool = SubagentTool::new();
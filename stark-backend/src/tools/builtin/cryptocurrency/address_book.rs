@@ -0,0 +1,333 @@
+//! Address Book tool for named transfer recipients
+//!
+//! Stores "alice" -> 0x... mappings, globally or for the current identity.
+//! send_eth accepts a book name wherever it takes a recipient; a transfer to
+//! an entry that has never been used needs explicit confirmation.
+
+use crate::db::tables::address_book::AddressBookEntry;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use ethers::types::Address;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Look up a recipient field in the address book. Raw `0x` addresses are
+/// never looked up.
+pub(super) fn lookup_recipient(value: &str, context: &ToolContext) -> Option<AddressBookEntry> {
+    let value = value.trim();
+    if value.is_empty() || value.starts_with("0x") {
+        return None;
+    }
+    let db = context.database.as_ref()?;
+    match db.resolve_address_book(value, context.identity_id.as_deref()) {
+        Ok(entry) => entry,
+        Err(e) => {
+            log::warn!("[address_book] Lookup of '{}' failed: {}", value, e);
+            None
+        }
+    }
+}
+
+/// Address Book tool
+pub struct AddressBookTool {
+    definition: ToolDefinition,
+}
+
+impl AddressBookTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "add: save a named recipient | list: show saved recipients | resolve: look up a name | remove: delete a name".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec![
+                    "add".to_string(),
+                    "list".to_string(),
+                    "resolve".to_string(),
+                    "remove".to_string(),
+                ]),
+            },
+        );
+
+        properties.insert(
+            "name".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Recipient name, e.g. 'alice'. Required for add, resolve and remove.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "address".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "0x address to save. Required for add.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "notes".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Optional note for the entry (add only)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "global".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Save/remove the entry for everyone instead of only the current user (add and remove)".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "cache_as".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register to store the resolved address in (e.g. 'send_to'). Only used with resolve.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        AddressBookTool {
+            definition: ToolDefinition {
+                name: "address_book".to_string(),
+                description: "Manage named transfer recipients (e.g. 'alice' -> 0x...). Saved names can be used directly as the 'send_to' recipient for send_eth.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["action".to_string()],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+}
+
+impl Default for AddressBookTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AddressBookParams {
+    action: String,
+    name: Option<String>,
+    address: Option<String>,
+    notes: Option<String>,
+    #[serde(default)]
+    global: bool,
+    cache_as: Option<String>,
+}
+
+fn entry_json(entry: &AddressBookEntry) -> Value {
+    json!({
+        "name": entry.name,
+        "address": entry.address,
+        "scope": if entry.identity_id.is_some() { "identity" } else { "global" },
+        "notes": entry.notes,
+        "use_count": entry.use_count,
+    })
+}
+
+/// A name must not be mistaken for an address or an ENS name
+fn validate_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("'name' is required".to_string());
+    }
+    if name.starts_with("0x") || name.contains('.') || name.contains(char::is_whitespace) {
+        return Err(format!(
+            "Invalid name '{}': names can't start with 0x or contain dots or spaces",
+            name
+        ));
+    }
+    Ok(name)
+}
+
+#[async_trait]
+impl Tool for AddressBookTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: AddressBookParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => return ToolResult::error("Database not available"),
+        };
+        let identity_id = context.identity_id.as_deref();
+        let scope = if params.global { None } else { identity_id };
+
+        match params.action.as_str() {
+            "add" => {
+                let name = match validate_name(params.name.as_deref().unwrap_or("")) {
+                    Ok(n) => n,
+                    Err(e) => return ToolResult::error(e),
+                };
+                let address = match params.address.as_deref().map(str::trim) {
+                    Some(a) => match a.parse::<Address>() {
+                        Ok(addr) => ethers::utils::to_checksum(&addr, None),
+                        Err(_) => return ToolResult::error(format!("Invalid address: {}", a)),
+                    },
+                    None => return ToolResult::error("'address' is required for add"),
+                };
+
+                match db.upsert_address_book_entry(name, &address, scope, params.notes.as_deref()) {
+                    Ok(entry) => ToolResult::success(format!(
+                        "Saved '{}' -> {} ({})",
+                        entry.name,
+                        entry.address,
+                        if entry.identity_id.is_some() { "this user" } else { "global" }
+                    ))
+                    .with_metadata(entry_json(&entry)),
+                    Err(e) => ToolResult::error(format!("Failed to save entry: {}", e)),
+                }
+            }
+            "list" => match db.list_address_book(identity_id) {
+                Ok(entries) if entries.is_empty() => ToolResult::success("Address book is empty."),
+                Ok(entries) => {
+                    let lines: Vec<String> = entries
+                        .iter()
+                        .map(|e| {
+                            let mut line = format!("{} -> {}", e.name, e.address);
+                            if e.identity_id.is_none() {
+                                line.push_str(" [global]");
+                            }
+                            if e.use_count == 0 {
+                                line.push_str(" [never used]");
+                            }
+                            if let Some(ref notes) = e.notes {
+                                line.push_str(&format!(" - {}", notes));
+                            }
+                            line
+                        })
+                        .collect();
+                    ToolResult::success(lines.join("\n")).with_metadata(json!({
+                        "entries": entries.iter().map(entry_json).collect::<Vec<_>>(),
+                    }))
+                }
+                Err(e) => ToolResult::error(format!("Failed to list address book: {}", e)),
+            },
+            "resolve" => {
+                let name = params.name.as_deref().unwrap_or("").trim();
+                if name.is_empty() {
+                    return ToolResult::error("'name' is required for resolve");
+                }
+                match db.resolve_address_book(name, identity_id) {
+                    Ok(Some(entry)) => {
+                        let mut msg = format!("{} -> {}", entry.name, entry.address);
+                        if let Some(ref reg) = params.cache_as {
+                            context.set_register(reg, json!(&entry.address), "address_book");
+                            msg.push_str(&format!("\nCached in register: '{}'", reg));
+                        }
+                        ToolResult::success(msg).with_metadata(entry_json(&entry))
+                    }
+                    Ok(None) => ToolResult::error(format!("'{}' is not in the address book", name)),
+                    Err(e) => ToolResult::error(format!("Failed to look up '{}': {}", name, e)),
+                }
+            }
+            "remove" => {
+                let name = params.name.as_deref().unwrap_or("").trim();
+                if name.is_empty() {
+                    return ToolResult::error("'name' is required for remove");
+                }
+                match db.remove_address_book_entry(name, scope) {
+                    Ok(true) => ToolResult::success(format!("Removed '{}'", name)),
+                    Ok(false) => ToolResult::error(format!(
+                        "No {} entry named '{}'",
+                        if scope.is_some() { "per-user" } else { "global" },
+                        name
+                    )),
+                    Err(e) => ToolResult::error(format!("Failed to remove '{}': {}", name, e)),
+                }
+            }
+            other => ToolResult::error(format!(
+                "Unknown action '{}'. Use add, list, resolve or remove.",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use std::sync::Arc;
+
+    const ALICE: &str = "0x52908400098527886E0F7030069857D2E4169EE7";
+
+    fn context(db: Arc<Database>, identity: &str) -> ToolContext {
+        ToolContext::new()
+            .with_database(db)
+            .with_identity(identity.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_identity_entry_shadows_global() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let tool = AddressBookTool::new();
+
+        let other = "0x8617E340B3D01FA5F11F306F4090FD50E238070D";
+        let result = tool
+            .execute(json!({"action": "add", "name": "alice", "address": other, "global": true}), &context(db.clone(), "u1"))
+            .await;
+        assert!(result.success, "{:?}", result.error);
+        let result = tool
+            .execute(json!({"action": "add", "name": "Alice", "address": ALICE}), &context(db.clone(), "u1"))
+            .await;
+        assert!(result.success, "{:?}", result.error);
+
+        let entry = lookup_recipient("ALICE", &context(db.clone(), "u1")).unwrap();
+        assert_eq!(entry.address, ALICE);
+        assert_eq!(entry.use_count, 0);
+        let entry = lookup_recipient("alice", &context(db.clone(), "u2")).unwrap();
+        assert_eq!(entry.address, other);
+        assert!(lookup_recipient(ALICE, &context(db, "u1")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_ambiguous_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let tool = AddressBookTool::new();
+
+        for name in ["vitalik.eth", "0xabc", ""] {
+            let result = tool
+                .execute(json!({"action": "add", "name": name, "address": ALICE}), &context(db.clone(), "u1"))
+                .await;
+            assert!(!result.success, "name '{}' should be rejected", name);
+        }
+    }
+}
@@ -0,0 +1,522 @@
+//! Approve and Swap tool - queue a swap together with the approval it needs
+//!
+//! Reads the 0x quote cached by `x402_fetch` (preset swap_quote), checks the
+//! sell token's current allowance for the quote's spender, and only if it's
+//! insufficient queues an `approve` first. The approval and the swap are
+//! signed with consecutive nonces, so broadcasting them in order just works.
+//!
+//! The swap's gas limit comes from the quote: it can't be estimated while
+//! the approval is still unmined.
+
+use super::network_lookup::resolve_network;
+use super::transfer_limits;
+use super::web3_function_call::{SignedTxForQueue, Web3FunctionCallTool};
+use super::web3_tx::parse_u256;
+use crate::tools::registry::Tool;
+use crate::tools::rpc_config::resolve_rpc_from_context;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tx_queue::{QueuedTransaction, TxQueueManager};
+use crate::wallet::{WalletSigner, WALLET_PARAM_DESCRIPTION};
+use async_trait::async_trait;
+use ethers::abi::Token;
+use ethers::prelude::*;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Placeholder address used by 0x and the token config for the native currency
+const NATIVE_TOKEN_ADDRESS: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+/// Approve and Swap tool
+pub struct ApproveAndSwapTool {
+    definition: ToolDefinition,
+}
+
+impl ApproveAndSwapTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "quote_register".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register holding the swap quote from x402_fetch (preset swap_quote).".to_string(),
+                default: Some(json!("swap_quote")),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "sell_token_register".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register holding the sell token address (set by token_lookup with cache_as: sell_token).".to_string(),
+                default: Some(json!("sell_token")),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "sell_amount_register".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register holding the raw sell amount the quote was requested for.".to_string(),
+                default: Some(json!("sell_amount")),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "approval".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'exact' approves only the sell amount (default); 'infinite' approves max uint256 so later swaps of this token need no approval.".to_string(),
+                default: Some(json!("exact")),
+                items: None,
+                enum_values: Some(vec!["exact".to_string(), "infinite".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "network".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Network the quote is for. Defaults to the selected network, then base.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "wallet".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: WALLET_PARAM_DESCRIPTION.to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "force".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Exceed the configured per-transaction/daily transfer caps. Only set after the user explicitly confirms the amount.".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        ApproveAndSwapTool {
+            definition: ToolDefinition {
+                name: "approve_and_swap".to_string(),
+                description: "Queue a swap from the swap_quote register, plus an ERC-20 approval first if the current allowance is insufficient. The two are signed with consecutive nonces. Returns both UUIDs: broadcast the approval, then the swap, with broadcast_web3_tx. Replaces the manual allowance check, approve, decode_calldata and swap_execute steps.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+
+    /// Calldata for ERC20 `allowance(address,address)`
+    fn allowance_calldata(owner: Address, spender: Address) -> Vec<u8> {
+        let mut calldata = ethers::utils::id("allowance(address,address)")[..4].to_vec();
+        calldata.extend(ethers::abi::encode(&[Token::Address(owner), Token::Address(spender)]));
+        calldata
+    }
+
+    /// Calldata for ERC20 `approve(address,uint256)`
+    fn approve_calldata(spender: Address, amount: U256) -> Vec<u8> {
+        let mut calldata = ethers::utils::id("approve(address,uint256)")[..4].to_vec();
+        calldata.extend(ethers::abi::encode(&[Token::Address(spender), Token::Uint(amount)]));
+        calldata
+    }
+
+    /// Amount to approve for the given mode
+    fn approval_amount(mode: &str, sell_amount: U256) -> Result<U256, String> {
+        match mode {
+            "exact" => Ok(sell_amount),
+            "infinite" => Ok(U256::MAX),
+            other => Err(format!("Invalid approval '{}'. Use 'exact' or 'infinite'", other)),
+        }
+    }
+
+    /// Read a register as a string
+    fn register_str(context: &ToolContext, key: &str) -> Result<String, String> {
+        let value = context
+            .registers
+            .get(key)
+            .ok_or_else(|| format!("Register '{}' is not set", key))?;
+        Ok(match value.as_str() {
+            Some(s) => s.to_string(),
+            None => value.to_string().trim_matches('"').to_string(),
+        })
+    }
+
+    fn queue(tx_queue: &TxQueueManager, signed: SignedTxForQueue, channel_id: Option<i64>) -> String {
+        let uuid = Uuid::new_v4().to_string();
+        tx_queue.queue(QueuedTransaction::new(
+            uuid.clone(),
+            signed.network,
+            signed.from,
+            signed.to,
+            signed.value,
+            signed.data,
+            signed.gas_limit,
+            signed.max_fee_per_gas,
+            signed.max_priority_fee_per_gas,
+            signed.nonce,
+            signed.signed_tx_hex,
+            channel_id,
+        ));
+        uuid
+    }
+}
+
+impl Default for ApproveAndSwapTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The parts of a 0x quote needed to queue the swap
+#[derive(Debug)]
+struct SwapQuote {
+    to: Address,
+    data: Vec<u8>,
+    value: U256,
+    gas: Option<U256>,
+    /// Contract that must be allowed to spend the sell token
+    spender: Address,
+}
+
+impl SwapQuote {
+    fn from_value(quote: &Value) -> Result<Self, String> {
+        let str_field = |v: &Value| -> Option<String> {
+            match v {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            }
+        };
+
+        let to_str = quote.get("to").and_then(str_field).ok_or("Quote has no 'to' address")?;
+        let to: Address = to_str.parse().map_err(|_| format!("Invalid 'to' address in quote: {}", to_str))?;
+
+        let data_str = quote.get("data").and_then(str_field).ok_or("Quote has no 'data'")?;
+        let data = hex::decode(data_str.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid 'data' in quote: {}", e))?;
+
+        let value = match quote.get("value").and_then(str_field) {
+            Some(v) => parse_u256(&v)?,
+            None => U256::zero(),
+        };
+        let gas = quote.get("gas").and_then(str_field).map(|g| parse_u256(&g)).transpose()?;
+
+        // allowance-holder quotes report the spender under issues.allowance
+        // when the allowance is short; otherwise it's the quote's target
+        let spender = match quote.pointer("/issues/allowance/spender").and_then(|s| s.as_str()) {
+            Some(s) => s.parse().map_err(|_| format!("Invalid spender in quote: {}", s))?,
+            None => to,
+        };
+
+        Ok(Self { to, data, value, gas, spender })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApproveAndSwapParams {
+    #[serde(default = "default_quote_register")]
+    quote_register: String,
+    #[serde(default = "default_sell_token_register")]
+    sell_token_register: String,
+    #[serde(default = "default_sell_amount_register")]
+    sell_amount_register: String,
+    #[serde(default = "default_approval")]
+    approval: String,
+    network: Option<String>,
+    wallet: Option<String>,
+    #[serde(default)]
+    force: bool,
+}
+
+fn default_quote_register() -> String {
+    "swap_quote".to_string()
+}
+
+fn default_sell_token_register() -> String {
+    "sell_token".to_string()
+}
+
+fn default_sell_amount_register() -> String {
+    "sell_amount".to_string()
+}
+
+fn default_approval() -> String {
+    "exact".to_string()
+}
+
+#[async_trait]
+impl Tool for ApproveAndSwapTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ApproveAndSwapParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let network = match resolve_network(params.network.as_deref(), context.selected_network.as_deref()) {
+            Ok(n) => n,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let quote = match context.registers.get(&params.quote_register) {
+            Some(q) => q,
+            None => {
+                return ToolResult::error(format!(
+                    "Register '{}' is not set. Get a quote first with x402_fetch (preset: swap_quote, cache_as: {}).",
+                    params.quote_register, params.quote_register
+                ))
+            }
+        };
+        let quote = match SwapQuote::from_value(&quote) {
+            Ok(q) => q,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let sell_token = match Self::register_str(context, &params.sell_token_register) {
+            Ok(t) => t,
+            Err(e) => return ToolResult::error(e),
+        };
+        let sell_amount = match Self::register_str(context, &params.sell_amount_register).and_then(|a| parse_u256(&a)) {
+            Ok(a) => a,
+            Err(e) => return ToolResult::error(e),
+        };
+        let approve_amount = match Self::approval_amount(&params.approval, sell_amount) {
+            Ok(a) => a,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        // Gateway channels require rogue mode for transactions
+        let is_gateway_channel = context.channel_type
+            .as_ref()
+            .map(|ct| {
+                let ct_lower = ct.to_lowercase();
+                ct_lower == "discord" || ct_lower == "telegram" || ct_lower == "slack"
+            })
+            .unwrap_or(false);
+        if is_gateway_channel && !context.is_rogue_mode() {
+            return ToolResult::error(
+                "Transactions cannot be executed in Discord/Telegram/Slack channels unless Rogue Mode is enabled. \
+                Please enable Rogue Mode in the bot settings to allow autonomous transactions from gateway channels."
+            );
+        }
+
+        let tx_queue = match &context.tx_queue {
+            Some(q) => q,
+            None => return ToolResult::error("Transaction queue not available. Contact administrator."),
+        };
+
+        let signer = match WalletSigner::for_wallet(context.wallet_name(params.wallet.as_deref()).as_deref()).await {
+            Ok(s) => s,
+            Err(e) => return ToolResult::error(e),
+        };
+        let rpc_config = resolve_rpc_from_context(&context.extra, &network);
+
+        // Transfer caps apply to the native value sent with the swap
+        let limits = transfer_limits::limits_from_context(context);
+        let mut warnings = Vec::new();
+        if let Some(transfer) = transfer_limits::classify(
+            &network,
+            &format!("{:?}", quote.to),
+            &quote.value.to_string(),
+            &hex::encode(&quote.data),
+        ) {
+            let spent_today = transfer_limits::spent_last_day(
+                tx_queue,
+                &network,
+                &format!("{:?}", signer.address()),
+                &transfer.asset,
+            );
+            match transfer_limits::check_caps(&limits, &transfer, spent_today, params.force) {
+                Ok(w) => warnings = w,
+                Err(e) => return ToolResult::error(e),
+            }
+        }
+
+        // Check the allowance (native sells need none)
+        let is_native = sell_token.eq_ignore_ascii_case(NATIVE_TOKEN_ADDRESS);
+        let token: Option<Address> = if is_native {
+            None
+        } else {
+            match sell_token.parse() {
+                Ok(a) => Some(a),
+                Err(_) => return ToolResult::error(format!("Invalid sell token address: {}", sell_token)),
+            }
+        };
+
+        let mut current_allowance = None;
+        if let Some(token) = token {
+            let calldata = Self::allowance_calldata(signer.address(), quote.spender);
+            let allowance = match Web3FunctionCallTool::call_function(&network, token, calldata, &rpc_config).await {
+                Ok(result) if result.len() >= 32 => U256::from_big_endian(&result[..32]),
+                Ok(_) => return ToolResult::error("Invalid allowance response"),
+                Err(e) => return ToolResult::error(format!("Failed to check allowance: {}", e)),
+            };
+            current_allowance = Some(allowance);
+        }
+        let needs_approval = current_allowance.is_some_and(|a| a < sell_amount);
+
+        // Queue the approval, then the swap with the next nonce
+        let mut approval_uuid = None;
+        let mut swap_nonce = None;
+        if let (true, Some(token)) = (needs_approval, token) {
+            let signed = match Web3FunctionCallTool::sign_transaction_for_queue(
+                &signer,
+                &network,
+                token,
+                Self::approve_calldata(quote.spender, approve_amount),
+                U256::zero(),
+                &rpc_config,
+            )
+            .await
+            {
+                Ok(s) => s,
+                Err(e) => return ToolResult::error(format!("Failed to sign approval: {}", e)),
+            };
+            let nonce = signed.nonce;
+            let uuid = Self::queue(tx_queue, signed, context.channel_id);
+            log::info!("[approve_and_swap] Approval queued as {} (nonce {})", uuid, nonce);
+            approval_uuid = Some(uuid);
+            swap_nonce = Some(U256::from(nonce + 1));
+        }
+
+        // Without a pending approval the swap can be estimated as usual
+        let swap_gas = if approval_uuid.is_some() { quote.gas } else { None };
+        let signed_swap = match Web3FunctionCallTool::sign_transaction_for_queue_with(
+            &signer,
+            &network,
+            quote.to,
+            quote.data.clone(),
+            quote.value,
+            &rpc_config,
+            swap_nonce,
+            swap_gas,
+        )
+        .await
+        {
+            Ok(s) => s,
+            Err(e) => {
+                let hint = approval_uuid
+                    .as_ref()
+                    .map(|u| format!(" The approval ({}) is still queued.", u))
+                    .unwrap_or_default();
+                return ToolResult::error(format!("Failed to sign swap: {}.{}", e, hint));
+            }
+        };
+        let nonce = signed_swap.nonce;
+        let from = signed_swap.from.clone();
+        let value = signed_swap.value.clone();
+        let swap_uuid = Self::queue(tx_queue, signed_swap, context.channel_id);
+        log::info!("[approve_and_swap] Swap queued as {} (nonce {})", swap_uuid, nonce);
+
+        let approval_line = match (&approval_uuid, current_allowance) {
+            (Some(uuid), _) => format!("Approval: {} ({} approval)", uuid, params.approval),
+            (None, Some(a)) => format!("Approval: not needed (allowance {} >= {})", a, sell_amount),
+            (None, None) => "Approval: not needed (native token)".to_string(),
+        };
+        let next_steps = match &approval_uuid {
+            Some(uuid) => format!(
+                "Broadcast the approval first, then the swap:\n\
+                1. `broadcast_web3_tx` with uuid: {}\n\
+                2. `broadcast_web3_tx` with uuid: {}",
+                uuid, swap_uuid
+            ),
+            None => format!("To broadcast: use `broadcast_web3_tx` with uuid: {}", swap_uuid),
+        };
+
+        ToolResult::success(format!(
+            "SWAP QUEUED (not yet broadcast)\n\n\
+            {}\n\
+            Swap: {}\n\
+            Network: {}\n\
+            From: {}\n\
+            Sell amount: {}\n\
+            Value: {} wei\n\n\
+            --- Next Steps ---\n\
+            {}{}",
+            approval_line, swap_uuid, network, from, sell_amount, value, next_steps,
+            warnings.iter().map(|w| format!("\n\nWARNING: {}", w)).collect::<String>()
+        ))
+        .with_metadata(json!({
+            "status": "queued",
+            "approval_uuid": approval_uuid,
+            "swap_uuid": swap_uuid,
+            "approval": params.approval,
+            "approval_needed": needs_approval,
+            "current_allowance": current_allowance.map(|a| a.to_string()),
+            "spender": format!("{:?}", quote.spender),
+            "sell_token": sell_token,
+            "sell_amount": sell_amount.to_string(),
+            "network": network,
+            "from": from,
+            "warnings": warnings,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quote() {
+        let quote = json!({
+            "to": "0x0000000000001fF3684f28c67538d4D072C22734",
+            "data": "0x2213bc0b",
+            "value": "0",
+            "gas": "210000",
+            "issues": {"allowance": {"actual": "0", "spender": "0x000000000022D473030F116dDEE9F6B43aC78BA3"}}
+        });
+        let parsed = SwapQuote::from_value(&quote).unwrap();
+        assert_eq!(parsed.data, vec![0x22, 0x13, 0xbc, 0x0b]);
+        assert_eq!(parsed.gas, Some(U256::from(210000)));
+        assert_eq!(format!("{:?}", parsed.spender), "0x000000000022d473030f116ddee9f6b43ac78ba3");
+
+        // Without an allowance issue, the quote's target is the spender
+        let quote = json!({"to": "0x0000000000001fF3684f28c67538d4D072C22734", "data": "0x", "issues": {"allowance": null}});
+        let parsed = SwapQuote::from_value(&quote).unwrap();
+        assert_eq!(parsed.spender, parsed.to);
+        assert_eq!(parsed.value, U256::zero());
+        assert_eq!(parsed.gas, None);
+
+        assert!(SwapQuote::from_value(&json!({"data": "0x"})).is_err());
+    }
+
+    #[test]
+    fn test_approval_amount() {
+        let amount = U256::from(1_000_000u64);
+        assert_eq!(ApproveAndSwapTool::approval_amount("exact", amount).unwrap(), amount);
+        assert_eq!(ApproveAndSwapTool::approval_amount("infinite", amount).unwrap(), U256::MAX);
+        assert!(ApproveAndSwapTool::approval_amount("unlimited", amount).is_err());
+
+        let calldata = ApproveAndSwapTool::approve_calldata(Address::zero(), amount);
+        assert_eq!(hex::encode(&calldata[..4]), "095ea7b3");
+        assert_eq!(calldata.len(), 4 + 64);
+    }
+}
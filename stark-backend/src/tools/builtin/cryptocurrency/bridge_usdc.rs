@@ -259,8 +259,7 @@ impl BridgeUsdcTool {
         let rpc = X402EvmRpc::new_with_config(
             &private_key,
             network,
-            Some(rpc_config.url.clone()),
-            rpc_config.use_x402,
+            rpc_config,
         )?;
 
         let wallet = Self::get_wallet(chain_id)?;
@@ -529,11 +528,7 @@ impl Tool for BridgeUsdcTool {
             })
             .unwrap_or(false);
 
-        let is_rogue_mode = context
-            .extra
-            .get("rogue_mode_enabled")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let is_rogue_mode = context.is_rogue_mode();
 
         if is_gateway_channel && !is_rogue_mode {
             return ToolResult::error(
@@ -643,8 +638,7 @@ impl Tool for BridgeUsdcTool {
             let rpc = match X402EvmRpc::new_with_config(
                 &private_key,
                 network,
-                Some(rpc_config.url.clone()),
-                rpc_config.use_x402,
+                &rpc_config,
             ) {
                 Ok(r) => r,
                 Err(e) => return ToolResult::error(format!("Failed to create RPC: {}", e)),
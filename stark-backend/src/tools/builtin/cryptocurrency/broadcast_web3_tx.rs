@@ -1,21 +1,27 @@
 //! Broadcast a queued Web3 transaction
 //!
 //! Takes a UUID from web3_tx and broadcasts the signed transaction to the network.
+//! An expired transaction can instead be re-signed with a fresh nonce, which
+//! queues it again under a new UUID.
 
 use crate::gateway::protocol::GatewayEvent;
-use super::web3_tx::SendEthTool;
+use super::web3_function_call::Web3FunctionCallTool;
+use super::web3_tx::{parse_u256, SendEthTool};
+use super::transfer_limits;
 use crate::tools::registry::Tool;
 use crate::tools::rpc_config::resolve_rpc_from_context;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
-use crate::tx_queue::QueuedTxStatus;
+use crate::tx_queue::{BroadcastClaim, QueuedTransaction, QueuedTxStatus, TxQueueManager};
+use crate::wallet::WalletSigner;
 use crate::x402::X402EvmRpc;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::time::Duration;
+use uuid::Uuid;
 
 /// Broadcast queued transaction tool
 pub struct BroadcastWeb3TxTool {
@@ -48,10 +54,21 @@ impl BroadcastWeb3TxTool {
             },
         );
 
+        properties.insert(
+            "resign".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Re-sign an expired (stale) transaction with a fresh nonce instead of broadcasting. Queues it under a new UUID, cached in uuid_register.".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
         BroadcastWeb3TxTool {
             definition: ToolDefinition {
                 name: "broadcast_web3_tx".to_string(),
-                description: "Broadcast a queued transaction. Reads UUID from 'uuid' param or 'uuid_register' (default: 'queued_tx_uuid'). Returns tx hash and explorer URL.".to_string(),
+                description: "Broadcast a queued transaction. Reads UUID from 'uuid' param or 'uuid_register' (default: 'queued_tx_uuid'). Returns tx hash and explorer URL. Expired transactions can't be broadcast; pass resign: true to re-sign them with a fresh nonce.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -67,6 +84,96 @@ impl BroadcastWeb3TxTool {
         crate::config::burner_wallet_private_key()
             .ok_or_else(|| "BURNER_WALLET_BOT_PRIVATE_KEY not set".to_string())
     }
+
+    /// Sign an expired transaction's call again with a fresh nonce and gas,
+    /// queuing it under a new UUID
+    async fn resign(
+        tx_queue: &TxQueueManager,
+        stale: &QueuedTransaction,
+        context: &ToolContext,
+    ) -> Result<QueuedTransaction, String> {
+        let to: ethers::types::Address = stale
+            .to
+            .parse()
+            .map_err(|_| format!("Invalid 'to' address: {}", stale.to))?;
+        let calldata = hex::decode(stale.data.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid calldata: {}", e))?;
+        let value = parse_u256(&stale.value)?;
+
+        // Re-sign from the wallet that signed the original
+        let signer = WalletSigner::for_address(&stale.from).await?;
+        let rpc_config = resolve_rpc_from_context(&context.extra, &stale.network);
+        let signed = Web3FunctionCallTool::sign_transaction_for_queue(
+            &signer,
+            &stale.network,
+            to,
+            calldata,
+            value,
+            &rpc_config,
+        )
+        .await?;
+
+        let queued = QueuedTransaction::new(
+            Uuid::new_v4().to_string(),
+            signed.network,
+            signed.from,
+            signed.to,
+            signed.value,
+            signed.data,
+            signed.gas_limit,
+            signed.max_fee_per_gas,
+            signed.max_priority_fee_per_gas,
+            signed.nonce,
+            signed.signed_tx_hex,
+            context.channel_id,
+        );
+        tx_queue.queue(queued.clone());
+        log::info!("[broadcast_web3_tx] Re-signed expired {} as {} (nonce {})", stale.uuid, queued.uuid, queued.nonce);
+        Ok(queued)
+    }
+}
+
+/// Result for a transaction that can't be broadcast because it isn't pending.
+/// A transaction that was already submitted reports its original hash, so a
+/// retried call reads the same as the first one.
+fn not_pending_result(tx: &QueuedTransaction) -> ToolResult {
+    if tx.is_submitted() {
+        let tx_hash = tx.tx_hash.as_deref().unwrap_or("unknown");
+        let explorer_url = tx.explorer_url.as_deref().unwrap_or("");
+        return ToolResult::success(format!(
+            "Transaction {} was already broadcast; it was not submitted again.\n\nHash: {}\nExplorer: {}\nStatus: {}",
+            tx.uuid, tx_hash, explorer_url, tx.status
+        ))
+        .with_metadata(json!({
+            "uuid": tx.uuid,
+            "tx_hash": tx.tx_hash,
+            "network": tx.network,
+            "explorer_url": tx.explorer_url,
+            "status": tx.status,
+            "already_broadcast": true
+        }));
+    }
+
+    match tx.status {
+        QueuedTxStatus::Broadcasting => ToolResult::error(format!(
+            "Transaction {} is already being broadcast. Please wait.",
+            tx.uuid
+        )),
+        QueuedTxStatus::Failed => ToolResult::error(format!(
+            "Transaction {} previously failed: {}\n\nYou may need to create a new transaction.",
+            tx.uuid,
+            tx.error.as_deref().unwrap_or("Unknown error")
+        )),
+        QueuedTxStatus::Expired => ToolResult::error(format!(
+            "Transaction {} has expired and is stale: {}\n\nTo re-sign it with a fresh nonce, call broadcast_web3_tx with uuid: {} and resign: true",
+            tx.uuid,
+            tx.error.as_deref().unwrap_or("its nonce may no longer be valid"),
+            tx.uuid
+        )),
+        QueuedTxStatus::Pending | QueuedTxStatus::Broadcast | QueuedTxStatus::Confirmed => {
+            ToolResult::error(format!("Transaction {} can't be broadcast ({})", tx.uuid, tx.status))
+        }
+    }
 }
 
 impl Default for BroadcastWeb3TxTool {
@@ -80,6 +187,8 @@ struct BroadcastParams {
     uuid: Option<String>,
     #[serde(default = "default_uuid_register")]
     uuid_register: String,
+    #[serde(default)]
+    resign: bool,
 }
 
 fn default_uuid_register() -> String {
@@ -92,6 +201,12 @@ impl Tool for BroadcastWeb3TxTool {
         self.definition.clone()
     }
 
+    /// Dropping a broadcast partway would leave the transaction claimed as
+    /// broadcasting with no hash recorded
+    fn cancel_on_timeout(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         log::info!("[broadcast_web3_tx] Raw params: {}", params);
 
@@ -134,20 +249,60 @@ impl Tool for BroadcastWeb3TxTool {
             if uuid_from_param { "param" } else { &params.uuid_register }
         );
 
-        // Check rogue mode from bot settings in ToolContext
-        let is_rogue_mode = context.extra
-            .get("rogue_mode_enabled")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let tx_queue = match &context.tx_queue {
+            Some(q) => q,
+            None => return ToolResult::error("Transaction queue not available. Contact administrator."),
+        };
+        tx_queue.expire_stale();
 
-        if !is_rogue_mode {
-            // Partner mode: trigger confirmation modal instead of broadcasting
-            let tx_queue = match &context.tx_queue {
-                Some(q) => q,
-                None => return ToolResult::error("Transaction queue not available. Contact administrator."),
+        if params.resign {
+            let stale = match tx_queue.get(&uuid) {
+                Some(tx) => tx,
+                None => return ToolResult::error(format!("Transaction with UUID '{}' not found.", uuid)),
+            };
+            if stale.status != QueuedTxStatus::Expired {
+                return ToolResult::error(format!(
+                    "Only expired transactions can be re-signed; {} is {}.",
+                    uuid, stale.status
+                ));
+            }
+            return match Self::resign(tx_queue, &stale, context).await {
+                Ok(queued) => {
+                    context.set_register(&params.uuid_register, json!(&queued.uuid), "broadcast_web3_tx");
+                    ToolResult::success(format!(
+                        "TRANSACTION RE-SIGNED (not yet broadcast)\n\n\
+                        Replaces: {}\n\
+                        New UUID: {}\n\
+                        Network: {}\n\
+                        To: {}\n\
+                        Value: {}\n\
+                        Nonce: {}\n\n\
+                        New UUID cached in register '{}'. To broadcast: use broadcast_web3_tx with uuid: {}",
+                        stale.uuid, queued.uuid, queued.network, queued.to,
+                        queued.format_value(), queued.nonce, params.uuid_register, queued.uuid
+                    )).with_metadata(json!({
+                        "uuid": queued.uuid,
+                        "replaces": stale.uuid,
+                        "status": "queued",
+                        "network": queued.network,
+                        "nonce": queued.nonce
+                    }))
+                }
+                Err(e) => ToolResult::error(format!("Failed to re-sign transaction {}: {}", uuid, e)),
             };
+        }
+
+        // Check rogue mode from bot settings in ToolContext
+        let is_rogue_mode = context.is_rogue_mode();
+
+        // Transfers above the configured confirm_above limit always wait for the user
+        let limits = transfer_limits::limits_from_context(context);
+        let is_large_transfer = tx_queue
+            .get(&uuid)
+            .is_some_and(|tx| transfer_limits::queued_tx_requires_confirmation(&limits, &tx));
 
-            // Get the transaction to show in the modal
+        if !is_rogue_mode || is_large_transfer {
+            // Partner mode: trigger confirmation modal instead of broadcasting
             let queued_tx = match tx_queue.get(&uuid) {
                 Some(tx) => tx,
                 None => return ToolResult::error(format!(
@@ -156,6 +311,11 @@ impl Tool for BroadcastWeb3TxTool {
                 )),
             };
 
+            // Don't ask the user to confirm a transaction that's already out
+            if queued_tx.status != QueuedTxStatus::Pending {
+                return not_pending_result(&queued_tx);
+            }
+
             // Emit event to open confirmation modal
             if let (Some(broadcaster), Some(ch_id)) = (&context.broadcaster, context.channel_id) {
                 broadcaster.broadcast(GatewayEvent::tx_queue_confirmation_required(
@@ -165,7 +325,7 @@ impl Tool for BroadcastWeb3TxTool {
                     &queued_tx.from,
                     &queued_tx.to,
                     &queued_tx.value,
-                    &queued_tx.format_value_eth(),
+                    &queued_tx.format_value(),
                     &queued_tx.data,
                 ));
                 log::info!("[broadcast_web3_tx] Partner mode: emitted tx_queue.confirmation_required for {}", queued_tx.uuid);
@@ -178,67 +338,27 @@ impl Tool for BroadcastWeb3TxTool {
                 To: {}\n\
                 Value: {}\n\n\
                 The user will be prompted to confirm or deny this transaction.",
-                queued_tx.uuid, queued_tx.network, queued_tx.to, queued_tx.format_value_eth()
+                queued_tx.uuid, queued_tx.network, queued_tx.to, queued_tx.format_value()
             )).with_metadata(json!({
                 "uuid": queued_tx.uuid,
                 "status": "awaiting_confirmation",
                 "network": queued_tx.network,
                 "to": queued_tx.to,
                 "value": queued_tx.value,
-                "value_formatted": queued_tx.format_value_eth()
+                "value_formatted": queued_tx.format_value()
             }));
         }
 
-        // Get tx_queue
-        let tx_queue = match &context.tx_queue {
-            Some(q) => q,
-            None => return ToolResult::error("Transaction queue not available. Contact administrator."),
-        };
-
-        // Get the queued transaction
-        let queued_tx = match tx_queue.get(&uuid) {
-            Some(tx) => tx,
-            None => return ToolResult::error(format!(
+        // Claim the transaction, so a retried or concurrent call can't submit it twice
+        let queued_tx = match tx_queue.claim_for_broadcast(&uuid) {
+            BroadcastClaim::Claimed(tx) => tx,
+            BroadcastClaim::NotPending(tx) => return not_pending_result(&tx),
+            BroadcastClaim::NotFound => return ToolResult::error(format!(
                 "Transaction with UUID '{}' not found. Use list_queued_web3_tx to see available transactions.",
                 uuid
             )),
         };
 
-        // Validate status is Pending
-        match queued_tx.status {
-            QueuedTxStatus::Pending => {},
-            QueuedTxStatus::Broadcasting => {
-                return ToolResult::error(format!(
-                    "Transaction {} is already being broadcast. Please wait.",
-                    uuid
-                ));
-            },
-            QueuedTxStatus::Broadcast | QueuedTxStatus::Confirmed => {
-                let tx_hash = queued_tx.tx_hash.as_deref().unwrap_or("unknown");
-                let explorer_url = queued_tx.explorer_url.as_deref().unwrap_or("");
-                return ToolResult::error(format!(
-                    "Transaction {} has already been broadcast.\n\nTx Hash: {}\nExplorer: {}",
-                    uuid, tx_hash, explorer_url
-                ));
-            },
-            QueuedTxStatus::Failed => {
-                let error = queued_tx.error.as_deref().unwrap_or("Unknown error");
-                return ToolResult::error(format!(
-                    "Transaction {} previously failed: {}\n\nYou may need to create a new transaction.",
-                    uuid, error
-                ));
-            },
-            QueuedTxStatus::Expired => {
-                return ToolResult::error(format!(
-                    "Transaction {} has expired. Please create a new transaction.",
-                    uuid
-                ));
-            },
-        }
-
-        // Mark as broadcasting
-        tx_queue.mark_broadcasting(&uuid);
-
         // Resolve RPC configuration from context (respects custom RPC settings)
         let rpc_config = resolve_rpc_from_context(&context.extra, &queued_tx.network);
 
@@ -259,8 +379,7 @@ impl Tool for BroadcastWeb3TxTool {
         let rpc = match X402EvmRpc::new_with_config(
             &private_key,
             &queued_tx.network,
-            Some(rpc_config.url.clone()),
-            rpc_config.use_x402,
+            &rpc_config,
         ) {
             Ok(r) => r,
             Err(e) => {
@@ -292,8 +411,7 @@ impl Tool for BroadcastWeb3TxTool {
         log::info!("[broadcast_web3_tx] Transaction sent: {}", tx_hash_str);
 
         // Get explorer URL
-        let explorer_base = queued_tx.get_explorer_base_url();
-        let explorer_url = format!("{}/{}", explorer_base, tx_hash_str);
+        let explorer_url = queued_tx.explorer_url_for(&tx_hash_str);
 
         // Mark as broadcast (rogue mode - agent initiated)
         tx_queue.mark_broadcast(&uuid, &tx_hash_str, &explorer_url, "rogue");
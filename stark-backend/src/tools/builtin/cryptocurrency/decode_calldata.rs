@@ -6,17 +6,65 @@
 //!
 //! Primary use case: Decoding 0x swap quotes so they can be executed via
 //! web3_function_call with proper ABI encoding.
+//!
+//! When the selector isn't in the given ABI (or no ABI is given), candidate
+//! signatures are fetched from the public 4byte directory and each is tried
+//! against the calldata. Matches from 4byte are reported as lower confidence.
 
+use crate::tools::abis::{self, AbiFile};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use async_trait::async_trait;
-use ethers::abi::{Abi, Token, ParamType};
+use ethers::abi::{Abi, AbiParser, Function, Token, ParamType};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// 4byte directory signature lookup endpoint
+const FOURBYTE_API_URL: &str = "https://www.4byte.directory/api/v1/signatures/";
+
+lazy_static::lazy_static! {
+    /// Selector -> candidate text signatures. Only successful lookups are cached.
+    static ref SELECTOR_CACHE: RwLock<HashMap<String, Vec<String>>> = RwLock::new(HashMap::new());
+}
+
+/// Where a decoded function signature came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecodeSource {
+    /// Selector matched a function in the loaded ABI
+    Abi,
+    /// Selector matched a signature from the 4byte directory (may be a collision)
+    FourByte,
+}
+
+impl DecodeSource {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DecodeSource::Abi => "abi",
+            DecodeSource::FourByte => "4byte",
+        }
+    }
+
+    fn confidence(&self) -> &'static str {
+        match self {
+            DecodeSource::Abi => "high",
+            DecodeSource::FourByte => "low",
+        }
+    }
+}
+
+/// Result of decoding calldata
+#[derive(Debug)]
+struct DecodedCall {
+    function: String,
+    signature: String,
+    params: Vec<Value>,
+    source: DecodeSource,
+}
 
 /// Decode calldata tool
 pub struct DecodeCalldataTool {
@@ -32,7 +80,7 @@ impl DecodeCalldataTool {
             "abi".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Name of the ABI file (without .json) to use for decoding. E.g., '0x_settler', 'erc20'.".to_string(),
+                description: "Name of the ABI file (without .json) to use for decoding. E.g., '0x_settler', 'erc20'. Optional - unknown selectors fall back to the 4byte signature directory.".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -73,18 +121,16 @@ impl DecodeCalldataTool {
         );
 
         // Determine abis directory relative to working directory
-        let abis_dir = std::env::current_dir()
-            .unwrap_or_else(|_| PathBuf::from("."))
-            .join("abis");
+        let abis_dir = crate::tools::presets::abis_dir();
 
         DecodeCalldataTool {
             definition: ToolDefinition {
                 name: "decode_calldata".to_string(),
-                description: "Decode raw calldata using an ABI. Extracts function name and parameters, storing them in registers for use with web3_function_call.".to_string(),
+                description: "Decode raw calldata using an ABI, falling back to the 4byte signature directory for unknown selectors. Extracts function name and parameters, storing them in registers for use with web3_function_call.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
-                    required: vec!["abi".to_string(), "cache_as".to_string()],
+                    required: vec!["cache_as".to_string()],
                 },
                 group: ToolGroup::Finance,
             },
@@ -92,17 +138,9 @@ impl DecodeCalldataTool {
         }
     }
 
-    /// Load ABI from file
+    /// Load ABI by name (uploaded ABIs first, then the abis folder)
     fn load_abi(&self, name: &str) -> Result<AbiFile, String> {
-        let path = self.abis_dir.join(format!("{}.json", name));
-
-        let content = std::fs::read_to_string(&path)
-            .map_err(|e| format!("Failed to load ABI '{}': {}. Available ABIs are in the /abis folder.", name, e))?;
-
-        let abi_file: AbiFile = serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse ABI '{}': {}", name, e))?;
-
-        Ok(abi_file)
+        abis::load_abi(&self.abis_dir, name)
     }
 
     /// Parse ethers Abi from our ABI file format
@@ -133,50 +171,130 @@ impl DecodeCalldataTool {
         }
     }
 
-    /// Decode calldata using the ABI
-    fn decode_calldata(&self, abi: &Abi, calldata: &[u8]) -> Result<(String, Vec<Value>), String> {
+    /// Decode parameters against a function, requiring the data to re-encode exactly
+    fn decode_params(&self, function: &Function, params_data: &[u8]) -> Result<Vec<Value>, String> {
+        let param_types: Vec<ParamType> = function.inputs.iter()
+            .map(|p| p.kind.clone())
+            .collect();
+
+        let tokens = ethers::abi::decode(&param_types, params_data)
+            .map_err(|e| format!("Failed to decode parameters for function '{}': {}", function.name, e))?;
+
+        Ok(tokens.iter().map(|t| self.token_to_value(t)).collect())
+    }
+
+    /// Decode calldata using the ABI. Returns None if the selector isn't in the ABI.
+    fn decode_calldata(&self, abi: &Abi, calldata: &[u8]) -> Result<Option<DecodedCall>, String> {
         if calldata.len() < 4 {
             return Err("Calldata too short - must have at least 4 bytes for function selector".to_string());
         }
 
         // Extract function selector (first 4 bytes)
         let selector = &calldata[0..4];
-        let selector_hex = format!("0x{}", hex::encode(selector));
 
         // Find matching function in ABI
         for function in abi.functions() {
-            let func_selector = function.short_signature();
-            if func_selector == selector {
-                // Found the function! Now decode the params
-                let params_data = &calldata[4..];
-
-                // Get param types
-                let param_types: Vec<ParamType> = function.inputs.iter()
-                    .map(|p| p.kind.clone())
-                    .collect();
-
-                // Decode parameters
-                let tokens = ethers::abi::decode(&param_types, params_data)
-                    .map_err(|e| format!("Failed to decode parameters for function '{}': {}", function.name, e))?;
-
-                // Convert tokens to JSON values
-                let params: Vec<Value> = tokens.iter()
-                    .map(|t| self.token_to_value(t))
-                    .collect();
+            if function.short_signature() == selector {
+                let params = self.decode_params(function, &calldata[4..])?;
 
                 log::info!(
                     "[decode_calldata] Decoded function '{}' with {} params",
                     function.name, params.len()
                 );
 
-                return Ok((function.name.clone(), params));
+                return Ok(Some(DecodedCall {
+                    function: function.name.clone(),
+                    signature: function.signature(),
+                    params,
+                    source: DecodeSource::Abi,
+                }));
             }
         }
 
-        Err(format!(
-            "No function found with selector {} in ABI. This may be a different contract or ABI version.",
-            selector_hex
-        ))
+        Ok(None)
+    }
+
+    /// Try each candidate text signature against the calldata, returning the first
+    /// whose selector matches and whose parameters decode and re-encode exactly.
+    fn decode_with_signatures(&self, calldata: &[u8], signatures: &[String]) -> Option<DecodedCall> {
+        if calldata.len() < 4 {
+            return None;
+        }
+        let (selector, params_data) = calldata.split_at(4);
+
+        for signature in signatures {
+            let function = match AbiParser::default().parse_function(signature) {
+                Ok(f) => f,
+                Err(_) => continue,
+            };
+            if function.short_signature() != selector {
+                continue;
+            }
+
+            let param_types: Vec<ParamType> = function.inputs.iter().map(|p| p.kind.clone()).collect();
+            let tokens = match ethers::abi::decode(&param_types, params_data) {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            // Loose decodes of colliding signatures rarely round-trip byte for byte
+            if ethers::abi::encode(&tokens) != params_data {
+                continue;
+            }
+
+            return Some(DecodedCall {
+                function: function.name.clone(),
+                signature: signature.clone(),
+                params: tokens.iter().map(|t| self.token_to_value(t)).collect(),
+                source: DecodeSource::FourByte,
+            });
+        }
+
+        None
+    }
+
+    /// Fetch candidate signatures for a selector from the 4byte directory (cached)
+    async fn lookup_4byte(&self, selector_hex: &str) -> Result<Vec<String>, String> {
+        if let Some(cached) = SELECTOR_CACHE.read().ok().and_then(|c| c.get(selector_hex).cloned()) {
+            return Ok(cached);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .user_agent("StarkBot/1.0")
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        let resp = client
+            .get(FOURBYTE_API_URL)
+            .query(&[("hex_signature", selector_hex)])
+            .send()
+            .await
+            .map_err(|e| format!("4byte lookup failed: {}", e))?;
+
+        if !resp.status().is_success() {
+            return Err(format!("4byte lookup failed: HTTP {}", resp.status()));
+        }
+
+        let body: FourByteResponse = resp
+            .json()
+            .await
+            .map_err(|e| format!("4byte lookup returned invalid JSON: {}", e))?;
+
+        // Oldest registrations first - later ones for the same selector are usually collisions
+        let mut results = body.results;
+        results.sort_by_key(|r| r.id);
+        let signatures: Vec<String> = results.into_iter().map(|r| r.text_signature).collect();
+
+        log::info!(
+            "[decode_calldata] 4byte returned {} candidate(s) for {}",
+            signatures.len(), selector_hex
+        );
+
+        if let Ok(mut cache) = SELECTOR_CACHE.write() {
+            cache.insert(selector_hex.to_string(), signatures.clone());
+        }
+
+        Ok(signatures)
     }
 }
 
@@ -186,20 +304,23 @@ impl Default for DecodeCalldataTool {
     }
 }
 
-/// ABI file structure
+/// 4byte directory API response
 #[derive(Debug, Deserialize)]
-struct AbiFile {
-    name: String,
+struct FourByteResponse {
     #[serde(default)]
-    description: String,
-    abi: Vec<Value>,
+    results: Vec<FourByteSignature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FourByteSignature {
     #[serde(default)]
-    address: HashMap<String, String>,
+    id: u64,
+    text_signature: String,
 }
 
 #[derive(Debug, Deserialize)]
 struct DecodeCalldataParams {
-    abi: String,
+    abi: Option<String>,
     calldata: Option<String>,
     calldata_register: Option<String>,
     cache_as: String,
@@ -226,7 +347,7 @@ impl Tool for DecodeCalldataTool {
             cd.clone()
         } else if let Some(ref reg_name) = params.calldata_register {
             // Read from register
-            match context.registers.get(reg_name) {
+            match context.registers.get_or_intrinsic(reg_name, context, None) {
                 Some(v) => {
                     // Could be a string directly, or an object with a "data" field
                     if let Some(s) = v.as_str() {
@@ -258,17 +379,6 @@ impl Tool for DecodeCalldataTool {
             return ToolResult::error("Must provide either 'calldata' or 'calldata_register'");
         };
 
-        // Load and parse ABI
-        let abi_file = match self.load_abi(&params.abi) {
-            Ok(a) => a,
-            Err(e) => return ToolResult::error(e),
-        };
-
-        let abi = match self.parse_abi(&abi_file) {
-            Ok(a) => a,
-            Err(e) => return ToolResult::error(e),
-        };
-
         // Parse calldata hex
         let hex_str = calldata_hex.strip_prefix("0x").unwrap_or(&calldata_hex);
         let calldata = match hex::decode(hex_str) {
@@ -276,12 +386,63 @@ impl Tool for DecodeCalldataTool {
             Err(e) => return ToolResult::error(format!("Invalid hex calldata: {}", e)),
         };
 
-        // Decode the calldata
-        let (function_name, decoded_params) = match self.decode_calldata(&abi, &calldata) {
-            Ok(r) => r,
-            Err(e) => return ToolResult::error(e),
+        if calldata.len() < 4 {
+            return ToolResult::error("Calldata too short - must have at least 4 bytes for function selector");
+        }
+        let selector_hex = format!("0x{}", hex::encode(&calldata[0..4]));
+
+        // Try the local ABI first, if one was given
+        let mut decoded = None;
+        if let Some(ref abi_name) = params.abi {
+            let abi_file = match self.load_abi(abi_name) {
+                Ok(a) => a,
+                Err(e) => return ToolResult::error(e),
+            };
+
+            let abi = match self.parse_abi(&abi_file) {
+                Ok(a) => a,
+                Err(e) => return ToolResult::error(e),
+            };
+
+            decoded = match self.decode_calldata(&abi, &calldata) {
+                Ok(d) => d,
+                Err(e) => return ToolResult::error(e),
+            };
+        }
+
+        // Fall back to the 4byte directory
+        let decoded = match decoded {
+            Some(d) => d,
+            None => {
+                let not_in_abi = match params.abi {
+                    Some(ref name) => format!(" is not in ABI '{}'", name),
+                    None => String::new(),
+                };
+                match self.lookup_4byte(&selector_hex).await {
+                    Ok(signatures) => match self.decode_with_signatures(&calldata, &signatures) {
+                        Some(d) => d,
+                        None => {
+                            return ToolResult::error(format!(
+                                "Unknown selector {}{}. 4byte returned {} candidate signature(s), none of which decode this calldata.",
+                                selector_hex, not_in_abi, signatures.len()
+                            ));
+                        }
+                    },
+                    Err(e) => {
+                        log::warn!("[decode_calldata] {}", e);
+                        return ToolResult::error(format!(
+                            "Unknown selector {}{}. The 4byte signature directory is unavailable ({}).",
+                            selector_hex, not_in_abi, e
+                        ));
+                    }
+                }
+            }
         };
 
+        let function_name = decoded.function.clone();
+        let decoded_params = decoded.params.clone();
+        let abi_label = params.abi.clone().unwrap_or_else(|| "(none)".to_string());
+
         // Store results in registers
         let function_key = format!("{}_function", params.cache_as);
         let params_key = format!("{}_params", params.cache_as);
@@ -308,7 +469,7 @@ impl Tool for DecodeCalldataTool {
 
         log::info!(
             "[decode_calldata] Decoded {}::{} -> registers {}, {}, contract={:?}, value={:?}, + {} individual params",
-            params.abi, function_name, function_key, params_key, contract_address, tx_value, decoded_params.len()
+            abi_label, function_name, function_key, params_key, contract_address, tx_value, decoded_params.len()
         );
 
         // Format params for display
@@ -328,12 +489,20 @@ impl Tool for DecodeCalldataTool {
             "Decoded calldata successfully!\n\n\
             ABI: {}\n\
             Function: {}\n\
+            Signature: {}\n\
             Parameters: {:?}\n",
-            params.abi,
+            abi_label,
             function_name,
+            decoded.signature,
             params_display,
         );
 
+        if decoded.source == DecodeSource::FourByte {
+            msg.push_str(
+                "Source: 4byte directory (LOWER CONFIDENCE - selector collisions are possible; verify against the contract's real ABI before relying on this)\n"
+            );
+        }
+
         if let Some(ref addr) = contract_address {
             msg.push_str(&format!("Contract: {}\n", addr));
         }
@@ -363,6 +532,9 @@ impl Tool for DecodeCalldataTool {
         ToolResult::success(msg).with_metadata(json!({
             "abi": params.abi,
             "function": function_name,
+            "signature": decoded.signature,
+            "source": decoded.source.as_str(),
+            "confidence": decoded.source.confidence(),
             "params": decoded_params,
             "contract": contract_address,
             "value": tx_value,
@@ -373,3 +545,49 @@ impl Tool for DecodeCalldataTool {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// transfer(0x...01, 1000)
+    fn transfer_calldata() -> Vec<u8> {
+        let mut data = hex::decode("a9059cbb").unwrap();
+        data.extend(ethers::abi::encode(&[
+            Token::Address(ethers::types::Address::from_low_u64_be(1)),
+            Token::Uint(1000u64.into()),
+        ]));
+        data
+    }
+
+    #[test]
+    fn test_decode_with_signatures_matches_selector() {
+        let tool = DecodeCalldataTool::new();
+        let signatures = vec![
+            "approve(address,uint256)".to_string(),
+            "transfer(address,uint256)".to_string(),
+        ];
+
+        let decoded = tool.decode_with_signatures(&transfer_calldata(), &signatures).unwrap();
+        assert_eq!(decoded.function, "transfer");
+        assert_eq!(decoded.signature, "transfer(address,uint256)");
+        assert_eq!(decoded.source, DecodeSource::FourByte);
+        assert_eq!(decoded.params[1], json!("1000"));
+    }
+
+    #[test]
+    fn test_decode_with_signatures_no_match() {
+        let tool = DecodeCalldataTool::new();
+        assert!(tool.decode_with_signatures(&transfer_calldata(), &[]).is_none());
+        assert!(tool
+            .decode_with_signatures(&transfer_calldata(), &["not a signature".to_string()])
+            .is_none());
+
+        // Right selector but trailing garbage doesn't round-trip
+        let mut data = transfer_calldata();
+        data.extend([0u8; 7]);
+        assert!(tool
+            .decode_with_signatures(&data, &["transfer(address,uint256)".to_string()])
+            .is_none());
+    }
+}
@@ -5,6 +5,8 @@
 //! - Getting token info by address
 //! - Getting pair information
 //! - Getting trending/boosted tokens
+//! - Checking a token for rug / honeypot risk signals (with GoPlus token
+//!   security data on EVM chains)
 
 use crate::tools::registry::Tool;
 use crate::tools::types::{
@@ -16,6 +18,7 @@ use serde_json::{json, Value};
 use std::collections::HashMap;
 
 const BASE_URL: &str = "https://api.dexscreener.com";
+const GOPLUS_URL: &str = "https://api.gopluslabs.io/api/v1/token_security";
 
 /// DexScreener API tool
 pub struct DexScreenerTool {
@@ -30,13 +33,14 @@ impl DexScreenerTool {
             "action".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Action: 'search' (find tokens), 'token' (get by address), 'pair' (get pool info), 'boosted' (paid promotions - NOT organic trending)".to_string(),
+                description: "Action: 'search' (find tokens), 'token' (get by address), 'pair' (get pool info), 'check_token' (rug/honeypot risk summary), 'boosted' (paid promotions - NOT organic trending)".to_string(),
                 default: None,
                 items: None,
                 enum_values: Some(vec![
                     "search".to_string(),
                     "token".to_string(),
                     "pair".to_string(),
+                    "check_token".to_string(),
                     "boosted".to_string(),
                 ]),
             },
@@ -57,7 +61,7 @@ impl DexScreenerTool {
             "chain".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Chain for 'token'/'pair'/'boosted' actions: ethereum, base, solana, bsc, polygon, arbitrum, optimism, avalanche. For 'boosted', filters results to this chain only.".to_string(),
+                description: "Chain for 'token'/'pair'/'check_token'/'boosted' actions: ethereum, base, solana, bsc, polygon, arbitrum, optimism, avalanche. For 'boosted', filters results to this chain only.".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -84,6 +88,7 @@ ACTIONS:
 - search: Find tokens by name/symbol/address (e.g., "PEPE", "0x6982...")
 - token: Get all trading pairs for a token address (requires chain + address)
 - pair: Get specific liquidity pool info (requires chain + pair_address)
+- check_token: Risk summary before trading (requires chain + address): liquidity, pair age, sell activity, holder concentration, LP lock/burn, honeypot and tax checks
 - boosted: See PAID promotional tokens (⚠️ NOT organic trending! These are paid ads, often scams)
 
 SUPPORTED CHAINS: ethereum, base, solana, bsc, polygon, arbitrum, optimism, avalanche
@@ -93,10 +98,13 @@ RETURNS: Price (USD), 24h change %, market cap, liquidity, volume, buy/sell txn
 EXAMPLES:
 - Search: {"action": "search", "query": "PEPE"}
 - Token info: {"action": "token", "chain": "base", "address": "0x..."}
+- Risk check: {"action": "check_token", "chain": "base", "address": "0x..."}
 - Boosted (paid): {"action": "boosted", "chain": "base"}
 
 ⚠️ IMPORTANT: 'boosted' shows tokens that PAID for promotion, not actual trending tokens. For real trending, use 'search' and look at volume/liquidity metrics.
 
+ALWAYS run check_token before buying an unfamiliar token and tell the user about any HIGH risk signals. Signals marked UNAVAILABLE were not checked - never describe them as safe.
+
 TIP: Low liquidity (<$10K) means high slippage risk. Same token name can exist on different chains."#.to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
@@ -109,6 +117,75 @@ TIP: Low liquidity (<$10K) means high slippage risk. Same token name can exist o
     }
 }
 
+impl DexScreenerTool {
+    /// USD price of a token, taken from its most liquid pair where it is the
+    /// base token. `Ok(None)` if DexScreener has no priced pair for it.
+    pub(crate) async fn token_price_usd(chain: &str, address: &str) -> Result<Option<f64>, String> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .user_agent("StarkBot/1.0")
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        let url = format!("{}/tokens/v1/{}/{}", BASE_URL, chain, address);
+        let resp = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+        if !resp.status().is_success() {
+            return Err(format!("API error: {}", resp.status()));
+        }
+        let pairs: Vec<Pair> = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+
+        Ok(best_price_usd(&pairs, address))
+    }
+}
+
+/// GoPlus security data for a token. `None` when the chain isn't covered or
+/// the lookup fails - callers report those signals as unavailable.
+async fn fetch_token_security(client: &reqwest::Client, chain: &str, address: &str) -> Option<TokenSecurity> {
+    let chain_id = goplus_chain_id(chain)?;
+    let url = format!("{}/{}?contract_addresses={}", GOPLUS_URL, chain_id, address);
+    let resp = match client.get(&url).send().await {
+        Ok(r) if r.status().is_success() => r,
+        Ok(r) => {
+            log::warn!("[dexscreener] Token security lookup failed: {}", r.status());
+            return None;
+        }
+        Err(e) => {
+            log::warn!("[dexscreener] Token security lookup failed: {}", e);
+            return None;
+        }
+    };
+    let data: SecurityResponse = resp.json().await.ok()?;
+    if data.code != Some(1) {
+        return None;
+    }
+    data.result?
+        .into_iter()
+        .find(|(a, _)| a.eq_ignore_ascii_case(address))
+        .map(|(_, security)| security)
+}
+
+/// Price from the most liquid pair that has `address` as its base token
+fn best_price_usd(pairs: &[Pair], address: &str) -> Option<f64> {
+    pairs
+        .iter()
+        .filter(|p| {
+            p.base_token
+                .as_ref()
+                .and_then(|t| t.address.as_deref())
+                .is_some_and(|a| a.eq_ignore_ascii_case(address))
+        })
+        .filter_map(|p| {
+            let price = p.price_usd.as_deref()?.parse::<f64>().ok()?;
+            Some((p.liquidity.usd.unwrap_or(0.0), price))
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, price)| price)
+}
+
 impl Default for DexScreenerTool {
     fn default() -> Self {
         Self::new()
@@ -150,6 +227,8 @@ struct Pair {
     market_cap: Option<f64>,
     #[serde(default)]
     txns: Txns,
+    /// Pair creation time (unix ms)
+    pair_created_at: Option<i64>,
     url: Option<String>,
 }
 
@@ -204,6 +283,292 @@ struct Boost {
     url: Option<String>,
 }
 
+/// GoPlus token security response
+#[derive(Debug, Deserialize)]
+struct SecurityResponse {
+    code: Option<i64>,
+    result: Option<HashMap<String, TokenSecurity>>,
+}
+
+/// The GoPlus token security fields used for risk checks. GoPlus encodes
+/// flags and fractions as strings ("1" / "0.05").
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct TokenSecurity {
+    is_honeypot: Option<String>,
+    cannot_sell_all: Option<String>,
+    buy_tax: Option<String>,
+    sell_tax: Option<String>,
+    is_open_source: Option<String>,
+    is_mintable: Option<String>,
+    holder_count: Option<String>,
+    holders: Vec<SecurityHolder>,
+    lp_holders: Vec<SecurityHolder>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct SecurityHolder {
+    address: Option<String>,
+    percent: Option<String>,
+    is_locked: Option<i64>,
+    is_contract: Option<i64>,
+}
+
+impl SecurityHolder {
+    fn fraction(&self) -> f64 {
+        self.percent.as_deref().and_then(|p| p.parse().ok()).unwrap_or(0.0)
+    }
+
+    fn is_burn(&self) -> bool {
+        self.address.as_deref().is_some_and(|a| {
+            let a = a.to_lowercase();
+            a == "0x0000000000000000000000000000000000000000"
+                || a == "0x000000000000000000000000000000000000dead"
+        })
+    }
+}
+
+fn flag(value: &Option<String>) -> Option<bool> {
+    value.as_deref().map(|v| v == "1")
+}
+
+/// Chain IDs GoPlus token security supports, by DexScreener chain name
+fn goplus_chain_id(chain: &str) -> Option<u64> {
+    match chain.to_lowercase().as_str() {
+        "ethereum" => Some(1),
+        "bsc" => Some(56),
+        "polygon" => Some(137),
+        "arbitrum" => Some(42161),
+        "optimism" => Some(10),
+        "avalanche" => Some(43114),
+        "base" => Some(8453),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum RiskLevel {
+    Low,
+    /// The signal couldn't be checked
+    Unavailable,
+    Medium,
+    High,
+}
+
+impl RiskLevel {
+    fn label(self) -> &'static str {
+        match self {
+            RiskLevel::Low => "LOW",
+            RiskLevel::Unavailable => "UNAVAILABLE",
+            RiskLevel::Medium => "MEDIUM",
+            RiskLevel::High => "HIGH",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RiskSignal {
+    name: &'static str,
+    level: RiskLevel,
+    detail: String,
+}
+
+impl RiskSignal {
+    fn new(name: &'static str, level: RiskLevel, detail: impl Into<String>) -> Self {
+        RiskSignal { name, level, detail: detail.into() }
+    }
+}
+
+/// Risk signals for a token from its DexScreener pairs and, when available,
+/// GoPlus security data. Missing data yields an `Unavailable` signal rather
+/// than being skipped.
+fn assess_risk(pairs: &[Pair], security: Option<&TokenSecurity>, now_ms: i64) -> Vec<RiskSignal> {
+    let mut signals = Vec::new();
+
+    // Liquidity across all pairs
+    let liquidity: f64 = pairs.iter().filter_map(|p| p.liquidity.usd).sum();
+    signals.push(if pairs.is_empty() {
+        RiskSignal::new("Liquidity", RiskLevel::High, "no DEX pairs found - the token may not be tradable")
+    } else if liquidity < 10_000.0 {
+        RiskSignal::new("Liquidity", RiskLevel::High, format!("${} total - very thin, easy to drain or manipulate", format_number(liquidity)))
+    } else if liquidity < 50_000.0 {
+        RiskSignal::new("Liquidity", RiskLevel::Medium, format!("${} total", format_number(liquidity)))
+    } else {
+        RiskSignal::new("Liquidity", RiskLevel::Low, format!("${} total", format_number(liquidity)))
+    });
+
+    // Age of the oldest pair
+    signals.push(match pairs.iter().filter_map(|p| p.pair_created_at).min() {
+        Some(created) => {
+            let hours = (now_ms - created).max(0) / 3_600_000;
+            let age = if hours < 48 { format!("{}h old", hours) } else { format!("{}d old", hours / 24) };
+            let level = match hours {
+                h if h < 24 => RiskLevel::High,
+                h if h < 24 * 7 => RiskLevel::Medium,
+                _ => RiskLevel::Low,
+            };
+            RiskSignal::new("Pair age", level, age)
+        }
+        None => RiskSignal::new("Pair age", RiskLevel::Unavailable, "pair creation time not reported"),
+    });
+
+    // Buys without sells is the classic honeypot pattern
+    let (buys, sells) = pairs
+        .iter()
+        .filter_map(|p| p.txns.h24.as_ref())
+        .fold((0, 0), |(b, s), t| (b + t.buys.unwrap_or(0), s + t.sells.unwrap_or(0)));
+    signals.push(if buys + sells == 0 {
+        RiskSignal::new("Sell activity", RiskLevel::Unavailable, "no trades in the last 24h to judge from")
+    } else if sells == 0 && buys >= 10 {
+        RiskSignal::new("Sell activity", RiskLevel::High, format!("{} buys and 0 sells in 24h - possible honeypot", buys))
+    } else if (sells as f64) < buys as f64 * 0.1 {
+        RiskSignal::new("Sell activity", RiskLevel::Medium, format!("{} buys vs {} sells in 24h - very few sells", buys, sells))
+    } else {
+        RiskSignal::new("Sell activity", RiskLevel::Low, format!("{} buys / {} sells in 24h", buys, sells))
+    });
+
+    let Some(security) = security else {
+        for name in ["Honeypot check", "Taxes", "Holder concentration", "Liquidity lock", "Contract"] {
+            signals.push(RiskSignal::new(name, RiskLevel::Unavailable, "no token security data for this token/chain"));
+        }
+        return signals;
+    };
+
+    // Honeypot simulation
+    signals.push(match (flag(&security.is_honeypot), flag(&security.cannot_sell_all)) {
+        (Some(true), _) => RiskSignal::new("Honeypot check", RiskLevel::High, "flagged as a honeypot - tokens likely cannot be sold"),
+        (_, Some(true)) => RiskSignal::new("Honeypot check", RiskLevel::High, "holders cannot sell their full balance"),
+        (Some(false), _) => RiskSignal::new("Honeypot check", RiskLevel::Low, "sell simulation passed"),
+        (None, _) => RiskSignal::new("Honeypot check", RiskLevel::Unavailable, "sell simulation not reported"),
+    });
+
+    // Buy / sell taxes
+    let tax = |v: &Option<String>| v.as_deref().and_then(|t| t.parse::<f64>().ok());
+    signals.push(match (tax(&security.buy_tax), tax(&security.sell_tax)) {
+        (None, None) => RiskSignal::new("Taxes", RiskLevel::Unavailable, "buy/sell tax not reported"),
+        (buy, sell) => {
+            let max = buy.unwrap_or(0.0).max(sell.unwrap_or(0.0));
+            let level = if max > 0.3 {
+                RiskLevel::High
+            } else if max > 0.1 {
+                RiskLevel::Medium
+            } else {
+                RiskLevel::Low
+            };
+            let pct = |t: Option<f64>| t.map(|t| format!("{:.1}%", t * 100.0)).unwrap_or_else(|| "?".to_string());
+            RiskSignal::new("Taxes", level, format!("buy {} / sell {}", pct(buy), pct(sell)))
+        }
+    });
+
+    // Top holders, excluding burn addresses and locked/contract holdings (LP pools, lockers)
+    if security.holders.is_empty() {
+        signals.push(RiskSignal::new("Holder concentration", RiskLevel::Unavailable, "holder list not reported"));
+    } else {
+        let top: f64 = security
+            .holders
+            .iter()
+            .filter(|h| !h.is_burn() && h.is_locked != Some(1) && h.is_contract != Some(1))
+            .take(10)
+            .map(SecurityHolder::fraction)
+            .sum();
+        let level = if top > 0.5 {
+            RiskLevel::High
+        } else if top > 0.25 {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        };
+        let holders = security
+            .holder_count
+            .as_deref()
+            .map(|c| format!(" ({} holders)", c))
+            .unwrap_or_default();
+        signals.push(RiskSignal::new(
+            "Holder concentration",
+            level,
+            format!("top wallets hold {:.1}% of supply{}", top * 100.0, holders),
+        ));
+    }
+
+    // Share of LP tokens locked or burned
+    if security.lp_holders.is_empty() {
+        signals.push(RiskSignal::new(
+            "Liquidity lock",
+            RiskLevel::Unavailable,
+            "LP holders not reported (e.g. concentrated-liquidity pools) - lock status unknown",
+        ));
+    } else {
+        let secured: f64 = security
+            .lp_holders
+            .iter()
+            .filter(|h| h.is_burn() || h.is_locked == Some(1))
+            .map(SecurityHolder::fraction)
+            .sum();
+        let level = if secured >= 0.9 {
+            RiskLevel::Low
+        } else if secured >= 0.5 {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::High
+        };
+        signals.push(RiskSignal::new(
+            "Liquidity lock",
+            level,
+            format!("{:.1}% of LP locked or burned", secured * 100.0),
+        ));
+    }
+
+    // Contract red flags
+    let mut flags = Vec::new();
+    let mut level = RiskLevel::Low;
+    match flag(&security.is_open_source) {
+        Some(false) => {
+            flags.push("source not verified");
+            level = RiskLevel::High;
+        }
+        None => {
+            flags.push("verification status unknown");
+            level = RiskLevel::Unavailable;
+        }
+        Some(true) => {}
+    }
+    if flag(&security.is_mintable) == Some(true) {
+        flags.push("owner can mint");
+        level = level.max(RiskLevel::Medium);
+    }
+    signals.push(RiskSignal::new(
+        "Contract",
+        level,
+        if flags.is_empty() { "verified, not mintable".to_string() } else { flags.join(", ") },
+    ));
+
+    signals
+}
+
+/// One-line verdict: the worst signal, noting any that couldn't be checked
+fn risk_verdict(signals: &[RiskSignal]) -> String {
+    let worst = signals
+        .iter()
+        .map(|s| s.level)
+        .filter(|l| *l != RiskLevel::Unavailable)
+        .max()
+        .unwrap_or(RiskLevel::Unavailable);
+    let unavailable = signals.iter().filter(|s| s.level == RiskLevel::Unavailable).count();
+
+    let verdict = match worst {
+        RiskLevel::High => "HIGH RISK - do not buy without explicit user confirmation",
+        RiskLevel::Medium => "ELEVATED RISK - proceed with caution",
+        RiskLevel::Low => "No red flags in the checked signals",
+        RiskLevel::Unavailable => "UNKNOWN - no signals could be checked",
+    };
+    if unavailable > 0 && worst != RiskLevel::Unavailable {
+        format!("{} ({} signal(s) could not be checked - this is not a guarantee of safety)", verdict, unavailable)
+    } else {
+        verdict.to_string()
+    }
+}
+
 fn format_number(n: f64) -> String {
     if n >= 1_000_000_000.0 {
         format!("{:.2}B", n / 1_000_000_000.0)
@@ -268,6 +633,10 @@ impl Tool for DexScreenerTool {
         self.definition.clone()
     }
 
+    fn is_mutating(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, params: Value, _context: &ToolContext) -> ToolResult {
         let params: Params = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -396,6 +765,64 @@ impl Tool for DexScreenerTool {
                 ToolResult::success(out).with_metadata(json!({"chain": chain, "pair": address}))
             }
 
+            "check_token" => {
+                let chain = match &params.chain {
+                    Some(c) if !c.is_empty() => c,
+                    _ => return ToolResult::error("'chain' required (ethereum, base, solana, etc.)"),
+                };
+                let address = match &params.address {
+                    Some(a) if !a.is_empty() => a,
+                    _ => return ToolResult::error("'address' required (token contract address)"),
+                };
+
+                let url = format!("{}/tokens/v1/{}/{}", BASE_URL, chain, address);
+
+                let resp = match client.get(&url).send().await {
+                    Ok(r) => r,
+                    Err(e) => return ToolResult::error(format!("Request failed: {}", e)),
+                };
+
+                if !resp.status().is_success() {
+                    return ToolResult::error(format!("API error: {}", resp.status()));
+                }
+
+                let pairs: Vec<Pair> = match resp.json().await {
+                    Ok(d) => d,
+                    Err(e) => return ToolResult::error(format!("Parse error: {}", e)),
+                };
+
+                let security = fetch_token_security(&client, chain, address).await;
+                let signals = assess_risk(&pairs, security.as_ref(), chrono::Utc::now().timestamp_millis());
+                let verdict = risk_verdict(&signals);
+
+                let symbol = pairs
+                    .iter()
+                    .filter_map(|p| p.base_token.as_ref())
+                    .find(|t| t.address.as_deref().is_some_and(|a| a.eq_ignore_ascii_case(address)))
+                    .and_then(|t| t.symbol.clone())
+                    .unwrap_or_else(|| address.to_string());
+
+                let mut out = format!("Risk check for {} on {}:\n\n", symbol, chain);
+                for signal in &signals {
+                    out.push_str(&format!("[{}] {}: {}\n", signal.level.label(), signal.name, signal.detail));
+                }
+                out.push_str(&format!("\nVerdict: {}\n", verdict));
+                if security.is_none() {
+                    out.push_str("Token security data (honeypot simulation, taxes, holders, LP locks) is unavailable for this token/chain.\n");
+                }
+
+                ToolResult::success(out).with_metadata(json!({
+                    "chain": chain,
+                    "address": address,
+                    "verdict": verdict,
+                    "signals": signals.iter().map(|s| json!({
+                        "name": s.name,
+                        "level": s.level.label(),
+                        "detail": s.detail,
+                    })).collect::<Vec<_>>(),
+                }))
+            }
+
             // Keep "trending" as alias for backwards compatibility, but prefer "boosted"
             "boosted" | "trending" => {
                 let url = format!("{}/token-boosts/top/v1", BASE_URL);
@@ -470,7 +897,70 @@ impl Tool for DexScreenerTool {
                 }))
             }
 
-            _ => ToolResult::error(format!("Unknown action '{}'. Use: search, token, pair, boosted", params.action)),
+            _ => ToolResult::error(format!("Unknown action '{}'. Use: search, token, pair, check_token, boosted", params.action)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: i64 = 3_600_000;
+
+    fn pair(liquidity: f64, created_hours_ago: i64, buys: u64, sells: u64) -> Pair {
+        serde_json::from_value(json!({
+            "liquidity": { "usd": liquidity },
+            "pairCreatedAt": 1_000 * HOUR_MS - created_hours_ago * HOUR_MS,
+            "txns": { "h24": { "buys": buys, "sells": sells } },
+        }))
+        .unwrap()
+    }
+
+    fn level(signals: &[RiskSignal], name: &str) -> RiskLevel {
+        signals.iter().find(|s| s.name == name).unwrap().level
+    }
+
+    #[test]
+    fn test_honeypot_pattern_without_security_data() {
+        let signals = assess_risk(&[pair(5_000.0, 2, 40, 0)], None, 1_000 * HOUR_MS);
+
+        assert_eq!(level(&signals, "Liquidity"), RiskLevel::High);
+        assert_eq!(level(&signals, "Pair age"), RiskLevel::High);
+        assert_eq!(level(&signals, "Sell activity"), RiskLevel::High);
+        assert_eq!(level(&signals, "Honeypot check"), RiskLevel::Unavailable);
+        assert_eq!(level(&signals, "Liquidity lock"), RiskLevel::Unavailable);
+        assert!(risk_verdict(&signals).starts_with("HIGH RISK"));
+    }
+
+    #[test]
+    fn test_established_token_with_security_data() {
+        let security: TokenSecurity = serde_json::from_value(json!({
+            "is_honeypot": "0",
+            "buy_tax": "0",
+            "sell_tax": "0.01",
+            "is_open_source": "1",
+            "is_mintable": "0",
+            "holders": [
+                { "address": "0x000000000000000000000000000000000000dead", "percent": "0.4" },
+                { "address": "0xaaaa", "percent": "0.05", "is_locked": 0, "is_contract": 0 },
+            ],
+            "lp_holders": [
+                { "address": "0xlocker", "percent": "0.95", "is_locked": 1 },
+            ],
+        }))
+        .unwrap();
+        let signals = assess_risk(&[pair(2_000_000.0, 24 * 90, 500, 450)], Some(&security), 1_000 * HOUR_MS);
+
+        assert!(signals.iter().all(|s| s.level == RiskLevel::Low), "{:?}", signals);
+        assert_eq!(risk_verdict(&signals), "No red flags in the checked signals");
+    }
+
+    #[test]
+    fn test_unchecked_signals_are_called_out() {
+        let signals = assess_risk(&[pair(2_000_000.0, 24 * 90, 500, 450)], None, 1_000 * HOUR_MS);
+        let verdict = risk_verdict(&signals);
+        assert!(verdict.contains("could not be checked"), "{}", verdict);
+        assert!(verdict.contains("not a guarantee of safety"));
+    }
+}
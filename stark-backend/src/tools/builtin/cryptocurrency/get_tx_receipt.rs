@@ -0,0 +1,486 @@
+//! Get Transaction Receipt tool - check the outcome of a broadcast transaction
+//!
+//! Fetches the receipt for a transaction hash (or a queued transaction's
+//! UUID), reports success/revert and gas used, and decodes the emitted logs.
+//! Logs are matched against the standard ERC-20/WETH events and every event
+//! in the loaded ABIs; amounts of tokens in tokens.ron are formatted with
+//! their decimals and symbol, so a swap shows the amounts actually moved.
+//!
+//! A transaction without a receipt is reported as not yet mined.
+
+use super::network_lookup::resolve_network;
+use super::token_lookup::find_token_by_address;
+use super::web3_tx::SendEthTool;
+use crate::tools::abis;
+use crate::tools::registry::Tool;
+use crate::tools::rpc_config::resolve_rpc_from_context;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tx_queue::QueuedTxStatus;
+use crate::x402::X402EvmRpc;
+use async_trait::async_trait;
+use ethers::abi::{Abi, Event, RawLog, Token};
+use ethers::types::{Address, Log, TransactionReceipt, H256, U256, U64};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Events decoded even when no loaded ABI defines them
+const STANDARD_EVENTS: &[&str] = &[
+    "event Transfer(address indexed from, address indexed to, uint256 value)",
+    "event Approval(address indexed owner, address indexed spender, uint256 value)",
+    "event Deposit(address indexed dst, uint256 wad)",
+    "event Withdrawal(address indexed src, uint256 wad)",
+];
+
+/// A log decoded against a known event
+#[derive(Debug)]
+struct DecodedLog {
+    address: Address,
+    event: String,
+    params: Vec<(String, Token)>,
+}
+
+/// Get Transaction Receipt tool
+pub struct GetTxReceiptTool {
+    definition: ToolDefinition,
+    abis_dir: PathBuf,
+}
+
+impl GetTxReceiptTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "tx_hash".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Transaction hash (0x-prefixed). Use either 'tx_hash' or 'uuid'.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "uuid".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "UUID of a broadcast transaction from the queue. Its hash and network are used.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "network".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Network the transaction was sent on. Defaults to the selected network. Ignored with 'uuid'.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        GetTxReceiptTool {
+            definition: ToolDefinition {
+                name: "get_tx_receipt".to_string(),
+                description: "Get a transaction's receipt: success/revert, gas used, and decoded event logs (e.g. token Transfers with human-readable amounts). Use after broadcasting to see what a swap actually moved. Reports 'pending' if the transaction isn't mined yet.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Finance,
+            },
+            abis_dir: crate::tools::presets::abis_dir(),
+        }
+    }
+
+    /// Get private key from environment (pays for x402 RPC calls)
+    fn get_private_key() -> Result<String, String> {
+        crate::config::burner_wallet_private_key()
+            .ok_or_else(|| "BURNER_WALLET_BOT_PRIVATE_KEY not set".to_string())
+    }
+
+    /// Known events by topic0: the standard events, then those of every loaded ABI
+    fn known_events(abis_dir: &Path) -> HashMap<H256, Vec<Event>> {
+        let mut events: Vec<Event> = ethers::abi::parse_abi(STANDARD_EVENTS)
+            .map(|abi| abi.events().cloned().collect())
+            .unwrap_or_default();
+
+        for name in abis::list_abis(abis_dir) {
+            let abi = abis::load_abi(abis_dir, &name)
+                .and_then(|f| serde_json::from_value::<Abi>(Value::Array(f.abi)).map_err(|e| e.to_string()));
+            match abi {
+                Ok(abi) => events.extend(abi.events().cloned()),
+                Err(e) => log::warn!("[get_tx_receipt] Skipping ABI '{}': {}", name, e),
+            }
+        }
+
+        let mut by_topic: HashMap<H256, Vec<Event>> = HashMap::new();
+        for event in events {
+            let candidates = by_topic.entry(event.signature()).or_default();
+            // The same event often appears in several ABIs
+            if !candidates.iter().any(|e| e.inputs == event.inputs) {
+                candidates.push(event);
+            }
+        }
+        by_topic
+    }
+
+    /// Decode a log against the candidates for its topic0. Events sharing a
+    /// signature (ERC-20 vs ERC-721 Transfer) differ in indexed params, so
+    /// the first candidate that parses wins.
+    fn decode_log(log: &Log, events: &HashMap<H256, Vec<Event>>) -> Option<DecodedLog> {
+        let candidates = events.get(log.topics.first()?)?;
+        candidates.iter().find_map(|event| {
+            let raw = RawLog { topics: log.topics.clone(), data: log.data.to_vec() };
+            let parsed = event.parse_log(raw).ok()?;
+            Some(DecodedLog {
+                address: log.address,
+                event: event.name.clone(),
+                params: parsed.params.into_iter().map(|p| (p.name, p.value)).collect(),
+            })
+        })
+    }
+
+    /// Format a raw amount of `token` with its decimals and symbol when known
+    fn format_amount(network: &str, token: Address, amount: U256) -> String {
+        match find_token_by_address(network, &format!("{:?}", token)) {
+            Some((symbol, info)) => match ethers::utils::format_units(amount, info.decimals as u32) {
+                Ok(formatted) => format!("{} {}", formatted, symbol),
+                Err(_) => format!("{} (raw {})", amount, symbol),
+            },
+            None => format!("{} (raw, token {:?})", amount, token),
+        }
+    }
+
+    fn token_to_value(token: &Token) -> Value {
+        match token {
+            Token::Address(a) => json!(format!("{:?}", a)),
+            Token::Uint(n) => json!(n.to_string()),
+            Token::Int(n) => json!(ethers::types::I256::from_raw(*n).to_string()),
+            Token::Bool(b) => json!(b),
+            Token::String(s) => json!(s),
+            Token::Bytes(b) | Token::FixedBytes(b) => json!(format!("0x{}", hex::encode(b))),
+            Token::Array(arr) | Token::FixedArray(arr) | Token::Tuple(arr) => {
+                json!(arr.iter().map(Self::token_to_value).collect::<Vec<_>>())
+            }
+        }
+    }
+
+    /// One-line, human-readable description of a decoded log
+    fn describe(network: &str, log: &DecodedLog) -> String {
+        let param = |i: usize| log.params.get(i).map(|(_, v)| v.clone());
+        match (log.event.as_str(), param(0), param(1), param(2)) {
+            ("Transfer", Some(Token::Address(from)), Some(Token::Address(to)), Some(Token::Uint(v))) => {
+                format!("Transfer {} from {:?} to {:?}", Self::format_amount(network, log.address, v), from, to)
+            }
+            ("Approval", Some(Token::Address(owner)), Some(Token::Address(spender)), Some(Token::Uint(v))) => {
+                let amount = if v == U256::MAX {
+                    "unlimited".to_string()
+                } else {
+                    Self::format_amount(network, log.address, v)
+                };
+                format!("Approval of {} by {:?} for {:?}", amount, owner, spender)
+            }
+            ("Deposit", Some(Token::Address(dst)), Some(Token::Uint(v)), None) => {
+                format!("Wrap {} to {:?}", Self::format_amount(network, log.address, v), dst)
+            }
+            ("Withdrawal", Some(Token::Address(src)), Some(Token::Uint(v)), None) => {
+                format!("Unwrap {} from {:?}", Self::format_amount(network, log.address, v), src)
+            }
+            _ => {
+                let params: Vec<String> = log
+                    .params
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, Self::token_to_value(value)))
+                    .collect();
+                format!("{}({}) on {:?}", log.event, params.join(", "), log.address)
+            }
+        }
+    }
+
+    /// Token amounts the sender sent and received, from its Transfer logs
+    fn movements(network: &str, wallet: Address, logs: &[DecodedLog]) -> (Vec<String>, Vec<String>) {
+        let mut sent = Vec::new();
+        let mut received = Vec::new();
+        for log in logs.iter().filter(|l| l.event == "Transfer") {
+            if let [(_, Token::Address(from)), (_, Token::Address(to)), (_, Token::Uint(v))] = log.params.as_slice() {
+                let amount = Self::format_amount(network, log.address, *v);
+                if *from == wallet {
+                    sent.push(amount.clone());
+                }
+                if *to == wallet {
+                    received.push(amount);
+                }
+            }
+        }
+        (sent, received)
+    }
+
+    fn explorer_url(network: &str, tx_hash: &str) -> String {
+        if network == "mainnet" {
+            format!("https://etherscan.io/tx/{}", tx_hash)
+        } else {
+            format!("https://basescan.org/tx/{}", tx_hash)
+        }
+    }
+
+    fn format_receipt(&self, network: &str, tx_hash: &str, receipt: &TransactionReceipt) -> ToolResult {
+        let success = receipt.status == Some(U64::from(1));
+        let status = if success { "success" } else { "reverted" };
+
+        let events = Self::known_events(&self.abis_dir);
+        let decoded: Vec<DecodedLog> = receipt.logs.iter().filter_map(|l| Self::decode_log(l, &events)).collect();
+        let undecoded = receipt.logs.len() - decoded.len();
+        let (sent, received) = Self::movements(network, receipt.from, &decoded);
+
+        let mut msg = String::new();
+        msg.push_str(&format!("TRANSACTION {}\n\n", status.to_uppercase()));
+        msg.push_str(&format!("Hash: {}\n", tx_hash));
+        msg.push_str(&format!("Explorer: {}\n", Self::explorer_url(network, tx_hash)));
+        msg.push_str(&format!("Network: {}\n", network));
+        msg.push_str(&format!("From: {:?}\n", receipt.from));
+        if let Some(to) = receipt.to {
+            msg.push_str(&format!("To: {:?}\n", to));
+        }
+        if let Some(block) = receipt.block_number {
+            msg.push_str(&format!("Block: {}\n", block));
+        }
+
+        msg.push_str("\n--- Gas ---\n");
+        if let Some(gas_used) = receipt.gas_used {
+            msg.push_str(&format!("Gas Used: {}\n", gas_used));
+        }
+        if let Some(price) = receipt.effective_gas_price {
+            msg.push_str(&format!("Effective Price: {}\n", SendEthTool::format_gwei(&price.to_string())));
+            if let Some(gas_used) = receipt.gas_used {
+                msg.push_str(&format!("Fee: {}\n", SendEthTool::format_eth(&(gas_used * price).to_string())));
+            }
+        }
+
+        if !sent.is_empty() || !received.is_empty() {
+            msg.push_str("\n--- Token Movements (sender) ---\n");
+            for amount in &sent {
+                msg.push_str(&format!("Sent: {}\n", amount));
+            }
+            for amount in &received {
+                msg.push_str(&format!("Received: {}\n", amount));
+            }
+        }
+
+        if !receipt.logs.is_empty() {
+            msg.push_str(&format!("\n--- Events ({}) ---\n", receipt.logs.len()));
+            for log in &decoded {
+                msg.push_str(&format!("{}\n", Self::describe(network, log)));
+            }
+            if undecoded > 0 {
+                msg.push_str(&format!("{} log(s) not matching any known event\n", undecoded));
+            }
+        }
+
+        let events_json: Vec<Value> = decoded
+            .iter()
+            .map(|log| {
+                let params: serde_json::Map<String, Value> = log
+                    .params
+                    .iter()
+                    .map(|(name, value)| (name.clone(), Self::token_to_value(value)))
+                    .collect();
+                json!({
+                    "address": format!("{:?}", log.address),
+                    "event": log.event,
+                    "params": params,
+                    "summary": Self::describe(network, log),
+                })
+            })
+            .collect();
+
+        ToolResult::success(msg).with_metadata(json!({
+            "tx_hash": tx_hash,
+            "network": network,
+            "status": status,
+            "block_number": receipt.block_number.map(|b| b.as_u64()),
+            "gas_used": receipt.gas_used.map(|g| g.to_string()),
+            "effective_gas_price": receipt.effective_gas_price.map(|p| p.to_string()),
+            "events": events_json,
+            "undecoded_logs": undecoded,
+            "sent": sent,
+            "received": received,
+        }))
+    }
+}
+
+impl Default for GetTxReceiptTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GetTxReceiptParams {
+    tx_hash: Option<String>,
+    uuid: Option<String>,
+    network: Option<String>,
+}
+
+#[async_trait]
+impl Tool for GetTxReceiptTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: GetTxReceiptParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        // Resolve hash and network, from the queue when given a UUID
+        let (tx_hash, network) = match (&params.uuid, &params.tx_hash) {
+            (Some(uuid), _) => {
+                let queued_tx = match context.tx_queue.as_ref().and_then(|q| q.get(uuid)) {
+                    Some(tx) => tx,
+                    None => return ToolResult::error(format!(
+                        "Transaction with UUID '{}' not found. Use list_queued_web3_tx to see available transactions.",
+                        uuid
+                    )),
+                };
+                match queued_tx.tx_hash {
+                    Some(hash) => (hash, queued_tx.network),
+                    None => return ToolResult::error(format!(
+                        "Transaction {} has not been broadcast (status: {}). Broadcast it first with broadcast_web3_tx.",
+                        uuid, queued_tx.status
+                    )),
+                }
+            }
+            (None, Some(hash)) => {
+                match resolve_network(params.network.as_deref(), context.selected_network.as_deref()) {
+                    Ok(n) => (hash.clone(), n),
+                    Err(e) => return ToolResult::error(e),
+                }
+            }
+            (None, None) => return ToolResult::error("Provide either 'tx_hash' or 'uuid'"),
+        };
+
+        let hash: H256 = match tx_hash.parse() {
+            Ok(h) => h,
+            Err(_) => return ToolResult::error(format!("Invalid transaction hash: {}", tx_hash)),
+        };
+        let tx_hash = format!("{:?}", hash);
+
+        let rpc_config = resolve_rpc_from_context(&context.extra, &network);
+        let private_key = match Self::get_private_key() {
+            Ok(pk) => pk,
+            Err(e) => return ToolResult::error(e),
+        };
+        let rpc = match X402EvmRpc::new_with_config(&private_key, &network, &rpc_config) {
+            Ok(r) => r,
+            Err(e) => return ToolResult::error(format!("Failed to initialize RPC: {}", e)),
+        };
+
+        let receipt = match rpc.get_full_transaction_receipt(hash).await {
+            Ok(Some(r)) => r,
+            Ok(None) => {
+                return ToolResult::success(format!(
+                    "TRANSACTION PENDING\n\nHash: {}\nNetwork: {}\n\nNot yet mined (or unknown to this RPC). Check again shortly.",
+                    tx_hash, network
+                ))
+                .with_metadata(json!({
+                    "tx_hash": tx_hash,
+                    "network": network,
+                    "status": "pending",
+                }));
+            }
+            Err(e) => return ToolResult::error(format!("Failed to fetch receipt: {}", e)),
+        };
+
+        // A broadcast whose confirmation timed out is still marked as broadcast
+        if let (Some(uuid), Some(tx_queue)) = (&params.uuid, &context.tx_queue) {
+            if tx_queue.get(uuid).is_some_and(|tx| tx.status == QueuedTxStatus::Broadcast) {
+                if receipt.status == Some(U64::from(1)) {
+                    tx_queue.mark_confirmed(uuid);
+                } else {
+                    tx_queue.mark_failed(uuid, "Transaction reverted on-chain");
+                }
+            }
+        }
+
+        self.format_receipt(&network, &tx_hash, &receipt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address_topic(address: Address) -> H256 {
+        H256::from(address)
+    }
+
+    fn transfer_log(token: Address, from: Address, to: Address, amount: u64) -> Log {
+        let mut data = [0u8; 32];
+        U256::from(amount).to_big_endian(&mut data);
+        Log {
+            address: token,
+            topics: vec![
+                ethers::utils::keccak256("Transfer(address,address,uint256)").into(),
+                address_topic(from),
+                address_topic(to),
+            ],
+            data: data.to_vec().into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decodes_standard_events_without_abis() {
+        let dir = tempfile::tempdir().unwrap();
+        let events = GetTxReceiptTool::known_events(dir.path());
+
+        let wallet = Address::repeat_byte(0x11);
+        let pool = Address::repeat_byte(0x22);
+        let token = Address::repeat_byte(0x33);
+
+        let decoded = GetTxReceiptTool::decode_log(&transfer_log(token, wallet, pool, 1500), &events).unwrap();
+        assert_eq!(decoded.event, "Transfer");
+        assert_eq!(decoded.params[2], ("value".to_string(), Token::Uint(U256::from(1500))));
+
+        let summary = GetTxReceiptTool::describe("base", &decoded);
+        assert!(summary.starts_with("Transfer 1500 (raw"));
+
+        // Unknown topic0 is left undecoded
+        let mut unknown = transfer_log(token, wallet, pool, 1);
+        unknown.topics[0] = H256::repeat_byte(0xab);
+        assert!(GetTxReceiptTool::decode_log(&unknown, &events).is_none());
+    }
+
+    #[test]
+    fn test_movements_for_sender() {
+        let dir = tempfile::tempdir().unwrap();
+        let events = GetTxReceiptTool::known_events(dir.path());
+
+        let wallet = Address::repeat_byte(0x11);
+        let pool = Address::repeat_byte(0x22);
+        let logs: Vec<DecodedLog> = [
+            transfer_log(Address::repeat_byte(0x33), wallet, pool, 10),
+            transfer_log(Address::repeat_byte(0x44), pool, wallet, 7),
+            transfer_log(Address::repeat_byte(0x55), pool, Address::repeat_byte(0x66), 3),
+        ]
+        .iter()
+        .filter_map(|l| GetTxReceiptTool::decode_log(l, &events))
+        .collect();
+
+        let (sent, received) = GetTxReceiptTool::movements("base", wallet, &logs);
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].starts_with("10 "));
+        assert_eq!(received.len(), 1);
+        assert!(received[0].starts_with("7 "));
+    }
+}
@@ -0,0 +1,616 @@
+//! Limit order tool for price-triggered DEX swaps
+//!
+//! On-chain swaps are market orders, so limit orders are emulated: the order
+//! is stored with a USD price target, the scheduler polls DexScreener, and
+//! when the price crosses the target it runs the swap tools itself, in a
+//! fixed order (token_lookup, x402_fetch swap_quote, approve_and_swap and,
+//! with auto_broadcast, broadcast_web3_tx), without involving the model.
+
+use super::dexscreener::DexScreenerTool;
+use super::network_lookup::{resolve_network, supported_network_ids};
+use super::portfolio::{dexscreener_chain, native_price_address};
+use super::token_lookup::TokenLookupTool;
+use super::transfer::TransferToken;
+use super::wallet_balances::NATIVE_TOKEN_ADDRESS;
+use crate::db::tables::limit_orders::{
+    LimitOrder, LimitOrderStatus, NewLimitOrder, TriggerDirection,
+};
+use crate::db::Database;
+use crate::tools::registry::{Tool, ToolRegistry};
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::wallet::{WalletSigner, PRIMARY_WALLET, WALLET_PARAM_DESCRIPTION};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Pending orders whose trigger price has been crossed, with the price seen.
+/// Prices are fetched once per watched token.
+pub async fn find_triggered_orders(db: &Database) -> Vec<(LimitOrder, f64)> {
+    let pending = match db.list_limit_orders(Some(LimitOrderStatus::Pending)) {
+        Ok(orders) => orders,
+        Err(e) => {
+            log::error!("[limit_order] Failed to list pending orders: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut prices: HashMap<(String, String), Option<f64>> = HashMap::new();
+    let mut triggered = Vec::new();
+    for order in pending {
+        let key = (order.network.clone(), order.trigger_address.clone());
+        if !prices.contains_key(&key) {
+            let chain = dexscreener_chain(&order.network);
+            let price = match DexScreenerTool::token_price_usd(chain, &order.trigger_address).await {
+                Ok(price) => price,
+                Err(e) => {
+                    log::warn!("[limit_order] Price check for {} failed: {}", order.trigger_token, e);
+                    None
+                }
+            };
+            prices.insert(key.clone(), price);
+        }
+
+        if let Some(price) = prices[&key] {
+            if order.trigger_direction.is_crossed(price, order.target_price_usd) {
+                triggered.push((order, price));
+            }
+        }
+    }
+    triggered
+}
+
+/// Transactions queued for a triggered order
+#[derive(Debug, Clone)]
+pub struct QueuedSwap {
+    /// Approval queued ahead of the swap, if the allowance was short
+    pub approval_uuid: Option<String>,
+    pub swap_uuid: String,
+    /// Outcome of broadcasting the swap, for auto_broadcast orders
+    pub broadcast: Option<Result<String, String>>,
+}
+
+impl QueuedSwap {
+    /// One-line outcome stored as the order's result
+    pub fn summary(&self) -> String {
+        let mut summary = format!("queued swap tx {}", self.swap_uuid);
+        if let Some(ref uuid) = self.approval_uuid {
+            summary.push_str(&format!(" after approval tx {}", uuid));
+        }
+        match &self.broadcast {
+            Some(Ok(status)) => summary.push_str(&format!(", broadcast: {}", status)),
+            Some(Err(e)) => summary.push_str(&format!(", broadcast failed: {}", e)),
+            None => summary.push_str(", awaiting broadcast"),
+        }
+        summary
+    }
+}
+
+/// Run one tool of a triggered order's swap, failing on an error result
+async fn run_step(tools: &ToolRegistry, name: &str, params: Value, context: &ToolContext) -> Result<ToolResult, String> {
+    let result = tools.execute(name, params, context, None).await;
+    if result.success {
+        Ok(result)
+    } else {
+        Err(format!("{} failed: {}", name, result.content))
+    }
+}
+
+/// Queue the swap for a triggered order. `context` carries the order's
+/// wallet address, network and transaction queue; the quote and token
+/// registers are set on it as the tools run. Broadcast failures don't fail
+/// the order: the swap is queued and can still be broadcast by hand.
+pub async fn execute_swap(order: &LimitOrder, tools: &ToolRegistry, context: &ToolContext) -> Result<QueuedSwap, String> {
+    let sell = TransferToken::resolve(&order.sell_token, Some(&order.network))?;
+    let sell_amount = sell.to_raw(&order.sell_amount)?;
+
+    run_step(tools, "token_lookup", json!({
+        "symbol": order.sell_token,
+        "network": order.network,
+        "cache_as": "sell_token",
+    }), context).await?;
+    run_step(tools, "token_lookup", json!({
+        "symbol": order.buy_token,
+        "network": order.network,
+        "cache_as": "buy_token",
+    }), context).await?;
+    context.set_register("sell_amount", json!(sell_amount), "limit_order");
+
+    run_step(tools, "x402_fetch", json!({
+        "preset": "swap_quote",
+        "network": order.network,
+        "cache_as": "swap_quote",
+    }), context).await?;
+
+    let queued = run_step(tools, "approve_and_swap", json!({
+        "network": order.network,
+        "wallet": order.wallet,
+        "approval": "exact",
+    }), context).await?;
+    let metadata = queued.metadata.unwrap_or_default();
+    let swap_uuid = metadata
+        .get("swap_uuid")
+        .and_then(|v| v.as_str())
+        .ok_or("approve_and_swap returned no swap UUID")?
+        .to_string();
+    let approval_uuid = metadata.get("approval_uuid").and_then(|v| v.as_str()).map(str::to_string);
+
+    let mut swap = QueuedSwap { approval_uuid, swap_uuid, broadcast: None };
+    if order.auto_broadcast {
+        swap.broadcast = Some(broadcast_swap(&swap, tools, context).await);
+    }
+    Ok(swap)
+}
+
+/// Broadcast the approval (if any) and then the swap, returning the swap's status
+async fn broadcast_swap(swap: &QueuedSwap, tools: &ToolRegistry, context: &ToolContext) -> Result<String, String> {
+    let mut status = String::new();
+    for uuid in swap.approval_uuid.iter().chain(std::iter::once(&swap.swap_uuid)) {
+        let result = run_step(tools, "broadcast_web3_tx", json!({ "uuid": uuid }), context).await?;
+        status = result
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("status"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("broadcast")
+            .to_string();
+    }
+    Ok(status)
+}
+
+/// Address whose DexScreener price stands for `symbol` (the wrapped token for
+/// the native currency)
+fn price_address(symbol: &str, network: &str) -> Option<String> {
+    let token = TokenLookupTool::lookup(symbol, network)?;
+    if token.address.eq_ignore_ascii_case(NATIVE_TOKEN_ADDRESS) {
+        native_price_address(network)
+    } else {
+        Some(token.address)
+    }
+}
+
+fn format_order(order: &LimitOrder) -> String {
+    let mut line = format!(
+        "#{} [{}] sell {} {} for {} on {} when {} is {} ${} (wallet '{}'{})",
+        order.id,
+        order.status.as_str(),
+        order.sell_amount,
+        order.sell_token,
+        order.buy_token,
+        order.network,
+        order.trigger_token,
+        order.trigger_direction.as_str(),
+        order.target_price_usd,
+        order.wallet,
+        if order.auto_broadcast { ", auto-broadcast" } else { "" }
+    );
+    if let Some(price) = order.triggered_price_usd {
+        line.push_str(&format!(" - triggered at ${}", price));
+    }
+    if let Some(ref result) = order.result {
+        line.push_str(&format!(" - {}", result));
+    }
+    line
+}
+
+/// Limit order tool
+pub struct LimitOrderTool {
+    definition: ToolDefinition,
+}
+
+impl LimitOrderTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "create: place a limit order | list: show orders | cancel: cancel a pending order".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec![
+                    "create".to_string(),
+                    "list".to_string(),
+                    "cancel".to_string(),
+                ]),
+            },
+        );
+
+        properties.insert(
+            "sell_token".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Token symbol to sell (create), e.g. 'USDC'".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "buy_token".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Token symbol to buy (create), e.g. 'WETH'".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "amount".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Amount of sell_token to sell, in human units (create), e.g. '100'".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "target_price".to_string(),
+            PropertySchema {
+                schema_type: "number".to_string(),
+                description: "USD price of the trigger token that fills the order (create)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "trigger_token".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Token whose USD price is watched. Defaults to buy_token (buy when it drops to the target).".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "trigger".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Fill when the price is 'below' or 'above' the target. Defaults to 'below' when watching buy_token and 'above' when watching sell_token.".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec!["below".to_string(), "above".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "auto_broadcast".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Broadcast the swap as soon as it is queued. Default false: the swap is queued for the user to review and broadcast.".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "order_id".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "Order ID (cancel)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "status".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Only list orders with this status (list)".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec![
+                    "pending".to_string(),
+                    "triggered".to_string(),
+                    "filled".to_string(),
+                    "failed".to_string(),
+                    "cancelled".to_string(),
+                ]),
+            },
+        );
+
+        properties.insert(
+            "network".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: format!(
+                    "Network (create). Configured networks: {}. If not specified, uses the currently selected network or defaults to 'base'.",
+                    supported_network_ids().join(", ")
+                ),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "wallet".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: WALLET_PARAM_DESCRIPTION.to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        LimitOrderTool {
+            definition: ToolDefinition {
+                name: "limit_order".to_string(),
+                description: "Place, list or cancel limit orders for DEX swaps. A pending order is checked against the DexScreener USD price every minute; when the price crosses the target the swap is prepared once (and broadcast only if auto_broadcast is set). Use for requests like \"buy ETH with 100 USDC if it drops to $2500\".".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["action".to_string()],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+}
+
+impl Default for LimitOrderTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LimitOrderParams {
+    action: String,
+    sell_token: Option<String>,
+    buy_token: Option<String>,
+    amount: Option<String>,
+    target_price: Option<f64>,
+    trigger_token: Option<String>,
+    trigger: Option<String>,
+    #[serde(default)]
+    auto_broadcast: bool,
+    order_id: Option<i64>,
+    status: Option<String>,
+    network: Option<String>,
+    wallet: Option<String>,
+}
+
+impl LimitOrderTool {
+    async fn create(&self, params: LimitOrderParams, db: &Database, context: &ToolContext) -> ToolResult {
+        let (sell_token, buy_token, amount, target) =
+            match (params.sell_token, params.buy_token, params.amount, params.target_price) {
+                (Some(s), Some(b), Some(a), Some(t)) => (s.to_uppercase(), b.to_uppercase(), a, t),
+                _ => return ToolResult::error("'sell_token', 'buy_token', 'amount' and 'target_price' are required to create an order"),
+            };
+        if !(target > 0.0) {
+            return ToolResult::error("'target_price' must be a positive USD price");
+        }
+        if !amount.trim().parse::<f64>().is_ok_and(|a| a > 0.0) {
+            return ToolResult::error(format!("Invalid amount '{}'. Use a positive number like '100' or '0.5'.", amount));
+        }
+
+        let network = match resolve_network(params.network.as_deref(), context.selected_network.as_deref()) {
+            Ok(n) => n,
+            Err(e) => return ToolResult::error(e),
+        };
+        for symbol in [&sell_token, &buy_token] {
+            if TokenLookupTool::lookup(symbol, &network).is_none() {
+                return ToolResult::error(format!(
+                    "Unknown token '{}' on {}. Available: {}",
+                    symbol,
+                    network,
+                    TokenLookupTool::list_available(&network).join(", ")
+                ));
+            }
+        }
+
+        let trigger_token = params.trigger_token.map(|t| t.to_uppercase()).unwrap_or_else(|| buy_token.clone());
+        let default_direction = if trigger_token == sell_token {
+            TriggerDirection::Above
+        } else {
+            TriggerDirection::Below
+        };
+        let direction = match params.trigger.as_deref() {
+            Some(t) => match TriggerDirection::from_str(t) {
+                Some(d) => d,
+                None => return ToolResult::error("'trigger' must be 'below' or 'above'"),
+            },
+            None => default_direction,
+        };
+        let trigger_address = match price_address(&trigger_token, &network) {
+            Some(a) => a,
+            None => return ToolResult::error(format!("No price source for '{}' on {}", trigger_token, network)),
+        };
+
+        let wallet = context.wallet_name(params.wallet.as_deref());
+        if let Err(e) = WalletSigner::for_wallet(wallet.as_deref()).await {
+            return ToolResult::error(e);
+        }
+
+        let current = DexScreenerTool::token_price_usd(dexscreener_chain(&network), &trigger_address)
+            .await
+            .ok()
+            .flatten();
+        if current.is_none() {
+            return ToolResult::error(format!(
+                "DexScreener has no USD price for {} on {}, so the order could never trigger",
+                trigger_token, network
+            ));
+        }
+
+        let order = NewLimitOrder {
+            network,
+            wallet: wallet.unwrap_or_else(|| PRIMARY_WALLET.to_string()),
+            sell_token,
+            buy_token,
+            sell_amount: amount.trim().to_string(),
+            trigger_token,
+            trigger_address,
+            trigger_direction: direction,
+            target_price_usd: target,
+            auto_broadcast: params.auto_broadcast,
+            channel_id: context.channel_id,
+        };
+        let order = match db.create_limit_order(&order) {
+            Ok(o) => o,
+            Err(e) => return ToolResult::error(format!("Failed to save order: {}", e)),
+        };
+
+        let mut message = format!("Limit order placed: {}", format_order(&order));
+        if let Some(price) = current {
+            message.push_str(&format!("\nCurrent {} price: ${}", order.trigger_token, price));
+            if order.trigger_direction.is_crossed(price, order.target_price_usd) {
+                message.push_str(" - already past the target, so it will fill on the next check (within a minute).");
+            }
+        }
+        ToolResult::success(message).with_metadata(json!(order))
+    }
+}
+
+#[async_trait]
+impl Tool for LimitOrderTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: LimitOrderParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => return ToolResult::error("Database not available"),
+        };
+
+        match params.action.as_str() {
+            "create" => self.create(params, db, context).await,
+            "list" => {
+                let status = match params.status.as_deref() {
+                    Some(s) => match LimitOrderStatus::from_str(s) {
+                        Some(status) => Some(status),
+                        None => return ToolResult::error(format!("Unknown status '{}'", s)),
+                    },
+                    None => None,
+                };
+                let orders = match db.list_limit_orders(status) {
+                    Ok(o) => o,
+                    Err(e) => return ToolResult::error(format!("Failed to list orders: {}", e)),
+                };
+                if orders.is_empty() {
+                    return ToolResult::success("No limit orders");
+                }
+                let lines: Vec<String> = orders.iter().map(format_order).collect();
+                ToolResult::success(format!("Limit orders:\n{}", lines.join("\n")))
+                    .with_metadata(json!({ "orders": orders }))
+            }
+            "cancel" => {
+                let id = match params.order_id {
+                    Some(id) => id,
+                    None => return ToolResult::error("'order_id' is required to cancel"),
+                };
+                match db.cancel_limit_order(id) {
+                    Ok(true) => ToolResult::success(format!("Limit order #{} cancelled", id)),
+                    Ok(false) => match db.get_limit_order(id) {
+                        Ok(Some(order)) => ToolResult::error(format!(
+                            "Limit order #{} is already {} and can't be cancelled",
+                            id,
+                            order.status.as_str()
+                        )),
+                        _ => ToolResult::error(format!("Limit order #{} not found", id)),
+                    },
+                    Err(e) => ToolResult::error(format!("Failed to cancel order: {}", e)),
+                }
+            }
+            other => ToolResult::error(format!(
+                "Unknown action '{}'. Use create, list or cancel.",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_order() -> NewLimitOrder {
+        NewLimitOrder {
+            network: "base".to_string(),
+            wallet: PRIMARY_WALLET.to_string(),
+            sell_token: "USDC".to_string(),
+            buy_token: "WETH".to_string(),
+            sell_amount: "100".to_string(),
+            trigger_token: "WETH".to_string(),
+            trigger_address: "0x4200000000000000000000000000000000000006".to_string(),
+            trigger_direction: TriggerDirection::Below,
+            target_price_usd: 2500.0,
+            auto_broadcast: false,
+            channel_id: None,
+        }
+    }
+
+    #[test]
+    fn test_order_fires_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap();
+        let order = db.create_limit_order(&new_order()).unwrap();
+        assert_eq!(order.status, LimitOrderStatus::Pending);
+
+        assert!(db.mark_limit_order_triggered(order.id, 2490.0).unwrap());
+        assert!(!db.mark_limit_order_triggered(order.id, 2480.0).unwrap());
+        assert!(db.complete_limit_order(order.id, LimitOrderStatus::Filled, "queued tx abc").unwrap());
+
+        let order = db.get_limit_order(order.id).unwrap().unwrap();
+        assert_eq!(order.status, LimitOrderStatus::Filled);
+        assert_eq!(order.triggered_price_usd, Some(2490.0));
+        assert!(!db.cancel_limit_order(order.id).unwrap());
+        assert!(db.list_limit_orders(Some(LimitOrderStatus::Pending)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cancel_pending_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap();
+        let order = db.create_limit_order(&new_order()).unwrap();
+
+        assert!(db.cancel_limit_order(order.id).unwrap());
+        assert!(!db.mark_limit_order_triggered(order.id, 2000.0).unwrap());
+        assert_eq!(db.list_limit_orders(None).unwrap()[0].status, LimitOrderStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_queued_swap_summary() {
+        let mut swap = QueuedSwap {
+            approval_uuid: Some("a1".to_string()),
+            swap_uuid: "s1".to_string(),
+            broadcast: None,
+        };
+        assert_eq!(swap.summary(), "queued swap tx s1 after approval tx a1, awaiting broadcast");
+
+        swap.approval_uuid = None;
+        swap.broadcast = Some(Ok("broadcasting".to_string()));
+        assert_eq!(swap.summary(), "queued swap tx s1, broadcast: broadcasting");
+    }
+
+    #[test]
+    fn test_trigger_direction() {
+        assert!(TriggerDirection::Below.is_crossed(2499.0, 2500.0));
+        assert!(!TriggerDirection::Below.is_crossed(2501.0, 2500.0));
+        assert!(TriggerDirection::Above.is_crossed(2500.0, 2500.0));
+        assert!(!TriggerDirection::Above.is_crossed(2400.0, 2500.0));
+    }
+}
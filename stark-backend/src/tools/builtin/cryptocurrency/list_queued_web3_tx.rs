@@ -1,6 +1,8 @@
 //! List queued Web3 transactions
 //!
-//! Shows transactions that have been signed but not yet broadcast.
+//! Shows transactions that have been signed but not yet broadcast. Pending
+//! transactions past their expiry are marked expired first, so stale ones are
+//! never offered for broadcast.
 
 use crate::gateway::protocol::GatewayEvent;
 use super::web3_tx::SendEthTool;
@@ -132,6 +134,7 @@ impl Tool for ListQueuedWeb3TxTool {
             Some(q) => q,
             None => return ToolResult::error("Transaction queue not available. Contact administrator."),
         };
+        tx_queue.expire_stale();
 
         // If specific UUID requested, get that transaction
         if let Some(uuid) = params.uuid {
@@ -149,6 +152,9 @@ impl Tool for ListQueuedWeb3TxTool {
                     msg.push_str(&format!("Max Fee: {} ({})\n", tx.max_fee_per_gas, SendEthTool::format_gwei(&tx.max_fee_per_gas)));
                     msg.push_str(&format!("Priority Fee: {} ({})\n", tx.max_priority_fee_per_gas, SendEthTool::format_gwei(&tx.max_priority_fee_per_gas)));
                     msg.push_str(&format!("Created: {}\n", tx.created_at.format("%Y-%m-%d %H:%M:%S UTC")));
+                    if tx.status == QueuedTxStatus::Pending {
+                        msg.push_str(&format!("Expires: {}\n", tx.expires_at.format("%Y-%m-%d %H:%M:%S UTC")));
+                    }
 
                     if let Some(ref tx_hash) = tx.tx_hash {
                         msg.push_str(&format!("Tx Hash: {}\n", tx_hash));
@@ -166,6 +172,10 @@ impl Tool for ListQueuedWeb3TxTool {
                     if tx.status == QueuedTxStatus::Pending {
                         msg.push_str("\n--- Action ---\n");
                         msg.push_str(&format!("To broadcast: use broadcast_web3_tx with uuid: {}\n", tx.uuid));
+                    } else if tx.status == QueuedTxStatus::Expired {
+                        msg.push_str("\n--- Action ---\n");
+                        msg.push_str("This transaction is STALE: its nonce may no longer be valid, so it can't be broadcast.\n");
+                        msg.push_str(&format!("To re-sign with a fresh nonce: use broadcast_web3_tx with uuid: {} and resign: true\n", tx.uuid));
                     }
 
                     ToolResult::success(msg).with_metadata(json!({
@@ -179,7 +189,8 @@ impl Tool for ListQueuedWeb3TxTool {
                         "tx_hash": tx.tx_hash,
                         "explorer_url": tx.explorer_url,
                         "error": tx.error,
-                        "created_at": tx.created_at.to_rfc3339()
+                        "created_at": tx.created_at.to_rfc3339(),
+                        "expires_at": tx.expires_at.to_rfc3339()
                     }))
                 },
                 None => ToolResult::error(format!("Transaction with UUID '{}' not found.", uuid)),
@@ -224,11 +235,15 @@ impl Tool for ListQueuedWeb3TxTool {
         let pending_count = transactions.iter().filter(|t| t.status == QueuedTxStatus::Pending).count();
         let confirmed_count = transactions.iter().filter(|t| t.status == QueuedTxStatus::Confirmed).count();
         let failed_count = transactions.iter().filter(|t| t.status == QueuedTxStatus::Failed).count();
+        let expired_count = transactions.iter().filter(|t| t.status == QueuedTxStatus::Expired).count();
 
         // Build response
         let mut msg = String::new();
         msg.push_str(&format!("QUEUED TRANSACTIONS ({} shown)\n", transactions.len()));
-        msg.push_str(&format!("Pending: {} | Confirmed: {} | Failed: {}\n\n", pending_count, confirmed_count, failed_count));
+        msg.push_str(&format!(
+            "Pending: {} | Confirmed: {} | Failed: {} | Expired: {}\n\n",
+            pending_count, confirmed_count, failed_count, expired_count
+        ));
 
         for tx in &transactions {
             let status_indicator = match tx.status {
@@ -237,7 +252,7 @@ impl Tool for ListQueuedWeb3TxTool {
                 QueuedTxStatus::Broadcast => "[BROADCAST]",
                 QueuedTxStatus::Confirmed => "[CONFIRMED]",
                 QueuedTxStatus::Failed => "[FAILED]",
-                QueuedTxStatus::Expired => "[EXPIRED]",
+                QueuedTxStatus::Expired => "[EXPIRED - STALE]",
             };
 
             msg.push_str(&format!("{} {}\n", status_indicator, tx.uuid));
@@ -267,10 +282,16 @@ impl Tool for ListQueuedWeb3TxTool {
             msg.push_str("\n");
         }
 
-        if pending_count > 0 {
+        if pending_count > 0 || expired_count > 0 {
             msg.push_str("--- Actions ---\n");
-            msg.push_str("To broadcast a pending transaction:\n");
-            msg.push_str("  broadcast_web3_tx with uuid: <UUID>\n");
+            if pending_count > 0 {
+                msg.push_str("To broadcast a pending transaction:\n");
+                msg.push_str("  broadcast_web3_tx with uuid: <UUID>\n");
+            }
+            if expired_count > 0 {
+                msg.push_str("Expired transactions are stale and can't be broadcast. To re-sign one with a fresh nonce:\n");
+                msg.push_str("  broadcast_web3_tx with uuid: <UUID> and resign: true\n");
+            }
             msg.push_str("To view details:\n");
             msg.push_str("  list_queued_web3_tx with uuid: <UUID>\n");
         }
@@ -288,15 +309,13 @@ impl Tool for ListQueuedWeb3TxTool {
                 "tx_hash": tx.tx_hash,
                 "explorer_url": tx.explorer_url,
                 "error": tx.error,
-                "created_at": tx.created_at.to_rfc3339()
+                "created_at": tx.created_at.to_rfc3339(),
+                "expires_at": tx.expires_at.to_rfc3339()
             })
         }).collect();
 
         // In partner mode, emit confirmation event for first pending tx
-        let is_rogue_mode = context.extra
-            .get("rogue_mode_enabled")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let is_rogue_mode = context.is_rogue_mode();
 
         if !is_rogue_mode && pending_count > 0 {
             if let Some(first_pending) = transactions.iter()
@@ -339,6 +358,7 @@ impl Tool for ListQueuedWeb3TxTool {
             "pending_count": pending_count,
             "confirmed_count": confirmed_count,
             "failed_count": failed_count,
+            "expired_count": expired_count,
             "transactions": tx_data
         }))
     }
@@ -3,38 +3,61 @@
 //! Tools for interacting with blockchain networks, EVM transactions,
 //! token operations, x402 payment protocol, and prediction markets.
 
+pub(crate) mod address_book;
+mod approve_and_swap;
 mod bridge_usdc;
 mod broadcast_web3_tx;
 mod decode_calldata;
 mod dexscreener;
+mod get_tx_receipt;
+pub mod limit_order;
 mod list_queued_web3_tx;
 pub mod network_lookup;
+pub mod portfolio;
 mod polymarket_trade;
+pub mod prediction_market;
+mod register_list;
 mod register_set;
+mod resolve_name;
 mod select_web3_network;
 mod to_raw_amount;
 pub mod token_lookup;
+pub mod transfer;
+mod transfer_limits;
+mod validation;
+mod wallet_balances;
 mod web3_function_call;
 pub mod web3_tx;
 mod x402_agent_invoke;
 mod x402_fetch;
+mod x402_inspect;
 mod x402_post;
 mod x402_rpc;
 
+pub use address_book::AddressBookTool;
+pub use approve_and_swap::ApproveAndSwapTool;
 pub use bridge_usdc::BridgeUsdcTool;
 pub use broadcast_web3_tx::BroadcastWeb3TxTool;
 pub use decode_calldata::DecodeCalldataTool;
 pub use dexscreener::DexScreenerTool;
+pub use get_tx_receipt::GetTxReceiptTool;
+pub use limit_order::LimitOrderTool;
 pub use list_queued_web3_tx::ListQueuedWeb3TxTool;
 pub use network_lookup::load_networks;
 pub use polymarket_trade::PolymarketTradeTool;
+pub use portfolio::PortfolioTool;
+pub use register_list::RegisterListTool;
 pub use register_set::RegisterSetTool;
+pub use resolve_name::ResolveNameTool;
 pub use select_web3_network::SelectWeb3NetworkTool;
 pub use to_raw_amount::ToRawAmountTool;
 pub use token_lookup::{load_tokens, TokenLookupTool};
+pub use validation::ValidationTool;
+pub use wallet_balances::WalletBalancesTool;
 pub use web3_function_call::Web3FunctionCallTool;
 pub use web3_tx::SendEthTool;
 pub use x402_agent_invoke::X402AgentInvokeTool;
 pub use x402_fetch::X402FetchTool;
+pub use x402_inspect::X402InspectTool;
 pub use x402_post::X402PostTool;
 pub use x402_rpc::X402RpcTool;
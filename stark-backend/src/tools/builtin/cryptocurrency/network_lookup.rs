@@ -1,15 +1,17 @@
-//! Network Lookup for context bank scanning
+//! Network Lookup
 //!
 //! Loads network configuration from config/networks.ron at startup.
-//! Used by context bank to detect network names in user input.
+//! This is the source of truth for supported networks (chain ID, native
+//! token, block explorer, default RPC). Used by web3 tools to validate network names and
+//! by context bank to detect network names in user input.
 
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
-use std::sync::OnceLock;
+use std::sync::{Arc, RwLock};
 
-/// Global network storage (loaded once at startup)
-static NETWORKS: OnceLock<HashMap<String, NetworkInfo>> = OnceLock::new();
+/// Global network storage (loaded at startup, swapped whole on reload)
+static NETWORKS: RwLock<Option<Arc<HashMap<String, NetworkInfo>>>> = RwLock::new(None);
 
 /// Network info loaded from config
 #[derive(Debug, Clone, Deserialize)]
@@ -18,6 +20,83 @@ pub struct NetworkInfo {
     pub chain_id: u64,
     #[serde(default)]
     pub aliases: Vec<String>,
+    #[serde(default = "default_native_token")]
+    pub native_token: String,
+    /// Block explorer base URL, e.g. "https://basescan.org"
+    #[serde(default)]
+    pub explorer: Option<String>,
+    /// Free public RPC used when no configured provider serves this network
+    #[serde(default)]
+    pub rpc_url: Option<String>,
+}
+
+fn default_native_token() -> String {
+    "ETH".to_string()
+}
+
+fn network(
+    name: &str,
+    chain_id: u64,
+    native_token: &str,
+    explorer: &str,
+    aliases: &[&str],
+) -> NetworkInfo {
+    NetworkInfo {
+        name: name.to_string(),
+        chain_id,
+        aliases: aliases.iter().map(|a| a.to_string()).collect(),
+        native_token: native_token.to_string(),
+        explorer: Some(explorer.to_string()),
+        rpc_url: None,
+    }
+}
+
+/// Built-in networks used when config/networks.ron is missing
+fn default_networks() -> HashMap<String, NetworkInfo> {
+    let mut defaults = HashMap::new();
+    defaults.insert(
+        "base".to_string(),
+        network("Base", 8453, "ETH", "https://basescan.org", &["base mainnet"]),
+    );
+    defaults.insert(
+        "mainnet".to_string(),
+        network("Ethereum Mainnet", 1, "ETH", "https://etherscan.io", &["ethereum", "eth"]),
+    );
+    defaults.insert(
+        "polygon".to_string(),
+        network("Polygon", 137, "POL", "https://polygonscan.com", &["matic"]),
+    );
+    defaults.insert(
+        "arbitrum".to_string(),
+        network("Arbitrum One", 42161, "ETH", "https://arbiscan.io", &["arbitrum one"]),
+    );
+    defaults.insert(
+        "optimism".to_string(),
+        network("Optimism", 10, "ETH", "https://optimistic.etherscan.io", &["op mainnet"]),
+    );
+    defaults
+}
+
+/// Read, parse and validate config/networks.ron
+fn parse_networks(networks_path: &Path) -> Result<HashMap<String, NetworkInfo>, String> {
+    let content = std::fs::read_to_string(networks_path)
+        .map_err(|e| format!("Failed to read {:?}: {}", networks_path, e))?;
+
+    let networks: HashMap<String, NetworkInfo> = ron::from_str(&content)
+        .map_err(|e| format!("Failed to parse {:?}: {}", networks_path, e))?;
+
+    if networks.is_empty() {
+        return Err(format!("No networks defined in {:?}", networks_path));
+    }
+    if let Some((id, _)) = networks.iter().find(|(_, info)| info.chain_id == 0) {
+        return Err(format!("Network '{}' has no chain_id", id));
+    }
+
+    Ok(networks)
+}
+
+fn set_networks(networks: HashMap<String, NetworkInfo>) {
+    *NETWORKS.write().unwrap() = Some(Arc::new(networks));
 }
 
 /// Load networks from config directory. Logs warning if config file is missing.
@@ -26,57 +105,49 @@ pub fn load_networks(config_dir: &Path) {
 
     if !networks_path.exists() {
         log::warn!("[networks] Config file not found: {:?}, using defaults", networks_path);
-        let mut defaults = HashMap::new();
-        defaults.insert("base".to_string(), NetworkInfo {
-            name: "Base".to_string(),
-            chain_id: 8453,
-            aliases: vec!["base mainnet".to_string()],
-        });
-        defaults.insert("mainnet".to_string(), NetworkInfo {
-            name: "Ethereum Mainnet".to_string(),
-            chain_id: 1,
-            aliases: vec!["ethereum".to_string(), "eth".to_string()],
-        });
-        let _ = NETWORKS.set(defaults);
+        set_networks(default_networks());
         return;
     }
 
-    let content = match std::fs::read_to_string(&networks_path) {
-        Ok(c) => c,
-        Err(e) => {
-            log::error!("[networks] Failed to read {:?}: {}", networks_path, e);
-            return;
-        }
-    };
-
-    let networks: HashMap<String, NetworkInfo> = match ron::from_str(&content) {
-        Ok(n) => n,
-        Err(e) => {
-            log::error!("[networks] Failed to parse {:?}: {}", networks_path, e);
-            return;
+    match parse_networks(&networks_path) {
+        Ok(networks) => {
+            log::info!(
+                "[networks] Loaded {} networks from {:?}",
+                networks.len(),
+                networks_path
+            );
+            set_networks(networks);
         }
-    };
+        Err(e) => log::error!("[networks] {}", e),
+    }
+}
 
-    log::info!(
-        "[networks] Loaded {} networks from {:?}",
-        networks.len(),
-        networks_path
-    );
+/// Reload networks from config directory, returning the number of networks.
+/// The file is validated first; on error the loaded networks are kept.
+pub fn reload_networks(config_dir: &Path) -> Result<usize, String> {
+    let networks_path = config_dir.join("networks.ron");
+    if !networks_path.exists() {
+        return Err(format!("Config file not found: {:?}", networks_path));
+    }
 
-    let _ = NETWORKS.set(networks);
+    let networks = parse_networks(&networks_path)?;
+    let count = networks.len();
+    log::info!("[networks] Reloaded {} networks from {:?}", count, networks_path);
+    set_networks(networks);
+    Ok(count)
 }
 
 /// Get all network identifiers with their names (for context bank scanning)
 /// Returns a list of (identifier, display_name) pairs including aliases
 pub fn get_all_network_identifiers() -> Vec<(String, String)> {
-    let networks = match NETWORKS.get() {
+    let networks = match NETWORKS.read().unwrap().clone() {
         Some(n) => n,
         None => return Vec::new(),
     };
 
     let mut result = Vec::new();
 
-    for (id, info) in networks {
+    for (id, info) in networks.iter() {
         // Add the primary identifier
         result.push((id.clone(), info.name.clone()));
         
@@ -88,3 +159,114 @@ pub fn get_all_network_identifiers() -> Vec<(String, String)> {
 
     result
 }
+
+/// Get loaded networks, falling back to built-in defaults if not loaded
+fn get_networks() -> Arc<HashMap<String, NetworkInfo>> {
+    if let Some(networks) = NETWORKS.read().unwrap().clone() {
+        return networks;
+    }
+    NETWORKS
+        .write()
+        .unwrap()
+        .get_or_insert_with(|| Arc::new(default_networks()))
+        .clone()
+}
+
+/// Look up a configured network by identifier (case-insensitive)
+pub fn get_network(id: &str) -> Option<NetworkInfo> {
+    let id = id.trim().to_lowercase();
+    get_networks().get(&id).cloned()
+}
+
+/// Sorted identifiers of all configured networks
+pub fn supported_network_ids() -> Vec<String> {
+    let mut ids: Vec<String> = get_networks().keys().cloned().collect();
+    ids.sort();
+    ids
+}
+
+/// Resolve a network from an explicit param, the context's selected network,
+/// or the default (base), validating it against the configured networks.
+/// Returns the canonical identifier.
+pub fn resolve_network(param_network: Option<&str>, context_network: Option<&str>) -> Result<String, String> {
+    // Priority: explicit param > context selected > default (Base)
+    let network_str = param_network
+        .or(context_network)
+        .unwrap_or("base");
+
+    let id = network_str.trim().to_lowercase();
+    if get_networks().contains_key(&id) {
+        Ok(id)
+    } else {
+        Err(format!(
+            "Invalid network '{}'. Must be one of: {}",
+            network_str,
+            supported_network_ids().join(", ")
+        ))
+    }
+}
+
+/// Chain ID for a configured network
+pub fn chain_id_for(id: &str) -> Option<u64> {
+    get_network(id).map(|n| n.chain_id)
+}
+
+/// Native token symbol of a network, "ETH" if it isn't configured
+pub fn native_token_for(id: &str) -> String {
+    get_network(id)
+        .map(|n| n.native_token)
+        .unwrap_or_else(default_native_token)
+}
+
+/// Block explorer URL for a transaction, if the network has an explorer
+pub fn explorer_tx_url(id: &str, tx_hash: &str) -> Option<String> {
+    let explorer = get_network(id)?.explorer?;
+    Some(format!("{}/tx/{}", explorer.trim_end_matches('/'), tx_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_network() {
+        load_networks(Path::new("../config"));
+
+        assert_eq!(resolve_network(Some("Polygon"), None).unwrap(), "polygon");
+        assert_eq!(resolve_network(None, Some("arbitrum")).unwrap(), "arbitrum");
+        assert_eq!(resolve_network(None, None).unwrap(), "base");
+
+        let err = resolve_network(Some("fantom"), None).unwrap_err();
+        assert!(err.contains("Invalid network 'fantom'"));
+        assert!(err.contains("optimism"));
+    }
+
+    #[test]
+    fn test_network_info() {
+        load_networks(Path::new("../config"));
+
+        assert_eq!(chain_id_for("polygon"), Some(137));
+        assert_eq!(chain_id_for("optimism"), Some(10));
+        assert_eq!(get_network("polygon").unwrap().native_token, "POL");
+        assert_eq!(get_network("base").unwrap().native_token, "ETH");
+        assert_eq!(native_token_for("polygon"), "POL");
+        assert_eq!(
+            explorer_tx_url("arbitrum", "0xabc").as_deref(),
+            Some("https://arbiscan.io/tx/0xabc")
+        );
+        assert_eq!(explorer_tx_url("fantom", "0xabc"), None);
+    }
+
+    #[test]
+    fn test_reload_keeps_networks_on_invalid_file() {
+        load_networks(Path::new("../config"));
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(reload_networks(dir.path()).unwrap_err().contains("not found"));
+
+        std::fs::write(dir.path().join("networks.ron"), "{}").unwrap();
+        assert!(reload_networks(dir.path()).unwrap_err().contains("No networks defined"));
+
+        assert_eq!(chain_id_for("polygon"), Some(137));
+    }
+}
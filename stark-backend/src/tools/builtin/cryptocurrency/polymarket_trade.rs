@@ -1,7 +1,11 @@
 //! Polymarket CLOB Trading Tool
 //!
 //! Enables trading on Polymarket prediction markets using the polymarket-client-sdk.
-//! Uses the burner wallet private key for signing EIP-712 orders.
+//! EIP-712 orders are signed by the bot wallet (see `crate::wallet`).
+//!
+//! Discovery, pricing, order placement and positions go through [`Polymarket`],
+//! the `PredictionMarket` implementation (see `prediction_market`). Order
+//! management, balances and fill tracking are Polymarket-specific.
 //!
 //! ## Discovery Actions (no auth required)
 //! - `search_markets`: Search markets by keyword
@@ -14,24 +18,43 @@
 //! - `cancel_order`: Cancel a specific order by ID
 //! - `cancel_all`: Cancel all open orders
 //! - `get_orders`: List open orders
+//! - `get_order`: Fill status of a specific order
 //! - `get_positions`: Get current positions and balances
 //! - `get_balance`: Get USDC balance and allowances on Polygon
+//!
+//! ## Authentication
+//! Authenticated CLOB clients are cached per wallet. A cached client is
+//! dropped when the wallet's address changes (key rotated), and a call that
+//! fails with an auth error evicts it and retries once with a fresh login.
+//!
+//! ## Fill notifications
+//! Placed orders are tracked in the database. The scheduler calls
+//! `poll_fills` to detect new fills and partial fills on tracked orders.
 
+use super::prediction_market::{
+    self, Market, MarketOutcome, MarketParams, MarketPrice, MarketQuery, MarketQuestion, MarketSummary,
+    OrderRequest, OrderSide, PlacedOrder, PredictionMarket, Positions,
+};
+use crate::db::tables::polymarket_orders::TrackedPolymarketOrder;
+use crate::db::Database;
 use crate::tools::registry::Tool;
+use ethers::types::Address;
+use crate::wallet::{AlloyWalletSigner, WalletSigner, PRIMARY_WALLET, WALLET_PARAM_DESCRIPTION};
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::str::FromStr;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 // Polymarket SDK imports - use SDK's re-exports
 use polymarket_client_sdk::auth::state::Authenticated;
-use polymarket_client_sdk::auth::{LocalSigner, Normal, Signer};
+use polymarket_client_sdk::auth::Normal;
 use polymarket_client_sdk::clob::types::request::OrdersRequest;
 use polymarket_client_sdk::clob::types::{OrderType, Side};
 use polymarket_client_sdk::clob::{Client, Config as ClobConfig};
@@ -44,17 +67,70 @@ type AuthenticatedClient = Client<Authenticated<Normal>>;
 /// Polymarket CLOB API base URL
 const CLOB_API_URL: &str = "https://clob.polymarket.com";
 
+/// Gamma API base URL (market discovery)
+const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com";
+
+/// Data API base URL (positions)
+const DATA_API_URL: &str = "https://data-api.polymarket.com";
+
 /// Polymarket trading tool
 pub struct PolymarketTradeTool {
     definition: ToolDefinition,
-    /// Cached authenticated client (lazily initialized)
-    client_cache: Arc<Mutex<Option<CachedClient>>>,
+    market: Polymarket,
+}
+
+/// Polymarket venue: public APIs plus per-wallet authenticated CLOB clients
+pub struct Polymarket {
+    /// Cached authenticated clients by wallet name (lazily initialized)
+    client_cache: Arc<Mutex<HashMap<String, CachedClient>>>,
 }
 
 /// Cached authenticated client
 /// Note: We don't cache the signer since creating it is cheap
 struct CachedClient {
     client: AuthenticatedClient,
+    /// Wallet address the client authenticated as
+    address: Address,
+}
+
+/// Whether a CLOB error means the API credentials are no longer accepted
+fn is_auth_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    ["401", "unauthorized", "invalid api key", "api key expired", "invalid l2", "invalid signature"]
+        .iter()
+        .any(|marker| error.contains(marker))
+}
+
+/// Run `attempt(false)`; if it fails with an auth error, run `attempt(true)`
+/// once, where `true` means "re-authenticate first"
+async fn retry_once_on_auth_error<T, F, Fut>(mut attempt: F) -> Result<T, String>
+where
+    F: FnMut(bool) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    match attempt(false).await {
+        Err(e) if is_auth_error(&e) => {
+            log::warn!("[polymarket] Auth error, re-authenticating once: {}", e);
+            attempt(true).await
+        }
+        result => result,
+    }
+}
+
+fn sdk_side(side: OrderSide) -> Side {
+    match side {
+        OrderSide::Buy => Side::Buy,
+        OrderSide::Sell => Side::Sell,
+    }
+}
+
+fn parse_order_type(order_type: &str) -> Result<OrderType, String> {
+    match order_type.to_uppercase().as_str() {
+        "GTC" => Ok(OrderType::GTC),
+        "FOK" => Ok(OrderType::FOK),
+        "GTD" => Ok(OrderType::GTD),
+        _ => Err(format!("Invalid order_type: {}. Use 'GTC', 'FOK', or 'GTD'", order_type)),
+    }
 }
 
 impl PolymarketTradeTool {
@@ -65,7 +141,7 @@ impl PolymarketTradeTool {
             "action".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Action: search_markets, trending_markets, get_market, get_price (discovery) | place_order, cancel_order, cancel_all, get_orders, get_positions, get_balance (trading)".to_string(),
+                description: "Action: search_markets, trending_markets, get_market, get_price (discovery) | place_order, cancel_order, cancel_all, get_orders, get_order, get_positions, get_balance (trading)".to_string(),
                 default: None,
                 items: None,
                 enum_values: Some(vec![
@@ -79,6 +155,7 @@ impl PolymarketTradeTool {
                     "cancel_order".to_string(),
                     "cancel_all".to_string(),
                     "get_orders".to_string(),
+                    "get_order".to_string(),
                     "get_positions".to_string(),
                     "get_balance".to_string(),
                 ]),
@@ -209,7 +286,29 @@ impl PolymarketTradeTool {
             "order_id".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Order ID for cancellation. Required for cancel_order.".to_string(),
+                description: "Order ID. Required for cancel_order and get_order.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "notify".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Post a message in this channel when the order fills or partially fills (place_order). Default: true.".to_string(),
+                default: Some(json!(true)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "wallet".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: WALLET_PARAM_DESCRIPTION.to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -219,7 +318,7 @@ impl PolymarketTradeTool {
         PolymarketTradeTool {
             definition: ToolDefinition {
                 name: "polymarket_trade".to_string(),
-                description: "Explore and trade on Polymarket prediction markets. Discovery: search_markets, trending_markets, get_market, get_price. Trading: place_order, cancel_order, get_orders, get_positions, get_balance. Trading requires BURNER_WALLET_BOT_PRIVATE_KEY with USDC on Polygon.".to_string(),
+                description: "Explore and trade on Polymarket prediction markets. Discovery: search_markets, trending_markets, get_market, get_price. Trading: place_order, cancel_order, get_orders, get_order (fill status), get_positions, get_balance. Fills on placed orders are reported automatically. Trading requires a configured wallet with USDC on Polygon.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -227,220 +326,64 @@ impl PolymarketTradeTool {
                 },
                 group: ToolGroup::Finance,
             },
-            client_cache: Arc::new(Mutex::new(None)),
+            market: Polymarket::new(),
         }
     }
 
-    /// Get the private key from environment
-    fn get_private_key() -> Result<String, String> {
-        crate::config::burner_wallet_private_key()
-            .ok_or_else(|| "BURNER_WALLET_BOT_PRIVATE_KEY not set. Configure this env var to trade on Polymarket.".to_string())
-    }
-
-    /// Get wallet address from private key
-    fn get_wallet_address() -> Result<String, String> {
-        let pk = Self::get_private_key()?;
-        let pk_clean = pk.strip_prefix("0x").unwrap_or(&pk);
-
-        // Use ethers to derive address (already in deps, simpler than alloy for this)
-        let wallet: ethers::signers::LocalWallet = pk_clean
-            .parse()
-            .map_err(|e| format!("Invalid private key: {}", e))?;
-
-        use ethers::signers::Signer as EthersSigner;
-        Ok(format!("{:?}", wallet.address()))
-    }
-
-    /// Get or create authenticated CLOB client
-    async fn get_authenticated_client(&self) -> Result<AuthenticatedClient, String> {
-        // Check cache first
-        {
-            let cache = self.client_cache.lock().await;
-            if let Some(cached) = cache.as_ref() {
-                return Ok(cached.client.clone());
-            }
-        }
-
-        // Create new authenticated client
-        let pk = Self::get_private_key()?;
-        let pk_clean = pk.strip_prefix("0x").unwrap_or(&pk);
-
-        let signer = LocalSigner::from_str(pk_clean)
-            .map(|s| s.with_chain_id(Some(POLYGON)))
-            .map_err(|e| format!("Invalid private key: {}", e))?;
-
-        let config = ClobConfig::builder()
-            .use_server_time(true)
-            .build();
-
-        let client = Client::new(CLOB_API_URL, config)
-            .map_err(|e| format!("Failed to create CLOB client: {}", e))?
-            .authentication_builder(&signer)
-            .authenticate()
-            .await
-            .map_err(|e| format!("Failed to authenticate with CLOB: {}", e))?;
-
-        // Cache for future use
-        {
-            let mut cache = self.client_cache.lock().await;
-            *cache = Some(CachedClient { client: client.clone() });
-        }
-
-        Ok(client)
-    }
-
-    /// Create a fresh signer for signing operations
-    fn create_signer_for_signing() -> Result<impl Signer + Clone, String> {
-        let pk = Self::get_private_key()?;
-        let pk_clean = pk.strip_prefix("0x").unwrap_or(&pk);
-
-        LocalSigner::from_str(pk_clean)
-            .map(|s| s.with_chain_id(Some(POLYGON)))
-            .map_err(|e| format!("Invalid private key: {}", e))
-    }
-
-    /// Place a limit order on Polymarket
-    async fn place_order(&self, params: &PolymarketParams) -> ToolResult {
-        // Validate required parameters
-        let token_id_str = match &params.token_id {
-            Some(t) => t,
-            None => return ToolResult::error("token_id is required for place_order"),
-        };
-
-        let side_str = match &params.side {
-            Some(s) => s.to_lowercase(),
-            None => return ToolResult::error("side is required for place_order (buy or sell)"),
-        };
-
-        // Price must be between 0.001 and 0.999, rounded to 3 decimal places (tick size 0.001)
-        let price = match params.price {
-            Some(p) if p > 0.0 && p < 1.0 => {
-                // Round to 3 decimal places (Polymarket tick size)
-                (p * 1000.0).round() / 1000.0
-            },
-            Some(p) => return ToolResult::error(format!(
-                "price must be between 0.01 and 0.99, got {}. Price represents probability (0.65 = 65%)",
-                p
-            )),
-            None => return ToolResult::error("price is required for place_order"),
-        };
-
-        // Size is in shares (whole numbers recommended, 2 decimal places max)
-        let size = match params.size {
-            Some(s) if s > 0.0 => {
-                // Round to 2 decimal places
-                (s * 100.0).round() / 100.0
-            },
-            Some(s) => return ToolResult::error(format!("size must be positive, got {}", s)),
-            None => return ToolResult::error("size is required for place_order"),
-        };
-
-        let order_type_str = params.order_type.clone().unwrap_or_else(|| "GTC".to_string());
-
-        // Parse token_id to U256
-        let token_id = match U256::from_str(token_id_str) {
-            Ok(t) => t,
-            Err(e) => return ToolResult::error(format!("Invalid token_id: {}", e)),
-        };
-
-        // Parse side
-        let side = match side_str.as_str() {
-            "buy" => Side::Buy,
-            "sell" => Side::Sell,
-            _ => return ToolResult::error(format!("Invalid side: {}. Use 'buy' or 'sell'", side_str)),
-        };
-
-        // Parse order type
-        let order_type = match order_type_str.to_uppercase().as_str() {
-            "GTC" => OrderType::GTC,
-            "FOK" => OrderType::FOK,
-            "GTD" => OrderType::GTD,
-            _ => return ToolResult::error(format!("Invalid order_type: {}. Use 'GTC', 'FOK', or 'GTD'", order_type_str)),
-        };
-
-        // Convert price and size to Decimal
-        let price_decimal = match Decimal::try_from(price) {
-            Ok(d) => d,
-            Err(e) => return ToolResult::error(format!("Invalid price decimal: {}", e)),
-        };
-
-        let size_decimal = match Decimal::try_from(size) {
-            Ok(d) => d,
-            Err(e) => return ToolResult::error(format!("Invalid size decimal: {}", e)),
-        };
-
-        // Get authenticated client and signer
-        let client = match self.get_authenticated_client().await {
-            Ok(c) => c,
+    /// Place a limit order, tracking it for fill notifications
+    async fn place_order(&self, params: &PolymarketParams, wallet: Option<&str>, context: &ToolContext) -> ToolResult {
+        let order = match prediction_market::order_request(&params.market) {
+            Ok(o) => o,
             Err(e) => return ToolResult::error(e),
         };
 
-        let signer = match Self::create_signer_for_signing() {
-            Ok(s) => s,
+        let placed = match self.market.place_order(&order, wallet).await {
+            Ok(p) => p,
             Err(e) => return ToolResult::error(e),
         };
 
-        // Build the limit order
-        let order = match client
-            .limit_order()
-            .token_id(token_id)
-            .price(price_decimal)
-            .size(size_decimal)
-            .side(side)
-            .order_type(order_type)
-            .build()
-            .await
-        {
-            Ok(o) => o,
-            Err(e) => return ToolResult::error(format!("Failed to build order: {}", e)),
-        };
-
-        // Sign the order
-        let signed_order = match client.sign(&signer, order).await {
-            Ok(s) => s,
-            Err(e) => return ToolResult::error(format!("Failed to sign order: {}", e)),
-        };
-
-        // Submit the order
-        let wallet_address = Self::get_wallet_address().unwrap_or_else(|_| "unknown".to_string());
-        match client.post_order(signed_order).await {
-            Ok(response) => {
-                let usdc_cost = size * price;
-                let result = json!({
-                    "status": "success",
-                    "order_id": response.order_id,
-                    "success": response.success,
-                    "details": {
-                        "token_id": token_id_str,
-                        "side": side_str,
-                        "price": price,
-                        "size": size,
-                        "order_type": order_type_str,
-                        "usdc_cost": format!("{:.2}", usdc_cost),
-                        "potential_payout": format!("{:.2}", size),
-                    },
-                    "wallet": wallet_address,
-                    "network": "polygon"
-                });
-                ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+        // Track the order so the scheduler reports fills
+        if placed.success && !placed.order_id.is_empty() {
+            if let Some(ref db) = context.database {
+                let tracked = TrackedPolymarketOrder {
+                    order_id: placed.order_id.clone(),
+                    wallet: wallet.unwrap_or(PRIMARY_WALLET).to_lowercase(),
+                    token_id: order.token_id.clone(),
+                    side: order.side.as_str().to_string(),
+                    price: order.price.to_string(),
+                    original_size: order.size.to_string(),
+                    size_matched: "0".to_string(),
+                    status: "Live".to_string(),
+                    outcome: None,
+                    is_open: true,
+                    channel_id: if params.notify.unwrap_or(true) { context.channel_id } else { None },
+                    created_at: chrono::Utc::now(),
+                    updated_at: chrono::Utc::now(),
+                };
+                if let Err(e) = db.track_polymarket_order(&tracked) {
+                    log::warn!("[polymarket] Failed to track order {}: {}", placed.order_id, e);
+                }
             }
-            Err(e) => ToolResult::error(format!("Failed to submit order: {}", e))
         }
+
+        prediction_market::order_placed_result(&self.market, &order, &placed)
     }
 
     /// Cancel a specific order
-    async fn cancel_order(&self, params: &PolymarketParams) -> ToolResult {
+    async fn cancel_order(&self, params: &PolymarketParams, wallet: Option<&str>) -> ToolResult {
         let order_id = match &params.order_id {
             Some(id) => id,
             None => return ToolResult::error("order_id is required for cancel_order"),
         };
 
-        let client = match self.get_authenticated_client().await {
-            Ok(c) => c,
-            Err(e) => return ToolResult::error(e),
-        };
+        let cancelled = self
+            .market
+            .with_client(wallet, |client| async move {
+                client.cancel_order(order_id).await.map_err(|e| e.to_string())
+            })
+            .await;
 
-        match client.cancel_order(order_id).await {
+        match cancelled {
             Ok(response) => {
                 let result = json!({
                     "status": "success",
@@ -455,13 +398,15 @@ impl PolymarketTradeTool {
     }
 
     /// Cancel all open orders
-    async fn cancel_all(&self) -> ToolResult {
-        let client = match self.get_authenticated_client().await {
-            Ok(c) => c,
-            Err(e) => return ToolResult::error(e),
-        };
-
-        match client.cancel_all_orders().await {
+    async fn cancel_all(&self, wallet: Option<&str>) -> ToolResult {
+        let cancelled = self
+            .market
+            .with_client(wallet, |client| async move {
+                client.cancel_all_orders().await.map_err(|e| e.to_string())
+            })
+            .await;
+
+        match cancelled {
             Ok(response) => {
                 let result = json!({
                     "status": "success",
@@ -475,16 +420,17 @@ impl PolymarketTradeTool {
     }
 
     /// Get open orders
-    async fn get_orders(&self) -> ToolResult {
-        let client = match self.get_authenticated_client().await {
-            Ok(c) => c,
-            Err(e) => return ToolResult::error(e),
-        };
+    async fn get_orders(&self, wallet: Option<&str>) -> ToolResult {
+        let fetched = self
+            .market
+            .with_client(wallet, |client| async move {
+                client.orders(&OrdersRequest::default(), None).await.map_err(|e| e.to_string())
+            })
+            .await;
 
-        let wallet_address = Self::get_wallet_address().unwrap_or_else(|_| "unknown".to_string());
+        let wallet_address = Polymarket::get_wallet_address(wallet).await.unwrap_or_else(|_| "unknown".to_string());
 
-        let request = OrdersRequest::default();
-        match client.orders(&request, None).await {
+        match fetched {
             Ok(orders) => {
                 let orders_json: Vec<Value> = orders.data.iter().map(|o| {
                     json!({
@@ -512,47 +458,159 @@ impl PolymarketTradeTool {
         }
     }
 
-    /// Get current positions from Data API
-    async fn get_positions(&self) -> ToolResult {
-        let wallet_address = match Self::get_wallet_address() {
-            Ok(addr) => addr,
-            Err(e) => return ToolResult::error(e),
+    /// Get the fill status of a single order
+    async fn get_order(&self, params: &PolymarketParams, wallet: Option<&str>) -> ToolResult {
+        let order_id = match &params.order_id {
+            Some(id) => id,
+            None => return ToolResult::error("order_id is required for get_order"),
         };
 
-        // Fetch positions from Data API
-        let http_client = reqwest::Client::new();
-        let url = format!("https://data-api.polymarket.com/positions?user={}", wallet_address);
+        let fetched = self
+            .market
+            .with_client(wallet, |client| async move {
+                client.order(order_id).await.map_err(|e| e.to_string())
+            })
+            .await;
+
+        match fetched {
+            Ok(o) => {
+                let original = decimal_f64(&o.original_size.to_string());
+                let matched = decimal_f64(&o.size_matched.to_string());
+                let fill = if matched <= 0.0 {
+                    "unfilled"
+                } else if matched >= original {
+                    "filled"
+                } else {
+                    "partially_filled"
+                };
+                let result = json!({
+                    "status": "success",
+                    "order_id": o.id,
+                    "order_status": format!("{:?}", o.status),
+                    "fill": fill,
+                    "filled_pct": if original > 0.0 { format!("{:.1}", matched / original * 100.0) } else { "0.0".to_string() },
+                    "token_id": o.asset_id.to_string(),
+                    "side": format!("{:?}", o.side),
+                    "original_size": o.original_size.to_string(),
+                    "size_matched": o.size_matched.to_string(),
+                    "price": o.price.to_string(),
+                    "outcome": o.outcome,
+                    "created_at": o.created_at,
+                });
+                ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+            }
+            Err(e) => ToolResult::error(format!("Failed to fetch order {}: {}", order_id, e))
+        }
+    }
 
-        match http_client.get(&url).send().await {
-            Ok(response) => {
-                match response.json::<Value>().await {
-                    Ok(positions) => {
-                        let result = json!({
-                            "status": "success",
-                            "wallet": wallet_address,
-                            "positions": positions,
-                        });
-                        ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+    /// Check tracked orders for new fills. Open orders found on the book for
+    /// a tracked wallet are tracked too, from their current fill. Returns one
+    /// entry per order whose matched size grew since the last poll.
+    pub async fn poll_fills(&self, db: &Database) -> Vec<OrderFill> {
+        let tracked = match db.list_open_polymarket_orders() {
+            Ok(t) => t,
+            Err(e) => {
+                log::error!("[polymarket] Failed to list tracked orders: {}", e);
+                return Vec::new();
+            }
+        };
+        let wallets: HashSet<String> = tracked.iter().map(|o| o.wallet.clone()).collect();
+
+        let mut fills = Vec::new();
+        for wallet in wallets {
+            let fetched = self
+                .market
+                .with_client(Some(&wallet), |client| async move {
+                    client.orders(&OrdersRequest::default(), None).await.map_err(|e| e.to_string())
+                })
+                .await;
+            let open = match fetched {
+                Ok(page) => page.data,
+                Err(e) => {
+                    log::warn!("[polymarket] Failed to fetch open orders for '{}': {}", wallet, e);
+                    continue;
+                }
+            };
+
+            let mut on_book = HashSet::new();
+            for o in &open {
+                on_book.insert(o.id.clone());
+                let seen = TrackedPolymarketOrder {
+                    order_id: o.id.clone(),
+                    wallet: wallet.clone(),
+                    token_id: o.asset_id.to_string(),
+                    side: format!("{:?}", o.side).to_lowercase(),
+                    price: o.price.to_string(),
+                    original_size: o.original_size.to_string(),
+                    size_matched: o.size_matched.to_string(),
+                    status: format!("{:?}", o.status),
+                    outcome: Some(o.outcome.clone()),
+                    is_open: true,
+                    channel_id: None,
+                    created_at: chrono::Utc::now(),
+                    updated_at: chrono::Utc::now(),
+                };
+                match tracked.iter().find(|t| t.order_id == o.id) {
+                    Some(previous) => fills.extend(Self::record_poll(db, previous, &seen)),
+                    // Not placed through the tool: start tracking from its current fill
+                    None => {
+                        let _ = db.track_polymarket_order(&seen);
+                    }
+                }
+            }
+
+            // Orders that left the book were filled or cancelled
+            for previous in tracked.iter().filter(|t| t.wallet == wallet && !on_book.contains(&t.order_id)) {
+                let fetched = self
+                    .market
+                    .with_client(Some(&wallet), |client| async move {
+                        client.order(&previous.order_id).await.map_err(|e| e.to_string())
+                    })
+                    .await;
+                match fetched {
+                    Ok(o) => {
+                        let seen = TrackedPolymarketOrder {
+                            size_matched: o.size_matched.to_string(),
+                            status: format!("{:?}", o.status),
+                            outcome: Some(o.outcome.clone()),
+                            is_open: false,
+                            ..previous.clone()
+                        };
+                        fills.extend(Self::record_poll(db, previous, &seen));
                     }
-                    Err(e) => ToolResult::error(format!("Failed to parse positions: {}", e))
+                    Err(e) => log::warn!("[polymarket] Failed to fetch order {}: {}", previous.order_id, e),
                 }
             }
-            Err(e) => ToolResult::error(format!("Failed to fetch positions: {}", e))
         }
+        fills
+    }
+
+    /// Store a polled order state and return a fill if more has matched
+    fn record_poll(db: &Database, previous: &TrackedPolymarketOrder, seen: &TrackedPolymarketOrder) -> Option<OrderFill> {
+        if let Err(e) = db.update_polymarket_order(&seen.order_id, &seen.size_matched, &seen.status, seen.is_open) {
+            log::warn!("[polymarket] Failed to update order {}: {}", seen.order_id, e);
+            return None;
+        }
+        order_fill(previous, seen)
     }
 
     /// Get balance and allowance info
-    async fn get_balance(&self) -> ToolResult {
-        let client = match self.get_authenticated_client().await {
-            Ok(c) => c,
-            Err(e) => return ToolResult::error(e),
-        };
+    async fn get_balance(&self, wallet: Option<&str>) -> ToolResult {
+        use polymarket_client_sdk::clob::types::request::BalanceAllowanceRequest;
 
-        let wallet_address = Self::get_wallet_address().unwrap_or_else(|_| "unknown".to_string());
+        let fetched = self
+            .market
+            .with_client(wallet, |client| async move {
+                client
+                    .balance_allowance(BalanceAllowanceRequest::default())
+                    .await
+                    .map_err(|e| e.to_string())
+            })
+            .await;
 
-        use polymarket_client_sdk::clob::types::request::BalanceAllowanceRequest;
+        let wallet_address = Polymarket::get_wallet_address(wallet).await.unwrap_or_else(|_| "unknown".to_string());
 
-        match client.balance_allowance(BalanceAllowanceRequest::default()).await {
+        match fetched {
             Ok(balance_resp) => {
                 // Convert allowances HashMap to a JSON-friendly format
                 let allowances: serde_json::Map<String, Value> = balance_resp.allowances
@@ -575,150 +633,238 @@ impl PolymarketTradeTool {
             Err(e) => ToolResult::error(format!("Failed to fetch balance: {}", e))
         }
     }
+}
 
-    // ==================== DISCOVERY METHODS ====================
-
-    /// Search markets by keyword (lightweight summaries only)
-    async fn search_markets(&self, params: &PolymarketParams) -> ToolResult {
-        let query = params.query.as_deref().unwrap_or("");
-        let limit = params.limit.unwrap_or(10).min(20);
-        let offset = params.offset.unwrap_or(0);
-        let tag = params.tag.as_deref();
-
-        let http_client = reqwest::Client::new();
+impl Polymarket {
+    pub fn new() -> Self {
+        Polymarket {
+            client_cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
 
-        // Build URL with query params
-        let mut url = format!(
-            "https://gamma-api.polymarket.com/events?active=true&closed=false&limit={}&offset={}",
-            limit, offset
-        );
+    /// Get the signer for a named wallet (`None` for the primary wallet)
+    async fn get_wallet_signer(wallet: Option<&str>) -> Result<WalletSigner, String> {
+        WalletSigner::for_wallet(wallet)
+            .await
+            .map_err(|e| format!("{}. Configure a wallet to trade on Polymarket.", e))
+    }
 
-        if !query.is_empty() {
-            url.push_str(&format!("&_q={}", urlencoding::encode(query)));
-        }
+    /// Get the trading wallet address
+    async fn get_wallet_address(wallet: Option<&str>) -> Result<String, String> {
+        Ok(format!("{:?}", Self::get_wallet_signer(wallet).await?.address()))
+    }
 
-        if let Some(t) = tag {
-            url.push_str(&format!("&tag={}", t));
-        }
+    /// Get or create the authenticated CLOB client for a wallet
+    async fn get_authenticated_client(&self, wallet: Option<&str>) -> Result<AuthenticatedClient, String> {
+        let cache_key = wallet.unwrap_or(PRIMARY_WALLET).to_lowercase();
 
-        match http_client.get(&url).send().await {
-            Ok(response) => {
-                match response.json::<Value>().await {
-                    Ok(events) => {
-                        // Use lightweight summaries (no outcomes) to keep context small
-                        let markets = Self::transform_events_to_summaries(&events);
-
-                        let result = json!({
-                            "status": "success",
-                            "query": query,
-                            "tag": tag,
-                            "count": markets.len(),
-                            "offset": offset,
-                            "limit": limit,
-                            "markets": markets,
-                            "note": "Use get_market with slug to see outcomes and token_ids for trading."
-                        });
-                        ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+        // Check cache first, dropping the client if the wallet's key changed
+        {
+            let mut cache = self.client_cache.lock().await;
+            if let Some(cached) = cache.get(&cache_key) {
+                match crate::wallet::cached_address(wallet) {
+                    Some(address) if address != cached.address => {
+                        log::info!("[polymarket] Wallet '{}' key changed, re-authenticating", cache_key);
+                        cache.remove(&cache_key);
                     }
-                    Err(e) => ToolResult::error(format!("Failed to parse markets: {}", e))
+                    _ => return Ok(cached.client.clone()),
                 }
             }
-            Err(e) => ToolResult::error(format!("Failed to search markets: {}", e))
         }
-    }
 
-    /// Get trending/popular markets (lightweight summaries only)
-    async fn trending_markets(&self, params: &PolymarketParams) -> ToolResult {
-        let limit = params.limit.unwrap_or(10).min(20);
-        let offset = params.offset.unwrap_or(0);
-        let tag = params.tag.as_deref();
+        // Create new authenticated client
+        let wallet_signer = Self::get_wallet_signer(wallet).await?;
+        let address = wallet_signer.address();
+        let signer = AlloyWalletSigner::new(wallet_signer, Some(POLYGON));
 
-        let http_client = reqwest::Client::new();
+        let config = ClobConfig::builder()
+            .use_server_time(true)
+            .build();
 
-        // Get markets sorted by volume (trending)
-        let mut url = format!(
-            "https://gamma-api.polymarket.com/events?active=true&closed=false&limit={}&offset={}&order=volume&ascending=false",
-            limit, offset
-        );
+        let client = Client::new(CLOB_API_URL, config)
+            .map_err(|e| format!("Failed to create CLOB client: {}", e))?
+            .authentication_builder(&signer)
+            .authenticate()
+            .await
+            .map_err(|e| format!("Failed to authenticate with CLOB: {}", e))?;
 
-        if let Some(t) = tag {
-            url.push_str(&format!("&tag={}", t));
+        // Cache for future use
+        {
+            let mut cache = self.client_cache.lock().await;
+            cache.insert(cache_key, CachedClient { client: client.clone(), address });
         }
 
-        match http_client.get(&url).send().await {
-            Ok(response) => {
-                match response.json::<Value>().await {
-                    Ok(events) => {
-                        // Use lightweight summaries (no outcomes) to keep context small
-                        let markets = Self::transform_events_to_summaries(&events);
-
-                        let result = json!({
-                            "status": "success",
-                            "type": "trending",
-                            "tag": tag,
-                            "count": markets.len(),
-                            "offset": offset,
-                            "limit": limit,
-                            "markets": markets,
-                            "note": "Use get_market with slug to see outcomes and token_ids for trading."
-                        });
-                        ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
-                    }
-                    Err(e) => ToolResult::error(format!("Failed to parse markets: {}", e))
-                }
+        Ok(client)
+    }
+
+    /// Drop a wallet's cached client so the next call re-authenticates
+    async fn evict_client(&self, wallet: Option<&str>) {
+        let cache_key = wallet.unwrap_or(PRIMARY_WALLET).to_lowercase();
+        self.client_cache.lock().await.remove(&cache_key);
+    }
+
+    /// Run a CLOB call with the wallet's authenticated client, re-authenticating
+    /// and retrying once if the cached credentials are rejected
+    async fn with_client<T, F, Fut>(&self, wallet: Option<&str>, op: F) -> Result<T, String>
+    where
+        F: Fn(AuthenticatedClient) -> Fut,
+        Fut: Future<Output = Result<T, String>>,
+    {
+        let op = &op;
+        retry_once_on_auth_error(|reauth| async move {
+            if reauth {
+                self.evict_client(wallet).await;
             }
-            Err(e) => ToolResult::error(format!("Failed to fetch trending markets: {}", e))
-        }
+            let client = self.get_authenticated_client(wallet).await?;
+            op(client).await
+        })
+        .await
+    }
+
+    /// Create a fresh signer for signing operations
+    async fn create_signer_for_signing(wallet: Option<&str>) -> Result<AlloyWalletSigner, String> {
+        let signer = Self::get_wallet_signer(wallet).await?;
+        Ok(AlloyWalletSigner::new(signer, Some(POLYGON)))
+    }
+
+    /// Fetch a Gamma API events query
+    async fn fetch_events(url: &str) -> Result<Value, String> {
+        let response = reqwest::Client::new()
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch markets: {}", e))?;
+        response
+            .json::<Value>()
+            .await
+            .map_err(|e| format!("Failed to parse markets: {}", e))
+    }
+
+    /// Transform Gamma API events into lightweight market summaries (no outcomes - for listing)
+    fn transform_events_to_summaries(events: &Value) -> Vec<MarketSummary> {
+        let empty_vec = vec![];
+        let events_arr = events.as_array().unwrap_or(&empty_vec);
+
+        events_arr.iter().filter_map(Self::transform_event_summary).collect()
     }
 
-    /// Get market details by slug
-    async fn get_market(&self, params: &PolymarketParams) -> ToolResult {
-        let slug = match &params.slug {
-            Some(s) => s,
-            None => return ToolResult::error("slug is required for get_market (e.g., 'will-bitcoin-hit-100k')"),
+    /// Transform a single event into a lightweight summary (no outcomes)
+    fn transform_event_summary(event: &Value) -> Option<MarketSummary> {
+        let title = event.get("title")?.as_str()?;
+        let field = |name: &str, default: &str| {
+            event.get(name).and_then(|v| v.as_str()).unwrap_or(default).to_string()
         };
 
-        let http_client = reqwest::Client::new();
-        let url = format!("https://gamma-api.polymarket.com/events?slug={}", slug);
+        Some(MarketSummary {
+            title: title.to_string(),
+            slug: field("slug", ""),
+            description: field("description", "").chars().take(150).collect(),
+            end_date: field("endDate", ""),
+            volume: field("volume", "0"),
+            liquidity: field("liquidity", "0"),
+        })
+    }
 
-        match http_client.get(&url).send().await {
-            Ok(response) => {
-                match response.json::<Value>().await {
-                    Ok(events) => {
-                        // Events is an array, get first match
-                        if let Some(event) = events.as_array().and_then(|arr| arr.first()) {
-                            let market_info = Self::transform_single_event(event);
-
-                            let result = json!({
-                                "status": "success",
-                                "market": market_info,
-                                "note": "Use the token_id values with place_order to trade specific outcomes."
-                            });
-                            ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
-                        } else {
-                            ToolResult::error(format!("Market not found with slug: {}", slug))
-                        }
-                    }
-                    Err(e) => ToolResult::error(format!("Failed to parse market: {}", e))
+    /// Transform a single event into full market info (with outcomes)
+    fn transform_single_event(event: &Value) -> Option<Market> {
+        let title = event.get("title")?.as_str()?;
+        let field = |name: &str, default: &str| {
+            event.get(name).and_then(|v| v.as_str()).unwrap_or(default).to_string()
+        };
+
+        // Extract markets/outcomes
+        let markets = event.get("markets").and_then(|m| m.as_array())?;
+
+        // Gamma encodes outcome lists as JSON-ish strings: "[\"Yes\", \"No\"]"
+        let list = |market: &Value, name: &str| -> Vec<String> {
+            market
+                .get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or("[]")
+                .trim_matches(|c| c == '[' || c == ']')
+                .split(',')
+                .map(|s| s.trim().trim_matches('"').to_string())
+                .collect()
+        };
+
+        let outcomes = markets
+            .iter()
+            .map(|market| {
+                let prices = list(market, "outcomePrices");
+                let token_ids = list(market, "clobTokenIds");
+
+                MarketQuestion {
+                    question: market.get("question").and_then(|q| q.as_str()).unwrap_or("").to_string(),
+                    condition_id: market.get("conditionId").and_then(|c| c.as_str()).unwrap_or("").to_string(),
+                    outcomes: list(market, "outcomes")
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, name)| MarketOutcome {
+                            name,
+                            price: prices.get(i).cloned().unwrap_or_else(|| "N/A".to_string()),
+                            token_id: token_ids.get(i).cloned().unwrap_or_default(),
+                        })
+                        .collect(),
                 }
-            }
-            Err(e) => ToolResult::error(format!("Failed to fetch market: {}", e))
+            })
+            .collect();
+
+        Some(Market {
+            title: title.to_string(),
+            slug: field("slug", ""),
+            description: field("description", "").chars().take(200).collect(),
+            end_date: field("endDate", ""),
+            volume: field("volume", "0"),
+            liquidity: field("liquidity", "0"),
+            outcomes,
+        })
+    }
+}
+
+#[async_trait]
+impl PredictionMarket for Polymarket {
+    fn network(&self) -> &'static str {
+        "polygon"
+    }
+
+    async fn search(&self, query: &MarketQuery) -> Result<Vec<MarketSummary>, String> {
+        let mut url = format!(
+            "{}/events?active=true&closed=false&limit={}&offset={}",
+            GAMMA_API_URL, query.limit, query.offset
+        );
+
+        if query.trending {
+            // Sorted by volume
+            url.push_str("&order=volume&ascending=false");
+        } else if !query.query.is_empty() {
+            url.push_str(&format!("&_q={}", urlencoding::encode(&query.query)));
         }
+
+        if let Some(ref t) = query.tag {
+            url.push_str(&format!("&tag={}", t));
+        }
+
+        let events = Self::fetch_events(&url).await?;
+        Ok(Self::transform_events_to_summaries(&events))
     }
 
-    /// Get current price and orderbook for a token
-    async fn get_price(&self, params: &PolymarketParams) -> ToolResult {
-        let token_id = match &params.token_id {
-            Some(t) => t,
-            None => return ToolResult::error("token_id is required for get_price"),
-        };
+    async fn get_market(&self, slug: &str) -> Result<Option<Market>, String> {
+        let events = Self::fetch_events(&format!("{}/events?slug={}", GAMMA_API_URL, slug)).await?;
 
+        // Events is an array, get first match
+        Ok(events
+            .as_array()
+            .and_then(|arr| arr.first())
+            .and_then(Self::transform_single_event))
+    }
+
+    async fn get_price(&self, token_id: &str) -> Result<MarketPrice, String> {
         let http_client = reqwest::Client::new();
 
         // Fetch midpoint, spread, and orderbook in parallel
-        let midpoint_url = format!("https://clob.polymarket.com/midpoint?token_id={}", token_id);
-        let spread_url = format!("https://clob.polymarket.com/spread?token_id={}", token_id);
-        let book_url = format!("https://clob.polymarket.com/book?token_id={}", token_id);
+        let midpoint_url = format!("{}/midpoint?token_id={}", CLOB_API_URL, token_id);
+        let spread_url = format!("{}/spread?token_id={}", CLOB_API_URL, token_id);
+        let book_url = format!("{}/book?token_id={}", CLOB_API_URL, token_id);
 
         let (midpoint_res, spread_res, book_res) = tokio::join!(
             http_client.get(&midpoint_url).send(),
@@ -740,155 +886,167 @@ impl PolymarketTradeTool {
             Err(_) => None,
         };
 
-        // Extract best bid/ask from orderbook
-        let best_bid = book.as_ref()
-            .and_then(|b| b.get("bids"))
-            .and_then(|bids| bids.as_array())
-            .and_then(|arr| arr.first())
-            .and_then(|bid| bid.get("price"))
-            .and_then(|p| p.as_str())
-            .unwrap_or("N/A");
+        let levels = |side: &str| book.as_ref().and_then(|b| b.get(side)).and_then(|l| l.as_array());
+        // Best bid/ask from the top of the orderbook
+        let best = |side: &str| {
+            levels(side)
+                .and_then(|arr| arr.first())
+                .and_then(|level| level.get("price"))
+                .and_then(|p| p.as_str())
+                .unwrap_or("N/A")
+                .to_string()
+        };
 
-        let best_ask = book.as_ref()
-            .and_then(|b| b.get("asks"))
-            .and_then(|asks| asks.as_array())
-            .and_then(|arr| arr.first())
-            .and_then(|ask| ask.get("price"))
-            .and_then(|p| p.as_str())
-            .unwrap_or("N/A");
-
-        let mid = midpoint.as_ref()
-            .and_then(|m| m.get("mid"))
-            .and_then(|p| p.as_str())
-            .unwrap_or("N/A");
-
-        let result = json!({
-            "status": "success",
-            "token_id": token_id,
-            "price": {
-                "midpoint": mid,
-                "best_bid": best_bid,
-                "best_ask": best_ask,
-                "spread": spread,
-            },
-            "orderbook_summary": {
-                "bids": book.as_ref().and_then(|b| b.get("bids")).and_then(|b| b.as_array()).map(|a| a.len()).unwrap_or(0),
-                "asks": book.as_ref().and_then(|b| b.get("asks")).and_then(|a| a.as_array()).map(|a| a.len()).unwrap_or(0),
-            },
-            "note": "Prices are 0-1 representing probability. Use this token_id with place_order to trade."
-        });
-        ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+        Ok(MarketPrice {
+            midpoint: midpoint
+                .as_ref()
+                .and_then(|m| m.get("mid"))
+                .and_then(|p| p.as_str())
+                .unwrap_or("N/A")
+                .to_string(),
+            best_bid: best("bids"),
+            best_ask: best("asks"),
+            spread,
+            bid_levels: levels("bids").map(|a| a.len()).unwrap_or(0),
+            ask_levels: levels("asks").map(|a| a.len()).unwrap_or(0),
+        })
     }
 
-    // ==================== HELPER METHODS ====================
-
-    /// Transform Gamma API events response into a cleaner market list
-    /// Transform events to lightweight market summaries (no outcomes - for listing)
-    fn transform_events_to_summaries(events: &Value) -> Vec<Value> {
-        let empty_vec = vec![];
-        let events_arr = events.as_array().unwrap_or(&empty_vec);
-
-        events_arr.iter().filter_map(|event| {
-            Self::transform_event_summary(event)
-        }).collect()
+    async fn place_order(&self, order: &OrderRequest, wallet: Option<&str>) -> Result<PlacedOrder, String> {
+        // Validate before authenticating
+        parse_order_type(&order.order_type)?;
+        let token_id = U256::from_str(&order.token_id).map_err(|e| format!("Invalid token_id: {}", e))?;
+        let price = Decimal::try_from(order.price).map_err(|e| format!("Invalid price decimal: {}", e))?;
+        let size = Decimal::try_from(order.size).map_err(|e| format!("Invalid size decimal: {}", e))?;
+
+        // Build, sign and submit the limit order
+        let response = self
+            .with_client(wallet, |client| async move {
+                let signer = Self::create_signer_for_signing(wallet).await?;
+                let limit_order = client
+                    .limit_order()
+                    .token_id(token_id)
+                    .price(price)
+                    .size(size)
+                    .side(sdk_side(order.side))
+                    .order_type(parse_order_type(&order.order_type)?)
+                    .build()
+                    .await
+                    .map_err(|e| format!("Failed to build order: {}", e))?;
+                let signed_order = client
+                    .sign(&signer, limit_order)
+                    .await
+                    .map_err(|e| format!("Failed to sign order: {}", e))?;
+                client
+                    .post_order(signed_order)
+                    .await
+                    .map_err(|e| format!("Failed to submit order: {}", e))
+            })
+            .await?;
+
+        Ok(PlacedOrder {
+            order_id: response.order_id,
+            success: response.success,
+            wallet_address: Self::get_wallet_address(wallet).await.unwrap_or_else(|_| "unknown".to_string()),
+        })
     }
 
-    /// Transform a single event into a lightweight summary (no outcomes)
-    fn transform_event_summary(event: &Value) -> Option<Value> {
-        let title = event.get("title")?.as_str()?;
-        let slug = event.get("slug").and_then(|s| s.as_str()).unwrap_or("");
-        let description = event.get("description").and_then(|d| d.as_str()).unwrap_or("");
-        let end_date = event.get("endDate").and_then(|d| d.as_str()).unwrap_or("");
-        let volume = event.get("volume").and_then(|v| v.as_str()).unwrap_or("0");
-        let liquidity = event.get("liquidity").and_then(|l| l.as_str()).unwrap_or("0");
-
-        Some(json!({
-            "title": title,
-            "slug": slug,
-            "description": description.chars().take(150).collect::<String>(),
-            "end_date": end_date,
-            "volume": volume,
-            "liquidity": liquidity
-        }))
-    }
+    async fn positions(&self, wallet: Option<&str>) -> Result<Positions, String> {
+        let wallet_address = Self::get_wallet_address(wallet).await?;
+        let url = format!("{}/positions?user={}", DATA_API_URL, wallet_address);
 
-    /// Transform a single event into full market info (with outcomes)
-    fn transform_single_event(event: &Value) -> Option<Value> {
-        let title = event.get("title")?.as_str()?;
-        let slug = event.get("slug").and_then(|s| s.as_str()).unwrap_or("");
-        let description = event.get("description").and_then(|d| d.as_str()).unwrap_or("");
-        let end_date = event.get("endDate").and_then(|d| d.as_str()).unwrap_or("");
-        let volume = event.get("volume").and_then(|v| v.as_str()).unwrap_or("0");
-        let liquidity = event.get("liquidity").and_then(|l| l.as_str()).unwrap_or("0");
+        let response = reqwest::Client::new()
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch positions: {}", e))?;
+        let positions = response
+            .json::<Value>()
+            .await
+            .map_err(|e| format!("Failed to parse positions: {}", e))?;
 
-        // Extract markets/outcomes
-        let markets = event.get("markets").and_then(|m| m.as_array())?;
+        Ok(Positions { wallet_address, positions })
+    }
+}
 
-        let outcomes: Vec<Value> = markets.iter().filter_map(|market| {
-            let question = market.get("question").and_then(|q| q.as_str()).unwrap_or("");
-            let condition_id = market.get("conditionId").and_then(|c| c.as_str()).unwrap_or("");
-            let outcome_prices = market.get("outcomePrices").and_then(|p| p.as_str()).unwrap_or("[]");
-            let outcomes = market.get("outcomes").and_then(|o| o.as_str()).unwrap_or("[]");
+impl Default for Polymarket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-            // Parse outcome prices
-            let prices: Vec<&str> = outcome_prices.trim_matches(|c| c == '[' || c == ']')
-                .split(',')
-                .map(|s| s.trim().trim_matches('"'))
-                .collect();
+/// New matching on a tracked order since the previous poll
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderFill {
+    pub order_id: String,
+    pub wallet: String,
+    pub token_id: String,
+    pub outcome: Option<String>,
+    pub side: String,
+    pub price: String,
+    pub original_size: String,
+    pub size_matched: String,
+    /// Shares matched since the previous poll
+    pub newly_matched: f64,
+    pub fully_filled: bool,
+    /// Channel to post the notification to
+    pub channel_id: Option<i64>,
+}
 
-            let outcome_names: Vec<&str> = outcomes.trim_matches(|c| c == '[' || c == ']')
-                .split(',')
-                .map(|s| s.trim().trim_matches('"'))
-                .collect();
+impl OrderFill {
+    /// Human-readable notification
+    pub fn message(&self) -> String {
+        let outcome = self.outcome.as_deref().map(|o| format!(" '{}'", o)).unwrap_or_default();
+        if self.fully_filled {
+            format!(
+                "Polymarket order {} filled: {} {} shares{} @ {} (wallet '{}').",
+                self.order_id, self.side, self.size_matched, outcome, self.price, self.wallet
+            )
+        } else {
+            format!(
+                "Polymarket order {} partially filled: {} more matched, {} of {} shares{} @ {} (wallet '{}').",
+                self.order_id, self.newly_matched, self.size_matched, self.original_size, outcome, self.price, self.wallet
+            )
+        }
+    }
+}
 
-            // Get individual token IDs for each outcome
-            let tokens = market.get("clobTokenIds").and_then(|t| t.as_str()).unwrap_or("[]");
-            let token_ids: Vec<&str> = tokens.trim_matches(|c| c == '[' || c == ']')
-                .split(',')
-                .map(|s| s.trim().trim_matches('"'))
-                .collect();
+fn decimal_f64(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
 
-            Some(json!({
-                "question": question,
-                "condition_id": condition_id,
-                "outcomes": outcome_names.iter().enumerate().map(|(i, name)| {
-                    json!({
-                        "name": name,
-                        "price": prices.get(i).unwrap_or(&"N/A"),
-                        "token_id": token_ids.get(i).unwrap_or(&"")
-                    })
-                }).collect::<Vec<Value>>()
-            }))
-        }).collect();
-
-        Some(json!({
-            "title": title,
-            "slug": slug,
-            "description": description.chars().take(200).collect::<String>(),
-            "end_date": end_date,
-            "volume": volume,
-            "liquidity": liquidity,
-            "outcomes": outcomes
-        }))
+/// The fill between two observations of an order, if more has matched
+fn order_fill(previous: &TrackedPolymarketOrder, seen: &TrackedPolymarketOrder) -> Option<OrderFill> {
+    let newly_matched = decimal_f64(&seen.size_matched) - decimal_f64(&previous.size_matched);
+    if newly_matched <= 0.0 {
+        return None;
     }
+    Some(OrderFill {
+        order_id: seen.order_id.clone(),
+        wallet: previous.wallet.clone(),
+        token_id: previous.token_id.clone(),
+        outcome: seen.outcome.clone().or_else(|| previous.outcome.clone()),
+        side: previous.side.clone(),
+        price: previous.price.clone(),
+        original_size: previous.original_size.clone(),
+        size_matched: seen.size_matched.clone(),
+        newly_matched,
+        fully_filled: decimal_f64(&seen.size_matched) >= decimal_f64(&previous.original_size),
+        channel_id: previous.channel_id,
+    })
 }
 
 #[derive(Debug, Deserialize)]
 struct PolymarketParams {
     action: String,
-    // Discovery params
-    query: Option<String>,
-    slug: Option<String>,
-    tag: Option<String>,
-    limit: Option<u32>,
-    offset: Option<u32>,
-    // Trading params
-    token_id: Option<String>,
-    side: Option<String>,
-    price: Option<f64>,
-    size: Option<f64>,
-    order_type: Option<String>,
+    /// Discovery and order params shared with other venues
+    #[serde(flatten)]
+    market: MarketParams,
     order_id: Option<String>,
+    /// Post fill notifications to the placing channel (place_order)
+    notify: Option<bool>,
+    /// Named wallet for trading actions
+    wallet: Option<String>,
 }
 
 #[async_trait]
@@ -897,27 +1055,30 @@ impl Tool for PolymarketTradeTool {
         self.definition.clone()
     }
 
-    async fn execute(&self, params: Value, _context: &ToolContext) -> ToolResult {
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: PolymarketParams = match serde_json::from_value(params) {
             Ok(p) => p,
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
+        let wallet = context.wallet_name(params.wallet.as_deref());
+        let wallet = wallet.as_deref();
 
         match params.action.as_str() {
             // Discovery actions (no auth required)
-            "search_markets" => self.search_markets(&params).await,
-            "trending_markets" => self.trending_markets(&params).await,
-            "get_market" => self.get_market(&params).await,
-            "get_price" => self.get_price(&params).await,
+            "search_markets" => prediction_market::search_markets(&self.market, &params.market, false).await,
+            "trending_markets" => prediction_market::search_markets(&self.market, &params.market, true).await,
+            "get_market" => prediction_market::get_market(&self.market, &params.market).await,
+            "get_price" => prediction_market::get_price(&self.market, &params.market).await,
             // Trading actions (require wallet)
-            "place_order" => self.place_order(&params).await,
-            "cancel_order" => self.cancel_order(&params).await,
-            "cancel_all" => self.cancel_all().await,
-            "get_orders" => self.get_orders().await,
-            "get_positions" => self.get_positions().await,
-            "get_balance" => self.get_balance().await,
+            "place_order" => self.place_order(&params, wallet, context).await,
+            "cancel_order" => self.cancel_order(&params, wallet).await,
+            "cancel_all" => self.cancel_all(wallet).await,
+            "get_orders" => self.get_orders(wallet).await,
+            "get_order" => self.get_order(&params, wallet).await,
+            "get_positions" => prediction_market::get_positions(&self.market, wallet).await,
+            "get_balance" => self.get_balance(wallet).await,
             _ => ToolResult::error(format!(
-                "Unknown action: '{}'. Discovery: search_markets, trending_markets, get_market, get_price. Trading: place_order, cancel_order, cancel_all, get_orders, get_positions, get_balance",
+                "Unknown action: '{}'. Discovery: search_markets, trending_markets, get_market, get_price. Trading: place_order, cancel_order, cancel_all, get_orders, get_order, get_positions, get_balance",
                 params.action
             )),
         }
@@ -935,7 +1096,7 @@ impl Clone for PolymarketTradeTool {
     fn clone(&self) -> Self {
         Self {
             definition: self.definition.clone(),
-            client_cache: Arc::new(Mutex::new(None)), // Fresh cache for clone
+            market: Polymarket::new(), // Fresh client cache for clone
         }
     }
 }
@@ -944,6 +1105,107 @@ impl Clone for PolymarketTradeTool {
 mod tests {
     use super::*;
 
+    fn tracked(size_matched: &str) -> TrackedPolymarketOrder {
+        TrackedPolymarketOrder {
+            order_id: "0xorder".to_string(),
+            wallet: PRIMARY_WALLET.to_string(),
+            token_id: "123".to_string(),
+            side: "buy".to_string(),
+            price: "0.65".to_string(),
+            original_size: "100".to_string(),
+            size_matched: size_matched.to_string(),
+            status: "Live".to_string(),
+            outcome: Some("Yes".to_string()),
+            is_open: true,
+            channel_id: Some(0),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_order_fill_detection() {
+        assert!(order_fill(&tracked("0"), &tracked("0")).is_none());
+
+        let partial = order_fill(&tracked("0"), &tracked("40")).unwrap();
+        assert_eq!(partial.newly_matched, 40.0);
+        assert!(!partial.fully_filled);
+        assert!(partial.message().contains("partially filled"));
+
+        let full = order_fill(&tracked("40"), &tracked("100")).unwrap();
+        assert_eq!(full.newly_matched, 60.0);
+        assert!(full.fully_filled);
+        assert_eq!(full.channel_id, Some(0));
+    }
+
+    #[test]
+    fn test_transform_event_outcomes() {
+        let event = json!({
+            "title": "Bitcoin above 100k?",
+            "slug": "bitcoin-above-100k",
+            "volume": "1500",
+            "markets": [{
+                "question": "Bitcoin above 100k on Dec 31?",
+                "conditionId": "0xcond",
+                "outcomes": "[\"Yes\", \"No\"]",
+                "outcomePrices": "[\"0.42\", \"0.58\"]",
+                "clobTokenIds": "[\"111\", \"222\"]"
+            }]
+        });
+
+        let market = Polymarket::transform_single_event(&event).unwrap();
+        assert_eq!(market.slug, "bitcoin-above-100k");
+        assert_eq!(market.liquidity, "0");
+        let outcomes = &market.outcomes[0].outcomes;
+        assert_eq!(outcomes[1].name, "No");
+        assert_eq!(outcomes[1].price, "0.58");
+        assert_eq!(outcomes[1].token_id, "222");
+
+        let summary = serde_json::to_value(Polymarket::transform_event_summary(&event).unwrap()).unwrap();
+        assert_eq!(summary["title"], "Bitcoin above 100k?");
+        assert!(summary.get("outcomes").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_auth_error_triggers_single_reauth() {
+        // Expired credentials: the retry re-authenticates and succeeds
+        let mut attempts = Vec::new();
+        let result = retry_once_on_auth_error(|reauth| {
+            attempts.push(reauth);
+            let first = attempts.len() == 1;
+            async move {
+                if first {
+                    Err("Status: 401 Unauthorized/Invalid api key".to_string())
+                } else {
+                    Ok("order")
+                }
+            }
+        })
+        .await;
+        assert_eq!(result, Ok("order"));
+        assert_eq!(attempts, vec![false, true]);
+
+        // Still rejected after re-auth: give up after one retry
+        let mut attempts = 0;
+        let result: Result<(), String> = retry_once_on_auth_error(|_| {
+            attempts += 1;
+            async { Err("401 Unauthorized".to_string()) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 2);
+
+        // Other errors are returned without re-authenticating
+        let mut attempts = 0;
+        let result: Result<(), String> = retry_once_on_auth_error(|_| {
+            attempts += 1;
+            async { Err("order not found".to_string()) }
+        })
+        .await;
+        assert_eq!(result, Err("order not found".to_string()));
+        assert_eq!(attempts, 1);
+    }
+
     /// Test that searching for politics markets returns sensible results
     /// This simulates a user query like "find me politics markets on polymarket"
     #[tokio::test]
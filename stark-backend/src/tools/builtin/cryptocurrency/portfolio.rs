@@ -0,0 +1,556 @@
+//! Portfolio tool for balance history and P&L
+//!
+//! The scheduler snapshots every configured wallet on the networks in
+//! `STARK_PORTFOLIO_NETWORKS`: balances come from `fetch_balances` (the
+//! wallet_balances reader) and USD prices from DexScreener. Holdings without a
+//! price are recorded as unvalued instead of failing the snapshot. The tool and
+//! `/api/portfolio` read the snapshots back to report history and simple P&L
+//! (change in USD value, which includes deposits and withdrawals).
+
+use super::dexscreener::DexScreenerTool;
+use super::network_lookup::{get_network, resolve_network, supported_network_ids};
+use super::token_lookup::TokenLookupTool;
+use super::wallet_balances::{fetch_balances, format_amount, resolve_tokens, NATIVE_TOKEN_ADDRESS};
+use crate::db::tables::portfolio_snapshots::{Holding, PortfolioSnapshot};
+use crate::db::Database;
+use crate::tools::registry::Tool;
+use crate::tools::rpc_config::{resolve_rpc_from_context, ResolvedRpcConfig};
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::wallet::{WalletSigner, PRIMARY_WALLET, WALLET_PARAM_DESCRIPTION};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use ethers::types::Address;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Default P&L / history window
+pub const DEFAULT_PORTFOLIO_DAYS: i64 = 7;
+
+/// Snapshots older than this are pruned after each scheduled run
+const SNAPSHOT_RETENTION_DAYS: i64 = 365;
+
+/// Change in one asset between the first and last snapshot of a range
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetChange {
+    pub symbol: String,
+    pub start_amount: String,
+    pub end_amount: String,
+    pub start_value_usd: Option<f64>,
+    pub end_value_usd: Option<f64>,
+}
+
+/// Simple P&L between the first and last snapshot of a range
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioPnl {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub start_usd: f64,
+    pub end_usd: f64,
+    pub change_usd: f64,
+    /// `None` when the starting value was zero
+    pub change_pct: Option<f64>,
+    pub assets: Vec<AssetChange>,
+    /// Assets without a price at either end; not reflected in the USD totals
+    pub unvalued: Vec<String>,
+}
+
+/// Snapshot history of one wallet on one network, with P&L over the range
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioHistory {
+    pub wallet: String,
+    pub address: String,
+    pub network: String,
+    pub days: i64,
+    pub snapshots: Vec<PortfolioSnapshot>,
+    /// `None` until there are at least two snapshots in the range
+    pub pnl: Option<PortfolioPnl>,
+}
+
+/// DexScreener chain id for a network identifier
+pub(super) fn dexscreener_chain(network: &str) -> &str {
+    match network {
+        "mainnet" => "ethereum",
+        other => other,
+    }
+}
+
+/// Address whose price stands in for the native currency: its wrapped token
+/// (e.g. WETH on base, WMATIC on polygon)
+pub(super) fn native_price_address(network: &str) -> Option<String> {
+    let mut candidates = Vec::new();
+    if let Some(info) = get_network(network) {
+        candidates.push(format!("W{}", info.native_token));
+    }
+    for symbol in TokenLookupTool::list_available(network) {
+        let is_native = TokenLookupTool::lookup(&symbol, network)
+            .is_some_and(|t| t.address.eq_ignore_ascii_case(NATIVE_TOKEN_ADDRESS));
+        if is_native {
+            candidates.push(format!("W{}", symbol));
+        }
+    }
+
+    candidates
+        .iter()
+        .find_map(|symbol| TokenLookupTool::lookup(symbol, network))
+        .map(|token| token.address)
+}
+
+/// Read, value and record one snapshot of `address` on `network`
+pub async fn snapshot_wallet(
+    db: &Database,
+    wallet: &str,
+    address: Address,
+    network: &str,
+    rpc_config: &ResolvedRpcConfig,
+) -> Result<PortfolioSnapshot, String> {
+    let (tokens, _) = resolve_tokens(&["common".to_string()], network);
+    let balances = fetch_balances(network, address, &tokens, rpc_config).await?;
+    let chain = dexscreener_chain(network);
+
+    let mut holdings = Vec::new();
+    let mut total_usd = 0.0;
+    for balance in balances {
+        let raw = match balance.raw {
+            Ok(raw) if raw.is_zero() => continue,
+            Ok(raw) => raw,
+            Err(e) => {
+                log::warn!("[portfolio] Skipping {} on {}: {}", balance.symbol, network, e);
+                continue;
+            }
+        };
+        let amount = format_amount(raw, balance.decimals);
+
+        let price_address = balance.address.clone().or_else(|| native_price_address(network));
+        let price_usd = match price_address {
+            Some(ref token) => match DexScreenerTool::token_price_usd(chain, token).await {
+                Ok(price) => price,
+                Err(e) => {
+                    log::warn!("[portfolio] No price for {} on {}: {}", balance.symbol, network, e);
+                    None
+                }
+            },
+            None => None,
+        };
+        let value_usd = price_usd.and_then(|p| amount.parse::<f64>().ok().map(|a| a * p));
+        total_usd += value_usd.unwrap_or(0.0);
+
+        holdings.push(Holding {
+            symbol: balance.symbol,
+            address: balance.address,
+            amount,
+            price_usd,
+            value_usd,
+        });
+    }
+
+    let address_str = format!("{:?}", address);
+    let id = db
+        .record_portfolio_snapshot(wallet, &address_str, network, total_usd, &holdings)
+        .map_err(|e| format!("Failed to record snapshot: {}", e))?;
+
+    Ok(PortfolioSnapshot {
+        id,
+        wallet: wallet.to_string(),
+        address: address_str.to_lowercase(),
+        network: network.to_string(),
+        total_usd,
+        holdings,
+        taken_at: Utc::now(),
+    })
+}
+
+/// Snapshot every configured wallet on every portfolio network (scheduler job)
+pub async fn snapshot_all_wallets(db: &Database) {
+    // Same RPC settings the dispatcher hands to tools
+    let mut extra = HashMap::new();
+    if let Ok(settings) = db.get_bot_settings() {
+        extra.insert("rpc_provider".to_string(), json!(settings.rpc_provider));
+        if let Some(endpoints) = settings.custom_rpc_endpoints {
+            extra.insert("custom_rpc_endpoints".to_string(), json!(endpoints));
+        }
+    }
+
+    for wallet in crate::wallet::list_wallets().await {
+        let Some(address) = wallet.address.as_deref().and_then(|a| a.parse::<Address>().ok()) else {
+            log::warn!("[portfolio] Skipping wallet '{}': {}", wallet.name, wallet.error.unwrap_or_default());
+            continue;
+        };
+        for network in crate::config::portfolio_networks() {
+            let rpc_config = resolve_rpc_from_context(&extra, &network);
+            match snapshot_wallet(db, &wallet.name, address, &network, &rpc_config).await {
+                Ok(snapshot) => log::info!(
+                    "[portfolio] Snapshot of '{}' on {}: ${:.2} ({} holdings)",
+                    wallet.name,
+                    network,
+                    snapshot.total_usd,
+                    snapshot.holdings.len()
+                ),
+                Err(e) => log::warn!("[portfolio] Snapshot of '{}' on {} failed: {}", wallet.name, network, e),
+            }
+        }
+    }
+
+    if let Err(e) = db.prune_portfolio_snapshots(Utc::now() - Duration::days(SNAPSHOT_RETENTION_DAYS)) {
+        log::warn!("[portfolio] Failed to prune old snapshots: {}", e);
+    }
+}
+
+/// P&L between the first and last of `snapshots` (oldest first)
+pub fn compute_pnl(snapshots: &[PortfolioSnapshot]) -> Option<PortfolioPnl> {
+    let (first, last) = match snapshots {
+        [first, .., last] => (first, last),
+        _ => return None,
+    };
+
+    let mut symbols: Vec<&str> = first.holdings.iter().map(|h| h.symbol.as_str()).collect();
+    for holding in &last.holdings {
+        if !symbols.contains(&holding.symbol.as_str()) {
+            symbols.push(&holding.symbol);
+        }
+    }
+
+    let mut assets = Vec::new();
+    let mut unvalued = Vec::new();
+    for symbol in symbols {
+        let start = first.holdings.iter().find(|h| h.symbol == symbol);
+        let end = last.holdings.iter().find(|h| h.symbol == symbol);
+        let start_value_usd = start.and_then(|h| h.value_usd);
+        let end_value_usd = end.and_then(|h| h.value_usd);
+        if start.is_some_and(|h| h.value_usd.is_none()) || end.is_some_and(|h| h.value_usd.is_none()) {
+            unvalued.push(symbol.to_string());
+        }
+        assets.push(AssetChange {
+            symbol: symbol.to_string(),
+            start_amount: start.map(|h| h.amount.clone()).unwrap_or_else(|| "0".to_string()),
+            end_amount: end.map(|h| h.amount.clone()).unwrap_or_else(|| "0".to_string()),
+            start_value_usd,
+            end_value_usd,
+        });
+    }
+
+    let change_usd = last.total_usd - first.total_usd;
+    Some(PortfolioPnl {
+        from: first.taken_at,
+        to: last.taken_at,
+        start_usd: first.total_usd,
+        end_usd: last.total_usd,
+        change_usd,
+        change_pct: (first.total_usd > 0.0).then(|| change_usd / first.total_usd * 100.0),
+        assets,
+        unvalued,
+    })
+}
+
+/// Snapshot history and P&L of a wallet (`None` = primary) over the last `days`
+pub async fn portfolio_history(
+    db: &Database,
+    wallet: Option<&str>,
+    network: &str,
+    days: i64,
+) -> Result<PortfolioHistory, String> {
+    let signer = WalletSigner::for_wallet(wallet).await?;
+    let address = format!("{:?}", signer.address());
+    let days = days.max(1);
+
+    let snapshots = db
+        .list_portfolio_snapshots(&address, network, Utc::now() - Duration::days(days))
+        .map_err(|e| format!("Failed to load snapshots: {}", e))?;
+    let pnl = compute_pnl(&snapshots);
+
+    Ok(PortfolioHistory {
+        wallet: wallet.unwrap_or(PRIMARY_WALLET).to_string(),
+        address,
+        network: network.to_string(),
+        days,
+        snapshots,
+        pnl,
+    })
+}
+
+/// Portfolio tool
+pub struct PortfolioTool {
+    definition: ToolDefinition,
+}
+
+impl PortfolioTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "pnl: value change over the range | history: snapshot values over the range | snapshot: record the current value now".to_string(),
+                default: Some(json!("pnl")),
+                items: None,
+                enum_values: Some(vec![
+                    "pnl".to_string(),
+                    "history".to_string(),
+                    "snapshot".to_string(),
+                ]),
+            },
+        );
+
+        properties.insert(
+            "days".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "Range in days, e.g. 7 for 'this week'. Defaults to 7.".to_string(),
+                default: Some(json!(DEFAULT_PORTFOLIO_DAYS)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "network".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: format!(
+                    "Network. Configured networks: {}. If not specified, uses the currently selected network or defaults to 'base'.",
+                    supported_network_ids().join(", ")
+                ),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "wallet".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: WALLET_PARAM_DESCRIPTION.to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        PortfolioTool {
+            definition: ToolDefinition {
+                name: "portfolio".to_string(),
+                description: "Wallet portfolio history from periodic USD-valued balance snapshots. Use 'pnl' to answer questions like \"how's my portfolio done this week\". P&L is the change in total value, so it includes deposits and withdrawals.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+}
+
+impl Default for PortfolioTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PortfolioParams {
+    #[serde(default = "default_action")]
+    action: String,
+    #[serde(default = "default_days")]
+    days: i64,
+    network: Option<String>,
+    wallet: Option<String>,
+}
+
+fn default_action() -> String {
+    "pnl".to_string()
+}
+
+fn default_days() -> i64 {
+    DEFAULT_PORTFOLIO_DAYS
+}
+
+fn format_usd(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("${:.2}", v))
+        .unwrap_or_else(|| "unvalued".to_string())
+}
+
+fn format_pnl(history: &PortfolioHistory, pnl: &PortfolioPnl) -> String {
+    let mut lines = vec![
+        format!(
+            "Portfolio '{}' ({}) on {}, {} to {}:",
+            history.wallet,
+            history.address,
+            history.network,
+            pnl.from.format("%Y-%m-%d %H:%M UTC"),
+            pnl.to.format("%Y-%m-%d %H:%M UTC")
+        ),
+        format!(
+            "  Value: ${:.2} -> ${:.2} ({:+.2} USD{})",
+            pnl.start_usd,
+            pnl.end_usd,
+            pnl.change_usd,
+            pnl.change_pct.map(|p| format!(", {:+.2}%", p)).unwrap_or_default()
+        ),
+    ];
+    for asset in &pnl.assets {
+        lines.push(format!(
+            "  {}: {} -> {} ({} -> {})",
+            asset.symbol,
+            asset.start_amount,
+            asset.end_amount,
+            format_usd(asset.start_value_usd),
+            format_usd(asset.end_value_usd)
+        ));
+    }
+    if !pnl.unvalued.is_empty() {
+        lines.push(format!(
+            "  No price for: {} (not included in USD totals)",
+            pnl.unvalued.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+#[async_trait]
+impl Tool for PortfolioTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: PortfolioParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => return ToolResult::error("Database not available"),
+        };
+        let network = match resolve_network(params.network.as_deref(), context.selected_network.as_deref()) {
+            Ok(n) => n,
+            Err(e) => return ToolResult::error(e),
+        };
+        let wallet = context.wallet_name(params.wallet.as_deref());
+
+        if params.action == "snapshot" {
+            let signer = match WalletSigner::for_wallet(wallet.as_deref()).await {
+                Ok(s) => s,
+                Err(e) => return ToolResult::error(e),
+            };
+            let rpc_config = resolve_rpc_from_context(&context.extra, &network);
+            let wallet_name = wallet.as_deref().unwrap_or(PRIMARY_WALLET);
+            return match snapshot_wallet(db, wallet_name, signer.address(), &network, &rpc_config).await {
+                Ok(snapshot) => {
+                    let mut lines = vec![format!(
+                        "Snapshot of '{}' on {}: ${:.2}",
+                        snapshot.wallet, snapshot.network, snapshot.total_usd
+                    )];
+                    for holding in &snapshot.holdings {
+                        lines.push(format!("  {}: {} ({})", holding.symbol, holding.amount, format_usd(holding.value_usd)));
+                    }
+                    ToolResult::success(lines.join("\n")).with_metadata(json!(snapshot))
+                }
+                Err(e) => ToolResult::error(format!("Snapshot failed: {}", e)),
+            };
+        }
+
+        let history = match portfolio_history(db, wallet.as_deref(), &network, params.days).await {
+            Ok(h) => h,
+            Err(e) => return ToolResult::error(e),
+        };
+        if history.snapshots.is_empty() {
+            return ToolResult::success(format!(
+                "No portfolio snapshots for '{}' on {} in the last {} days. Snapshots are taken every {} minutes; use action 'snapshot' to record one now.",
+                history.wallet,
+                history.network,
+                history.days,
+                crate::config::portfolio_snapshot_interval_mins()
+            ));
+        }
+
+        match params.action.as_str() {
+            "history" => {
+                let mut lines = vec![format!(
+                    "Portfolio '{}' on {} over the last {} days ({} snapshots):",
+                    history.wallet,
+                    history.network,
+                    history.days,
+                    history.snapshots.len()
+                )];
+                for snapshot in &history.snapshots {
+                    lines.push(format!(
+                        "  {}: ${:.2}",
+                        snapshot.taken_at.format("%Y-%m-%d %H:%M UTC"),
+                        snapshot.total_usd
+                    ));
+                }
+                ToolResult::success(lines.join("\n")).with_metadata(json!(history))
+            }
+            "pnl" => match history.pnl {
+                Some(ref pnl) => ToolResult::success(format_pnl(&history, pnl)).with_metadata(json!(history)),
+                None => ToolResult::success(format!(
+                    "Only one snapshot for '{}' on {} in the last {} days (${:.2}) - not enough to compute P&L yet.",
+                    history.wallet, history.network, history.days, history.snapshots[0].total_usd
+                )),
+            },
+            other => ToolResult::error(format!(
+                "Unknown action '{}'. Use pnl, history or snapshot.",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(symbol: &str, amount: &str, value_usd: Option<f64>) -> Holding {
+        Holding {
+            symbol: symbol.to_string(),
+            address: None,
+            amount: amount.to_string(),
+            price_usd: value_usd.map(|v| v / amount.parse::<f64>().unwrap()),
+            value_usd,
+        }
+    }
+
+    fn snapshot(total_usd: f64, holdings: Vec<Holding>, hours_ago: i64) -> PortfolioSnapshot {
+        PortfolioSnapshot {
+            id: 0,
+            wallet: PRIMARY_WALLET.to_string(),
+            address: "0xabc".to_string(),
+            network: "base".to_string(),
+            total_usd,
+            holdings,
+            taken_at: Utc::now() - Duration::hours(hours_ago),
+        }
+    }
+
+    #[test]
+    fn test_compute_pnl() {
+        let snapshots = vec![
+            snapshot(200.0, vec![holding("ETH", "0.1", Some(200.0)), holding("MEME", "1000", None)], 48),
+            snapshot(210.0, vec![holding("ETH", "0.1", Some(210.0))], 24),
+            snapshot(250.0, vec![holding("ETH", "0.1", Some(250.0)), holding("USDC", "5", Some(5.0))], 0),
+        ];
+
+        let pnl = compute_pnl(&snapshots).unwrap();
+        assert_eq!(pnl.change_usd, 50.0);
+        assert_eq!(pnl.change_pct, Some(25.0));
+        assert_eq!(pnl.assets.len(), 3);
+        assert_eq!(pnl.unvalued, vec!["MEME"]);
+        let meme = pnl.assets.iter().find(|a| a.symbol == "MEME").unwrap();
+        assert_eq!(meme.end_amount, "0");
+    }
+
+    #[test]
+    fn test_pnl_needs_two_snapshots() {
+        assert!(compute_pnl(&[]).is_none());
+        assert!(compute_pnl(&[snapshot(1.0, Vec::new(), 0)]).is_none());
+        let pnl = compute_pnl(&[snapshot(0.0, Vec::new(), 1), snapshot(5.0, Vec::new(), 0)]).unwrap();
+        assert_eq!(pnl.change_pct, None);
+    }
+}
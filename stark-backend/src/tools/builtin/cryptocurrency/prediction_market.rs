@@ -0,0 +1,361 @@
+//! Prediction market venues
+//!
+//! A venue (Polymarket today) implements [`PredictionMarket`] for market
+//! discovery, pricing, order placement and positions. The action handlers
+//! here turn tool parameters into venue calls and format the results, so
+//! every venue's tool exposes the same actions with the same JSON shapes.
+//! Venue-specific actions (order management, balances) stay in the venue's
+//! own tool.
+
+use crate::tools::types::ToolResult;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Default and maximum number of markets returned by search
+pub const DEFAULT_SEARCH_LIMIT: u32 = 10;
+pub const MAX_SEARCH_LIMIT: u32 = 20;
+
+/// A prediction market venue
+#[async_trait]
+pub trait PredictionMarket: Send + Sync {
+    /// Chain the venue settles on (reported with orders)
+    fn network(&self) -> &'static str;
+
+    /// Active markets matching `query`, as lightweight summaries
+    async fn search(&self, query: &MarketQuery) -> Result<Vec<MarketSummary>, String>;
+
+    /// Full market details with tradeable outcomes. `Ok(None)` if not found.
+    async fn get_market(&self, slug: &str) -> Result<Option<Market>, String>;
+
+    /// Current price and book depth for an outcome token
+    async fn get_price(&self, token_id: &str) -> Result<MarketPrice, String>;
+
+    /// Place a limit order for an outcome token
+    async fn place_order(&self, order: &OrderRequest, wallet: Option<&str>) -> Result<PlacedOrder, String>;
+
+    /// Current positions held by a wallet
+    async fn positions(&self, wallet: Option<&str>) -> Result<Positions, String>;
+}
+
+/// Market search filters
+#[derive(Debug, Clone, Default)]
+pub struct MarketQuery {
+    /// Keyword filter (empty for none)
+    pub query: String,
+    /// Category tag (e.g. "politics")
+    pub tag: Option<String>,
+    pub limit: u32,
+    pub offset: u32,
+    /// Sort by volume instead of relevance
+    pub trending: bool,
+}
+
+/// A market in search results (no outcomes, to keep context small)
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketSummary {
+    pub title: String,
+    pub slug: String,
+    pub description: String,
+    pub end_date: String,
+    pub volume: String,
+    pub liquidity: String,
+}
+
+/// A market with its questions and tradeable outcomes
+#[derive(Debug, Clone, Serialize)]
+pub struct Market {
+    pub title: String,
+    pub slug: String,
+    pub description: String,
+    pub end_date: String,
+    pub volume: String,
+    pub liquidity: String,
+    pub outcomes: Vec<MarketQuestion>,
+}
+
+/// One question within a market
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketQuestion {
+    pub question: String,
+    pub condition_id: String,
+    pub outcomes: Vec<MarketOutcome>,
+}
+
+/// A tradeable outcome of a question
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketOutcome {
+    pub name: String,
+    /// Last price as probability 0-1, or "N/A"
+    pub price: String,
+    pub token_id: String,
+}
+
+/// Price snapshot for an outcome token. Prices are strings as reported by
+/// the venue, "N/A" when unavailable.
+#[derive(Debug, Clone)]
+pub struct MarketPrice {
+    pub midpoint: String,
+    pub best_bid: String,
+    pub best_ask: String,
+    /// Raw spread response, if the venue returned one
+    pub spread: Option<Value>,
+    /// Number of price levels on each side of the book
+    pub bid_levels: usize,
+    pub ask_levels: usize,
+}
+
+/// Order side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+impl OrderSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "buy" => Some(OrderSide::Buy),
+            "sell" => Some(OrderSide::Sell),
+            _ => None,
+        }
+    }
+}
+
+/// A validated limit order
+#[derive(Debug, Clone)]
+pub struct OrderRequest {
+    pub token_id: String,
+    pub side: OrderSide,
+    /// Probability price, rounded to the 0.001 tick
+    pub price: f64,
+    /// Shares, rounded to 2 decimals
+    pub size: f64,
+    /// Venue order type (e.g. "GTC")
+    pub order_type: String,
+}
+
+/// Venue response to a placed order
+#[derive(Debug, Clone)]
+pub struct PlacedOrder {
+    pub order_id: String,
+    pub success: bool,
+    /// Address of the wallet that placed it
+    pub wallet_address: String,
+}
+
+/// A wallet's positions as reported by the venue
+#[derive(Debug, Clone)]
+pub struct Positions {
+    pub wallet_address: String,
+    pub positions: Value,
+}
+
+/// Tool parameters shared by every venue's discovery and order actions
+#[derive(Debug, Default, Deserialize)]
+pub struct MarketParams {
+    // Discovery params
+    pub query: Option<String>,
+    pub slug: Option<String>,
+    pub tag: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    // Trading params
+    pub token_id: Option<String>,
+    pub side: Option<String>,
+    pub price: Option<f64>,
+    pub size: Option<f64>,
+    pub order_type: Option<String>,
+}
+
+/// `search_markets` / `trending_markets`
+pub async fn search_markets(market: &dyn PredictionMarket, params: &MarketParams, trending: bool) -> ToolResult {
+    let query = MarketQuery {
+        query: params.query.clone().unwrap_or_default(),
+        tag: params.tag.clone(),
+        limit: params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).min(MAX_SEARCH_LIMIT),
+        offset: params.offset.unwrap_or(0),
+        trending,
+    };
+
+    let markets = match market.search(&query).await {
+        Ok(m) => m,
+        Err(e) => return ToolResult::error(e),
+    };
+
+    let mut result = json!({
+        "status": "success",
+        "tag": query.tag,
+        "count": markets.len(),
+        "offset": query.offset,
+        "limit": query.limit,
+        "markets": markets,
+        "note": "Use get_market with slug to see outcomes and token_ids for trading."
+    });
+    if trending {
+        result["type"] = json!("trending");
+    } else {
+        result["query"] = json!(query.query);
+    }
+    ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+}
+
+/// `get_market`
+pub async fn get_market(market: &dyn PredictionMarket, params: &MarketParams) -> ToolResult {
+    let slug = match &params.slug {
+        Some(s) => s,
+        None => return ToolResult::error("slug is required for get_market (e.g., 'will-bitcoin-hit-100k')"),
+    };
+
+    match market.get_market(slug).await {
+        Ok(Some(market_info)) => {
+            let result = json!({
+                "status": "success",
+                "market": market_info,
+                "note": "Use the token_id values with place_order to trade specific outcomes."
+            });
+            ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+        }
+        Ok(None) => ToolResult::error(format!("Market not found with slug: {}", slug)),
+        Err(e) => ToolResult::error(e),
+    }
+}
+
+/// `get_price`
+pub async fn get_price(market: &dyn PredictionMarket, params: &MarketParams) -> ToolResult {
+    let token_id = match &params.token_id {
+        Some(t) => t,
+        None => return ToolResult::error("token_id is required for get_price"),
+    };
+
+    match market.get_price(token_id).await {
+        Ok(price) => {
+            let result = json!({
+                "status": "success",
+                "token_id": token_id,
+                "price": {
+                    "midpoint": price.midpoint,
+                    "best_bid": price.best_bid,
+                    "best_ask": price.best_ask,
+                    "spread": price.spread,
+                },
+                "orderbook_summary": {
+                    "bids": price.bid_levels,
+                    "asks": price.ask_levels,
+                },
+                "note": "Prices are 0-1 representing probability. Use this token_id with place_order to trade."
+            });
+            ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+        }
+        Err(e) => ToolResult::error(e),
+    }
+}
+
+/// Validate `place_order` parameters
+pub fn order_request(params: &MarketParams) -> Result<OrderRequest, String> {
+    let token_id = params.token_id.clone().ok_or("token_id is required for place_order")?;
+
+    let side = match &params.side {
+        Some(s) => OrderSide::from_str(s).ok_or_else(|| format!("Invalid side: {}. Use 'buy' or 'sell'", s))?,
+        None => return Err("side is required for place_order (buy or sell)".to_string()),
+    };
+
+    // Price must be between 0.001 and 0.999, rounded to 3 decimal places (tick size 0.001)
+    let price = match params.price {
+        Some(p) if p > 0.0 && p < 1.0 => (p * 1000.0).round() / 1000.0,
+        Some(p) => {
+            return Err(format!(
+                "price must be between 0.01 and 0.99, got {}. Price represents probability (0.65 = 65%)",
+                p
+            ))
+        }
+        None => return Err("price is required for place_order".to_string()),
+    };
+
+    // Size is in shares (whole numbers recommended, 2 decimal places max)
+    let size = match params.size {
+        Some(s) if s > 0.0 => (s * 100.0).round() / 100.0,
+        Some(s) => return Err(format!("size must be positive, got {}", s)),
+        None => return Err("size is required for place_order".to_string()),
+    };
+
+    Ok(OrderRequest {
+        token_id,
+        side,
+        price,
+        size,
+        order_type: params.order_type.clone().unwrap_or_else(|| "GTC".to_string()),
+    })
+}
+
+/// `place_order` result
+pub fn order_placed_result(market: &dyn PredictionMarket, order: &OrderRequest, placed: &PlacedOrder) -> ToolResult {
+    let result = json!({
+        "status": "success",
+        "order_id": placed.order_id,
+        "success": placed.success,
+        "details": {
+            "token_id": order.token_id,
+            "side": order.side.as_str(),
+            "price": order.price,
+            "size": order.size,
+            "order_type": order.order_type,
+            "usdc_cost": format!("{:.2}", order.size * order.price),
+            "potential_payout": format!("{:.2}", order.size),
+        },
+        "wallet": placed.wallet_address,
+        "network": market.network()
+    });
+    ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+}
+
+/// `get_positions`
+pub async fn get_positions(market: &dyn PredictionMarket, wallet: Option<&str>) -> ToolResult {
+    match market.positions(wallet).await {
+        Ok(p) => {
+            let result = json!({
+                "status": "success",
+                "wallet": p.wallet_address,
+                "positions": p.positions,
+            });
+            ToolResult::success(serde_json::to_string_pretty(&result).unwrap())
+        }
+        Err(e) => ToolResult::error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(side: &str, price: f64, size: f64) -> MarketParams {
+        MarketParams {
+            token_id: Some("123".to_string()),
+            side: Some(side.to_string()),
+            price: Some(price),
+            size: Some(size),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_order_request_validation() {
+        let order = order_request(&params("BUY", 0.6543, 10.006)).unwrap();
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.price, 0.654);
+        assert_eq!(order.size, 10.01);
+        assert_eq!(order.order_type, "GTC");
+
+        assert!(order_request(&params("hold", 0.5, 10.0)).unwrap_err().contains("Invalid side"));
+        assert!(order_request(&params("buy", 1.2, 10.0)).unwrap_err().contains("price must be"));
+        assert!(order_request(&params("sell", 0.5, 0.0)).unwrap_err().contains("size must be positive"));
+        assert!(order_request(&MarketParams::default()).unwrap_err().contains("token_id is required"));
+    }
+}
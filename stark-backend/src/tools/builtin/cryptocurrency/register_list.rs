@@ -0,0 +1,247 @@
+//! Register List tool for inspecting and resetting the register store
+//!
+//! Lists the registers set in the current session (value, source tool, age)
+//! so a preset failing with "register not set" can be debugged, and clears
+//! one or all registers. Registers and JSON fields whose names look like
+//! secrets are redacted in the listing. The listing also shows the context
+//! bank's contents and size against its limits.
+
+use crate::tools::redaction::REDACTED;
+use crate::tools::register::IntrinsicRegister;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Name fragments of registers (or JSON fields) whose values are never listed
+const SECRET_MARKERS: &[&str] = &[
+    "private_key",
+    "privatekey",
+    "secret",
+    "password",
+    "passphrase",
+    "mnemonic",
+    "seed_phrase",
+    "api_key",
+    "apikey",
+    "access_token",
+    "auth_token",
+    "bearer",
+    "jwt",
+];
+
+/// Intrinsic registers, always readable without being set
+const INTRINSIC_REGISTERS: &[&str] = &["wallet_address"];
+
+/// Register List tool
+pub struct RegisterListTool {
+    definition: ToolDefinition,
+}
+
+impl RegisterListTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'list' shows the registers currently set (default); 'clear' removes one register (with 'key') or all of them.".to_string(),
+                default: Some(json!("list")),
+                items: None,
+                enum_values: Some(vec!["list".to_string(), "clear".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "key".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register to clear. Omit to clear all registers.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        RegisterListTool {
+            definition: ToolDefinition {
+                name: "register_list".to_string(),
+                description: "List the registers currently set (values, source tool, age) and the context bank's contents and size, or clear the registers. Use it to debug 'register not set' errors in preset-driven web3 flows. Secret-looking values are redacted.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+
+    /// Whether a register or field name looks like it holds a secret
+    fn is_secret_name(name: &str) -> bool {
+        let name = name.to_lowercase();
+        SECRET_MARKERS.iter().any(|marker| name.contains(marker))
+    }
+
+    /// Copy of a register value with secret-looking fields redacted
+    fn redact(key: &str, value: &Value) -> Value {
+        if Self::is_secret_name(key) {
+            return json!(REDACTED);
+        }
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), Self::redact(k, v)))
+                    .collect(),
+            ),
+            Value::Array(items) => Value::Array(items.iter().map(|v| Self::redact(key, v)).collect()),
+            _ => value.clone(),
+        }
+    }
+
+    fn list(context: &ToolContext) -> ToolResult {
+        let mut keys = context.registers.keys();
+        keys.sort();
+
+        let registers: Vec<Value> = keys
+            .iter()
+            .filter_map(|key| {
+                let entry = context.registers.get_entry(key)?;
+                Some(json!({
+                    "key": key,
+                    "value": Self::redact(key, &entry.value),
+                    "source": entry.source_tool,
+                    "age_secs": entry.created_at.elapsed().as_secs(),
+                }))
+            })
+            .collect();
+
+        // Intrinsics resolve on read, so they're available even when unset
+        let intrinsics: Vec<&str> = INTRINSIC_REGISTERS
+            .iter()
+            .copied()
+            .filter(|name| !keys.iter().any(|k| k == name))
+            .filter(|name| IntrinsicRegister::from_name(name).and_then(|i| i.resolve(context, None)).is_some())
+            .collect();
+
+        let mut content = if registers.is_empty() {
+            "No registers set.".to_string()
+        } else {
+            registers
+                .iter()
+                .map(|r| {
+                    format!(
+                        "{} = {} (from {}, {}s ago)",
+                        r["key"].as_str().unwrap_or(""),
+                        r["value"],
+                        r["source"].as_str().unwrap_or(""),
+                        r["age_secs"]
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let bank = context.context_bank.to_json();
+        content.push_str(&format!(
+            "\n\nContext bank: {} items, {}/{} bytes (max {} items)",
+            bank["count"], bank["bytes"], bank["max_bytes"], bank["max_items"]
+        ));
+        if let Some(formatted) = bank["formatted"].as_str() {
+            content.push_str(&format!("\n{}", formatted));
+        }
+
+        ToolResult::success(content).with_metadata(json!({
+            "count": registers.len(),
+            "registers": registers,
+            "intrinsic": intrinsics,
+            "context_bank": bank,
+        }))
+    }
+}
+
+impl Default for RegisterListTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterListParams {
+    action: Option<String>,
+    key: Option<String>,
+}
+
+#[async_trait]
+impl Tool for RegisterListTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: RegisterListParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        match params.action.as_deref().unwrap_or("list") {
+            "list" => Self::list(context),
+            "clear" => {
+                let removed = context.clear_registers(params.key.as_deref());
+                log::info!("[register_list] Cleared {} register(s) (key: {:?})", removed, params.key);
+                let message = match (&params.key, removed) {
+                    (Some(key), 0) => format!("Register '{}' was not set", key),
+                    (Some(key), _) => format!("Cleared register '{}'", key),
+                    (None, n) => format!("Cleared {} register(s)", n),
+                };
+                ToolResult::success(message).with_metadata(json!({ "removed": removed }))
+            }
+            other => ToolResult::error(format!("Unknown action: '{}'. Use 'list' or 'clear'", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::register::RegisterStore;
+
+    #[test]
+    fn test_redacts_secret_looking_registers() {
+        assert_eq!(RegisterListTool::redact("signer_private_key", &json!("0xabc")), json!(REDACTED));
+
+        let quote = json!({"to": "0x123", "auth": {"api_key": "k", "scope": "read"}});
+        let redacted = RegisterListTool::redact("swap_quote", &quote);
+        assert_eq!(redacted["to"], "0x123");
+        assert_eq!(redacted["auth"]["api_key"], REDACTED);
+        assert_eq!(redacted["auth"]["scope"], "read");
+
+        assert_eq!(RegisterListTool::redact("sell_token", &json!("USDC")), json!("USDC"));
+    }
+
+    #[tokio::test]
+    async fn test_list_and_clear() {
+        let tool = RegisterListTool::new();
+        let context = ToolContext::new().with_registers(RegisterStore::new());
+        context.registers.set("sell_amount", json!("10"), "register_set");
+        context.registers.set("swap_quote", json!({"to": "0x123"}), "x402_fetch");
+
+        let listed = tool.execute(json!({}), &context).await;
+        assert!(listed.success);
+        assert_eq!(listed.metadata.as_ref().unwrap()["count"], 2);
+        assert!(listed.content.contains("sell_amount = \"10\" (from register_set"));
+
+        let cleared = tool.execute(json!({"action": "clear", "key": "sell_amount"}), &context).await;
+        assert!(cleared.success);
+        assert!(!context.registers.exists("sell_amount"));
+        assert!(context.registers.exists("swap_quote"));
+
+        tool.execute(json!({"action": "clear"}), &context).await;
+        assert!(context.registers.keys().is_empty());
+    }
+}
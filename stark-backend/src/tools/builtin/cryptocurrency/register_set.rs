@@ -3,6 +3,7 @@
 //! This tool allows skills to set values into registers for use by other tools.
 //! Used in conjunction with tools that read from registers (like web3_tx with from_register).
 
+use crate::tools::register::validate_register_key;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -70,17 +71,7 @@ impl RegisterSetTool {
 
     /// Validate a register key name
     fn validate_key(key: &str) -> Result<(), String> {
-        if key.is_empty() {
-            return Err("Register key cannot be empty".to_string());
-        }
-        if key.len() > 64 {
-            return Err("Register key too long (max 64 characters)".to_string());
-        }
-        // Allow alphanumeric and underscore only
-        if !key.chars().all(|c| c.is_alphanumeric() || c == '_') {
-            return Err("Register key must contain only alphanumeric characters and underscores".to_string());
-        }
-        Ok(())
+        validate_register_key(key)
     }
 
     /// Check if a string is a valid Ethereum address
@@ -115,6 +106,8 @@ impl RegisterSetTool {
         ("amount_raw", "Use 'to_raw_amount' tool with decimals: 18 for ETH. This ensures safe conversion from human amounts."),
         ("transfer_data", "Do not use transfer_data. Set 'send_to' and 'amount_raw' registers separately instead."),
         ("transfer_tx", "Do not use transfer_tx. Set 'send_to' and 'amount_raw' registers separately instead."),
+        // x402 payment challenges - only x402_inspect may capture them
+        ("x402_challenge", "Use 'x402_inspect' tool to capture a payment challenge"),
     ];
 
     /// Register keys that MUST contain valid Ethereum addresses (if not blocked)
@@ -299,6 +292,7 @@ mod tests {
         assert!(RegisterSetTool::check_blocked("sell_token").is_err());
         assert!(RegisterSetTool::check_blocked("buy_token").is_err());
         assert!(RegisterSetTool::check_blocked("wallet_address").is_err());
+        assert!(RegisterSetTool::check_blocked("x402_challenge").is_err());
 
         // Swap execution registers are blocked (must use decode_calldata)
         assert!(RegisterSetTool::check_blocked("swap_param_0").is_err());
@@ -0,0 +1,413 @@
+//! Resolve Name tool for ENS names and Base basenames
+//!
+//! Forward-resolves `vitalik.eth` (ENS on mainnet) and `jesse.base.eth`
+//! (basenames on Base) to addresses via the registry's resolver, and
+//! reverse-resolves an address to its primary name. Resolutions are cached
+//! briefly. Used directly and by send_eth / web3_function_call when an
+//! address field holds a name.
+
+use super::address_book::lookup_recipient;
+use crate::tools::registry::Tool;
+use crate::tools::rpc_config::resolve_rpc_from_context;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::x402::X402EvmRpc;
+use async_trait::async_trait;
+use ethers::abi::{decode, ParamType, Token};
+use ethers::providers::ens::{namehash, ADDR_SELECTOR, ENS_ADDRESS, NAME_SELECTOR};
+use ethers::types::{Address, H256};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Basenames registry on Base (ENS-compatible)
+const BASENAME_REGISTRY: &str = "0xB94704422c2a1E396835A571837Aa5AE53285a95";
+
+/// resolver(bytes32) selector on the registry
+const RESOLVER_SELECTOR: [u8; 4] = [0x01, 0x78, 0xb8, 0xbf];
+
+/// Reverse namespace for Base primary names (coinType 0x80000000 | 8453)
+const BASE_REVERSE_DOMAIN: &str = "80002105.reverse";
+
+/// How long successful resolutions are cached
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+lazy_static::lazy_static! {
+    static ref NAME_CACHE: RwLock<HashMap<String, (String, Instant)>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Which naming system a name belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NameService {
+    /// ENS on Ethereum mainnet
+    Ens,
+    /// Basenames on Base
+    Basename,
+}
+
+impl NameService {
+    fn for_name(name: &str) -> Self {
+        if name.ends_with(".base.eth") {
+            NameService::Basename
+        } else {
+            NameService::Ens
+        }
+    }
+
+    fn network(&self) -> &'static str {
+        match self {
+            NameService::Ens => "mainnet",
+            NameService::Basename => "base",
+        }
+    }
+
+    fn registry(&self) -> Address {
+        match self {
+            NameService::Ens => ENS_ADDRESS,
+            NameService::Basename => BASENAME_REGISTRY.parse().expect("valid basename registry address"),
+        }
+    }
+
+    /// Reverse record name for an address in this service
+    fn reverse_name(&self, address: Address) -> String {
+        let addr_hex = hex::encode(address.as_bytes());
+        match self {
+            NameService::Ens => format!("{}.addr.reverse", addr_hex),
+            NameService::Basename => format!("{}.{}", addr_hex, BASE_REVERSE_DOMAIN),
+        }
+    }
+}
+
+/// Whether a value looks like an ENS/basename name rather than a hex address
+pub(super) fn looks_like_name(value: &str) -> bool {
+    let value = value.trim();
+    if value.starts_with("0x") || !value.contains('.') {
+        return false;
+    }
+    let tld = value.rsplit('.').next().unwrap_or("");
+    !tld.is_empty()
+        && tld.chars().all(|c| c.is_ascii_alphabetic())
+        && value.split('.').all(|label| !label.is_empty())
+        && !value.contains(char::is_whitespace)
+}
+
+fn cache_get(key: &str) -> Option<String> {
+    let cache = NAME_CACHE.read().ok()?;
+    cache
+        .get(key)
+        .filter(|(_, at)| at.elapsed() < CACHE_TTL)
+        .map(|(v, _)| v.clone())
+}
+
+fn cache_put(key: String, value: String) {
+    if let Ok(mut cache) = NAME_CACHE.write() {
+        cache.retain(|_, (_, at)| at.elapsed() < CACHE_TTL);
+        cache.insert(key, (value, Instant::now()));
+    }
+}
+
+/// Decode a 32-byte ABI word as an address (None if zero)
+fn decode_address_word(data: &[u8]) -> Option<Address> {
+    if data.len() < 32 {
+        return None;
+    }
+    let address = Address::from_slice(&data[12..32]);
+    if address.is_zero() {
+        None
+    } else {
+        Some(address)
+    }
+}
+
+/// Decode an ABI-encoded string return value (None if empty)
+fn decode_string(data: &[u8]) -> Option<String> {
+    match decode(&[ParamType::String], data).ok()?.pop()? {
+        Token::String(s) if !s.is_empty() => Some(s),
+        _ => None,
+    }
+}
+
+fn node_call(selector: [u8; 4], node: H256) -> Vec<u8> {
+    [&selector[..], node.as_bytes()].concat()
+}
+
+fn rpc_for(network: &str, context: &ToolContext) -> Result<X402EvmRpc, String> {
+    let private_key = crate::config::burner_wallet_private_key()
+        .ok_or("BURNER_WALLET_BOT_PRIVATE_KEY not set")?;
+    let rpc_config = resolve_rpc_from_context(&context.extra, network);
+    X402EvmRpc::new_with_config(&private_key, network, &rpc_config)
+}
+
+/// Look up the resolver contract for a node
+async fn get_resolver(rpc: &X402EvmRpc, service: NameService, node: H256) -> Result<Option<Address>, String> {
+    let result = rpc.call(service.registry(), &node_call(RESOLVER_SELECTOR, node)).await?;
+    Ok(decode_address_word(&result))
+}
+
+/// Resolve a name to an address. Errors if the name has no resolver or no address.
+pub(super) async fn resolve_name_to_address(name: &str, context: &ToolContext) -> Result<String, String> {
+    let name = name.trim().to_lowercase();
+    let cache_key = format!("fwd:{}", name);
+    if let Some(cached) = cache_get(&cache_key) {
+        return Ok(cached);
+    }
+
+    let service = NameService::for_name(&name);
+    let rpc = rpc_for(service.network(), context)?;
+    let node = namehash(&name);
+
+    let resolver = get_resolver(&rpc, service, node)
+        .await
+        .map_err(|e| format!("Failed to look up resolver for '{}': {}", name, e))?
+        .ok_or_else(|| format!("Name '{}' is not registered (no resolver set)", name))?;
+
+    let result = rpc
+        .call(resolver, &node_call(ADDR_SELECTOR, node))
+        .await
+        .map_err(|e| format!("Failed to resolve '{}': {}", name, e))?;
+    let address = decode_address_word(&result)
+        .ok_or_else(|| format!("Name '{}' does not resolve to an address", name))?;
+
+    let address = format!("{:?}", address);
+    log::info!("[resolve_name] {} -> {} (via {})", name, address, service.network());
+    cache_put(cache_key, address.clone());
+    Ok(address)
+}
+
+/// Reverse-resolve an address to its primary name in a service.
+/// The name is only returned if it forward-resolves back to the same address.
+async fn reverse_resolve(address: Address, service: NameService, context: &ToolContext) -> Result<Option<String>, String> {
+    let cache_key = format!("rev:{:?}:{:?}", service, address);
+    if let Some(cached) = cache_get(&cache_key) {
+        return Ok(Some(cached));
+    }
+
+    let rpc = rpc_for(service.network(), context)?;
+    let node = namehash(&service.reverse_name(address));
+
+    let resolver = match get_resolver(&rpc, service, node).await? {
+        Some(r) => r,
+        None => return Ok(None),
+    };
+    let result = rpc.call(resolver, &node_call(NAME_SELECTOR, node)).await?;
+    let name = match decode_string(&result) {
+        Some(n) => n,
+        None => return Ok(None),
+    };
+
+    // Verify the forward record matches, otherwise the reverse record is spoofable
+    match resolve_name_to_address(&name, context).await {
+        Ok(forward) if forward.eq_ignore_ascii_case(&format!("{:?}", address)) => {
+            cache_put(cache_key, name.clone());
+            Ok(Some(name))
+        }
+        _ => {
+            log::warn!("[resolve_name] Reverse record {} for {:?} does not resolve back", name, address);
+            Ok(None)
+        }
+    }
+}
+
+/// Resolve a value that may be a hex address, an address book name, or an
+/// ENS/basename, for use in address fields.
+/// Returns (address, Some(name)) when a name was resolved.
+pub(super) async fn resolve_address_field(value: &str, context: &ToolContext) -> Result<(String, Option<String>), String> {
+    if let Some(entry) = lookup_recipient(value, context) {
+        Ok((entry.address, Some(entry.name)))
+    } else if looks_like_name(value) {
+        let address = resolve_name_to_address(value, context).await?;
+        Ok((address, Some(value.trim().to_lowercase())))
+    } else {
+        Ok((value.to_string(), None))
+    }
+}
+
+/// Resolve Name tool
+pub struct ResolveNameTool {
+    definition: ToolDefinition,
+}
+
+impl ResolveNameTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "name".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Name to resolve to an address, e.g. 'vitalik.eth' (ENS) or 'jesse.base.eth' (basename)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "address".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Address to reverse-resolve to its primary ENS name and basename".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "cache_as".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register to store the resolved address in (e.g. 'send_to'). Only used with 'name'.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        ResolveNameTool {
+            definition: ToolDefinition {
+                name: "resolve_name".to_string(),
+                description: "Resolve an ENS name (.eth) or Base basename (.base.eth) to an address, or reverse-resolve an address to its primary name. Provide either 'name' or 'address'.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+}
+
+impl Default for ResolveNameTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ResolveNameParams {
+    name: Option<String>,
+    address: Option<String>,
+    cache_as: Option<String>,
+}
+
+#[async_trait]
+impl Tool for ResolveNameTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ResolveNameParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        match (params.name, params.address) {
+            (Some(name), _) => {
+                if !looks_like_name(&name) {
+                    return ToolResult::error(format!(
+                        "'{}' is not a valid name. Expected something like 'vitalik.eth' or 'jesse.base.eth'.",
+                        name
+                    ));
+                }
+
+                match resolve_name_to_address(&name, context).await {
+                    Ok(address) => {
+                        let mut msg = format!("{} -> {}", name.to_lowercase(), address);
+                        if let Some(ref reg) = params.cache_as {
+                            context.set_register(reg, json!(&address), "resolve_name");
+                            msg.push_str(&format!("\nCached in register: '{}'", reg));
+                        }
+                        ToolResult::success(msg).with_metadata(json!({
+                            "name": name.to_lowercase(),
+                            "address": address,
+                            "service": NameService::for_name(&name.to_lowercase()).network(),
+                            "cached_in_register": params.cache_as,
+                        }))
+                    }
+                    Err(e) => ToolResult::error(e),
+                }
+            }
+            (None, Some(address)) => {
+                let parsed: Address = match address.parse() {
+                    Ok(a) => a,
+                    Err(_) => return ToolResult::error(format!("Invalid address: {}", address)),
+                };
+
+                let ens = reverse_resolve(parsed, NameService::Ens, context).await;
+                let basename = reverse_resolve(parsed, NameService::Basename, context).await;
+
+                let mut lines = vec![format!("Primary names for {:?}:", parsed)];
+                for (label, result) in [("ENS", &ens), ("Basename", &basename)] {
+                    match result {
+                        Ok(Some(name)) => lines.push(format!("  {}: {}", label, name)),
+                        Ok(None) => lines.push(format!("  {}: none", label)),
+                        Err(e) => lines.push(format!("  {}: lookup failed ({})", label, e)),
+                    }
+                }
+
+                ToolResult::success(lines.join("\n")).with_metadata(json!({
+                    "address": format!("{:?}", parsed),
+                    "ens": ens.ok().flatten(),
+                    "basename": basename.ok().flatten(),
+                }))
+            }
+            (None, None) => ToolResult::error("Provide either 'name' or 'address'"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_name() {
+        assert!(looks_like_name("vitalik.eth"));
+        assert!(looks_like_name("jesse.base.eth"));
+        assert!(looks_like_name(" Nick.ETH "));
+        assert!(!looks_like_name("0x1234567890abcdef1234567890abcdef12345678"));
+        assert!(!looks_like_name("vitalik"));
+        assert!(!looks_like_name("foo..eth"));
+        assert!(!looks_like_name("1.5"));
+    }
+
+    #[test]
+    fn test_service_for_name() {
+        assert_eq!(NameService::for_name("vitalik.eth"), NameService::Ens);
+        assert_eq!(NameService::for_name("jesse.base.eth"), NameService::Basename);
+        assert_eq!(NameService::Basename.network(), "base");
+    }
+
+    #[test]
+    fn test_reverse_name() {
+        let addr: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
+        assert_eq!(
+            NameService::Ens.reverse_name(addr),
+            "d8da6bf26964af9d7eed9e03e53415d37aa96045.addr.reverse"
+        );
+        assert_eq!(
+            NameService::Basename.reverse_name(addr),
+            "d8da6bf26964af9d7eed9e03e53415d37aa96045.80002105.reverse"
+        );
+    }
+
+    #[test]
+    fn test_decode_helpers() {
+        let mut word = [0u8; 32];
+        assert_eq!(decode_address_word(&word), None);
+        word[31] = 1;
+        assert_eq!(decode_address_word(&word), Some(Address::from_low_u64_be(1)));
+
+        let encoded = ethers::abi::encode(&[Token::String("vitalik.eth".to_string())]);
+        assert_eq!(decode_string(&encoded), Some("vitalik.eth".to_string()));
+        let empty = ethers::abi::encode(&[Token::String(String::new())]);
+        assert_eq!(decode_string(&empty), None);
+    }
+}
@@ -13,10 +13,13 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::path::Path;
-use std::sync::OnceLock;
+use std::sync::{Arc, RwLock};
 
-/// Global token storage (loaded once at startup)
-static TOKENS: OnceLock<HashMap<String, HashMap<String, TokenInfo>>> = OnceLock::new();
+/// Network -> symbol -> token info
+type TokenMap = HashMap<String, HashMap<String, TokenInfo>>;
+
+/// Global token storage (loaded at startup, swapped whole on reload)
+static TOKENS: RwLock<Option<Arc<TokenMap>>> = RwLock::new(None);
 
 /// Token info loaded from config
 #[derive(Debug, Clone, Deserialize)]
@@ -26,40 +29,80 @@ pub struct TokenInfo {
     pub name: String,
 }
 
-/// Load tokens from config directory. Panics if config file is missing or invalid.
-pub fn load_tokens(config_dir: &Path) {
+/// Read, parse and validate config/tokens.ron
+fn parse_tokens(config_dir: &Path) -> Result<TokenMap, String> {
     let tokens_path = config_dir.join("tokens.ron");
 
     if !tokens_path.exists() {
-        panic!("[tokens] Config file not found: {:?}", tokens_path);
+        return Err(format!("Config file not found: {:?}", tokens_path));
     }
 
     let content = std::fs::read_to_string(&tokens_path)
-        .unwrap_or_else(|e| panic!("[tokens] Failed to read {:?}: {}", tokens_path, e));
+        .map_err(|e| format!("Failed to read {:?}: {}", tokens_path, e))?;
+
+    let tokens: TokenMap = ron::from_str(&content)
+        .map_err(|e| format!("Failed to parse {:?}: {}", tokens_path, e))?;
 
-    let tokens: HashMap<String, HashMap<String, TokenInfo>> = ron::from_str(&content)
-        .unwrap_or_else(|e| panic!("[tokens] Failed to parse {:?}: {}", tokens_path, e));
+    for (network, network_tokens) in &tokens {
+        for (symbol, info) in network_tokens {
+            let valid = info.address.len() == 42
+                && info.address.starts_with("0x")
+                && info.address[2..].chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                return Err(format!(
+                    "Invalid address for {} on {}: {}",
+                    symbol, network, info.address
+                ));
+            }
+        }
+    }
 
+    Ok(tokens)
+}
+
+fn set_tokens(tokens: TokenMap, source: &Path) -> usize {
     let total: usize = tokens.values().map(|t| t.len()).sum();
     log::info!(
         "[tokens] Loaded {} tokens across {} networks from {:?}",
         total,
         tokens.len(),
-        tokens_path
+        source.join("tokens.ron")
     );
+    *TOKENS.write().unwrap() = Some(Arc::new(tokens));
+    total
+}
+
+/// Load tokens from config directory. Panics if config file is missing or invalid.
+pub fn load_tokens(config_dir: &Path) {
+    match parse_tokens(config_dir) {
+        Ok(tokens) => {
+            set_tokens(tokens, config_dir);
+        }
+        Err(e) => panic!("[tokens] {}", e),
+    }
+}
+
+/// Reload tokens from config directory, returning the number of tokens.
+/// The file is validated first; on error the loaded tokens are kept.
+pub fn reload_tokens(config_dir: &Path) -> Result<usize, String> {
+    let tokens = parse_tokens(config_dir)?;
+    Ok(set_tokens(tokens, config_dir))
+}
 
-    let _ = TOKENS.set(tokens);
+/// Loaded tokens, if load_tokens() was called
+fn loaded_tokens() -> Option<Arc<TokenMap>> {
+    TOKENS.read().unwrap().clone()
 }
 
 /// Get tokens. Panics if load_tokens() was not called.
-fn get_tokens() -> &'static HashMap<String, HashMap<String, TokenInfo>> {
-    TOKENS.get().expect("[tokens] Token config not loaded - call load_tokens() first")
+fn get_tokens() -> Arc<TokenMap> {
+    loaded_tokens().expect("[tokens] Token config not loaded - call load_tokens() first")
 }
 
 /// Get all token symbols with their names (for context bank scanning)
 /// Returns a list of (symbol, name) pairs from all networks
 pub fn get_all_token_symbols() -> Vec<(String, String)> {
-    let tokens = match TOKENS.get() {
+    let tokens = match loaded_tokens() {
         Some(t) => t,
         None => return Vec::new(), // Return empty if tokens not loaded yet
     };
@@ -79,6 +122,16 @@ pub fn get_all_token_symbols() -> Vec<(String, String)> {
     result
 }
 
+/// Find a known token on a network by contract address.
+/// Returns (symbol, info), or None if the address isn't in tokens.ron.
+pub fn find_token_by_address(network: &str, address: &str) -> Option<(String, TokenInfo)> {
+    loaded_tokens()?
+        .get(network)?
+        .iter()
+        .find(|(_, info)| info.address.eq_ignore_ascii_case(address.trim()))
+        .map(|(symbol, info)| (symbol.clone(), info.clone()))
+}
+
 /// Token Lookup tool
 pub struct TokenLookupTool {
     definition: ToolDefinition,
@@ -141,7 +194,7 @@ impl TokenLookupTool {
         }
     }
 
-    fn lookup(symbol: &str, network: &str) -> Option<TokenInfo> {
+    pub(super) fn lookup(symbol: &str, network: &str) -> Option<TokenInfo> {
         let symbol_upper = symbol.to_uppercase();
         let tokens = get_tokens();
 
@@ -158,7 +211,13 @@ impl TokenLookupTool {
                     "[token_lookup] Network '{}' has tokens: {:?}",
                     network, network_tokens.keys().collect::<Vec<_>>()
                 );
-                network_tokens.get(&symbol_upper)
+                // Config keys may be mixed-case (e.g. "USDbC", "cbBTC")
+                network_tokens.get(&symbol_upper).or_else(|| {
+                    network_tokens
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case(symbol))
+                        .map(|(_, info)| info)
+                })
             })
             .cloned();
 
@@ -166,7 +225,7 @@ impl TokenLookupTool {
         result
     }
 
-    fn list_available(network: &str) -> Vec<String> {
+    pub(super) fn list_available(network: &str) -> Vec<String> {
         let tokens = get_tokens();
 
         tokens
@@ -322,6 +381,26 @@ mod tests {
         assert_eq!(token2.address, token3.address);
     }
 
+    #[test]
+    fn test_reload_keeps_tokens_on_invalid_file() {
+        setup();
+        let dir = tempfile::tempdir().unwrap();
+
+        std::fs::write(dir.path().join("tokens.ron"), "{ \"base\": { \"USDC\": (").unwrap();
+        assert!(reload_tokens(dir.path()).unwrap_err().contains("Failed to parse"));
+
+        std::fs::write(
+            dir.path().join("tokens.ron"),
+            r#"{ "base": { "USDC": (address: "0x1234", decimals: 6, name: "USD Coin") } }"#,
+        )
+        .unwrap();
+        assert!(reload_tokens(dir.path()).unwrap_err().contains("Invalid address for USDC"));
+
+        // The loaded config is untouched
+        let token = TokenLookupTool::lookup("USDC", "base").unwrap();
+        assert_eq!(token.address, "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+    }
+
     #[test]
     fn test_eth_special_address() {
         setup();
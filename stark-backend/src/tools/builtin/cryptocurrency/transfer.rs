@@ -0,0 +1,152 @@
+//! Token transfers outside the agent tool loop
+//!
+//! Signs and queues a native or ERC20 transfer from the burner wallet in one
+//! call, for flows like Discord tips that already know the recipient, token
+//! and amount. Transfers are QUEUED, not broadcast - the caller is
+//! responsible for getting them confirmed.
+
+use super::network_lookup::resolve_network;
+use super::to_raw_amount::ToRawAmountTool;
+use super::token_lookup::{TokenInfo, TokenLookupTool};
+use super::web3_function_call::Web3FunctionCallTool;
+use super::web3_tx::parse_u256;
+use crate::tools::rpc_config::resolve_rpc_from_network;
+use crate::tx_queue::{QueuedTransaction, TxQueueManager};
+use crate::wallet::WalletSigner;
+use ethers::abi::Token;
+use ethers::prelude::*;
+use uuid::Uuid;
+
+/// Placeholder address used in the token config for the native currency
+const NATIVE_TOKEN_ADDRESS: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+/// A resolved token on a specific network
+#[derive(Debug, Clone)]
+pub struct TransferToken {
+    pub symbol: String,
+    pub network: String,
+    pub info: TokenInfo,
+}
+
+impl TransferToken {
+    /// Look up a token symbol on a network (defaults to base)
+    pub fn resolve(symbol: &str, network: Option<&str>) -> Result<Self, String> {
+        let network = resolve_network(network, None)?;
+        let info = TokenLookupTool::lookup(symbol, &network).ok_or_else(|| {
+            format!(
+                "Unknown token '{}' on {}. Available: {}",
+                symbol,
+                network,
+                TokenLookupTool::list_available(&network).join(", ")
+            )
+        })?;
+        Ok(Self {
+            symbol: symbol.to_uppercase(),
+            network,
+            info,
+        })
+    }
+
+    pub fn is_native(&self) -> bool {
+        self.info.address.eq_ignore_ascii_case(NATIVE_TOKEN_ADDRESS)
+    }
+
+    /// Convert a human amount ("1.5") to raw units, rejecting zero
+    pub fn to_raw(&self, amount: &str) -> Result<String, String> {
+        let raw = ToRawAmountTool::convert_to_raw(amount, self.info.decimals)?;
+        if raw == "0" {
+            return Err("Amount must be greater than zero".to_string());
+        }
+        Ok(raw)
+    }
+}
+
+/// Calldata for ERC20 `transfer(address,uint256)`
+fn erc20_transfer_calldata(to: Address, amount: U256) -> Vec<u8> {
+    let mut calldata = ethers::utils::id("transfer(address,uint256)")[..4].to_vec();
+    calldata.extend(ethers::abi::encode(&[Token::Address(to), Token::Uint(amount)]));
+    calldata
+}
+
+/// Sign a transfer of `amount_raw` units of `token` to `recipient` from the
+/// named wallet (`None` for the primary one) and add it to the transaction
+/// queue. Returns the queued transaction.
+pub async fn queue_transfer(
+    tx_queue: &TxQueueManager,
+    token: &TransferToken,
+    recipient: &str,
+    amount_raw: &str,
+    wallet: Option<&str>,
+    channel_id: Option<i64>,
+) -> Result<QueuedTransaction, String> {
+    let recipient_address: Address = recipient
+        .parse()
+        .map_err(|_| format!("Invalid recipient address: {}", recipient))?;
+    let amount = parse_u256(amount_raw)?;
+
+    let (to, calldata, value) = if token.is_native() {
+        (recipient_address, Vec::new(), amount)
+    } else {
+        let contract: Address = token
+            .info
+            .address
+            .parse()
+            .map_err(|_| format!("Invalid token address in config: {}", token.info.address))?;
+        (contract, erc20_transfer_calldata(recipient_address, amount), U256::zero())
+    };
+
+    let signer = WalletSigner::for_wallet(wallet).await?;
+    let rpc_config = resolve_rpc_from_network(&token.network);
+    let signed = Web3FunctionCallTool::sign_transaction_for_queue(
+        &signer,
+        &token.network,
+        to,
+        calldata,
+        value,
+        &rpc_config,
+    )
+    .await?;
+
+    let uuid = Uuid::new_v4().to_string();
+    let queued = QueuedTransaction::new(
+        uuid.clone(),
+        signed.network,
+        signed.from,
+        signed.to,
+        signed.value,
+        signed.data,
+        signed.gas_limit,
+        signed.max_fee_per_gas,
+        signed.max_priority_fee_per_gas,
+        signed.nonce,
+        signed.signed_tx_hex,
+        channel_id,
+    );
+    tx_queue.queue(queued.clone());
+
+    log::info!(
+        "[transfer] Queued {} {} transfer to {} as {}",
+        amount_raw,
+        token.symbol,
+        recipient,
+        uuid
+    );
+    Ok(queued)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erc20_transfer_calldata() {
+        let to: Address = "0x1111111111111111111111111111111111111111".parse().unwrap();
+        let calldata = erc20_transfer_calldata(to, U256::from(5_000_000u64));
+
+        // transfer(address,uint256) selector, then two 32-byte words
+        assert_eq!(hex::encode(&calldata[..4]), "a9059cbb");
+        assert_eq!(calldata.len(), 4 + 64);
+        assert_eq!(&calldata[16..36], to.as_bytes());
+        assert_eq!(U256::from_big_endian(&calldata[36..68]), U256::from(5_000_000u64));
+    }
+}
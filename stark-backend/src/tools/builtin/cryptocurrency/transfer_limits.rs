@@ -0,0 +1,332 @@
+//! Transfer amount guardrails
+//!
+//! Applies the `transfer_limits` bot setting to outgoing native and ERC-20
+//! transfers: per-transaction and rolling 24-hour caps that need `force: true`
+//! to exceed, a threshold above which broadcast always waits for the user
+//! (even in rogue mode), and a warning when too little is left for gas.
+
+use super::network_lookup::native_token_for;
+use super::to_raw_amount::ToRawAmountTool;
+use super::token_lookup::find_token_by_address;
+use super::web3_tx::parse_u256;
+use crate::models::{TransferCap, TransferLimits};
+use crate::tools::rpc_config::ResolvedRpcConfig;
+use crate::tools::types::ToolContext;
+use crate::tx_queue::{QueuedTransaction, TxQueueManager};
+use crate::x402::X402EvmRpc;
+use chrono::{Duration, Utc};
+use ethers::types::{Address, U256};
+
+/// ERC-20 transfer(address,uint256) selector
+const TRANSFER_SELECTOR: &str = "a9059cbb";
+
+/// Native currency symbol capped by the `native` limits
+const NATIVE_CAP_SYMBOL: &str = "ETH";
+
+/// The asset a transfer moves
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum TransferAsset {
+    /// A network's native currency, by its symbol from networks.ron
+    Native { symbol: String },
+    Token { symbol: String, decimals: u8 },
+}
+
+impl TransferAsset {
+    /// The native currency of `network`
+    pub(super) fn native(network: &str) -> Self {
+        TransferAsset::Native { symbol: native_token_for(network) }
+    }
+
+    /// ETH uses the `native` caps; other native currencies (e.g. POL) and
+    /// tokens use the `tokens` entry for their symbol
+    fn cap<'a>(&self, limits: &'a TransferLimits) -> Option<&'a TransferCap> {
+        let symbol = match self {
+            TransferAsset::Native { symbol } if symbol.eq_ignore_ascii_case(NATIVE_CAP_SYMBOL) => {
+                return Some(&limits.native);
+            }
+            TransferAsset::Native { symbol } | TransferAsset::Token { symbol, .. } => symbol,
+        };
+        limits
+            .tokens
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(symbol))
+            .map(|(_, cap)| cap)
+    }
+
+    fn decimals(&self) -> u8 {
+        match self {
+            TransferAsset::Native { .. } => 18,
+            TransferAsset::Token { decimals, .. } => *decimals,
+        }
+    }
+
+    fn symbol(&self) -> &str {
+        match self {
+            TransferAsset::Native { symbol } | TransferAsset::Token { symbol, .. } => symbol,
+        }
+    }
+
+    fn format(&self, amount: U256) -> String {
+        let formatted = ethers::utils::format_units(amount, self.decimals() as u32)
+            .unwrap_or_else(|_| amount.to_string());
+        let trimmed = if formatted.contains('.') {
+            formatted.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            &formatted
+        };
+        format!("{} {}", trimmed, self.symbol())
+    }
+}
+
+/// An outgoing transfer read from a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Transfer {
+    pub asset: TransferAsset,
+    pub amount: U256,
+}
+
+/// What a transaction transfers: an ERC-20 `transfer` of a token known from
+/// tokens.ron, or any native value it carries. None for everything else.
+pub(super) fn classify(network: &str, to: &str, value: &str, data: &str) -> Option<Transfer> {
+    let data = data.trim().trim_start_matches("0x");
+    if let Some(args) = data.strip_prefix(TRANSFER_SELECTOR) {
+        if let Some((symbol, info)) = find_token_by_address(network, to) {
+            let amount = U256::from_str_radix(args.get(64..128)?, 16).ok()?;
+            return Some(Transfer {
+                asset: TransferAsset::Token { symbol, decimals: info.decimals },
+                amount,
+            });
+        }
+    }
+
+    let amount = parse_u256(value).ok()?;
+    (!amount.is_zero()).then(|| Transfer {
+        asset: TransferAsset::native(network),
+        amount,
+    })
+}
+
+/// The transfer limits from bot settings (passed in the tool context)
+pub(super) fn limits_from_context(context: &ToolContext) -> TransferLimits {
+    context
+        .extra
+        .get("transfer_limits")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Convert a configured human amount to raw units. Invalid amounts are
+/// rejected when settings are saved, so they're treated as "no cap" here.
+fn to_raw(amount: Option<&String>, decimals: u8) -> Option<U256> {
+    let amount = amount?;
+    match ToRawAmountTool::convert_to_raw(amount, decimals).map(|raw| U256::from_dec_str(&raw)) {
+        Ok(Ok(raw)) => Some(raw),
+        _ => {
+            log::warn!("[transfer_limits] Ignoring invalid limit '{}'", amount);
+            None
+        }
+    }
+}
+
+/// Total of `asset` sent by `from` on `network` in the last 24 hours
+pub(super) fn spent_last_day(tx_queue: &TxQueueManager, network: &str, from: &str, asset: &TransferAsset) -> U256 {
+    let since = Utc::now() - Duration::hours(24);
+    tx_queue
+        .list_outgoing_since(network, from, since)
+        .iter()
+        .filter_map(|tx| classify(&tx.network, &tx.to, &tx.value, &tx.data))
+        .filter(|t| &t.asset == asset)
+        .fold(U256::zero(), |total, t| total.saturating_add(t.amount))
+}
+
+/// Enforce the per-transaction and daily caps. Returns warnings to show the
+/// user, or an error naming the cap exceeded when `force` isn't set.
+pub(super) fn check_caps(
+    limits: &TransferLimits,
+    transfer: &Transfer,
+    spent_today: U256,
+    force: bool,
+) -> Result<Vec<String>, String> {
+    let Some(cap) = transfer.asset.cap(limits) else {
+        return Ok(Vec::new());
+    };
+    let decimals = transfer.asset.decimals();
+    let mut exceeded = Vec::new();
+
+    if let Some(per_tx) = to_raw(cap.per_tx.as_ref(), decimals) {
+        if transfer.amount > per_tx {
+            exceeded.push(format!(
+                "{} exceeds the per-transaction cap of {}",
+                transfer.asset.format(transfer.amount),
+                transfer.asset.format(per_tx)
+            ));
+        }
+    }
+    if let Some(per_day) = to_raw(cap.per_day.as_ref(), decimals) {
+        let total = spent_today.saturating_add(transfer.amount);
+        if total > per_day {
+            exceeded.push(format!(
+                "{} already sent in the last 24h plus {} exceeds the daily cap of {}",
+                transfer.asset.format(spent_today),
+                transfer.asset.format(transfer.amount),
+                transfer.asset.format(per_day)
+            ));
+        }
+    }
+
+    if exceeded.is_empty() {
+        Ok(Vec::new())
+    } else if force {
+        log::warn!("[transfer_limits] Cap overridden with force: {}", exceeded.join("; "));
+        Ok(exceeded
+            .into_iter()
+            .map(|e| format!("Transfer cap overridden (force=true): {}", e))
+            .collect())
+    } else {
+        Err(format!(
+            "TRANSFER CAP EXCEEDED: {}.\n\nDouble-check the amount with the user. If they really mean it, call again with force=true.",
+            exceeded.join("; ")
+        ))
+    }
+}
+
+/// Whether a transfer is above its `confirm_above` threshold
+pub(super) fn requires_confirmation(limits: &TransferLimits, transfer: &Transfer) -> bool {
+    transfer
+        .asset
+        .cap(limits)
+        .and_then(|cap| to_raw(cap.confirm_above.as_ref(), transfer.asset.decimals()))
+        .is_some_and(|threshold| transfer.amount > threshold)
+}
+
+/// Whether a queued transaction must wait for user confirmation regardless of
+/// rogue mode
+pub(super) fn queued_tx_requires_confirmation(limits: &TransferLimits, tx: &QueuedTransaction) -> bool {
+    classify(&tx.network, &tx.to, &tx.value, &tx.data)
+        .is_some_and(|transfer| requires_confirmation(limits, &transfer))
+}
+
+/// A warning if the signing wallet (`signer`, from `WalletSigner::address()`)
+/// would be left with less than `min_gas_reserve` of native currency once this
+/// transaction spends `native_out` plus `max_gas_cost`
+pub(super) async fn low_gas_warning(
+    limits: &TransferLimits,
+    network: &str,
+    signer: Address,
+    native_out: U256,
+    max_gas_cost: U256,
+    rpc_config: &ResolvedRpcConfig,
+) -> Option<String> {
+    let reserve = to_raw(Some(&limits.min_gas_reserve), 18).filter(|r| !r.is_zero())?;
+    // The burner key only pays for x402 RPC calls; without one (keystore
+    // mode) the balance is read from the endpoints that need no payment
+    let rpc = match crate::config::burner_wallet_private_key() {
+        Some(key) => X402EvmRpc::new_with_config(&key, network, rpc_config),
+        None => X402EvmRpc::new_read_only(network, rpc_config),
+    };
+    let rpc = match rpc {
+        Ok(rpc) => rpc,
+        Err(e) => {
+            log::warn!("[transfer_limits] Balance check unavailable: {}", e);
+            return None;
+        }
+    };
+    let balance = match rpc.get_balance(signer).await {
+        Ok(b) => b,
+        Err(e) => {
+            log::warn!("[transfer_limits] Balance check failed: {}", e);
+            return None;
+        }
+    };
+
+    let remaining = balance.saturating_sub(native_out.saturating_add(max_gas_cost));
+    let native = TransferAsset::native(network);
+    (remaining < reserve).then(|| {
+        format!(
+            "LOW GAS: this leaves about {} in the wallet, below the {} reserve - follow-up transactions may fail for lack of gas.",
+            native.format(remaining),
+            native.format(reserve)
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(per_tx: &str, per_day: &str) -> TransferLimits {
+        TransferLimits {
+            native: TransferCap {
+                per_tx: Some(per_tx.to_string()),
+                per_day: Some(per_day.to_string()),
+                confirm_above: Some("0.5".to_string()),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn eth(amount: &str) -> U256 {
+        ethers::utils::parse_ether(amount).unwrap()
+    }
+
+    #[test]
+    fn test_caps_need_force() {
+        let limits = limits("1", "2");
+        let transfer = Transfer { asset: TransferAsset::native("base"), amount: eth("100") };
+
+        let err = check_caps(&limits, &transfer, U256::zero(), false).unwrap_err();
+        assert!(err.contains("per-transaction cap of 1 ETH"), "{}", err);
+        assert!(err.contains("force=true"));
+
+        let warnings = check_caps(&limits, &transfer, U256::zero(), true).unwrap();
+        assert_eq!(warnings.len(), 2);
+
+        let small = Transfer { asset: TransferAsset::native("base"), amount: eth("0.1") };
+        assert!(check_caps(&limits, &small, U256::zero(), false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_daily_cap_counts_earlier_transfers() {
+        let limits = limits("1", "2");
+        let transfer = Transfer { asset: TransferAsset::native("base"), amount: eth("0.9") };
+
+        assert!(check_caps(&limits, &transfer, eth("1"), false).is_ok());
+        let err = check_caps(&limits, &transfer, eth("1.5"), false).unwrap_err();
+        assert!(err.contains("daily cap of 2 ETH"), "{}", err);
+    }
+
+    #[test]
+    fn test_confirmation_threshold() {
+        let limits = limits("1", "2");
+        let small = Transfer { asset: TransferAsset::native("base"), amount: eth("0.5") };
+        let large = Transfer { asset: TransferAsset::native("base"), amount: eth("0.6") };
+        assert!(!requires_confirmation(&limits, &small));
+        assert!(requires_confirmation(&limits, &large));
+        assert!(!requires_confirmation(&TransferLimits::default(), &large));
+    }
+
+    #[test]
+    fn test_classify_native_value() {
+        let transfer = classify("base", "0x1234567890abcdef1234567890abcdef12345678", "1000", "0x").unwrap();
+        assert_eq!(transfer.asset, TransferAsset::Native { symbol: "ETH".to_string() });
+        assert_eq!(transfer.amount, U256::from(1000));
+        assert!(classify("base", "0x1234567890abcdef1234567890abcdef12345678", "0", "0x").is_none());
+    }
+
+    #[test]
+    fn test_native_caps_follow_network_symbol() {
+        let mut limits = limits("1", "2");
+        let pol = Transfer { asset: TransferAsset::native("polygon"), amount: eth("100") };
+
+        // The ETH caps don't apply to POL
+        assert!(check_caps(&limits, &pol, U256::zero(), false).unwrap().is_empty());
+        assert!(!requires_confirmation(&limits, &pol));
+
+        limits.tokens.insert(
+            "POL".to_string(),
+            TransferCap { per_tx: Some("50".to_string()), ..Default::default() },
+        );
+        let err = check_caps(&limits, &pol, U256::zero(), false).unwrap_err();
+        assert!(err.contains("100 POL exceeds the per-transaction cap of 50 POL"), "{}", err);
+    }
+}
@@ -0,0 +1,468 @@
+//! EIP-8004 Validation tool
+//!
+//! Drives the validation request/response flow:
+//! - `request`: ask a validator agent to validate a result of ours
+//! - `respond`: act as a validator and score someone else's result (0-100)
+//!
+//! Records are written to the `validations` table. When `anchor` is set and a
+//! Validation Registry is deployed, the encoded registry calldata is returned
+//! so the call can be submitted on-chain.
+
+use crate::db::tables::validations::MAX_VALIDATION_RESPONSE;
+use crate::eip8004::abi::{encode_validation_request, encode_validation_response, keccak256};
+use crate::eip8004::config::Eip8004Config;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// EIP-8004 validation tool
+pub struct ValidationTool {
+    definition: ToolDefinition,
+}
+
+impl ValidationTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'request' (ask a validator to validate our result), 'respond' (score a result as a validator), or 'list' (show recent validation records)".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec![
+                    "request".to_string(),
+                    "respond".to_string(),
+                    "list".to_string(),
+                ]),
+            },
+        );
+
+        properties.insert(
+            "agent_id".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "EIP-8004 agent ID whose work is being validated. Required for 'request'; for 'respond' it defaults to the agent of a matching stored request.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "validator_address".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Address of the validator agent (request only)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "request_uri".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "URI of the validation request payload, e.g. ipfs://... (request only)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "request_data".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Request payload content to hash. If omitted the request_uri is hashed.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "request_hash".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "0x-prefixed keccak256 request hash being responded to (respond only)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "response".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "Validation score from 0 (fail) to 100 (pass) (respond only)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "response_uri".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "URI of the validation evidence/report (respond only)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "response_data".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Response content to hash. If omitted the response_uri is hashed.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "tag".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Optional tag categorizing the validation (respond only)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "anchor".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Also return Validation Registry calldata for anchoring on-chain".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        ValidationTool {
+            definition: ToolDefinition {
+                name: "eip8004_validation".to_string(),
+                description: "Participate in EIP-8004 trustless validation. 'request' asks a validator agent to validate one of our results; 'respond' records our 0-100 score as a validator. Records are stored locally and can optionally be anchored on-chain.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["action".to_string()],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+}
+
+impl Default for ValidationTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidationParams {
+    action: String,
+    agent_id: Option<u64>,
+    validator_address: Option<String>,
+    request_uri: Option<String>,
+    request_data: Option<String>,
+    request_hash: Option<String>,
+    response: Option<i64>,
+    response_uri: Option<String>,
+    response_data: Option<String>,
+    tag: Option<String>,
+    #[serde(default)]
+    anchor: bool,
+}
+
+/// keccak256 of content, 0x-prefixed hex
+fn hash_hex(content: &str) -> String {
+    format!("0x{}", hex::encode(keccak256(content.as_bytes())))
+}
+
+/// Parse a 0x-prefixed 32-byte hex hash
+fn parse_hash(s: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(s.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid hash '{}': {}", s, e))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("Invalid hash '{}': expected 32 bytes", s))
+}
+
+/// Validate a score against the 0-100 constraint
+fn validate_response(response: i64) -> Result<u8, String> {
+    if (0..=MAX_VALIDATION_RESPONSE as i64).contains(&response) {
+        Ok(response as u8)
+    } else {
+        Err(format!(
+            "Validation response must be between 0 and {}, got {}",
+            MAX_VALIDATION_RESPONSE, response
+        ))
+    }
+}
+
+fn is_valid_address(addr: &str) -> bool {
+    addr.starts_with("0x") && addr.len() == 42 && addr[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl ValidationTool {
+    fn anchor_info(config: &Eip8004Config, calldata: Vec<u8>) -> Value {
+        if !config.is_validation_deployed() {
+            return json!({
+                "available": false,
+                "reason": "Validation Registry not deployed (set EIP8004_VALIDATION_REGISTRY)"
+            });
+        }
+        json!({
+            "available": true,
+            "to": config.validation_registry,
+            "data": format!("0x{}", hex::encode(calldata)),
+            "chain_id": config.chain_id,
+        })
+    }
+
+    fn request(&self, params: &ValidationParams, context: &ToolContext) -> ToolResult {
+        let db = match &context.database {
+            Some(db) => db,
+            None => return ToolResult::error("Database not available"),
+        };
+
+        let agent_id = match params.agent_id {
+            Some(id) => id,
+            None => return ToolResult::error("'agent_id' is required for request"),
+        };
+        let validator = match params.validator_address.as_deref() {
+            Some(v) if is_valid_address(v) => v,
+            Some(v) => return ToolResult::error(format!("Invalid validator_address '{}'", v)),
+            None => return ToolResult::error("'validator_address' is required for request"),
+        };
+        let request_uri = match params.request_uri.as_deref() {
+            Some(u) if !u.is_empty() => u,
+            _ => return ToolResult::error("'request_uri' is required for request"),
+        };
+
+        let request_hash = hash_hex(params.request_data.as_deref().unwrap_or(request_uri));
+        let config = Eip8004Config::from_env();
+        let registry = config.agent_registry_string();
+
+        let id = match db.insert_validation_request(
+            &request_hash,
+            agent_id,
+            Some(&registry),
+            validator,
+            request_uri,
+        ) {
+            Ok(id) => id,
+            Err(e) => return ToolResult::error(format!("Failed to store validation request: {}", e)),
+        };
+
+        let mut metadata = json!({
+            "id": id,
+            "direction": "requested",
+            "request_hash": request_hash,
+            "agent_id": agent_id,
+            "validator_address": validator,
+            "request_uri": request_uri,
+        });
+
+        if params.anchor {
+            let hash = parse_hash(&request_hash).unwrap_or([0u8; 32]);
+            metadata["anchor"] = Self::anchor_info(
+                &config,
+                encode_validation_request(validator, agent_id, request_uri, hash),
+            );
+        }
+
+        ToolResult::success(format!(
+            "Validation requested from {} for agent {}.\nRequest hash: {}\nShare the request hash and URI with the validator.",
+            validator, agent_id, request_hash
+        ))
+        .with_metadata(metadata)
+    }
+
+    fn respond(&self, params: &ValidationParams, context: &ToolContext) -> ToolResult {
+        // Enforce the 0-100 constraint before anything is written
+        let response = match params.response.map(validate_response) {
+            Some(Ok(r)) => r,
+            Some(Err(e)) => return ToolResult::error(e),
+            None => return ToolResult::error("'response' (0-100) is required for respond"),
+        };
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => return ToolResult::error("Database not available"),
+        };
+
+        let request_hash = match params.request_hash.as_deref() {
+            Some(h) => match parse_hash(h) {
+                Ok(_) => h.to_lowercase(),
+                Err(e) => return ToolResult::error(e),
+            },
+            None => return ToolResult::error("'request_hash' is required for respond"),
+        };
+
+        let existing = db.get_validations_by_request_hash(&request_hash).unwrap_or_default();
+        let agent_id = match params.agent_id.or_else(|| existing.first().map(|r| r.agent_id)) {
+            Some(id) => id,
+            None => return ToolResult::error(
+                "'agent_id' is required when responding to a request we haven't seen before"
+            ),
+        };
+        let agent_registry = existing.first().and_then(|r| r.agent_registry.clone());
+
+        let response_hash = params
+            .response_data
+            .as_deref()
+            .or(params.response_uri.as_deref())
+            .map(hash_hex);
+
+        let id = match db.insert_validation_response(
+            &request_hash,
+            agent_id,
+            agent_registry.as_deref(),
+            response,
+            params.response_uri.as_deref(),
+            response_hash.as_deref(),
+            params.tag.as_deref(),
+        ) {
+            Ok(id) => id,
+            Err(e) => return ToolResult::error(format!("Failed to store validation response: {}", e)),
+        };
+
+        let mut metadata = json!({
+            "id": id,
+            "direction": "responded",
+            "request_hash": request_hash,
+            "agent_id": agent_id,
+            "response": response,
+            "response_uri": params.response_uri,
+            "response_hash": response_hash,
+            "tag": params.tag,
+        });
+
+        if params.anchor {
+            let config = Eip8004Config::from_env();
+            let req_hash = parse_hash(&request_hash).unwrap_or([0u8; 32]);
+            let resp_hash = response_hash
+                .as_deref()
+                .and_then(|h| parse_hash(h).ok())
+                .unwrap_or([0u8; 32]);
+            metadata["anchor"] = Self::anchor_info(
+                &config,
+                encode_validation_response(
+                    req_hash,
+                    response,
+                    params.response_uri.as_deref().unwrap_or(""),
+                    resp_hash,
+                    params.tag.as_deref().unwrap_or(""),
+                ),
+            );
+        }
+
+        ToolResult::success(format!(
+            "Validation response recorded: score {}/100 for request {} (agent {}).",
+            response, request_hash, agent_id
+        ))
+        .with_metadata(metadata)
+    }
+
+    fn list(&self, context: &ToolContext) -> ToolResult {
+        let db = match &context.database {
+            Some(db) => db,
+            None => return ToolResult::error("Database not available"),
+        };
+
+        match db.list_validations(None, 20) {
+            Ok(records) if records.is_empty() => ToolResult::success("No validation records yet."),
+            Ok(records) => {
+                let mut out = String::from("Recent validations:\n");
+                for r in &records {
+                    out.push_str(&format!(
+                        "- [{}] agent {} request {} score {}\n",
+                        r.direction,
+                        r.agent_id,
+                        r.request_hash,
+                        r.response.map(|s| s.to_string()).unwrap_or_else(|| "pending".to_string())
+                    ));
+                }
+                ToolResult::success(out).with_metadata(json!({ "validations": records }))
+            }
+            Err(e) => ToolResult::error(format!("Failed to list validations: {}", e)),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ValidationTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ValidationParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        match params.action.as_str() {
+            "request" => self.request(&params, context),
+            "respond" => self.respond(&params, context),
+            "list" => self.list(context),
+            other => ToolResult::error(format!(
+                "Unknown action '{}'. Use 'request', 'respond', or 'list'.",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_response_bounds() {
+        assert_eq!(validate_response(0), Ok(0));
+        assert_eq!(validate_response(100), Ok(100));
+        assert!(validate_response(101).is_err());
+        assert!(validate_response(-1).is_err());
+    }
+
+    #[test]
+    fn test_hash_roundtrip() {
+        let h = hash_hex("ipfs://QmRequest");
+        assert_eq!(h.len(), 66);
+        assert!(parse_hash(&h).is_ok());
+        assert!(parse_hash("0x1234").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_respond_rejects_out_of_range_before_db() {
+        let tool = ValidationTool::new();
+        // No database in context: the range check must fire first
+        let result = tool
+            .execute(
+                json!({ "action": "respond", "request_hash": hash_hex("x"), "response": 150 }),
+                &ToolContext::new(),
+            )
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("between 0 and 100"));
+    }
+}
@@ -0,0 +1,437 @@
+//! Wallet Balances tool for checking several token balances at once
+//!
+//! Resolves token symbols via the token_lookup table, then reads the native
+//! balance plus `balanceOf` and `decimals` for every token in a single
+//! batched RPC request.
+
+use super::network_lookup::{resolve_network, supported_network_ids};
+use super::token_lookup::{TokenInfo, TokenLookupTool};
+use super::web3_function_call::is_self_balance_query;
+use crate::tools::registry::Tool;
+use crate::tools::rpc_config::{resolve_rpc_from_context, ResolvedRpcConfig};
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::wallet::{WalletSigner, WALLET_PARAM_DESCRIPTION};
+use crate::x402::X402EvmRpc;
+use async_trait::async_trait;
+use ethers::types::{Address, U256};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Placeholder address used in tokens.ron for the native currency
+pub(super) const NATIVE_TOKEN_ADDRESS: &str = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+
+/// balanceOf(address) selector
+const BALANCE_OF_SELECTOR: &str = "70a08231";
+/// decimals() selector
+const DECIMALS_SELECTOR: &str = "313ce567";
+
+/// Wallet Balances tool
+pub struct WalletBalancesTool {
+    definition: ToolDefinition,
+}
+
+impl WalletBalancesTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "tokens".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "Token symbols to check (e.g. [\"USDC\", \"WETH\"]). Use [\"common\"] to check every known token on the network. Native balance is always included.".to_string(),
+                default: Some(json!(["common"])),
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Token symbol".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "network".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: format!(
+                    "Network to check. Configured networks: {}. If not specified, uses the currently selected network or defaults to 'base'.",
+                    supported_network_ids().join(", ")
+                ),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "address".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Wallet address to check. Defaults to the bot's wallet.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "wallet".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: format!("{} Ignored when 'address' is given.", WALLET_PARAM_DESCRIPTION),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        WalletBalancesTool {
+            definition: ToolDefinition {
+                name: "wallet_balances".to_string(),
+                description: "Get a wallet's native and token balances in one call. Resolves token symbols automatically and returns human-readable amounts. Use instead of repeated token_lookup + web3_function_call balance checks.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+}
+
+impl Default for WalletBalancesTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WalletBalancesParams {
+    #[serde(default = "default_tokens")]
+    tokens: Vec<String>,
+    network: Option<String>,
+    address: Option<String>,
+    wallet: Option<String>,
+}
+
+fn default_tokens() -> Vec<String> {
+    vec!["common".to_string()]
+}
+
+/// Resolve requested symbols to (symbol, token) pairs, skipping the native
+/// placeholder. Unknown symbols are returned separately.
+pub(crate) fn resolve_tokens(symbols: &[String], network: &str) -> (Vec<(String, TokenInfo)>, Vec<String>) {
+    let expanded: Vec<String> = if symbols.iter().any(|s| s.eq_ignore_ascii_case("common")) {
+        TokenLookupTool::list_available(network)
+    } else {
+        symbols.iter().map(|s| s.to_uppercase()).collect()
+    };
+
+    let mut resolved: Vec<(String, TokenInfo)> = Vec::new();
+    let mut unknown = Vec::new();
+    for symbol in expanded {
+        if resolved.iter().any(|(s, _)| s.eq_ignore_ascii_case(&symbol)) {
+            continue;
+        }
+        match TokenLookupTool::lookup(&symbol, network) {
+            Some(token) if token.address.to_lowercase() == NATIVE_TOKEN_ADDRESS => {}
+            Some(token) => resolved.push((symbol, token)),
+            None => unknown.push(symbol),
+        }
+    }
+
+    (resolved, unknown)
+}
+
+/// Format a raw integer amount with the given decimals, trimming trailing zeros
+pub(crate) fn format_amount(raw: U256, decimals: u8) -> String {
+    let digits = raw.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, frac) = padded.split_at(padded.len() - decimals);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, frac)
+    }
+}
+
+/// Parse a hex quantity or 32-byte word returned by the RPC
+fn parse_u256(value: &Value) -> Option<U256> {
+    let hex_str = value.as_str()?.trim_start_matches("0x");
+    if hex_str.is_empty() {
+        return None;
+    }
+    U256::from_str_radix(hex_str, 16).ok()
+}
+
+/// One balance read by `fetch_balances`
+#[derive(Debug, Clone)]
+pub(crate) struct TokenBalance {
+    pub symbol: String,
+    /// Token contract; `None` for the native currency
+    pub address: Option<String>,
+    pub decimals: u8,
+    pub raw: Result<U256, String>,
+}
+
+/// Read the native balance plus every token's balance in one batched RPC
+/// request. The native balance comes first.
+pub(crate) async fn fetch_balances(
+    network: &str,
+    holder: Address,
+    tokens: &[(String, TokenInfo)],
+    rpc_config: &ResolvedRpcConfig,
+) -> Result<Vec<TokenBalance>, String> {
+    let private_key = crate::config::burner_wallet_private_key()
+        .ok_or_else(|| "BURNER_WALLET_BOT_PRIVATE_KEY not set".to_string())?;
+    let rpc = X402EvmRpc::new_with_config(&private_key, network, rpc_config)
+        .map_err(|e| format!("Failed to create RPC client: {}", e))?;
+
+    // One batch: native balance, then balanceOf + decimals per token
+    let holder_word = format!("{:0>64}", hex::encode(holder.as_bytes()));
+    let mut calls = vec![(
+        "eth_getBalance".to_string(),
+        json!([format!("{:?}", holder), "latest"]),
+    )];
+    for (_, token) in tokens {
+        calls.push((
+            "eth_call".to_string(),
+            json!([{ "to": token.address, "data": format!("0x{}{}", BALANCE_OF_SELECTOR, holder_word) }, "latest"]),
+        ));
+        calls.push((
+            "eth_call".to_string(),
+            json!([{ "to": token.address, "data": format!("0x{}", DECIMALS_SELECTOR) }, "latest"]),
+        ));
+    }
+
+    let results = rpc
+        .batch_call(calls)
+        .await
+        .map_err(|e| format!("Balance lookup failed: {}", e))?;
+
+    let native_symbol = super::network_lookup::get_network(network)
+        .map(|n| n.native_token)
+        .unwrap_or_else(|| "ETH".to_string());
+
+    let mut balances = vec![TokenBalance {
+        symbol: native_symbol,
+        address: None,
+        decimals: 18,
+        raw: match results[0].as_ref().map(parse_u256) {
+            Ok(Some(raw)) => Ok(raw),
+            Ok(None) => Err("invalid balance response".to_string()),
+            Err(e) => Err(e.to_string()),
+        },
+    }];
+
+    for (i, (symbol, token)) in tokens.iter().enumerate() {
+        // Prefer on-chain decimals, fall back to the token table
+        let decimals = results[2 + i * 2]
+            .as_ref()
+            .ok()
+            .and_then(parse_u256)
+            .filter(|d| *d <= U256::from(77u8))
+            .map(|d| d.as_u32() as u8)
+            .unwrap_or(token.decimals);
+
+        balances.push(TokenBalance {
+            symbol: symbol.clone(),
+            address: Some(token.address.clone()),
+            decimals,
+            raw: match results[1 + i * 2].as_ref().map(parse_u256) {
+                Ok(Some(raw)) => Ok(raw),
+                Ok(None) => Err("invalid balanceOf response".to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+        });
+    }
+
+    Ok(balances)
+}
+
+#[async_trait]
+impl Tool for WalletBalancesTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    fn is_mutating(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: WalletBalancesParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let register_network = context
+            .registers
+            .get("network_name")
+            .and_then(|v| v.as_str().map(|s| s.to_string()));
+        let network = match resolve_network(
+            params.network.as_deref(),
+            context.selected_network.as_deref().or(register_network.as_deref()),
+        ) {
+            Ok(n) => n,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let address = match params.address.clone() {
+            Some(a) => a,
+            None => match WalletSigner::for_wallet(context.wallet_name(params.wallet.as_deref()).as_deref()).await {
+                Ok(wallet) => format!("{:?}", wallet.address()),
+                Err(e) => return ToolResult::error(format!("No address given and wallet not configured: {}", e)),
+            },
+        };
+        let holder = match Address::from_str(&address) {
+            Ok(a) => a,
+            Err(_) => return ToolResult::error(format!("Invalid address: {}", address)),
+        };
+
+        let (tokens, unknown) = resolve_tokens(&params.tokens, &network);
+
+        // SAFETY CHECK: never ask a token contract for its own balance
+        if let Some((symbol, _)) = tokens
+            .iter()
+            .find(|(_, token)| is_self_balance_query(&token.address, &address))
+        {
+            return ToolResult::error(format!(
+                "ERROR: The address {} is the {} token contract itself, not a wallet. \
+                Omit 'address' to check the bot's wallet, or pass the wallet address you meant.",
+                address, symbol
+            ));
+        }
+
+        let rpc_config = resolve_rpc_from_context(&context.extra, &network);
+
+        log::info!(
+            "[wallet_balances] Checking {} tokens + native for {} on {}",
+            tokens.len(),
+            address,
+            network
+        );
+
+        let fetched = match fetch_balances(&network, holder, &tokens, &rpc_config).await {
+            Ok(b) => b,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let mut lines = vec![format!("Balances for {} on {}:", address, network)];
+        let mut balances = Vec::new();
+
+        for balance in &fetched {
+            let symbol = &balance.symbol;
+            let native = balance.address.is_none();
+            match &balance.raw {
+                Ok(raw) => {
+                    let amount = format_amount(*raw, balance.decimals);
+                    if native || !raw.is_zero() {
+                        lines.push(format!("  {}: {}", symbol, amount));
+                    }
+                    if native {
+                        balances.push(json!({
+                            "symbol": symbol,
+                            "native": true,
+                            "raw": raw.to_string(),
+                            "decimals": balance.decimals,
+                            "amount": amount,
+                        }));
+                    } else {
+                        balances.push(json!({
+                            "symbol": symbol,
+                            "address": balance.address,
+                            "raw": raw.to_string(),
+                            "decimals": balance.decimals,
+                            "amount": amount,
+                        }));
+                    }
+                }
+                Err(e) => {
+                    lines.push(format!("  {}: error ({})", symbol, e));
+                    if !native {
+                        balances.push(json!({ "symbol": symbol, "address": balance.address, "error": e }));
+                    }
+                }
+            }
+        }
+
+        let zero_count = balances
+            .iter()
+            .filter(|b| b.get("raw").and_then(|r| r.as_str()) == Some("0") && b.get("native").is_none())
+            .count();
+        if zero_count > 0 {
+            lines.push(format!("  ({} other tokens with zero balance)", zero_count));
+        }
+        if !unknown.is_empty() {
+            lines.push(format!("Unknown tokens on {}: {}", network, unknown.join(", ")));
+        }
+
+        ToolResult::success(lines.join("\n")).with_metadata(json!({
+            "address": address,
+            "network": network,
+            "balances": balances,
+            "unknown_tokens": unknown,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::token_lookup::load_tokens;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn setup() {
+        INIT.call_once(|| {
+            load_tokens(std::path::Path::new("../config"));
+        });
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(U256::from(1_500_000u64), 6), "1.5");
+        assert_eq!(format_amount(U256::from(42u64), 6), "0.000042");
+        assert_eq!(format_amount(U256::zero(), 18), "0");
+        assert_eq!(format_amount(U256::from(7u64), 0), "7");
+        assert_eq!(format_amount(U256::exp10(18) * 3, 18), "3");
+    }
+
+    #[test]
+    fn test_resolve_tokens() {
+        setup();
+        let (tokens, unknown) = resolve_tokens(
+            &["usdc".to_string(), "ETH".to_string(), "NOPE".to_string(), "USDC".to_string()],
+            "base",
+        );
+        // ETH is the native placeholder and is reported separately
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].0, "USDC");
+        assert_eq!(unknown, vec!["NOPE"]);
+    }
+
+    #[test]
+    fn test_resolve_common_tokens() {
+        setup();
+        let (tokens, unknown) = resolve_tokens(&["common".to_string()], "base");
+        assert!(tokens.iter().any(|(s, _)| s == "USDC"));
+        assert!(tokens.iter().any(|(s, _)| s == "USDbC"));
+        assert!(!tokens.iter().any(|(s, _)| s == "ETH"));
+        assert!(unknown.is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 //! Web3 Function Call tool - call any contract function using ABI
 //!
-//! This tool loads ABIs from the /abis folder and encodes function calls,
-//! so the LLM doesn't have to deal with hex-encoded calldata.
+//! This tool loads ABIs from the /abis folder (or uploaded through the API)
+//! and encodes function calls, so the LLM doesn't have to deal with
+//! hex-encoded calldata.
 //!
 //! Supports presets for common operations (weth_deposit, weth_withdraw, etc.)
 //! that read parameters from registers.
@@ -10,13 +11,17 @@
 
 use super::web3_tx::parse_u256;
 use crate::tools::presets::{get_web3_preset, list_web3_presets};
+use super::network_lookup::{resolve_network, supported_network_ids};
+use super::resolve_name::resolve_address_field;
+use crate::tools::abis::{self, AbiFile};
+use super::transfer_limits;
 use crate::tools::registry::Tool;
-use crate::tools::rpc_config::{resolve_rpc_from_context, Network, ResolvedRpcConfig};
+use crate::tools::rpc_config::{resolve_rpc_from_context, ResolvedRpcConfig};
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
-use std::str::FromStr;
 use crate::tx_queue::QueuedTransaction;
+use crate::wallet::{WalletSigner, WALLET_PARAM_DESCRIPTION};
 use crate::x402::X402EvmRpc;
 use async_trait::async_trait;
 use ethers::abi::{Abi, Function, Token, ParamType};
@@ -31,17 +36,17 @@ use uuid::Uuid;
 
 /// Signed transaction result for queuing (not broadcast)
 #[derive(Debug)]
-struct SignedTxForQueue {
-    from: String,
-    to: String,
-    value: String,
-    data: String,
-    gas_limit: String,
-    max_fee_per_gas: String,
-    max_priority_fee_per_gas: String,
-    nonce: u64,
-    signed_tx_hex: String,
-    network: String,
+pub(super) struct SignedTxForQueue {
+    pub(super) from: String,
+    pub(super) to: String,
+    pub(super) value: String,
+    pub(super) data: String,
+    pub(super) gas_limit: String,
+    pub(super) max_fee_per_gas: String,
+    pub(super) max_priority_fee_per_gas: String,
+    pub(super) nonce: u64,
+    pub(super) signed_tx_hex: String,
+    pub(super) network: String,
 }
 
 /// Web3 function call tool
@@ -80,7 +85,7 @@ impl Web3FunctionCallTool {
             "contract".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Contract address to call".to_string(),
+                description: "Contract address to call (ENS/basenames like 'name.eth' are resolved, as are names in address params)".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -130,10 +135,24 @@ impl Web3FunctionCallTool {
             "network".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Network: 'base', 'mainnet', or 'polygon'. If not specified, uses the user's selected network from the UI.".to_string(),
+                description: format!(
+                    "Network name. Configured networks: {}. If not specified, uses the user's selected network from the UI.",
+                    supported_network_ids().join(", ")
+                ),
                 default: None,  // No default - will use context's selected_network
                 items: None,
-                enum_values: Some(vec!["base".to_string(), "mainnet".to_string(), "polygon".to_string()]),
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "wallet".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: WALLET_PARAM_DESCRIPTION.to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
             },
         );
 
@@ -148,10 +167,19 @@ impl Web3FunctionCallTool {
             },
         );
 
+        properties.insert(
+            "force".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Exceed the configured per-transaction/daily transfer caps. Only set after the user explicitly confirms the amount.".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
         // Determine abis directory relative to working directory
-        let abis_dir = std::env::current_dir()
-            .unwrap_or_else(|_| PathBuf::from("."))
-            .join("abis");
+        let abis_dir = crate::tools::presets::abis_dir();
 
         Web3FunctionCallTool {
             definition: ToolDefinition {
@@ -168,17 +196,9 @@ impl Web3FunctionCallTool {
         }
     }
 
-    /// Load ABI from file
+    /// Load ABI by name (uploaded ABIs first, then the abis folder)
     fn load_abi(&self, name: &str) -> Result<AbiFile, String> {
-        let path = self.abis_dir.join(format!("{}.json", name));
-
-        let content = std::fs::read_to_string(&path)
-            .map_err(|e| format!("Failed to load ABI '{}': {}. Available ABIs are in the /abis folder.", name, e))?;
-
-        let abi_file: AbiFile = serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse ABI '{}': {}", name, e))?;
-
-        Ok(abi_file)
+        abis::load_abi(&self.abis_dir, name)
     }
 
     /// Parse ethers Abi from our ABI file format
@@ -314,25 +334,14 @@ impl Web3FunctionCallTool {
             .map_err(|e| format!("Failed to encode function call: {}", e))
     }
 
-    /// Get wallet from environment
-    fn get_wallet(chain_id: u64) -> Result<LocalWallet, String> {
-        let private_key = crate::config::burner_wallet_private_key()
-            .ok_or("BURNER_WALLET_BOT_PRIVATE_KEY not set")?;
-
-        private_key
-            .parse::<LocalWallet>()
-            .map(|w| w.with_chain_id(chain_id))
-            .map_err(|e| format!("Invalid private key: {}", e))
-    }
-
-    /// Get private key from environment
+    /// Get private key from environment (pays for x402 RPC calls)
     fn get_private_key() -> Result<String, String> {
         crate::config::burner_wallet_private_key()
             .ok_or_else(|| "BURNER_WALLET_BOT_PRIVATE_KEY not set".to_string())
     }
 
     /// Execute a read-only call
-    async fn call_function(
+    pub(super) async fn call_function(
         network: &str,
         to: Address,
         calldata: Vec<u8>,
@@ -342,40 +351,61 @@ impl Web3FunctionCallTool {
         let rpc = X402EvmRpc::new_with_config(
             &private_key,
             network,
-            Some(rpc_config.url.clone()),
-            rpc_config.use_x402,
+            rpc_config,
         )?;
 
         rpc.call(to, &calldata).await
     }
 
     /// Sign a transaction for queuing (does NOT broadcast)
-    async fn sign_transaction_for_queue(
+    pub(super) async fn sign_transaction_for_queue(
+        signer: &WalletSigner,
         network: &str,
         to: Address,
         calldata: Vec<u8>,
         value: U256,
         rpc_config: &ResolvedRpcConfig,
+    ) -> Result<SignedTxForQueue, String> {
+        Self::sign_transaction_for_queue_with(signer, network, to, calldata, value, rpc_config, None, None).await
+    }
+
+    /// Sign a transaction for queuing with an explicit nonce and/or gas limit.
+    /// Used to chain transactions whose later steps can't be estimated until
+    /// the earlier ones are mined (e.g. a swap behind its approval).
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn sign_transaction_for_queue_with(
+        signer: &WalletSigner,
+        network: &str,
+        to: Address,
+        calldata: Vec<u8>,
+        value: U256,
+        rpc_config: &ResolvedRpcConfig,
+        nonce: Option<U256>,
+        gas: Option<U256>,
     ) -> Result<SignedTxForQueue, String> {
         let private_key = Self::get_private_key()?;
         let rpc = X402EvmRpc::new_with_config(
             &private_key,
             network,
-            Some(rpc_config.url.clone()),
-            rpc_config.use_x402,
+            rpc_config,
         )?;
         let chain_id = rpc.chain_id();
 
-        let wallet = Self::get_wallet(chain_id)?;
-        let from_address = wallet.address();
+        let from_address = signer.address();
         let from_str = format!("{:?}", from_address);
         let to_str = format!("{:?}", to);
 
         // Get nonce
-        let nonce = rpc.get_transaction_count(from_address).await?;
+        let nonce = match nonce {
+            Some(n) => n,
+            None => rpc.get_transaction_count(from_address).await?,
+        };
 
         // Estimate gas
-        let gas: U256 = rpc.estimate_gas(from_address, to, &calldata, value).await?;
+        let gas: U256 = match gas {
+            Some(g) => g,
+            None => rpc.estimate_gas(from_address, to, &calldata, value).await?,
+        };
         let gas = gas * U256::from(120) / U256::from(100); // 20% buffer
 
         // Get gas prices
@@ -400,10 +430,7 @@ impl Web3FunctionCallTool {
 
         // Sign the transaction
         let typed_tx: TypedTransaction = tx.into();
-        let signature = wallet
-            .sign_transaction(&typed_tx)
-            .await
-            .map_err(|e| format!("Failed to sign transaction: {}", e))?;
+        let signature = signer.sign_transaction(&typed_tx).await?;
 
         // Serialize the signed transaction
         let signed_tx = typed_tx.rlp_signed(&signature);
@@ -466,17 +493,6 @@ impl Default for Web3FunctionCallTool {
     }
 }
 
-/// ABI file structure
-#[derive(Debug, Deserialize)]
-struct AbiFile {
-    name: String,
-    #[serde(default)]
-    description: String,
-    abi: Vec<Value>,
-    #[serde(default)]
-    address: HashMap<String, String>,
-}
-
 #[derive(Debug, Deserialize)]
 struct Web3FunctionCallParams {
     preset: Option<String>,
@@ -489,23 +505,23 @@ struct Web3FunctionCallParams {
     value: String,
     /// Network for the call. If not specified, uses context's selected_network or defaults to Base
     network: Option<String>,
+    /// Named wallet to sign with. If not specified, uses the channel's wallet or the primary wallet
+    wallet: Option<String>,
     #[serde(default)]
     call_only: bool,
+    /// Exceed the configured transfer caps
+    #[serde(default)]
+    force: bool,
 }
 
 fn default_value() -> String {
     "0".to_string()
 }
 
-/// Resolve the network from params, context, or default
-fn resolve_network(param_network: Option<&str>, context_network: Option<&str>) -> Result<Network, String> {
-    // Priority: explicit param > context selected > default (Base)
-    let network_str = param_network
-        .or(context_network)
-        .unwrap_or("base");
-
-    Network::from_str(network_str)
-        .map_err(|_| format!("Invalid network '{}'. Must be one of: base, mainnet, polygon", network_str))
+/// Whether a balanceOf query asks the token contract for its OWN balance.
+/// This is a common mistake - the wallet's balance is almost always intended.
+pub(super) fn is_self_balance_query(contract: &str, holder: &str) -> bool {
+    contract.trim().trim_matches('"').eq_ignore_ascii_case(holder.trim().trim_matches('"'))
 }
 
 #[async_trait]
@@ -533,7 +549,7 @@ impl Tool for Web3FunctionCallTool {
             network, params.network, context.selected_network);
 
         // Resolve preset or use direct params
-        let (abi_name, contract_addr, function_name, call_params, value) = if let Some(ref preset_name) = params.preset {
+        let (abi_name, mut contract_addr, function_name, mut call_params, value) = if let Some(ref preset_name) = params.preset {
             // Using preset - load config and resolve from registers
             let preset = match get_web3_preset(preset_name) {
                 Some(p) => p,
@@ -549,7 +565,7 @@ impl Tool for Web3FunctionCallTool {
             // Get contract address - either from register or hardcoded per network
             let contract = if let Some(ref contract_reg) = preset.contract_register {
                 // Read contract address from register
-                match context.registers.get(contract_reg) {
+                match context.registers.get_or_intrinsic(contract_reg, context, params.wallet.as_deref()) {
                     Some(v) => match v.as_str() {
                         Some(s) => s.to_string(),
                         None => v.to_string().trim_matches('"').to_string(),
@@ -563,7 +579,7 @@ impl Tool for Web3FunctionCallTool {
                 }
             } else {
                 // Use hardcoded contract for network
-                match preset.contracts.get(network.as_ref()) {
+                match preset.contracts.get(&network) {
                     Some(c) => c.clone(),
                     None => {
                         return ToolResult::error(format!(
@@ -577,7 +593,7 @@ impl Tool for Web3FunctionCallTool {
             // Read params from registers
             let mut resolved_params = Vec::new();
             for reg_key in &preset.params_registers {
-                match context.registers.get(reg_key) {
+                match context.registers.get_or_intrinsic(reg_key, context, params.wallet.as_deref()) {
                     Some(v) => {
                         // Convert JSON value to string for params
                         let param_str = match v.as_str() {
@@ -597,7 +613,7 @@ impl Tool for Web3FunctionCallTool {
 
             // Read value from register if specified
             let value = if let Some(ref val_reg) = preset.value_register {
-                match context.registers.get(val_reg) {
+                match context.registers.get_or_intrinsic(val_reg, context, params.wallet.as_deref()) {
                     Some(v) => {
                         match v.as_str() {
                             Some(s) => s.to_string(),
@@ -657,6 +673,32 @@ impl Tool for Web3FunctionCallTool {
             Err(e) => return ToolResult::error(e),
         };
 
+        // Resolve ENS/basenames in the contract and address-typed params
+        let mut resolved_names = Vec::new();
+        match resolve_address_field(&contract_addr, context).await {
+            Ok((address, Some(name))) => {
+                resolved_names.push(format!("{} -> {}", name, address));
+                contract_addr = address;
+            }
+            Ok(_) => {}
+            Err(e) => return ToolResult::error(format!("Cannot resolve contract: {}", e)),
+        }
+        for (input, param) in function.inputs.iter().zip(call_params.iter_mut()) {
+            if input.kind != ParamType::Address {
+                continue;
+            }
+            if let Some(s) = param.as_str().map(|s| s.to_string()) {
+                match resolve_address_field(&s, context).await {
+                    Ok((address, Some(name))) => {
+                        resolved_names.push(format!("{} -> {}", name, address));
+                        *param = Value::String(address);
+                    }
+                    Ok(_) => {}
+                    Err(e) => return ToolResult::error(format!("Cannot resolve param '{}': {}", input.name, e)),
+                }
+            }
+        }
+
         // Encode call
         let calldata = match self.encode_call(function, &call_params) {
             Ok(d) => d,
@@ -673,12 +715,11 @@ impl Tool for Web3FunctionCallTool {
         // When checking token balance, you want balanceOf(wallet_address), NOT balanceOf(contract_address)
         if function_name == "balanceOf" && call_params.len() == 1 {
             let param_str = match &call_params[0] {
-                Value::String(s) => s.to_lowercase(),
-                _ => call_params[0].to_string().trim_matches('"').to_lowercase(),
+                Value::String(s) => s.clone(),
+                _ => call_params[0].to_string(),
             };
-            let contract_str = contract_addr.to_lowercase();
 
-            if param_str == contract_str {
+            if is_self_balance_query(&contract_addr, &param_str) {
                 return ToolResult::error(format!(
                     "ERROR: You're calling balanceOf on the token contract with the contract's OWN address as the parameter. \
                     This checks how many tokens the contract itself holds, NOT your wallet balance!\n\n\
@@ -724,7 +765,7 @@ impl Tool for Web3FunctionCallTool {
         }
 
         // Resolve RPC configuration from context (respects custom RPC settings)
-        let rpc_config = resolve_rpc_from_context(&context.extra, network.as_ref());
+        let rpc_config = resolve_rpc_from_context(&context.extra, &network);
 
         log::info!(
             "[web3_function_call] {}::{}({:?}) on {} (call_only={}, rpc={})",
@@ -733,7 +774,7 @@ impl Tool for Web3FunctionCallTool {
 
         if params.call_only {
             // Read-only call
-            match Self::call_function(network.as_ref(), contract, calldata, &rpc_config).await {
+            match Self::call_function(&network, contract, calldata, &rpc_config).await {
                 Ok(result) => {
                     let decoded = self.decode_return(function, &result)
                         .unwrap_or_else(|_| json!(format!("0x{}", hex::encode(&result))));
@@ -745,6 +786,7 @@ impl Tool for Web3FunctionCallTool {
                             "contract": contract_addr,
                             "function": function_name,
                             "result": decoded,
+                            "resolved_names": resolved_names,
                         }))
                 }
                 Err(e) => ToolResult::error(e),
@@ -766,10 +808,7 @@ impl Tool for Web3FunctionCallTool {
                 })
                 .unwrap_or(false);
 
-            let is_rogue_mode = context.extra
-                .get("rogue_mode_enabled")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
+            let is_rogue_mode = context.is_rogue_mode();
 
             if is_gateway_channel && !is_rogue_mode {
                 return ToolResult::error(
@@ -784,9 +823,37 @@ impl Tool for Web3FunctionCallTool {
                 None => return ToolResult::error("Transaction queue not available. Contact administrator."),
             };
 
+            let signer = match WalletSigner::for_wallet(context.wallet_name(params.wallet.as_deref()).as_deref()).await {
+                Ok(s) => s,
+                Err(e) => return ToolResult::error(e),
+            };
+
+            // Transfer caps apply to native value and ERC-20 transfers of known tokens
+            let limits = transfer_limits::limits_from_context(context);
+            let transfer = transfer_limits::classify(
+                &network,
+                &contract_addr,
+                &tx_value.to_string(),
+                &hex::encode(&calldata),
+            );
+            let mut warnings = Vec::new();
+            if let Some(ref transfer) = transfer {
+                let spent_today = transfer_limits::spent_last_day(
+                    tx_queue,
+                    &network,
+                    &format!("{:?}", signer.address()),
+                    &transfer.asset,
+                );
+                match transfer_limits::check_caps(&limits, transfer, spent_today, params.force) {
+                    Ok(w) => warnings = w,
+                    Err(e) => return ToolResult::error(e),
+                }
+            }
+
             // Sign the transaction (but don't broadcast)
             match Self::sign_transaction_for_queue(
-                network.as_ref(),
+                &signer,
+                &network,
                 contract,
                 calldata,
                 tx_value,
@@ -817,6 +884,26 @@ impl Tool for Web3FunctionCallTool {
 
                     log::info!("[web3_function_call] Transaction queued with UUID: {}", uuid);
 
+                    let max_gas_cost = parse_u256(&signed.gas_limit)
+                        .unwrap_or_default()
+                        .saturating_mul(parse_u256(&signed.max_fee_per_gas).unwrap_or_default());
+                    if let Some(w) = transfer_limits::low_gas_warning(
+                        &limits,
+                        &network,
+                        signer.address(),
+                        tx_value,
+                        max_gas_cost,
+                        &rpc_config,
+                    ).await {
+                        warnings.push(w);
+                    }
+                    let needs_confirmation = transfer
+                        .as_ref()
+                        .is_some_and(|t| transfer_limits::requires_confirmation(&limits, t));
+                    if needs_confirmation {
+                        warnings.push("Large transfer: broadcast will wait for the user to confirm it, even in rogue mode.".to_string());
+                    }
+
                     // Format value as ETH for display
                     let value_eth = if let Ok(w) = signed.value.parse::<u128>() {
                         let eth = w as f64 / 1e18;
@@ -840,9 +927,15 @@ impl Tool for Web3FunctionCallTool {
                         Nonce: {}\n\n\
                         --- Next Steps ---\n\
                         To view queued: use `list_queued_web3_tx`\n\
-                        To broadcast: use `broadcast_web3_tx` with uuid: {}",
+                        To broadcast: use `broadcast_web3_tx` with uuid: {}{}{}",
                         uuid, abi_name, function_name, signed.network, signed.from,
-                        contract_addr, signed.value, value_eth, signed.nonce, uuid
+                        contract_addr, signed.value, value_eth, signed.nonce, uuid,
+                        if resolved_names.is_empty() {
+                            String::new()
+                        } else {
+                            format!("\n\nResolved names: {}", resolved_names.join(", "))
+                        },
+                        warnings.iter().map(|w| format!("\n\nWARNING: {}", w)).collect::<String>()
                     )).with_metadata(json!({
                         "uuid": uuid,
                         "status": "queued",
@@ -854,7 +947,10 @@ impl Tool for Web3FunctionCallTool {
                         "to": contract_addr,
                         "value": signed.value,
                         "nonce": signed.nonce,
-                        "network": network
+                        "network": network,
+                        "resolved_names": resolved_names,
+                        "requires_confirmation": needs_confirmation,
+                        "warnings": warnings
                     }))
                 }
                 Err(e) => ToolResult::error(e),
@@ -1,6 +1,6 @@
 //! Send ETH tool - simple native ETH transfers only
 //!
-//! Signs and queues native ETH transfers using the burner wallet.
+//! Signs and queues native ETH transfers using the bot wallet (see `crate::wallet`).
 //! This tool is RESTRICTED to ETH transfers only (data must be "0x" or empty).
 //! For contract calls, use web3_function_call instead.
 //!
@@ -11,12 +11,17 @@
 //!
 //! All RPC calls go through defirelay.com with x402 payments.
 
+use super::network_lookup::{resolve_network, supported_network_ids};
+use super::address_book::lookup_recipient;
+use super::resolve_name::{looks_like_name, resolve_address_field};
+use super::transfer_limits::{self, Transfer, TransferAsset};
 use crate::tools::registry::Tool;
-use crate::tools::rpc_config::{resolve_rpc_from_context, Network, ResolvedRpcConfig};
+use crate::tools::rpc_config::{resolve_rpc_from_context, ResolvedRpcConfig};
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use crate::tx_queue::QueuedTransaction;
+use crate::wallet::{WalletSigner, WALLET_PARAM_DESCRIPTION};
 use crate::x402::X402EvmRpc;
 use async_trait::async_trait;
 use ethers::prelude::*;
@@ -25,7 +30,6 @@ use ethers::types::transaction::eip2718::TypedTransaction;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::str::FromStr;
 use uuid::Uuid;
 
 /// Signed transaction result with all details needed for queuing
@@ -56,17 +60,53 @@ impl SendEthTool {
             "network".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Network: 'base', 'mainnet', or 'polygon'. If not specified, uses the user's selected network from the UI.".to_string(),
+                description: format!(
+                    "Network name. Configured networks: {}. If not specified, uses the user's selected network from the UI.",
+                    supported_network_ids().join(", ")
+                ),
                 default: None,  // No default - will use context's selected_network
                 items: None,
-                enum_values: Some(vec!["base".to_string(), "mainnet".to_string(), "polygon".to_string()]),
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "wallet".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: WALLET_PARAM_DESCRIPTION.to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "confirm_new_recipient".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Set to true only after the user has confirmed a transfer to an address book entry that has never been used before".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "force".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Exceed the configured per-transaction/daily transfer caps. Only set after the user explicitly confirms the amount.".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
             },
         );
 
         SendEthTool {
             definition: ToolDefinition {
                 name: "send_eth".to_string(),
-                description: "Send native ETH to an address. Reads 'send_to' (recipient) and 'amount_raw' (wei value) from registers. Use 'register_set' to set 'send_to', and 'to_raw_amount' with decimals=18 to set 'amount_raw'. Transaction is QUEUED - use broadcast_web3_tx to broadcast.".to_string(),
+                description: "Send native ETH to an address. Reads 'send_to' (recipient address, an address book name like 'alice', or an ENS/basename like 'vitalik.eth') and 'amount_raw' (wei value) from registers. Use 'register_set' to set 'send_to', and 'to_raw_amount' with decimals=18 to set 'amount_raw'. Transaction is QUEUED - use broadcast_web3_tx to broadcast.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -77,18 +117,7 @@ impl SendEthTool {
         }
     }
 
-    /// Get the wallet from environment
-    fn get_wallet(chain_id: u64) -> Result<LocalWallet, String> {
-        let private_key = crate::config::burner_wallet_private_key()
-            .ok_or("BURNER_WALLET_BOT_PRIVATE_KEY not set")?;
-
-        private_key
-            .parse::<LocalWallet>()
-            .map(|w| w.with_chain_id(chain_id))
-            .map_err(|e| format!("Invalid private key: {}", e))
-    }
-
-    /// Get the private key from environment
+    /// Get the private key from environment (pays for x402 RPC calls)
     fn get_private_key() -> Result<String, String> {
         crate::config::burner_wallet_private_key()
             .ok_or_else(|| "BURNER_WALLET_BOT_PRIVATE_KEY not set".to_string())
@@ -96,6 +125,7 @@ impl SendEthTool {
 
     /// Sign an ETH transfer (simple value transfer, no data)
     async fn sign_eth_transfer(
+        signer: &WalletSigner,
         network: &str,
         to: &str,
         value: &str,
@@ -105,13 +135,11 @@ impl SendEthTool {
         let rpc = X402EvmRpc::new_with_config(
             &private_key,
             network,
-            Some(rpc_config.url.clone()),
-            rpc_config.use_x402,
+            rpc_config,
         )?;
         let chain_id = rpc.chain_id();
 
-        let wallet = Self::get_wallet(chain_id)?;
-        let from_address = wallet.address();
+        let from_address = signer.address();
         let from_str = format!("{:?}", from_address);
 
         // Parse recipient address
@@ -148,10 +176,7 @@ impl SendEthTool {
 
         // Sign the transaction
         let typed_tx: TypedTransaction = tx.into();
-        let signature = wallet
-            .sign_transaction(&typed_tx)
-            .await
-            .map_err(|e| format!("Failed to sign transaction: {}", e))?;
+        let signature = signer.sign_transaction(&typed_tx).await?;
 
         let signed_tx = typed_tx.rlp_signed(&signature);
         let signed_tx_hex = format!("0x{}", hex::encode(&signed_tx));
@@ -249,10 +274,12 @@ impl ResolvedTxData {
             .ok_or_else(|| "Register 'send_to' must be a string (Ethereum address)".to_string())?
             .to_string();
 
-        // Validate it looks like an Ethereum address
-        if !to.starts_with("0x") || to.len() != 42 {
+        // Validate it looks like an Ethereum address (or an address book name or
+        // ENS/basename, resolved in execute)
+        let is_address = to.starts_with("0x") && to.len() == 42;
+        if !is_address && !looks_like_name(&to) && lookup_recipient(&to, context).is_none() {
             return Err(format!(
-                "Register 'send_to' must contain a valid Ethereum address (0x + 40 hex chars), got '{}'",
+                "Register 'send_to' must contain a valid Ethereum address (0x + 40 hex chars), an address book name or an ENS/basename, got '{}'",
                 to
             ));
         }
@@ -292,6 +319,14 @@ impl ResolvedTxData {
 struct SendEthParams {
     /// Network - if not specified, uses context's selected_network or defaults to Base
     network: Option<String>,
+    /// Named wallet - if not specified, uses the channel's wallet or the primary wallet
+    wallet: Option<String>,
+    /// User confirmed sending to a never-used address book entry
+    #[serde(default)]
+    confirm_new_recipient: bool,
+    /// Exceed the configured transfer caps
+    #[serde(default)]
+    force: bool,
 }
 
 /// Resolved transfer data read from register
@@ -302,17 +337,6 @@ struct ResolvedTxData {
     source: String,
 }
 
-/// Resolve the network from params, context, or default
-fn resolve_network(param_network: Option<&str>, context_network: Option<&str>) -> Result<Network, String> {
-    // Priority: explicit param > context selected > default (Base)
-    let network_str = param_network
-        .or(context_network)
-        .unwrap_or("base");
-
-    Network::from_str(network_str)
-        .map_err(|_| format!("Invalid network '{}'. Must be one of: base, mainnet, polygon", network_str))
-}
-
 #[async_trait]
 impl Tool for SendEthTool {
     fn definition(&self) -> ToolDefinition {
@@ -340,11 +364,32 @@ impl Tool for SendEthTool {
             network, params.network, context.selected_network);
 
         // Resolve transfer data from individual registers (send_to, amount_raw)
-        let tx_data = match ResolvedTxData::from_registers(context) {
+        let mut tx_data = match ResolvedTxData::from_registers(context) {
             Ok(d) => d,
             Err(e) => return ToolResult::error(e),
         };
 
+        // First transfer to an address book entry needs the user's go-ahead
+        let book_entry = lookup_recipient(&tx_data.to, context);
+        if let Some(ref entry) = book_entry {
+            if entry.use_count == 0 && !params.confirm_new_recipient {
+                return ToolResult::error(format!(
+                    "'{}' ({}) is a new address book entry that has never received a transfer. \
+                     Confirm the recipient address with the user, then call send_eth again with confirm_new_recipient=true.",
+                    entry.name, entry.address
+                ));
+            }
+        }
+
+        // Resolve address book and ENS/basename recipients - never send to an unresolved name
+        let resolved_name = match resolve_address_field(&tx_data.to, context).await {
+            Ok((address, name)) => {
+                tx_data.to = address;
+                name
+            }
+            Err(e) => return ToolResult::error(format!("Cannot send: {}", e)),
+        };
+
         log::info!(
             "[send_eth] Resolved: to={}, value={}",
             tx_data.to, tx_data.value
@@ -359,10 +404,7 @@ impl Tool for SendEthTool {
             })
             .unwrap_or(false);
 
-        let is_rogue_mode = context.extra
-            .get("rogue_mode_enabled")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let is_rogue_mode = context.is_rogue_mode();
 
         if is_gateway_channel && !is_rogue_mode {
             return ToolResult::error(
@@ -377,11 +419,37 @@ impl Tool for SendEthTool {
         };
 
         // Resolve RPC configuration
-        let rpc_config = resolve_rpc_from_context(&context.extra, network.as_ref());
+        let rpc_config = resolve_rpc_from_context(&context.extra, &network);
+
+        let signer = match WalletSigner::for_wallet(context.wallet_name(params.wallet.as_deref()).as_deref()).await {
+            Ok(s) => s,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        // Transfer caps - a fat-fingered amount needs an explicit force
+        let limits = transfer_limits::limits_from_context(context);
+        let transfer = Transfer {
+            asset: TransferAsset::native(&network),
+            amount: match parse_u256(&tx_data.value) {
+                Ok(v) => v,
+                Err(e) => return ToolResult::error(e),
+            },
+        };
+        let spent_today = transfer_limits::spent_last_day(
+            tx_queue,
+            &network,
+            &format!("{:?}", signer.address()),
+            &transfer.asset,
+        );
+        let mut warnings = match transfer_limits::check_caps(&limits, &transfer, spent_today, params.force) {
+            Ok(w) => w,
+            Err(e) => return ToolResult::error(e),
+        };
 
         // Sign the ETH transfer (data is always "0x", gas is 21000 for simple transfer)
         match Self::sign_eth_transfer(
-            network.as_ref(),
+            &signer,
+            &network,
             &tx_data.to,
             &tx_data.value,
             &rpc_config,
@@ -411,15 +479,45 @@ impl Tool for SendEthTool {
 
                 log::info!("[send_eth] Transaction queued with UUID: {}", uuid);
 
+                let max_gas_cost = parse_u256(&signed.gas_limit)
+                    .unwrap_or_default()
+                    .saturating_mul(parse_u256(&signed.max_fee_per_gas).unwrap_or_default());
+                if let Some(w) = transfer_limits::low_gas_warning(
+                    &limits,
+                    &network,
+                    signer.address(),
+                    transfer.amount,
+                    max_gas_cost,
+                    &rpc_config,
+                ).await {
+                    warnings.push(w);
+                }
+                let needs_confirmation = transfer_limits::requires_confirmation(&limits, &transfer);
+                if needs_confirmation {
+                    warnings.push("Large transfer: broadcast will wait for the user to confirm it, even in rogue mode.".to_string());
+                }
+
+                if let (Some(entry), Some(db)) = (&book_entry, &context.database) {
+                    if let Err(e) = db.mark_address_book_used(entry.id) {
+                        log::warn!("[send_eth] Failed to record address book use: {}", e);
+                    }
+                }
+
                 // Build response message
                 let mut msg = String::new();
                 msg.push_str("ETH TRANSFER QUEUED (not yet broadcast)\n\n");
                 msg.push_str(&format!("UUID: {}\n", uuid));
                 msg.push_str(&format!("Network: {}\n", signed.network));
                 msg.push_str(&format!("From: {}\n", signed.from));
-                msg.push_str(&format!("To: {}\n", signed.to));
+                match &resolved_name {
+                    Some(name) => msg.push_str(&format!("To: {} ({})\n", signed.to, name)),
+                    None => msg.push_str(&format!("To: {}\n", signed.to)),
+                }
                 msg.push_str(&format!("Value: {} ({})\n", signed.value, Self::format_eth(&signed.value)));
                 msg.push_str(&format!("Nonce: {}\n", signed.nonce));
+                for warning in &warnings {
+                    msg.push_str(&format!("\nWARNING: {}\n", warning));
+                }
                 msg.push_str("\n--- Next Steps ---\n");
                 msg.push_str("To view queued: use `list_queued_web3_tx`\n");
                 msg.push_str(&format!("To broadcast: use `broadcast_web3_tx` with uuid: {}\n", uuid));
@@ -430,14 +528,17 @@ impl Tool for SendEthTool {
                     "network": signed.network,
                     "from": signed.from,
                     "to": signed.to,
+                    "to_name": resolved_name,
                     "value": signed.value,
                     "nonce": signed.nonce,
                     "gas_limit": signed.gas_limit,
                     "max_fee_per_gas": signed.max_fee_per_gas,
-                    "max_priority_fee_per_gas": signed.max_priority_fee_per_gas
+                    "max_priority_fee_per_gas": signed.max_priority_fee_per_gas,
+                    "requires_confirmation": needs_confirmation,
+                    "warnings": warnings
                 }))
             }
-            Err(e) => ToolResult::error(Self::parse_rpc_error(&e, &tx_data, network.as_ref())),
+            Err(e) => ToolResult::error(Self::parse_rpc_error(&e, &tx_data, &network)),
         }
     }
 }
@@ -518,7 +619,7 @@ mod tests {
 
         let params: SendEthParams = serde_json::from_value(json).unwrap();
 
-        assert_eq!(params.network, "base");
+        assert_eq!(params.network.as_deref(), Some("base"));
     }
 
     #[test]
@@ -526,7 +627,7 @@ mod tests {
         let json = json!({});
 
         let params: SendEthParams = serde_json::from_value(json).unwrap();
-        assert_eq!(params.network, "base");
+        assert_eq!(params.network, None);
     }
 
     #[test]
@@ -593,6 +694,21 @@ mod tests {
         assert!(result.unwrap_err().contains("valid Ethereum address"));
     }
 
+    #[test]
+    fn test_resolved_tx_data_accepts_name() {
+        use crate::tools::RegisterStore;
+
+        let registers = RegisterStore::new();
+        registers.set("send_to", json!("vitalik.eth"), "register_set");
+        registers.set("amount_raw", json!("100000000000000"), "to_raw_amount");
+
+        let context = crate::tools::ToolContext::new()
+            .with_registers(registers);
+
+        let tx_data = ResolvedTxData::from_registers(&context).unwrap();
+        assert_eq!(tx_data.to, "vitalik.eth");
+    }
+
     #[test]
     fn test_resolved_tx_data_invalid_amount() {
         use crate::tools::RegisterStore;
@@ -4,7 +4,8 @@
 
 use crate::tools::registry::Tool;
 use crate::tools::types::{
-    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    PropertySchema, ToolContext, ToolCost, ToolDefinition, ToolGroup, ToolInputSchema,
+    ToolResult,
 };
 use crate::x402::X402Signer;
 use async_trait::async_trait;
@@ -27,7 +28,18 @@ impl X402AgentInvokeTool {
             "agent_url".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Base URL of the x402 agent (e.g., https://dad-jokes-agent-production.up.railway.app)".to_string(),
+                description: "Base URL of the x402 agent (e.g., https://dad-jokes-agent-production.up.railway.app). Optional if agent_name is given.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "agent_name".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Name of an agent discovered from the EIP-8004 registry. Its x402 endpoint is looked up automatically.".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
@@ -60,21 +72,22 @@ impl X402AgentInvokeTool {
             "network".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Network for payment: 'base' (mainnet) or 'base-sepolia' (testnet)".to_string(),
-                default: Some(json!("base")),
+                description: "Preferred network for payment (e.g. 'base'). Defaults to the first network allowed by STARK_X402_NETWORKS."
+                    .to_string(),
+                default: None,
                 items: None,
-                enum_values: Some(vec!["base".to_string(), "base-sepolia".to_string()]),
+                enum_values: None,
             },
         );
 
         X402AgentInvokeTool {
             definition: ToolDefinition {
                 name: "x402_agent_invoke".to_string(),
-                description: "Invoke an x402-enabled AI agent endpoint with automatic USDC payment on Base. Handles 402 Payment Required flow automatically.".to_string(),
+                description: "Invoke an x402-enabled AI agent endpoint with automatic USDC payment on Base. Target an agent by agent_url or by agent_name (from EIP-8004 discovery). Handles 402 Payment Required flow automatically.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
-                    required: vec!["agent_url".to_string(), "entrypoint".to_string()],
+                    required: vec!["entrypoint".to_string()],
                 },
                 group: ToolGroup::Finance,
             },
@@ -98,16 +111,15 @@ impl Default for X402AgentInvokeTool {
 
 #[derive(Debug, Deserialize)]
 struct X402AgentInvokeParams {
-    agent_url: String,
+    #[serde(default)]
+    agent_url: Option<String>,
+    #[serde(default)]
+    agent_name: Option<String>,
     entrypoint: String,
     #[serde(default)]
     input: Value,
-    #[serde(default = "default_network")]
-    network: String,
-}
-
-fn default_network() -> String {
-    "base".to_string()
+    #[serde(default)]
+    network: Option<String>,
 }
 
 /// 402 response from agent (JSON body format)
@@ -210,16 +222,21 @@ impl Tool for X402AgentInvokeTool {
         self.definition.clone()
     }
 
-    async fn execute(&self, params: Value, _context: &ToolContext) -> ToolResult {
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: X402AgentInvokeParams = match serde_json::from_value(params) {
             Ok(p) => p,
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
 
+        let agent_url = match resolve_agent_url(&params, context) {
+            Ok(u) => u,
+            Err(e) => return ToolResult::error(e),
+        };
+
         // Build the invoke URL
         let url = format!(
             "{}/entrypoints/{}/invoke",
-            params.agent_url.trim_end_matches('/'),
+            agent_url.trim_end_matches('/'),
             params.entrypoint
         );
 
@@ -291,20 +308,14 @@ impl Tool for X402AgentInvokeTool {
             }
         };
 
-        // Find a matching payment option for our network
-        let payment_option = payment_info
-            .accepts
-            .iter()
-            .find(|opt| {
-                opt.network == params.network ||
-                opt.network == "base" && params.network == "base" ||
-                opt.network == "base-sepolia" && params.network == "base-sepolia"
-            })
-            .or_else(|| payment_info.accepts.first());
-
-        let payment_option = match payment_option {
-            Some(opt) => opt.clone(),
-            None => return ToolResult::error("No compatible payment option found in 402 response"),
+        // Pick an option in an allowed asset, on the requested network if possible
+        let payment_option = match crate::x402::PaymentPolicy::from_env().select(
+            &payment_info.accepts,
+            params.network.as_deref(),
+            |opt| (opt.network.as_str(), opt.asset.as_str()),
+        ) {
+            Ok(opt) => opt.clone(),
+            Err(e) => return ToolResult::error(e),
         };
 
         log::info!(
@@ -370,7 +381,12 @@ impl Tool for X402AgentInvokeTool {
         log::info!("[x402_agent] Payment successful! Status: {}", paid_status);
 
         // Format amount for display (USDC has 6 decimals)
-        let amount_formatted = format_usdc(&payment_option.max_amount_required);
+        let asset = crate::x402::asset_label(&payment_option.network, &payment_option.asset);
+        // The policy only selects assets whose decimals it knows
+        let decimals = crate::x402::PaymentPolicy::from_env()
+            .decimals(&payment_option.network, &payment_option.asset)
+            .unwrap_or(crate::x402::USDC_DECIMALS);
+        let amount_formatted = format_amount(&payment_option.max_amount_required, decimals, &asset);
 
         // Try to parse response as JSON
         let result_content = if let Ok(json_val) = serde_json::from_str::<Value>(&paid_body) {
@@ -379,6 +395,17 @@ impl Tool for X402AgentInvokeTool {
             paid_body
         };
 
+        let cost = ToolCost {
+            amount: payment_option.max_amount_required.clone(),
+            amount_formatted: crate::x402::format_token_amount(&payment_option.max_amount_required, decimals),
+            asset,
+            decimals,
+            pay_to: payment_option.pay_to.clone(),
+            resource: Some(url.clone()),
+            tx_hash: None,
+            status: "confirmed".to_string(),
+        };
+
         ToolResult::success(result_content).with_metadata(json!({
             "url": url,
             "status": paid_status.as_u16(),
@@ -392,9 +419,52 @@ impl Tool for X402AgentInvokeTool {
                 "wallet": wallet_address,
             }
         }))
+        .with_cost(Some(cost))
     }
 }
 
+/// Resolve the agent base URL from an explicit agent_url or a discovered agent_name
+fn resolve_agent_url(params: &X402AgentInvokeParams, context: &ToolContext) -> Result<String, String> {
+    if let Some(url) = params.agent_url.as_ref().filter(|u| !u.is_empty()) {
+        return Ok(url.clone());
+    }
+
+    let name = params
+        .agent_name
+        .as_ref()
+        .filter(|n| !n.is_empty())
+        .ok_or("Either agent_url or agent_name is required")?;
+
+    let db = context
+        .database
+        .as_ref()
+        .ok_or("Database not available to look up agent by name")?;
+
+    let agent = db
+        .get_known_agent_by_name(name)
+        .map_err(|e| format!("Failed to look up agent '{}': {}", name, e))?
+        .ok_or_else(|| format!(
+            "No active agent named '{}' in known_agents. Run an EIP-8004 discovery sync first.",
+            name
+        ))?;
+
+    if !agent.x402_support {
+        return Err(format!("Agent '{}' does not advertise x402 support", name));
+    }
+
+    agent
+        .service_endpoint("x402")
+        .or_else(|| agent.service_endpoint("a2a"))
+        .or_else(|| {
+            agent
+                .services
+                .first()
+                .and_then(|s| s.get("endpoint").and_then(|e| e.as_str()))
+                .map(|s| s.to_string())
+        })
+        .ok_or_else(|| format!("Agent '{}' has no service endpoint in its registration card", name))
+}
+
 /// Sign payment using EIP-2612 (permit) or EIP-3009 (exact) based on scheme
 async fn sign_agent_payment(
     signer: &X402Signer,
@@ -465,17 +535,10 @@ async fn sign_agent_payment(
     })
 }
 
-/// Format USDC amount (6 decimals) to human readable
-fn format_usdc(raw: &str) -> String {
-    if let Ok(value) = raw.parse::<u128>() {
-        let whole = value / 1_000_000;
-        let frac = value % 1_000_000;
-        if frac == 0 {
-            format!("{} USDC", whole)
-        } else {
-            let frac_str = format!("{:06}", frac).trim_end_matches('0').to_string();
-            format!("{}.{} USDC", whole, frac_str)
-        }
+/// Format a stablecoin amount (6 decimals) with its symbol
+fn format_amount(raw: &str, decimals: u8, symbol: &str) -> String {
+    if raw.parse::<u128>().is_ok() {
+        format!("{} {}", crate::x402::format_token_amount(raw, decimals), symbol)
     } else {
         format!("{} units", raw)
     }
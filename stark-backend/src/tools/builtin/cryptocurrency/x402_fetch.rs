@@ -6,9 +6,10 @@ use crate::tools::http_retry::HttpRetryManager;
 use crate::tools::presets::{get_chain_id, get_fetch_preset, get_network_name, list_fetch_presets};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
-    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    PropertySchema, ToolContext, ToolCost, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use crate::x402::X402Client;
+use super::x402_inspect::InspectedChallenge;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -45,6 +46,17 @@ impl X402FetchTool {
             },
         );
 
+        properties.insert(
+            "challenge".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register holding a payment challenge from x402_inspect for the same preset. Pays it directly instead of requesting the challenge again.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "cache_as".to_string(),
             PropertySchema {
@@ -187,6 +199,7 @@ struct X402FetchParams {
     #[serde(default = "default_network")]
     network: String,
     cache_as: Option<String>,
+    challenge: Option<String>,
 }
 
 fn default_network() -> String {
@@ -217,50 +230,20 @@ impl Tool for X402FetchTool {
             }
         };
 
-        // Store network info in registers for use by other tools
-        let chain_id = get_chain_id(&params.network);
-        let network_name = get_network_name(&params.network);
-        context.set_register("network_name", json!(&network_name), "x402_fetch");
-        context.set_register("chain_id", json!(&chain_id), "x402_fetch");
-        log::info!(
-            "[x402_fetch] Stored network info: name={}, chain_id={}",
-            network_name, chain_id
-        );
-
-        // Build URL from registers
-        let mut url_params: Vec<String> = Vec::new();
-
-        // Add chain ID
-        url_params.push(format!("chainId={}", chain_id));
-
-        // Read register values and build URL params
-        for (reg_key, param_name) in &preset.params {
-            let value = match context.registers.get(reg_key) {
-                Some(v) => match v.as_str() {
-                    Some(s) => s.to_string(),
-                    None => v.to_string().trim_matches('"').to_string(),
-                },
-                None => {
-                    return ToolResult::error(format!(
-                        "Preset '{}' requires register '{}' but it was not found. Available: {:?}",
-                        params.preset,
-                        reg_key,
-                        context.registers.keys()
-                    ));
-                }
-            };
-            url_params.push(format!("{}={}", param_name, value));
-        }
-
-        let url = format!("{}?{}", preset.base_url, url_params.join("&"));
-        log::info!("[x402_fetch] Preset '{}' built URL: {}", params.preset, url);
+        let url = match preset_url(&params.preset, &params.network, context, "x402_fetch") {
+            Ok(url) => url,
+            Err(e) => return ToolResult::error(e),
+        };
 
-        // Validate URL is an x402 endpoint
-        if !crate::x402::is_x402_endpoint(&url) {
-            return ToolResult::error(
-                "URL must be an x402-enabled endpoint. Check preset configuration.",
-            );
-        }
+        // A challenge inspected beforehand is paid straight away, as long as
+        // it is for this URL and hasn't expired
+        let challenge = match params.challenge.as_deref() {
+            Some(register) => match InspectedChallenge::load(context, register, &url) {
+                Ok(challenge) => Some(challenge),
+                Err(e) => return ToolResult::error(e),
+            },
+            None => None,
+        };
 
         // Get the x402 client
         let client = match self.get_client() {
@@ -286,7 +269,13 @@ impl Tool for X402FetchTool {
                 tokio::time::sleep(tokio::time::Duration::from_secs(retry_delay_secs)).await;
             }
 
-            match client.get_with_payment(&url).await {
+            let result = match (&challenge, attempt) {
+                (Some(challenge), 1) => {
+                    client.get_with_challenge(&url, &challenge.payment_required).await
+                }
+                _ => client.get_with_payment(&url).await,
+            };
+            match result {
                 Ok(r) => {
                     // Check if response is successful before accepting
                     let status = r.response.status();
@@ -362,23 +351,29 @@ impl Tool for X402FetchTool {
         // Success - reset backoff
         retry_manager.record_success(&retry_key);
 
+        // The call is paid for from here on, whatever the body holds
+        let cost = response.payment.as_ref().map(ToolCost::from);
+
         // Parse response body
         let body = match response.response.text().await {
             Ok(b) => b,
-            Err(e) => return ToolResult::error(format!("Failed to read response: {}", e)),
+            Err(e) => {
+                return ToolResult::error(format!("Failed to read response: {}", e)).with_cost(cost)
+            }
         };
 
         // Parse as JSON and apply filter
         let json_value: Value = match serde_json::from_str(&body) {
             Ok(v) => v,
             Err(_) => {
-                return ToolResult::error(format!("Response is not valid JSON: {}", body));
+                return ToolResult::error(format!("Response is not valid JSON: {}", body))
+                    .with_cost(cost);
             }
         };
 
         let filtered = match self.apply_jq_filter(&json_value, &preset.jq_filter) {
             Ok(f) => f,
-            Err(e) => return ToolResult::error(format!("Filter error: {}", e)),
+            Err(e) => return ToolResult::error(format!("Filter error: {}", e)).with_cost(cost),
         };
 
         let result_content =
@@ -414,6 +409,70 @@ impl Tool for X402FetchTool {
             metadata["cached_in_register"] = json!(register_name);
         }
 
-        ToolResult::success(result_content).with_metadata(metadata)
+        ToolResult::success(result_content)
+            .with_metadata(metadata)
+            .with_cost(cost)
     }
 }
+
+/// Build a fetch preset's URL from the registers, storing the network's name
+/// and chain ID in registers for later tools
+pub(super) fn preset_url(
+    preset_name: &str,
+    network: &str,
+    context: &ToolContext,
+    source_tool: &str,
+) -> Result<String, String> {
+    let preset = get_fetch_preset(preset_name).ok_or_else(|| {
+        format!(
+            "Unknown preset: '{}'. Available: {}",
+            preset_name,
+            list_fetch_presets().join(", ")
+        )
+    })?;
+
+    // Store network info in registers for use by other tools
+    let chain_id = get_chain_id(network);
+    let network_name = get_network_name(network);
+    context.set_register("network_name", json!(&network_name), source_tool);
+    context.set_register("chain_id", json!(&chain_id), source_tool);
+    log::info!(
+        "[{}] Stored network info: name={}, chain_id={}",
+        source_tool, network_name, chain_id
+    );
+
+    // Build URL from registers
+    let mut url_params: Vec<String> = Vec::new();
+
+    // Add chain ID
+    url_params.push(format!("chainId={}", chain_id));
+
+    // Read register values and build URL params
+    for (reg_key, param_name) in &preset.params {
+        let value = match context.registers.get_or_intrinsic(reg_key, context, None) {
+            Some(v) => match v.as_str() {
+                Some(s) => s.to_string(),
+                None => v.to_string().trim_matches('"').to_string(),
+            },
+            None => {
+                return Err(format!(
+                    "Preset '{}' requires register '{}' but it was not found. Available: {:?}",
+                    preset_name,
+                    reg_key,
+                    context.registers.keys()
+                ));
+            }
+        };
+        url_params.push(format!("{}={}", param_name, value));
+    }
+
+    let url = format!("{}?{}", preset.base_url, url_params.join("&"));
+    log::info!("[{}] Preset '{}' built URL: {}", source_tool, preset_name, url);
+
+    // Validate URL is an x402 endpoint
+    if !crate::x402::is_x402_endpoint(&url) {
+        return Err("URL must be an x402-enabled endpoint. Check preset configuration.".to_string());
+    }
+
+    Ok(url)
+}
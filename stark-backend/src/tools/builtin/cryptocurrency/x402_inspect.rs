@@ -0,0 +1,299 @@
+//! x402 Inspect tool for reading a payment challenge without paying
+//!
+//! Makes the unpaid request for a fetch preset and reports what the endpoint
+//! asks for, so the agent can decide whether to pay before any money moves.
+//! The challenge is kept in a register; passing that register to x402_fetch
+//! pays it without requesting it again.
+
+use super::x402_fetch::preset_url;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::x402::{asset_label, format_token_amount, PaymentPolicy, PaymentRequired, X402Client};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Source tool recorded on the registers holding challenges
+const INSPECT_TOOL: &str = "x402_inspect";
+
+/// How long a challenge is good for when the endpoint doesn't say
+const DEFAULT_CHALLENGE_TTL_SECS: u64 = 60;
+
+/// A payment challenge captured by x402_inspect, as stored in its register
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct InspectedChallenge {
+    pub url: String,
+    pub captured_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub payment_required: PaymentRequired,
+}
+
+impl InspectedChallenge {
+    /// Read the challenge in `register`, checking that x402_inspect wrote it,
+    /// that it was captured for `url` and that it hasn't expired. Any other
+    /// tool writing the register could otherwise plant a challenge with its
+    /// own recipient and amount.
+    pub fn load(context: &ToolContext, register: &str, url: &str) -> Result<Self, String> {
+        let entry = context.registers.get_entry(register).ok_or_else(|| {
+            format!("Register '{}' not found. Run x402_inspect first.", register)
+        })?;
+        if entry.source_tool != INSPECT_TOOL {
+            return Err(format!(
+                "Register '{}' was written by {}, not x402_inspect. Run x402_inspect again.",
+                register, entry.source_tool
+            ));
+        }
+        let challenge: Self = serde_json::from_value(entry.value).map_err(|_| {
+            format!("Register '{}' does not hold an x402_inspect challenge", register)
+        })?;
+
+        if challenge.url != url {
+            return Err(format!(
+                "The challenge in '{}' was inspected for a different request. Run x402_inspect again.",
+                register
+            ));
+        }
+        if Utc::now() >= challenge.expires_at {
+            return Err(format!(
+                "The challenge in '{}' expired at {}. Run x402_inspect again.",
+                register,
+                challenge.expires_at.to_rfc3339()
+            ));
+        }
+        Ok(challenge)
+    }
+}
+
+/// x402 Inspect tool (preset-only, like x402_fetch)
+pub struct X402InspectTool {
+    definition: ToolDefinition,
+}
+
+impl X402InspectTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "preset".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "x402_fetch preset to inspect. Available: 'swap_quote'.".to_string(),
+                default: None,
+                items: None,
+                enum_values: Some(vec!["swap_quote".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "network".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Network: 'base' or 'mainnet'".to_string(),
+                default: Some(json!("base")),
+                items: None,
+                enum_values: Some(vec!["base".to_string(), "mainnet".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "cache_as".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Register to keep the challenge in, for x402_fetch's 'challenge' parameter".to_string(),
+                default: Some(json!("x402_challenge")),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        X402InspectTool {
+            definition: ToolDefinition {
+                name: "x402_inspect".to_string(),
+                description: "Check what an x402_fetch preset would cost without paying: amount, asset, recipient and expiry of the endpoint's payment challenge, and whether it is allowed. Pass the returned register to x402_fetch as 'challenge' to pay it.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["preset".to_string()],
+                },
+                group: ToolGroup::Finance,
+            },
+        }
+    }
+
+    fn get_client(&self) -> Result<X402Client, String> {
+        let private_key = crate::config::burner_wallet_private_key()
+            .ok_or("BURNER_WALLET_BOT_PRIVATE_KEY environment variable not set")?;
+
+        X402Client::new(&private_key)
+    }
+}
+
+impl Default for X402InspectTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct X402InspectParams {
+    preset: String,
+    #[serde(default = "default_network")]
+    network: String,
+    #[serde(default = "default_cache_as")]
+    cache_as: String,
+}
+
+fn default_network() -> String {
+    "base".to_string()
+}
+
+fn default_cache_as() -> String {
+    "x402_challenge".to_string()
+}
+
+#[async_trait]
+impl Tool for X402InspectTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: X402InspectParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let url = match preset_url(&params.preset, &params.network, context, "x402_inspect") {
+            Ok(url) => url,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let client = match self.get_client() {
+            Ok(c) => c,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let payment_required = match client.get_challenge(&url).await {
+            Ok(Some(payment_required)) => payment_required,
+            Ok(None) => {
+                return ToolResult::success(
+                    json!({ "url": url, "payment_required": false }).to_string(),
+                )
+            }
+            Err(e) => return ToolResult::error(format!("Failed to inspect payment challenge: {}", e)),
+        };
+
+        // Report the option x402_fetch would pay, or why none would be paid
+        let policy = PaymentPolicy::from_env();
+        let selected = policy.select(&payment_required.accepts, None, |a| {
+            (a.network.as_str(), a.asset.as_str())
+        });
+        let option = match &selected {
+            Ok(option) => *option,
+            Err(_) => match payment_required.accepts.first() {
+                Some(option) => option,
+                None => return ToolResult::error("402 response offers no payment options"),
+            },
+        };
+
+        let captured_at = Utc::now();
+        let ttl = match option.max_timeout_seconds {
+            0 => DEFAULT_CHALLENGE_TTL_SECS,
+            secs => secs,
+        };
+        let expires_at = captured_at + Duration::seconds(ttl as i64);
+
+        let mut summary = json!({
+            "url": url,
+            "payment_required": true,
+            "amount": option.max_amount_required,
+            "amount_formatted": policy
+                .decimals(&option.network, &option.asset)
+                .map(|decimals| format_token_amount(&option.max_amount_required, decimals)),
+            "decimals": policy.decimals(&option.network, &option.asset),
+            "asset": asset_label(&option.network, &option.asset),
+            "asset_address": option.asset,
+            "network": option.network,
+            "pay_to": option.pay_to_address,
+            "resource": option.resource,
+            "description": option.description,
+            "expires_at": expires_at.to_rfc3339(),
+            "options": payment_required.accepts.len(),
+            "allowed": selected.is_ok(),
+        });
+        if let Err(e) = &selected {
+            summary["reason"] = json!(e);
+        }
+
+        let challenge = InspectedChallenge {
+            url,
+            captured_at,
+            expires_at,
+            payment_required,
+        };
+        match serde_json::to_value(&challenge) {
+            Ok(value) => {
+                context.set_register(&params.cache_as, value, INSPECT_TOOL);
+                summary["challenge_register"] = json!(params.cache_as);
+            }
+            Err(e) => log::warn!("[x402_inspect] Failed to store challenge: {}", e),
+        }
+
+        log::info!(
+            "[x402_inspect] Preset '{}' asks for {} {} (allowed: {})",
+            params.preset,
+            summary["amount_formatted"],
+            summary["asset"],
+            summary["allowed"]
+        );
+
+        ToolResult::success(serde_json::to_string_pretty(&summary).unwrap_or_default())
+            .with_metadata(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://quote.example/swap";
+
+    fn challenge() -> Value {
+        let now = Utc::now();
+        json!({
+            "url": URL,
+            "captured_at": now,
+            "expires_at": now + Duration::seconds(60),
+            "payment_required": {
+                "x402Version": 1,
+                "accepts": [{
+                    "scheme": "exact",
+                    "network": "base",
+                    "maxAmountRequired": "1000000",
+                    "payToAddress": "0x0000000000000000000000000000000000000001",
+                    "asset": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+                }],
+            },
+        })
+    }
+
+    #[test]
+    fn test_load_accepts_inspected_challenge() {
+        let context = ToolContext::new();
+        context.set_register("x402_challenge", challenge(), INSPECT_TOOL);
+        assert!(InspectedChallenge::load(&context, "x402_challenge", URL).is_ok());
+    }
+
+    #[test]
+    fn test_load_refuses_challenge_from_other_tools() {
+        let context = ToolContext::new();
+        context.set_register("planted", challenge(), "register_set");
+
+        let err = InspectedChallenge::load(&context, "planted", URL).unwrap_err();
+        assert!(err.contains("not x402_inspect"), "{}", err);
+    }
+}
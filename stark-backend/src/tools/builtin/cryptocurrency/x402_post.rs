@@ -8,7 +8,8 @@
 use crate::controllers::api_keys::ApiKeyId;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
-    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    PropertySchema, ToolContext, ToolCost, ToolDefinition, ToolGroup, ToolInputSchema,
+    ToolResult,
 };
 use crate::x402::X402Signer;
 use async_trait::async_trait;
@@ -67,11 +68,11 @@ impl X402PostTool {
             "network".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Network for payment: 'base' (mainnet) or 'base-sepolia' (testnet)"
+                description: "Preferred network for payment (e.g. 'base'). Defaults to the first network allowed by STARK_X402_NETWORKS."
                     .to_string(),
-                default: Some(json!("base")),
+                default: None,
                 items: None,
-                enum_values: Some(vec!["base".to_string(), "base-sepolia".to_string()]),
+                enum_values: None,
             },
         );
 
@@ -142,12 +143,8 @@ struct X402PostParams {
     body: Value,
     #[serde(default)]
     headers: HashMap<String, String>,
-    #[serde(default = "default_network")]
-    network: String,
-}
-
-fn default_network() -> String {
-    "base".to_string()
+    #[serde(default)]
+    network: Option<String>,
 }
 
 /// 402 response format (x402 spec)
@@ -344,18 +341,14 @@ impl Tool for X402PostTool {
             }
         };
 
-        // Find matching payment option
-        let payment_option = payment_info
-            .accepts
-            .iter()
-            .find(|opt| opt.network == params.network)
-            .or_else(|| payment_info.accepts.first());
-
-        let payment_option = match payment_option {
-            Some(opt) => opt.clone(),
-            None => {
-                return ToolResult::error("No compatible payment option found in 402 response")
-            }
+        // Pick an option in an allowed asset, on the requested network if possible
+        let payment_option = match crate::x402::PaymentPolicy::from_env().select(
+            &payment_info.accepts,
+            params.network.as_deref(),
+            |opt| (opt.network.as_str(), opt.asset.as_str()),
+        ) {
+            Ok(opt) => opt.clone(),
+            Err(e) => return ToolResult::error(e),
         };
 
         log::info!(
@@ -422,7 +415,12 @@ impl Tool for X402PostTool {
 
         log::info!("[x402_post] Success! Status: {}", paid_status);
 
-        let amount_formatted = format_usdc(&payment_option.max_amount_required);
+        let asset = crate::x402::asset_label(&payment_option.network, &payment_option.asset);
+        // The policy only selects assets whose decimals it knows
+        let decimals = crate::x402::PaymentPolicy::from_env()
+            .decimals(&payment_option.network, &payment_option.asset)
+            .unwrap_or(crate::x402::USDC_DECIMALS);
+        let amount_formatted = format_amount(&payment_option.max_amount_required, decimals, &asset);
 
         let result_content = if let Ok(json_val) = serde_json::from_str::<Value>(&paid_body) {
             serde_json::to_string_pretty(&json_val).unwrap_or(paid_body.clone())
@@ -430,6 +428,17 @@ impl Tool for X402PostTool {
             paid_body
         };
 
+        let cost = ToolCost {
+            amount: payment_option.max_amount_required.clone(),
+            amount_formatted: crate::x402::format_token_amount(&payment_option.max_amount_required, decimals),
+            asset,
+            decimals,
+            pay_to: payment_option.pay_to.clone(),
+            resource: Some(params.url.clone()),
+            tx_hash: None,
+            status: "confirmed".to_string(),
+        };
+
         ToolResult::success(result_content).with_metadata(json!({
             "url": params.url,
             "status": paid_status.as_u16(),
@@ -443,6 +452,7 @@ impl Tool for X402PostTool {
                 "wallet": wallet_address,
             }
         }))
+        .with_cost(Some(cost))
     }
 }
 
@@ -513,17 +523,10 @@ async fn sign_payment(
     })
 }
 
-/// Format USDC amount (6 decimals)
-fn format_usdc(raw: &str) -> String {
-    if let Ok(value) = raw.parse::<u128>() {
-        let whole = value / 1_000_000;
-        let frac = value % 1_000_000;
-        if frac == 0 {
-            format!("{} USDC", whole)
-        } else {
-            let frac_str = format!("{:06}", frac).trim_end_matches('0').to_string();
-            format!("{}.{} USDC", whole, frac_str)
-        }
+/// Format a stablecoin amount (6 decimals) with its symbol
+fn format_amount(raw: &str, decimals: u8, symbol: &str) -> String {
+    if raw.parse::<u128>().is_ok() {
+        format!("{} {}", crate::x402::format_token_amount(raw, decimals), symbol)
     } else {
         format!("{} units", raw)
     }
@@ -1,16 +1,18 @@
 //! x402 RPC tool for making paid EVM RPC calls via DeFi Relay
 //!
 //! Uses presets to build RPC params from register values, preventing hallucination.
+//! A `batch` action sends multiple read-only requests as one JSON-RPC batch.
 //! Supports configurable RPC endpoints via bot settings.
 
+use super::network_lookup::{resolve_network, supported_network_ids};
 use crate::tools::http_retry::HttpRetryManager;
 use crate::tools::presets::{get_rpc_preset, list_rpc_presets};
 use crate::tools::registry::Tool;
-use crate::tools::rpc_config::resolve_rpc_from_context;
+use crate::tools::rpc_config::{resolve_rpc_from_context, ResolvedRpcConfig, RpcEndpoint};
 use crate::tools::types::{
-    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    PropertySchema, ToolContext, ToolCost, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
-use crate::x402::X402Client;
+use crate::x402::{X402Client, X402Response, FAILOVER_ATTEMPT_TIMEOUT};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -34,8 +36,9 @@ struct JsonRpcResponse {
     jsonrpc: String,
     result: Option<Value>,
     error: Option<JsonRpcError>,
-    #[allow(dead_code)]
-    id: u64,
+    /// Null for errors the provider couldn't tie to a request
+    #[serde(default)]
+    id: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -72,25 +75,53 @@ impl X402RpcTool {
             },
         );
 
+        properties.insert(
+            "action".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "'preset' (default) runs a single preset. 'batch' sends an array of raw read-only JSON-RPC requests as one paid call and returns results in the same order.".to_string(),
+                default: Some(json!("preset")),
+                items: None,
+                enum_values: Some(vec!["preset".to_string(), "batch".to_string()]),
+            },
+        );
+
+        properties.insert(
+            "requests".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "For batch: array of {method, params} JSON-RPC requests (read-only methods such as eth_call, eth_getBalance). Max 50. Failed items report an error without failing the batch.".to_string(),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "object".to_string(),
+                    description: "JSON-RPC request: {\"method\": \"eth_call\", \"params\": [...]}".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "network".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Network: 'base' or 'mainnet'".to_string(),
+                description: format!("Network. Configured networks: {}", supported_network_ids().join(", ")),
                 default: Some(json!("base")),
                 items: None,
-                enum_values: Some(vec!["base".to_string(), "mainnet".to_string()]),
+                enum_values: None,
             },
         );
 
         X402RpcTool {
             definition: ToolDefinition {
                 name: "x402_rpc".to_string(),
-                description: "Make paid EVM RPC calls using presets. Presets read from registers. Available: gas_price, block_number, get_balance, get_nonce.".to_string(),
+                description: "Make paid EVM RPC calls using presets. Presets read from registers. Available: gas_price, block_number, get_balance, get_nonce. Use action 'batch' to send many read-only calls (e.g. several eth_call balance reads) in a single paid request.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
-                    required: vec!["preset".to_string()],
+                    required: vec![],
                 },
                 group: ToolGroup::Finance,
             },
@@ -115,39 +146,241 @@ impl Default for X402RpcTool {
 
 #[derive(Debug, Deserialize)]
 struct X402RpcParams {
-    preset: String,
+    #[serde(default = "default_action")]
+    action: String,
+    preset: Option<String>,
+    requests: Option<Vec<BatchRequestItem>>,
     #[serde(default = "default_network")]
     network: String,
 }
 
+/// A single raw JSON-RPC request in a batch
+#[derive(Debug, Deserialize)]
+struct BatchRequestItem {
+    method: String,
+    #[serde(default = "default_batch_params")]
+    params: Value,
+}
+
+fn default_action() -> String {
+    "preset".to_string()
+}
+
 fn default_network() -> String {
     "base".to_string()
 }
 
-#[async_trait]
-impl Tool for X402RpcTool {
-    fn definition(&self) -> ToolDefinition {
-        self.definition.clone()
+fn default_batch_params() -> Value {
+    json!([])
+}
+
+/// Maximum number of requests in a single batch
+const MAX_BATCH_SIZE: usize = 50;
+
+/// Read-only methods allowed in raw batches (no state-changing calls)
+const BATCH_ALLOWED_METHODS: &[&str] = &[
+    "eth_call",
+    "eth_getBalance",
+    "eth_getTransactionCount",
+    "eth_getCode",
+    "eth_getStorageAt",
+    "eth_blockNumber",
+    "eth_gasPrice",
+    "eth_maxPriorityFeePerGas",
+    "eth_chainId",
+    "eth_estimateGas",
+    "eth_getBlockByNumber",
+    "eth_getTransactionByHash",
+    "eth_getTransactionReceipt",
+    "eth_getLogs",
+];
+
+/// Validate batch items, splitting them into requests to send and per-item
+/// results for items rejected up front. Request ids are the item indices.
+fn build_batch(items: &[BatchRequestItem]) -> (Vec<JsonRpcRequest>, Vec<Option<Value>>) {
+    let mut requests = Vec::new();
+    let mut results = vec![None; items.len()];
+
+    for (index, item) in items.iter().enumerate() {
+        if !BATCH_ALLOWED_METHODS.contains(&item.method.as_str()) {
+            results[index] = Some(json!({
+                "index": index,
+                "method": item.method,
+                "error": format!("Method '{}' is not allowed in batch (read-only methods only)", item.method),
+            }));
+            continue;
+        }
+        if !item.params.is_array() {
+            results[index] = Some(json!({
+                "index": index,
+                "method": item.method,
+                "error": "params must be an array",
+            }));
+            continue;
+        }
+        requests.push(JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: item.method.clone(),
+            params: item.params.clone(),
+            id: index as u64,
+        });
     }
 
-    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
-        let params: X402RpcParams = match serde_json::from_value(params) {
-            Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+    (requests, results)
+}
+
+/// Match batch responses back to their requests by id, preserving input order
+fn collect_batch_results(
+    items: &[BatchRequestItem],
+    mut results: Vec<Option<Value>>,
+    responses: Vec<JsonRpcResponse>,
+) -> Vec<Value> {
+    for response in responses {
+        let index = match response.id {
+            Some(id) if (id as usize) < results.len() => id as usize,
+            _ => continue,
         };
+        if results[index].is_some() {
+            continue;
+        }
+        let method = &items[index].method;
+        results[index] = Some(match response.error {
+            Some(error) => json!({
+                "index": index,
+                "method": method,
+                "error": format!("RPC error {}: {}", error.code, error.message),
+            }),
+            None => json!({
+                "index": index,
+                "method": method,
+                "result": response.result.unwrap_or(Value::Null),
+            }),
+        });
+    }
+
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(index, r)| {
+            r.unwrap_or_else(|| {
+                json!({
+                    "index": index,
+                    "method": items[index].method,
+                    "error": "No response for this request",
+                })
+            })
+        })
+        .collect()
+}
+
+impl X402RpcTool {
+    /// POST a JSON-RPC payload, failing over to the next provider on
+    /// connection errors, timeouts and 5xx. Returns the response and the
+    /// endpoint used.
+    async fn post_with_failover<'a, T: Serialize>(
+        client: &X402Client,
+        rpc_config: &'a ResolvedRpcConfig,
+        body: &T,
+        label: &str,
+    ) -> (Result<X402Response, String>, &'a RpcEndpoint) {
+        let last = rpc_config.endpoints.len() - 1;
+        for (i, endpoint) in rpc_config.endpoints.iter().enumerate() {
+            let attempt = async {
+                if endpoint.use_x402 {
+                    client.post_with_payment(&endpoint.url, body).await
+                } else {
+                    client.post_regular(&endpoint.url, body).await
+                }
+            };
+            let result = match tokio::time::timeout(FAILOVER_ATTEMPT_TIMEOUT, attempt).await {
+                Ok(result) => result,
+                Err(_) => Err(format!(
+                    "Timed out after {}s waiting for {}",
+                    FAILOVER_ATTEMPT_TIMEOUT.as_secs(),
+                    endpoint.url
+                )),
+            };
 
-        // Validate network
-        if params.network != "base" && params.network != "mainnet" {
-            return ToolResult::error("Network must be 'base' or 'mainnet'");
+            let failed = match &result {
+                Ok(r) => r.response.status().is_server_error(),
+                Err(_) => true,
+            };
+            if failed && i < last {
+                log::warn!(
+                    "[x402_rpc] Provider '{}' failed for {}, trying '{}'",
+                    endpoint.provider,
+                    label,
+                    rpc_config.endpoints[i + 1].provider
+                );
+                continue;
+            }
+
+            return (result, endpoint);
         }
+        unreachable!("resolved RPC config always has at least one endpoint")
+    }
+
+    /// Send a payload and apply the shared status/backoff handling.
+    /// Returns the response body, what the call paid and the provider on success.
+    async fn send(
+        client: &X402Client,
+        rpc_config: &ResolvedRpcConfig,
+        body: &impl Serialize,
+        label: &str,
+        retry_key: &str,
+    ) -> Result<(String, Option<ToolCost>, String), ToolResult> {
+        let retry_manager = HttpRetryManager::global();
+        let (response, served_by) = Self::post_with_failover(client, rpc_config, body, label).await;
 
+        let response = match response {
+            Ok(r) => r,
+            Err(e) => {
+                let error_msg = format!("RPC request failed: {}", e);
+                if HttpRetryManager::is_retryable_error(&error_msg) {
+                    let delay = retry_manager.record_error(retry_key);
+                    return Err(ToolResult::retryable_error(error_msg, delay));
+                }
+                return Err(ToolResult::error(error_msg));
+            }
+        };
+
+        // Check HTTP status
+        let status = response.response.status();
+        if !status.is_success() {
+            let body = response.response.text().await.unwrap_or_default();
+            let error_msg = format!("HTTP error {}: {}", status, body);
+            if HttpRetryManager::is_retryable_status(status.as_u16()) {
+                let delay = retry_manager.record_error(retry_key);
+                return Err(ToolResult::retryable_error(error_msg, delay));
+            }
+            return Err(ToolResult::error(error_msg));
+        }
+
+        // Success - reset backoff
+        retry_manager.record_success(retry_key);
+        log::info!("[x402_rpc] {} served by '{}'", label, served_by.provider);
+
+        let cost = response.payment.as_ref().map(ToolCost::from);
+
+        match response.response.text().await {
+            Ok(b) => Ok((b, cost, served_by.provider.clone())),
+            Err(e) => Err(ToolResult::error(format!("Failed to read response: {}", e)).with_cost(cost)),
+        }
+    }
+
+    async fn execute_preset(
+        &self,
+        preset_name: &str,
+        network: &str,
+        context: &ToolContext,
+    ) -> ToolResult {
         // Get preset configuration
-        let preset = match get_rpc_preset(&params.preset) {
+        let preset = match get_rpc_preset(preset_name) {
             Some(p) => p,
             None => {
                 return ToolResult::error(format!(
                     "Unknown preset: '{}'. Available: {}",
-                    params.preset,
+                    preset_name,
                     list_rpc_presets().join(", ")
                 ))
             }
@@ -156,7 +389,7 @@ impl Tool for X402RpcTool {
         // Build params from registers
         let mut param_values: Vec<Value> = Vec::new();
         for reg_key in &preset.params {
-            let value = match context.registers.get(reg_key) {
+            let value = match context.registers.get_or_intrinsic(reg_key, context, None) {
                 Some(v) => match v.as_str() {
                     Some(s) => json!(s),
                     None => v,
@@ -164,7 +397,7 @@ impl Tool for X402RpcTool {
                 None => {
                     return ToolResult::error(format!(
                         "Preset '{}' requires register '{}' but it was not found. Available: {:?}",
-                        params.preset,
+                        preset_name,
                         reg_key,
                         context.registers.keys()
                     ));
@@ -179,14 +412,14 @@ impl Tool for X402RpcTool {
         }
 
         // Resolve RPC configuration from context (respects custom RPC settings)
-        let rpc_config = resolve_rpc_from_context(&context.extra, &params.network);
+        let rpc_config = resolve_rpc_from_context(&context.extra, network);
 
         log::info!(
             "[x402_rpc] Preset '{}' -> {} with {} params on {} (rpc={})",
-            params.preset,
+            preset_name,
             preset.method,
             param_values.len(),
-            params.network,
+            network,
             rpc_config.url
         );
 
@@ -204,49 +437,13 @@ impl Tool for X402RpcTool {
             Err(e) => return ToolResult::error(e),
         };
 
-        // Setup retry tracking
-        let retry_key = format!("x402_rpc:{}:{}", params.network, params.preset);
-        let retry_manager = HttpRetryManager::global();
-
         // Make the request (with or without x402 payment based on config)
-        let response = if rpc_config.use_x402 {
-            client.post_with_payment(&rpc_config.url, &rpc_request).await
-        } else {
-            client.post_regular(&rpc_config.url, &rpc_request).await
-        };
-
-        let response = match response {
-            Ok(r) => r,
-            Err(e) => {
-                let error_msg = format!("RPC request failed: {}", e);
-                if HttpRetryManager::is_retryable_error(&error_msg) {
-                    let delay = retry_manager.record_error(&retry_key);
-                    return ToolResult::retryable_error(error_msg, delay);
-                }
-                return ToolResult::error(error_msg);
-            }
-        };
-
-        // Check HTTP status
-        let status = response.response.status();
-        if !status.is_success() {
-            let body = response.response.text().await.unwrap_or_default();
-            let error_msg = format!("HTTP error {}: {}", status, body);
-            if HttpRetryManager::is_retryable_status(status.as_u16()) {
-                let delay = retry_manager.record_error(&retry_key);
-                return ToolResult::retryable_error(error_msg, delay);
-            }
-            return ToolResult::error(error_msg);
-        }
-
-        // Success - reset backoff
-        retry_manager.record_success(&retry_key);
-
-        // Parse response
-        let body = match response.response.text().await {
-            Ok(b) => b,
-            Err(e) => return ToolResult::error(format!("Failed to read response: {}", e)),
-        };
+        let retry_key = format!("x402_rpc:{}:{}", network, preset_name);
+        let (body, cost, provider) =
+            match Self::send(&client, &rpc_config, &rpc_request, &preset.method, &retry_key).await {
+                Ok(r) => r,
+                Err(result) => return result,
+            };
 
         let rpc_response: JsonRpcResponse = match serde_json::from_str(&body) {
             Ok(r) => r,
@@ -255,37 +452,218 @@ impl Tool for X402RpcTool {
                     "Invalid JSON-RPC response: {} - Body: {}",
                     e, body
                 ))
+                .with_cost(cost)
             }
         };
 
-        // Check for RPC error
+        // Check for RPC error (the call was still paid for)
         if let Some(error) = rpc_response.error {
-            return ToolResult::error(format!("RPC error {}: {}", error.code, error.message));
+            return ToolResult::error(format!("RPC error {}: {}", error.code, error.message))
+                .with_cost(cost);
         }
 
         // Build metadata
         let mut metadata = json!({
-            "preset": params.preset,
+            "preset": preset_name,
             "method": preset.method,
-            "network": params.network,
+            "network": network,
+            "rpc_provider": provider,
             "wallet": client.wallet_address(),
         });
 
-        if let Some(payment) = response.payment {
-            metadata["payment"] = json!({
-                "amount": payment.amount_formatted,
-                "asset": payment.asset,
-                "pay_to": payment.pay_to,
-            });
+        if let Some(ref cost) = cost {
+            metadata["payment"] = payment_summary(cost);
         }
 
         // Return the result
-        match rpc_response.result {
+        let result = match rpc_response.result {
             Some(result) => ToolResult::success(
                 serde_json::to_string_pretty(&result).unwrap_or_else(|_| result.to_string()),
-            )
-            .with_metadata(metadata),
-            None => ToolResult::success("null").with_metadata(metadata),
+            ),
+            None => ToolResult::success("null"),
+        };
+        result.with_metadata(metadata).with_cost(cost)
+    }
+
+    async fn execute_batch(
+        &self,
+        items: &[BatchRequestItem],
+        network: &str,
+        context: &ToolContext,
+    ) -> ToolResult {
+        if items.is_empty() {
+            return ToolResult::error("Batch 'requests' must contain at least one request");
+        }
+        if items.len() > MAX_BATCH_SIZE {
+            return ToolResult::error(format!(
+                "Batch too large: {} requests (max {})",
+                items.len(),
+                MAX_BATCH_SIZE
+            ));
         }
+
+        let (requests, results) = build_batch(items);
+        let mut metadata = json!({
+            "action": "batch",
+            "network": network,
+            "count": items.len(),
+        });
+
+        let mut cost = None;
+        let responses = if requests.is_empty() {
+            Vec::new()
+        } else {
+            let rpc_config = resolve_rpc_from_context(&context.extra, network);
+            log::info!(
+                "[x402_rpc] Batch of {} requests on {} (rpc={})",
+                requests.len(),
+                network,
+                rpc_config.url
+            );
+
+            let client = match self.get_client().await {
+                Ok(c) => c,
+                Err(e) => return ToolResult::error(e),
+            };
+
+            let retry_key = format!("x402_rpc:{}:batch", network);
+            let label = format!("batch of {}", requests.len());
+            let (body, paid, provider) =
+                match Self::send(&client, &rpc_config, &requests, &label, &retry_key).await {
+                    Ok(r) => r,
+                    Err(result) => return result,
+                };
+            cost = paid;
+
+            metadata["rpc_provider"] = json!(provider);
+            metadata["wallet"] = json!(client.wallet_address());
+            if let Some(ref cost) = cost {
+                metadata["payment"] = payment_summary(cost);
+            }
+
+            // Providers return an array for batches, but may return a single
+            // error object if they reject the batch as a whole
+            match serde_json::from_str::<Vec<JsonRpcResponse>>(&body) {
+                Ok(r) => r,
+                Err(_) => match serde_json::from_str::<JsonRpcResponse>(&body) {
+                    Ok(JsonRpcResponse { error: Some(error), .. }) => {
+                        return ToolResult::error(format!(
+                            "RPC rejected batch: {} {}",
+                            error.code, error.message
+                        ))
+                        .with_cost(cost)
+                    }
+                    _ => {
+                        return ToolResult::error(format!(
+                            "Invalid JSON-RPC batch response - Body: {}",
+                            body
+                        ))
+                        .with_cost(cost)
+                    }
+                },
+            }
+        };
+
+        let results = collect_batch_results(items, results, responses);
+        let errors = results.iter().filter(|r| r.get("error").is_some()).count();
+        metadata["errors"] = json!(errors);
+
+        ToolResult::success(
+            serde_json::to_string_pretty(&results).unwrap_or_else(|_| json!(results).to_string()),
+        )
+        .with_metadata(metadata)
+        .with_cost(cost)
+    }
+}
+
+/// The payment as shown to the agent in the result metadata
+fn payment_summary(cost: &ToolCost) -> Value {
+    json!({
+        "amount": cost.amount_formatted,
+        "asset": cost.asset,
+        "pay_to": cost.pay_to,
+    })
+}
+
+#[async_trait]
+impl Tool for X402RpcTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: X402RpcParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        // Validate network against configured networks
+        let network = match resolve_network(Some(&params.network), None) {
+            Ok(n) => n,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        match params.action.as_str() {
+            "preset" => match params.preset {
+                Some(ref preset) => self.execute_preset(preset, &network, context).await,
+                None => ToolResult::error("'preset' is required for the preset action"),
+            },
+            "batch" => match params.requests {
+                Some(ref requests) => self.execute_batch(requests, &network, context).await,
+                None => ToolResult::error("'requests' is required for the batch action"),
+            },
+            other => ToolResult::error(format!(
+                "Unknown action '{}'. Use 'preset' or 'batch'.",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(value: Value) -> Vec<BatchRequestItem> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_build_batch_rejects_write_methods() {
+        let items = items(json!([
+            { "method": "eth_getBalance", "params": ["0xabc", "latest"] },
+            { "method": "eth_sendRawTransaction", "params": ["0x00"] },
+            { "method": "eth_blockNumber" },
+        ]));
+        let (requests, results) = build_batch(&items);
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].id, 0);
+        assert_eq!(requests[1].id, 2);
+        assert!(results[0].is_none());
+        assert!(results[1].as_ref().unwrap()["error"].as_str().unwrap().contains("not allowed"));
+    }
+
+    #[test]
+    fn test_collect_batch_results_preserves_order() {
+        let items = items(json!([
+            { "method": "eth_blockNumber" },
+            { "method": "eth_call", "params": [{ "to": "0xabc", "data": "0x" }, "latest"] },
+            { "method": "eth_gasPrice" },
+        ]));
+        let (_, results) = build_batch(&items);
+
+        // Responses arrive out of order, one failed, one missing
+        let responses: Vec<JsonRpcResponse> = serde_json::from_value(json!([
+            { "jsonrpc": "2.0", "id": 1, "error": { "code": 3, "message": "execution reverted" } },
+            { "jsonrpc": "2.0", "id": 0, "result": "0x10" },
+        ]))
+        .unwrap();
+
+        let results = collect_batch_results(&items, results, responses);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["result"], "0x10");
+        assert!(results[1]["error"].as_str().unwrap().contains("execution reverted"));
+        assert_eq!(results[2]["error"], "No response for this request");
     }
 }
@@ -19,6 +19,7 @@ mod process_status;
 mod qmd_memory_read;
 mod qmd_memory_search;
 mod web_fetch;
+mod webhook;
 
 // Re-exports from submodules
 pub use bash::{
@@ -28,18 +29,24 @@ pub use bash::{
 pub use code::{CommitterTool, DeployTool, PrQualityTool};
 pub use core::{
     AgentSendTool, ApiKeysCheckTool, AskUserTool, ManageSkillsTool, ModifySoulTool, SayToUserTool,
-    SetAgentSubtypeTool, SubagentStatusTool, SubagentTool, TaskFullyCompletedTool,
+    SetAgentSubtypeTool, SubagentCollectTool, SubagentStatusTool, SubagentTool,
+    TaskFullyCompletedTool,
 };
 pub use cryptocurrency::{
-    load_networks, load_tokens, BridgeUsdcTool, BroadcastWeb3TxTool, DecodeCalldataTool,
-    DexScreenerTool, ListQueuedWeb3TxTool, PolymarketTradeTool, RegisterSetTool,
-    SelectWeb3NetworkTool, SendEthTool, ToRawAmountTool, TokenLookupTool, Web3FunctionCallTool,
-    X402AgentInvokeTool, X402FetchTool, X402PostTool, X402RpcTool,
+    load_networks, load_tokens, AddressBookTool, ApproveAndSwapTool, BridgeUsdcTool,
+    BroadcastWeb3TxTool, DecodeCalldataTool, DexScreenerTool, GetTxReceiptTool, LimitOrderTool,
+    ListQueuedWeb3TxTool, PolymarketTradeTool, PortfolioTool, RegisterListTool, RegisterSetTool,
+    ResolveNameTool, SelectWeb3NetworkTool, SendEthTool, ToRawAmountTool, TokenLookupTool,
+    ValidationTool, WalletBalancesTool, Web3FunctionCallTool, X402AgentInvokeTool, X402FetchTool,
+    X402InspectTool, X402PostTool, X402RpcTool,
+};
+pub use social_media::{
+    DiscordLookupTool, DiscordTool, GithubPrTool, GithubUserTool, TwitterPostTool,
 };
-pub use social_media::{DiscordLookupTool, DiscordTool, GithubUserTool, TwitterPostTool};
 
 // Re-exports from individual tools
 pub use process_status::ProcessStatusTool;
 pub use qmd_memory_read::QmdMemoryReadTool;
 pub use qmd_memory_search::QmdMemorySearchTool;
 pub use web_fetch::WebFetchTool;
+pub use webhook::WebhookTool;
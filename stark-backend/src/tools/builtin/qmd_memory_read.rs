@@ -101,6 +101,10 @@ impl Tool for QmdMemoryReadTool {
         self.definition.clone()
     }
 
+    fn is_mutating(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: ReadParams = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -75,6 +75,10 @@ impl Tool for QmdMemorySearchTool {
         self.definition.clone()
     }
 
+    fn is_mutating(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: SearchParams = match serde_json::from_value(params) {
             Ok(p) => p,
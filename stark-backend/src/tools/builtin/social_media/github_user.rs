@@ -44,6 +44,10 @@ impl Tool for GithubUserTool {
         self.definition.clone()
     }
 
+    fn is_mutating(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, _params: Value, context: &ToolContext) -> ToolResult {
         // Check if already cached in context
         if let Some(cached_user) = context.extra.get("github_user") {
@@ -76,7 +80,8 @@ impl Tool for GithubUserTool {
         cmd.args(["api", "user", "--jq", ".login"])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .env("GH_TOKEN", &token);
+            .env("GH_TOKEN", &token)
+            .kill_on_drop(true);
 
         let output = cmd.output().await;
 
@@ -4,10 +4,14 @@
 
 mod discord;
 mod discord_lookup;
+mod github_pr;
 mod github_user;
 mod twitter_post;
 
 pub use discord::DiscordTool;
 pub use discord_lookup::DiscordLookupTool;
+pub use github_pr::GithubPrTool;
 pub use github_user::GithubUserTool;
 pub use twitter_post::TwitterPostTool;
+
+pub(crate) use twitter_post::oauth_header;
@@ -1,8 +1,13 @@
 //! Twitter posting tool using OAuth 1.0a
 //!
 //! Posts tweets on behalf of a user using their OAuth 1.0a credentials.
+//! Long texts are split into numbered threads, explicit threads are chained
+//! reply by reply, and images (URLs or workspace files) are uploaded through
+//! the v1.1 media endpoint before being attached to the first tweet.
 
 use crate::controllers::api_keys::ApiKeyId;
+use crate::tools::builtin::bash::path_jail::resolve_workspace_path;
+use crate::tools::builtin::web_fetch::validate_outbound_url;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -31,13 +36,58 @@ impl TwitterPostTool {
             "text".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "The text content of the tweet (max 280 characters)".to_string(),
+                description: "The text content of the tweet. Text over 280 characters is split into a numbered thread unless auto_split is false.".to_string(),
                 default: None,
                 items: None,
                 enum_values: None,
             },
         );
 
+        properties.insert(
+            "thread".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "Optional: Post a thread instead of a single tweet. Each entry is one tweet (max 280 characters), each replying to the previous. Use instead of 'text'.".to_string(),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Tweet text".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "media".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "Optional: Up to 4 images to attach to the first tweet, as image URLs or workspace file paths (png, jpg, gif, webp; max 5MB each)".to_string(),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Image URL or workspace path".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "auto_split".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Split text longer than 280 characters into a numbered thread (default: true). When false, long text is rejected.".to_string(),
+                default: Some(json!(true)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "reply_to".to_string(),
             PropertySchema {
@@ -63,11 +113,11 @@ impl TwitterPostTool {
         TwitterPostTool {
             definition: ToolDefinition {
                 name: "twitter_post".to_string(),
-                description: "Post a tweet to Twitter/X. Requires Twitter OAuth credentials to be configured in Settings > API Keys.".to_string(),
+                description: "Post a tweet or a thread to Twitter/X, optionally with images. Returns the tweet IDs and URLs. Requires Twitter OAuth credentials to be configured in Settings > API Keys.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
-                    required: vec!["text".to_string()],
+                    required: vec![],
                 },
                 group: ToolGroup::Messaging,
             },
@@ -97,85 +147,94 @@ impl TwitterPostTool {
         None
     }
 
-    /// Generate OAuth 1.0a Authorization header
-    fn generate_oauth_header(
+    /// Load all 4 OAuth credentials
+    fn load_credentials(&self, context: &ToolContext) -> Result<TwitterCredentials, String> {
+        let get = |key_id: ApiKeyId, name: &str| {
+            self.get_credential(key_id, context)
+                .ok_or_else(|| format!("{} not configured. Add it in Settings > API Keys.", name))
+        };
+        Ok(TwitterCredentials {
+            consumer_key: get(ApiKeyId::TwitterConsumerKey, "TWITTER_CONSUMER_KEY")?,
+            consumer_secret: get(ApiKeyId::TwitterConsumerSecret, "TWITTER_CONSUMER_SECRET")?,
+            access_token: get(ApiKeyId::TwitterAccessToken, "TWITTER_ACCESS_TOKEN")?,
+            access_token_secret: get(ApiKeyId::TwitterAccessTokenSecret, "TWITTER_ACCESS_TOKEN_SECRET")?,
+        })
+    }
+
+    fn auth_header(&self, credentials: &TwitterCredentials, method: &str, url: &str) -> String {
+        oauth_header(
+            method,
+            url,
+            &credentials.consumer_key,
+            &credentials.consumer_secret,
+            &credentials.access_token,
+            &credentials.access_token_secret,
+        )
+    }
+
+    /// Post one tweet via the v2 API
+    async fn post_tweet(
         &self,
-        method: &str,
-        url: &str,
-        consumer_key: &str,
-        consumer_secret: &str,
-        access_token: &str,
-        access_token_secret: &str,
-    ) -> String {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
+        client: &reqwest::Client,
+        credentials: &TwitterCredentials,
+        body: &Value,
+    ) -> Result<TwitterTweetData, TwitterFailure> {
+        let response = client
+            .post(TWEETS_URL)
+            .header("Authorization", self.auth_header(credentials, "POST", TWEETS_URL))
+            .header("Content-Type", "application/json")
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| TwitterFailure::error(format!("Failed to send request: {}", e)))?;
 
-        let nonce: String = (0..32)
-            .map(|_| format!("{:x}", rand::random::<u8>()))
-            .collect();
+        if !response.status().is_success() {
+            return Err(failure_from_response(response).await);
+        }
 
-        // OAuth parameters
-        let mut oauth_params: Vec<(&str, String)> = vec![
-            ("oauth_consumer_key", consumer_key.to_string()),
-            ("oauth_nonce", nonce.clone()),
-            ("oauth_signature_method", "HMAC-SHA1".to_string()),
-            ("oauth_timestamp", timestamp.clone()),
-            ("oauth_token", access_token.to_string()),
-            ("oauth_version", "1.0".to_string()),
-        ];
-
-        // Sort parameters
-        oauth_params.sort_by(|a, b| a.0.cmp(&b.0));
-
-        // Create parameter string
-        let param_string: String = oauth_params
-            .iter()
-            .map(|(k, v)| format!("{}={}", percent_encode(k), percent_encode(v)))
-            .collect::<Vec<_>>()
-            .join("&");
-
-        // Create signature base string
-        let base_string = format!(
-            "{}&{}&{}",
-            method.to_uppercase(),
-            percent_encode(url),
-            percent_encode(&param_string)
-        );
+        let response_text = response.text().await.unwrap_or_default();
+        match serde_json::from_str::<TwitterApiResponse>(&response_text) {
+            Ok(TwitterApiResponse { data: Some(data), .. }) => Ok(data),
+            Ok(_) => Err(TwitterFailure::error("Unexpected response format from Twitter API")),
+            Err(e) => Err(TwitterFailure::error(format!("Failed to parse Twitter response: {}", e))),
+        }
+    }
 
-        // Create signing key
-        let signing_key = format!(
-            "{}&{}",
-            percent_encode(consumer_secret),
-            percent_encode(access_token_secret)
-        );
+    /// Upload an image via the v1.1 media endpoint and return its media ID.
+    /// Multipart bodies aren't part of the OAuth signature, so only the OAuth params are signed.
+    async fn upload_media(
+        &self,
+        client: &reqwest::Client,
+        credentials: &TwitterCredentials,
+        bytes: Vec<u8>,
+        mime: &str,
+        file_name: String,
+    ) -> Result<String, TwitterFailure> {
+        let part = reqwest::multipart::Part::bytes(bytes)
+            .file_name(file_name)
+            .mime_str(mime)
+            .map_err(|e| TwitterFailure::error(format!("Invalid media type '{}': {}", mime, e)))?;
+        let form = reqwest::multipart::Form::new().part("media", part);
+
+        let response = client
+            .post(MEDIA_UPLOAD_URL)
+            .header("Authorization", self.auth_header(credentials, "POST", MEDIA_UPLOAD_URL))
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| TwitterFailure::error(format!("Failed to upload media: {}", e)))?;
 
-        // Generate HMAC-SHA1 signature
-        let mut mac = HmacSha1::new_from_slice(signing_key.as_bytes())
-            .expect("HMAC can take key of any size");
-        mac.update(base_string.as_bytes());
-        let signature = BASE64.encode(mac.finalize().into_bytes());
-
-        // Build Authorization header
-        let auth_params = [
-            ("oauth_consumer_key", consumer_key),
-            ("oauth_nonce", &nonce),
-            ("oauth_signature", &signature),
-            ("oauth_signature_method", "HMAC-SHA1"),
-            ("oauth_timestamp", &timestamp),
-            ("oauth_token", access_token),
-            ("oauth_version", "1.0"),
-        ];
-
-        let auth_string: String = auth_params
-            .iter()
-            .map(|(k, v)| format!("{}=\"{}\"", k, percent_encode(v)))
-            .collect::<Vec<_>>()
-            .join(", ");
+        if !response.status().is_success() {
+            let mut failure = failure_from_response(response).await;
+            failure.message = format!("Media upload failed: {}", failure.message);
+            return Err(failure);
+        }
 
-        format!("OAuth {}", auth_string)
+        response
+            .json::<MediaUploadResponse>()
+            .await
+            .map(|r| r.media_id_string)
+            .map_err(|e| TwitterFailure::error(format!("Failed to parse media upload response: {}", e)))
     }
 }
 
@@ -185,6 +244,87 @@ impl Default for TwitterPostTool {
     }
 }
 
+/// Generate an OAuth 1.0a Authorization header for a request without body
+/// or query parameters
+pub(crate) fn oauth_header(
+    method: &str,
+    url: &str,
+    consumer_key: &str,
+    consumer_secret: &str,
+    access_token: &str,
+    access_token_secret: &str,
+) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        .to_string();
+
+    let nonce: String = (0..32)
+        .map(|_| format!("{:x}", rand::random::<u8>()))
+        .collect();
+
+    // OAuth parameters
+    let mut oauth_params: Vec<(&str, String)> = vec![
+        ("oauth_consumer_key", consumer_key.to_string()),
+        ("oauth_nonce", nonce.clone()),
+        ("oauth_signature_method", "HMAC-SHA1".to_string()),
+        ("oauth_timestamp", timestamp.clone()),
+        ("oauth_token", access_token.to_string()),
+        ("oauth_version", "1.0".to_string()),
+    ];
+
+    // Sort parameters
+    oauth_params.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // Create parameter string
+    let param_string: String = oauth_params
+        .iter()
+        .map(|(k, v)| format!("{}={}", percent_encode(k), percent_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    // Create signature base string
+    let base_string = format!(
+        "{}&{}&{}",
+        method.to_uppercase(),
+        percent_encode(url),
+        percent_encode(&param_string)
+    );
+
+    // Create signing key
+    let signing_key = format!(
+        "{}&{}",
+        percent_encode(consumer_secret),
+        percent_encode(access_token_secret)
+    );
+
+    // Generate HMAC-SHA1 signature
+    let mut mac = HmacSha1::new_from_slice(signing_key.as_bytes())
+        .expect("HMAC can take key of any size");
+    mac.update(base_string.as_bytes());
+    let signature = BASE64.encode(mac.finalize().into_bytes());
+
+    // Build Authorization header
+    let auth_params = [
+        ("oauth_consumer_key", consumer_key),
+        ("oauth_nonce", &nonce),
+        ("oauth_signature", &signature),
+        ("oauth_signature_method", "HMAC-SHA1"),
+        ("oauth_timestamp", &timestamp),
+        ("oauth_token", access_token),
+        ("oauth_version", "1.0"),
+    ];
+
+    let auth_string: String = auth_params
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, percent_encode(v)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("OAuth {}", auth_string)
+}
+
 /// Percent-encode a string per OAuth spec (RFC 3986)
 fn percent_encode(s: &str) -> String {
     let mut result = String::new();
@@ -201,11 +341,38 @@ fn percent_encode(s: &str) -> String {
     result
 }
 
+/// Maximum characters per tweet
+const MAX_TWEET_CHARS: usize = 280;
+
+/// Images allowed on a single tweet
+const MAX_MEDIA_PER_TWEET: usize = 4;
+
+/// Limit for the simple (non-chunked) media upload endpoint
+const MAX_MEDIA_BYTES: usize = 5 * 1024 * 1024;
+
+/// Wait used when a 429 response has no rate-limit reset header
+const DEFAULT_RATE_LIMIT_WAIT_SECS: u64 = 60;
+
+/// Never ask the dispatcher to sleep longer than the 15 minute rate-limit window
+const MAX_RATE_LIMIT_WAIT_SECS: u64 = 900;
+
+const TWEETS_URL: &str = "https://api.twitter.com/2/tweets";
+const MEDIA_UPLOAD_URL: &str = "https://upload.twitter.com/1.1/media/upload.json";
+
+fn default_true() -> bool {
+    true
+}
+
 #[derive(Debug, Deserialize)]
 struct TwitterPostParams {
-    text: String,
+    text: Option<String>,
+    thread: Option<Vec<String>>,
+    #[serde(default)]
+    media: Vec<String>,
     reply_to: Option<String>,
     quote_tweet_id: Option<String>,
+    #[serde(default = "default_true")]
+    auto_split: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -227,6 +394,248 @@ struct TwitterApiError {
     error_type: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct MediaUploadResponse {
+    media_id_string: String,
+}
+
+/// The four OAuth 1.0a credentials
+struct TwitterCredentials {
+    consumer_key: String,
+    consumer_secret: String,
+    access_token: String,
+    access_token_secret: String,
+}
+
+/// A failed API call, with the wait the rate limit asks for (if any)
+struct TwitterFailure {
+    message: String,
+    retry_after_secs: Option<u64>,
+}
+
+impl TwitterFailure {
+    fn error(message: impl Into<String>) -> Self {
+        TwitterFailure { message: message.into(), retry_after_secs: None }
+    }
+}
+
+fn tweet_url(id: &str) -> String {
+    format!("https://twitter.com/i/web/status/{}", id)
+}
+
+/// Seconds until the rate limit resets, from `x-rate-limit-reset` (epoch seconds)
+/// or `retry-after`, clamped to a sane range
+fn rate_limit_wait(headers: &reqwest::header::HeaderMap, now_secs: u64) -> u64 {
+    let header_u64 = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    let wait = header_u64("x-rate-limit-reset")
+        .map(|reset| reset.saturating_sub(now_secs))
+        .or_else(|| header_u64("retry-after"))
+        .unwrap_or(DEFAULT_RATE_LIMIT_WAIT_SECS);
+    wait.clamp(1, MAX_RATE_LIMIT_WAIT_SECS)
+}
+
+/// Turn an error response into a failure, reading the rate-limit headers on 429
+async fn failure_from_response(response: reqwest::Response) -> TwitterFailure {
+    let status = response.status();
+    let retry_after_secs = (status.as_u16() == 429).then(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        rate_limit_wait(response.headers(), now)
+    });
+    let response_text = response.text().await.unwrap_or_default();
+
+    let message = match serde_json::from_str::<TwitterApiResponse>(&response_text) {
+        Ok(TwitterApiResponse { errors: Some(errors), .. }) => format!(
+            "Twitter API error: {}",
+            errors
+                .iter()
+                .map(|e| e.message.clone())
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
+        _ => format!("Twitter API error ({}): {}", status, response_text),
+    };
+    TwitterFailure { message, retry_after_secs }
+}
+
+/// Greedily pack space-separated words into chunks of at most `width` characters,
+/// hard-splitting words that are longer than a whole chunk
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for word in text.split(' ').filter(|w| !w.is_empty()) {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            chunks.push(word.drain(..width).collect());
+        }
+        let word: String = word.into_iter().collect();
+        let needed = if current.is_empty() { 0 } else { 1 } + word.chars().count();
+        if current.chars().count() + needed > width {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+/// Split a long text into numbered tweets ("... 1/3") that each fit the limit
+fn split_into_thread(text: &str, limit: usize) -> Vec<String> {
+    let mut digits = 1;
+    loop {
+        // Room for " N/M" with N and M up to `digits` wide
+        let chunks = wrap_words(text, limit - (2 * digits + 2));
+        if chunks.len() < 10usize.pow(digits as u32) {
+            let total = chunks.len();
+            return chunks
+                .into_iter()
+                .enumerate()
+                .map(|(i, chunk)| format!("{} {}/{}", chunk, i + 1, total))
+                .collect();
+        }
+        digits += 1;
+    }
+}
+
+/// The texts to post, in order: an explicit thread, or `text` (auto-split when too long)
+fn plan_tweets(params: &TwitterPostParams) -> Result<Vec<String>, String> {
+    match (&params.text, &params.thread) {
+        (Some(_), Some(_)) => Err("Provide either 'text' or 'thread', not both".to_string()),
+        (None, None) => Err("Either 'text' or 'thread' is required".to_string()),
+        (None, Some(thread)) => {
+            if thread.is_empty() {
+                return Err("Thread must contain at least one tweet".to_string());
+            }
+            for (i, text) in thread.iter().enumerate() {
+                let chars = text.trim().chars().count();
+                if chars == 0 {
+                    return Err(format!("Thread tweet {} is empty", i + 1));
+                }
+                if chars > MAX_TWEET_CHARS {
+                    return Err(format!(
+                        "Thread tweet {} exceeds {} characters (got {})",
+                        i + 1,
+                        MAX_TWEET_CHARS,
+                        chars
+                    ));
+                }
+            }
+            Ok(thread.iter().map(|t| t.trim().to_string()).collect())
+        }
+        (Some(text), None) => {
+            let text = text.trim();
+            if text.is_empty() {
+                return Err("Tweet text cannot be empty".to_string());
+            }
+            let chars = text.chars().count();
+            if chars <= MAX_TWEET_CHARS {
+                Ok(vec![text.to_string()])
+            } else if params.auto_split {
+                Ok(split_into_thread(text, MAX_TWEET_CHARS))
+            } else {
+                Err(format!("Tweet exceeds {} characters (got {})", MAX_TWEET_CHARS, chars))
+            }
+        }
+    }
+}
+
+/// MIME type for an image file extension supported by the upload endpoint
+fn image_mime_for_path(path: &str) -> Option<&'static str> {
+    let ext = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())?
+        .to_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Read an image from a URL or a workspace file: (bytes, mime type, file name)
+async fn load_media(
+    source: &str,
+    client: &reqwest::Client,
+    context: &ToolContext,
+) -> Result<(Vec<u8>, String, String), String> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let url = url::Url::parse(source).map_err(|e| format!("Invalid media URL '{}': {}", source, e))?;
+        validate_outbound_url(&url)?;
+        let response = client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|e| format!("Failed to download media '{}': {}", source, e))?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to download media '{}': HTTP {}", source, response.status()));
+        }
+        if response.content_length().unwrap_or(0) as usize > MAX_MEDIA_BYTES {
+            return Err(format!("Media '{}' is larger than {} bytes", source, MAX_MEDIA_BYTES));
+        }
+        let mime = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.split(';').next().unwrap_or("").trim().to_string())
+            .filter(|m| m.starts_with("image/"))
+            .or_else(|| image_mime_for_path(url.path()).map(String::from))
+            .ok_or_else(|| format!("Media '{}' is not an image", source))?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to download media '{}': {}", source, e))?;
+        if bytes.len() > MAX_MEDIA_BYTES {
+            return Err(format!("Media '{}' is larger than {} bytes", source, MAX_MEDIA_BYTES));
+        }
+        let name = url
+            .path_segments()
+            .and_then(|mut s| s.next_back())
+            .filter(|n| !n.is_empty())
+            .unwrap_or("image")
+            .to_string();
+        Ok((bytes.to_vec(), mime, name))
+    } else {
+        let path = resolve_workspace_path(context, source)?;
+        let mime = image_mime_for_path(source)
+            .ok_or_else(|| format!("Media '{}' must be a png, jpg, gif or webp image", source))?;
+        let metadata = std::fs::metadata(&path).map_err(|_| format!("Media file not found: {}", source))?;
+        if !metadata.is_file() {
+            return Err(format!("Media path is not a file: {}", source));
+        }
+        if metadata.len() as usize > MAX_MEDIA_BYTES {
+            return Err(format!("Media '{}' is larger than {} bytes", source, MAX_MEDIA_BYTES));
+        }
+        let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read media '{}': {}", source, e))?;
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("image")
+            .to_string();
+        Ok((bytes, mime.to_string(), name))
+    }
+}
+
 #[async_trait]
 impl Tool for TwitterPostTool {
     fn definition(&self) -> ToolDefinition {
@@ -239,137 +648,119 @@ impl Tool for TwitterPostTool {
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
 
-        // Validate tweet length
-        if params.text.is_empty() {
-            return ToolResult::error("Tweet text cannot be empty");
-        }
-        if params.text.chars().count() > 280 {
+        let tweets = match plan_tweets(&params) {
+            Ok(t) => t,
+            Err(e) => return ToolResult::error(e),
+        };
+        if params.media.len() > MAX_MEDIA_PER_TWEET {
             return ToolResult::error(format!(
-                "Tweet exceeds 280 characters (got {})",
-                params.text.chars().count()
+                "At most {} media attachments are allowed (got {})",
+                MAX_MEDIA_PER_TWEET,
+                params.media.len()
             ));
         }
 
-        // Get all 4 OAuth credentials
-        let consumer_key = match self.get_credential(ApiKeyId::TwitterConsumerKey, context) {
-            Some(k) => k,
-            None => {
-                return ToolResult::error(
-                    "TWITTER_CONSUMER_KEY not configured. Add it in Settings > API Keys.",
-                )
-            }
+        let credentials = match self.load_credentials(context) {
+            Ok(c) => c,
+            Err(e) => return ToolResult::error(e),
         };
 
-        let consumer_secret = match self.get_credential(ApiKeyId::TwitterConsumerSecret, context) {
-            Some(k) => k,
-            None => {
-                return ToolResult::error(
-                    "TWITTER_CONSUMER_SECRET not configured. Add it in Settings > API Keys.",
-                )
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(60))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        // Upload media first; it's attached to the first tweet
+        let mut media_ids = Vec::new();
+        for source in &params.media {
+            let (bytes, mime, name) = match load_media(source, &client, context).await {
+                Ok(m) => m,
+                Err(e) => return ToolResult::error(e),
+            };
+            match self.upload_media(&client, &credentials, bytes, &mime, name).await {
+                Ok(id) => media_ids.push(id),
+                Err(failure) => return failure_result(failure, &[], &tweets),
             }
-        };
+        }
 
-        let access_token = match self.get_credential(ApiKeyId::TwitterAccessToken, context) {
-            Some(k) => k,
-            None => {
-                return ToolResult::error(
-                    "TWITTER_ACCESS_TOKEN not configured. Add it in Settings > API Keys.",
-                )
-            }
-        };
+        let mut posted: Vec<TwitterTweetData> = Vec::new();
+        for (i, text) in tweets.iter().enumerate() {
+            let mut body = json!({ "text": text });
 
-        let access_token_secret =
-            match self.get_credential(ApiKeyId::TwitterAccessTokenSecret, context) {
-                Some(k) => k,
-                None => {
-                    return ToolResult::error(
-                        "TWITTER_ACCESS_TOKEN_SECRET not configured. Add it in Settings > API Keys.",
-                    )
+            // Each tweet replies to the previous one; the first uses reply_to if given
+            let in_reply_to = posted.last().map(|t| t.id.clone()).or_else(|| params.reply_to.clone());
+            if let Some(reply_to) = in_reply_to {
+                body["reply"] = json!({ "in_reply_to_tweet_id": reply_to });
+            }
+            if i == 0 {
+                if let Some(quote_id) = &params.quote_tweet_id {
+                    body["quote_tweet_id"] = json!(quote_id);
                 }
-            };
-
-        // Build request body
-        let mut body = json!({
-            "text": params.text
-        });
-
-        if let Some(reply_to) = &params.reply_to {
-            body["reply"] = json!({
-                "in_reply_to_tweet_id": reply_to
-            });
-        }
+                if !media_ids.is_empty() {
+                    body["media"] = json!({ "media_ids": media_ids });
+                }
+            }
 
-        if let Some(quote_id) = &params.quote_tweet_id {
-            body["quote_tweet_id"] = json!(quote_id);
+            match self.post_tweet(&client, &credentials, &body).await {
+                Ok(data) => posted.push(data),
+                Err(failure) => return failure_result(failure, &posted, &tweets),
+            }
         }
 
-        // Twitter API v2 endpoint
-        let url = "https://api.twitter.com/2/tweets";
-
-        // Generate OAuth header
-        let auth_header = self.generate_oauth_header(
-            "POST",
-            url,
-            &consumer_key,
-            &consumer_secret,
-            &access_token,
-            &access_token_secret,
-        );
+        let tweet_ids: Vec<&str> = posted.iter().map(|t| t.id.as_str()).collect();
+        let metadata = json!({
+            "tweet_ids": tweet_ids,
+            "urls": posted.iter().map(|t| tweet_url(&t.id)).collect::<Vec<_>>(),
+            "media_ids": media_ids,
+            "thread": posted.len() > 1,
+        });
 
-        // Make the request
-        let client = reqwest::Client::new();
-        let response = match client
-            .post(url)
-            .header("Authorization", auth_header)
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => return ToolResult::error(format!("Failed to send request: {}", e)),
+        // Single tweets keep their original response shape
+        let content = if let [data] = posted.as_slice() {
+            json!({
+                "success": true,
+                "tweet_id": data.id,
+                "text": data.text,
+                "url": tweet_url(&data.id)
+            })
+        } else {
+            json!({
+                "success": true,
+                "tweet_ids": tweet_ids,
+                "url": posted.first().map(|t| tweet_url(&t.id)),
+                "tweets": posted
+                    .iter()
+                    .map(|t| json!({ "tweet_id": t.id, "text": t.text, "url": tweet_url(&t.id) }))
+                    .collect::<Vec<_>>()
+            })
         };
+        ToolResult::success(content.to_string()).with_metadata(metadata)
+    }
+}
 
-        let status = response.status();
-        let response_text = response.text().await.unwrap_or_default();
-
-        if !status.is_success() {
-            // Try to parse error response
-            if let Ok(error_resp) = serde_json::from_str::<TwitterApiResponse>(&response_text) {
-                if let Some(errors) = error_resp.errors {
-                    let error_msg = errors
-                        .iter()
-                        .map(|e| e.message.clone())
-                        .collect::<Vec<_>>()
-                        .join("; ");
-                    return ToolResult::error(format!("Twitter API error: {}", error_msg));
-                }
-            }
-            return ToolResult::error(format!(
-                "Twitter API error ({}): {}",
-                status, response_text
-            ));
-        }
-
-        // Parse success response
-        match serde_json::from_str::<TwitterApiResponse>(&response_text) {
-            Ok(resp) => {
-                if let Some(data) = resp.data {
-                    ToolResult::success(
-                        json!({
-                            "success": true,
-                            "tweet_id": data.id,
-                            "text": data.text,
-                            "url": format!("https://twitter.com/i/web/status/{}", data.id)
-                        })
-                        .to_string(),
-                    )
-                } else {
-                    ToolResult::error("Unexpected response format from Twitter API")
-                }
-            }
-            Err(e) => ToolResult::error(format!("Failed to parse Twitter response: {}", e)),
-        }
+/// Error result for a failure partway through, telling the agent how to resume
+/// without re-posting tweets that already went out
+fn failure_result(failure: TwitterFailure, posted: &[TwitterTweetData], tweets: &[String]) -> ToolResult {
+    let remaining = &tweets[posted.len()..];
+    let mut message = failure.message;
+    if let Some(last) = posted.last() {
+        message.push_str(&format!(
+            "\n\nPosted {} of {} tweets (last tweet {}). Do not repost them: to finish the thread, \
+             call twitter_post with thread = the remaining {} tweet(s) and reply_to = \"{}\".",
+            posted.len(),
+            tweets.len(),
+            tweet_url(&last.id),
+            remaining.len(),
+            last.id
+        ));
+    }
+    let metadata = json!({
+        "tweet_ids": posted.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+        "remaining": remaining,
+    });
+    match failure.retry_after_secs {
+        Some(secs) => ToolResult::retryable_error(message, secs).with_metadata(metadata),
+        None => ToolResult::error(message).with_metadata(metadata),
     }
 }
 
@@ -389,6 +780,86 @@ mod tests {
         let tool = TwitterPostTool::new();
         let def = tool.definition();
         assert_eq!(def.name, "twitter_post");
-        assert!(def.input_schema.required.contains(&"text".to_string()));
+        assert!(def.input_schema.properties.contains_key("text"));
+        assert!(def.input_schema.properties.contains_key("thread"));
+    }
+
+    #[test]
+    fn test_split_into_numbered_thread() {
+        let text = (0..120).map(|i| format!("word{}", i)).collect::<Vec<_>>().join(" ");
+        let tweets = split_into_thread(&text, MAX_TWEET_CHARS);
+
+        assert!(tweets.len() > 1);
+        let total = tweets.len();
+        for (i, tweet) in tweets.iter().enumerate() {
+            assert!(tweet.chars().count() <= MAX_TWEET_CHARS, "{}", tweet);
+            assert!(tweet.ends_with(&format!(" {}/{}", i + 1, total)));
+        }
+        // Nothing lost and no word split across tweets
+        let rejoined: Vec<&str> = tweets
+            .iter()
+            .flat_map(|t| t.rsplit_once(' ').unwrap().0.split(' '))
+            .collect();
+        assert_eq!(rejoined.join(" "), text);
+
+        // Words longer than a tweet are hard-split
+        let long_word = "x".repeat(600);
+        assert!(split_into_thread(&long_word, MAX_TWEET_CHARS)
+            .iter()
+            .all(|t| t.chars().count() <= MAX_TWEET_CHARS));
+    }
+
+    #[test]
+    fn test_plan_tweets() {
+        let params = |v: Value| serde_json::from_value::<TwitterPostParams>(v).unwrap();
+
+        assert_eq!(plan_tweets(&params(json!({"text": "gm"}))).unwrap(), vec!["gm"]);
+        assert_eq!(
+            plan_tweets(&params(json!({"thread": ["one", "two"]}))).unwrap(),
+            vec!["one", "two"]
+        );
+        assert!(plan_tweets(&params(json!({"text": "a".repeat(300)}))).unwrap().len() > 1);
+        assert!(plan_tweets(&params(json!({"text": "a".repeat(300), "auto_split": false})))
+            .unwrap_err()
+            .contains("exceeds 280"));
+        assert!(plan_tweets(&params(json!({"thread": ["ok", "b".repeat(281)]})))
+            .unwrap_err()
+            .contains("Thread tweet 2"));
+        assert!(plan_tweets(&params(json!({"text": "x", "thread": ["y"]}))).is_err());
+        assert!(plan_tweets(&params(json!({}))).is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(rate_limit_wait(&headers, 1000), DEFAULT_RATE_LIMIT_WAIT_SECS);
+
+        headers.insert("retry-after", "30".parse().unwrap());
+        assert_eq!(rate_limit_wait(&headers, 1000), 30);
+
+        headers.insert("x-rate-limit-reset", "1120".parse().unwrap());
+        assert_eq!(rate_limit_wait(&headers, 1000), 120);
+
+        // Reset already passed, or absurdly far away
+        headers.insert("x-rate-limit-reset", "900".parse().unwrap());
+        assert_eq!(rate_limit_wait(&headers, 1000), 1);
+        headers.insert("x-rate-limit-reset", "999999".parse().unwrap());
+        assert_eq!(rate_limit_wait(&headers, 1000), MAX_RATE_LIMIT_WAIT_SECS);
+    }
+
+    #[test]
+    fn test_partial_thread_failure_is_resumable() {
+        let posted = vec![TwitterTweetData { id: "111".to_string(), text: "one 1/3".to_string() }];
+        let tweets = vec!["one 1/3".to_string(), "two 2/3".to_string(), "three 3/3".to_string()];
+        let failure = TwitterFailure {
+            message: "Twitter API error: Too Many Requests".to_string(),
+            retry_after_secs: Some(60),
+        };
+
+        let result = failure_result(failure, &posted, &tweets);
+        assert_eq!(result.retry_after_secs, Some(60));
+        let error = result.error.unwrap();
+        assert!(error.contains("Posted 1 of 3") && error.contains("reply_to = \"111\""));
+        assert_eq!(result.metadata.unwrap()["remaining"], json!(["two 2/3", "three 3/3"]));
     }
 }
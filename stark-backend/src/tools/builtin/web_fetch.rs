@@ -4,6 +4,7 @@ use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use async_trait::async_trait;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Deserializer};
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -25,6 +26,40 @@ where
     }
 }
 
+/// Response bytes downloaded before the body is cut off
+const DEFAULT_MAX_BYTES: usize = 2 * 1024 * 1024;
+const MAX_BYTES_LIMIT: usize = 10 * 1024 * 1024;
+
+const DEFAULT_TIMEOUT_SECS: usize = 30;
+const MAX_TIMEOUT_SECS: usize = 120;
+
+const DEFAULT_MAX_REDIRECTS: usize = 5;
+const MAX_REDIRECTS_LIMIT: usize = 10;
+
+/// Elements that never hold a page's main content
+const BOILERPLATE_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "canvas", "iframe", "nav", "footer",
+    "aside", "form", "button", "select",
+];
+
+/// Words in a class/id/role marking navigation, ads and other page chrome
+const BOILERPLATE_MARKERS: &[&str] = &[
+    "nav", "navbar", "navigation", "menu", "sidebar", "footer", "masthead", "breadcrumb",
+    "breadcrumbs", "ad", "ads", "advert", "advertisement", "sponsor", "sponsored", "promo",
+    "banner", "cookie", "cookies", "consent", "popup", "modal", "newsletter", "subscribe",
+    "social", "share", "sharing", "related", "comment", "comments", "contentinfo",
+];
+
+/// class/id/role attribute values of an opening tag
+static BOILERPLATE_ATTR_RE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r#"(?:class|id|role)\s*=\s*["']([^"']*)["']"#).unwrap());
+
+/// Void elements have no closing tag
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
 /// Cache entry for fetch results
 struct CacheEntry {
     result: ToolResult,
@@ -93,10 +128,10 @@ impl WebFetchTool {
             },
         );
         properties.insert(
-            "extract_mode".to_string(),
+            "mode".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Output format: 'markdown' for readable markdown, 'text' for plain text, 'raw' for unprocessed content".to_string(),
+                description: "Output format: 'markdown' converts the page's main content to markdown, 'text' returns its plain text (navigation, ads and other boilerplate stripped), 'raw' returns the unprocessed body".to_string(),
                 default: Some(json!("markdown")),
                 items: None,
                 enum_values: Some(vec![
@@ -117,6 +152,45 @@ impl WebFetchTool {
                 enum_values: None,
             },
         );
+        properties.insert(
+            "max_bytes".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!(
+                    "Maximum response bytes to download before truncating (default: {}, max: {})",
+                    DEFAULT_MAX_BYTES, MAX_BYTES_LIMIT
+                ),
+                default: Some(json!(DEFAULT_MAX_BYTES)),
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "timeout_secs".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!(
+                    "Request timeout in seconds (default: {}, max: {})",
+                    DEFAULT_TIMEOUT_SECS, MAX_TIMEOUT_SECS
+                ),
+                default: Some(json!(DEFAULT_TIMEOUT_SECS)),
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "max_redirects".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!(
+                    "Maximum redirects to follow (default: {}, max: {}). Every hop is checked against the private-host block.",
+                    DEFAULT_MAX_REDIRECTS, MAX_REDIRECTS_LIMIT
+                ),
+                default: Some(json!(DEFAULT_MAX_REDIRECTS)),
+                items: None,
+                enum_values: None,
+            },
+        );
         properties.insert(
             "headers".to_string(),
             PropertySchema {
@@ -157,7 +231,7 @@ impl WebFetchTool {
         WebFetchTool {
             definition: ToolDefinition {
                 name: "web_fetch".to_string(),
-                description: "Fetch content from a URL and extract readable text or markdown. Blocks private/internal URLs for security.".to_string(),
+                description: "Fetch content from a URL and extract the main content as markdown or text, with navigation and ads stripped. Binary responses are described, not returned. Blocks private/internal URLs for security.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -181,7 +255,14 @@ struct WebFetchParams {
     url: String,
     #[serde(alias = "max_length", default, deserialize_with = "deserialize_usize_lenient")]
     max_chars: Option<usize>,
-    extract_mode: Option<String>,
+    #[serde(alias = "extract_mode")]
+    mode: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_usize_lenient")]
+    max_bytes: Option<usize>,
+    #[serde(default, deserialize_with = "deserialize_usize_lenient")]
+    timeout_secs: Option<usize>,
+    #[serde(default, deserialize_with = "deserialize_usize_lenient")]
+    max_redirects: Option<usize>,
     // Legacy parameter support
     extract_text: Option<bool>,
     // Optional custom headers
@@ -217,6 +298,11 @@ impl Tool for WebFetchTool {
         self.definition.clone()
     }
 
+    /// Allow the longest per-request timeout the caller may ask for
+    fn timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(MAX_TIMEOUT_SECS as u64 + 30))
+    }
+
     async fn execute(&self, params: Value, _context: &ToolContext) -> ToolResult {
         let params: WebFetchParams = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -224,15 +310,24 @@ impl Tool for WebFetchTool {
         };
 
         let max_chars = params.max_chars.unwrap_or(50000);
+        let max_bytes = params.max_bytes.unwrap_or(DEFAULT_MAX_BYTES).clamp(1, MAX_BYTES_LIMIT);
+        let timeout_secs = params.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS).clamp(1, MAX_TIMEOUT_SECS);
+        let max_redirects = params.max_redirects.unwrap_or(DEFAULT_MAX_REDIRECTS).min(MAX_REDIRECTS_LIMIT);
 
-        // Handle extract_mode with legacy extract_text fallback
-        let extract_mode = params.extract_mode.unwrap_or_else(|| {
+        // Handle mode with legacy extract_text fallback
+        let extract_mode = params.mode.unwrap_or_else(|| {
             if params.extract_text == Some(false) {
                 "raw".to_string()
             } else {
                 "markdown".to_string()
             }
         });
+        if !matches!(extract_mode.as_str(), "raw" | "text" | "markdown") {
+            return ToolResult::error(format!(
+                "Invalid mode '{}'. Use 'markdown', 'text' or 'raw'.",
+                extract_mode
+            ));
+        }
 
         // Validate URL scheme
         if !params.url.starts_with("http://") && !params.url.starts_with("https://") {
@@ -245,15 +340,15 @@ impl Tool for WebFetchTool {
             Err(e) => return ToolResult::error(format!("Invalid URL: {}", e)),
         };
 
-        // Check for private/internal hostnames
-        if let Err(e) = validate_public_url(&url) {
+        // Check for private/internal hostnames and the outbound allowlist
+        if let Err(e) = validate_outbound_url(&url) {
             return ToolResult::error(e);
         }
 
         // Build cache key (include method - don't cache POST/PUT/PATCH/DELETE)
         let method = params.method.as_deref().unwrap_or("GET").to_uppercase();
         let should_cache = method == "GET";
-        let cache_key = format!("{}:{}:{}", params.url, extract_mode, max_chars);
+        let cache_key = format!("{}:{}:{}:{}", params.url, extract_mode, max_chars, max_bytes);
 
         // Check cache first (only for GET requests)
         if should_cache {
@@ -264,9 +359,9 @@ impl Tool for WebFetchTool {
         }
 
         let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
+            .timeout(Duration::from_secs(timeout_secs as u64))
             .user_agent("StarkBot/1.0 (Web Fetch Tool)")
-            .redirect(reqwest::redirect::Policy::limited(5))
+            .redirect(redirect_policy(max_redirects))
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
 
@@ -308,7 +403,11 @@ impl Tool for WebFetchTool {
         let response = match request.send().await {
             Ok(r) => r,
             Err(e) => {
-                let error_msg = format!("Failed to fetch URL: {}", e);
+                let error_msg = if e.is_timeout() {
+                    format!("Request timed out after {}s: {}", timeout_secs, params.url)
+                } else {
+                    format!("Failed to fetch URL: {}", e)
+                };
                 if is_reqwest_error_retryable(&e) {
                     let delay = retry_manager.record_error(&retry_key);
                     return ToolResult::retryable_error(error_msg, delay);
@@ -322,12 +421,8 @@ impl Tool for WebFetchTool {
 
         if !status.is_success() {
             // Extract the response body to include in the error message (truncate to avoid huge HTML pages)
-            let body = response.text().await.unwrap_or_default();
-            let truncated_body = if body.len() > 2000 {
-                format!("{}...\n[truncated, {} total bytes]", &body[..2000], body.len())
-            } else {
-                body
-            };
+            let (body, _) = read_body_capped(response, 2000).await.unwrap_or_default();
+            let truncated_body = String::from_utf8_lossy(&body).to_string();
             let error_msg = if truncated_body.is_empty() {
                 format!("HTTP error: {} for URL: {}", status, params.url)
             } else {
@@ -350,40 +445,79 @@ impl Tool for WebFetchTool {
             .unwrap_or("")
             .to_string();
 
-        let body = match response.text().await {
-            Ok(t) => t,
+        let content_length = response.content_length();
+
+        // Don't download binary bodies at all when the server says what they are
+        if !content_type.is_empty() && !is_textual_content_type(&content_type) {
+            return ToolResult::success(describe_binary(&content_type, content_length, &final_url))
+                .with_metadata(json!({
+                    "url": params.url,
+                    "final_url": final_url,
+                    "content_type": content_type,
+                    "binary": true,
+                    "content_length": content_length,
+                    "cached": false
+                }));
+        }
+
+        let (bytes, body_truncated) = match read_body_capped(response, max_bytes).await {
+            Ok(b) => b,
             Err(e) => return ToolResult::error(format!("Failed to read response body: {}", e)),
         };
 
+        // Untyped responses are sniffed for binary data
+        if content_type.is_empty() && looks_binary(&bytes) {
+            return ToolResult::success(describe_binary("application/octet-stream", content_length, &final_url))
+                .with_metadata(json!({
+                    "url": params.url,
+                    "final_url": final_url,
+                    "content_type": content_type,
+                    "binary": true,
+                    "content_length": content_length,
+                    "cached": false
+                }));
+        }
+
+        let body = String::from_utf8_lossy(&bytes).to_string();
         let original_length = body.len();
-        let is_html = content_type.contains("text/html");
+        let is_html = content_type.contains("text/html") || content_type.contains("application/xhtml");
 
         let content = match extract_mode.as_str() {
-            "raw" => body,
-            "text" if is_html => extract_text_from_html(&body),
-            "markdown" if is_html => extract_markdown_from_html(&body),
-            _ => body, // For non-HTML, return as-is
+            "text" if is_html => extract_text_from_html(&extract_main_content(&body)),
+            "markdown" if is_html => extract_markdown_from_html(&extract_main_content(&body)),
+            _ => body, // raw, or non-HTML returned as-is
         };
 
         // Truncate if necessary
         let truncated = content.len() > max_chars;
-        let final_content = if truncated {
+        let mut final_content = if truncated {
+            let mut end = max_chars;
+            while !content.is_char_boundary(end) {
+                end -= 1;
+            }
             format!(
                 "{}\n\n[Content truncated at {} characters. Original length: {} characters]",
-                &content[..max_chars],
+                &content[..end],
                 max_chars,
                 content.len()
             )
         } else {
             content
         };
+        if body_truncated {
+            final_content.push_str(&format!(
+                "\n\n[Download stopped at {} bytes (max_bytes); the page continues beyond this]",
+                max_bytes
+            ));
+        }
 
         let result = ToolResult::success(final_content).with_metadata(json!({
             "url": params.url,
             "final_url": final_url,
             "content_type": content_type,
             "extract_mode": extract_mode,
-            "truncated": truncated,
+            "truncated": truncated || body_truncated,
+            "body_truncated": body_truncated,
             "original_length": original_length,
             "cached": false
         }));
@@ -397,6 +531,33 @@ impl Tool for WebFetchTool {
     }
 }
 
+/// Validate a URL for the outbound HTTP tools: it must point to a public host and,
+/// when `STARK_OUTBOUND_URL_ALLOWLIST` is set, to an allowed one
+pub(super) fn validate_outbound_url(url: &url::Url) -> Result<(), String> {
+    validate_public_url(url)?;
+    check_allowlist(url, &crate::config::outbound_url_allowlist())
+}
+
+/// Check a URL's host against an allowlist (entries also match their subdomains)
+fn check_allowlist(url: &url::Url, allowlist: &[String]) -> Result<(), String> {
+    if allowlist.is_empty() {
+        return Ok(());
+    }
+    let host = url.host_str().unwrap_or("").to_lowercase();
+    let allowed = allowlist
+        .iter()
+        .any(|entry| host == *entry || host.ends_with(&format!(".{}", entry)));
+    if allowed {
+        Ok(())
+    } else {
+        Err(format!(
+            "Host '{}' is not in the outbound URL allowlist (allowed: {})",
+            host,
+            allowlist.join(", ")
+        ))
+    }
+}
+
 /// Validate that a URL points to a public host (not private/internal)
 fn validate_public_url(url: &url::Url) -> Result<(), String> {
     let host = url.host_str().ok_or("URL has no host")?;
@@ -463,6 +624,193 @@ fn is_private_ip(ip: IpAddr) -> bool {
     }
 }
 
+/// Follow at most `max_redirects` hops, re-validating each target so a public URL
+/// can't redirect into the private network
+fn redirect_policy(max_redirects: usize) -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() > max_redirects {
+            return attempt.error(format!("Too many redirects (max {})", max_redirects));
+        }
+        match validate_outbound_url(attempt.url()) {
+            Ok(()) => attempt.follow(),
+            Err(e) => attempt.error(format!("Redirect blocked: {}", e)),
+        }
+    })
+}
+
+/// Read at most `max_bytes` of a response body. Returns whether it was cut off.
+async fn read_body_capped(mut response: reqwest::Response, max_bytes: usize) -> Result<(Vec<u8>, bool), reqwest::Error> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let room = max_bytes - bytes.len();
+        if chunk.len() >= room {
+            bytes.extend_from_slice(&chunk[..room]);
+            // Exactly filling the budget only counts as truncation if more follows
+            let more = chunk.len() > room || response.chunk().await?.is_some_and(|c| !c.is_empty());
+            return Ok((bytes, more));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok((bytes, false))
+}
+
+/// Content types whose bodies are readable text
+fn is_textual_content_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_lowercase();
+    mime.starts_with("text/")
+        || ["json", "xml", "javascript", "ecmascript", "yaml", "csv", "graphql", "x-www-form-urlencoded"]
+            .iter()
+            .any(|t| mime.contains(t))
+}
+
+/// A NUL byte in the first KB is a reliable sign of binary data
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(1024).any(|b| *b == 0)
+}
+
+fn describe_binary(content_type: &str, content_length: Option<u64>, url: &str) -> String {
+    let size = content_length
+        .map(|n| format!("{} bytes", n))
+        .unwrap_or_else(|| "unknown size".to_string());
+    format!(
+        "Binary content ({}, {}) at {} was not downloaded. Use exec with curl -o to save it to the workspace if needed.",
+        content_type.split(';').next().unwrap_or(content_type).trim(),
+        size,
+        url
+    )
+}
+
+/// Lowercased tag name and whether the tag is a closing tag, from the text between `<` and `>`
+fn parse_tag(tag: &str) -> (String, bool) {
+    let closing = tag.starts_with('/');
+    let name = tag
+        .trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    (name, closing)
+}
+
+/// Whether an opening tag is page chrome (nav, ads, cookie banners, ...) by name or class/id/role
+fn is_boilerplate_tag(name: &str, tag: &str) -> bool {
+    if BOILERPLATE_TAGS.contains(&name) {
+        return true;
+    }
+    let attrs = tag.to_lowercase();
+    if attrs.contains("aria-hidden=\"true\"") || attrs.split_whitespace().any(|a| a == "hidden") {
+        return true;
+    }
+    BOILERPLATE_ATTR_RE.captures_iter(&attrs).any(|caps| {
+        caps[1]
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| BOILERPLATE_MARKERS.contains(&word))
+    })
+}
+
+/// Remove comments and boilerplate elements (with everything inside them)
+fn strip_boilerplate(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    // Name and nesting depth of the element currently being skipped
+    let mut skipping: Option<(String, usize)> = None;
+    let mut pos = 0;
+
+    while let Some(rel) = html[pos..].find('<') {
+        let start = pos + rel;
+        if skipping.is_none() {
+            out.push_str(&html[pos..start]);
+        }
+        if html[start..].starts_with("<!--") {
+            pos = html[start..].find("-->").map(|e| start + e + 3).unwrap_or(html.len());
+            continue;
+        }
+        let end = match html[start..].find('>') {
+            Some(e) => start + e + 1,
+            None => {
+                pos = html.len();
+                break;
+            }
+        };
+        let tag = &html[start + 1..end - 1];
+        let (name, closing) = parse_tag(tag);
+        let self_closing = tag.ends_with('/') || VOID_TAGS.contains(&name.as_str());
+
+        match skipping {
+            Some((ref skip_name, ref mut depth)) if *skip_name == name => {
+                if closing {
+                    *depth -= 1;
+                    if *depth == 0 {
+                        skipping = None;
+                    }
+                } else if !self_closing {
+                    *depth += 1;
+                }
+            }
+            Some(_) => {}
+            None if !closing && !self_closing && is_boilerplate_tag(&name, tag) => {
+                skipping = Some((name, 1));
+            }
+            None => out.push_str(&html[start..end]),
+        }
+        pos = end;
+    }
+    if skipping.is_none() {
+        out.push_str(&html[pos..]);
+    }
+    out
+}
+
+/// Inner HTML of the first `<name>` element, matching nested tags of the same name
+fn element_inner<'a>(html: &'a str, html_lower: &str, name: &str) -> Option<&'a str> {
+    let open = format!("<{}", name);
+    let mut search = 0;
+    let start = loop {
+        let idx = search + html_lower[search..].find(&open)?;
+        let next = html_lower[idx + open.len()..].chars().next();
+        if matches!(next, Some('>') | Some(' ') | Some('\n') | Some('\t') | Some('\r')) {
+            break idx;
+        }
+        search = idx + open.len();
+    };
+    let content_start = start + html_lower[start..].find('>')? + 1;
+
+    let close = format!("</{}", name);
+    let mut depth = 1;
+    let mut pos = content_start;
+    while depth > 0 {
+        let next_open = html_lower[pos..].find(&open).map(|i| pos + i);
+        let next_close = pos + html_lower[pos..].find(&close)?;
+        match next_open {
+            Some(o) if o < next_close => {
+                depth += 1;
+                pos = o + open.len();
+            }
+            _ => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&html[content_start..next_close]);
+                }
+                pos = next_close + close.len();
+            }
+        }
+    }
+    None
+}
+
+/// Readability-style main content: boilerplate stripped, then narrowed to the
+/// `<article>`, `<main>` or `<body>` element when the page has one
+fn extract_main_content(html: &str) -> String {
+    let stripped = strip_boilerplate(html);
+    // ASCII lowercasing keeps byte offsets aligned with the original
+    let lower = stripped.to_ascii_lowercase();
+    for name in ["article", "main", "body"] {
+        if let Some(inner) = element_inner(&stripped, &lower, name).filter(|i| !i.trim().is_empty()) {
+            return inner.to_string();
+        }
+    }
+    stripped
+}
+
 /// Extract readable markdown from HTML
 fn extract_markdown_from_html(html: &str) -> String {
     let mut result = String::new();
@@ -858,4 +1206,68 @@ mod tests {
         assert!(!is_private_ip("8.8.8.8".parse().unwrap()));
         assert!(!is_private_ip("1.1.1.1".parse().unwrap()));
     }
+
+    #[test]
+    fn test_main_content_extraction_strips_boilerplate() {
+        let html = r#"
+        <html><body>
+            <nav><ul><li><a href="/">Home</a></li></ul></nav>
+            <div class="cookie-banner">We use cookies</div>
+            <!-- tracking <div>comment</div> -->
+            <main>
+                <div class="sidebar"><div>Trending</div></div>
+                <article>
+                    <h1>Real Title</h1>
+                    <p>The <b>actual</b> story.</p>
+                    <div id="share-buttons">Share on X</div>
+                    <p>More story.<br/>Line two.</p>
+                </article>
+            </main>
+            <footer>Copyright</footer>
+            <script>var ads = "<div>";</script>
+        </body></html>
+        "#;
+
+        let md = extract_markdown_from_html(&extract_main_content(html));
+        assert!(md.contains("# Real Title"));
+        assert!(md.contains("The **actual** story."));
+        assert!(md.contains("More story."));
+        for boilerplate in ["Home", "cookies", "comment", "Trending", "Share on X", "Copyright", "var ads"] {
+            assert!(!md.contains(boilerplate), "{} leaked into: {}", boilerplate, md);
+        }
+
+        // Without article/main the body is used
+        let text = extract_text_from_html(&extract_main_content(
+            "<body><header><nav>Menu</nav><h1>Site</h1></header><div><div>Nested</div> text</div></body>",
+        ));
+        assert!(text.contains("Site") && text.contains("Nested text"));
+        assert!(!text.contains("Menu"));
+    }
+
+    #[test]
+    fn test_content_type_handling() {
+        assert!(is_textual_content_type("text/html; charset=utf-8"));
+        assert!(is_textual_content_type("application/json"));
+        assert!(is_textual_content_type("application/rss+xml"));
+        assert!(!is_textual_content_type("image/png"));
+        assert!(!is_textual_content_type("application/pdf"));
+
+        assert!(looks_binary(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"));
+        assert!(!looks_binary(b"plain text"));
+
+        let description = describe_binary("image/png", Some(2048), "https://example.com/a.png");
+        assert!(description.starts_with("Binary content (image/png, 2048 bytes)"));
+    }
+
+    #[test]
+    fn test_outbound_allowlist() {
+        let allowlist = vec!["hooks.zapier.com".to_string(), "example.org".to_string()];
+        let check = |u: &str| check_allowlist(&url::Url::parse(u).unwrap(), &allowlist);
+
+        assert!(check("https://hooks.zapier.com/hooks/catch/1/abc").is_ok());
+        assert!(check("https://api.example.org/v1").is_ok());
+        assert!(check("https://notexample.org/").is_err());
+        assert!(check("https://evil.com/?example.org").unwrap_err().contains("not in the outbound URL allowlist"));
+        assert!(check_allowlist(&url::Url::parse("https://any.host/").unwrap(), &[]).is_ok());
+    }
 }
@@ -0,0 +1,393 @@
+//! Outbound webhook tool
+//!
+//! Sends a JSON body to a user-configured endpoint (Zapier, n8n, custom
+//! services) with optional custom headers and an HMAC-SHA256 signature so
+//! the receiver can verify the request came from this bot. Secrets are never
+//! passed inline: header values reference stored API keys by name with
+//! `{api_key:NAME}` placeholders, and the signing secret is an API key name.
+
+use super::web_fetch::validate_outbound_url;
+use crate::tools::http_retry::{is_reqwest_error_retryable, HttpRetryManager};
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Default header carrying the signature (same scheme as GitHub webhooks)
+const DEFAULT_SIGNATURE_HEADER: &str = "X-Signature-256";
+
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const MAX_TIMEOUT_SECS: u64 = 120;
+
+/// Response bodies longer than this are truncated in the result
+const MAX_RESPONSE_CHARS: usize = 10000;
+
+/// Webhook tool for POSTing structured data to external endpoints
+pub struct WebhookTool {
+    definition: ToolDefinition,
+}
+
+impl WebhookTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+        properties.insert(
+            "url".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Webhook endpoint URL (HTTPS recommended). Private/internal hosts are blocked.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "method".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "HTTP method (default: POST)".to_string(),
+                default: Some(json!("POST")),
+                items: None,
+                enum_values: Some(vec!["POST".to_string(), "PUT".to_string()]),
+            },
+        );
+        properties.insert(
+            "body".to_string(),
+            PropertySchema {
+                schema_type: "object".to_string(),
+                description: "JSON payload to send".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "headers".to_string(),
+            PropertySchema {
+                schema_type: "object".to_string(),
+                description: "Extra HTTP headers. Use {api_key:NAME} in a value to insert a stored API key, e.g. {\"Authorization\": \"Bearer {api_key:N8N_TOKEN}\"}.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "signing_key".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Name of a stored API key to use as the HMAC-SHA256 secret. The signature of the exact request body is sent as 'sha256=<hex>'.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "signature_header".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: format!("Header for the HMAC signature (default: {})", DEFAULT_SIGNATURE_HEADER),
+                default: Some(json!(DEFAULT_SIGNATURE_HEADER)),
+                items: None,
+                enum_values: None,
+            },
+        );
+        properties.insert(
+            "timeout_secs".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: format!("Request timeout in seconds (default: {}, max: {})", DEFAULT_TIMEOUT_SECS, MAX_TIMEOUT_SECS),
+                default: Some(json!(DEFAULT_TIMEOUT_SECS)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        WebhookTool {
+            definition: ToolDefinition {
+                name: "webhook".to_string(),
+                description: "Send a JSON payload to a webhook endpoint (Zapier, n8n, custom services) with optional auth headers from stored API keys and an HMAC signature. Returns the response status and body.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["url".to_string(), "body".to_string()],
+                },
+                group: ToolGroup::Web,
+            },
+        }
+    }
+}
+
+impl Default for WebhookTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WebhookParams {
+    url: String,
+    method: Option<String>,
+    body: Value,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    signing_key: Option<String>,
+    signature_header: Option<String>,
+    timeout_secs: Option<u64>,
+}
+
+/// Replace `{api_key:NAME}` placeholders with API keys from the context
+fn resolve_header_value(value: &str, context: &ToolContext) -> Result<String, String> {
+    let re = regex::Regex::new(r"\{api_key:([A-Za-z0-9_]+)\}").unwrap();
+    let mut missing = Vec::new();
+    let resolved = re.replace_all(value, |caps: &regex::Captures| {
+        context.get_api_key(&caps[1]).unwrap_or_else(|| {
+            missing.push(caps[1].to_string());
+            String::new()
+        })
+    });
+    if missing.is_empty() {
+        Ok(resolved.to_string())
+    } else {
+        Err(format!(
+            "API key(s) not configured: {}. Add them in Settings > API Keys.",
+            missing.join(", ")
+        ))
+    }
+}
+
+/// `sha256=<hex>` HMAC of the request body
+fn sign_body(secret: &str, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn truncate_body(body: &str) -> (String, bool) {
+    if body.len() <= MAX_RESPONSE_CHARS {
+        return (body.to_string(), false);
+    }
+    let mut end = MAX_RESPONSE_CHARS;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    (
+        format!("{}\n\n[Response truncated at {} of {} bytes]", &body[..end], end, body.len()),
+        true,
+    )
+}
+
+#[async_trait]
+impl Tool for WebhookTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    /// Allow the longest per-request timeout the caller may ask for
+    fn timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(MAX_TIMEOUT_SECS + 30))
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: WebhookParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let method = params.method.as_deref().unwrap_or("POST").to_uppercase();
+        if method != "POST" && method != "PUT" {
+            return ToolResult::error(format!("Unsupported method '{}'. Use POST or PUT.", method));
+        }
+
+        if !params.url.starts_with("http://") && !params.url.starts_with("https://") {
+            return ToolResult::error("URL must start with http:// or https://");
+        }
+        let url = match url::Url::parse(&params.url) {
+            Ok(u) => u,
+            Err(e) => return ToolResult::error(format!("Invalid URL: {}", e)),
+        };
+        if let Err(e) = validate_outbound_url(&url) {
+            return ToolResult::error(e);
+        }
+
+        // Serialize once so the signature covers exactly the bytes sent
+        let body = match serde_json::to_vec(&params.body) {
+            Ok(b) => b,
+            Err(e) => return ToolResult::error(format!("Failed to serialize body: {}", e)),
+        };
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+        for (name, value) in &params.headers {
+            let value = match resolve_header_value(value, context) {
+                Ok(v) => v,
+                Err(e) => return ToolResult::error(e),
+            };
+            let header_name = match reqwest::header::HeaderName::from_bytes(name.as_bytes()) {
+                Ok(n) => n,
+                Err(_) => return ToolResult::error(format!("Invalid header name '{}'", name)),
+            };
+            let header_value = match reqwest::header::HeaderValue::from_str(&value) {
+                Ok(v) => v,
+                Err(_) => return ToolResult::error(format!("Invalid value for header '{}'", name)),
+            };
+            headers.insert(header_name, header_value);
+        }
+
+        let signature_header = params
+            .signature_header
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SIGNATURE_HEADER.to_string());
+        if let Some(ref key_name) = params.signing_key {
+            let secret = match context.get_api_key(key_name) {
+                Some(s) if !s.is_empty() => s,
+                _ => {
+                    return ToolResult::error(format!(
+                        "Signing key '{}' is not configured. Add it in Settings > API Keys.",
+                        key_name
+                    ))
+                }
+            };
+            let header_name = match reqwest::header::HeaderName::from_bytes(signature_header.as_bytes()) {
+                Ok(n) => n,
+                Err(_) => return ToolResult::error(format!("Invalid signature header '{}'", signature_header)),
+            };
+            let signature = sign_body(&secret, &body);
+            headers.insert(header_name, reqwest::header::HeaderValue::from_str(&signature).unwrap());
+        }
+
+        let timeout_secs = params.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS).clamp(1, MAX_TIMEOUT_SECS);
+        // No redirects: a redirected POST could deliver the payload to a host that wasn't checked
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
+            .user_agent("StarkBot/1.0 (Webhook Tool)")
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        let request = if method == "PUT" {
+            client.put(url.clone())
+        } else {
+            client.post(url.clone())
+        };
+
+        let retry_key = url.host_str().unwrap_or("unknown").to_string();
+        let retry_manager = HttpRetryManager::global();
+        let start = Instant::now();
+
+        let response = match request.headers(headers).body(body).send().await {
+            Ok(r) => r,
+            Err(e) => {
+                let error_msg = if e.is_timeout() {
+                    format!("Webhook request timed out after {}s", timeout_secs)
+                } else {
+                    format!("Webhook request failed: {}", e)
+                };
+                if is_reqwest_error_retryable(&e) {
+                    let delay = retry_manager.record_error(&retry_key);
+                    return ToolResult::retryable_error(error_msg, delay);
+                }
+                return ToolResult::error(error_msg);
+            }
+        };
+
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+        let response_body = response.text().await.unwrap_or_default();
+        let duration_ms = start.elapsed().as_millis() as u64;
+        let (response_body, truncated) = truncate_body(&response_body);
+
+        let metadata = json!({
+            "url": params.url,
+            "method": method,
+            "status": status.as_u16(),
+            "content_type": content_type,
+            "signed": params.signing_key.is_some(),
+            "signature_header": params.signing_key.as_ref().map(|_| signature_header),
+            "truncated": truncated,
+            "duration_ms": duration_ms,
+        });
+
+        let summary = if response_body.is_empty() {
+            format!("{} {} -> {}", method, params.url, status)
+        } else {
+            format!("{} {} -> {}\n\n{}", method, params.url, status, response_body)
+        };
+
+        if status.is_success() {
+            retry_manager.record_success(&retry_key);
+            return ToolResult::success(summary).with_metadata(metadata);
+        }
+        if status.is_redirection() {
+            return ToolResult::error(format!(
+                "{}\n\nWebhook endpoint redirected; redirects are not followed. Use the final URL.",
+                summary
+            ))
+            .with_metadata(metadata);
+        }
+        if HttpRetryManager::is_retryable_status(status.as_u16()) {
+            let delay = retry_manager.record_error(&retry_key);
+            return ToolResult::retryable_error(summary, delay).with_metadata(metadata);
+        }
+        ToolResult::error(summary).with_metadata(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_body() {
+        // Matches `echo -n '{"a":1}' | openssl dgst -sha256 -hmac secret`
+        assert_eq!(
+            sign_body("secret", br#"{"a":1}"#),
+            "sha256=aa9e2e3575f5d7098b6caccd790888c36d5fdb63342a73bada2d6a51747a8494"
+        );
+    }
+
+    #[test]
+    fn test_resolve_header_value_from_api_keys() {
+        let context = ToolContext::new().with_api_key("N8N_TOKEN", "tok_123".to_string());
+
+        assert_eq!(
+            resolve_header_value("Bearer {api_key:N8N_TOKEN}", &context).unwrap(),
+            "Bearer tok_123"
+        );
+        assert_eq!(resolve_header_value("plain", &context).unwrap(), "plain");
+        let err = resolve_header_value("{api_key:MISSING}", &context).unwrap_err();
+        assert!(err.contains("MISSING"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_private_hosts_and_bad_method() {
+        let tool = WebhookTool::new();
+        let context = ToolContext::new();
+
+        let result = tool
+            .execute(json!({"url": "http://127.0.0.1:9000/hook", "body": {"a": 1}}), &context)
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("blocked"));
+
+        let result = tool
+            .execute(json!({"url": "https://example.com/hook", "body": {}, "method": "DELETE"}), &context)
+            .await;
+        assert!(result.content.contains("Unsupported method"));
+    }
+}
@@ -0,0 +1,99 @@
+//! Tool call memoization within a single dispatch
+//!
+//! Agents sometimes repeat an identical call in one loop (re-reading the same
+//! file, re-checking the same price). Successful results of read-only tools
+//! are cached here keyed by (tool name, arguments hash), and a repeat returns
+//! the cached result with a note instead of running the tool again.
+//!
+//! The cache lives in the `ToolContext`, so it is scoped to one dispatch.
+//! Any mutating tool call clears it, since it may have changed what the
+//! read-only tools would now return.
+
+use crate::tools::types::ToolResult;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// Prepended to a result served from the cache
+pub const CACHED_NOTE: &str =
+    "[Identical call already made in this execution - returning the cached result. Don't repeat it.]";
+
+/// Cached read-only tool results for one dispatch
+#[derive(Debug, Clone, Default)]
+pub struct ToolCallCache {
+    inner: Arc<Mutex<HashMap<(String, u64), ToolResult>>>,
+}
+
+impl ToolCallCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash of the arguments. serde_json objects are sorted maps, so key
+    /// order in the call doesn't matter.
+    fn args_hash(params: &Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        params.to_string().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The cached result of an identical earlier call, with a note
+    pub fn get(&self, tool_name: &str, params: &Value) -> Option<ToolResult> {
+        let key = (tool_name.to_string(), Self::args_hash(params));
+        let mut result = self.inner.lock().ok()?.get(&key).cloned()?;
+        result.content = format!("{}\n\n{}", CACHED_NOTE, result.content);
+        Some(result)
+    }
+
+    /// Cache a result. Only successful results are kept, so failures are retried.
+    pub fn insert(&self, tool_name: &str, params: &Value, result: &ToolResult) {
+        if !result.success {
+            return;
+        }
+        if let Ok(mut cache) = self.inner.lock() {
+            cache.insert((tool_name.to_string(), Self::args_hash(params)), result.clone());
+        }
+    }
+
+    /// Drop every cached result
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.inner.lock() {
+            cache.clear();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().map(|cache| cache.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_cache_hits_identical_calls_only() {
+        let cache = ToolCallCache::new();
+        cache.insert("read_file", &json!({"path": "a.txt", "limit": 10}), &ToolResult::success("contents"));
+        cache.insert("read_file", &json!({"path": "missing.txt"}), &ToolResult::error("not found"));
+
+        let hit = cache.get("read_file", &json!({"limit": 10, "path": "a.txt"})).unwrap();
+        assert!(hit.content.starts_with(CACHED_NOTE));
+        assert!(hit.content.ends_with("contents"));
+
+        assert!(cache.get("read_file", &json!({"path": "b.txt"})).is_none());
+        assert!(cache.get("grep", &json!({"path": "a.txt", "limit": 10})).is_none());
+        assert!(cache.get("read_file", &json!({"path": "missing.txt"})).is_none());
+
+        // Clones share the cache, like the clones of a ToolContext
+        cache.clone().clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! Config hot-reload
+//!
+//! Reloads tokens.ron, networks.ron and rpc_providers.ron without a restart,
+//! either on request (`POST /api/config/reload`) or from a watcher that polls
+//! the files for changes. Each file is validated before its in-memory map is
+//! swapped, so a malformed file leaves the previous config in place.
+
+use crate::tools::builtin::cryptocurrency::{network_lookup, token_lookup};
+use crate::tools::rpc_config;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+/// Config directory the server was started with
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Reloadable configs and their files
+const RELOADABLE: &[(&str, &str)] = &[
+    ("tokens", "tokens.ron"),
+    ("networks", "networks.ron"),
+    ("rpc_providers", "rpc_providers.ron"),
+];
+
+/// Outcome of reloading one config
+#[derive(Debug, Clone, Serialize)]
+pub struct ReloadResult {
+    pub config: String,
+    pub success: bool,
+    /// Number of entries loaded (tokens, networks or providers)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Remember the config directory for later reloads. Called once at startup.
+pub fn set_config_dir(config_dir: &Path) {
+    let _ = CONFIG_DIR.set(config_dir.to_path_buf());
+}
+
+/// The config directory, if set at startup
+pub fn config_dir() -> Option<&'static Path> {
+    CONFIG_DIR.get().map(|p| p.as_path())
+}
+
+/// Reload one config by name
+fn reload(config_dir: &Path, config: &str) -> ReloadResult {
+    let result = match config {
+        "tokens" => token_lookup::reload_tokens(config_dir),
+        "networks" => network_lookup::reload_networks(config_dir),
+        "rpc_providers" => rpc_config::reload_rpc_providers(config_dir),
+        other => Err(format!("Unknown config: {}", other)),
+    };
+
+    if let Err(e) = &result {
+        log::error!("[config_reload] Keeping current {} config: {}", config, e);
+    }
+
+    ReloadResult {
+        config: config.to_string(),
+        success: result.is_ok(),
+        count: result.as_ref().ok().copied(),
+        error: result.err(),
+    }
+}
+
+/// Reload every reloadable config. Each is validated and swapped
+/// independently, so one bad file doesn't block the others.
+pub fn reload_all(config_dir: &Path) -> Vec<ReloadResult> {
+    RELOADABLE
+        .iter()
+        .map(|(config, _)| reload(config_dir, config))
+        .collect()
+}
+
+fn modified_times(config_dir: &Path) -> HashMap<&'static str, SystemTime> {
+    RELOADABLE
+        .iter()
+        .filter_map(|(config, file)| {
+            let modified = std::fs::metadata(config_dir.join(file)).and_then(|m| m.modified()).ok()?;
+            Some((*config, modified))
+        })
+        .collect()
+}
+
+/// Poll the config files every `interval` and reload any that changed
+pub fn spawn_watcher(config_dir: PathBuf, interval: Duration) {
+    log::info!(
+        "[config_reload] Watching {:?} for config changes every {}s",
+        config_dir,
+        interval.as_secs()
+    );
+
+    tokio::spawn(async move {
+        let mut last = modified_times(&config_dir);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            let current = modified_times(&config_dir);
+            for (config, modified) in &current {
+                if last.get(config) != Some(modified) {
+                    log::info!("[config_reload] {} changed, reloading", config);
+                    reload(&config_dir, config);
+                }
+            }
+            last = current;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_reports_each_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("tokens.ron"), "not ron").unwrap();
+
+        let results = reload_all(dir.path());
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| !r.success && r.count.is_none()));
+        assert!(results[0].error.as_ref().unwrap().contains("Failed to parse"));
+        assert!(results[1].error.as_ref().unwrap().contains("not found"));
+    }
+}
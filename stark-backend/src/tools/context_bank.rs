@@ -28,10 +28,81 @@ pub struct ContextBankItem {
     pub label: Option<String>,
 }
 
-/// Context bank storage - thread-safe collection of detected terms
+impl ContextBankItem {
+    /// Size counted against the bank's byte budget
+    pub fn size_bytes(&self) -> usize {
+        self.value.len() + self.item_type.len() + self.label.as_ref().map_or(0, |l| l.len())
+    }
+}
+
+/// An item with its recency, for LRU eviction
+#[derive(Debug, Clone)]
+struct BankEntry {
+    item: ContextBankItem,
+    /// Tick of the last add or reference
+    last_used: u64,
+    /// Turn the item was last added or referenced in
+    turn: u64,
+}
+
+#[derive(Debug)]
+struct BankState {
+    entries: Vec<BankEntry>,
+    tick: u64,
+    turn: u64,
+    max_items: usize,
+    max_bytes: usize,
+    /// Items evicted or dropped over the bank's lifetime
+    evicted: usize,
+}
+
+impl BankState {
+    fn bytes(&self) -> usize {
+        self.entries.iter().map(|e| e.item.size_bytes()).sum()
+    }
+
+    fn over_budget(&self) -> bool {
+        self.entries.len() > self.max_items || self.bytes() > self.max_bytes
+    }
+
+    /// Add or refresh an item in the current turn, then evict down to budget.
+    /// Least recently used items from earlier turns go first; items of the
+    /// current turn are never evicted for each other, so once they alone
+    /// fill the budget a newly added item is dropped instead.
+    fn insert(&mut self, item: ContextBankItem) {
+        self.tick += 1;
+        let (tick, turn) = (self.tick, self.turn);
+
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.item == item) {
+            entry.last_used = tick;
+            entry.turn = turn;
+            return;
+        }
+        self.entries.push(BankEntry { item, last_used: tick, turn });
+
+        while self.over_budget() {
+            let lru = self
+                .entries
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.turn < turn)
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(i, _)| i);
+            let index = lru.unwrap_or(self.entries.len() - 1);
+            self.entries.remove(index);
+            self.evicted += 1;
+            if lru.is_none() {
+                break;
+            }
+        }
+    }
+}
+
+/// Context bank storage - thread-safe collection of detected terms, bounded
+/// by an item count and a byte budget
 #[derive(Debug, Clone)]
 pub struct ContextBank {
-    inner: Arc<RwLock<HashSet<ContextBankItem>>>,
+    inner: Arc<RwLock<BankState>>,
 }
 
 impl Default for ContextBank {
@@ -41,36 +112,69 @@ impl Default for ContextBank {
 }
 
 impl ContextBank {
+    /// Create a bank with the configured limits
     pub fn new() -> Self {
+        Self::with_limits(
+            crate::config::context_bank_max_items(),
+            crate::config::context_bank_max_bytes(),
+        )
+    }
+
+    pub fn with_limits(max_items: usize, max_bytes: usize) -> Self {
         Self {
-            inner: Arc::new(RwLock::new(HashSet::new())),
+            inner: Arc::new(RwLock::new(BankState {
+                entries: Vec::new(),
+                tick: 0,
+                turn: 0,
+                max_items,
+                max_bytes,
+                evicted: 0,
+            })),
         }
     }
 
-    /// Add an item to the context bank
+    /// Add an item to the context bank in the current turn
     pub fn add(&self, item: ContextBankItem) {
         if let Ok(mut bank) = self.inner.write() {
             bank.insert(item);
         }
     }
 
-    /// Add multiple items at once
+    /// Add the items scanned from one message, starting a new turn
     pub fn add_all(&self, items: Vec<ContextBankItem>) {
         if let Ok(mut bank) = self.inner.write() {
+            bank.turn += 1;
             for item in items {
                 bank.insert(item);
             }
         }
     }
 
-    /// Get all items in the context bank
+    /// Mark an item as referenced, keeping it through the current turn
+    pub fn touch(&self, item: &ContextBankItem) {
+        if let Ok(mut bank) = self.inner.write() {
+            bank.tick += 1;
+            let (tick, turn) = (bank.tick, bank.turn);
+            if let Some(entry) = bank.entries.iter_mut().find(|e| &e.item == item) {
+                entry.last_used = tick;
+                entry.turn = turn;
+            }
+        }
+    }
+
+    /// Get all items in the context bank, oldest first
     pub fn items(&self) -> Vec<ContextBankItem> {
         self.inner
             .read()
-            .map(|bank| bank.iter().cloned().collect())
+            .map(|bank| bank.entries.iter().map(|e| e.item.clone()).collect())
             .unwrap_or_default()
     }
 
+    /// Total size of the items in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.inner.read().map(|bank| bank.bytes()).unwrap_or(0)
+    }
+
     /// Get items formatted for display/agent context
     pub fn format_for_agent(&self) -> Option<String> {
         let items = self.items();
@@ -150,7 +254,7 @@ impl ContextBank {
     /// Clear the context bank
     pub fn clear(&self) {
         if let Ok(mut bank) = self.inner.write() {
-            bank.clear();
+            bank.entries.clear();
         }
     }
 
@@ -158,7 +262,7 @@ impl ContextBank {
     pub fn is_empty(&self) -> bool {
         self.inner
             .read()
-            .map(|bank| bank.is_empty())
+            .map(|bank| bank.entries.is_empty())
             .unwrap_or(true)
     }
 
@@ -166,15 +270,24 @@ impl ContextBank {
     pub fn len(&self) -> usize {
         self.inner
             .read()
-            .map(|bank| bank.len())
+            .map(|bank| bank.entries.len())
             .unwrap_or(0)
     }
 
     /// Convert to JSON for frontend
     pub fn to_json(&self) -> serde_json::Value {
+        let (max_items, max_bytes, evicted) = self
+            .inner
+            .read()
+            .map(|bank| (bank.max_items, bank.max_bytes, bank.evicted))
+            .unwrap_or_default();
         serde_json::json!({
             "items": self.items(),
             "count": self.len(),
+            "bytes": self.size_bytes(),
+            "max_items": max_items,
+            "max_bytes": max_bytes,
+            "evicted": evicted,
             "formatted": self.format_for_agent()
         })
     }
@@ -403,4 +516,56 @@ mod tests {
         assert!(formatted.is_some());
         assert!(formatted.unwrap().contains("0x123"));
     }
+
+    fn number(value: &str) -> ContextBankItem {
+        ContextBankItem {
+            value: value.to_string(),
+            item_type: "number".to_string(),
+            label: None,
+        }
+    }
+
+    fn values(bank: &ContextBank) -> Vec<String> {
+        bank.items().into_iter().map(|i| i.value).collect()
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_from_earlier_turns() {
+        let bank = ContextBank::with_limits(3, usize::MAX);
+        bank.add_all(vec![number("1"), number("2"), number("3")]);
+
+        // "1" is mentioned again, so "2" is now the least recently used
+        bank.add_all(vec![number("1"), number("4")]);
+        assert_eq!(values(&bank), vec!["1", "3", "4"]);
+
+        bank.add_all(vec![number("5")]);
+        assert_eq!(values(&bank), vec!["1", "4", "5"]);
+        assert_eq!(bank.to_json()["evicted"], 2);
+    }
+
+    #[test]
+    fn test_keeps_current_turn_items_under_pressure() {
+        let bank = ContextBank::with_limits(2, usize::MAX);
+        bank.add_all(vec![number("1")]);
+
+        // The current turn's items outlive earlier ones; overflow is dropped
+        bank.add_all(vec![number("2"), number("3"), number("4")]);
+        assert_eq!(values(&bank), vec!["2", "3"]);
+
+        // A referenced item survives into the next turn's eviction order
+        bank.touch(&number("2"));
+        bank.add_all(vec![number("5")]);
+        assert_eq!(values(&bank), vec!["2", "5"]);
+    }
+
+    #[test]
+    fn test_byte_budget() {
+        // "number" plus a 4-byte value is 10 bytes per item
+        let bank = ContextBank::with_limits(usize::MAX, 25);
+        bank.add_all(vec![number("1000"), number("2000")]);
+        bank.add_all(vec![number("3000")]);
+
+        assert_eq!(values(&bank), vec!["2000", "3000"]);
+        assert_eq!(bank.size_bytes(), 20);
+    }
 }
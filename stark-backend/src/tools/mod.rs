@@ -1,18 +1,24 @@
+pub mod abis;
 pub mod builtin;
+pub mod call_cache;
+pub mod config_reload;
 pub mod context_bank;
 pub mod http_retry;
 pub mod presets;
+pub mod redaction;
 pub mod register;
 pub mod registry;
 pub mod rpc_config;
 pub mod types;
 
+pub use call_cache::ToolCallCache;
 pub use context_bank::{scan_input, ContextBank, ContextBankItem};
+pub use redaction::SecretRedactor;
 pub use register::{PresetOrCustom, RegisterStore};
 pub use registry::{Tool, ToolRegistry};
 pub use types::{
-    PropertySchema, ToolConfig, ToolContext, ToolDefinition, ToolExecution, ToolGroup,
-    ToolInputSchema, ToolProfile, ToolResult,
+    PropertySchema, ToolConfig, ToolContext, ToolCost, ToolDefinition, ToolErrorCode,
+    ToolExecution, ToolGroup, ToolInputSchema, ToolProfile, ToolResult,
 };
 
 use std::sync::Arc;
@@ -22,6 +28,7 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     // System tools (always available)
     registry.register(Arc::new(builtin::SubagentTool::new()));
     registry.register(Arc::new(builtin::SubagentStatusTool::new()));
+    registry.register(Arc::new(builtin::SubagentCollectTool::new()));
     registry.register(Arc::new(builtin::SetAgentSubtypeTool::new()));
     registry.register(Arc::new(builtin::AskUserTool::new()));
     registry.register(Arc::new(builtin::SayToUserTool::new()));
@@ -35,29 +42,49 @@ fn register_all_tools(registry: &mut ToolRegistry) {
 
     // Web tools (shared)
     registry.register(Arc::new(builtin::WebFetchTool::new()));
+    registry.register(Arc::new(builtin::WebhookTool::new()));
 
     // Finance tools (crypto/DeFi operations)
     registry.register(Arc::new(builtin::X402RpcTool::new()));
     registry.register(Arc::new(builtin::X402FetchTool::new()));
+    registry.register(Arc::new(builtin::X402InspectTool::new()));
     registry.register(Arc::new(builtin::X402AgentInvokeTool::new()));
     registry.register(Arc::new(builtin::X402PostTool::new()));
     // send_eth for simple native ETH transfers (no ABI needed)
     registry.register(Arc::new(builtin::SendEthTool::new()));
     registry.register(Arc::new(builtin::BroadcastWeb3TxTool::new()));
     registry.register(Arc::new(builtin::ListQueuedWeb3TxTool::new()));
+    // Receipt status and decoded event logs for broadcast transactions
+    registry.register(Arc::new(builtin::GetTxReceiptTool::new()));
     registry.register(Arc::new(builtin::Web3FunctionCallTool::new()));
     registry.register(Arc::new(builtin::DecodeCalldataTool::new()));
     registry.register(Arc::new(builtin::TokenLookupTool::new()));
+    // Multi-token balance check in one batched RPC call
+    registry.register(Arc::new(builtin::WalletBalancesTool::new()));
+    // ENS / basename resolution
+    registry.register(Arc::new(builtin::ResolveNameTool::new()));
+    // Named transfer recipients
+    registry.register(Arc::new(builtin::AddressBookTool::new()));
     registry.register(Arc::new(builtin::ToRawAmountTool::new()));
     registry.register(Arc::new(builtin::RegisterSetTool::new()));
+    // Register inspection/reset for debugging presets
+    registry.register(Arc::new(builtin::RegisterListTool::new()));
     // Network selection for chain-specific operations
     registry.register(Arc::new(builtin::SelectWeb3NetworkTool::new()));
     // Polymarket prediction market trading
     registry.register(Arc::new(builtin::PolymarketTradeTool::new()));
     // DexScreener market data
     registry.register(Arc::new(builtin::DexScreenerTool::new()));
+    // Wallet balance history and P&L from scheduled snapshots
+    registry.register(Arc::new(builtin::PortfolioTool::new()));
+    // Price-triggered DEX swaps (checked by the scheduler)
+    registry.register(Arc::new(builtin::LimitOrderTool::new()));
+    // Swap with allowance check and chained approval
+    registry.register(Arc::new(builtin::ApproveAndSwapTool::new()));
     // Cross-chain USDC bridging via Across Protocol
     registry.register(Arc::new(builtin::BridgeUsdcTool::new()));
+    // EIP-8004 validation request/response flow
+    registry.register(Arc::new(builtin::ValidationTool::new()));
 
     // Filesystem tools (read-only, shared)
     registry.register(Arc::new(builtin::ReadFileTool::new()));
@@ -73,6 +100,7 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::GlobTool::new()));
     registry.register(Arc::new(builtin::GitTool::new()));
     registry.register(Arc::new(builtin::GithubUserTool::new()));
+    registry.register(Arc::new(builtin::GithubPrTool::new()));
 
     // Advanced development tools (scoped commits, deployment, PR quality)
     registry.register(Arc::new(builtin::CommitterTool::new()));
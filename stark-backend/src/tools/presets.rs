@@ -2,11 +2,18 @@
 //!
 //! Presets define how tools should build requests from register values,
 //! preventing hallucination of URLs, params, and other critical data.
+//!
+//! Web3 presets can also be added at runtime through the API. Those are
+//! stored in the database, cached here, and take precedence over the file
+//! presets with the same name.
 
-use serde::Deserialize;
+use crate::db::Database;
+use crate::tools::register::validate_register_key;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
-use std::sync::OnceLock;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
 
 /// Global preset storage (loaded once at startup)
 static FETCH_PRESETS: OnceLock<HashMap<String, FetchPreset>> = OnceLock::new();
@@ -14,6 +21,9 @@ static RPC_PRESETS: OnceLock<HashMap<String, RpcPreset>> = OnceLock::new();
 static WEB3_PRESETS: OnceLock<HashMap<String, Web3Preset>> = OnceLock::new();
 static NETWORKS: OnceLock<HashMap<String, NetworkConfig>> = OnceLock::new();
 
+/// Web3 presets saved through the API (mirrors the `web3_presets` table)
+static DB_WEB3_PRESETS: Lazy<RwLock<HashMap<String, Web3Preset>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
 /// x402_fetch preset configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct FetchPreset {
@@ -40,7 +50,7 @@ pub struct RpcPreset {
 }
 
 /// web3_function_call preset configuration
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Web3Preset {
     /// ABI file name (without .json)
     pub abi: String,
@@ -186,8 +196,16 @@ pub fn get_rpc_preset(name: &str) -> Option<RpcPreset> {
         .and_then(|p| p.get(name).cloned())
 }
 
-/// Get a Web3 preset by name
+/// Get a Web3 preset by name, preferring one saved through the API
 pub fn get_web3_preset(name: &str) -> Option<Web3Preset> {
+    if let Some(preset) = DB_WEB3_PRESETS.read().ok().and_then(|p| p.get(name).cloned()) {
+        return Some(preset);
+    }
+    get_file_web3_preset(name)
+}
+
+/// Get a Web3 preset from the config file (or defaults) only
+pub fn get_file_web3_preset(name: &str) -> Option<Web3Preset> {
     WEB3_PRESETS.get()
         .or_else(|| {
             let _ = WEB3_PRESETS.set(default_web3_presets());
@@ -215,13 +233,111 @@ pub fn list_rpc_presets() -> Vec<String> {
         .unwrap_or_else(|| vec!["gas_price".to_string(), "get_balance".to_string(), "get_nonce".to_string(), "block_number".to_string()])
 }
 
-/// List available Web3 preset names
+/// List available Web3 preset names (API-saved and file presets)
 pub fn list_web3_presets() -> Vec<String> {
+    let mut names: Vec<String> = DB_WEB3_PRESETS
+        .read()
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default();
+    for name in list_file_web3_presets() {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names.sort();
+    names
+}
+
+/// List Web3 preset names from the config file (or defaults) only
+pub fn list_file_web3_presets() -> Vec<String> {
     WEB3_PRESETS.get()
         .map(|p| p.keys().cloned().collect())
         .unwrap_or_else(|| vec!["weth_deposit".to_string(), "weth_withdraw".to_string()])
 }
 
+/// Load the Web3 presets saved through the API (call once at startup)
+pub fn load_db_web3_presets(db: &Database) {
+    match db.list_web3_presets() {
+        Ok(stored) => {
+            log::info!("[presets] Loaded {} Web3 presets from database", stored.len());
+            if let Ok(mut presets) = DB_WEB3_PRESETS.write() {
+                *presets = stored.into_iter().map(|p| (p.name, p.preset)).collect();
+            }
+        }
+        Err(e) => log::error!("[presets] Failed to load Web3 presets from database: {}", e),
+    }
+}
+
+/// Update the cached copy of an API-saved Web3 preset (`None` removes it)
+pub fn cache_db_web3_preset(name: &str, preset: Option<Web3Preset>) {
+    if let Ok(mut presets) = DB_WEB3_PRESETS.write() {
+        match preset {
+            Some(p) => {
+                presets.insert(name.to_string(), p);
+            }
+            None => {
+                presets.remove(name);
+            }
+        }
+    }
+}
+
+/// Directory ABI files are loaded from
+pub fn abis_dir() -> PathBuf {
+    std::env::current_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("abis")
+}
+
+/// Check a Web3 preset before saving: its ABI file must exist and define
+/// the function, register names must be valid register keys, and it needs
+/// a contract address per network or a contract register (not both).
+pub fn validate_web3_preset(name: &str, preset: &Web3Preset, abis_dir: &Path) -> Result<(), String> {
+    validate_register_key(name).map_err(|e| format!("Invalid preset name '{}': {}", name, e))?;
+
+    if preset.function.trim().is_empty() {
+        return Err("function is required".to_string());
+    }
+    if preset.description.trim().is_empty() {
+        return Err("description is required".to_string());
+    }
+
+    // ABI must exist (uploaded or in abis_dir) and contain the function
+    crate::tools::abis::validate_abi_name(&preset.abi)?;
+    let abi_file = crate::tools::abis::load_abi(abis_dir, &preset.abi)
+        .map_err(|_| format!("ABI '{}' not found in {:?}", preset.abi, abis_dir))?;
+    let has_function = abi_file.function_names().contains(&preset.function);
+    if !has_function {
+        return Err(format!("ABI '{}' has no function '{}'", preset.abi, preset.function));
+    }
+
+    // Contract source: fixed addresses or a register, exactly one
+    match (&preset.contract_register, preset.contracts.is_empty()) {
+        (Some(_), false) => return Err("Use either contracts or contract_register, not both".to_string()),
+        (None, true) => return Err("Either contracts or contract_register is required".to_string()),
+        _ => {}
+    }
+    for (network, address) in &preset.contracts {
+        let valid = address.len() == 42
+            && address.starts_with("0x")
+            && address[2..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(format!("Invalid contract address for '{}': {}", network, address));
+        }
+    }
+
+    let registers = preset
+        .contract_register
+        .iter()
+        .chain(preset.params_registers.iter())
+        .chain(preset.value_register.iter());
+    for register in registers {
+        validate_register_key(register).map_err(|e| format!("Invalid register '{}': {}", register, e))?;
+    }
+
+    Ok(())
+}
+
 /// List available network names
 pub fn list_networks() -> Vec<String> {
     get_networks().keys().cloned().collect()
@@ -359,3 +475,47 @@ pub fn get_explorer_url(network: &str) -> String {
         .map(|n| n.explorer.clone())
         .unwrap_or_else(|| "https://basescan.org".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset() -> Web3Preset {
+        Web3Preset {
+            abi: "token".to_string(),
+            contracts: HashMap::new(),
+            contract_register: Some("token_address".to_string()),
+            function: "approve".to_string(),
+            params_registers: vec!["spender_address".to_string(), "approve_amount".to_string()],
+            value_register: None,
+            static_params: vec![],
+            description: "Approve a spender".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_web3_preset() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("token.json"),
+            r#"{"name": "token", "abi": [{"type": "function", "name": "approve", "inputs": []}]}"#,
+        )
+        .unwrap();
+
+        assert!(validate_web3_preset("token_approve", &preset(), dir.path()).is_ok());
+
+        let missing_abi = Web3Preset { abi: "missing".to_string(), ..preset() };
+        assert!(validate_web3_preset("x", &missing_abi, dir.path()).unwrap_err().contains("not found"));
+
+        let missing_fn = Web3Preset { function: "transfer".to_string(), ..preset() };
+        assert!(validate_web3_preset("x", &missing_fn, dir.path()).unwrap_err().contains("no function"));
+
+        let bad_register = Web3Preset { params_registers: vec!["spender-address".to_string()], ..preset() };
+        assert!(validate_web3_preset("x", &bad_register, dir.path()).unwrap_err().contains("spender-address"));
+
+        let no_contract = Web3Preset { contract_register: None, ..preset() };
+        assert!(validate_web3_preset("x", &no_contract, dir.path()).is_err());
+
+        assert!(validate_web3_preset("bad name", &preset(), dir.path()).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Secret redaction for tool output and logs
+//!
+//! Tools run shell commands and HTTP requests with the burner wallet key and
+//! the external API keys in their environment, so anything they print can
+//! contain those values. A `SecretRedactor` holds the configured secrets and
+//! replaces every occurrence with `***REDACTED***`. The dispatcher builds one
+//! per dispatch, attaches it to the `ToolContext` (applied to every
+//! `ToolResult` by the registry) and installs it for the process-wide logger.
+
+use crate::db::Database;
+use crate::tools::types::ToolResult;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+
+/// Replacement text for redacted secrets
+pub const REDACTED: &str = "***REDACTED***";
+
+/// Values shorter than this are ignored; redacting them would mangle ordinary output
+const MIN_SECRET_LEN: usize = 8;
+
+/// Redactor used by the logger and background processes
+static ACTIVE: Lazy<RwLock<SecretRedactor>> = Lazy::new(|| RwLock::new(SecretRedactor::default()));
+
+/// A set of secret values to scrub from text
+#[derive(Clone, Default)]
+pub struct SecretRedactor {
+    /// Longest first, so a secret containing another is replaced whole
+    secrets: Arc<Vec<String>>,
+}
+
+impl std::fmt::Debug for SecretRedactor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretRedactor")
+            .field("secrets", &self.secrets.len())
+            .finish()
+    }
+}
+
+impl SecretRedactor {
+    pub fn new<I, S>(secrets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut values: Vec<String> = Vec::new();
+        for secret in secrets {
+            let secret: String = secret.into();
+            let secret = secret.trim();
+            if secret.len() < MIN_SECRET_LEN {
+                continue;
+            }
+            // Hex keys are printed both with and without the 0x prefix
+            let variants = match secret.strip_prefix("0x") {
+                Some(bare) if bare.len() >= MIN_SECRET_LEN => vec![secret.to_string(), bare.to_string()],
+                _ => vec![secret.to_string()],
+            };
+            for variant in variants {
+                if !values.contains(&variant) {
+                    values.push(variant);
+                }
+            }
+        }
+        values.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        SecretRedactor { secrets: Arc::new(values) }
+    }
+
+    /// Build the redactor for a dispatch: the burner and named wallet private
+    /// keys and Google OAuth client secret from config plus every value in the
+    /// external API keys table
+    pub fn load(db: &Database) -> Self {
+        let mut secrets: Vec<String> = crate::config::burner_wallet_private_key().into_iter().collect();
+        secrets.extend(
+            crate::config::named_wallets()
+                .into_iter()
+                .map(|(_, source)| source)
+                .filter(|source| !source.eq_ignore_ascii_case("keystore")),
+        );
+        secrets.extend(crate::config::google_oauth_client().map(|(_, secret)| secret));
+        match db.list_api_keys() {
+            Ok(keys) => secrets.extend(keys.into_iter().map(|k| k.api_key)),
+            Err(e) => log::warn!("[REDACTION] Failed to load API keys for redaction: {}", e),
+        }
+        Self::new(secrets)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    /// Replace every known secret in `text`
+    pub fn redact(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for secret in self.secrets.iter() {
+            if out.contains(secret.as_str()) {
+                out = out.replace(secret.as_str(), REDACTED);
+            }
+        }
+        out
+    }
+
+    /// Redact every string inside a JSON value in place
+    pub fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::String(s) if self.secrets.iter().any(|secret| s.contains(secret.as_str())) => {
+                *s = self.redact(s);
+            }
+            Value::Array(items) => items.iter_mut().for_each(|v| self.redact_value(v)),
+            Value::Object(map) => map.values_mut().for_each(|v| self.redact_value(v)),
+            _ => {}
+        }
+    }
+
+    /// Redact a tool result's content, error and metadata
+    pub fn redact_result(&self, mut result: ToolResult) -> ToolResult {
+        if self.is_empty() {
+            return result;
+        }
+        result.content = self.redact(&result.content);
+        result.error = result.error.map(|e| self.redact(&e));
+        if let Some(ref mut metadata) = result.metadata {
+            self.redact_value(metadata);
+        }
+        result
+    }
+}
+
+/// Make `redactor` the one applied to log records and background process output
+pub fn install(redactor: &SecretRedactor) {
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = redactor.clone();
+    }
+}
+
+/// The most recently installed redactor
+pub fn active() -> SecretRedactor {
+    ACTIVE.read().map(|r| r.clone()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    #[test]
+    fn test_redacts_prefixed_and_bare_keys() {
+        let redactor = SecretRedactor::new([KEY, "ghp_abcdefghijklmnop", "short", ""]);
+
+        let text = format!("key={} bare={} gh=ghp_abcdefghijklmnop short", KEY, &KEY[2..]);
+        let redacted = redactor.redact(&text);
+        assert_eq!(
+            redacted,
+            format!("key={} bare={} gh={} short", REDACTED, REDACTED, REDACTED)
+        );
+        assert!(!redacted.contains(&KEY[2..]));
+    }
+
+    #[test]
+    fn test_redact_result_covers_content_error_and_metadata() {
+        let redactor = SecretRedactor::new(["sk-secret-value-123"]);
+        let result = ToolResult::error("auth failed for sk-secret-value-123")
+            .with_metadata(json!({"env": ["API=sk-secret-value-123"], "code": 1}));
+
+        let result = redactor.redact_result(result);
+        assert!(!result.content.contains("sk-secret"));
+        assert!(!result.error.as_deref().unwrap_or("").contains("sk-secret"));
+        assert_eq!(result.metadata.unwrap(), json!({"env": [format!("API={}", REDACTED)], "code": 1}));
+    }
+}
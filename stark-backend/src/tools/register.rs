@@ -20,12 +20,13 @@
 //! let to = quote.get("to").unwrap();
 //! ```
 
-use ethers::prelude::*;
 use serde::{Deserialize, Deserializer};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+use crate::tools::types::ToolContext;
+
 /// A monad for tool parameters that can either use a preset (reading from registers)
 /// or custom raw parameters provided by the agent.
 ///
@@ -111,6 +112,21 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for PresetOrCustom<T> {
     }
 }
 
+/// Validate a register key name: 1-64 alphanumeric or underscore characters
+pub fn validate_register_key(key: &str) -> Result<(), String> {
+    if key.is_empty() {
+        return Err("Register key cannot be empty".to_string());
+    }
+    if key.len() > 64 {
+        return Err("Register key too long (max 64 characters)".to_string());
+    }
+    // Allow alphanumeric and underscore only
+    if !key.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err("Register key must contain only alphanumeric characters and underscores".to_string());
+    }
+    Ok(())
+}
+
 /// Intrinsic registers that are lazily computed when accessed.
 /// These are always available without needing explicit tool calls.
 pub enum IntrinsicRegister {
@@ -126,13 +142,15 @@ impl IntrinsicRegister {
         }
     }
 
-    /// Resolve the intrinsic value
-    pub fn resolve(&self) -> Option<Value> {
+    /// Resolve the intrinsic for a tool running in `context`. `wallet` is the
+    /// tool's `wallet` param, if it has one: `wallet_address` is the address
+    /// of the wallet the tool signs with (see `ToolContext::wallet_name`).
+    pub fn resolve(&self, context: &ToolContext, wallet: Option<&str>) -> Option<Value> {
         match self {
             Self::WalletAddress => {
-                let pk = crate::config::burner_wallet_private_key()?;
-                let wallet: LocalWallet = pk.parse().ok()?;
-                Some(json!(format!("{:?}", wallet.address())))
+                let name = context.wallet_name(wallet);
+                let address = crate::wallet::cached_address(name.as_deref())?;
+                Some(json!(format!("{:?}", address)))
             }
         }
     }
@@ -194,16 +212,27 @@ impl RegisterStore {
 
     /// Get a value from the register
     ///
-    /// Returns None if the key doesn't exist.
-    /// Falls back to intrinsic resolution for special registers like `wallet_address`.
+    /// Returns None if the key doesn't exist. Intrinsics like `wallet_address`
+    /// depend on the tool's context; read them with `get_or_intrinsic`.
     pub fn get(&self, key: &str) -> Option<Value> {
+        self.get_entry(key).map(|entry| entry.value)
+    }
+
+    /// Get a value from the register, falling back to intrinsic resolution
+    /// for special registers like `wallet_address`
+    pub fn get_or_intrinsic(
+        &self,
+        key: &str,
+        context: &ToolContext,
+        wallet: Option<&str>,
+    ) -> Option<Value> {
         // First check explicit registers
         if let Some(entry) = self.get_entry(key) {
             return Some(entry.value);
         }
 
         // Fall back to intrinsic resolution
-        IntrinsicRegister::from_name(key).and_then(|i| i.resolve())
+        IntrinsicRegister::from_name(key).and_then(|i| i.resolve(context, wallet))
     }
 
     /// Get the full entry (value + metadata) from the register
@@ -212,7 +241,12 @@ impl RegisterStore {
     }
 
     /// Get entry with metadata, falling back to intrinsic if not set
-    pub fn get_entry_or_intrinsic(&self, key: &str) -> Option<RegisterEntry> {
+    pub fn get_entry_or_intrinsic(
+        &self,
+        key: &str,
+        context: &ToolContext,
+        wallet: Option<&str>,
+    ) -> Option<RegisterEntry> {
         // Check explicit first
         if let Some(entry) = self.get_entry(key) {
             return Some(entry);
@@ -220,7 +254,7 @@ impl RegisterStore {
 
         // Fall back to intrinsic
         IntrinsicRegister::from_name(key).and_then(|i| {
-            i.resolve().map(|value| RegisterEntry {
+            i.resolve(context, wallet).map(|value| RegisterEntry {
                 value,
                 source_tool: "intrinsic".to_string(),
                 created_at: std::time::Instant::now(),
@@ -381,4 +415,55 @@ mod tests {
         assert_eq!(entry.source_tool, "my_tool");
         assert!(entry.created_at.elapsed().as_secs() < 1);
     }
+
+    #[test]
+    fn test_wallet_address_follows_channel_wallet() {
+        use crate::db::Database;
+        use crate::models::ChannelSettingKey;
+
+        const TRADING_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+        // A named wallet no other test configures
+        unsafe {
+            std::env::set_var(
+                crate::config::env_vars::WALLETS,
+                format!("intrinsic_test={}", TRADING_KEY),
+            );
+        }
+        let trading = format!(
+            "{:?}",
+            crate::wallet::cached_address(Some("intrinsic_test")).unwrap()
+        );
+        let primary = crate::wallet::cached_address(None).map(|a| format!("{:?}", a));
+        assert_ne!(primary.as_deref(), Some(trading.as_str()));
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+        let channel = db.create_channel("discord", "test", "token", None).unwrap();
+        db.set_channel_setting(channel.id, ChannelSettingKey::Wallet.as_ref(), "intrinsic_test")
+            .unwrap();
+
+        let store = RegisterStore::new();
+        let context = ToolContext::new()
+            .with_channel(channel.id, "discord".to_string())
+            .with_database(db)
+            .with_registers(store.clone());
+
+        // Without an explicit register, the channel's wallet is used
+        assert_eq!(store.get("wallet_address"), None);
+        assert_eq!(
+            store.get_or_intrinsic("wallet_address", &context, None),
+            Some(json!(trading))
+        );
+
+        // A tool's wallet param takes precedence over the channel's
+        let unassigned = ToolContext::new().with_registers(store.clone());
+        assert_eq!(
+            store.get_or_intrinsic("wallet_address", &unassigned, Some("intrinsic_test")),
+            Some(json!(trading))
+        );
+        assert_eq!(
+            store.get_or_intrinsic("wallet_address", &unassigned, None),
+            primary.map(|a| json!(a))
+        );
+    }
 }
@@ -1,9 +1,16 @@
 use crate::ai::multi_agent::types::AgentSubtype;
-use crate::tools::types::{ToolConfig, ToolContext, ToolDefinition, ToolGroup, ToolResult};
+use crate::tool_validators::input_schema::validate_input;
+use crate::tools::types::{
+    ToolConfig, ToolContext, ToolDefinition, ToolErrorCode, ToolGroup, ToolResult,
+};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Longest tool argument string stored per rogue mode audit entry
+const MAX_AUDIT_DETAILS_CHARS: usize = 2000;
 
 /// Trait that all tools must implement
 #[async_trait]
@@ -23,26 +30,49 @@ pub trait Tool: Send + Sync {
     fn group(&self) -> ToolGroup {
         self.definition().group
     }
+
+    /// Upper bound on a single execution, overriding the group default.
+    /// Tools that enforce their own (longer) limits should return them here.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Whether a timed-out execution may be dropped. Tools that must not stop
+    /// partway return false: on timeout they keep running in the background
+    /// and the caller gets the timeout error without waiting for them.
+    fn cancel_on_timeout(&self) -> bool {
+        true
+    }
+
+    /// Whether the tool can change state. Identical calls to tools returning
+    /// false are deduplicated within a dispatch; a call to a mutating tool
+    /// always runs and invalidates those cached results.
+    fn is_mutating(&self) -> bool {
+        true
+    }
 }
 
 /// Registry that holds all available tools
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn Tool>>,
     default_config: ToolConfig,
+    /// Configured per-tool timeouts, taking precedence over the tool's own
+    timeout_overrides: HashMap<String, Duration>,
 }
 
 impl ToolRegistry {
     pub fn new() -> Self {
-        ToolRegistry {
-            tools: HashMap::new(),
-            default_config: ToolConfig::default(),
-        }
+        Self::with_config(ToolConfig::default())
     }
 
     pub fn with_config(config: ToolConfig) -> Self {
         ToolRegistry {
             tools: HashMap::new(),
             default_config: config,
+            timeout_overrides: crate::config::tool_timeout_overrides()
+                .into_iter()
+                .map(|(name, secs)| (name, Duration::from_secs(secs)))
+                .collect(),
         }
     }
 
@@ -175,8 +205,138 @@ impl ToolRegistry {
             return ToolResult::error(format!("Tool '{}' is not allowed", name));
         }
 
-        // Execute the tool
-        tool.execute(params, context).await
+        // Reject arguments that don't match the declared schema before running
+        if let Err(e) = validate_input(&tool.definition().input_schema, &params) {
+            log::info!("[REGISTRY] Invalid parameters for '{}': {}", name, e);
+            return ToolResult::error(format!("Invalid parameters for '{}': {}", name, e))
+                .with_error_code(ToolErrorCode::InvalidParams);
+        }
+
+        // Serve repeats of identical read-only calls from this dispatch's cache
+        let memoize = !tool.is_mutating() && crate::config::tool_call_dedup_enabled();
+        if memoize {
+            if let Some(cached) = context.tool_call_cache.get(name, &params) {
+                log::info!("[REGISTRY] Returning cached result for repeated '{}' call", name);
+                return cached;
+            }
+        }
+        let cache_key = memoize.then(|| params.clone());
+
+        // Execute the tool under its timeout, scrubbing configured secrets from
+        // whatever it returns. On timeout the tool's future is dropped, so tools
+        // must not leave work running that outlives it, unless they opt out of
+        // cancellation and run detached instead.
+        let limit = self.timeout_for(tool.as_ref());
+        let audit_details = context
+            .is_rogue_mode()
+            .then(|| context.redactor.redact(&params.to_string()));
+        let started = std::time::Instant::now();
+        let result = if tool.cancel_on_timeout() {
+            match tokio::time::timeout(limit, tool.execute(params, context)).await {
+                Ok(result) => result,
+                Err(_) => {
+                    log::warn!("[REGISTRY] Tool '{}' timed out after {}s", name, limit.as_secs());
+                    ToolResult::error(format!(
+                        "Tool '{}' timed out after {}s and was cancelled",
+                        name,
+                        limit.as_secs()
+                    ))
+                    .with_error_code(ToolErrorCode::Timeout)
+                }
+            }
+        } else {
+            let detached_tool = Arc::clone(&tool);
+            let detached_context = context.clone();
+            let mut handle =
+                tokio::spawn(async move { detached_tool.execute(params, &detached_context).await });
+            match tokio::time::timeout(limit, &mut handle).await {
+                Ok(Ok(result)) => result,
+                Ok(Err(e)) => ToolResult::error(format!("Tool '{}' failed: {}", name, e)),
+                Err(_) => {
+                    log::warn!(
+                        "[REGISTRY] Tool '{}' timed out after {}s, leaving it running in the background",
+                        name,
+                        limit.as_secs()
+                    );
+                    ToolResult::error(format!(
+                        "Tool '{}' timed out after {}s. It is still running and will finish on its own; check its outcome before retrying",
+                        name,
+                        limit.as_secs()
+                    ))
+                    .with_error_code(ToolErrorCode::Timeout)
+                }
+            }
+        };
+        crate::metrics::record_tool_execution(name, result.success, started.elapsed());
+
+        // Record what the call paid, attributed to the tool and channel
+        let spent = result.cost.as_ref().and_then(|cost| {
+            if let Some(db) = &context.database {
+                if let Err(e) = db.record_x402_payment(
+                    context.channel_id,
+                    Some(name),
+                    cost.resource.as_deref(),
+                    &cost.amount,
+                    &cost.amount_formatted,
+                    &cost.asset,
+                    &cost.pay_to,
+                    cost.tx_hash.as_deref(),
+                    &cost.status,
+                ) {
+                    log::error!("[REGISTRY] Failed to record x402 payment for '{}': {}", name, e);
+                }
+            }
+            match cost.micro_units() {
+                Some(amount) => Some(amount),
+                None => {
+                    log::warn!("[REGISTRY] Unparseable x402 amount '{}' not counted towards quota", cost.amount);
+                    None
+                }
+            }
+        });
+
+        // Count the call, and what it paid, towards the requesting identity's daily quota
+        if let (Some(identity_id), Some(db)) = (context.quota_identity(), &context.database) {
+            if let Err(e) = db.record_identity_usage(identity_id, 0, 1, spent.unwrap_or(0)) {
+                log::warn!("[REGISTRY] Failed to record tool usage for identity {}: {}", identity_id, e);
+            }
+        }
+
+        // Everything the agent does in rogue mode goes in the audit trail
+        if let (Some(details), Some(db)) = (audit_details, &context.database) {
+            let details: String = details.chars().take(MAX_AUDIT_DETAILS_CHARS).collect();
+            if let Err(e) = db.record_rogue_audit(
+                &format!("tool:{}", name),
+                Some(&details),
+                context.channel_id,
+                context.session_id,
+                Some(result.success),
+            ) {
+                log::error!("[REGISTRY] Failed to record rogue mode audit for '{}': {}", name, e);
+            }
+        }
+
+        let result = context.redactor.redact_result(result);
+        match cache_key {
+            Some(params) => context.tool_call_cache.insert(name, &params, &result),
+            None => context.tool_call_cache.clear(),
+        }
+        result
+    }
+
+    /// Timeout applied to a tool: the configured override, else the tool's
+    /// own, else its group's default
+    pub fn timeout_for(&self, tool: &dyn Tool) -> Duration {
+        self.timeout_overrides
+            .get(&tool.name())
+            .copied()
+            .or_else(|| tool.timeout())
+            .unwrap_or_else(|| tool.group().default_timeout())
+    }
+
+    /// Override the timeout for a single tool
+    pub fn set_timeout_override(&mut self, name: &str, timeout: Duration) {
+        self.timeout_overrides.insert(name.to_string(), timeout);
     }
 
     /// Get default configuration
@@ -255,6 +415,149 @@ mod tests {
         assert_eq!(registry.len(), 1);
     }
 
+    struct SlowTool;
+
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn definition(&self) -> ToolDefinition {
+            MockTool::new("slow_tool", ToolGroup::Web).definition
+        }
+
+        async fn execute(&self, _params: Value, _context: &ToolContext) -> ToolResult {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            ToolResult::success("finished")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_times_out() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(SlowTool));
+        assert_eq!(
+            registry.timeout_for(&SlowTool),
+            ToolGroup::Web.default_timeout()
+        );
+
+        registry.set_timeout_override("slow_tool", Duration::from_millis(50));
+        let result = registry
+            .execute("slow_tool", Value::Null, &ToolContext::new(), None)
+            .await;
+
+        assert!(!result.success);
+        assert_eq!(result.error_code, Some(ToolErrorCode::Timeout));
+    }
+
+    /// Like SlowTool, but mustn't be dropped partway
+    struct UncancellableTool {
+        finished: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    #[async_trait]
+    impl Tool for UncancellableTool {
+        fn definition(&self) -> ToolDefinition {
+            MockTool::new("uncancellable_tool", ToolGroup::Finance).definition
+        }
+
+        async fn execute(&self, _params: Value, _context: &ToolContext) -> ToolResult {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            self.finished.store(true, std::sync::atomic::Ordering::SeqCst);
+            ToolResult::success("finished")
+        }
+
+        fn cancel_on_timeout(&self) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_leaves_uncancellable_tool_running() {
+        let finished = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(UncancellableTool { finished: finished.clone() }));
+        registry.set_timeout_override("uncancellable_tool", Duration::from_millis(50));
+
+        let result = registry
+            .execute("uncancellable_tool", Value::Null, &ToolContext::new(), None)
+            .await;
+        assert_eq!(result.error_code, Some(ToolErrorCode::Timeout));
+        assert!(!finished.load(std::sync::atomic::Ordering::SeqCst));
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(finished.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_invalid_params() {
+        let mut tool = MockTool::new("typed_tool", ToolGroup::Web);
+        tool.definition.input_schema.properties.insert(
+            "count".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "Count".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+        tool.definition.input_schema.required = vec!["count".to_string()];
+
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(tool));
+        let context = ToolContext::new();
+
+        let result = registry.execute("typed_tool", serde_json::json!({}), &context, None).await;
+        assert!(!result.success);
+        assert_eq!(result.error_code, Some(ToolErrorCode::InvalidParams));
+        assert!(result.content.contains("missing required field(s): count"));
+
+        let result = registry.execute("typed_tool", serde_json::json!({"count": 3}), &context, None).await;
+        assert!(result.success);
+    }
+
+    struct CountingTool {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Tool for CountingTool {
+        fn definition(&self) -> ToolDefinition {
+            MockTool::new("counting_tool", ToolGroup::Web).definition
+        }
+
+        fn is_mutating(&self) -> bool {
+            false
+        }
+
+        async fn execute(&self, _params: Value, _context: &ToolContext) -> ToolResult {
+            let n = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            ToolResult::success(format!("call {}", n))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_deduplicates_read_only_calls() {
+        let counting = Arc::new(CountingTool { calls: Default::default() });
+        let mut registry = ToolRegistry::new();
+        registry.register(counting.clone());
+        registry.register(Arc::new(MockTool::new("mutating_tool", ToolGroup::Web)));
+        let context = ToolContext::new();
+        let args = serde_json::json!({"path": "a.txt"});
+
+        registry.execute("counting_tool", args.clone(), &context, None).await;
+        let repeat = registry.execute("counting_tool", args.clone(), &context, None).await;
+        assert!(repeat.content.ends_with("call 1"));
+        registry.execute("counting_tool", serde_json::json!({"path": "b.txt"}), &context, None).await;
+        assert_eq!(counting.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // A mutating call invalidates the cache
+        registry.execute("mutating_tool", Value::Null, &context, None).await;
+        let after = registry.execute("counting_tool", args, &context, None).await;
+        assert_eq!(after.content, "call 3");
+
+        // A new dispatch starts with an empty cache
+        assert!(ToolContext::new().tool_call_cache.is_empty());
+    }
+
     #[test]
     fn test_tool_config_allows() {
         let config = ToolConfig {
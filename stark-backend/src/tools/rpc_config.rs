@@ -2,86 +2,16 @@
 //!
 //! Loads RPC provider configurations from config/rpc_providers.ron
 //! Supports x402-enabled (paid) and regular (free) RPC endpoints.
+//! Supported networks themselves are defined by `network_lookup`.
 
+use crate::tools::builtin::cryptocurrency::network_lookup;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
-use std::sync::OnceLock;
-use strum::{Display, EnumString, AsRefStr};
-
-/// Supported blockchain networks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString, AsRefStr)]
-#[strum(serialize_all = "lowercase")]
-#[serde(rename_all = "lowercase")]
-pub enum Network {
-    Base,
-    Mainnet,
-    Polygon,
-}
-
-impl Network {
-    /// Get the chain ID for this network
-    pub fn chain_id(&self) -> u64 {
-        match self {
-            Network::Base => 8453,
-            Network::Mainnet => 1,
-            Network::Polygon => 137,
-        }
-    }
-
-    /// Get the native currency symbol
-    pub fn native_currency(&self) -> &'static str {
-        match self {
-            Network::Base => "ETH",
-            Network::Mainnet => "ETH",
-            Network::Polygon => "MATIC",
-        }
-    }
-
-    /// Get the block explorer URL
-    pub fn explorer_url(&self) -> &'static str {
-        match self {
-            Network::Base => "https://basescan.org",
-            Network::Mainnet => "https://etherscan.io",
-            Network::Polygon => "https://polygonscan.com",
-        }
-    }
-
-    /// Get the USDC contract address for this network
-    pub fn usdc_address(&self) -> &'static str {
-        match self {
-            Network::Base => "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
-            Network::Mainnet => "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
-            Network::Polygon => "0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359",
-        }
-    }
-
-    /// All supported networks
-    pub fn all() -> &'static [Network] {
-        &[Network::Base, Network::Mainnet, Network::Polygon]
-    }
-
-    /// Try to detect network from a known contract address
-    pub fn from_contract_address(address: &str) -> Option<Network> {
-        let addr_lower = address.to_lowercase();
-        match addr_lower.as_str() {
-            // USDC addresses
-            "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913" => Some(Network::Base),
-            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48" => Some(Network::Mainnet),
-            "0x3c499c542cef5e3811e1192ce70d8cc03d5c3359" => Some(Network::Polygon),
-            _ => None,
-        }
-    }
-}
-
-impl Default for Network {
-    fn default() -> Self {
-        Network::Base
-    }
-}
+use std::sync::{Arc, RwLock};
 
-/// Global storage for RPC providers
-static RPC_PROVIDERS: OnceLock<HashMap<String, RpcProvider>> = OnceLock::new();
+/// Global storage for RPC providers (swapped whole on reload)
+static RPC_PROVIDERS: RwLock<Option<Arc<HashMap<String, RpcProvider>>>> = RwLock::new(None);
 
 /// RPC Provider configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +24,11 @@ pub struct RpcProvider {
     pub x402: bool,
     /// Network -> URL mapping (e.g., "base" -> "https://...")
     pub endpoints: HashMap<String, String>,
+    /// Network -> ordered list of provider names to fail over to.
+    /// May include this provider's own name and/or "custom" (the bot's
+    /// custom_rpc_endpoints) to place them at a specific priority.
+    #[serde(default)]
+    pub failover: HashMap<String, Vec<String>>,
 }
 
 impl RpcProvider {
@@ -108,28 +43,48 @@ impl RpcProvider {
     }
 }
 
+/// Read, parse and validate config/rpc_providers.ron
+fn parse_rpc_providers(config_path: &Path) -> Result<HashMap<String, RpcProvider>, String> {
+    let content = std::fs::read_to_string(config_path)
+        .map_err(|e| format!("Failed to read rpc_providers.ron: {}", e))?;
+
+    let providers: HashMap<String, RpcProvider> = ron::from_str(&content)
+        .map_err(|e| format!("Failed to parse rpc_providers.ron: {}", e))?;
+
+    if providers.is_empty() {
+        return Err("No providers defined in rpc_providers.ron".to_string());
+    }
+    for (name, provider) in &providers {
+        for (network, url) in &provider.endpoints {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("Invalid endpoint for provider '{}' on {}: {}", name, network, url));
+            }
+        }
+    }
+
+    Ok(providers)
+}
+
+fn set_rpc_providers(providers: HashMap<String, RpcProvider>) {
+    *RPC_PROVIDERS.write().unwrap() = Some(Arc::new(providers));
+}
+
 /// Load RPC providers from config directory
 pub fn load_rpc_providers(config_dir: &Path) {
     let config_path = config_dir.join("rpc_providers.ron");
 
     let providers = if config_path.exists() {
-        match std::fs::read_to_string(&config_path) {
-            Ok(content) => match ron::from_str::<HashMap<String, RpcProvider>>(&content) {
-                Ok(providers) => {
-                    log::info!(
-                        "Loaded {} RPC providers from config: {:?}",
-                        providers.len(),
-                        providers.keys().collect::<Vec<_>>()
-                    );
-                    providers
-                }
-                Err(e) => {
-                    log::error!("Failed to parse rpc_providers.ron: {}", e);
-                    default_providers()
-                }
-            },
+        match parse_rpc_providers(&config_path) {
+            Ok(providers) => {
+                log::info!(
+                    "Loaded {} RPC providers from config: {:?}",
+                    providers.len(),
+                    providers.keys().collect::<Vec<_>>()
+                );
+                providers
+            }
             Err(e) => {
-                log::error!("Failed to read rpc_providers.ron: {}", e);
+                log::error!("{}", e);
                 default_providers()
             }
         }
@@ -138,9 +93,31 @@ pub fn load_rpc_providers(config_dir: &Path) {
         default_providers()
     };
 
-    if RPC_PROVIDERS.set(providers).is_err() {
-        log::warn!("RPC providers already initialized");
+    set_rpc_providers(providers);
+}
+
+/// Reload RPC providers from config directory, returning the number of
+/// providers. The file is validated first; on error the loaded providers are kept.
+pub fn reload_rpc_providers(config_dir: &Path) -> Result<usize, String> {
+    let config_path = config_dir.join("rpc_providers.ron");
+    if !config_path.exists() {
+        return Err(format!("Config file not found: {:?}", config_path));
     }
+
+    let providers = parse_rpc_providers(&config_path)?;
+    let count = providers.len();
+    log::info!(
+        "Reloaded {} RPC providers from config: {:?}",
+        count,
+        providers.keys().collect::<Vec<_>>()
+    );
+    set_rpc_providers(providers);
+    Ok(count)
+}
+
+/// Loaded providers, if load_rpc_providers() was called
+fn loaded_providers() -> Option<Arc<HashMap<String, RpcProvider>>> {
+    RPC_PROVIDERS.read().unwrap().clone()
 }
 
 /// Get default providers (DeFi Relay)
@@ -160,6 +137,14 @@ fn default_providers() -> HashMap<String, RpcProvider> {
         "https://rpc.defirelay.com/rpc/light/polygon".to_string(),
     );
 
+    let mut failover = HashMap::new();
+    for network in ["base", "mainnet", "polygon"] {
+        failover.insert(
+            network.to_string(),
+            vec!["defirelay".to_string(), "publicnode".to_string()],
+        );
+    }
+
     providers.insert(
         "defirelay".to_string(),
         RpcProvider {
@@ -167,6 +152,32 @@ fn default_providers() -> HashMap<String, RpcProvider> {
             description: "Paid RPC via x402 payment protocol".to_string(),
             x402: true,
             endpoints,
+            failover,
+        },
+    );
+
+    let mut endpoints = HashMap::new();
+    endpoints.insert(
+        "base".to_string(),
+        "https://base-rpc.publicnode.com".to_string(),
+    );
+    endpoints.insert(
+        "mainnet".to_string(),
+        "https://ethereum-rpc.publicnode.com".to_string(),
+    );
+    endpoints.insert(
+        "polygon".to_string(),
+        "https://polygon-bor-rpc.publicnode.com".to_string(),
+    );
+
+    providers.insert(
+        "publicnode".to_string(),
+        RpcProvider {
+            display_name: "PublicNode".to_string(),
+            description: "Free public RPC (rate limited)".to_string(),
+            x402: false,
+            endpoints,
+            failover: HashMap::new(),
         },
     );
 
@@ -175,15 +186,12 @@ fn default_providers() -> HashMap<String, RpcProvider> {
 
 /// Get a specific RPC provider by name
 pub fn get_rpc_provider(name: &str) -> Option<RpcProvider> {
-    RPC_PROVIDERS
-        .get()
-        .and_then(|providers| providers.get(name).cloned())
+    loaded_providers().and_then(|providers| providers.get(name).cloned())
 }
 
 /// List all available RPC providers
 pub fn list_rpc_providers() -> Vec<(String, RpcProvider)> {
-    RPC_PROVIDERS
-        .get()
+    loaded_providers()
         .map(|providers| {
             providers
                 .iter()
@@ -193,69 +201,158 @@ pub fn list_rpc_providers() -> Vec<(String, RpcProvider)> {
         .unwrap_or_default()
 }
 
-/// Get the endpoint URL for a provider and network
-/// Returns (url, use_x402) tuple
-pub fn get_rpc_endpoint(provider_name: &str, network: &str) -> Option<(String, bool)> {
-    get_rpc_provider(provider_name).and_then(|provider| {
-        provider
-            .get_endpoint(network)
-            .map(|url| (url.clone(), provider.x402))
-    })
+/// A single RPC endpoint in a failover list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcEndpoint {
+    /// Provider name that supplied this endpoint ("custom" for custom endpoints)
+    pub provider: String,
+    pub url: String,
+    pub use_x402: bool,
 }
 
-/// Resolve RPC configuration from settings
-/// If custom endpoints are provided, uses those (no x402)
-/// Otherwise falls back to the configured provider
-pub fn resolve_rpc_config(
+/// Name used in failover lists to refer to the bot's custom RPC endpoints
+const CUSTOM_PROVIDER: &str = "custom";
+
+/// Build the ordered failover list for a network.
+///
+/// The selected provider's `failover` list for the network defines the order.
+/// If it doesn't mention the provider itself, the provider goes first. Custom
+/// endpoints go first unless "custom" appears in the list, in which case they
+/// keep that position. Providers without an endpoint for the network are skipped.
+fn resolve_endpoints(
+    providers: &HashMap<String, RpcProvider>,
     provider_name: &str,
     custom_endpoints: Option<&HashMap<String, String>>,
     network: &str,
-) -> Option<(String, bool)> {
-    // Custom endpoints take precedence (no x402)
-    if let Some(endpoints) = custom_endpoints {
-        if let Some(url) = endpoints.get(network) {
-            if !url.is_empty() {
-                return Some((url.clone(), false));
-            }
+) -> Vec<RpcEndpoint> {
+    let custom_url = custom_endpoints
+        .and_then(|endpoints| endpoints.get(network))
+        .filter(|url| !url.is_empty());
+
+    let mut order: Vec<String> = providers
+        .get(provider_name)
+        .and_then(|p| p.failover.get(network))
+        .cloned()
+        .unwrap_or_default();
+    if !order.iter().any(|name| name == provider_name) {
+        order.insert(0, provider_name.to_string());
+    }
+    if custom_url.is_some() && !order.iter().any(|name| name == CUSTOM_PROVIDER) {
+        order.insert(0, CUSTOM_PROVIDER.to_string());
+    }
+
+    let mut endpoints: Vec<RpcEndpoint> = Vec::new();
+    for name in order {
+        let endpoint = if name == CUSTOM_PROVIDER {
+            custom_url.map(|url| RpcEndpoint {
+                provider: name.clone(),
+                url: url.clone(),
+                use_x402: false,
+            })
+        } else {
+            providers.get(&name).and_then(|provider| {
+                provider.get_endpoint(network).map(|url| RpcEndpoint {
+                    provider: name.clone(),
+                    url: url.clone(),
+                    use_x402: provider.x402,
+                })
+            })
+        };
+
+        if let Some(endpoint) = endpoint.filter(|ep| !endpoints.iter().any(|e| e.url == ep.url)) {
+            endpoints.push(endpoint);
         }
     }
 
-    // Fall back to provider
-    get_rpc_endpoint(provider_name, network)
+    endpoints
+}
+
+/// Resolve RPC configuration from settings
+/// Returns the ordered failover list (custom endpoints first unless the
+/// provider's failover list places them elsewhere)
+pub fn resolve_rpc_config(
+    provider_name: &str,
+    custom_endpoints: Option<&HashMap<String, String>>,
+    network: &str,
+) -> Vec<RpcEndpoint> {
+    match loaded_providers() {
+        Some(providers) => resolve_endpoints(&providers, provider_name, custom_endpoints, network),
+        None => resolve_endpoints(&default_providers(), provider_name, custom_endpoints, network),
+    }
 }
 
 /// Resolved RPC configuration ready for use
 #[derive(Debug, Clone)]
 pub struct ResolvedRpcConfig {
+    /// Primary endpoint URL (first in the failover list)
     pub url: String,
     pub use_x402: bool,
+    /// Ordered failover list, primary first
+    pub endpoints: Vec<RpcEndpoint>,
 }
 
-/// Resolve RPC configuration using default provider
-/// Used when tool context is not available (e.g., gateway RPC methods)
-pub fn resolve_rpc_from_network(network: &str) -> ResolvedRpcConfig {
-    match resolve_rpc_config("defirelay", None, network) {
-        Some((url, use_x402)) => {
-            log::info!(
-                "[rpc_config] Resolved RPC for {} (default provider): {} (x402={})",
-                network,
-                url,
-                use_x402
-            );
-            ResolvedRpcConfig { url, use_x402 }
+impl ResolvedRpcConfig {
+    /// Build from an ordered endpoint list. If the list is empty, falls back
+    /// to the network's public RPC from networks.ron, then the defirelay URL.
+    fn from_endpoints(mut endpoints: Vec<RpcEndpoint>, network: &str) -> Self {
+        if endpoints.is_empty() {
+            if let Some(url) = network_lookup::get_network(network).and_then(|n| n.rpc_url) {
+                log::info!("[rpc_config] Using public RPC for {}: {} (x402=false)", network, url);
+                endpoints.push(RpcEndpoint {
+                    provider: "public".to_string(),
+                    url,
+                    use_x402: false,
+                });
+            }
         }
-        None => {
+        if endpoints.is_empty() {
             let url = format!("https://rpc.defirelay.com/rpc/light/{}", network);
             log::info!(
                 "[rpc_config] Using fallback RPC for {}: {} (x402=true)",
                 network,
                 url
             );
-            ResolvedRpcConfig { url, use_x402: true }
+            endpoints.push(RpcEndpoint {
+                provider: "defirelay".to_string(),
+                url,
+                use_x402: true,
+            });
         }
+
+        Self {
+            url: endpoints[0].url.clone(),
+            use_x402: endpoints[0].use_x402,
+            endpoints,
+        }
+    }
+
+    /// Provider names in failover order, for logging
+    pub fn provider_chain(&self) -> String {
+        self.endpoints
+            .iter()
+            .map(|e| e.provider.as_str())
+            .collect::<Vec<_>>()
+            .join(" -> ")
     }
 }
 
+/// Resolve RPC configuration using default provider
+/// Used when tool context is not available (e.g., gateway RPC methods)
+pub fn resolve_rpc_from_network(network: &str) -> ResolvedRpcConfig {
+    let config = ResolvedRpcConfig::from_endpoints(
+        resolve_rpc_config("defirelay", None, network),
+        network,
+    );
+    log::info!(
+        "[rpc_config] Resolved RPC for {} (default provider): {} (x402={}, failover: {})",
+        network,
+        config.url,
+        config.use_x402,
+        config.provider_chain()
+    );
+    config
+}
+
 /// Extract and resolve RPC configuration from ToolContext.extra
 /// This is the canonical way to get RPC config in any tool.
 pub fn resolve_rpc_from_context(
@@ -271,25 +368,95 @@ pub fn resolve_rpc_from_context(
         .get("custom_rpc_endpoints")
         .and_then(|v| serde_json::from_value(v.clone()).ok());
 
-    match resolve_rpc_config(rpc_provider, custom_endpoints.as_ref(), network) {
-        Some((url, use_x402)) => {
-            log::info!(
-                "[rpc_config] Resolved RPC for {}: {} (x402={})",
-                network,
-                url,
-                use_x402
-            );
-            ResolvedRpcConfig { url, use_x402 }
-        }
-        None => {
-            // Fallback to default defirelay URL
-            let url = format!("https://rpc.defirelay.com/rpc/light/{}", network);
-            log::info!(
-                "[rpc_config] Using fallback RPC for {}: {} (x402=true)",
-                network,
-                url
-            );
-            ResolvedRpcConfig { url, use_x402: true }
-        }
+    let config = ResolvedRpcConfig::from_endpoints(
+        resolve_rpc_config(rpc_provider, custom_endpoints.as_ref(), network),
+        network,
+    );
+    log::info!(
+        "[rpc_config] Resolved RPC for {}: {} (x402={}, failover: {})",
+        network,
+        config.url,
+        config.use_x402,
+        config.provider_chain()
+    );
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom(network: &str, url: &str) -> HashMap<String, String> {
+        let mut endpoints = HashMap::new();
+        endpoints.insert(network.to_string(), url.to_string());
+        endpoints
+    }
+
+    fn providers_of(endpoints: &[RpcEndpoint]) -> Vec<&str> {
+        endpoints.iter().map(|e| e.provider.as_str()).collect()
+    }
+
+    #[test]
+    fn test_default_failover_order() {
+        let endpoints = resolve_endpoints(&default_providers(), "defirelay", None, "base");
+        assert_eq!(providers_of(&endpoints), vec!["defirelay", "publicnode"]);
+        assert!(endpoints[0].use_x402);
+        assert!(!endpoints[1].use_x402);
+    }
+
+    #[test]
+    fn test_custom_endpoint_goes_first_by_default() {
+        let custom = custom("base", "https://my-node.example.com");
+        let endpoints = resolve_endpoints(&default_providers(), "defirelay", Some(&custom), "base");
+        assert_eq!(providers_of(&endpoints), vec!["custom", "defirelay", "publicnode"]);
+        assert!(!endpoints[0].use_x402);
+
+        // Custom endpoints for other networks don't apply
+        let endpoints = resolve_endpoints(&default_providers(), "defirelay", Some(&custom), "mainnet");
+        assert_eq!(providers_of(&endpoints), vec!["defirelay", "publicnode"]);
+    }
+
+    #[test]
+    fn test_custom_endpoint_at_configured_priority() {
+        let mut providers = default_providers();
+        providers.get_mut("defirelay").unwrap().failover.insert(
+            "base".to_string(),
+            vec!["defirelay".to_string(), "custom".to_string(), "publicnode".to_string()],
+        );
+
+        let custom = custom("base", "https://my-node.example.com");
+        let endpoints = resolve_endpoints(&providers, "defirelay", Some(&custom), "base");
+        assert_eq!(providers_of(&endpoints), vec!["defirelay", "custom", "publicnode"]);
+
+        // Without a custom endpoint the placeholder is skipped
+        let endpoints = resolve_endpoints(&providers, "defirelay", None, "base");
+        assert_eq!(providers_of(&endpoints), vec!["defirelay", "publicnode"]);
+    }
+
+    #[test]
+    fn test_unknown_network_falls_back_to_defirelay() {
+        let endpoints = resolve_endpoints(&default_providers(), "defirelay", None, "fantom");
+        assert!(endpoints.is_empty());
+
+        let config = ResolvedRpcConfig::from_endpoints(endpoints, "fantom");
+        assert_eq!(config.url, "https://rpc.defirelay.com/rpc/light/fantom");
+        assert!(config.use_x402);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_endpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rpc_providers.ron");
+
+        std::fs::write(
+            &path,
+            r#"{ "mynode": (display_name: "My Node", description: "", x402: false, endpoints: { "base": "localhost:8545" }) }"#,
+        )
+        .unwrap();
+        let err = parse_rpc_providers(&path).unwrap_err();
+        assert!(err.contains("Invalid endpoint for provider 'mynode' on base"));
+
+        std::fs::write(&path, "{ \"mynode\": (").unwrap();
+        assert!(parse_rpc_providers(&path).unwrap_err().contains("Failed to parse"));
     }
 }
@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use strum::{EnumIter, IntoEnumIterator};
 
 /// Tool groups for access control
@@ -105,6 +106,18 @@ impl ToolGroup {
             ToolGroup::Memory => "memory",
         }
     }
+
+    /// Default upper bound on a single execution of a tool in this group
+    pub fn default_timeout(&self) -> Duration {
+        let secs = match self {
+            ToolGroup::Web | ToolGroup::Filesystem | ToolGroup::Memory => 60,
+            ToolGroup::Messaging | ToolGroup::Social => 120,
+            ToolGroup::System | ToolGroup::Development | ToolGroup::Exec => 300,
+            // On-chain operations wait for confirmations and bridges
+            ToolGroup::Finance => 600,
+        };
+        Duration::from_secs(secs)
+    }
 }
 
 /// Tool profiles for quick configuration
@@ -245,6 +258,63 @@ pub struct ToolDefinition {
     pub group: ToolGroup,
 }
 
+/// Machine-readable reason a tool failed, for errors the agent can react to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolErrorCode {
+    /// The tool didn't finish within its timeout and was cancelled
+    Timeout,
+    /// The arguments didn't match the tool's input schema or invariants
+    InvalidParams,
+}
+
+/// What a tool call paid over x402. The registry records it in
+/// `x402_payments` under the tool's name, so spend can be reported per tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCost {
+    /// Amount in the asset's smallest unit (micro-USDC for USDC)
+    pub amount: String,
+    /// Human-readable amount (e.g. "0.001")
+    pub amount_formatted: String,
+    pub asset: String,
+    /// Decimals of the asset, which `amount` is scaled by
+    #[serde(default = "default_cost_decimals")]
+    pub decimals: u8,
+    pub pay_to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    /// pending, confirmed or failed
+    pub status: String,
+}
+
+fn default_cost_decimals() -> u8 {
+    crate::x402::USDC_DECIMALS
+}
+
+impl ToolCost {
+    /// The amount in micro-units (6 decimals), as counted towards quotas
+    pub fn micro_units(&self) -> Option<u64> {
+        crate::x402::to_micro_units(&self.amount, self.decimals)
+    }
+}
+
+impl From<&crate::x402::X402PaymentInfo> for ToolCost {
+    fn from(payment: &crate::x402::X402PaymentInfo) -> Self {
+        ToolCost {
+            amount: payment.amount.clone(),
+            amount_formatted: payment.amount_formatted.clone(),
+            asset: payment.asset.clone(),
+            decimals: payment.decimals,
+            pay_to: payment.pay_to.clone(),
+            resource: payment.resource.clone(),
+            tx_hash: payment.tx_hash.clone(),
+            status: payment.status.to_string(),
+        }
+    }
+}
+
 /// Result of tool execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolResult {
@@ -258,6 +328,11 @@ pub struct ToolResult {
     /// Used for transient network errors with exponential backoff.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_after_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ToolErrorCode>,
+    /// What the call paid, for tools that charge per call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<ToolCost>,
 }
 
 impl ToolResult {
@@ -268,6 +343,8 @@ impl ToolResult {
             error: None,
             metadata: None,
             retry_after_secs: None,
+            error_code: None,
+            cost: None,
         }
     }
 
@@ -279,6 +356,8 @@ impl ToolResult {
             error: Some(msg),
             metadata: None,
             retry_after_secs: None,
+            error_code: None,
+            cost: None,
         }
     }
 
@@ -295,6 +374,8 @@ impl ToolResult {
             error: Some(msg),
             metadata: None,
             retry_after_secs: Some(retry_after_secs),
+            error_code: None,
+            cost: None,
         }
     }
 
@@ -308,6 +389,20 @@ impl ToolResult {
         self
     }
 
+    pub fn with_error_code(mut self, code: ToolErrorCode) -> Self {
+        self.error_code = Some(code);
+        self
+    }
+
+    /// Attach what the call paid. `None` leaves the result unchanged, so a
+    /// tool can pass on whatever its payment client reported.
+    pub fn with_cost(mut self, cost: Option<ToolCost>) -> Self {
+        if cost.is_some() {
+            self.cost = cost;
+        }
+        self
+    }
+
     /// Check if this result indicates the tool should be retried
     pub fn should_retry(&self) -> bool {
         self.retry_after_secs.is_some()
@@ -349,6 +444,13 @@ pub struct ToolContext {
     pub selected_network: Option<String>,
     /// QMD Memory store for markdown-based memory system
     pub memory_store: Option<Arc<MemoryStore>>,
+    /// Secrets scrubbed from tool results before they are broadcast or stored
+    pub redactor: crate::tools::SecretRedactor,
+    /// Cancelled when the user stops the execution; long-running tools
+    /// (like exec) watch it to abort early
+    pub cancellation_token: Option<tokio_util::sync::CancellationToken>,
+    /// Results of read-only tool calls made in this dispatch, for deduplication
+    pub tool_call_cache: crate::tools::ToolCallCache,
 }
 
 impl std::fmt::Debug for ToolContext {
@@ -371,6 +473,9 @@ impl std::fmt::Debug for ToolContext {
             .field("tx_queue", &self.tx_queue.is_some())
             .field("selected_network", &self.selected_network)
             .field("memory_store", &self.memory_store.is_some())
+            .field("redactor", &self.redactor)
+            .field("cancellation_token", &self.cancellation_token.is_some())
+            .field("tool_call_cache", &self.tool_call_cache.len())
             .finish()
     }
 }
@@ -395,6 +500,9 @@ impl Default for ToolContext {
             tx_queue: None,
             selected_network: None,
             memory_store: None,
+            redactor: crate::tools::SecretRedactor::default(),
+            cancellation_token: None,
+            tool_call_cache: crate::tools::ToolCallCache::new(),
         }
     }
 }
@@ -520,6 +628,18 @@ impl ToolContext {
         self
     }
 
+    /// Set the secrets to redact from tool results
+    pub fn with_redactor(mut self, redactor: crate::tools::SecretRedactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    /// Set the execution's cancellation token
+    pub fn with_cancellation_token(mut self, token: tokio_util::sync::CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
     /// Populate context bank with extracted terms from user input and broadcast update
     pub fn scan_and_set_context_bank(&mut self, text: &str) {
         let items = crate::tools::scan_input(text);
@@ -546,8 +666,28 @@ impl ToolContext {
     pub fn set_register(&self, key: &str, value: Value, source_tool: &str) {
         // Set the register value
         self.registers.set(key, value, source_tool);
+        self.broadcast_registers();
+    }
+
+    /// Remove one register, or all of them when `key` is None, and broadcast
+    /// the update. Returns the number of registers removed.
+    pub fn clear_registers(&self, key: Option<&str>) -> usize {
+        let removed = match key {
+            Some(key) => self.registers.remove(key).map_or(0, |_| 1),
+            None => {
+                let count = self.registers.keys().len();
+                self.registers.clear();
+                count
+            }
+        };
+        if removed > 0 {
+            self.broadcast_registers();
+        }
+        removed
+    }
 
-        // Broadcast the update if we have a broadcaster and channel
+    /// Broadcast the register snapshot if we have a broadcaster and channel
+    fn broadcast_registers(&self) {
         if let (Some(broadcaster), Some(channel_id)) = (&self.broadcaster, self.channel_id) {
             let registers_snapshot = self.get_registers_snapshot();
             broadcaster.broadcast(GatewayEvent::register_update(channel_id, registers_snapshot));
@@ -570,6 +710,36 @@ impl ToolContext {
         Value::Object(map)
     }
 
+    /// Whether rogue mode is active for this execution. Rogue mode skips the
+    /// exec allowlist, the gateway-channel transaction block and partner-mode
+    /// broadcast confirmation; every tool call made under it is audited.
+    pub fn is_rogue_mode(&self) -> bool {
+        self.extra
+            .get("rogue_mode_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// The identity whose daily quota this execution's tool calls count
+    /// against, if it is subject to one
+    pub fn quota_identity(&self) -> Option<&str> {
+        self.extra.get("quota_identity_id").and_then(|v| v.as_str())
+    }
+
+    /// The wallet a tool should use: the one it was asked for, else the
+    /// channel's `wallet` setting. `None` means the primary wallet.
+    pub fn wallet_name(&self, requested: Option<&str>) -> Option<String> {
+        if let Some(name) = requested.map(str::trim).filter(|n| !n.is_empty()) {
+            return Some(name.to_string());
+        }
+        let (db, channel_id) = (self.database.as_ref()?, self.channel_id?);
+        db.get_channel_setting(channel_id, crate::models::ChannelSettingKey::Wallet.as_ref())
+            .ok()
+            .flatten()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
+
     /// Get bot name from the context
     pub fn get_bot_name(&self) -> String {
         self.extra.get("bot_name")
@@ -641,6 +811,24 @@ impl ToolConfig {
             _ => self.profile.allowed_groups().contains(&tool_group),
         }
     }
+
+    /// Narrow this configuration to the given tools plus the system group.
+    /// Denials in this configuration still apply.
+    pub fn restricted_to(&self, tools: &[String]) -> ToolConfig {
+        ToolConfig {
+            id: self.id,
+            channel_id: self.channel_id,
+            profile: ToolProfile::Custom,
+            allow_list: tools
+                .iter()
+                .filter(|t| !self.deny_list.contains(t))
+                .cloned()
+                .collect(),
+            deny_list: self.deny_list.clone(),
+            allowed_groups: vec![ToolGroup::System.as_str().to_string()],
+            denied_groups: self.denied_groups.clone(),
+        }
+    }
 }
 
 /// Tool execution record for audit logging
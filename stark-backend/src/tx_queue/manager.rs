@@ -1,23 +1,46 @@
 //! Transaction queue manager
 //!
-//! Thread-safe storage and management of queued transactions.
+//! Thread-safe storage and management of queued transactions. With a database
+//! every change is written through to the `queued_transactions` table, and the
+//! queue is reloaded from it on startup.
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::types::{QueuedTransaction, QueuedTxStatus, QueuedTxSummary};
 use crate::db::tables::broadcasted_transactions::{
     BroadcastMode, BroadcastedTxStatus, RecordBroadcastRequest,
 };
 use crate::db::Database;
+use crate::tools::rpc_config::resolve_rpc_from_network;
+use crate::x402::X402EvmRpc;
+
+/// How often the background sweep expires stale transactions
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+/// Terminal transactions older than this are purged by the sweep
+const PURGE_AFTER_HOURS: i64 = 24;
+
+/// Outcome of claiming a transaction for broadcast
+#[derive(Debug)]
+pub enum BroadcastClaim {
+    /// The transaction was pending and is now marked broadcasting; the caller
+    /// owns the broadcast
+    Claimed(QueuedTransaction),
+    /// The transaction isn't pending (already broadcasting, submitted, failed
+    /// or expired); it's returned as it stands
+    NotPending(QueuedTransaction),
+    NotFound,
+}
 
 /// Manager for the transaction queue
 /// Uses DashMap for thread-safe concurrent access
 pub struct TxQueueManager {
     /// Map of UUID -> QueuedTransaction
     transactions: DashMap<String, QueuedTransaction>,
-    /// Optional database for persistent broadcast history
+    /// Optional database for the persistent queue and broadcast history
     db: Option<Arc<Database>>,
 }
 
@@ -30,18 +53,54 @@ impl TxQueueManager {
         }
     }
 
-    /// Create a new transaction queue manager with database persistence
+    /// Create a new transaction queue manager with database persistence,
+    /// loading the transactions queued before the last shutdown
     pub fn with_db(db: Arc<Database>) -> Self {
+        let transactions = DashMap::new();
+        match db.list_queued_transactions() {
+            Ok(queued) => {
+                for mut tx in queued {
+                    // A broadcast interrupted by the restart may or may not have
+                    // reached the network; resubmitting the same signed bytes is
+                    // harmless, and reconcile_nonces drops it if it landed
+                    if tx.status == QueuedTxStatus::Broadcasting && tx.tx_hash.is_none() {
+                        tx.set_status(QueuedTxStatus::Pending);
+                    }
+                    transactions.insert(tx.uuid.clone(), tx);
+                }
+                log::info!("[TxQueue] Loaded {} queued transactions", transactions.len());
+            }
+            Err(e) => log::error!("[TxQueue] Failed to load queued transactions: {}", e),
+        }
+
         Self {
-            transactions: DashMap::new(),
+            transactions,
             db: Some(db),
         }
     }
 
+    /// Write a transaction through to the database, if there is one
+    fn persist(&self, tx: &QueuedTransaction) {
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.save_queued_transaction(tx) {
+                log::error!("[TxQueue] Failed to persist transaction {}: {}", tx.uuid, e);
+            }
+        }
+    }
+
+    fn unpersist(&self, uuid: &str) {
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.delete_queued_transaction(uuid) {
+                log::error!("[TxQueue] Failed to delete transaction {}: {}", uuid, e);
+            }
+        }
+    }
+
     /// Queue a new transaction
     pub fn queue(&self, tx: QueuedTransaction) -> String {
         let uuid = tx.uuid.clone();
         log::info!("[TxQueue] Queuing transaction {} to {}", uuid, tx.to);
+        self.persist(&tx);
         self.transactions.insert(uuid.clone(), tx);
         uuid
     }
@@ -93,15 +152,69 @@ impl TxQueueManager {
             .collect()
     }
 
+    /// Transactions sent from `from` on `network` since `since`, excluding
+    /// failed and expired ones (used for daily transfer caps)
+    pub fn list_outgoing_since(&self, network: &str, from: &str, since: DateTime<Utc>) -> Vec<QueuedTransaction> {
+        self.transactions
+            .iter()
+            .filter(|r| {
+                let tx = r.value();
+                tx.network == network
+                    && tx.from.eq_ignore_ascii_case(from)
+                    && tx.created_at >= since
+                    && !matches!(tx.status, QueuedTxStatus::Failed | QueuedTxStatus::Expired)
+            })
+            .map(|r| r.value().clone())
+            .collect()
+    }
+
+    /// Apply `change` to a transaction under its map guard and return the
+    /// updated copy, so it's persisted after the guard is released
+    fn modify(&self, uuid: &str, change: impl FnOnce(&mut QueuedTransaction)) -> Option<QueuedTransaction> {
+        let mut tx = self.transactions.get_mut(uuid)?;
+        change(&mut tx);
+        Some(tx.clone())
+    }
+
     /// Update transaction status
     pub fn update_status(&self, uuid: &str, status: QueuedTxStatus) -> bool {
-        if let Some(mut tx) = self.transactions.get_mut(uuid) {
+        let Some(tx) = self.modify(uuid, |tx| {
             log::info!("[TxQueue] Updating {} status to {:?}", uuid, status);
-            tx.status = status;
-            true
-        } else {
-            false
+            tx.set_status(status);
+        }) else {
+            return false;
+        };
+        self.persist(&tx);
+        true
+    }
+
+    /// Atomically move a pending transaction to broadcasting. Only one caller
+    /// can claim a transaction, so a retry or double call can't submit it twice.
+    pub fn claim_for_broadcast(&self, uuid: &str) -> BroadcastClaim {
+        let (claim, changed) = {
+            let Some(mut tx) = self.transactions.get_mut(uuid) else {
+                return BroadcastClaim::NotFound;
+            };
+            let mut changed = false;
+            if tx.is_stale() {
+                log::warn!("[TxQueue] Not broadcasting {}: expired", uuid);
+                Self::expire(&mut tx, "Not broadcast before it expired; re-sign it with a fresh nonce");
+                changed = true;
+            }
+            if tx.status != QueuedTxStatus::Pending {
+                log::info!("[TxQueue] Not broadcasting {}: already {}", uuid, tx.status);
+                (BroadcastClaim::NotPending(tx.clone()), changed)
+            } else {
+                log::info!("[TxQueue] Claimed {} for broadcast", uuid);
+                tx.set_status(QueuedTxStatus::Broadcasting);
+                (BroadcastClaim::Claimed(tx.clone()), true)
+            }
+        };
+
+        if let (BroadcastClaim::Claimed(tx) | BroadcastClaim::NotPending(tx), true) = (&claim, changed) {
+            self.persist(tx);
         }
+        claim
     }
 
     /// Mark transaction as broadcasting
@@ -112,89 +225,210 @@ impl TxQueueManager {
     /// Mark transaction as broadcast with tx_hash
     /// broadcast_mode: "rogue" or "partner"
     pub fn mark_broadcast(&self, uuid: &str, tx_hash: &str, explorer_url: &str, broadcast_mode: &str) -> bool {
-        if let Some(mut tx) = self.transactions.get_mut(uuid) {
+        let Some(tx) = self.modify(uuid, |tx| {
             log::info!("[TxQueue] Transaction {} broadcast as {} (mode: {})", uuid, tx_hash, broadcast_mode);
-            tx.status = QueuedTxStatus::Broadcast;
+            tx.set_status(QueuedTxStatus::Broadcast);
             tx.tx_hash = Some(tx_hash.to_string());
             tx.explorer_url = Some(explorer_url.to_string());
             tx.broadcast_at = Some(Utc::now());
-
-            // Persist to database if available
-            if let Some(ref db) = self.db {
-                let mode = match broadcast_mode {
-                    "rogue" => BroadcastMode::Rogue,
-                    _ => BroadcastMode::Partner,
-                };
-                let req = RecordBroadcastRequest {
-                    uuid: tx.uuid.clone(),
-                    network: tx.network.clone(),
-                    from_address: tx.from.clone(),
-                    to_address: tx.to.clone(),
-                    value: tx.value.clone(),
-                    value_formatted: tx.format_value_eth(),
-                    tx_hash: Some(tx_hash.to_string()),
-                    explorer_url: Some(explorer_url.to_string()),
-                    broadcast_mode: mode,
-                };
-                if let Err(e) = db.record_broadcast(req) {
-                    log::error!("[TxQueue] Failed to persist broadcast to DB: {}", e);
-                }
+        }) else {
+            return false;
+        };
+        self.persist(&tx);
+
+        // Persist to database if available
+        if let Some(ref db) = self.db {
+            let mode = match broadcast_mode {
+                "rogue" => BroadcastMode::Rogue,
+                _ => BroadcastMode::Partner,
+            };
+            let req = RecordBroadcastRequest {
+                uuid: tx.uuid.clone(),
+                network: tx.network.clone(),
+                from_address: tx.from.clone(),
+                to_address: tx.to.clone(),
+                value: tx.value.clone(),
+                value_formatted: tx.format_value(),
+                tx_hash: Some(tx_hash.to_string()),
+                explorer_url: Some(explorer_url.to_string()),
+                broadcast_mode: mode,
+            };
+            if let Err(e) = db.record_broadcast(req) {
+                log::error!("[TxQueue] Failed to persist broadcast to DB: {}", e);
             }
-
-            true
-        } else {
-            false
         }
+
+        self.expire_superseded(uuid, &tx.network, &tx.from, tx.nonce);
+        true
     }
 
     /// Mark transaction as confirmed
     pub fn mark_confirmed(&self, uuid: &str) -> bool {
-        if let Some(mut tx) = self.transactions.get_mut(uuid) {
+        let Some(tx) = self.modify(uuid, |tx| {
             log::info!("[TxQueue] Transaction {} confirmed", uuid);
-            tx.status = QueuedTxStatus::Confirmed;
-
-            // Update database status if available
-            if let Some(ref db) = self.db {
-                if let Err(e) = db.update_broadcast_status(uuid, BroadcastedTxStatus::Confirmed, None) {
-                    log::error!("[TxQueue] Failed to update DB status: {}", e);
-                }
+            tx.set_status(QueuedTxStatus::Confirmed);
+        }) else {
+            return false;
+        };
+        self.persist(&tx);
+
+        // Update database status if available
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.update_broadcast_status(uuid, BroadcastedTxStatus::Confirmed, None) {
+                log::error!("[TxQueue] Failed to update DB status: {}", e);
             }
-
-            true
-        } else {
-            false
         }
+
+        true
     }
 
     /// Mark transaction as failed with error
     pub fn mark_failed(&self, uuid: &str, error: &str) -> bool {
-        if let Some(mut tx) = self.transactions.get_mut(uuid) {
+        let Some(tx) = self.modify(uuid, |tx| {
             log::warn!("[TxQueue] Transaction {} failed: {}", uuid, error);
-            tx.status = QueuedTxStatus::Failed;
+            tx.set_status(QueuedTxStatus::Failed);
             tx.error = Some(error.to_string());
-
-            // Update database status if available
-            if let Some(ref db) = self.db {
-                if let Err(e) = db.update_broadcast_status(uuid, BroadcastedTxStatus::Failed, Some(error)) {
-                    log::error!("[TxQueue] Failed to update DB status: {}", e);
-                }
+        }) else {
+            return false;
+        };
+        self.persist(&tx);
+
+        // Update database status if available
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.update_broadcast_status(uuid, BroadcastedTxStatus::Failed, Some(error)) {
+                log::error!("[TxQueue] Failed to update DB status: {}", e);
             }
-
-            true
-        } else {
-            false
         }
+
+        true
     }
 
     /// Mark transaction as expired
     pub fn mark_expired(&self, uuid: &str) -> bool {
-        if let Some(mut tx) = self.transactions.get_mut(uuid) {
+        let Some(tx) = self.modify(uuid, |tx| {
             log::warn!("[TxQueue] Transaction {} expired", uuid);
-            tx.status = QueuedTxStatus::Expired;
-            true
-        } else {
-            false
+            tx.set_status(QueuedTxStatus::Expired);
+        }) else {
+            return false;
+        };
+        self.persist(&tx);
+        true
+    }
+
+    fn expire(tx: &mut QueuedTransaction, reason: &str) {
+        tx.set_status(QueuedTxStatus::Expired);
+        tx.error = Some(reason.to_string());
+    }
+
+    /// Expire pending transactions past their TTL. Returns how many expired.
+    pub fn expire_stale(&self) -> usize {
+        // Persisted once the map's shard guards are released
+        let expired: Vec<QueuedTransaction> = self
+            .transactions
+            .iter_mut()
+            .filter_map(|mut entry| {
+                if !entry.is_stale() {
+                    return None;
+                }
+                log::warn!("[TxQueue] Transaction {} expired", entry.key());
+                Self::expire(entry.value_mut(), "Not broadcast before it expired; re-sign it with a fresh nonce");
+                Some(entry.value().clone())
+            })
+            .collect();
+        for tx in &expired {
+            self.persist(tx);
         }
+        expired.len()
+    }
+
+    /// Expire pending transactions from the same sender and network that
+    /// reuse a nonce another transaction was just broadcast with
+    fn expire_superseded(&self, broadcast_uuid: &str, network: &str, from: &str, nonce: u64) {
+        let superseded: Vec<QueuedTransaction> = self
+            .transactions
+            .iter_mut()
+            .filter_map(|mut entry| {
+                let tx = entry.value_mut();
+                if tx.uuid == broadcast_uuid
+                    || tx.status != QueuedTxStatus::Pending
+                    || tx.network != network
+                    || !tx.from.eq_ignore_ascii_case(from)
+                    || tx.nonce != nonce
+                {
+                    return None;
+                }
+                log::warn!("[TxQueue] Transaction {} expired: nonce {} used by {}", tx.uuid, nonce, broadcast_uuid);
+                Self::expire(tx, &format!("Nonce {} was used by transaction {}; re-sign it with a fresh nonce", nonce, broadcast_uuid));
+                Some(tx.clone())
+            })
+            .collect();
+        for tx in &superseded {
+            self.persist(tx);
+        }
+    }
+
+    /// Drop pending transactions whose nonce the sender has already used
+    /// on-chain; they can never be mined. Run after loading a persisted queue.
+    pub async fn reconcile_nonces(&self) -> usize {
+        // Reads go through x402 endpoints when there's a key to pay with,
+        // otherwise through the free ones only
+        let private_key = crate::config::burner_wallet_private_key();
+
+        // One nonce lookup per sender and network
+        let mut senders: HashMap<(String, String), Vec<(String, u64)>> = HashMap::new();
+        for entry in self.transactions.iter() {
+            if entry.status == QueuedTxStatus::Pending {
+                senders
+                    .entry((entry.network.clone(), entry.from.clone()))
+                    .or_default()
+                    .push((entry.uuid.clone(), entry.nonce));
+            }
+        }
+
+        let mut dropped = 0;
+        for ((network, from), queued) in senders {
+            let Ok(address) = from.parse() else { continue };
+            let rpc_config = resolve_rpc_from_network(&network);
+            let rpc = match private_key.as_deref() {
+                Some(key) => X402EvmRpc::new_with_config(key, &network, &rpc_config),
+                None => X402EvmRpc::new_read_only(&network, &rpc_config),
+            };
+            let rpc = match rpc {
+                Ok(rpc) => rpc,
+                Err(e) => {
+                    log::warn!("[TxQueue] Can't reconcile nonces on {}: {}", network, e);
+                    continue;
+                }
+            };
+            let next_nonce = match rpc.get_transaction_count(address).await {
+                Ok(n) => n.as_u64(),
+                Err(e) => {
+                    log::warn!("[TxQueue] Can't reconcile nonces for {} on {}: {}", from, network, e);
+                    continue;
+                }
+            };
+            for (uuid, nonce) in queued {
+                if nonce < next_nonce {
+                    log::info!("[TxQueue] Dropping {}: nonce {} already used on {}", uuid, nonce, network);
+                    self.remove(&uuid);
+                    dropped += 1;
+                }
+            }
+        }
+        dropped
+    }
+
+    /// Periodically expire stale transactions and purge old finished ones
+    pub fn start_sweeper(self: &Arc<Self>) {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                ticker.tick().await;
+                manager.expire_stale();
+                manager.cleanup_old(PURGE_AFTER_HOURS);
+            }
+        });
     }
 
     /// Get count of transactions by status
@@ -212,6 +446,7 @@ impl TxQueueManager {
 
     /// Remove a transaction by UUID (for cleanup)
     pub fn remove(&self, uuid: &str) -> Option<QueuedTransaction> {
+        self.unpersist(uuid);
         self.transactions.remove(uuid).map(|(_, tx)| tx)
     }
 
@@ -231,7 +466,7 @@ impl TxQueueManager {
 
         let count = old_uuids.len();
         for uuid in old_uuids {
-            self.transactions.remove(&uuid);
+            self.remove(&uuid);
         }
 
         if count > 0 {
@@ -312,6 +547,81 @@ mod tests {
         assert_eq!(tx.status, QueuedTxStatus::Confirmed);
     }
 
+    #[test]
+    fn test_claim_for_broadcast_once() {
+        let manager = TxQueueManager::new();
+        manager.queue(create_test_tx("claim-1"));
+
+        assert!(matches!(manager.claim_for_broadcast("claim-1"), BroadcastClaim::Claimed(_)));
+        // A second claim while broadcasting is refused
+        assert!(matches!(
+            manager.claim_for_broadcast("claim-1"),
+            BroadcastClaim::NotPending(tx) if tx.status == QueuedTxStatus::Broadcasting
+        ));
+
+        manager.mark_broadcast("claim-1", "0xhash", "https://basescan.org/tx/0xhash", "rogue");
+        match manager.claim_for_broadcast("claim-1") {
+            BroadcastClaim::NotPending(tx) => {
+                assert!(tx.is_submitted());
+                assert_eq!(tx.tx_hash.as_deref(), Some("0xhash"));
+            }
+            other => panic!("expected NotPending, got {:?}", other),
+        }
+
+        let statuses: Vec<_> = manager.get("claim-1").unwrap().transitions.iter().map(|t| t.status).collect();
+        assert_eq!(
+            statuses,
+            vec![QueuedTxStatus::Pending, QueuedTxStatus::Broadcasting, QueuedTxStatus::Broadcast]
+        );
+
+        assert!(matches!(manager.claim_for_broadcast("missing"), BroadcastClaim::NotFound));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let manager = TxQueueManager::new();
+        let mut stale = create_test_tx("stale-1");
+        stale.expires_at = Utc::now() - chrono::Duration::minutes(1);
+        manager.queue(stale);
+        manager.queue(create_test_tx("fresh-1"));
+
+        assert_eq!(manager.expire_stale(), 1);
+        assert_eq!(manager.get("stale-1").unwrap().status, QueuedTxStatus::Expired);
+        assert_eq!(manager.get("fresh-1").unwrap().status, QueuedTxStatus::Pending);
+        assert!(matches!(manager.claim_for_broadcast("stale-1"), BroadcastClaim::NotPending(_)));
+
+        // Broadcasting another transaction with the same nonce makes this one stale
+        manager.queue(create_test_tx("same-nonce"));
+        manager.claim_for_broadcast("fresh-1");
+        manager.mark_broadcast("fresh-1", "0xhash", "https://basescan.org/tx/0xhash", "rogue");
+        let superseded = manager.get("same-nonce").unwrap();
+        assert_eq!(superseded.status, QueuedTxStatus::Expired);
+        assert!(superseded.error.unwrap().contains("fresh-1"));
+    }
+
+    #[test]
+    fn test_queue_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(dir.path().join("test.db").to_str().unwrap()).unwrap());
+
+        let manager = TxQueueManager::with_db(db.clone());
+        manager.queue(create_test_tx("persist-1"));
+        manager.queue(create_test_tx("persist-2"));
+        manager.claim_for_broadcast("persist-2");
+        manager.queue(create_test_tx("persist-3"));
+        manager.remove("persist-3");
+        drop(manager);
+
+        let reloaded = TxQueueManager::with_db(db);
+        assert_eq!(reloaded.count(), 2);
+        let tx = reloaded.get("persist-1").unwrap();
+        assert_eq!(tx.status, QueuedTxStatus::Pending);
+        assert_eq!(tx.channel_id, Some(1));
+        assert_eq!(tx.signed_tx_hex, "0xabcd");
+        // An interrupted broadcast comes back as pending
+        assert_eq!(reloaded.get("persist-2").unwrap().status, QueuedTxStatus::Pending);
+    }
+
     #[test]
     fn test_list_pending() {
         let manager = TxQueueManager::new();
@@ -8,9 +8,20 @@
 //! 3. `broadcast_web3_tx` broadcasts a transaction by UUID
 //!
 //! This creates a safety layer where transactions can be reviewed before broadcast.
+//! A transaction is claimed for broadcast at most once, so a retried broadcast
+//! reports the original tx hash instead of submitting it again.
+//!
+//! Pending transactions expire after `QUEUED_TX_TTL_MINUTES`, or as soon as
+//! another transaction uses their nonce. Expired ones can't be broadcast, only
+//! re-signed with a fresh nonce, and are purged by a background sweep.
+//!
+//! The queue is persisted in the `queued_transactions` table and reloaded on
+//! startup, dropping transactions whose nonce was used on-chain meanwhile.
 
 mod types;
 mod manager;
 
-pub use types::{QueuedTransaction, QueuedTxStatus, QueuedTxSummary};
-pub use manager::{TxQueueManager, create_tx_queue_manager};
+pub use types::{
+    QueuedTransaction, QueuedTxStatus, QueuedTxSummary, QueuedTxTransition, QUEUED_TX_TTL_MINUTES,
+};
+pub use manager::{BroadcastClaim, TxQueueManager, create_tx_queue_manager};
@@ -3,6 +3,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::tools::builtin::cryptocurrency::network_lookup;
+
+/// How long a signed transaction stays broadcastable. Its nonce is fixed at
+/// signing time, so one left sitting is likely invalidated by later transactions.
+pub const QUEUED_TX_TTL_MINUTES: i64 = 30;
+
 /// Status of a queued transaction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -34,6 +40,29 @@ impl std::fmt::Display for QueuedTxStatus {
     }
 }
 
+impl std::str::FromStr for QueuedTxStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(QueuedTxStatus::Pending),
+            "broadcasting" => Ok(QueuedTxStatus::Broadcasting),
+            "broadcast" => Ok(QueuedTxStatus::Broadcast),
+            "confirmed" => Ok(QueuedTxStatus::Confirmed),
+            "failed" => Ok(QueuedTxStatus::Failed),
+            "expired" => Ok(QueuedTxStatus::Expired),
+            _ => Err(format!("Unknown status: {}", s)),
+        }
+    }
+}
+
+/// A status change of a queued transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedTxTransition {
+    pub status: QueuedTxStatus,
+    pub at: DateTime<Utc>,
+}
+
 /// A queued transaction waiting to be broadcast
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueuedTransaction {
@@ -67,12 +96,17 @@ pub struct QueuedTransaction {
     pub error: Option<String>,
     /// When the transaction was queued
     pub created_at: DateTime<Utc>,
+    /// After this a pending transaction is stale and won't be broadcast
+    pub expires_at: DateTime<Utc>,
     /// When the transaction was broadcast
     pub broadcast_at: Option<DateTime<Utc>>,
     /// Channel ID that queued this transaction
     pub channel_id: Option<i64>,
     /// Explorer URL (set after tx_hash is known)
     pub explorer_url: Option<String>,
+    /// Every status the transaction has been in, oldest first
+    #[serde(default)]
+    pub transitions: Vec<QueuedTxTransition>,
 }
 
 impl QueuedTransaction {
@@ -92,6 +126,7 @@ impl QueuedTransaction {
         signed_tx_hex: String,
         channel_id: Option<i64>,
     ) -> Self {
+        let created_at = Utc::now();
         Self {
             uuid,
             network,
@@ -107,28 +142,50 @@ impl QueuedTransaction {
             status: QueuedTxStatus::Pending,
             tx_hash: None,
             error: None,
-            created_at: Utc::now(),
+            created_at,
+            expires_at: created_at + chrono::Duration::minutes(QUEUED_TX_TTL_MINUTES),
             broadcast_at: None,
             channel_id,
             explorer_url: None,
+            transitions: vec![QueuedTxTransition {
+                status: QueuedTxStatus::Pending,
+                at: created_at,
+            }],
         }
     }
 
-    /// Get the explorer URL for this transaction's network
-    pub fn get_explorer_base_url(&self) -> &'static str {
-        if self.network == "mainnet" {
-            "https://etherscan.io/tx"
-        } else {
-            "https://basescan.org/tx"
-        }
+    /// Move to a new status, recording the transition
+    pub fn set_status(&mut self, status: QueuedTxStatus) {
+        self.status = status;
+        self.transitions.push(QueuedTxTransition {
+            status,
+            at: Utc::now(),
+        });
+    }
+
+    /// Whether a pending transaction has outlived its TTL
+    pub fn is_stale(&self) -> bool {
+        self.status == QueuedTxStatus::Pending && Utc::now() >= self.expires_at
     }
 
-    /// Format value as human-readable ETH
-    pub fn format_value_eth(&self) -> String {
+    /// Whether the signed transaction has already been submitted to the network
+    pub fn is_submitted(&self) -> bool {
+        matches!(self.status, QueuedTxStatus::Broadcast | QueuedTxStatus::Confirmed)
+            || self.tx_hash.is_some()
+    }
+
+    /// Explorer URL for a hash of this transaction, from the network's
+    /// configured explorer. Empty if the network has none.
+    pub fn explorer_url_for(&self, tx_hash: &str) -> String {
+        network_lookup::explorer_tx_url(&self.network, tx_hash).unwrap_or_default()
+    }
+
+    /// Format value as a human-readable amount of the network's native token
+    pub fn format_value(&self) -> String {
         if let Ok(w) = self.value.parse::<u128>() {
-            let eth = w as f64 / 1e18;
-            if eth >= 0.0001 {
-                format!("{:.6} ETH", eth)
+            let amount = w as f64 / 1e18;
+            if amount >= 0.0001 {
+                format!("{:.6} {}", amount, network_lookup::native_token_for(&self.network))
             } else {
                 format!("{} wei", self.value)
             }
@@ -153,7 +210,9 @@ pub struct QueuedTxSummary {
     pub tx_hash: Option<String>,
     pub explorer_url: Option<String>,
     pub error: Option<String>,
+    pub channel_id: Option<i64>,
     pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
     pub broadcast_at: Option<DateTime<Utc>>,
 }
 
@@ -165,13 +224,15 @@ impl From<&QueuedTransaction> for QueuedTxSummary {
             from: tx.from.clone(),
             to: tx.to.clone(),
             value: tx.value.clone(),
-            value_formatted: tx.format_value_eth(),
+            value_formatted: tx.format_value(),
             data: tx.data.clone(),
             status: tx.status,
             tx_hash: tx.tx_hash.clone(),
             explorer_url: tx.explorer_url.clone(),
             error: tx.error.clone(),
+            channel_id: tx.channel_id,
             created_at: tx.created_at,
+            expires_at: tx.expires_at,
             broadcast_at: tx.broadcast_at,
         }
     }
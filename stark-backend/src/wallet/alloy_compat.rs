@@ -0,0 +1,48 @@
+//! `alloy` signer adapter for SDKs built on alloy (e.g. the Polymarket client)
+
+use alloy_primitives::{Address, ChainId, Signature, B256};
+use async_trait::async_trait;
+use ethers::types::H256;
+use std::sync::Arc;
+
+use super::WalletSigner;
+
+/// Exposes a `WalletSigner` through alloy's `Signer` trait
+#[derive(Debug, Clone)]
+pub struct AlloyWalletSigner {
+    inner: Arc<WalletSigner>,
+    chain_id: Option<ChainId>,
+}
+
+impl AlloyWalletSigner {
+    pub fn new(inner: WalletSigner, chain_id: Option<ChainId>) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            chain_id,
+        }
+    }
+}
+
+#[async_trait]
+impl alloy_signer::Signer for AlloyWalletSigner {
+    async fn sign_hash(&self, hash: &B256) -> alloy_signer::Result<Signature> {
+        let signature = self
+            .inner
+            .sign_hash(H256::from(hash.0))
+            .await
+            .map_err(alloy_signer::Error::other)?;
+        Signature::from_raw(&signature.to_vec()).map_err(alloy_signer::Error::other)
+    }
+
+    fn address(&self) -> Address {
+        Address::from(self.inner.address().0)
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.chain_id = chain_id;
+    }
+}
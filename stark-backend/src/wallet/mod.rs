@@ -0,0 +1,335 @@
+//! Wallet signing abstraction
+//!
+//! Tools that sign transactions or orders go through `WalletSigner` instead of
+//! reading the burner private key themselves. Two backends are supported,
+//! selected by `STARK_WALLET_SIGNER`:
+//!
+//! - `local` (default, development): signs in-process with
+//!   `BURNER_WALLET_BOT_PRIVATE_KEY`
+//! - `keystore` (production): digests are sent to the keystore signer API,
+//!   authenticated with `STARK_KEYSTORE_SIGNER_TOKEN`, so the signing key never
+//!   enters this process
+//!
+//! Further wallets (e.g. "trading", "gas", "treasury") are configured in
+//! `STARK_WALLETS` as `name=<private key>` or `name=keystore` entries. Tools
+//! take an optional `wallet` param; without one they use the channel's
+//! `wallet` setting, then the primary wallet.
+//!
+//! x402 payments for RPC calls are separate and still use the burner key.
+
+mod alloy_compat;
+
+pub use alloy_compat::AlloyWalletSigner;
+
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Signature, H256};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::config::{self, WalletSignerMode};
+use crate::keystore_client::KEYSTORE_CLIENT;
+
+/// Name of the wallet configured by `BURNER_WALLET_BOT_PRIVATE_KEY` /
+/// `STARK_WALLET_SIGNER`
+pub const PRIMARY_WALLET: &str = "primary";
+
+/// Description of the optional `wallet` param shared by wallet tools
+pub const WALLET_PARAM_DESCRIPTION: &str = "Named wallet to use (e.g. 'trading', 'treasury'). If not specified, uses this channel's wallet setting or the primary wallet.";
+
+/// `STARK_WALLETS` source value for keystore-held wallets
+const KEYSTORE_SOURCE: &str = "keystore";
+
+/// Addresses of keystore-held wallets by name, fetched once per process
+static KEYSTORE_ADDRESSES: Lazy<Mutex<HashMap<String, Address>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A configured wallet as listed by the API (never includes keys)
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletInfo {
+    pub name: String,
+    /// `None` if the address couldn't be resolved (see `error`)
+    pub address: Option<String>,
+    /// "local" or "keystore"
+    pub signer: &'static str,
+    pub primary: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Names of all configured wallets, primary first
+pub fn wallet_names() -> Vec<String> {
+    std::iter::once(PRIMARY_WALLET.to_string())
+        .chain(config::named_wallets().into_iter().map(|(name, _)| name))
+        .collect()
+}
+
+/// List configured wallets with their addresses
+pub async fn list_wallets() -> Vec<WalletInfo> {
+    let mut wallets = Vec::new();
+    for name in wallet_names() {
+        let primary = name == PRIMARY_WALLET;
+        let signer = match wallet_source(&name) {
+            Some(source) if source.eq_ignore_ascii_case(KEYSTORE_SOURCE) => "keystore",
+            Some(_) => "local",
+            None => match config::wallet_signer_mode() {
+                WalletSignerMode::Local => "local",
+                WalletSignerMode::Keystore => "keystore",
+            },
+        };
+        let (address, error) = match WalletSigner::for_wallet(Some(&name)).await {
+            Ok(wallet) => (Some(format!("{:?}", wallet.address())), None),
+            Err(e) => (None, Some(e)),
+        };
+        wallets.push(WalletInfo {
+            name,
+            address,
+            signer,
+            primary,
+            error,
+        });
+    }
+    wallets
+}
+
+/// The `STARK_WALLETS` source for a named (non-primary) wallet
+fn wallet_source(name: &str) -> Option<String> {
+    config::named_wallets()
+        .into_iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, source)| source)
+}
+
+/// Address of a wallet without any network access: derived from the key for
+/// local wallets, or from the cache for keystore wallets already resolved
+pub fn cached_address(name: Option<&str>) -> Option<Address> {
+    let name = normalize_name(name);
+    if name == PRIMARY_WALLET {
+        return match config::wallet_signer_mode() {
+            WalletSignerMode::Local => WalletSigner::from_private_key(&config::burner_wallet_private_key()?)
+                .ok()
+                .map(|w| w.address()),
+            WalletSignerMode::Keystore => KEYSTORE_ADDRESSES.lock().ok()?.get(&name).copied(),
+        };
+    }
+    match wallet_source(&name)? {
+        source if source.eq_ignore_ascii_case(KEYSTORE_SOURCE) => {
+            KEYSTORE_ADDRESSES.lock().ok()?.get(&name).copied()
+        }
+        private_key => WalletSigner::from_private_key(&private_key).ok().map(|w| w.address()),
+    }
+}
+
+fn normalize_name(name: Option<&str>) -> String {
+    name.map(|n| n.trim().to_lowercase())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| PRIMARY_WALLET.to_string())
+}
+
+/// Fetch (or reuse) the address of a keystore-held wallet
+async fn keystore_address(token: &str, name: &str) -> Result<Address, String> {
+    if let Some(address) = KEYSTORE_ADDRESSES.lock().ok().and_then(|c| c.get(name).copied()) {
+        return Ok(address);
+    }
+
+    let wallet = (name != PRIMARY_WALLET).then_some(name);
+    let address = KEYSTORE_CLIENT
+        .signer_address(token, wallet)
+        .await?
+        .parse::<Address>()
+        .map_err(|e| format!("Invalid keystore signer address: {}", e))?;
+    log::info!("[Wallet] Using keystore signer {:?} for wallet '{}'", address, name);
+
+    if let Ok(mut cache) = KEYSTORE_ADDRESSES.lock() {
+        cache.insert(name.to_string(), address);
+    }
+    Ok(address)
+}
+
+/// Signs digests and transactions with the bot wallet
+#[derive(Debug, Clone)]
+pub enum WalletSigner {
+    /// In-process wallet from a private key
+    Local(LocalWallet),
+    /// Remote wallet held by the keystore. `wallet` is the keystore's name
+    /// for it (`None` for the primary wallet).
+    Keystore {
+        address: Address,
+        token: String,
+        wallet: Option<String>,
+    },
+}
+
+impl WalletSigner {
+    /// Build the signer for the primary wallet
+    pub async fn from_env() -> Result<Self, String> {
+        Self::for_wallet(None).await
+    }
+
+    /// Build the signer for a named wallet (`None` or "primary" for the
+    /// primary wallet)
+    pub async fn for_wallet(name: Option<&str>) -> Result<Self, String> {
+        let name = normalize_name(name);
+        if name == PRIMARY_WALLET {
+            if config::wallet_signer_mode() == WalletSignerMode::Local {
+                let private_key = config::burner_wallet_private_key()
+                    .ok_or("BURNER_WALLET_BOT_PRIVATE_KEY not set")?;
+                return Self::from_private_key(&private_key);
+            }
+        } else {
+            match wallet_source(&name) {
+                Some(source) if source.eq_ignore_ascii_case(KEYSTORE_SOURCE) => {}
+                Some(private_key) => {
+                    return Self::from_private_key(&private_key)
+                        .map_err(|e| format!("Wallet '{}': {}", name, e));
+                }
+                None => {
+                    return Err(format!(
+                        "Unknown wallet '{}'. Available wallets: {}",
+                        name,
+                        wallet_names().join(", ")
+                    ));
+                }
+            }
+        }
+
+        // Keystore-held wallet
+        let token = config::keystore_signer_token()
+            .ok_or_else(|| format!("{} not set", config::env_vars::KEYSTORE_SIGNER_TOKEN))?;
+        let address = keystore_address(&token, &name).await?;
+        Ok(Self::Keystore {
+            address,
+            token,
+            wallet: (name != PRIMARY_WALLET).then_some(name),
+        })
+    }
+
+    /// Find the configured wallet with the given address (used to re-sign a
+    /// transaction from the wallet that originally signed it)
+    pub async fn for_address(address: &str) -> Result<Self, String> {
+        let target: Address = address
+            .parse()
+            .map_err(|_| format!("Invalid address: {}", address))?;
+        for name in wallet_names() {
+            if let Ok(signer) = Self::for_wallet(Some(&name)).await {
+                if signer.address() == target {
+                    return Ok(signer);
+                }
+            }
+        }
+        Err(format!("No configured wallet has address {}", address))
+    }
+
+    /// Build a local signer from a hex private key (with or without 0x)
+    pub fn from_private_key(private_key: &str) -> Result<Self, String> {
+        private_key
+            .strip_prefix("0x")
+            .unwrap_or(private_key)
+            .parse::<LocalWallet>()
+            .map(Self::Local)
+            .map_err(|e| format!("Invalid private key: {}", e))
+    }
+
+    /// The wallet address
+    pub fn address(&self) -> Address {
+        match self {
+            Self::Local(wallet) => wallet.address(),
+            Self::Keystore { address, .. } => *address,
+        }
+    }
+
+    /// Sign a 32-byte digest. The returned signature has `v` = 27 or 28.
+    pub async fn sign_hash(&self, hash: H256) -> Result<Signature, String> {
+        match self {
+            Self::Local(wallet) => wallet
+                .sign_hash(hash)
+                .map_err(|e| format!("Failed to sign: {}", e)),
+            Self::Keystore {
+                address,
+                token,
+                wallet,
+            } => {
+                let signature_hex = KEYSTORE_CLIENT.sign_hash(token, wallet.as_deref(), hash.0).await?;
+                let bytes = hex::decode(signature_hex.trim_start_matches("0x"))
+                    .map_err(|e| format!("Invalid signature from keystore: {}", e))?;
+                let mut signature = Signature::try_from(bytes.as_slice())
+                    .map_err(|e| format!("Invalid signature from keystore: {}", e))?;
+                if signature.v < 27 {
+                    signature.v += 27;
+                }
+
+                // Never hand back a signature the keystore made with another key
+                let signer = signature
+                    .recover(hash)
+                    .map_err(|e| format!("Invalid signature from keystore: {}", e))?;
+                if signer != *address {
+                    return Err(format!(
+                        "Keystore signed with {:?}, expected {:?}",
+                        signer, address
+                    ));
+                }
+                Ok(signature)
+            }
+        }
+    }
+
+    /// Sign a transaction. The transaction must carry its chain ID.
+    pub async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String> {
+        let chain_id = tx
+            .chain_id()
+            .map(|id| id.as_u64())
+            .ok_or("Transaction has no chain ID")?;
+
+        match self {
+            Self::Local(wallet) => wallet
+                .clone()
+                .with_chain_id(chain_id)
+                .sign_transaction(tx)
+                .await
+                .map_err(|e| format!("Failed to sign transaction: {}", e)),
+            Self::Keystore { .. } => {
+                let mut signature = self.sign_hash(tx.sighash()).await?;
+                // EIP-155 v, as the local signer produces it
+                let recovery_id = signature.v - 27;
+                signature.v = recovery_id + 35 + chain_id * 2;
+                Ok(signature)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::transaction::eip1559::Eip1559TransactionRequest;
+
+    const TEST_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    #[tokio::test]
+    async fn test_local_sign_hash_recovers_address() {
+        let signer = WalletSigner::from_private_key(TEST_KEY).unwrap();
+        let hash = H256::from(ethers::utils::keccak256(b"stark"));
+        let signature = signer.sign_hash(hash).await.unwrap();
+        assert!(signature.v == 27 || signature.v == 28);
+        assert_eq!(signature.recover(hash).unwrap(), signer.address());
+    }
+
+    #[tokio::test]
+    async fn test_local_sign_transaction() {
+        let signer = WalletSigner::from_private_key(TEST_KEY).unwrap();
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::zero())
+            .value(1u64)
+            .nonce(0u64)
+            .chain_id(8453u64)
+            .into();
+
+        let signature = signer.sign_transaction(&tx).await.unwrap();
+        assert_eq!(signature.recover(tx.sighash()).unwrap(), signer.address());
+
+        let unsigned: TypedTransaction = Eip1559TransactionRequest::new().into();
+        assert!(signer.sign_transaction(&unsigned).await.is_err());
+    }
+}
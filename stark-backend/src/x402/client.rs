@@ -6,6 +6,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use super::signer::X402Signer;
+use super::policy::PaymentPolicy;
 use super::types::{PaymentRequired, X402PaymentInfo};
 
 /// Result of a request that may have required payment
@@ -109,28 +110,61 @@ impl X402Client {
 
         log::info!("[X402] Received 402 Payment Required");
 
-        // Get payment requirements from header
-        let payment_header = initial_response
-            .headers()
-            .get("payment-required")
-            .or_else(|| initial_response.headers().get("PAYMENT-REQUIRED"))
-            .ok_or_else(|| "402 response missing payment-required header".to_string())?
-            .to_str()
-            .map_err(|e| format!("Invalid payment-required header: {}", e))?;
+        let payment_required = parse_challenge(&initial_response)?;
+        self.pay(&payment_required, build_request).await
+    }
+
+    /// Make a GET request without paying, returning the payment challenge
+    /// if the endpoint answers 402 and `None` if it needs no payment
+    pub async fn get_challenge(&self, url: &str) -> Result<Option<PaymentRequired>, String> {
+        log::info!("[X402] Inspecting payment challenge for {}", url);
+
+        let response = self.client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
 
-        let payment_required = PaymentRequired::from_base64(payment_header)?;
+        if response.status().as_u16() != 402 {
+            log::info!("[X402] No payment required, status: {}", response.status());
+            return Ok(None);
+        }
+        parse_challenge(&response).map(Some)
+    }
+
+    /// Make a GET request paying a challenge captured earlier with
+    /// `get_challenge`, skipping the unpaid first request
+    pub async fn get_with_challenge(
+        &self,
+        url: &str,
+        payment_required: &PaymentRequired,
+    ) -> Result<X402Response, String> {
+        log::info!("[X402] Making GET request to {} with a pre-inspected challenge", url);
+
+        self.pay(payment_required, || self.client.get(url)).await
+    }
+
+    /// Sign a payment for a challenge and send the request with it
+    async fn pay<F>(
+        &self,
+        payment_required: &PaymentRequired,
+        build_request: F,
+    ) -> Result<X402Response, String>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        // Use the first option in an allowed asset, on the most preferred network
+        let requirements = PaymentPolicy::from_env()
+            .select(&payment_required.accepts, None, |a| (a.network.as_str(), a.asset.as_str()))?;
 
         log::info!(
-            "[X402] Payment requirements: {} {} to {}",
-            payment_required.accepts.first().map(|a| a.max_amount_required.as_str()).unwrap_or("?"),
-            payment_required.accepts.first().map(|a| a.asset.as_str()).unwrap_or("?"),
-            payment_required.accepts.first().map(|a| a.pay_to_address.as_str()).unwrap_or("?")
+            "[X402] Payment requirements: {} {} on {} to {}",
+            requirements.max_amount_required,
+            requirements.asset,
+            requirements.network,
+            requirements.pay_to_address
         );
 
-        // Get the first (and typically only) payment option
-        let requirements = payment_required.accepts.first()
-            .ok_or_else(|| "No payment options in 402 response".to_string())?;
-
         // Create payment info before signing
         let payment_info = X402PaymentInfo::from_requirements(requirements);
 
@@ -232,6 +266,19 @@ impl X402Client {
     }
 }
 
+/// Payment requirements from a 402 response's payment-required header
+fn parse_challenge(response: &Response) -> Result<PaymentRequired, String> {
+    let payment_header = response
+        .headers()
+        .get("payment-required")
+        .or_else(|| response.headers().get("PAYMENT-REQUIRED"))
+        .ok_or_else(|| "402 response missing payment-required header".to_string())?
+        .to_str()
+        .map_err(|e| format!("Invalid payment-required header: {}", e))?;
+
+    PaymentRequired::from_base64(payment_header)
+}
+
 /// Check if a URL is a defirelay endpoint that uses x402
 pub fn is_x402_endpoint(url: &str) -> bool {
     url.contains("defirelay.com") || url.contains("defirelay.io")
@@ -2,26 +2,50 @@
 //!
 //! Provides high-level EVM RPC methods using defirelay.com with x402 payments.
 //! RPC calls can go through x402 payment protocol or regular HTTP depending on config.
+//! When configured with a failover list, connection errors, timeouts and 5xx
+//! responses move on to the next provider.
 
 use ethers::types::{Address, Bytes, H256, U256, U64};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::time::Duration;
 
-use super::client::X402Client;
+use super::client::{X402Client, X402Response};
+use crate::tools::rpc_config::{ResolvedRpcConfig, RpcEndpoint};
 
 /// Default RPC endpoints for defirelay (used when no custom config)
 const DEFAULT_RPC_BASE: &str = "https://rpc.defirelay.com/rpc/light/base";
 const DEFAULT_RPC_MAINNET: &str = "https://rpc.defirelay.com/rpc/light/mainnet";
 
+/// Per-provider timeout for each failover attempt, so a hung provider can't
+/// stall the call
+pub const FAILOVER_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// HTTP client for read-only RPC clients, which have no x402 client to send with
+static READ_ONLY_HTTP: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(120))
+        .build()
+        .unwrap_or_default()
+});
+
 /// X402-backed EVM RPC client
 pub struct X402EvmRpc {
-    client: X402Client,
+    /// Pays x402 endpoints; `None` for read-only clients, which only use
+    /// endpoints that need no payment
+    client: Option<X402Client>,
     network: String,
-    /// Custom RPC URL (overrides default based on network)
-    rpc_url: Option<String>,
-    /// Whether to use x402 payment for RPC calls
-    use_x402: bool,
+    /// Ordered RPC endpoints (primary first)
+    endpoints: Vec<RpcEndpoint>,
+}
+
+/// Outcome of a failed call against a single endpoint
+enum AttemptError {
+    /// Provider unreachable or unhealthy - try the next one
+    Unavailable(String),
+    /// Provider answered; the error applies regardless of provider
+    Fatal(String),
 }
 
 /// JSON-RPC request structure
@@ -67,56 +91,70 @@ impl X402EvmRpc {
     /// Create a new X402 EVM RPC client with default settings (x402 enabled)
     pub fn new(private_key: &str, network: &str) -> Result<Self, String> {
         let client = X402Client::new(private_key)?;
+        let url = match network {
+            "mainnet" => DEFAULT_RPC_MAINNET,
+            _ => DEFAULT_RPC_BASE,
+        };
         Ok(Self {
-            client,
+            client: Some(client),
             network: network.to_string(),
-            rpc_url: None,
-            use_x402: true,
+            endpoints: vec![RpcEndpoint {
+                provider: "defirelay".to_string(),
+                url: url.to_string(),
+                use_x402: true,
+            }],
         })
     }
 
-    /// Create a new X402 EVM RPC client with custom configuration
+    /// Create a new X402 EVM RPC client from a resolved RPC configuration,
+    /// failing over through its endpoints in order
     pub fn new_with_config(
         private_key: &str,
         network: &str,
-        rpc_url: Option<String>,
-        use_x402: bool,
+        rpc_config: &ResolvedRpcConfig,
     ) -> Result<Self, String> {
+        if rpc_config.endpoints.is_empty() {
+            return Err(format!("No RPC endpoints configured for {}", network));
+        }
         let client = X402Client::new(private_key)?;
         Ok(Self {
-            client,
+            client: Some(client),
             network: network.to_string(),
-            rpc_url,
-            use_x402,
+            endpoints: rpc_config.endpoints.clone(),
         })
     }
 
-    /// Get the RPC endpoint URL for the current network
-    fn rpc_url(&self) -> String {
-        if let Some(ref url) = self.rpc_url {
-            url.clone()
-        } else {
-            match self.network.as_str() {
-                "mainnet" => DEFAULT_RPC_MAINNET.to_string(),
-                _ => DEFAULT_RPC_BASE.to_string(),
-            }
+    /// Create a client without a payment key. Only the endpoints that don't
+    /// use x402 are kept, so reads work when no burner wallet is configured.
+    pub fn new_read_only(network: &str, rpc_config: &ResolvedRpcConfig) -> Result<Self, String> {
+        let endpoints: Vec<RpcEndpoint> = rpc_config
+            .endpoints
+            .iter()
+            .filter(|e| !e.use_x402)
+            .cloned()
+            .collect();
+        if endpoints.is_empty() {
+            return Err(format!("No RPC endpoint for {} works without x402 payment", network));
         }
+        Ok(Self {
+            client: None,
+            network: network.to_string(),
+            endpoints,
+        })
     }
 
-    /// Check if x402 payment is enabled
+    /// Check if x402 payment is enabled (for the primary endpoint)
     pub fn uses_x402(&self) -> bool {
-        self.use_x402
+        self.endpoints[0].use_x402
     }
 
     /// Get the chain ID for the current network
     pub fn chain_id(&self) -> u64 {
-        match self.network.as_str() {
-            "mainnet" => 1,
-            _ => 8453, // Base
-        }
+        crate::tools::builtin::cryptocurrency::network_lookup::chain_id_for(&self.network)
+            .unwrap_or(8453) // Base
     }
 
-    /// Make a JSON-RPC call via x402 or regular HTTP depending on config
+    /// Make a JSON-RPC call, failing over through the configured endpoints
     async fn rpc_call(&self, method: &str, params: Value) -> Result<Value, String> {
         let request = JsonRpcRequest {
             jsonrpc: "2.0",
@@ -125,22 +163,7 @@ impl X402EvmRpc {
             id: 1,
         };
 
-        let url = self.rpc_url();
-        log::debug!("[X402EvmRpc] {} to {} with params: {:?} (x402={})", method, url, request.params, self.use_x402);
-
-        let response = if self.use_x402 {
-            self.client.post_with_payment(&url, &request).await?
-        } else {
-            self.client.post_regular(&url, &request).await?
-        };
-
-        let status = response.response.status();
-        let body = response.response.text().await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
-
-        if !status.is_success() {
-            return Err(format!("RPC error ({}) from {}: {}", status, url, if body.is_empty() { "empty response" } else { &body }));
-        }
+        let body = self.post_with_failover(method, &request).await?;
 
         let rpc_response: JsonRpcResponse = serde_json::from_str(&body)
             .map_err(|e| format!("Failed to parse RPC response: {} - body: {}", e, body))?;
@@ -152,6 +175,147 @@ impl X402EvmRpc {
         rpc_response.result.ok_or_else(|| "RPC returned null result".to_string())
     }
 
+    /// Send several JSON-RPC calls as a single batch request.
+    /// Results are returned in input order; each item fails independently.
+    pub async fn batch_call(
+        &self,
+        calls: Vec<(String, Value)>,
+    ) -> Result<Vec<Result<Value, String>>, String> {
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let requests: Vec<JsonRpcRequest> = calls
+            .into_iter()
+            .enumerate()
+            .map(|(i, (method, params))| JsonRpcRequest {
+                jsonrpc: "2.0",
+                method,
+                params,
+                id: i as u64,
+            })
+            .collect();
+
+        let label = format!("batch of {}", requests.len());
+        let body = self.post_with_failover(&label, &requests).await?;
+
+        let responses: Vec<JsonRpcResponse> = serde_json::from_str(&body)
+            .map_err(|e| format!("Failed to parse batch RPC response: {} - body: {}", e, body))?;
+
+        let mut results: Vec<Result<Value, String>> = (0..requests.len())
+            .map(|_| Err("No response for this request".to_string()))
+            .collect();
+        for response in responses {
+            let index = response.id as usize;
+            if index >= results.len() {
+                continue;
+            }
+            results[index] = match response.error {
+                Some(error) => Err(format!("RPC error {}: {}", error.code, error.message)),
+                None => response.result.ok_or_else(|| "RPC returned null result".to_string()),
+            };
+        }
+
+        Ok(results)
+    }
+
+    /// POST a JSON-RPC payload, failing over through the configured endpoints.
+    /// Returns the response body from the first healthy provider.
+    async fn post_with_failover<T: Serialize>(&self, label: &str, body: &T) -> Result<String, String> {
+        let last = self.endpoints.len() - 1;
+        let mut failures = Vec::new();
+
+        for (i, endpoint) in self.endpoints.iter().enumerate() {
+            let attempt = self.post_endpoint(endpoint, label, body);
+            let result = match tokio::time::timeout(FAILOVER_ATTEMPT_TIMEOUT, attempt).await {
+                Ok(result) => result,
+                Err(_) => Err(AttemptError::Unavailable(format!(
+                    "Timed out after {}s waiting for {}",
+                    FAILOVER_ATTEMPT_TIMEOUT.as_secs(),
+                    endpoint.url
+                ))),
+            };
+
+            match result {
+                Ok(body) => {
+                    log::info!(
+                        "[X402EvmRpc] {} on {} served by '{}'{}",
+                        label,
+                        self.network,
+                        endpoint.provider,
+                        if i > 0 { format!(" (after {} failover)", i) } else { String::new() }
+                    );
+                    return Ok(body);
+                }
+                Err(AttemptError::Fatal(e)) => return Err(e),
+                Err(AttemptError::Unavailable(e)) => {
+                    if i < last {
+                        log::warn!(
+                            "[X402EvmRpc] {} via '{}' failed: {} - trying '{}'",
+                            label,
+                            endpoint.provider,
+                            e,
+                            self.endpoints[i + 1].provider
+                        );
+                    }
+                    failures.push((endpoint.provider.clone(), e));
+                }
+            }
+        }
+
+        if failures.len() == 1 {
+            return Err(failures.remove(0).1);
+        }
+        Err(format!(
+            "All RPC providers failed for {}: {}",
+            label,
+            failures
+                .iter()
+                .map(|(provider, e)| format!("{}: {}", provider, e))
+                .collect::<Vec<_>>()
+                .join("; ")
+        ))
+    }
+
+    /// POST a payload to a single endpoint via x402 or regular HTTP
+    async fn post_endpoint<T: Serialize>(
+        &self,
+        endpoint: &RpcEndpoint,
+        label: &str,
+        body: &T,
+    ) -> Result<String, AttemptError> {
+        let url = &endpoint.url;
+        log::debug!("[X402EvmRpc] {} to {} (x402={})", label, url, endpoint.use_x402);
+
+        let response = match &self.client {
+            Some(client) if endpoint.use_x402 => client.post_with_payment(url, body).await,
+            Some(client) => client.post_regular(url, body).await,
+            None => READ_ONLY_HTTP
+                .post(url)
+                .json(body)
+                .send()
+                .await
+                .map(|response| X402Response { response, payment: None })
+                .map_err(|e| format!("Request failed: {}", e)),
+        }
+        .map_err(AttemptError::Unavailable)?;
+
+        let status = response.response.status();
+        let body = response.response.text().await
+            .map_err(|e| AttemptError::Unavailable(format!("Failed to read response: {}", e)))?;
+
+        if !status.is_success() {
+            let msg = format!("RPC error ({}) from {}: {}", status, url, if body.is_empty() { "empty response" } else { &body });
+            return Err(if status.is_server_error() || status.as_u16() == 429 {
+                AttemptError::Unavailable(msg)
+            } else {
+                AttemptError::Fatal(msg)
+            });
+        }
+
+        Ok(body)
+    }
+
     /// Get ETH balance of an address
     /// Returns balance in wei
     pub async fn get_balance(&self, address: Address) -> Result<U256, String> {
@@ -250,17 +414,112 @@ impl X402EvmRpc {
         Ok((max_fee, capped_priority_fee))
     }
 
-    /// Send a raw signed transaction
+    /// Send a raw signed transaction, failing over through the configured
+    /// endpoints. Returns the hash computed from the signed bytes.
+    ///
+    /// A provider can accept the transaction and still time out or error, so
+    /// once an attempt may have reached one the send is never reported as
+    /// failed: later "already known"/"nonce too low" answers and errors are
+    /// treated as submitted, and receipt polling decides the outcome.
     pub async fn send_raw_transaction(&self, signed_tx: &[u8]) -> Result<H256, String> {
-        let params = json!([format!("0x{}", hex::encode(signed_tx))]);
-
-        let result = self.rpc_call("eth_sendRawTransaction", params).await?;
+        let tx_hash = H256::from(ethers::utils::keccak256(signed_tx));
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            method: "eth_sendRawTransaction".to_string(),
+            params: json!([format!("0x{}", hex::encode(signed_tx))]),
+            id: 1,
+        };
 
-        let hash_hex = result.as_str()
-            .ok_or_else(|| "Invalid sendRawTransaction response".to_string())?;
+        let last = self.endpoints.len() - 1;
+        let mut maybe_sent = false;
+        let mut failures = Vec::new();
+
+        for (i, endpoint) in self.endpoints.iter().enumerate() {
+            let attempt = self.post_endpoint(endpoint, "eth_sendRawTransaction", &request);
+            let error = match tokio::time::timeout(FAILOVER_ATTEMPT_TIMEOUT, attempt).await {
+                Ok(Ok(body)) => match serde_json::from_str::<JsonRpcResponse>(&body) {
+                    Ok(JsonRpcResponse { error: Some(error), .. }) => {
+                        AttemptError::Fatal(format!("RPC error {}: {}", error.code, error.message))
+                    }
+                    Ok(JsonRpcResponse { result, .. }) => {
+                        if let Some(hash) = result.as_ref().and_then(|r| r.as_str()) {
+                            if hash.parse::<H256>().ok() != Some(tx_hash) {
+                                log::warn!(
+                                    "[X402EvmRpc] '{}' returned hash {} for transaction {:?}",
+                                    endpoint.provider,
+                                    hash,
+                                    tx_hash
+                                );
+                            }
+                        }
+                        log::info!(
+                            "[X402EvmRpc] Transaction {:?} on {} sent via '{}'",
+                            tx_hash,
+                            self.network,
+                            endpoint.provider
+                        );
+                        return Ok(tx_hash);
+                    }
+                    Err(e) => AttemptError::Unavailable(format!(
+                        "Failed to parse RPC response: {} - body: {}",
+                        e, body
+                    )),
+                },
+                Ok(Err(e)) => e,
+                Err(_) => AttemptError::Unavailable(format!(
+                    "Timed out after {}s waiting for {}",
+                    FAILOVER_ATTEMPT_TIMEOUT.as_secs(),
+                    endpoint.url
+                )),
+            };
+
+            match error {
+                AttemptError::Fatal(e) => {
+                    if treat_as_submitted(&e, maybe_sent) {
+                        log::warn!(
+                            "[X402EvmRpc] '{}' answered '{}' for transaction {:?}; treating it as submitted",
+                            endpoint.provider,
+                            e,
+                            tx_hash
+                        );
+                        return Ok(tx_hash);
+                    }
+                    return Err(e);
+                }
+                AttemptError::Unavailable(e) => {
+                    maybe_sent |= may_have_reached_provider(&e);
+                    if i < last {
+                        log::warn!(
+                            "[X402EvmRpc] eth_sendRawTransaction via '{}' failed: {} - trying '{}'",
+                            endpoint.provider,
+                            e,
+                            self.endpoints[i + 1].provider
+                        );
+                    }
+                    failures.push((endpoint.provider.clone(), e));
+                }
+            }
+        }
 
-        hash_hex.parse()
-            .map_err(|e| format!("Failed to parse tx hash: {}", e))
+        if maybe_sent {
+            log::warn!(
+                "[X402EvmRpc] Transaction {:?} may have been sent on {} despite errors; leaving it to receipt polling",
+                tx_hash,
+                self.network
+            );
+            return Ok(tx_hash);
+        }
+        if failures.len() == 1 {
+            return Err(failures.remove(0).1);
+        }
+        Err(format!(
+            "All RPC providers failed for eth_sendRawTransaction: {}",
+            failures
+                .iter()
+                .map(|(provider, e)| format!("{}: {}", provider, e))
+                .collect::<Vec<_>>()
+                .join("; ")
+        ))
     }
 
     /// Get transaction receipt
@@ -279,6 +538,24 @@ impl X402EvmRpc {
         Ok(Some(receipt))
     }
 
+    /// Get a transaction receipt with its logs, sender and recipient
+    pub async fn get_full_transaction_receipt(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<ethers::types::TransactionReceipt>, String> {
+        let params = json!([format!("{:?}", tx_hash)]);
+
+        let result = self.rpc_call("eth_getTransactionReceipt", params).await?;
+
+        if result.is_null() {
+            return Ok(None);
+        }
+
+        serde_json::from_value(result)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse receipt: {}", e))
+    }
+
     /// Get transaction count (nonce) for an address
     pub async fn get_transaction_count(&self, address: Address) -> Result<U256, String> {
         let params = json!([format!("{:?}", address), "pending"]);
@@ -320,3 +597,43 @@ impl X402EvmRpc {
         }
     }
 }
+
+/// Whether a failed attempt could have delivered the request. Only a failed
+/// connection is known not to have.
+fn may_have_reached_provider(error: &str) -> bool {
+    !error.contains("error trying to connect")
+}
+
+/// Whether a rejected send should count as submitted: the node already has
+/// the transaction, or an earlier attempt may have sent it
+fn treat_as_submitted(error: &str, maybe_sent: bool) -> bool {
+    let error = error.to_lowercase();
+    let already_known = ["already known", "known transaction", "alreadyknown", "already imported"]
+        .iter()
+        .any(|pattern| error.contains(pattern));
+    already_known || maybe_sent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_treat_as_submitted() {
+        assert!(treat_as_submitted("RPC error -32000: already known", false));
+        assert!(treat_as_submitted("RPC error -32000: nonce too low", true));
+        assert!(treat_as_submitted("RPC error -32000: insufficient funds", true));
+
+        assert!(!treat_as_submitted("RPC error -32000: nonce too low", false));
+        assert!(!treat_as_submitted("RPC error -32000: insufficient funds", false));
+    }
+
+    #[test]
+    fn test_may_have_reached_provider() {
+        assert!(may_have_reached_provider("Timed out after 15s waiting for https://rpc.example"));
+        assert!(may_have_reached_provider("RPC error (502 Bad Gateway) from https://rpc.example: empty response"));
+        assert!(!may_have_reached_provider(
+            "Request failed: error sending request for url (https://rpc.example/): error trying to connect: dns error"
+        ));
+    }
+}
@@ -10,14 +10,18 @@
 //!
 //! The token metadata (name, version, address, chain_id) is dynamically extracted
 //! from the 402 response, allowing compatibility with any x402-enabled endpoint.
+//! Which assets and networks the bot will actually pay with is limited by
+//! `PaymentPolicy`.
 
 mod types;
 mod client;
 mod signer;
 mod evm_rpc;
+mod policy;
 pub mod erc20;
 
 pub use types::*;
 pub use client::{X402Client, X402Response, is_x402_endpoint};
 pub use signer::X402Signer;
-pub use evm_rpc::X402EvmRpc;
+pub use evm_rpc::{X402EvmRpc, FAILOVER_ATTEMPT_TIMEOUT};
+pub use policy::{asset_label, normalize_network, PaymentPolicy};
@@ -0,0 +1,247 @@
+//! Which x402 payment requirements the bot will pay
+//!
+//! A 402 response lists the assets and networks the server accepts. The bot
+//! picks the first option allowed by `STARK_X402_NETWORKS` and
+//! `STARK_X402_ASSETS`, and the signer refuses anything else. Assets are
+//! allowed by contract address, or by symbol for the stablecoins in
+//! `KNOWN_ASSETS`: a symbol only matches its known address on that network,
+//! since the `extra.token` label in a 402 response is the server's claim.
+//!
+//! Amounts are only meaningful with the asset's decimals, so the policy
+//! carries them: known stablecoins have theirs listed, and a token allowed by
+//! address is written `<address>:<decimals>`. An asset whose decimals aren't
+//! known is never paid.
+
+/// Stablecoins that can be allowed by symbol: (network, symbol, address, decimals)
+const KNOWN_ASSETS: &[(&str, &str, &str, u8)] = &[
+    ("base", "USDC", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", 6),
+    ("base-sepolia", "USDC", "0x036CbD53842c5426634e7929541eC2318f3dCF7e", 6),
+    ("ethereum", "USDC", "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", 6),
+    ("sepolia", "USDC", "0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238", 6),
+    ("base", "EURC", "0x60a3E35Cc302bFA44Cb288Bc5a4F316Fdb1adb42", 6),
+    ("ethereum", "EURC", "0x1aBaEA1f7C830bD89Acc67eC4af516284b1bC33c", 6),
+];
+
+/// Network name for a network as given in a 402 response, which may be a
+/// name ("base") or a CAIP-2 chain ID ("eip155:8453")
+pub fn normalize_network(network: &str) -> String {
+    let network = network.trim().to_lowercase();
+    match network.strip_prefix("eip155:") {
+        Some("8453") => "base".to_string(),
+        Some("84532") => "base-sepolia".to_string(),
+        Some("1") => "ethereum".to_string(),
+        Some("11155111") => "sepolia".to_string(),
+        _ => network,
+    }
+}
+
+/// A known stablecoin by its address on a network
+fn known_asset(network: &str, address: &str) -> Option<&'static (&'static str, &'static str, &'static str, u8)> {
+    let network = normalize_network(network);
+    KNOWN_ASSETS
+        .iter()
+        .find(|(n, _, a, _)| *n == network && a.eq_ignore_ascii_case(address))
+}
+
+/// Symbol of a known stablecoin by its address on a network
+pub fn known_asset_symbol(network: &str, address: &str) -> Option<&'static str> {
+    known_asset(network, address).map(|(_, symbol, _, _)| *symbol)
+}
+
+/// Display name for a payment asset: its symbol if known, else its address
+pub fn asset_label(network: &str, address: &str) -> String {
+    known_asset_symbol(network, address)
+        .map(str::to_string)
+        .unwrap_or_else(|| address.to_string())
+}
+
+/// Networks and assets the bot may pay with
+#[derive(Debug, Clone)]
+pub struct PaymentPolicy {
+    /// Allowed networks, most preferred first
+    networks: Vec<String>,
+    /// Allowed asset symbols and addresses, lowercased
+    assets: Vec<String>,
+    /// Decimals given for allowed addresses (`<address>:<decimals>`), by address
+    decimals: Vec<(String, u8)>,
+}
+
+impl PaymentPolicy {
+    pub fn new(networks: Vec<String>, assets: Vec<String>) -> Self {
+        let mut allowed = Vec::new();
+        let mut decimals = Vec::new();
+        for entry in assets {
+            let entry = entry.trim().to_lowercase();
+            match entry.split_once(':') {
+                Some((address, d)) => {
+                    let address = address.trim().to_string();
+                    match d.trim().parse::<u8>() {
+                        Ok(d) => decimals.push((address.clone(), d)),
+                        Err(_) => log::warn!("[X402] Ignoring invalid decimals in x402 asset '{}'", entry),
+                    }
+                    allowed.push(address);
+                }
+                None => allowed.push(entry),
+            }
+        }
+        Self {
+            networks: networks.iter().map(|n| normalize_network(n)).collect(),
+            assets: allowed,
+            decimals,
+        }
+    }
+
+    /// The policy configured by `STARK_X402_NETWORKS` and `STARK_X402_ASSETS`
+    pub fn from_env() -> Self {
+        Self::new(crate::config::x402_networks(), crate::config::x402_assets())
+    }
+
+    /// Decimals of a payment asset: a known stablecoin's, else those
+    /// configured for its address
+    pub fn decimals(&self, network: &str, asset: &str) -> Option<u8> {
+        known_asset(network, asset).map(|(_, _, _, d)| *d).or_else(|| {
+            self.decimals
+                .iter()
+                .find(|(address, _)| address.eq_ignore_ascii_case(asset))
+                .map(|(_, d)| *d)
+        })
+    }
+
+    /// Whether `asset` (a token address) on `network` is on the allowlist
+    fn listed(&self, network: &str, asset: &str) -> bool {
+        let network = normalize_network(network);
+        if !self.networks.contains(&network) {
+            return false;
+        }
+        let symbol = known_asset_symbol(&network, asset).map(str::to_lowercase);
+        self.assets
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(asset) || Some(allowed) == symbol.as_ref())
+    }
+
+    /// Whether a payment of `asset` (a token address) on `network` is allowed:
+    /// it is on the allowlist and its decimals are known
+    pub fn allows(&self, network: &str, asset: &str) -> bool {
+        self.listed(network, asset) && self.decimals(network, asset).is_some()
+    }
+
+    /// Error unless a payment of `asset` on `network` is allowed
+    pub fn check(&self, network: &str, asset: &str) -> Result<(), String> {
+        if self.allows(network, asset) {
+            return Ok(());
+        }
+        if self.listed(network, asset) {
+            return Err(format!(
+                "x402 payment in {} on {} is not allowed: its decimals are unknown (allow it as '{}:<decimals>')",
+                asset, network, asset
+            ));
+        }
+        Err(format!(
+            "x402 payment in {} on {} is not allowed (allowed assets: {}; networks: {})",
+            asset_label(network, asset),
+            network,
+            self.assets.join(", "),
+            self.networks.join(", ")
+        ))
+    }
+
+    /// Pick the payment option to use from a 402 response: an allowed one on
+    /// `preferred_network` if given, else on the most preferred allowed
+    /// network. `terms` gives an option's (network, asset address).
+    pub fn select<'a, T>(
+        &self,
+        options: &'a [T],
+        preferred_network: Option<&str>,
+        terms: impl Fn(&T) -> (&str, &str),
+    ) -> Result<&'a T, String> {
+        let allowed = |opt: &&T| {
+            let (network, asset) = terms(*opt);
+            self.allows(network, asset)
+        };
+        let on_network = |wanted: &str| {
+            let wanted = normalize_network(wanted);
+            options
+                .iter()
+                .filter(allowed)
+                .find(|opt| normalize_network(terms(*opt).0) == wanted)
+        };
+
+        preferred_network
+            .and_then(on_network)
+            .or_else(|| self.networks.iter().find_map(|n| on_network(n)))
+            .ok_or_else(|| {
+                let offered = options
+                    .iter()
+                    .map(|opt| {
+                        let (network, asset) = terms(opt);
+                        format!("{} on {}", asset_label(network, asset), network)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "No allowed x402 payment option (offered: {}; allowed assets: {}; networks: {})",
+                    if offered.is_empty() { "none" } else { offered.as_str() },
+                    self.assets.join(", "),
+                    self.networks.join(", ")
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_USDC: &str = "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913";
+    const BASE_EURC: &str = "0x60a3E35Cc302bFA44Cb288Bc5a4F316Fdb1adb42";
+    const ETH_USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+    fn policy(networks: &[&str], assets: &[&str]) -> PaymentPolicy {
+        PaymentPolicy::new(
+            networks.iter().map(|s| s.to_string()).collect(),
+            assets.iter().map(|s| s.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_allows_by_symbol_or_address() {
+        let usdc = policy(&["base"], &["USDC"]);
+        assert!(usdc.allows("base", BASE_USDC));
+        assert!(usdc.allows("eip155:8453", BASE_USDC));
+        assert!(!usdc.allows("base", BASE_EURC));
+        assert!(!usdc.allows("ethereum", ETH_USDC));
+        // A symbol never matches an unknown token, whatever the server calls it
+        assert!(!usdc.allows("base", "0x0000000000000000000000000000000000000001"));
+
+        let custom = policy(&["base"], &["0x0000000000000000000000000000000000000001:18"]);
+        assert!(custom.allows("base", "0x0000000000000000000000000000000000000001"));
+        assert!(custom.check("base", BASE_USDC).is_err());
+    }
+
+    #[test]
+    fn test_decimals_are_required() {
+        let policy = policy(&["base"], &["USDC", "0x0000000000000000000000000000000000000001:18", "0x0000000000000000000000000000000000000002"]);
+        assert_eq!(policy.decimals("base", BASE_USDC), Some(6));
+        assert_eq!(policy.decimals("base", "0x0000000000000000000000000000000000000001"), Some(18));
+
+        // An address without decimals is listed but never paid
+        assert!(!policy.allows("base", "0x0000000000000000000000000000000000000002"));
+        let err = policy.check("base", "0x0000000000000000000000000000000000000002").unwrap_err();
+        assert!(err.contains("decimals are unknown"));
+    }
+
+    #[test]
+    fn test_select_prefers_configured_network() {
+        let policy = policy(&["ethereum", "base"], &["USDC", "EURC"]);
+        let options = [("base", BASE_EURC), ("polygon", BASE_USDC), ("ethereum", ETH_USDC)];
+
+        let pick = |preferred| policy.select(&options, preferred, |o| (o.0, o.1)).map(|o| o.0);
+        assert_eq!(pick(None), Ok("ethereum"));
+        assert_eq!(pick(Some("base")), Ok("base"));
+        // A preference for a network with nothing allowed falls back
+        assert_eq!(pick(Some("polygon")), Ok("ethereum"));
+
+        let err = policy.select(&options[1..2], None, |o| (o.0, o.1)).unwrap_err();
+        assert!(err.contains(&format!("offered: {} on polygon", BASE_USDC)));
+    }
+}
@@ -11,6 +11,7 @@ use ethers::utils::keccak256;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::erc20;
+use super::policy::PaymentPolicy;
 use super::types::*;
 
 /// x402 payment signer using a local wallet
@@ -106,13 +107,20 @@ impl X402Signer {
     }
 
     /// Sign a payment based on the scheme in requirements
-    /// Automatically chooses EIP-2612 (permit) or EIP-3009 (exact) based on scheme
+    /// Automatically chooses EIP-2612 (permit) or EIP-3009 (exact) based on scheme.
+    /// Payments in an asset or on a network the payment policy doesn't allow are refused.
     pub async fn sign_payment(
         &self,
         requirements: &PaymentRequirements,
     ) -> Result<PaymentPayload, String> {
         let token_metadata = TokenMetadata::from_requirements(requirements);
 
+        // The signature is for the token in `extra.address` when given, so
+        // both it and the advertised asset must be allowed
+        let policy = PaymentPolicy::from_env();
+        policy.check(&requirements.network, &requirements.asset)?;
+        policy.check(&requirements.network, &token_metadata.address)?;
+
         match requirements.scheme.as_str() {
             "permit" => self.sign_permit(requirements, &token_metadata).await,
             "exact" | "eip3009" => self.sign_transfer_with_auth(requirements, &token_metadata).await,
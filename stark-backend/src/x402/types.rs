@@ -17,9 +17,9 @@ pub const X402_VERSION: u8 = 2;
 /// Network identifier for Base
 pub const NETWORK_ID: &str = "eip155:8453";
 
-/// Get chain ID from network name
+/// Get chain ID from network name (or CAIP-2 ID, e.g. "eip155:8453")
 pub fn chain_id_for_network(network: &str) -> u64 {
-    match network {
+    match super::normalize_network(network).as_str() {
         "base" => BASE_CHAIN_ID,
         "base-sepolia" => BASE_SEPOLIA_CHAIN_ID,
         "ethereum" => 1,
@@ -29,7 +29,7 @@ pub fn chain_id_for_network(network: &str) -> u64 {
 }
 
 /// Payment requirements returned by server in 402 response
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PaymentRequired {
     pub x402_version: u8,
@@ -37,7 +37,7 @@ pub struct PaymentRequired {
 }
 
 /// Extra metadata about the token (provided in 402 response)
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PaymentExtra {
     /// Token symbol (e.g., "USDC")
@@ -54,7 +54,7 @@ pub struct PaymentExtra {
     pub facilitator_signer: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PaymentRequirements {
     pub scheme: String,
@@ -216,8 +216,11 @@ pub struct X402PaymentInfo {
     pub amount: String,
     /// Human-readable amount (e.g., "0.001234")
     pub amount_formatted: String,
-    /// Asset symbol (e.g., "USDC")
+    /// Asset symbol (e.g., "USDC"), or the token address if it isn't a known stablecoin
     pub asset: String,
+    /// Decimals of the asset, which `amount` is scaled by
+    #[serde(default = "usdc_decimals")]
+    pub decimals: u8,
     /// Address that received the payment
     pub pay_to: String,
     /// Optional resource identifier
@@ -233,13 +236,16 @@ pub struct X402PaymentInfo {
 impl X402PaymentInfo {
     /// Create from payment requirements (starts as pending with no tx_hash)
     pub fn from_requirements(req: &PaymentRequirements) -> Self {
-        // Format amount (USDC has 6 decimals)
-        let amount_formatted = format_usdc_amount(&req.max_amount_required);
+        // The policy only selects assets whose decimals it knows
+        let decimals = super::PaymentPolicy::from_env()
+            .decimals(&req.network, &req.asset)
+            .unwrap_or(USDC_DECIMALS);
 
         Self {
             amount: req.max_amount_required.clone(),
-            amount_formatted,
-            asset: req.asset.clone(),
+            amount_formatted: format_token_amount(&req.max_amount_required, decimals),
+            asset: super::asset_label(&req.network, &req.asset),
+            decimals,
             pay_to: req.pay_to_address.clone(),
             resource: req.resource.clone(),
             tx_hash: None,
@@ -266,23 +272,65 @@ impl X402PaymentInfo {
         self.status = PaymentStatus::Failed;
         self
     }
+
+    /// The amount in micro-units (6 decimals), as counted towards quotas
+    pub fn micro_units(&self) -> Option<u64> {
+        to_micro_units(&self.amount, self.decimals)
+    }
 }
 
-/// Format USDC amount from raw value (6 decimals) to human-readable string
-fn format_usdc_amount(raw: &str) -> String {
-    // Parse as u128 to handle large values
-    if let Ok(value) = raw.parse::<u128>() {
-        // USDC has 6 decimals
-        let whole = value / 1_000_000;
-        let frac = value % 1_000_000;
-        if frac == 0 {
-            format!("{}", whole)
-        } else {
-            // Remove trailing zeros
-            let frac_str = format!("{:06}", frac).trim_end_matches('0').to_string();
-            format!("{}.{}", whole, frac_str)
+/// Decimals of USDC, the unit quotas are counted in
+pub const USDC_DECIMALS: u8 = 6;
+
+fn usdc_decimals() -> u8 {
+    USDC_DECIMALS
+}
+
+/// Rescale a raw amount of a token with `decimals` to micro-units (6
+/// decimals), rounding up so a payment never counts as free
+pub fn to_micro_units(raw: &str, decimals: u8) -> Option<u64> {
+    let value = raw.parse::<u128>().ok()?;
+    let micro = match decimals.checked_sub(USDC_DECIMALS) {
+        Some(extra) => {
+            let scale = 10u128.checked_pow(extra as u32)?;
+            value.div_ceil(scale)
         }
+        None => value.checked_mul(10u128.pow((USDC_DECIMALS - decimals) as u32))?,
+    };
+    u64::try_from(micro).ok()
+}
+
+/// Format a raw token amount with `decimals` as a human-readable string
+pub fn format_token_amount(raw: &str, decimals: u8) -> String {
+    // Parse as u128 to handle large values
+    let (Ok(value), Some(scale)) = (raw.parse::<u128>(), 10u128.checked_pow(decimals as u32)) else {
+        return raw.to_string();
+    };
+    let whole = value / scale;
+    let frac = value % scale;
+    if frac == 0 {
+        format!("{}", whole)
     } else {
-        raw.to_string()
+        // Remove trailing zeros
+        let frac_str = format!("{:0width$}", frac, width = decimals as usize).trim_end_matches('0').to_string();
+        format!("{}.{}", whole, frac_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amounts_scale_by_decimals() {
+        assert_eq!(format_token_amount("1500000", 6), "1.5");
+        assert_eq!(format_token_amount("1500000000000000000", 18), "1.5");
+        assert_eq!(format_token_amount("not a number", 6), "not a number");
+
+        assert_eq!(to_micro_units("1500000", 6), Some(1_500_000));
+        assert_eq!(to_micro_units("1500000000000000000", 18), Some(1_500_000));
+        // Dust still counts
+        assert_eq!(to_micro_units("1", 18), Some(1));
+        assert_eq!(to_micro_units("15", 1), Some(1_500_000));
     }
 }
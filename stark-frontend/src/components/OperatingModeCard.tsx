@@ -18,11 +18,18 @@ export default function OperatingModeCard({
 
   const handleToggle = async () => {
     const newValue = !rogueModeEnabled;
+    if (newValue && !window.confirm(
+      'Rogue mode lets the agent run commands and broadcast transactions without asking you. ' +
+      'Every action is recorded in the rogue mode audit log. Enable rogue mode?'
+    )) {
+      return;
+    }
     setIsSaving(true);
     onMessage?.(null as any);
     try {
       await updateBotSettings({
         rogue_mode_enabled: newValue,
+        confirm_rogue_mode: newValue,
       });
       onModeChange(newValue);
       onMessage?.({ type: 'success', text: `Switched to ${newValue ? 'Rogue' : 'Partner'} mode` });
@@ -240,6 +240,8 @@ export async function getSessions(): Promise<Array<{
   updated_at: string;
   message_count?: number;
   initial_query?: string;
+  max_context_tokens?: number;
+  context_window_override?: number | null;
 }>> {
   return apiFetch('/sessions');
 }
@@ -255,10 +257,24 @@ export async function getSession(id: number): Promise<{
   updated_at: string;
   message_count?: number;
   initial_query?: string;
+  max_context_tokens?: number;
+  context_window_override?: number | null;
 }> {
   return apiFetch(`/sessions/${id}`);
 }
 
+// Set a session's context window override (null goes back to the model's window)
+export async function updateSessionContextWindow(id: number, contextWindowOverride: number | null): Promise<{
+  id: number;
+  max_context_tokens: number;
+  context_window_override: number | null;
+}> {
+  return apiFetch(`/sessions/${id}/context_window`, {
+    method: 'PUT',
+    body: JSON.stringify({ context_window_override: contextWindowOverride }),
+  });
+}
+
 export async function deleteSession(id: string): Promise<{
   success: boolean;
   message: string;
@@ -617,6 +633,15 @@ export interface ChannelInfo {
   created_at: string;
   updated_at: string;
   running?: boolean;
+  connection?: ChannelConnection;
+}
+
+export type ConnectionState = 'connecting' | 'connected' | 'disconnected' | 'error';
+
+export interface ChannelConnection {
+  state: ConnectionState;
+  reason?: string;
+  since: string;
 }
 
 // Channel Settings types
@@ -806,11 +831,17 @@ export async function getApiKeys(): Promise<ApiKey[]> {
   return response.keys || [];
 }
 
-export async function upsertApiKey(keyName: string, apiKey: string): Promise<void> {
-  await apiFetch('/keys', {
+export interface KeyValidation {
+  status: 'valid' | 'invalid' | 'unchecked';
+  message: string;
+}
+
+export async function upsertApiKey(keyName: string, apiKey: string): Promise<KeyValidation | undefined> {
+  const response = await apiFetch<{ validation?: KeyValidation }>('/keys', {
     method: 'POST',
     body: JSON.stringify({ key_name: keyName, api_key: apiKey }),
   });
+  return response.validation;
 }
 
 export async function deleteApiKey(keyName: string): Promise<void> {
@@ -890,6 +921,7 @@ export interface CronJobInfo {
   thinking_level?: string;
   timeout_seconds?: number;
   delete_after_run: boolean;
+  seed_findings: boolean;
   status: string;
   last_run_at?: string;
   next_run_at?: string;
@@ -930,6 +962,7 @@ export async function createCronJob(data: {
   thinking_level?: string;
   timeout_seconds?: number;
   delete_after_run?: boolean;
+  seed_findings?: boolean;
 }): Promise<CronJobInfo> {
   const response = await apiFetch<CronJobResponse>('/cron/jobs', {
     method: 'POST',
@@ -957,6 +990,7 @@ export async function updateCronJob(id: number, data: Partial<{
   thinking_level: string;
   timeout_seconds: number;
   delete_after_run: boolean;
+  seed_findings: boolean;
   status: string;
 }>): Promise<CronJobInfo> {
   const response = await apiFetch<CronJobResponse>(`/cron/jobs/${id}`, {
@@ -1080,6 +1114,26 @@ export async function pulseHeartbeatOnce(): Promise<HeartbeatConfigInfo> {
 }
 
 // Bot Settings API
+export interface ModerationSettings {
+  enabled: boolean;
+  check_responses: boolean;
+  endpoint: string;
+  failure_policy: 'open' | 'closed';
+  refusal_message: string;
+}
+
+export interface TransferCap {
+  per_tx?: string | null;
+  per_day?: string | null;
+  confirm_above?: string | null;
+}
+
+export interface TransferLimits {
+  native: TransferCap;
+  tokens: Record<string, TransferCap>;
+  min_gas_reserve: string;
+}
+
 export interface BotSettings {
   id: number;
   bot_name: string;
@@ -1089,6 +1143,10 @@ export interface BotSettings {
   custom_rpc_endpoints?: Record<string, string>;
   max_tool_iterations: number;
   rogue_mode_enabled: boolean;
+  exec_allowlist: string[];
+  exec_denylist: string[];
+  moderation: ModerationSettings;
+  transfer_limits: TransferLimits;
   created_at: string;
   updated_at: string;
 }
@@ -1105,6 +1163,11 @@ export async function updateBotSettings(data: {
   custom_rpc_endpoints?: Record<string, string>;
   max_tool_iterations?: number;
   rogue_mode_enabled?: boolean;
+  confirm_rogue_mode?: boolean;
+  exec_allowlist?: string[];
+  exec_denylist?: string[];
+  moderation?: ModerationSettings;
+  transfer_limits?: TransferLimits;
 }): Promise<BotSettings> {
   return apiFetch('/bot-settings', {
     method: 'PUT',
@@ -1112,6 +1175,24 @@ export async function updateBotSettings(data: {
   });
 }
 
+export interface RogueAuditEntry {
+  id: number;
+  action: string;
+  details?: string;
+  channel_id?: number;
+  session_id?: number;
+  success?: boolean;
+  created_at: string;
+}
+
+export async function getRogueAudit(limit = 100, offset = 0): Promise<{
+  entries: RogueAuditEntry[];
+  limit: number;
+  offset: number;
+}> {
+  return apiFetch(`/bot-settings/rogue-audit?limit=${limit}&offset=${offset}`);
+}
+
 // RPC Providers API
 export interface RpcProvider {
   id: string;
@@ -1304,6 +1385,41 @@ export async function getSessionTranscript(sessionId: number, limit?: number): P
   return apiFetch(`/sessions/${sessionId}/transcript${query}`);
 }
 
+// Agent Context API (orchestrator state for debugging multi-agent runs)
+export interface AgentContext {
+  original_request: string;
+  mode: string;
+  subtype: string;
+  mode_iterations: number;
+  total_iterations: number;
+  exploration_notes: string[];
+  scratchpad: string;
+  task_queue: {
+    tasks: PlannerTaskInfo[];
+    current_task_idx: number | null;
+  };
+  planner_completed: boolean;
+  replans: number;
+  replan_note: string | null;
+  task_signature: string | null;
+  findings: string[];
+  plan_awaiting_approval: boolean;
+}
+
+export interface AgentContextResponse {
+  session_id: number;
+  updated_at: string | null;
+  context: AgentContext;
+}
+
+export async function getAgentContext(sessionId: number): Promise<AgentContextResponse> {
+  return apiFetch(`/sessions/${sessionId}/agent_context`);
+}
+
+export async function clearAgentContext(sessionId: number): Promise<{ success: boolean; session_id: number }> {
+  return apiFetch(`/sessions/${sessionId}/agent_context`, { method: 'DELETE' });
+}
+
 // Intrinsic Files API
 export interface IntrinsicFileInfo {
   name: string;
@@ -1402,7 +1518,11 @@ export interface QueuedTransactionInfo {
   tx_hash?: string;
   explorer_url?: string;
   error?: string;
+  /** Channel the transaction was queued from */
+  channel_id?: number;
   created_at: string;
+  /** A pending transaction past this is stale and can no longer be broadcast */
+  expires_at: string;
   broadcast_at?: string;
 }
 
@@ -1554,6 +1674,230 @@ export async function deleteMindConnection(parentId: number, childId: number): P
   });
 }
 
+// Wallets API
+export interface WalletInfo {
+  name: string;
+  address: string | null;
+  signer: 'local' | 'keystore';
+  primary: boolean;
+  error?: string;
+}
+
+export async function getWallets(): Promise<{ wallets: WalletInfo[] }> {
+  return apiFetch('/wallets');
+}
+
+// Limit Orders API
+export interface LimitOrder {
+  id: number;
+  network: string;
+  wallet: string;
+  sell_token: string;
+  buy_token: string;
+  sell_amount: string;
+  trigger_token: string;
+  trigger_address: string;
+  trigger_direction: 'below' | 'above';
+  target_price_usd: number;
+  auto_broadcast: boolean;
+  status: 'pending' | 'triggered' | 'filled' | 'failed' | 'cancelled';
+  channel_id: number | null;
+  triggered_price_usd: number | null;
+  result: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export async function getLimitOrders(status?: LimitOrder['status']): Promise<{ orders: LimitOrder[] }> {
+  return apiFetch(`/limit-orders${status ? `?status=${status}` : ''}`);
+}
+
+export async function cancelLimitOrder(id: number): Promise<{ success: boolean }> {
+  return apiFetch(`/limit-orders/${id}/cancel`, { method: 'POST' });
+}
+
+// Web3 Presets API
+export interface Web3Preset {
+  abi: string;
+  contracts: Record<string, string>;
+  contract_register: string | null;
+  function: string;
+  params_registers: string[];
+  value_register: string | null;
+  static_params: string[];
+  description: string;
+}
+
+export interface Web3PresetInfo {
+  name: string;
+  source: 'database' | 'file';
+  preset: Web3Preset;
+  updated_at?: string;
+}
+
+export async function getWeb3Presets(): Promise<{ presets: Web3PresetInfo[] }> {
+  return apiFetch('/presets/web3');
+}
+
+export async function getWeb3Preset(name: string): Promise<Web3PresetInfo> {
+  return apiFetch(`/presets/web3/${encodeURIComponent(name)}`);
+}
+
+export async function saveWeb3Preset(name: string, preset: Web3Preset): Promise<{ success: boolean }> {
+  return apiFetch(`/presets/web3/${encodeURIComponent(name)}`, {
+    method: 'PUT',
+    body: JSON.stringify(preset),
+  });
+}
+
+export async function deleteWeb3Preset(name: string): Promise<{ success: boolean }> {
+  return apiFetch(`/presets/web3/${encodeURIComponent(name)}`, { method: 'DELETE' });
+}
+
+// ABI registry API
+export interface AbiFile {
+  name: string;
+  description?: string;
+  abi: unknown[];
+  address?: Record<string, string>;
+}
+
+export interface AbiSummary {
+  name: string;
+  source: 'database' | 'file';
+  display_name: string;
+  description: string;
+  functions: string[];
+  address: Record<string, string>;
+}
+
+export async function getAbis(): Promise<{ abis: AbiSummary[] }> {
+  return apiFetch('/abis');
+}
+
+export async function getAbi(name: string): Promise<{ name: string; source: 'database' | 'file'; abi: AbiFile; updated_at?: string }> {
+  return apiFetch(`/abis/${encodeURIComponent(name)}`);
+}
+
+export async function uploadAbi(name: string, abi: AbiFile): Promise<{ success: boolean; abi: AbiSummary }> {
+  return apiFetch(`/abis/${encodeURIComponent(name)}`, {
+    method: 'PUT',
+    body: JSON.stringify(abi),
+  });
+}
+
+// Config reload API
+export interface ConfigReloadResult {
+  config: 'tokens' | 'networks' | 'rpc_providers';
+  success: boolean;
+  count?: number;
+  error?: string;
+}
+
+export async function reloadConfig(): Promise<{ success: boolean; results: ConfigReloadResult[] }> {
+  return apiFetch('/config/reload', { method: 'POST' });
+}
+
+// Portfolio API
+export interface PortfolioHolding {
+  symbol: string;
+  address: string | null;
+  amount: string;
+  price_usd: number | null;
+  value_usd: number | null;
+}
+
+export interface PortfolioSnapshot {
+  id: number;
+  wallet: string;
+  address: string;
+  network: string;
+  total_usd: number;
+  holdings: PortfolioHolding[];
+  taken_at: string;
+}
+
+export interface PortfolioPnl {
+  from: string;
+  to: string;
+  start_usd: number;
+  end_usd: number;
+  change_usd: number;
+  change_pct: number | null;
+  assets: {
+    symbol: string;
+    start_amount: string;
+    end_amount: string;
+    start_value_usd: number | null;
+    end_value_usd: number | null;
+  }[];
+  unvalued: string[];
+}
+
+export interface PortfolioHistory {
+  wallet: string;
+  address: string;
+  network: string;
+  days: number;
+  snapshots: PortfolioSnapshot[];
+  pnl: PortfolioPnl | null;
+}
+
+export async function getPortfolioHistory(params: {
+  wallet?: string;
+  network?: string;
+  days?: number;
+} = {}): Promise<PortfolioHistory> {
+  const query = new URLSearchParams();
+  if (params.wallet) query.set('wallet', params.wallet);
+  if (params.network) query.set('network', params.network);
+  if (params.days) query.set('days', String(params.days));
+  const qs = query.toString();
+  return apiFetch(`/portfolio/history${qs ? `?${qs}` : ''}`);
+}
+
+// Personas API
+export interface PersonaInfo {
+  id: number;
+  name: string;
+  description: string | null;
+  content: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export async function getPersonas(): Promise<PersonaInfo[]> {
+  return apiFetch('/personas');
+}
+
+export async function createPersona(data: {
+  name: string;
+  description?: string;
+  content: string;
+}): Promise<PersonaInfo> {
+  return apiFetch('/personas', {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
+export async function updatePersona(id: number, data: {
+  name?: string;
+  description?: string;
+  content?: string;
+}): Promise<PersonaInfo> {
+  return apiFetch(`/personas/${id}`, {
+    method: 'PUT',
+    body: JSON.stringify(data),
+  });
+}
+
+export async function deletePersona(id: number): Promise<{ success: boolean; message: string }> {
+  return apiFetch(`/personas/${id}`, {
+    method: 'DELETE',
+  });
+}
+
 // Heartbeat session info for mind map sidebar
 export interface HeartbeatSessionInfo {
   id: number;
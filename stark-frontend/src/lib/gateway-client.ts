@@ -21,6 +21,8 @@ export class GatewayClient {
   private connectionPromise: Promise<void> | null = null;
   private connectionResolve: (() => void) | null = null;
   private authenticated = false;
+  // Id of the last event received, sent on reconnect so missed events are replayed
+  private lastEventId: number | null = null;
 
   constructor(url?: string) {
     if (url) {
@@ -119,7 +121,7 @@ export class GatewayClient {
       jsonrpc: '2.0',
       id,
       method: 'auth',
-      params: { token },
+      params: this.lastEventId !== null ? { token, last_event_id: this.lastEventId } : { token },
     };
 
     return new Promise((resolve, reject) => {
@@ -158,6 +160,9 @@ export class GatewayClient {
 
       // Handle server events
       if (message.type === 'event' && message.event) {
+        if (typeof message.event_id === 'number') {
+          this.lastEventId = message.event_id;
+        }
         this.emitEvent(message.event, message.data);
         return;
       }
@@ -3,7 +3,7 @@ import { Save, Settings } from 'lucide-react';
 import Card, { CardContent, CardHeader, CardTitle } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
 import Input from '@/components/ui/Input';
-import { getAgentSettings, updateAgentSettings, getBotSettings, updateBotSettings } from '@/lib/api';
+import { getAgentSettings, updateAgentSettings, getBotSettings, updateBotSettings, ModerationSettings } from '@/lib/api';
 
 const ENDPOINTS = {
   kimi: 'https://kimi.defirelay.com/api/v1/chat/completions',
@@ -30,6 +30,9 @@ export default function AgentSettings() {
   const [secretKey, setSecretKey] = useState('');
   const [hasExistingSecretKey, setHasExistingSecretKey] = useState(false);
   const [maxToolIterations, setMaxToolIterations] = useState(50);
+  const [execAllowlist, setExecAllowlist] = useState('');
+  const [execDenylist, setExecDenylist] = useState('');
+  const [moderation, setModeration] = useState<ModerationSettings | null>(null);
   const [isLoading, setIsLoading] = useState(true);
   const [isSaving, setIsSaving] = useState(false);
   const [isSavingBehavior, setIsSavingBehavior] = useState(false);
@@ -94,6 +97,9 @@ export default function AgentSettings() {
     try {
       const data = await getBotSettings();
       setMaxToolIterations(data.max_tool_iterations || 50);
+      setExecAllowlist((data.exec_allowlist || []).join(', '));
+      setExecDenylist((data.exec_denylist || []).join(', '));
+      setModeration(data.moderation || null);
     } catch (err) {
       console.error('Failed to load bot settings:', err);
     }
@@ -104,8 +110,13 @@ export default function AgentSettings() {
     setIsSavingBehavior(true);
     setMessage(null);
     try {
+      const toList = (value: string) =>
+        value.split(',').map((s) => s.trim()).filter((s) => s.length > 0);
       await updateBotSettings({
         max_tool_iterations: maxToolIterations,
+        exec_allowlist: toList(execAllowlist),
+        exec_denylist: toList(execDenylist),
+        ...(moderation ? { moderation } : {}),
       });
       setMessage({ type: 'success', text: 'Agent behavior settings saved successfully' });
     } catch (err) {
@@ -298,7 +309,7 @@ export default function AgentSettings() {
                   className="w-full px-4 py-3 bg-slate-900/50 border border-slate-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-stark-500 focus:border-transparent"
                 />
                 <p className="text-xs text-slate-500 mt-1">
-                  Context window limit for conversation history (min: 80,000, default: 100,000). Controls when compaction triggers.
+                  Context window limit for conversation history (min: 80,000). Controls when compaction triggers. At the default of 100,000, each session uses its model's own window instead; sessions can also override it individually.
                 </p>
               </div>
 
@@ -337,6 +348,109 @@ export default function AgentSettings() {
                 </p>
               </div>
 
+              <div>
+                <label className="block text-sm font-medium text-slate-300 mb-2">
+                  Exec Allowlist
+                </label>
+                <input
+                  type="text"
+                  value={execAllowlist}
+                  onChange={(e) => setExecAllowlist(e.target.value)}
+                  placeholder="git, cargo, ls"
+                  className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white focus:border-stark-500 focus:outline-none"
+                />
+                <p className="text-xs text-slate-500 mt-1">
+                  Comma-separated commands the exec tool may run. Leave empty to allow any command. Bypassed in rogue mode.
+                </p>
+              </div>
+
+              <div>
+                <label className="block text-sm font-medium text-slate-300 mb-2">
+                  Exec Denylist
+                </label>
+                <input
+                  type="text"
+                  value={execDenylist}
+                  onChange={(e) => setExecDenylist(e.target.value)}
+                  placeholder="rm -rf, curl | sh"
+                  className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white focus:border-stark-500 focus:outline-none"
+                />
+                <p className="text-xs text-slate-500 mt-1">
+                  Comma-separated command patterns that are always blocked, even in rogue mode.
+                </p>
+              </div>
+
+              {moderation && (
+                <>
+                  <div>
+                    <label className="block text-sm font-medium text-slate-300 mb-2">
+                      Content Moderation
+                    </label>
+                    <select
+                      value={!moderation.enabled ? 'off' : moderation.check_responses ? 'both' : 'inbound'}
+                      onChange={(e) => setModeration({
+                        ...moderation,
+                        enabled: e.target.value !== 'off',
+                        check_responses: e.target.value === 'both',
+                      })}
+                      className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white focus:border-stark-500 focus:outline-none"
+                    >
+                      <option value="off">Off</option>
+                      <option value="inbound">Check user messages</option>
+                      <option value="both">Check user messages and responses</option>
+                    </select>
+                    <p className="text-xs text-slate-500 mt-1">
+                      Flagged content is answered with the refusal message instead of reaching the AI. Set MODERATION_API_KEY in API Keys if the endpoint needs one.
+                    </p>
+                  </div>
+
+                  {moderation.enabled && (
+                    <>
+                      <div>
+                        <label className="block text-sm font-medium text-slate-300 mb-2">
+                          Moderation Endpoint
+                        </label>
+                        <input
+                          type="text"
+                          value={moderation.endpoint}
+                          onChange={(e) => setModeration({ ...moderation, endpoint: e.target.value })}
+                          className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white focus:border-stark-500 focus:outline-none"
+                        />
+                      </div>
+
+                      <div>
+                        <label className="block text-sm font-medium text-slate-300 mb-2">
+                          If Moderation Fails
+                        </label>
+                        <select
+                          value={moderation.failure_policy}
+                          onChange={(e) => setModeration({
+                            ...moderation,
+                            failure_policy: e.target.value as ModerationSettings['failure_policy'],
+                          })}
+                          className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white focus:border-stark-500 focus:outline-none"
+                        >
+                          <option value="open">Allow the message (fail open)</option>
+                          <option value="closed">Refuse the message (fail closed)</option>
+                        </select>
+                      </div>
+
+                      <div>
+                        <label className="block text-sm font-medium text-slate-300 mb-2">
+                          Refusal Message
+                        </label>
+                        <input
+                          type="text"
+                          value={moderation.refusal_message}
+                          onChange={(e) => setModeration({ ...moderation, refusal_message: e.target.value })}
+                          className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white focus:border-stark-500 focus:outline-none"
+                        />
+                      </div>
+                    </>
+                  )}
+                </>
+              )}
+
               <Button type="submit" isLoading={isSavingBehavior} className="w-fit">
                 <Save className="w-4 h-4 mr-2" />
                 Save Behavior Settings
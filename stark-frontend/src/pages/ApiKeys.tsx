@@ -11,7 +11,7 @@ export default function ApiKeys() {
   const [isLoading, setIsLoading] = useState(true);
   const [savingKeys, setSavingKeys] = useState<Set<string>>(new Set());
   const [keyInputs, setKeyInputs] = useState<Record<string, string>>({});
-  const [message, setMessage] = useState<{ type: 'success' | 'error'; text: string } | null>(null);
+  const [message, setMessage] = useState<{ type: 'success' | 'warning' | 'error'; text: string } | null>(null);
 
   // Cloud backup state
   const [isUploading, setIsUploading] = useState(false);
@@ -81,8 +81,12 @@ export default function ApiKeys() {
     setMessage(null);
 
     try {
-      await upsertApiKey(keyName, value);
-      setMessage({ type: 'success', text: 'API key saved successfully' });
+      const validation = await upsertApiKey(keyName, value);
+      if (validation && validation.status !== 'valid') {
+        setMessage({ type: 'warning', text: `API key saved, but: ${validation.message}` });
+      } else {
+        setMessage({ type: 'success', text: 'API key saved successfully' });
+      }
       setKeyInputs(prev => ({ ...prev, [keyName]: '' }));
       await loadKeys();
     } catch (err) {
@@ -114,10 +118,18 @@ export default function ApiKeys() {
     }
 
     try {
+      const warnings: string[] = [];
       for (const key of keysToSave) {
-        await upsertApiKey(key.name, keyInputs[key.name].trim());
+        const validation = await upsertApiKey(key.name, keyInputs[key.name].trim());
+        if (validation && validation.status !== 'valid') {
+          warnings.push(validation.message);
+        }
+      }
+      if (warnings.length > 0) {
+        setMessage({ type: 'warning', text: `${config.label} keys saved, but: ${warnings.join('; ')}` });
+      } else {
+        setMessage({ type: 'success', text: `${config.label} keys saved successfully` });
       }
-      setMessage({ type: 'success', text: `${config.label} keys saved successfully` });
 
       // Clear inputs for saved keys
       const clearedInputs = { ...keyInputs };
@@ -234,6 +246,8 @@ export default function ApiKeys() {
           className={`mb-6 px-4 py-3 rounded-lg ${
             message.type === 'success'
               ? 'bg-green-500/20 border border-green-500/50 text-green-400'
+              : message.type === 'warning'
+              ? 'bg-yellow-500/20 border border-yellow-500/50 text-yellow-400'
               : 'bg-red-500/20 border border-red-500/50 text-red-400'
           }`}
         >
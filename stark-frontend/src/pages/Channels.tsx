@@ -1,8 +1,9 @@
 import { useState, useEffect } from 'react';
-import { MessageSquare, Hash, Plus, Play, Square, Trash2, Save, Settings } from 'lucide-react';
+import { MessageSquare, Hash, Plus, Play, Square, Trash2, Save, Settings, XCircle } from 'lucide-react';
 import Card, { CardContent, CardHeader, CardTitle } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
 import Input from '@/components/ui/Input';
+import { useGateway } from '@/hooks/useGateway';
 import {
   getChannels,
   createChannel,
@@ -14,6 +15,7 @@ import {
   getChannelSettingsSchema,
   updateChannelSettings,
   ChannelInfo,
+  ChannelConnection,
   ChannelSetting,
   ChannelSettingDefinition,
 } from '@/lib/api';
@@ -24,6 +26,13 @@ const CHANNEL_TYPES = [
   { value: 'discord', label: 'Discord', icon: MessageSquare, color: 'indigo' },
 ];
 
+const connectionColors: Record<ChannelConnection['state'], string> = {
+  connecting: 'bg-amber-500/20 text-amber-400',
+  connected: 'bg-green-500/20 text-green-400',
+  disconnected: 'bg-slate-700 text-slate-400',
+  error: 'bg-red-500/20 text-red-400',
+};
+
 function getChannelHints(channelType: string): string[] {
   switch (channelType) {
     case 'discord':
@@ -91,6 +100,8 @@ export default function Channels() {
   const [settingsSchema, setSettingsSchema] = useState<ChannelSettingDefinition[]>([]);
   const [settingsValues, setSettingsValues] = useState<Record<string, string>>({});
   const [settingsLoading, setSettingsLoading] = useState(false);
+  const [activeExecutions, setActiveExecutions] = useState<Set<number>>(new Set());
+  const { on, off, call } = useGateway();
 
   const fetchChannels = async () => {
     try {
@@ -108,6 +119,55 @@ export default function Channels() {
     fetchChannels();
   }, []);
 
+  // Track which channels have an agent execution in flight
+  useEffect(() => {
+    const channelIdOf = (data: unknown) => (data as { channel_id?: number })?.channel_id;
+    const handleStarted = (data: unknown) => {
+      const id = channelIdOf(data);
+      if (id === undefined) return;
+      setActiveExecutions((prev) => new Set(prev).add(id));
+    };
+    const handleFinished = (data: unknown) => {
+      const id = channelIdOf(data);
+      if (id === undefined) return;
+      setActiveExecutions((prev) => {
+        const next = new Set(prev);
+        next.delete(id);
+        return next;
+      });
+    };
+
+    on('execution.started', handleStarted);
+    on('execution.completed', handleFinished);
+    on('execution.stopped', handleFinished);
+    return () => {
+      off('execution.started', handleStarted);
+      off('execution.completed', handleFinished);
+      off('execution.stopped', handleFinished);
+    };
+  }, [on, off]);
+
+  // Keep each channel's connection indicator live
+  useEffect(() => {
+    const handleConnection = (data: unknown) => {
+      const event = data as { channel_id?: number; state?: ChannelConnection['state']; reason?: string | null };
+      if (event?.channel_id === undefined || !event.state) return;
+      const connection: ChannelConnection = {
+        state: event.state,
+        reason: event.reason ?? undefined,
+        since: new Date().toISOString(),
+      };
+      setChannels((prev) =>
+        prev.map((ch) => (ch.id === event.channel_id ? { ...ch, connection } : ch))
+      );
+    };
+
+    on('channel.connection', handleConnection);
+    return () => {
+      off('channel.connection', handleConnection);
+    };
+  }, [on, off]);
+
   const handleCreate = async () => {
     if (!newChannel.name.trim() || !newChannel.bot_token.trim()) {
       setError('Name and bot token are required');
@@ -180,6 +240,17 @@ export default function Channels() {
     }
   };
 
+  const handleCancelExecution = async (id: number) => {
+    setActionLoading(id);
+    try {
+      await call('cancel_execution', { channel_id: id });
+    } catch (e) {
+      setError(e instanceof Error ? e.message : 'Failed to cancel execution');
+    } finally {
+      setActionLoading(null);
+    }
+  };
+
   const handleStop = async (id: number) => {
     setActionLoading(id);
     try {
@@ -387,10 +458,33 @@ export default function Channels() {
                       }`}>
                         {channel.running ? 'Running' : 'Stopped'}
                       </span>
+                      {channel.connection && (
+                        <span
+                          className={`px-2 py-1 rounded text-xs font-medium capitalize ${
+                            connectionColors[channel.connection.state]
+                          }`}
+                          title={channel.connection.reason}
+                        >
+                          {channel.connection.state}
+                        </span>
+                      )}
                       <SettingsToggle
                         enabled={settingsMode === channel.id}
                         onToggle={() => toggleSettingsMode(channel)}
                       />
+                      {activeExecutions.has(channel.id) && (
+                        <Button
+                          variant="secondary"
+                          size="sm"
+                          onClick={() => handleCancelExecution(channel.id)}
+                          disabled={isActionLoading}
+                          className="text-amber-400 hover:text-amber-300"
+                          title="Cancel the agent's current run"
+                        >
+                          <XCircle className="w-4 h-4 sm:mr-1" />
+                          <span className="hidden sm:inline">Cancel run</span>
+                        </Button>
+                      )}
                       {channel.running ? (
                         <Button
                           variant="secondary"
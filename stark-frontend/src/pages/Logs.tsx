@@ -16,11 +16,13 @@ const eventColors: Record<string, string> = {
   'channel.started': 'text-green-400 bg-green-500/20',
   'channel.stopped': 'text-yellow-400 bg-yellow-500/20',
   'channel.error': 'text-red-400 bg-red-500/20',
+  'channel.connection': 'text-sky-400 bg-sky-500/20',
   'channel.message': 'text-blue-400 bg-blue-500/20',
   'agent.response': 'text-emerald-400 bg-emerald-500/20',
   'tool.execution': 'text-purple-400 bg-purple-500/20',
   'tool.result': 'text-violet-400 bg-violet-500/20',
   'skill.invoked': 'text-pink-400 bg-pink-500/20',
+  'deploy.log': 'text-orange-400 bg-orange-500/20',
   'connected': 'text-green-400 bg-green-500/20',
   'disconnected': 'text-red-400 bg-red-500/20',
   'error': 'text-red-400 bg-red-500/20',
@@ -114,6 +114,7 @@ function CronJobsTab({ jobs, setJobs, showCreateForm, setShowCreateForm, setErro
     schedule_value: '',
     session_mode: 'main',
     message: '',
+    seed_findings: false,
   });
 
   // Interval helper state (for 'every' schedule type)
@@ -147,6 +148,7 @@ function CronJobsTab({ jobs, setJobs, showCreateForm, setShowCreateForm, setErro
         schedule_value: '',
         session_mode: 'main',
         message: '',
+        seed_findings: false,
       });
       setIntervalValue(1);
       setIntervalUnit('hours');
@@ -339,6 +341,16 @@ function CronJobsTab({ jobs, setJobs, showCreateForm, setShowCreateForm, setErro
                 </select>
               </div>
 
+              <label className="flex items-center gap-2 text-sm text-slate-300">
+                <input
+                  type="checkbox"
+                  checked={formData.seed_findings}
+                  onChange={(e) => setFormData({ ...formData, seed_findings: e.target.checked })}
+                  className="rounded border-slate-600 bg-slate-700"
+                />
+                Reuse findings from previous runs of this task
+              </label>
+
               <div className="flex gap-2">
                 <Button type="submit" isLoading={isCreating}>
                   <Plus className="w-4 h-4 mr-2" />
@@ -440,6 +452,10 @@ function CronJobsTab({ jobs, setJobs, showCreateForm, setShowCreateForm, setErro
                         <p className="text-xs text-slate-500">Session Mode</p>
                         <p className="text-sm text-slate-300">{job.session_mode}</p>
                       </div>
+                      <div>
+                        <p className="text-xs text-slate-500">Reuses Previous Findings</p>
+                        <p className="text-sm text-slate-300">{job.seed_findings ? 'Yes' : 'No'}</p>
+                      </div>
                     </div>
 
                     {jobRuns[job.id] && jobRuns[job.id].length > 0 && (
@@ -3,7 +3,7 @@ import { useParams, useNavigate } from 'react-router-dom';
 import { Calendar, Trash2, MessageSquare, Download, ChevronLeft, User, Bot, Wrench, CheckCircle, XCircle, AlertCircle, Play, Pause, RefreshCw } from 'lucide-react';
 import Card, { CardContent } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
-import { getSessions, getSession, deleteSession, deleteAllSessions, getSessionTranscript, SessionMessage, getCronJobs, CronJobInfo, stopSession, resumeSession } from '@/lib/api';
+import { getSessions, getSession, deleteSession, deleteAllSessions, getSessionTranscript, SessionMessage, getCronJobs, CronJobInfo, stopSession, resumeSession, updateSessionContextWindow } from '@/lib/api';
 
 type CompletionStatus = 'active' | 'complete' | 'cancelled' | 'failed';
 
@@ -17,6 +17,8 @@ interface Session {
   message_count?: number;
   completion_status?: string;
   initial_query?: string;
+  max_context_tokens?: number;
+  context_window_override?: number | null;
 }
 
 function isValidStatus(status: string | undefined): status is CompletionStatus {
@@ -49,6 +51,12 @@ export default function Sessions() {
   const [successMessage, setSuccessMessage] = useState<string | null>(null);
   const [isRefreshing, setIsRefreshing] = useState(false);
   const [isDeletingAll, setIsDeletingAll] = useState(false);
+  const [contextWindowInput, setContextWindowInput] = useState('');
+  const [isSavingContextWindow, setIsSavingContextWindow] = useState(false);
+
+  useEffect(() => {
+    setContextWindowInput(selectedSession?.context_window_override ? String(selectedSession.context_window_override) : '');
+  }, [selectedSession?.id]);
 
   useEffect(() => {
     loadSessions();
@@ -266,6 +274,32 @@ export default function Sessions() {
     }
   };
 
+  const handleSaveContextWindow = async () => {
+    if (!selectedSession) return;
+    const value = contextWindowInput.trim() === '' ? null : parseInt(contextWindowInput, 10);
+    if (value !== null && (isNaN(value) || value < 32000)) {
+      setError('Context window must be at least 32,000 tokens');
+      return;
+    }
+
+    setIsSavingContextWindow(true);
+    setError(null);
+    try {
+      const updated = await updateSessionContextWindow(selectedSession.id, value);
+      setSelectedSession({
+        ...selectedSession,
+        max_context_tokens: updated.max_context_tokens,
+        context_window_override: updated.context_window_override,
+      });
+      setSuccessMessage(value === null ? 'Context window reset to the model default.' : 'Context window updated.');
+      setTimeout(() => setSuccessMessage(null), 3000);
+    } catch (err) {
+      setError('Failed to update context window');
+    } finally {
+      setIsSavingContextWindow(false);
+    }
+  };
+
   const formatDate = (dateStr: string) => {
     return new Date(dateStr).toLocaleString();
   };
@@ -451,6 +485,45 @@ export default function Sessions() {
           </div>
         )}
 
+        {successMessage && (
+          <div className="mb-6 bg-green-500/20 border border-green-500/50 text-green-400 px-4 py-3 rounded-lg">
+            {successMessage}
+          </div>
+        )}
+
+        <Card className="mb-6">
+          <CardContent>
+            <div className="flex flex-col sm:flex-row sm:items-end gap-3">
+              <div className="flex-1">
+                <label className="block text-sm font-medium text-slate-300 mb-2">
+                  Context Window Override
+                </label>
+                <input
+                  type="number"
+                  value={contextWindowInput}
+                  onChange={(e) => setContextWindowInput(e.target.value)}
+                  placeholder="Model default"
+                  min={32000}
+                  className="w-full px-4 py-2 bg-slate-900/50 border border-slate-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-stark-500 focus:border-transparent"
+                />
+                <p className="text-xs text-slate-500 mt-1">
+                  Tokens this session may use before compaction
+                  {selectedSession.max_context_tokens ? ` (currently ${selectedSession.max_context_tokens.toLocaleString()})` : ''}.
+                  Leave empty to use the model's window.
+                </p>
+              </div>
+              <Button
+                variant="secondary"
+                size="sm"
+                onClick={handleSaveContextWindow}
+                isLoading={isSavingContextWindow}
+              >
+                Save
+              </Button>
+            </div>
+          </CardContent>
+        </Card>
+
         {isLoadingMessages ? (
           <div className="flex items-center justify-center py-12">
             <div className="flex items-center gap-3">
@@ -15,6 +15,7 @@ export interface GatewayMessage {
   type?: 'event';
   event?: string;
   data?: unknown;
+  event_id?: number;
   result?: unknown;
   error?: {
     code: number;
@@ -270,6 +271,7 @@ export interface CronJob {
   thinking_level?: string;
   timeout_seconds?: number;
   delete_after_run: boolean;
+  seed_findings: boolean;
   status: 'active' | 'paused' | 'completed' | 'failed';
   last_run_at?: string;
   next_run_at?: string;